@@ -30,8 +30,6 @@ fn main() {
                     .unwrap_or_else(|_| panic!("Unable to read file {:?}", full_path));
                 file_string = file_string
                     .replace("\\", "\\\\")
-                    .replace('{', "{{")
-                    .replace('}', "}}")
                     .replace('"', "\\\"")
                     .replace("'", "\\'");
                 include_files.insert(path.to_string(), file_string);
@@ -122,9 +120,7 @@ pub fn load_ez_files(file_paths: Vec<&str>) -> HashMap<String, String> {
             .unwrap_or_else(|_| panic!("Unable to read file {}", path));
         file_string = file_string
             .replace('\\', "\\\\")
-            .replace('"', "\\\"")
-            .replace('{', "{{")
-            .replace('}', "}}");
+            .replace('"', "\\\"");
         contents.insert(path.to_string(), file_string);
     }
     contents
@@ -151,7 +151,7 @@ fn main() {
             .as_label_mut()
             .set_text(format!("Generated label {}", i));
 
-        scheduler.create_widget(new_widget, new_states, &mut state_tree);
+        scheduler.create_widget(new_widget, new_states, None, &mut state_tree);
     }
     // Remove on widget programmatically.
     scheduler.remove_widget("label_9");
@@ -173,7 +173,7 @@ fn main() {
             .as_label_mut()
             .set_text(format!("Generated label {}", i));
 
-        scheduler.create_widget(new_widget, new_states, &mut state_tree);
+        scheduler.create_widget(new_widget, new_states, None, &mut state_tree);
     }
     let navigate_back_callback = |context: Context| {
         let state = context.state_tree.get_mut("view_layout").as_layout_mut();
@@ -37,7 +37,7 @@ fn main() {
             .as_label_mut()
             .set_text(sql_record.get("date").unwrap().to_string());
 
-        scheduler.create_widget(new_widget, new_states, &mut state_tree);
+        scheduler.create_widget(new_widget, new_states, None, &mut state_tree);
     }
     run(root_widget, state_tree, scheduler, custom_data);
 }
@@ -37,7 +37,7 @@ fn main() {
             &mut state_tree,
         );
         new_states.as_label_mut().set_text(new_id);
-        scheduler.create_widget(new_widget, new_states, &mut state_tree);
+        scheduler.create_widget(new_widget, new_states, None, &mut state_tree);
     }
 
     run(root_widget, state_tree, scheduler, custom_data);
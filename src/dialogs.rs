@@ -0,0 +1,159 @@
+//! # Dialogs
+//!
+//! Module containing the built-in modal templates. They are registered automatically, so they can
+//! be opened by name without writing any .ez file:
+//!
+//! - "AboutDialog": a title, a message and an "OK" button.
+//! - "ErrorDialog": like the about dialog, with the title in red.
+//! - "BusyOverlay": a message covering the whole screen, without buttons. Dismiss it from code
+//!   when the work is done.
+//! - "UnsavedChangesPrompt": a message with "Save", "Discard" and "Cancel" buttons. Open it with
+//!   [SchedulerFrontend.open_modal_with_result] to get the [UnsavedChangesChoice] of the user.
+//!
+//! The title and message are bound to the "dialog_title" and "dialog_message" custom properties;
+//! set them before opening a dialog. The buttons dismiss the dialog by themselves. Define a
+//! template with the same name in an .ez file to replace a built-in one, or extend one to change
+//! it (e.g. "- <MyAbout@AboutDialog>:").
+//!
+//! ```
+//! use ez_term::*;
+//!
+//! let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+//!
+//! scheduler
+//!     .get_property_mut("dialog_title")
+//!     .as_string_mut()
+//!     .set("Error".to_string());
+//! scheduler
+//!     .get_property_mut("dialog_message")
+//!     .as_string_mut()
+//!     .set("Could not save the file".to_string());
+//! scheduler.open_modal("ErrorDialog", &mut state_tree);
+//!
+//! run(root_widget, state_tree, scheduler, custom_data);
+//! ```
+use crate::parser::ez_definition::Templates;
+use crate::parser::parse_lang::parse_level;
+use crate::scheduler::definitions::Context;
+use crate::scheduler::scheduler::SchedulerFrontend;
+use crate::states::definitions::CallbackConfig;
+
+/// Name of the file the built-in templates are reported in, e.g. in parse errors.
+pub const DIALOGS_FILE: &str = "<built-in dialogs>";
+
+/// Definition of the built-in dialogs. Button callbacks are bound by
+/// [get_dialog_callback_config].
+const DIALOGS_EZ: &str = "
+- <DialogTitle@Label>:
+    id: dialog_title
+    text: properties.dialog_title
+    halign: center
+    height: 2
+    size_hint_y: none
+
+- <DialogMessage@Label>:
+    id: dialog_message
+    text: properties.dialog_message
+    halign: center
+
+- <DialogButton@Button>:
+    height: 3
+    size_hint_y: none
+
+- <DialogWindow@Layout>:
+    mode: box
+    orientation: vertical
+    size_hint: 0.5, 0.5
+    pos_hint: center, middle
+    border: true
+    padding: 0, 0, 1, 1
+
+- <AboutDialog@DialogWindow>:
+    - DialogTitle:
+        fg_color: yellow
+    - DialogMessage:
+    - DialogButton:
+        id: dialog_ok_button
+        text: OK
+        selection_order: 1
+
+- <ErrorDialog@DialogWindow>:
+    - DialogTitle:
+        fg_color: red
+    - DialogMessage:
+    - DialogButton:
+        id: dialog_ok_button
+        text: OK
+        selection_order: 1
+
+- <BusyOverlay@Layout>:
+    mode: box
+    orientation: vertical
+    valign: middle
+    - DialogMessage:
+        auto_scale_height: true
+        size_hint_y: none
+
+- <UnsavedChangesPrompt@DialogWindow>:
+    - DialogTitle:
+        fg_color: yellow
+    - DialogMessage:
+    - Layout:
+        mode: box
+        orientation: horizontal
+        height: 3
+        size_hint_y: none
+        - DialogButton:
+            id: dialog_save_button
+            text: Save
+            selection_order: 1
+        - DialogButton:
+            id: dialog_discard_button
+            text: Discard
+            selection_order: 2
+        - DialogButton:
+            id: dialog_cancel_button
+            text: Cancel
+            selection_order: 3
+";
+
+/// Choice of the user in the "UnsavedChangesPrompt" dialog. Open the dialog with
+/// [SchedulerFrontend.open_modal_with_result] to receive it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UnsavedChangesChoice {
+    Save,
+    Discard,
+    Cancel,
+}
+
+/// Parse the built-in dialog templates.
+pub fn get_dialog_templates() -> Templates {
+    let lines = DIALOGS_EZ.lines().map(|x| x.to_string()).collect();
+    let (_, _, templates) = parse_level(lines, 0, 0, DIALOGS_FILE.to_string()).unwrap();
+    templates
+}
+
+/// Create the custom properties the dialogs are bound to.
+pub fn create_dialog_properties(scheduler: &mut SchedulerFrontend) {
+    scheduler.new_string_property("dialog_title", String::new());
+    scheduler.new_string_property("dialog_message", String::new());
+}
+
+/// Get the callbacks of a widget of a built-in dialog. Returns the default [CallbackConfig] for
+/// any other widget.
+pub fn get_dialog_callback_config(path: &str) -> CallbackConfig {
+    let choice = match path.rsplit_once('/').map(|x| x.1).unwrap_or(path) {
+        "dialog_ok_button" => None,
+        "dialog_save_button" => Some(UnsavedChangesChoice::Save),
+        "dialog_discard_button" => Some(UnsavedChangesChoice::Discard),
+        "dialog_cancel_button" => Some(UnsavedChangesChoice::Cancel),
+        _ => return CallbackConfig::default(),
+    };
+    CallbackConfig::from_on_press(Box::new(move |context: Context| {
+        match choice {
+            Some(choice) => context.scheduler.set_modal_result(choice, context.state_tree),
+            None => context.scheduler.dismiss_modal(context.state_tree),
+        }
+        true
+    }))
+}
@@ -0,0 +1,742 @@
+//! # Gallery
+//!
+//! Module containing [load_gallery], which builds a UI that showcases every built-in widget. Each
+//! widget can be tweaked interactively, and a color and symbol page shows what the terminal
+//! supports. Run it to explore what the framework can do or to check a terminal:
+//!
+//! ```
+//! use ez_term::*;
+//!
+//! let (root_widget, state_tree, scheduler, custom_data) = load_gallery();
+//! run(root_widget, state_tree, scheduler, custom_data);
+//! ```
+use std::collections::HashMap;
+
+use crossterm::style::Color;
+
+use crate::parser::parse_lang::{finish_loading, load_ez_text};
+use crate::parser::parse_properties::parse_color_property;
+use crate::run::definitions::{Pixel, StateTree};
+use crate::scheduler::definitions::{Context, CustomDataMap};
+use crate::scheduler::scheduler::SchedulerFrontend;
+use crate::states::definitions::CallbackConfig;
+use crate::states::ez_state::GenericState;
+use crate::widgets::layout::layout::Layout;
+
+/// Widgets shown in the gallery as (key, title). Each widget has a navigation button
+/// "gallery_nav_{key}", a screen "gallery_screen_{key}" and a sample widget "gallery_sample_{key}"
+/// that the property panel applies to.
+const GALLERY_PAGES: [(&str, &str); 18] = [
+    ("label", "Label"),
+    ("button", "Button"),
+    ("checkbox", "CheckBox"),
+    ("radio", "RadioButton"),
+    ("input", "TextInput"),
+    ("textbox", "TextBox"),
+    ("dropdown", "Dropdown"),
+    ("slider", "Slider"),
+    ("stepper", "Stepper"),
+    ("rating", "Rating"),
+    ("progress", "ProgressBar"),
+    ("datagrid", "DataGrid"),
+    ("listview", "ListView"),
+    ("ticker", "Ticker"),
+    ("qrcode", "QrCode"),
+    ("heatmap", "Heatmap"),
+    ("canvas", "Canvas"),
+    ("colors", "Colors"),
+];
+
+/// Colors that can be chosen in the property panel. "default" restores the original color of the
+/// sample widget.
+const GALLERY_COLORS: [&str; 17] = [
+    "default",
+    "black",
+    "dark_grey",
+    "grey",
+    "white",
+    "red",
+    "dark_red",
+    "green",
+    "dark_green",
+    "yellow",
+    "dark_yellow",
+    "blue",
+    "dark_blue",
+    "magenta",
+    "dark_magenta",
+    "cyan",
+    "dark_cyan",
+];
+
+/// Width of the sample widgets when the gallery starts.
+const SAMPLE_WIDTH: usize = 20;
+
+/// Definition of the gallery UI. The navigation buttons and property panel are wired up in code
+/// by [load_gallery].
+const GALLERY_EZ: &str = "
+- <GalleryNavButton@Button>:
+    height: 3
+    size_hint_y: none
+
+- <GalleryPage@Layout>:
+    mode: box
+    orientation: vertical
+    padding: 1, 1, 2, 2
+
+- <GalleryDescription@Label>:
+    auto_scale_height: true
+    size_hint_y: none
+    padding_bottom: 1
+
+- <GalleryPanelLabel@Label>:
+    height: 2
+    size_hint_y: none
+    padding_top: 1
+
+- Layout:
+    mode: box
+    orientation: horizontal
+    - Layout:
+        id: gallery_nav
+        mode: box
+        orientation: vertical
+        size_hint_x: none
+        width: 17
+        border: true
+        scroll_y: true
+        - Label:
+            text: Widgets
+            height: 2
+            size_hint_y: none
+            fg_color: yellow
+        - GalleryNavButton:
+            id: gallery_nav_label
+            text: Label
+        - GalleryNavButton:
+            id: gallery_nav_button
+            text: Button
+        - GalleryNavButton:
+            id: gallery_nav_checkbox
+            text: CheckBox
+        - GalleryNavButton:
+            id: gallery_nav_radio
+            text: RadioButton
+        - GalleryNavButton:
+            id: gallery_nav_input
+            text: TextInput
+        - GalleryNavButton:
+            id: gallery_nav_textbox
+            text: TextBox
+        - GalleryNavButton:
+            id: gallery_nav_dropdown
+            text: Dropdown
+        - GalleryNavButton:
+            id: gallery_nav_slider
+            text: Slider
+        - GalleryNavButton:
+            id: gallery_nav_stepper
+            text: Stepper
+        - GalleryNavButton:
+            id: gallery_nav_rating
+            text: Rating
+        - GalleryNavButton:
+            id: gallery_nav_progress
+            text: ProgressBar
+        - GalleryNavButton:
+            id: gallery_nav_datagrid
+            text: DataGrid
+        - GalleryNavButton:
+            id: gallery_nav_listview
+            text: ListView
+        - GalleryNavButton:
+            id: gallery_nav_ticker
+            text: Ticker
+        - GalleryNavButton:
+            id: gallery_nav_qrcode
+            text: QrCode
+        - GalleryNavButton:
+            id: gallery_nav_heatmap
+            text: Heatmap
+        - GalleryNavButton:
+            id: gallery_nav_canvas
+            text: Canvas
+        - GalleryNavButton:
+            id: gallery_nav_colors
+            text: Colors
+    - Layout:
+        id: gallery_screens
+        mode: screen
+        weight: 1
+        border: true
+        - GalleryPage:
+            id: gallery_screen_label
+            - GalleryDescription:
+                text: A Label shows text. Long text wraps to the width of the label. Use the panel on the right to change the properties of the label below.
+            - Label:
+                id: gallery_sample_label
+                text: Hello from ez_term! This label wraps its' text when it gets too narrow.
+                size_hint: none, none
+                height: 4
+        - GalleryPage:
+            id: gallery_screen_button
+            - GalleryDescription:
+                text: A Button can be pressed with the mouse or by selecting it and pressing enter. It flashes when pressed.
+            - Button:
+                id: gallery_sample_button
+                text: Press me
+                size_hint: none, none
+                height: 3
+            - Label:
+                id: gallery_button_presses
+                text: Pressed 0 times
+                padding_top: 1
+        - GalleryPage:
+            id: gallery_screen_checkbox
+            - GalleryDescription:
+                text: A CheckBox can be toggled with the mouse or by selecting it and pressing enter.
+            - CheckBox:
+                id: gallery_sample_checkbox
+                size_hint: none, none
+                height: 1
+        - GalleryPage:
+            id: gallery_screen_radio
+            - GalleryDescription:
+                text: RadioButtons in the same group exclude each other: only one can be active at a time. The property panel applies to the first one.
+            - RadioButton:
+                id: gallery_sample_radio
+                group: gallery_radio
+                active: true
+                size_hint: none, none
+                height: 1
+            - RadioButton:
+                group: gallery_radio
+                size_hint: none, none
+                width: 20
+                height: 1
+            - RadioButton:
+                group: gallery_radio
+                size_hint: none, none
+                width: 20
+                height: 1
+        - GalleryPage:
+            id: gallery_screen_input
+            - GalleryDescription:
+                text: A TextInput lets the user type text. Select it and start typing; it scrolls when the text is longer than the input.
+            - TextInput:
+                id: gallery_sample_input
+                text: Type here
+                size_hint: none, none
+                height: 1
+        - GalleryPage:
+            id: gallery_screen_textbox
+            - GalleryDescription:
+                text: A TextBox lets the user edit multiple lines of text. Enter starts a new line; hold shift with the arrow keys to select text.
+            - TextBox:
+                id: gallery_sample_textbox
+                text: Type here.\\nLong lines are wrapped at word boundaries.
+                size_hint: none, none
+                height: 4
+        - GalleryPage:
+            id: gallery_screen_dropdown
+            - GalleryDescription:
+                text: A Dropdown lets the user choose one of a list of options. Select it and press enter or click it to open it.
+            - Dropdown:
+                id: gallery_sample_dropdown
+                options: first, second, third, fourth
+                choice: first
+                size_hint: none, none
+                height: 3
+        - GalleryPage:
+            id: gallery_screen_slider
+            - GalleryDescription:
+                text: A Slider lets the user choose a number. Select it and use the arrow keys, or drag it with the mouse.
+            - Slider:
+                id: gallery_sample_slider
+                min: 0
+                max: 100
+                step: 5
+                value: 50
+                size_hint: none, none
+                height: 1
+        - GalleryPage:
+            id: gallery_screen_stepper
+            - GalleryDescription:
+                text: A Stepper lets the user choose one of a short list of options. Select it and use the arrow keys, or click the arrows.
+            - Stepper:
+                id: gallery_sample_stepper
+                options: small, medium, large
+                value: medium
+                size_hint: none, none
+                height: 1
+        - GalleryPage:
+            id: gallery_screen_rating
+            - GalleryDescription:
+                text: A Rating lets the user give a score. Select it and use the arrow keys, or click a symbol. Clicking the last selected symbol clears the rating.
+            - Rating:
+                id: gallery_sample_rating
+                max: 5
+                value: 3
+                size_hint: none, none
+                height: 1
+        - GalleryPage:
+            id: gallery_screen_progress
+            - GalleryDescription:
+                text: A ProgressBar shows progress. These are bound to the slider below them; the second one is smooth, drawn in eighths of a cell.
+            - ProgressBar:
+                id: gallery_sample_progress
+                max: 100
+                value: parent.gallery_progress_slider.value
+                size_hint: none, none
+                height: 1
+            - ProgressBar:
+                id: gallery_sample_smooth_progress
+                max: 100
+                value: parent.gallery_progress_slider.value
+                smooth: true
+                size_hint: none, none
+                height: 1
+                padding_top: 1
+            - Slider:
+                id: gallery_progress_slider
+                min: 0
+                max: 100
+                step: 10
+                value: 30
+                size_hint: none, none
+                width: 20
+                height: 1
+                padding_top: 1
+        - GalleryPage:
+            id: gallery_screen_datagrid
+            - GalleryDescription:
+                text: A DataGrid shows rows of data under column headers. Click a header to sort by that column; select a row by clicking it or with the arrow keys.
+            - DataGrid:
+                id: gallery_sample_datagrid
+                columns: name, language, stars
+                rows: ez_term, Rust, 120; crossterm, Rust, 2700; textual, Python, 21000; bubbletea, Go, 22000
+                size_hint: none, none
+                height: 6
+        - GalleryPage:
+            id: gallery_screen_listview
+            - GalleryDescription:
+                text: A ListView shows a list of text items. Only the visible items are drawn, so it stays fast with many thousands of items, like the log lines below. Select an item by clicking it or with the arrow keys.
+            - ListView:
+                id: gallery_sample_listview
+                size_hint: none, none
+                height: 6
+        - GalleryPage:
+            id: gallery_screen_ticker
+            - GalleryDescription:
+                text: A Ticker scrolls a text that is too long for it, like a news headline or a status bar. Hover over it with the mouse to pause it.
+            - Ticker:
+                id: gallery_sample_ticker
+                text: ez_term 1.0 released; tickers now scroll your headlines through any width
+                size_hint: none, none
+                height: 1
+        - GalleryPage:
+            id: gallery_screen_qrcode
+            - GalleryDescription:
+                text: A QrCode shows a text as a QR code, e.g. to open a URL on a phone. The code is scaled up to fill the widget as far as it fits.
+            - QrCode:
+                id: gallery_sample_qrcode
+                text: https://github.com/ddbnl/ez_term
+        - GalleryPage:
+            id: gallery_screen_heatmap
+            - GalleryDescription:
+                text: A Heatmap colors a grid of values from a low to a high color, like the commits per day of four weeks below. Single cells can be updated without redrawing the whole heatmap.
+            - Heatmap:
+                id: gallery_sample_heatmap
+                values: 0, 3, 5, 2, 8, 1, 0; 1, 4, 9, 6, 7, 0, 0; 2, 2, 4, 12, 5, 3, 1; 0, 6, 8, 7, 10, 2, 0
+                show_values: true
+                cell_width: 4
+                size_hint: none, none
+                height: 4
+        - GalleryPage:
+            id: gallery_screen_canvas
+            - GalleryDescription:
+                text: A Canvas shows any content set from code. Below are box drawing, block and braille symbols; if they look broken, the terminal font does not support them.
+            - Canvas:
+                id: gallery_sample_canvas
+                size_hint: none, none
+                height: 6
+        - GalleryPage:
+            id: gallery_screen_colors
+            - GalleryDescription:
+                text: The colors supported by every terminal. The property panel applies to the layout below.
+            - Layout:
+                id: gallery_sample_colors
+                mode: box
+                orientation: vertical
+                size_hint: none, none
+                height: 16
+    - Layout:
+        id: gallery_panel
+        mode: box
+        orientation: vertical
+        size_hint_x: none
+        width: 24
+        border: true
+        - Label:
+            id: gallery_panel_title
+            text: Label
+            height: 2
+            size_hint_y: none
+            fg_color: yellow
+        - Layout:
+            mode: box
+            orientation: horizontal
+            size_hint_y: none
+            height: 1
+            - CheckBox:
+                id: gallery_disabled
+                size_hint_x: none
+                width: 4
+            - Label:
+                text: Disabled
+        - Layout:
+            mode: box
+            orientation: horizontal
+            size_hint_y: none
+            height: 1
+            - CheckBox:
+                id: gallery_border
+                size_hint_x: none
+                width: 4
+            - Label:
+                text: Border
+        - GalleryPanelLabel:
+            text: Foreground color
+        - Dropdown:
+            id: gallery_fg_color
+            options: default, black, dark_grey, grey, white, red, dark_red, green, dark_green, yellow, dark_yellow, blue, dark_blue, magenta, dark_magenta, cyan, dark_cyan
+            choice: default
+            size_hint_y: none
+            height: 3
+        - GalleryPanelLabel:
+            text: Background color
+        - Dropdown:
+            id: gallery_bg_color
+            options: default, black, dark_grey, grey, white, red, dark_red, green, dark_green, yellow, dark_yellow, blue, dark_blue, magenta, dark_magenta, cyan, dark_cyan
+            choice: default
+            size_hint_y: none
+            height: 3
+        - GalleryPanelLabel:
+            id: gallery_width_label
+            text: Width: 20
+        - Slider:
+            id: gallery_width
+            min: 4
+            max: 60
+            step: 1
+            value: 20
+            size_hint_y: none
+            height: 1
+";
+
+/// Build a gallery UI that showcases every built-in widget, with a panel to change the
+/// properties of the shown widget interactively and a page with all colors. Returns the same
+/// objects as [load_ui], so the gallery can be run (or tested) like any other UI. Does not
+/// depend on the .ez files of your project.
+///
+/// # Example:
+///
+/// ```
+/// use ez_term::*;
+///
+/// let (root_widget, state_tree, scheduler, custom_data) = load_gallery();
+/// run(root_widget, state_tree, scheduler, custom_data);
+/// ```
+pub fn load_gallery() -> (Layout, StateTree, SchedulerFrontend, CustomDataMap) {
+    let mut files = HashMap::new();
+    files.insert("gallery.ez".to_string(), GALLERY_EZ.to_string());
+    let (root_widget, scheduler) = load_ez_text(files).unwrap();
+    let (root_widget, mut state_tree, mut scheduler, custom_data) =
+        finish_loading(root_widget, scheduler);
+
+    fill_colors_page(&mut state_tree, &mut scheduler);
+    fill_canvas(&mut state_tree);
+    state_tree
+        .get_mut("gallery_sample_listview")
+        .as_list_view_mut()
+        .set_items(
+            (1..=10000)
+                .map(|i| format!("{:05} INFO request {} handled", i, i))
+                .collect(),
+        );
+
+    // Widths written in an .ez file cannot be changed from code, so the width of the samples is
+    // set here to keep it adjustable. Remember the original colors of the samples so "default" can
+    // restore them.
+    let mut default_colors = HashMap::new();
+    for (key, _) in GALLERY_PAGES {
+        let state = state_tree
+            .get_mut(&format!("gallery_sample_{}", key))
+            .as_generic_mut();
+        state.get_size_mut().set_width(SAMPLE_WIDTH);
+        let colors = state.get_color_config();
+        default_colors.insert(key, (colors.get_fg_color(), colors.get_bg_color()));
+    }
+
+    for (key, title) in GALLERY_PAGES {
+        let (default_fg, default_bg) = default_colors[key];
+        let show_page = move |context: Context| {
+            context
+                .scheduler
+                .show_screen(&format!("gallery_screen_{}", key), context.state_tree);
+            context
+                .state_tree
+                .get_mut("gallery_panel_title")
+                .as_label_mut()
+                .set_text(title.to_string());
+            sync_panel(key, (default_fg, default_bg), context.state_tree);
+            context.scheduler.update_widget("gallery_panel");
+            true
+        };
+        scheduler.update_callback_config(
+            &format!("gallery_nav_{}", key),
+            CallbackConfig::from_on_press(Box::new(show_page)),
+        );
+    }
+
+    let mut presses = 0;
+    let count_presses = move |context: Context| {
+        presses += 1;
+        context
+            .state_tree
+            .get_mut("gallery_button_presses")
+            .as_label_mut()
+            .set_text(format!("Pressed {} times", presses));
+        true
+    };
+    scheduler.update_callback_config(
+        "gallery_sample_button",
+        CallbackConfig::from_on_press(Box::new(count_presses)),
+    );
+
+    let set_disabled = |context: Context| {
+        let disabled = context
+            .state_tree
+            .get("gallery_disabled")
+            .as_checkbox()
+            .get_active();
+        let sample = get_current_sample(context.state_tree);
+        context
+            .state_tree
+            .get_mut(&sample)
+            .as_generic_mut()
+            .set_disabled(disabled);
+        context.scheduler.update_widget(&sample);
+        true
+    };
+    scheduler.update_callback_config(
+        "gallery_disabled",
+        CallbackConfig::from_on_value_change(Box::new(set_disabled)),
+    );
+
+    let set_border = |context: Context| {
+        let border = context
+            .state_tree
+            .get("gallery_border")
+            .as_checkbox()
+            .get_active();
+        let sample = get_current_sample(context.state_tree);
+        context
+            .state_tree
+            .get_mut(&sample)
+            .as_generic_mut()
+            .get_border_config_mut()
+            .set_border(border);
+        context.scheduler.update_widget("gallery_screens");
+        true
+    };
+    scheduler.update_callback_config(
+        "gallery_border",
+        CallbackConfig::from_on_value_change(Box::new(set_border)),
+    );
+
+    for foreground in [true, false] {
+        let default_colors = default_colors.clone();
+        let set_color = move |context: Context| {
+            let dropdown = if foreground {
+                "gallery_fg_color"
+            } else {
+                "gallery_bg_color"
+            };
+            let choice = context.state_tree.get(dropdown).as_dropdown().get_choice();
+            let sample = get_current_sample(context.state_tree);
+            let key = sample.strip_prefix("gallery_sample_").unwrap();
+            let (default_fg, default_bg) = default_colors[key];
+            let color = if choice == "default" || choice.is_empty() {
+                if foreground {
+                    default_fg
+                } else {
+                    default_bg
+                }
+            } else {
+                parse_color_property(&choice).unwrap()
+            };
+            let colors = context
+                .state_tree
+                .get_mut(&sample)
+                .as_generic_mut()
+                .get_color_config_mut();
+            if foreground {
+                colors.set_fg_color(color);
+            } else {
+                colors.set_bg_color(color);
+            }
+            context.scheduler.update_widget("gallery_screens");
+            true
+        };
+        let dropdown = if foreground {
+            "gallery_fg_color"
+        } else {
+            "gallery_bg_color"
+        };
+        scheduler.update_callback_config(
+            dropdown,
+            CallbackConfig::from_on_value_change(Box::new(set_color)),
+        );
+    }
+
+    let set_width = |context: Context| {
+        let width = context
+            .state_tree
+            .get("gallery_width")
+            .as_slider()
+            .get_value();
+        context
+            .state_tree
+            .get_mut("gallery_width_label")
+            .as_label_mut()
+            .set_text(format!("Width: {}", width));
+        let sample = get_current_sample(context.state_tree);
+        context
+            .state_tree
+            .get_mut(&sample)
+            .as_generic_mut()
+            .get_size_mut()
+            .set_width(width);
+        context.scheduler.update_widget("gallery_screens");
+        context.scheduler.update_widget("gallery_panel");
+        true
+    };
+    scheduler.update_callback_config(
+        "gallery_width",
+        CallbackConfig::from_on_value_change(Box::new(set_width)),
+    );
+
+    (root_widget, state_tree, scheduler, custom_data)
+}
+
+/// Get the ID of the sample widget on the page that is currently shown.
+fn get_current_sample(state_tree: &StateTree) -> String {
+    let screen = state_tree
+        .get("gallery_screens")
+        .as_layout()
+        .get_active_screen();
+    screen.replace("gallery_screen_", "gallery_sample_")
+}
+
+/// Set the controls of the property panel to the properties of the sample widget of a page. Colors
+/// that are the same as the original colors of the sample are shown as "default".
+fn sync_panel(key: &str, default_colors: (Color, Color), state_tree: &mut StateTree) {
+    let state = state_tree
+        .get(&format!("gallery_sample_{}", key))
+        .as_generic();
+    let disabled = state.get_disabled();
+    let border = state.get_border_config().get_border();
+    let width = state.get_size().get_width();
+    let colors = state.get_color_config();
+    let (fg_color, bg_color) = (colors.get_fg_color(), colors.get_bg_color());
+
+    state_tree
+        .get_mut("gallery_disabled")
+        .as_checkbox_mut()
+        .set_active(disabled);
+    state_tree
+        .get_mut("gallery_border")
+        .as_checkbox_mut()
+        .set_active(border);
+    state_tree
+        .get_mut("gallery_width")
+        .as_slider_mut()
+        .set_value(width.clamp(4, 60));
+    state_tree
+        .get_mut("gallery_width_label")
+        .as_label_mut()
+        .set_text(format!("Width: {}", width));
+    state_tree
+        .get_mut("gallery_fg_color")
+        .as_dropdown_mut()
+        .set_choice(get_color_name(fg_color, default_colors.0));
+    state_tree
+        .get_mut("gallery_bg_color")
+        .as_dropdown_mut()
+        .set_choice(get_color_name(bg_color, default_colors.1));
+}
+
+/// Get the name of a color in [GALLERY_COLORS], or "default" if it is the default color or not in
+/// there.
+fn get_color_name(color: Color, default: Color) -> &'static str {
+    if color == default {
+        return GALLERY_COLORS[0];
+    }
+    GALLERY_COLORS
+        .iter()
+        .skip(1)
+        .find(|x| parse_color_property(x).map_or(false, |y| y == color))
+        .unwrap_or(&GALLERY_COLORS[0])
+}
+
+/// Add a label for each color to the colors page.
+fn fill_colors_page(state_tree: &mut StateTree, scheduler: &mut SchedulerFrontend) {
+    for name in GALLERY_COLORS.iter().skip(1) {
+        let color = parse_color_property(name).unwrap();
+        let (new_label, mut new_states) = scheduler.prepare_create_widget(
+            "Label",
+            &format!("gallery_color_{}", name),
+            "gallery_sample_colors",
+            state_tree,
+        );
+        let state = new_states.as_label_mut();
+        state.set_text(name.to_string());
+        state.set_height(1);
+        state.set_size_hint_y(None);
+        state.get_color_config_mut().set_bg_color(color);
+        let fg_color = if matches!(color, Color::Black | Color::DarkGrey | Color::DarkBlue) {
+            Color::White
+        } else {
+            Color::Black
+        };
+        state.get_color_config_mut().set_fg_color(fg_color);
+        scheduler.create_widget(new_label, new_states, None, state_tree);
+    }
+}
+
+/// Fill the canvas page with box drawing, block and braille symbols.
+fn fill_canvas(state_tree: &mut StateTree) {
+    let state = state_tree
+        .get_mut("gallery_sample_canvas")
+        .as_canvas_mut();
+    let (width, height) = (state.get_size().get_width(), state.get_size().get_height());
+    let (fg_color, bg_color) = (
+        state.get_color_config().get_fg_color(),
+        state.get_color_config().get_bg_color(),
+    );
+    let rows = [
+        "┌──────┬──────┐",
+        "│ ░▒▓█ │ ▁▃▅▇ │",
+        "├──────┼──────┤",
+        "│ ⣿⡇⠿⠛ │ ●◆▲■ │",
+        "└──────┴──────┘",
+    ];
+    let mut contents = vec![vec![Pixel::new(" ".to_string(), fg_color, bg_color); height]; width];
+    for (y, row) in rows.iter().enumerate().take(height) {
+        for (x, symbol) in row.chars().enumerate().take(width) {
+            contents[x][y].symbol = symbol.to_string();
+        }
+    }
+    state.set_contents(contents);
+}
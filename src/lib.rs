@@ -4,6 +4,8 @@
 //!
 //! For a tutorial, examples, and API reference, see the wiki in the
 //! [Github repo](https://github.com/ddbnl/ez_term/wiki)
+mod dialogs;
+mod gallery;
 mod parser;
 mod property;
 mod run;
@@ -11,24 +13,81 @@ mod scheduler;
 mod states;
 mod widgets;
 
-pub use crate::parser::parse_lang::load_ui;
-pub use crate::run::run::run;
+pub use crate::dialogs::UnsavedChangesChoice;
+pub use crate::gallery::load_gallery;
+pub use crate::parser::parse_lang::{
+    detect_terminal_background, load_ez_files_checked, load_ez_text_checked, load_ui,
+    load_ui_checked, EzError, ParseError,
+};
+pub use crate::parser::format::{format_ez, try_format_ez};
+pub use crate::parser::lint::{validate_ez, EzLintKind, EzLintWarning};
+pub use crate::parser::syntax::{
+    parse_ez, EzPropertyNode, EzSpan, EzSyntaxError, EzSyntaxTree, EzWidgetNode,
+};
+pub use crate::run::run::{run, run_with_terminal};
+pub use crate::run::terminal::Terminal;
+pub use crate::run::testing::TestUi;
+pub use crate::run::cli::CliOverrides;
+pub use crate::run::input_source::{ChannelInputSource, EventInjector, InputSource};
 
 pub use crate::run::definitions::{Coordinates, IsizeCoordinates};
-pub use crossterm::event::{KeyCode, KeyModifiers};
+pub use crossterm::event::{
+    Event, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
 pub use crossterm::style::Color;
 
-pub use crate::run::definitions::{Pixel, PixelMap, StateTree};
+pub use crate::run::definitions::{Pixel, PixelMap, StateSnapshot, StateTree};
 pub use crate::scheduler::definitions::{Context, EzPropertiesMap, ThreadedContext, CustomData,
-                                        CustomDataMap};
+                                        CustomDataMap, DataSourceCallback, DragPayload,
+                                        DragStartCallbackFunction, DropCallbackFunction,
+                                        EventControl, EventPhase,
+                                        InputFilterFunction, ModalResult, ModalResultFunction,
+                                        PasteCallbackFunction, AccelerationCurve, KeyAcceleration,
+                                        ComputedPropertyFunction, StartupProfile, ExitTeardown};
+pub use crate::scheduler::clock::{Clock, MockClock, SystemClock};
+pub use crate::scheduler::data_source::{DataFormat, DataSource, DataTable};
+pub use crate::scheduler::undo::{UndoableCommand, UndoableFunction};
+pub use crate::scheduler::actions::{format_key, get_action_property_name, run_action, Action};
+pub use crate::scheduler::wizard::{
+    Wizard, WizardFinishFunction, WizardResult, WizardStep, WizardValidator,
+};
 pub use crate::scheduler::scheduler::SchedulerFrontend;
+#[cfg(feature = "metrics")]
+pub use crate::scheduler::system_metrics::SystemMetrics;
+#[cfg(feature = "tokio")]
+pub use crate::scheduler::async_tasks::AsyncCallback;
 
 pub use crate::property::ez_properties::EzProperties;
+pub use crate::property::ez_values::EzValues;
 pub use crate::property::ez_property::EzProperty;
 
 pub use crate::states::definitions::{
-    CallbackConfig, HorizontalAlignment, HorizontalPosHint, KeyMap, LayoutMode, LayoutOrientation,
-    SizeHint, VerticalAlignment, VerticalPosHint,
+    Anchor, CallbackConfig, CursorStyle, DockPosition, FillerPattern, HorizontalAlignment,
+    HorizontalPosHint, KeyMap, LayoutMode, LayoutOrientation, SelectionStyle, SizeHint, StyledLine,
+    StyledSpan, Theme, VerticalAlignment, VerticalPosHint,
+};
+pub use crate::states::colors::{
+    blend, contrast_ratio, darken, desaturate, dim, disabled_color, hover_color, lighten,
+    readable_fg, relative_luminance, to_rgb, MIN_CONTRAST_RATIO,
 };
-pub use crate::states::ez_state::GenericState;
+pub use crate::states::button_state::ButtonState;
+pub use crate::states::canvas_state::CanvasState;
+pub use crate::states::checkbox_state::CheckboxState;
+pub use crate::states::dropdown_state::{DropdownState, DroppedDownMenuState};
+pub use crate::states::ez_state::{EzState, GenericState, StateType};
+pub use crate::states::label_state::LabelState;
+pub use crate::states::layout_state::LayoutState;
+pub use crate::states::progress_bar_state::ProgressBarState;
+pub use crate::states::radio_button_state::RadioButtonState;
+pub use crate::states::rating_state::RatingState;
+pub use crate::states::slider_state::SliderState;
+pub use crate::states::stepper_state::StepperState;
+pub use crate::states::data_grid_state::DataGridState;
+pub use crate::states::list_view_state::ListViewState;
+pub use crate::states::text_box_state::TextBoxState;
+pub use crate::states::text_input_state::TextInputState;
+pub use crate::states::ticker_state::TickerState;
+pub use crate::states::qr_code_state::QrCodeState;
+pub use crate::states::heatmap_state::HeatmapState;
 pub use crate::widgets::ez_object::EzObject;
+pub use crate::widgets::sub_cell::{horizontal_bar, horizontal_eighth, vertical_bar, BrailleGrid};
@@ -7,24 +7,102 @@
 //! functions as a bridge between parsed plain text and the initialized interface.
 use std::collections::HashMap;
 use std::fmt::{Debug, Formatter};
-use std::io::{Error, ErrorKind};
-
 use crossterm::terminal::size;
 
-use crate::parser::parse_lang;
+use crate::parser::parse_lang::ParseError;
+use crate::parser::{parse_lang, parse_properties};
 use crate::scheduler::scheduler::SchedulerFrontend;
 use crate::states::ez_state::GenericState;
 use crate::widgets::{
-    button::Button, canvas::Canvas, checkbox::Checkbox, dropdown::Dropdown, ez_object::EzObjects,
-    label::Label, layout::layout::Layout, progress_bar::ProgressBar, radio_button::RadioButton,
-    slider::Slider, text_input::TextInput,
+    button::Button, canvas::Canvas, checkbox::Checkbox, data_grid::DataGrid, dropdown::Dropdown,
+    ez_object::EzObjects, heatmap::Heatmap,
+    label::Label, layout::layout::Layout, list_view::ListView, progress_bar::ProgressBar, radio_button::RadioButton,
+    rating::Rating, slider::Slider, stepper::Stepper, text_box::TextBox, text_input::TextInput,
+    qr_code::QrCode, ticker::Ticker,
 };
 
+/// Name of the directive that puts a child widget of a template instance in a slot of the
+/// template, e.g. "in_slot: body" puts the widget in the layout of the template with "slot: body".
+pub const SLOT_DIRECTIVE: &str = "in_slot";
+
+/// Name of the property that makes the content of a layout load the first time it is shown as
+/// the active tab or screen, e.g. "lazy: true".
+pub const LAZY_PROPERTY: &str = "lazy";
+
 /// A hashmap to resolve template names to their [EzWidgetDefinition]'.
 ///
 /// Used to instantiate widget templates at runtime. E.g. when spawning popups.
 pub type Templates = HashMap<String, EzWidgetDefinition>;
 
+/// A widget created from a template, kept so it can be created again when the template is
+/// reloaded. See [SchedulerFrontend.reload_template].
+#[derive(Clone, Debug)]
+pub struct TemplateInstance {
+    /// Names of the templates the widget was created from, starting with the template of the
+    /// instance itself, followed by the templates it extends.
+    pub templates: Vec<String>,
+
+    /// Definition of the instance, containing its' own config and child widgets
+    pub definition: EzWidgetDefinition,
+
+    /// Config the definition was parsed with
+    pub config: Vec<ConfigLine>,
+
+    /// Path of the layout the instance was created in
+    pub parent_path: String,
+
+    /// Position of the instance in its' parent layout when it was created
+    pub order: usize,
+}
+
+/// A config line of a widget definition, e.g. "height: 5", along with where it was defined so
+/// mistakes in it can be reported.
+#[derive(Clone, Debug)]
+pub struct ConfigLine {
+    /// Text of the line, without indentation
+    pub text: String,
+
+    /// Path to the file the line was defined in. Empty for lines created from code.
+    pub file: String,
+
+    /// Number of the line in the file (starting from 1). 0 for lines created from code.
+    pub line: usize,
+
+    /// Column of the first character of the text in the file (starting from 0)
+    pub column: usize,
+}
+impl ConfigLine {
+    pub fn new(text: String, file: &str, line: usize, column: usize) -> Self {
+        ConfigLine {
+            text,
+            file: file.to_string(),
+            line,
+            column,
+        }
+    }
+
+    /// Create an error for a mistake at a column of the text of this line.
+    pub fn error(&self, column: usize, message: String) -> ParseError {
+        ParseError::new(&self.file, self.line, self.column + column, message)
+    }
+
+    /// Create an error for a mistake in the value of this line, i.e. the text after the ":".
+    pub fn value_error(&self, message: String) -> ParseError {
+        let column = match self.text.split_once(':') {
+            Some((name, value)) => {
+                name.chars().count() + 1 + value.chars().take_while(|x| x.is_whitespace()).count()
+            }
+            None => 0,
+        };
+        self.error(column, message)
+    }
+}
+impl From<String> for ConfigLine {
+    fn from(text: String) -> Self {
+        ConfigLine::new(text, "", 0, 0)
+    }
+}
+
 /// Struct representing a widget definition in a .ez file.
 ///
 /// Has methods for parsing the definition into an initialized widget. The definition of a widget
@@ -47,6 +125,8 @@ pub struct EzWidgetDefinition {
 
     /// Offset in lines where the content of the widget definition begins in the config file.
     /// Zero-indexed. Indicates the first line AFTER the initial definition starting with '- .
+    /// Empty lines and comments are kept (empty) in the content, so line 'i' of the content is
+    /// line 'line_offset + i + 1' of the file.
     pub line_offset: usize,
 
     /// Indentation offset of this widget in the config
@@ -75,51 +155,104 @@ impl EzWidgetDefinition {
     }
 
     /// Based on a template, find the base widget type. Templates can be based on templates so the
-    /// base type might be a few levels deep.
+    /// base type might be a few levels deep. If templates are based on each other in a cycle, the
+    /// template that closes the cycle is returned (see [check_template_inheritance]).
     pub fn resolve_base_type(&self, templates: &Templates) -> String {
         let mut type_name = &self.type_name;
-        while templates.contains_key(type_name) {
+        let mut seen = Vec::new();
+        while templates.contains_key(type_name) && !seen.contains(&type_name) {
+            seen.push(type_name);
             type_name = &templates.get(type_name).unwrap().type_name;
         }
         type_name.to_string()
     }
 
+    /// Create an error for a mistake in this definition, pointing at the line that defines it
+    /// (e.g. "- Label:").
+    pub fn error(&self, message: String) -> ParseError {
+        ParseError::new(
+            &self.file,
+            self.line_offset,
+            self.indentation_offset.saturating_sub(4),
+            message,
+        )
+    }
+
     /// Parse a definition by separating the config lines from the sub widget definitions. Then
-    /// apply the config to the initialized widget, then initialize and add sub widgets.
+    /// apply the config to the initialized widget, then initialize and add sub widgets. Returns
+    /// an error for the first mistake found in the definition or its' sub widgets.
     pub fn parse(
         &mut self,
         scheduler: &mut SchedulerFrontend,
         parent_path: String,
         order: usize,
-        merge_config: Option<Vec<String>>,
-    ) -> EzObjects {
+        merge_config: Option<Vec<ConfigLine>>,
+    ) -> Result<EzObjects, ParseError> {
         let (mut config, mut sub_widgets, _) = parse_lang::parse_level(
             self.content.clone(),
             self.indentation_offset,
             self.line_offset,
             self.file.clone(),
-        )
-        .unwrap();
+        )?;
 
         // Templates can have properties, and instances of templates can also have properties.
         // Merge the configs making sure that the instance config takes precedence.
         if let Some(config_to_merge) = merge_config {
             config = merge_configs(config, config_to_merge);
         }
-        let initialized = self
-            .initialize(config, scheduler, parent_path, order)
-            .unwrap();
+        let lazy = self.take_lazy_property(&mut config)?;
+        if lazy && scheduler.backend.templates.contains_key(&self.type_name) {
+            // The content of the template should be lazy as well
+            config.push(ConfigLine::from(format!("{}: true", LAZY_PROPERTY)));
+        }
+        let initialized = self.initialize(config, scheduler, parent_path, order)?;
         let parent_path = initialized.as_ez_object().get_path();
 
         if let EzObjects::Layout(mut obj) = initialized {
+            if lazy {
+                if let Some(slotted) = sub_widgets.iter().find(|x| x.peek_slot_directive()) {
+                    return Err(slotted.error(
+                        "Widgets cannot be put in a slot of a lazy layout".to_string(),
+                    ));
+                }
+                // Sub widgets are parsed once the layout is shown, see [load_lazy_widgets]
+                let pending = &mut scheduler.backend.lazy_widgets;
+                match pending.iter_mut().find(|(path, _)| path == &parent_path) {
+                    Some((_, definitions)) => definitions.extend(sub_widgets),
+                    None => pending.push((parent_path, sub_widgets)),
+                }
+                return Ok(EzObjects::Layout(obj));
+            }
             for (i, sub_widget) in sub_widgets.iter_mut().enumerate() {
+                // Widgets can be put in a slot of a template instead of directly in the layout
+                if let Some(slot_name) = sub_widget.take_slot_directive() {
+                    let slot = match find_slot(&mut obj, &slot_name) {
+                        Some(i) => i,
+                        None => {
+                            return Err(sub_widget.error(format!(
+                                "There is no layout with \"slot: {}\" in \"{}\" to put this \
+                                widget in.",
+                                slot_name, self.type_name
+                            )))
+                        }
+                    };
+                    let initialized_sub_widget = sub_widget.parse(
+                        scheduler,
+                        slot.state.get_path().clone(),
+                        slot.children.len(),
+                        None,
+                    )?;
+                    slot.add_child(initialized_sub_widget, scheduler);
+                    continue;
+                }
                 let initialized_sub_widget =
-                    sub_widget.parse(scheduler, parent_path.clone(), i, None);
+                    sub_widget.parse(scheduler, parent_path.clone(), i, None)?;
 
                 obj.add_child(initialized_sub_widget, scheduler);
             }
             if self.is_root {
-                let terminal_size = size().unwrap();
+                // Fall back to a default size when there is no terminal (e.g. in tests)
+                let terminal_size = size().unwrap_or((80, 24));
                 if obj.state.get_size().get_width() == 0 {
                     obj.state.get_size_mut().set_width(terminal_size.0 as usize);
                 }
@@ -129,33 +262,77 @@ impl EzWidgetDefinition {
                         .set_height(terminal_size.1 as usize);
                 }
             }
-            return EzObjects::Layout(obj);
+            return Ok(EzObjects::Layout(obj));
+        }
+        Ok(initialized)
+    }
+
+    /// Remove the [LAZY_PROPERTY] from a config, returning whether the layout is lazy.
+    fn take_lazy_property(&self, config: &mut Vec<ConfigLine>) -> Result<bool, ParseError> {
+        let index = match config.iter().position(|x| {
+            x.text
+                .split_once(':')
+                .map(|(name, _)| name.trim() == LAZY_PROPERTY)
+                .unwrap_or(false)
+        }) {
+            Some(i) => i,
+            None => return Ok(false),
+        };
+        let line = config.remove(index);
+        let value = line.text.split_once(':').unwrap().1;
+        parse_properties::parse_bool_property(value.trim())
+            .map_err(|e| line.value_error(e.to_string()))
+    }
+
+    /// Whether this definition puts the widget in a slot, see [take_slot_directive].
+    fn peek_slot_directive(&self) -> bool {
+        self.content
+            .iter()
+            .take_while(|x| !x.starts_with('-'))
+            .filter_map(|x| x.split_once(':'))
+            .any(|(name, _)| !name.starts_with(' ') && name.trim() == SLOT_DIRECTIVE)
+    }
+
+    /// Remove the [SLOT_DIRECTIVE] from the config of this definition, returning the name of the
+    /// slot the widget should be put in, if any.
+    fn take_slot_directive(&mut self) -> Option<String> {
+        for (i, line) in self.content.iter().enumerate() {
+            // Sub widget definitions start after the config of this widget
+            if line.starts_with('-') {
+                break;
+            }
+            if let Some((name, value)) = line.split_once(':') {
+                if !line.starts_with(' ') && name.trim() == SLOT_DIRECTIVE {
+                    let slot = value.trim().to_string();
+                    // Empty the line rather than removing it, so the line numbers stay correct
+                    self.content[i].clear();
+                    return Some(slot);
+                }
+            }
         }
-        initialized
+        None
     }
 
     /// Initialize a widget object based on the type specified by the definition. The type can be
     /// a template defined by the user.
     fn initialize(
         &mut self,
-        mut config: Vec<String>,
+        mut config: Vec<ConfigLine>,
         scheduler: &mut SchedulerFrontend,
         parent_path: String,
         order: usize,
-    ) -> Result<EzObjects, Error> {
+    ) -> Result<EzObjects, ParseError> {
 
         if self.is_root {
             let id = peek_id_from_config(&config);
-            if !id.is_empty() {
-                if id != "root" {
-                    return Err(Error::new(
-                        ErrorKind::InvalidData,
-                        "Root widget cannot have an ID parameter; \
-                                          it is \"root\" by default",
-                    ));
-                }
-            } else {
-                config.push("id: root".to_string());
+            if id.is_empty() {
+                config.push(ConfigLine::from("id: root".to_string()));
+            } else if id != "root" {
+                let line = config.iter().rev().find(|x| is_id_line(x)).unwrap();
+                return Err(line.value_error(
+                    "Root widget cannot have an ID parameter; it is \"root\" by default"
+                        .to_string(),
+                ));
             }
         }
         // If this is a template, clone the template definition and parse that instead; we want to
@@ -168,9 +345,44 @@ impl EzWidgetDefinition {
                 .unwrap()
                 .clone();
             template.is_root = self.is_root;
-            let object = template.parse(scheduler, parent_path, order, Some(config));
-            let object = object.as_ez_object().get_clone(scheduler);
-            Ok(object)
+
+            // Remember the instance so it can be created again if the template is reloaded. A
+            // template extending another template is parsed again for the same path; only the
+            // outermost definition is the instance itself.
+            let mut id = peek_id_from_config(&config);
+            if id.is_empty() {
+                id = order.to_string()
+            };
+            let path = format!("{}/{}", parent_path, id.trim());
+            let new_instance = scheduler.backend.instantiating.last() != Some(&path);
+            if new_instance {
+                scheduler.backend.template_instances.insert(
+                    path.clone(),
+                    TemplateInstance {
+                        templates: Vec::new(),
+                        definition: self.clone(),
+                        config: config.clone(),
+                        parent_path: parent_path.clone(),
+                        order,
+                    },
+                );
+                scheduler.backend.instantiating.push(path.clone());
+            }
+            scheduler
+                .backend
+                .template_instances
+                .get_mut(&path)
+                .unwrap()
+                .templates
+                .push(self.type_name.clone());
+
+            let object = template
+                .parse(scheduler, parent_path, order, Some(config))
+                .map(|x| x.as_ez_object().get_clone(scheduler));
+            if new_instance {
+                scheduler.backend.instantiating.pop();
+            }
+            object
         // If this is a base widget definition initialize a widget of that type from the config of
         // this widget definition.
         } else {
@@ -179,92 +391,43 @@ impl EzWidgetDefinition {
                 id = order.to_string()
             };
             let path = format!("{}/{}", parent_path, id.trim());
-            match self.type_name.as_str() {
-                "Layout" => Ok(EzObjects::Layout(Layout::from_config(
-                    config,
-                    id,
-                    path,
-                    scheduler,
-                    self.file.clone(),
-                    self.line_offset,
-                ))),
-                "Canvas" => Ok(EzObjects::Canvas(Canvas::from_config(
-                    config,
-                    id,
-                    path,
-                    scheduler,
-                    self.file.clone(),
-                    self.line_offset,
-                ))),
-                "Label" => Ok(EzObjects::Label(Label::from_config(
-                    config,
-                    id,
-                    path,
-                    scheduler,
-                    self.file.clone(),
-                    self.line_offset,
-                ))),
-                "Button" => Ok(EzObjects::Button(Button::from_config(
-                    config,
-                    id,
-                    path,
-                    scheduler,
-                    self.file.clone(),
-                    self.line_offset,
-                ))),
-                "CheckBox" => Ok(EzObjects::Checkbox(Checkbox::from_config(
-                    config,
-                    id,
-                    path,
-                    scheduler,
-                    self.file.clone(),
-                    self.line_offset,
-                ))),
-                "RadioButton" => Ok(EzObjects::RadioButton(RadioButton::from_config(
-                    config,
-                    id,
-                    path,
-                    scheduler,
-                    self.file.clone(),
-                    self.line_offset,
-                ))),
-                "TextInput" => Ok(EzObjects::TextInput(TextInput::from_config(
-                    config,
-                    id,
-                    path,
-                    scheduler,
-                    self.file.clone(),
-                    self.line_offset,
-                ))),
-                "Dropdown" => Ok(EzObjects::Dropdown(Dropdown::from_config(
-                    config,
-                    id,
-                    path,
-                    scheduler,
-                    self.file.clone(),
-                    self.line_offset,
-                ))),
-                "Slider" => Ok(EzObjects::Slider(Slider::from_config(
-                    config,
-                    id,
-                    path,
-                    scheduler,
-                    self.file.clone(),
-                    self.line_offset,
-                ))),
-                "ProgressBar" => Ok(EzObjects::ProgressBar(ProgressBar::from_config(
-                    config,
-                    id,
-                    path,
-                    scheduler,
-                    self.file.clone(),
-                    self.line_offset,
-                ))),
-                _ => Err(Error::new(
-                    ErrorKind::InvalidData,
-                    format!("Invalid widget type {}", self.type_name),
-                )),
-            }
+            Ok(match self.type_name.as_str() {
+                "Layout" => EzObjects::Layout(Layout::from_config(config, id, path, scheduler)?),
+                "Canvas" => EzObjects::Canvas(Canvas::from_config(config, id, path, scheduler)?),
+                "Label" => EzObjects::Label(Label::from_config(config, id, path, scheduler)?),
+                "Button" => EzObjects::Button(Button::from_config(config, id, path, scheduler)?),
+                "CheckBox" => {
+                    EzObjects::Checkbox(Checkbox::from_config(config, id, path, scheduler)?)
+                }
+                "RadioButton" => {
+                    EzObjects::RadioButton(RadioButton::from_config(config, id, path, scheduler)?)
+                }
+                "TextInput" => {
+                    EzObjects::TextInput(TextInput::from_config(config, id, path, scheduler)?)
+                }
+                "TextBox" => EzObjects::TextBox(TextBox::from_config(config, id, path, scheduler)?),
+                "DataGrid" => {
+                    EzObjects::DataGrid(DataGrid::from_config(config, id, path, scheduler)?)
+                }
+                "ListView" => {
+                    EzObjects::ListView(ListView::from_config(config, id, path, scheduler)?)
+                }
+                "Dropdown" => {
+                    EzObjects::Dropdown(Dropdown::from_config(config, id, path, scheduler)?)
+                }
+                "Slider" => EzObjects::Slider(Slider::from_config(config, id, path, scheduler)?),
+                "ProgressBar" => {
+                    EzObjects::ProgressBar(ProgressBar::from_config(config, id, path, scheduler)?)
+                }
+                "Rating" => EzObjects::Rating(Rating::from_config(config, id, path, scheduler)?),
+                "Stepper" => EzObjects::Stepper(Stepper::from_config(config, id, path, scheduler)?),
+                "Ticker" => EzObjects::Ticker(Ticker::from_config(config, id, path, scheduler)?),
+                "QrCode" => EzObjects::QrCode(QrCode::from_config(config, id, path, scheduler)?),
+                "Heatmap" => EzObjects::Heatmap(Heatmap::from_config(config, id, path, scheduler)?),
+                _ => {
+                    return Err(self.error(format!("Invalid widget type {}", self.type_name)))
+                }
+            })
         }
     }
 }
@@ -274,44 +437,82 @@ impl Debug for EzWidgetDefinition {
     }
 }
 
-/// Check if a widget definition config contains an ID. If so, return an ID and path from it.
-fn peek_id_from_config(config: &[String]) -> String {
-    let mut id = String::new();
-    for line_str in config.iter() {
-        if line_str.trim().to_lowercase().starts_with("id:") {
-            id = line_str
-                .trim()
-                .split_once(':')
-                .unwrap()
-                .1
-                .trim()
-                .to_string();
+/// Check that no template is based on itself, directly or through other templates (e.g.
+/// "<MyButton@FancyButton>" and "<FancyButton@MyButton>"). A template can extend another template,
+/// inheriting its' properties and child widgets. Returns an error for the first cycle found.
+pub fn check_template_inheritance(templates: &Templates) -> Result<(), ParseError> {
+    let mut names: Vec<&String> = templates.keys().collect();
+    names.sort();
+    for name in names {
+        let mut chain = vec![name];
+        let mut type_name = &templates.get(name).unwrap().type_name;
+        while let Some(template) = templates.get(type_name) {
+            if chain.contains(&type_name) {
+                chain.push(type_name);
+                return Err(template.error(format!(
+                    "Template \"{}\" is based on itself: {}.",
+                    type_name,
+                    chain
+                        .iter()
+                        .map(|x| x.as_str())
+                        .collect::<Vec<&str>>()
+                        .join(" -> ")
+                )));
+            }
+            chain.push(type_name);
+            type_name = &template.type_name;
         }
     }
-    id
+    Ok(())
 }
 
-/// Merge two configs, where config_1 takes precedence, overwriting any properties it has in common
-/// with config_2. This is used for templates which can have properties, where the instance of a
-/// template may have the same property defined. In that case the instance of the template takes
-/// precedence.
-fn merge_configs(config_1: Vec<String>, config_2: Vec<String>) -> Vec<String> {
-    let mut merged_config: Vec<String> = Vec::new();
-    let mut existing_options: Vec<String> = Vec::new();
-    for line in config_1 {
-        if !line.contains(":") {
-            panic!(
-                "Line should contain a \":\" to separate property and value: {}",
-                line
-            )
+/// Find the layout with the given [LayoutState.slot] name among the descendants of a layout.
+fn find_slot<'a>(layout: &'a mut Layout, name: &str) -> Option<&'a mut Layout> {
+    for child in layout.children.iter_mut() {
+        if let EzObjects::Layout(i) = child {
+            if i.state.get_slot() == name {
+                return Some(i);
+            }
+            if let Some(slot) = find_slot(i, name) {
+                return Some(slot);
+            }
         }
-        merged_config.push(line.clone());
-        existing_options.push(line.split_once(':').unwrap().0.to_string());
     }
-    for line in config_2 {
-        if !merged_config.contains(&line.split_once(':').unwrap().0.to_string()) {
-            merged_config.push(line);
-        }
+    None
+}
+
+/// Check if a widget definition config contains an ID. If so, return an ID and path from it.
+fn peek_id_from_config(config: &[ConfigLine]) -> String {
+    let mut id = String::new();
+    for line in config.iter().filter(|x| is_id_line(x)) {
+        id = line.text.trim().split_once(':').unwrap().1.trim().to_string();
     }
+    id
+}
+
+/// Whether a config line sets the ID of the widget.
+fn is_id_line(line: &ConfigLine) -> bool {
+    line.text.trim().to_lowercase().starts_with("id:")
+}
+
+/// Merge two configs, where config_2 takes precedence, overwriting any properties it has in common
+/// with config_1. This is used for templates which can have properties, where the instance of a
+/// template (or a template extending it) may have the same property defined. In that case the
+/// instance of the template takes precedence. Overwritten properties are left out entirely, so
+/// e.g. a binding in the template is not created when the instance sets a fixed value.
+fn merge_configs(config_1: Vec<ConfigLine>, config_2: Vec<ConfigLine>) -> Vec<ConfigLine> {
+    // Lines without a ":" are kept; they are reported when the config is loaded
+    let property_name = |line: &ConfigLine| -> String {
+        match line.text.split_once(':') {
+            Some((name, _)) => name.trim().to_string(),
+            None => line.text.trim().to_string(),
+        }
+    };
+    let overwritten: Vec<String> = config_2.iter().map(property_name).collect();
+    let mut merged_config: Vec<ConfigLine> = config_1
+        .into_iter()
+        .filter(|x| !overwritten.contains(&property_name(x)))
+        .collect();
+    merged_config.extend(config_2);
     merged_config
 }
@@ -0,0 +1,221 @@
+//! # Format
+//!
+//! Module containing [format_ez], which rewrites .ez files in a consistent style so that teams
+//! can keep their layout files tidy (e.g. from a CLI or a pre-commit hook). Built on the
+//! [EzSyntaxTree] returned by [parse_ez].
+use crate::parser::parse_lang::DEFINE_PREFIX;
+use crate::parser::syntax::{parse_ez, EzPropertyNode, EzSyntaxError, EzWidgetNode};
+
+/// Order in which the formatter writes the common properties of widgets. Properties that are not
+/// in this list (i.e. properties specific to a widget type) are written after these, in the order
+/// they were written in the file.
+const PROPERTY_ORDER: [&str; 86] = [
+    "id",
+    "x",
+    "y",
+    "pos",
+    "size_hint",
+    "size_hint_x",
+    "size_hint_y",
+    "size",
+    "width",
+    "height",
+    "pos_hint",
+    "pos_hint_x",
+    "pos_hint_y",
+    "auto_scale",
+    "auto_scale_width",
+    "auto_scale_height",
+    "padding",
+    "padding_x",
+    "padding_y",
+    "padding_top",
+    "padding_bottom",
+    "padding_left",
+    "padding_right",
+    "disabled",
+    "selection_order",
+    "dock",
+    "collapsed",
+    "weight",
+    "z_index",
+    "anchor",
+    "anchor_offset",
+    "anchor_offset_x",
+    "anchor_offset_y",
+    "halign",
+    "valign",
+    "fg_color",
+    "bg_color",
+    "disabled_fg_color",
+    "disabled_bg_color",
+    "tab_header_active_fg_color",
+    "tab_header_active_bg_color",
+    "selection_fg_color",
+    "selection_bg_color",
+    "flash_fg_color",
+    "flash_bg_color",
+    "tab_header_fg_color",
+    "tab_header_bg_color",
+    "tab_header_border_fg_color",
+    "tab_header_border_bg_color",
+    "filler_fg_color",
+    "filler_bg_color",
+    "filler_alt_bg_color",
+    "filler_header_fg_color",
+    "filler_header_bg_color",
+    "cursor_color",
+    "auto_colors",
+    "auto_contrast",
+    "selection_style",
+    "selection_marker",
+    "hover_fg_color",
+    "hover_bg_color",
+    "pressed_fg_color",
+    "pressed_bg_color",
+    "focused_border",
+    "border",
+    "border_horizontal_symbol",
+    "border_vertical_symbol",
+    "border_top_right_symbol",
+    "border_top_left_symbol",
+    "border_bottom_left_symbol",
+    "border_bottom_right_symbol",
+    "border_fg_color",
+    "border_bg_color",
+    "refresh_every",
+    "group",
+    "action",
+    "track_dirty",
+    // Layout specific properties that change how the rest of the definition reads
+    "mode",
+    "orientation",
+    "active_tab",
+    "tab_name",
+    "active_screen",
+    "scroll",
+    "scroll_x",
+    "scroll_y",
+    "lazy",
+];
+
+/// Properties of which the value is used as written (apart from a single leading space), so the
+/// formatter must not change their whitespace.
+const WHITESPACE_SENSITIVE: [&str; 2] = ["text", "separator"];
+
+/// Format the text of an .ez file:
+///
+/// - Indentation is four spaces per level and whitespace around values is removed, except for
+///   "text" values of which the whitespace is part of the text
+/// - Properties are written as "name: value", with "id" first, followed by the common properties
+///   (position, size, padding, colors, border) in a fixed order, followed by the properties
+///   specific to the widget type in the order they were written
+/// - Variables ("@define name: value") are written at the top of the file, followed by an empty
+///   line
+/// - Top level definitions are separated by a single empty line; other empty lines are removed
+/// - Comments are kept above the definition or property they were written above
+///
+/// Files with mistakes the formatter cannot fix (see [EzSyntaxError.fixable]) are returned
+/// unchanged; use [try_format_ez] to get the mistakes.
+///
+/// # Example:
+///
+/// ```
+/// use ez_term::*;
+///
+/// let formatted = format_ez("- Layout:\n  mode:box\n  id:   root\n");
+/// assert_eq!(formatted, "- Layout:\n    id: root\n    mode: box\n");
+/// ```
+pub fn format_ez(source: &str) -> String {
+    try_format_ez(source).unwrap_or_else(|_| source.to_string())
+}
+
+/// Same as [format_ez], but returns the mistakes the formatter cannot fix instead of returning the
+/// file unchanged.
+pub fn try_format_ez(source: &str) -> Result<String, Vec<EzSyntaxError>> {
+    let tree = parse_ez(source);
+    if tree.errors.iter().any(|x| !x.fixable) {
+        return Err(tree.errors.into_iter().filter(|x| !x.fixable).collect());
+    }
+    let mut definitions: Vec<&EzWidgetNode> =
+        tree.templates.iter().chain(tree.widgets.iter()).collect();
+    definitions.sort_by_key(|x| x.span.start_line);
+
+    let mut lines = Vec::new();
+    for variable in tree.variables.iter() {
+        format_comments(&variable.comments, 0, &mut lines);
+        lines.push(format!("{} {}:{}", DEFINE_PREFIX, variable.name, format_value(&variable.value)));
+    }
+    for (i, definition) in definitions.into_iter().enumerate() {
+        if i > 0 || !tree.variables.is_empty() {
+            lines.push(String::new());
+        }
+        format_node(definition, 0, &mut lines);
+    }
+    if !tree.trailing_comments.is_empty() && !lines.is_empty() {
+        lines.push(String::new());
+    }
+    format_comments(&tree.trailing_comments, 0, &mut lines);
+
+    let mut formatted = lines.join("\n");
+    if !formatted.is_empty() {
+        formatted.push('\n');
+    }
+    Ok(formatted)
+}
+
+/// Write a definition with its' properties and children.
+fn format_node(node: &EzWidgetNode, level: usize, lines: &mut Vec<String>) {
+    let indentation = " ".repeat(level * 4);
+    format_comments(&node.comments, level, lines);
+    match node.template_name {
+        Some(ref name) => lines.push(format!("{}- <{}@{}>:", indentation, name, node.type_name)),
+        None => lines.push(format!("{}- {}:", indentation, node.type_name)),
+    }
+
+    let mut properties: Vec<&EzPropertyNode> = node.properties.iter().collect();
+    properties.sort_by_key(|x| {
+        PROPERTY_ORDER
+            .iter()
+            .position(|y| *y == x.name)
+            .unwrap_or(PROPERTY_ORDER.len())
+    });
+    for property in properties {
+        format_comments(&property.comments, level + 1, lines);
+        let value = if WHITESPACE_SENSITIVE.contains(&property.name.as_str()) {
+            if property.raw_value.starts_with(' ') {
+                property.raw_value.clone()
+            } else {
+                format!(" {}", property.raw_value)
+            }
+        } else {
+            format_value(&property.value)
+        };
+        lines.push(format!("{}    {}:{}", indentation, property.name, value));
+    }
+    for child in node.children.iter() {
+        format_node(child, level + 1, lines);
+    }
+}
+
+/// Write a property value after the ":" of its' name, separated by a single space.
+fn format_value(value: &str) -> String {
+    if value.is_empty() {
+        String::new()
+    } else {
+        format!(" {}", value)
+    }
+}
+
+/// Write comment lines at an indentation level.
+fn format_comments(comments: &[String], level: usize, lines: &mut Vec<String>) {
+    for comment in comments {
+        let comment = comment.trim_end();
+        let separator = if comment.is_empty() || comment.starts_with(' ') {
+            ""
+        } else {
+            " "
+        };
+        lines.push(format!("{}//{}{}", " ".repeat(level * 4), separator, comment));
+    }
+}
@@ -0,0 +1,667 @@
+//! # Lint
+//!
+//! Module containing [validate_ez], which checks .ez files for mistakes without running the UI.
+//! Unlike [load_ui], which panics on the first mistake, all mistakes found are returned, which
+//! makes it suitable for use in CI and editors.
+use std::any::Any;
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::panic::{catch_unwind, set_hook, take_hook, AssertUnwindSafe};
+use std::path::Path;
+
+use crate::dialogs::{get_dialog_templates, DIALOGS_FILE};
+use crate::parser::ez_definition::{
+    ConfigLine, EzWidgetDefinition, Templates, LAZY_PROPERTY, SLOT_DIRECTIVE,
+};
+use crate::parser::load_base_properties::resolve_property;
+use crate::parser::parse_properties::parse_bool_property;
+use crate::parser::parse_lang::{
+    collect_variables, interpolate_env_vars, parse_level, resolve_variables, EzVariables,
+};
+use crate::scheduler::scheduler::{Scheduler, SchedulerFrontend};
+use crate::widgets::{
+    button::Button, canvas::Canvas, checkbox::Checkbox, data_grid::DataGrid, dropdown::Dropdown,
+    ez_object::EzObjects, heatmap::Heatmap,
+    label::Label, layout::layout::Layout, list_view::ListView, progress_bar::ProgressBar, radio_button::RadioButton,
+    rating::Rating, slider::Slider, stepper::Stepper, text_box::TextBox, text_input::TextInput,
+    qr_code::QrCode, ticker::Ticker,
+};
+
+/// Kinds of mistakes found by [validate_ez].
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum EzLintKind {
+    /// A file could not be read or parsed, e.g. because of invalid indentation
+    ParseError,
+
+    /// A widget has a property that does not exist for its' type
+    UnknownProperty,
+
+    /// A property has a value that cannot be parsed
+    InvalidValue,
+
+    /// A widget can never be shown, e.g. because it is defined inside a widget that is not a layout
+    UnreachableWidget,
+
+    /// Multiple widgets have the same path, or the same ID is used in multiple places (which makes
+    /// looking up widgets by ID ambiguous)
+    DuplicateId,
+
+    /// A widget type is not a base widget type and not a template
+    MissingTemplate,
+
+    /// A property is bound to a widget or property that does not exist
+    BadBinding,
+}
+
+/// A mistake found in an .ez file by [validate_ez].
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+pub struct EzLintWarning {
+    /// Kind of mistake
+    pub kind: EzLintKind,
+
+    /// Path to the file that contains the mistake
+    pub file: String,
+
+    /// Line of the mistake (starting from 1), if known
+    pub line: Option<usize>,
+
+    /// Human readable description of the mistake
+    pub message: String,
+}
+impl Display for EzLintWarning {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "{}:{}: {}", self.file, line, self.message),
+            None => write!(f, "{}: {}", self.file, self.message),
+        }
+    }
+}
+
+/// Base widget types that can be used in .ez files.
+const BASE_TYPES: [&str; 18] = [
+    "Layout",
+    "Canvas",
+    "Label",
+    "Button",
+    "CheckBox",
+    "RadioButton",
+    "TextInput",
+    "TextBox",
+    "DataGrid",
+    "ListView",
+    "Dropdown",
+    "Slider",
+    "ProgressBar",
+    "Stepper",
+    "Rating",
+    "Ticker",
+    "QrCode",
+    "Heatmap",
+];
+
+/// Check .ez files for mistakes without running the UI. Reports files that cannot be parsed,
+/// unknown properties, invalid property values, widgets that can never be shown, duplicate IDs,
+/// unknown widget types or templates and bindings to widgets or properties that do not exist.
+/// Paths can be files or folders; folders are searched for .ez files recursively. Returns an
+/// empty Vec if no mistakes were found.
+///
+/// Panics are used internally to detect mistakes, so the panic hook is replaced while validating.
+/// Do not call this while other threads may panic.
+///
+/// # Example:
+///
+/// ```
+/// use ez_term::*;
+///
+/// let warnings = validate_ez(&["./ui"]);
+/// for warning in warnings.iter() {
+///     println!("{}", warning);
+/// }
+/// if !warnings.is_empty() {
+///     std::process::exit(1);
+/// }
+/// ```
+pub fn validate_ez<P: AsRef<Path>>(paths: &[P]) -> Vec<EzLintWarning> {
+    let hook = take_hook();
+    set_hook(Box::new(|_| {}));
+    let mut linter = Linter::default();
+    linter.lint(paths);
+    drop(take_hook());
+    set_hook(hook);
+
+    let mut seen = HashSet::new();
+    linter
+        .warnings
+        .into_iter()
+        .filter(|x| seen.insert(x.clone()))
+        .collect()
+}
+
+/// Widget found while linting.
+struct LintedWidget {
+    file: String,
+    line: Option<usize>,
+}
+
+/// Binding found while linting.
+struct LintedBinding {
+    target: String,
+    value: String,
+    file: String,
+    line: Option<usize>,
+}
+
+/// State collected while linting the widget tree.
+struct Linter {
+    warnings: Vec<EzLintWarning>,
+    files: HashMap<String, Vec<String>>,
+    templates: Templates,
+    used_templates: HashSet<String>,
+    widgets: HashMap<String, LintedWidget>,
+    ids: HashMap<String, Vec<String>>,
+    bindings: Vec<LintedBinding>,
+    scheduler: SchedulerFrontend,
+}
+impl Default for Linter {
+    fn default() -> Self {
+        let mut scheduler = SchedulerFrontend::default();
+        scheduler.backend = Scheduler::new();
+        Linter {
+            warnings: Vec::new(),
+            files: HashMap::new(),
+            templates: get_dialog_templates(),
+            used_templates: HashSet::new(),
+            widgets: HashMap::new(),
+            ids: HashMap::new(),
+            bindings: Vec::new(),
+            scheduler,
+        }
+    }
+}
+impl Linter {
+    fn warn(&mut self, kind: EzLintKind, file: &str, line: Option<usize>, message: String) {
+        self.warnings.push(EzLintWarning {
+            kind,
+            file: file.to_string(),
+            line,
+            message,
+        });
+    }
+
+    /// Lint all files: parse them, walk the widget tree starting from the root widget, then lint
+    /// templates that are not used in the tree (they may be used from code) and check bindings.
+    fn lint<P: AsRef<Path>>(&mut self, paths: &[P]) {
+        let mut ez_files = Vec::new();
+        for path in paths {
+            collect_ez_files(path.as_ref(), &mut ez_files);
+        }
+        let mut roots = Vec::new();
+        let mut file_lines = Vec::new();
+        for file in ez_files {
+            let text = match fs::read_to_string(&file) {
+                Ok(i) => i,
+                Err(e) => {
+                    self.warn(EzLintKind::ParseError, &file, None, e.to_string());
+                    continue;
+                }
+            };
+            let mut lines = Vec::new();
+            for (i, line) in text.lines().enumerate() {
+                match interpolate_env_vars(line, &file, i + 1) {
+                    Ok(i) => lines.push(i),
+                    Err(e) => {
+                        self.warn(EzLintKind::ParseError, &file, e.line, e.message);
+                        lines.push(String::new());
+                    }
+                }
+            }
+            file_lines.push((file, lines));
+        }
+
+        // Variables can be used in any file, so collect the variables of all files first
+        let mut variables = EzVariables::new();
+        for (file, lines) in file_lines.iter_mut() {
+            let mut collected = variables.clone();
+            match collect_variables(lines, file, &mut collected) {
+                Ok(_) => variables = collected,
+                Err(e) => self.warn(EzLintKind::ParseError, file, e.line, e.message),
+            }
+        }
+        for (file, lines) in file_lines {
+            let mut resolved = Vec::new();
+            for (i, line) in lines.iter().enumerate() {
+                match resolve_variables(line, &variables, &file, i + 1) {
+                    Ok(i) => resolved.push(i),
+                    Err(e) => {
+                        self.warn(EzLintKind::ParseError, &file, e.line, e.message);
+                        resolved.push(String::new());
+                    }
+                }
+            }
+            let lines = resolved;
+            self.files.insert(file.clone(), lines.clone());
+            match parse_level(lines, 0, 0, file.clone()) {
+                Ok((_, widgets, templates)) => {
+                    roots.extend(widgets);
+                    self.templates.extend(templates);
+                }
+                Err(e) => self.warn(EzLintKind::ParseError, &file, e.line, e.message),
+            }
+        }
+
+        if roots.len() > 1 {
+            for root in roots.iter().skip(1) {
+                let line = self.find_line(root, 0, &format!("- {}:", root.type_name));
+                self.warn(
+                    EzLintKind::ParseError,
+                    &root.file,
+                    line,
+                    format!(
+                        "There can be only one root widget but {} were found. Create one root \
+                        layout with \"mode: screen\" if you meant to use multiple screens",
+                        roots.len()
+                    ),
+                );
+            }
+        }
+        if let Some(root) = roots.first() {
+            let mut root = root.clone();
+            root.is_root = true;
+            if root.resolve_base_type(&self.templates) != "Layout" {
+                let line = self.find_line(&root, 0, &format!("- {}:", root.type_name));
+                self.warn(
+                    EzLintKind::ParseError,
+                    &root.file,
+                    line,
+                    "Root widget of an Ez file must be a layout".to_string(),
+                );
+            }
+            self.lint_definition(&root, "", 0, None, &mut Vec::new());
+        }
+
+        let mut unused: Vec<String> = self
+            .templates
+            .keys()
+            .filter(|x| !self.used_templates.contains(*x))
+            .filter(|x| self.templates.get(*x).unwrap().file != DIALOGS_FILE)
+            .cloned()
+            .collect();
+        unused.sort();
+        for name in unused {
+            let template = self.templates.get(&name).unwrap().clone();
+            self.lint_definition(&template, &format!("<{}>", name), 0, None, &mut vec![name]);
+        }
+        self.check_ids();
+        self.check_bindings();
+    }
+
+    /// Find the line of a definition or property in the original file. Definitions only know an
+    /// approximate line (empty lines and comments are not counted in nested definitions) which is
+    /// never after the real line, so search forward from there.
+    fn find_line(&self, definition: &EzWidgetDefinition, offset: usize, text: &str) -> Option<usize> {
+        let lines = self.files.get(&definition.file)?;
+        let start = definition.line_offset.saturating_sub(1) + offset;
+        lines
+            .iter()
+            .enumerate()
+            .skip(start)
+            .find(|(_, x)| x.trim() == text.trim())
+            .map(|(i, _)| i + 1)
+    }
+
+    /// Lint a widget definition and its' sub widgets. Returns the path of the widget. Templates
+    /// are followed to their base type; [template_stack] is used to detect templates that are
+    /// (indirectly) based on themselves.
+    fn lint_definition(
+        &mut self,
+        definition: &EzWidgetDefinition,
+        parent_path: &str,
+        order: usize,
+        id_override: Option<String>,
+        template_stack: &mut Vec<String>,
+    ) -> String {
+        let def_line = self.find_line(definition, 0, &format!("- {}:", definition.type_name));
+        let content = definition.content.clone();
+        let (indentation, line_offset, file) = (
+            definition.indentation_offset,
+            definition.line_offset,
+            definition.file.clone(),
+        );
+        let (config, sub_widgets) = match parse_level(content, indentation, line_offset, file) {
+            Ok((config, sub_widgets, _)) => (config, sub_widgets),
+            Err(e) => {
+                self.warn(EzLintKind::ParseError, &definition.file, e.line, e.message);
+                return parent_path.to_string();
+            }
+        };
+
+        let own_id = config
+            .iter()
+            .rev()
+            .filter_map(|x| x.text.split_once(':'))
+            .filter(|(name, _)| name.trim() == "id")
+            .map(|(_, value)| value.trim().to_string())
+            .next();
+        if definition.is_root && own_id.as_deref().map_or(false, |x| x != "root") {
+            self.warn(
+                EzLintKind::ParseError,
+                &definition.file,
+                def_line,
+                "Root widget cannot have an ID parameter; it is \"root\" by default".to_string(),
+            );
+        }
+        let id = id_override.clone().or_else(|| own_id.clone()).unwrap_or_else(|| {
+            if definition.is_root {
+                "root".to_string()
+            } else {
+                order.to_string()
+            }
+        });
+
+        let type_name = definition.type_name.clone();
+        let (path, base_type) = if self.templates.contains_key(&type_name) {
+            if template_stack.contains(&type_name) {
+                self.warn(
+                    EzLintKind::MissingTemplate,
+                    &definition.file,
+                    def_line,
+                    format!("Template \"{}\" is based on itself", type_name),
+                );
+                return parent_path.to_string();
+            }
+            self.used_templates.insert(type_name.clone());
+            let mut template = self.templates.get(&type_name).unwrap().clone();
+            template.is_root = definition.is_root;
+            template_stack.push(type_name.clone());
+            let id_override = id_override.clone().or_else(|| own_id.clone());
+            let path =
+                self.lint_definition(&template, parent_path, order, id_override, template_stack);
+            template_stack.pop();
+            (path, template.resolve_base_type(&self.templates))
+        } else if BASE_TYPES.contains(&type_name.as_str()) {
+            let path = format!("{}/{}", parent_path, id);
+            if path.starts_with("/root") {
+                if let Some(existing) = self.widgets.get(&path) {
+                    let (existing_file, existing_line) = (existing.file.clone(), existing.line);
+                    self.warn(
+                        EzLintKind::DuplicateId,
+                        &definition.file,
+                        def_line,
+                        format!(
+                            "Widget \"{}\" has the same path as the widget defined at {}:{}",
+                            path,
+                            existing_file,
+                            existing_line.map(|x| x.to_string()).unwrap_or_default()
+                        ),
+                    );
+                }
+                self.widgets.insert(
+                    path.clone(),
+                    LintedWidget {
+                        file: definition.file.clone(),
+                        line: def_line,
+                    },
+                );
+                if id_override.is_some() || own_id.is_some() {
+                    self.ids.entry(id.clone()).or_default().push(path.clone());
+                }
+            }
+            (path, type_name.clone())
+        } else {
+            self.warn(
+                EzLintKind::MissingTemplate,
+                &definition.file,
+                def_line,
+                format!(
+                    "\"{}\" is not a widget type or template. Widget types are: {}",
+                    type_name,
+                    BASE_TYPES.join(", ")
+                ),
+            );
+            return parent_path.to_string();
+        };
+
+        self.lint_config(definition, &config, &id, &path, &base_type);
+
+        if base_type == "Layout" {
+            for (i, sub_widget) in sub_widgets.iter().enumerate() {
+                self.lint_definition(sub_widget, &path, i, None, &mut Vec::new());
+            }
+        } else {
+            for sub_widget in sub_widgets.iter() {
+                let line = self.find_line(sub_widget, 0, &format!("- {}:", sub_widget.type_name));
+                self.warn(
+                    EzLintKind::UnreachableWidget,
+                    &sub_widget.file,
+                    line,
+                    format!(
+                        "Widget is defined inside a {} and will never be shown; only layouts \
+                        can contain widgets",
+                        base_type
+                    ),
+                );
+            }
+        }
+        path
+    }
+
+    /// Load each property of a definition into a new widget of the base type to find unknown
+    /// properties and invalid values, and collect bindings to check later.
+    fn lint_config(
+        &mut self,
+        definition: &EzWidgetDefinition,
+        config: &[ConfigLine],
+        id: &str,
+        path: &str,
+        base_type: &str,
+    ) {
+        let mut widget = match create_base_widget(base_type, id, path, &mut self.scheduler) {
+            Some(i) => i,
+            None => return,
+        };
+        for config_line in config.iter() {
+            let line_str = &config_line.text;
+            let line = Some(config_line.line).filter(|x| *x > 0);
+            let (name, value) = match line_str.split_once(':') {
+                Some(i) => i,
+                None => {
+                    self.warn(
+                        EzLintKind::ParseError,
+                        &definition.file,
+                        line,
+                        format!(
+                            "Property must contain a \":\", e.g. \"property: value\": \"{}\"",
+                            line_str.trim()
+                        ),
+                    );
+                    continue;
+                }
+            };
+            let (name, value) = (name.trim().to_string(), value.to_string());
+            if name == "id" || name == SLOT_DIRECTIVE {
+                continue;
+            }
+            if name == LAZY_PROPERTY {
+                if let Err(e) = parse_bool_property(value.trim()) {
+                    self.warn(
+                        EzLintKind::InvalidValue,
+                        &definition.file,
+                        line,
+                        format!("Invalid value for property \"{}\": {}", name, e),
+                    );
+                }
+                continue;
+            }
+            for part in value.split(|x| ['+', '-', '/', '*', '(', ')', ','].contains(&x)) {
+                let part = part.trim();
+                if part.starts_with("properties.") {
+                    continue;
+                }
+                match catch_unwind(|| resolve_property(part, path.to_string())) {
+                    Ok(Some(target)) => self.bindings.push(LintedBinding {
+                        target,
+                        value: part.to_string(),
+                        file: definition.file.clone(),
+                        line,
+                    }),
+                    Ok(None) => (),
+                    Err(_) => self.warn(
+                        EzLintKind::BadBinding,
+                        &definition.file,
+                        line,
+                        format!("\"{}\" refers to a parent above the root layout", part),
+                    ),
+                }
+            }
+            let scheduler = &mut self.scheduler;
+            let result = catch_unwind(AssertUnwindSafe(|| {
+                widget
+                    .as_ez_object_mut()
+                    .load_ez_parameter(name.clone(), value.clone(), scheduler)
+            }));
+            match result {
+                Ok(Ok(())) => (),
+                Ok(Err(e)) if e.to_string().starts_with("Invalid parameter name") => {
+                    self.warn(EzLintKind::UnknownProperty, &definition.file, line, e.to_string())
+                }
+                Ok(Err(e)) => self.warn(
+                    EzLintKind::InvalidValue,
+                    &definition.file,
+                    line,
+                    format!("Invalid value for property \"{}\": {}", name, e),
+                ),
+                Err(e) => {
+                    let message = panic_message(e);
+                    let kind = if message.starts_with("Invalid parameter name") {
+                        EzLintKind::UnknownProperty
+                    } else {
+                        EzLintKind::InvalidValue
+                    };
+                    self.warn(kind, &definition.file, line, message);
+                }
+            }
+        }
+    }
+
+    /// Warn about explicit IDs that are used by multiple widgets.
+    fn check_ids(&mut self) {
+        let mut ids: Vec<(String, Vec<String>)> = self
+            .ids
+            .iter()
+            .filter(|(_, paths)| {
+                paths.iter().collect::<HashSet<&String>>().len() > 1
+            })
+            .map(|(id, paths)| (id.clone(), paths.clone()))
+            .collect();
+        ids.sort();
+        for (id, mut paths) in ids {
+            paths.sort();
+            paths.dedup();
+            for path in paths.iter() {
+                let widget = self.widgets.get(path).unwrap();
+                let (file, line) = (widget.file.clone(), widget.line);
+                self.warn(
+                    EzLintKind::DuplicateId,
+                    &file,
+                    line,
+                    format!(
+                        "ID \"{}\" is used by multiple widgets ({}); looking it up by ID is \
+                        ambiguous, use the full path instead",
+                        id,
+                        paths.join(", ")
+                    ),
+                );
+            }
+        }
+    }
+
+    /// Warn about bindings to widgets or properties that do not exist. Bindings in templates that
+    /// are not used in the widget tree cannot be resolved, so they are skipped.
+    fn check_bindings(&mut self) {
+        let bindings = std::mem::take(&mut self.bindings);
+        for binding in bindings {
+            if !binding.target.starts_with("/root") {
+                continue;
+            }
+            let (widget, property) = binding.target.rsplit_once('/').unwrap();
+            let message = if !self.widgets.contains_key(widget) {
+                format!(
+                    "\"{}\" refers to widget \"{}\" which does not exist",
+                    binding.value, widget
+                )
+            } else if !self.scheduler.backend.properties.contains_key(&binding.target) {
+                format!(
+                    "\"{}\" refers to property \"{}\" which does not exist on widget \"{}\"",
+                    binding.value, property, widget
+                )
+            } else {
+                continue;
+            };
+            self.warn(EzLintKind::BadBinding, &binding.file, binding.line, message);
+        }
+    }
+}
+
+/// Create a widget of a base type so that properties can be loaded into it.
+fn create_base_widget(
+    type_name: &str,
+    id: &str,
+    path: &str,
+    scheduler: &mut SchedulerFrontend,
+) -> Option<EzObjects> {
+    let (id, path) = (id.to_string(), path.to_string());
+    let widget = match type_name {
+        "Layout" => EzObjects::Layout(Layout::new(id, path, scheduler)),
+        "Canvas" => EzObjects::Canvas(Canvas::new(id, path, scheduler)),
+        "Label" => EzObjects::Label(Label::new(id, path, scheduler)),
+        "Button" => EzObjects::Button(Button::new(id, path, scheduler)),
+        "CheckBox" => EzObjects::Checkbox(Checkbox::new(id, path, scheduler)),
+        "RadioButton" => EzObjects::RadioButton(RadioButton::new(id, path, scheduler)),
+        "TextInput" => EzObjects::TextInput(TextInput::new(id, path, scheduler)),
+        "TextBox" => EzObjects::TextBox(TextBox::new(id, path, scheduler)),
+        "DataGrid" => EzObjects::DataGrid(DataGrid::new(id, path, scheduler)),
+        "ListView" => EzObjects::ListView(ListView::new(id, path, scheduler)),
+        "Dropdown" => EzObjects::Dropdown(Dropdown::new(id, path, scheduler)),
+        "Slider" => EzObjects::Slider(Slider::new(id, path, scheduler)),
+        "ProgressBar" => EzObjects::ProgressBar(ProgressBar::new(id, path, scheduler)),
+        "Rating" => EzObjects::Rating(Rating::new(id, path, scheduler)),
+        "Stepper" => EzObjects::Stepper(Stepper::new(id, path, scheduler)),
+        "Ticker" => EzObjects::Ticker(Ticker::new(id, path, scheduler)),
+        "QrCode" => EzObjects::QrCode(QrCode::new(id, path, scheduler)),
+        "Heatmap" => EzObjects::Heatmap(Heatmap::new(id, path, scheduler)),
+        _ => return None,
+    };
+    Some(widget)
+}
+
+/// Find all files that end with '.ez' in a folder recursively. A path to a file is used as is.
+pub(crate) fn collect_ez_files(path: &Path, ez_files: &mut Vec<String>) {
+    if path.is_dir() {
+        let mut entries: Vec<_> = match fs::read_dir(path) {
+            Ok(i) => i.filter_map(|x| x.ok()).map(|x| x.path()).collect(),
+            Err(_) => return,
+        };
+        entries.sort();
+        for entry in entries {
+            if entry.is_dir() {
+                collect_ez_files(&entry, ez_files);
+            } else if entry.extension().map_or(false, |x| x == "ez") {
+                ez_files.push(entry.to_string_lossy().to_string());
+            }
+        }
+    } else {
+        ez_files.push(path.to_string_lossy().to_string());
+    }
+}
+
+/// Get the message of a caught panic.
+pub(crate) fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "Unknown error".to_string()
+    }
+}
@@ -14,7 +14,7 @@ use crate::parser::parse_properties;
 use crate::property::ez_values::EzValues;
 use crate::scheduler::scheduler::SchedulerFrontend;
 use crate::states::definitions::{
-    HorizontalAlignment, LayoutMode, LayoutOrientation, VerticalAlignment,
+    DockPosition, HorizontalAlignment, LayoutMode, LayoutOrientation, VerticalAlignment,
 };
 use crate::{GenericState, StateTree};
 
@@ -94,7 +94,7 @@ pub fn load_usize_property(
             path,
             property_name.to_string(),
             scheduler,
-        );
+        )?;
         state.update_property(property_name, EzValues::Usize(0));
         Ok(())
     } else if let Some(_) = resolve_property(value, path.clone()) {
@@ -116,13 +116,14 @@ pub fn load_usize_property(
 }
 
 /// In EzLang it's possible to use math (e.g. widget1.height - widget2.height + 5). This function
-/// wraps a usize update method in a new update method that performs this math.
+/// wraps a usize update method in a new update method that performs this math. Returns an error
+/// if the expression cannot be parsed.
 pub fn wrap_usize_property(
     value: String,
     path: String,
     property_name: String,
     scheduler: &mut SchedulerFrontend,
-) {
+) -> Result<(), Error> {
     let mut value = value.clone();
     let parts: Vec<String> = value
         .split(|x| ['+', '-', '/', '*', '(', ')'].contains(&x))
@@ -140,7 +141,13 @@ pub fn wrap_usize_property(
         let (mut object, mut property) = (String::new(), String::new());
         if part.trim().parse::<usize>().is_err() {
             let property_path = resolve_property(part.trim(), path.clone())
-                .unwrap_or_else(|| panic!("Cannot parse this value expression: {}", value));
+                .filter(|x| x.contains('/'))
+                .ok_or_else(|| {
+                    Error::new(
+                        ErrorKind::InvalidData,
+                        format!("Cannot parse this value expression: {}", value),
+                    )
+                })?;
             let (_object, _property) = property_path.rsplit_once('/').unwrap().to_owned();
             (object, property) = (_object.to_string(), _property.to_string());
             to_bind.push(property_path.to_string());
@@ -157,9 +164,15 @@ pub fn wrap_usize_property(
         values.push((name, getter));
     }
 
-    let expr: meval::Expr = value.parse().unwrap();
+    let expr: meval::Expr = value.parse().map_err(|e| {
+        Error::new(
+            ErrorKind::InvalidData,
+            format!("Cannot parse this value expression: {}: {}", value, e),
+        )
+    })?;
     let values_c = values.clone();
     let property_path = format!("{}/{}", path, property_name);
+    scheduler._get_update_func(&property_path);
     let mut update_func = scheduler
         .backend
         .property_updaters
@@ -183,6 +196,7 @@ pub fn wrap_usize_property(
     for bind in to_bind {
         scheduler.subscribe_to_property(&bind, property_path.clone());
     }
+    Ok(())
 }
 
 /// Load a f64 [EzProperty]. It is either bound to another f64 property and initialized with 0.0
@@ -248,6 +262,28 @@ pub fn load_string_property(
     }
 }
 
+/// Load a string list [EzProperty]. It is either bound to another string list property and
+/// initialized with an empty list or parsed from comma separated values in the .ez file.
+pub fn load_string_list_property(
+    value: &str,
+    scheduler: &mut SchedulerFrontend,
+    path: String,
+    property_name: &str,
+    state: &mut dyn GenericState,
+) -> Result<(), Error> {
+    if bind_ez_property(value, scheduler, path, property_name.to_string()) {
+        state.update_property(property_name, EzValues::StringList(Vec::new()));
+    } else {
+        let values = value
+            .split(',')
+            .map(|x| x.trim().to_string())
+            .filter(|x| !x.is_empty())
+            .collect();
+        state.update_property(property_name, EzValues::StringList(values));
+    }
+    Ok(())
+}
+
 /// Load a [Color] [EzProperty]. It is either bound to another Color property and initialized with
 /// [Color::Black] or parsed from the user defined string from the .ez file.
 pub fn load_color_property(
@@ -310,6 +346,26 @@ pub fn load_layout_orientation_property(
     }
 }
 
+/// Load a [DockPosition] [EzProperty]. It is either bound to another DockPosition property and
+/// initialized with [DockPosition::Center] or parsed from the user defined string from the .ez
+/// file.
+pub fn load_dock_position_property(
+    value: &str,
+    scheduler: &mut SchedulerFrontend,
+    path: String,
+    property_name: &str,
+    state: &mut dyn GenericState,
+) -> Result<(), Error> {
+    if bind_ez_property(value, scheduler, path, property_name.to_string()) {
+        state.update_property(property_name, EzValues::DockPosition(DockPosition::Center));
+        Ok(())
+    } else {
+        let val = parse_properties::parse_dock_position_property(value)?;
+        state.update_property(property_name, EzValues::DockPosition(val));
+        Ok(())
+    }
+}
+
 /// Load a [VerticalAlignment] [EzProperty]. It is either bound to another valign property and
 /// initialized with [VerticalAlignment::Top] or parsed from the user defined string from the
 /// .ez file.
@@ -6,7 +6,8 @@
 //! - Pass an update closure, which is used if that property is bound to another property
 use std::io::{Error, ErrorKind};
 
-use crate::parser::load_base_properties;
+use crate::parser::{load_base_properties, parse_properties};
+use crate::scheduler::dirty::get_dirty_property_name;
 use crate::scheduler::scheduler::SchedulerFrontend;
 use crate::widgets::ez_object::EzObject;
 
@@ -163,7 +164,13 @@ pub fn load_common_property(
             state.get_size_mut().height.locked = property_value.trim().parse::<usize>().is_ok();
         }
         "pos_hint" => {
-            let (x_str, y_str) = property_value.split_once(',').unwrap();
+            let (x_str, y_str) = property_value.split_once(',').ok_or_else(|| {
+                Error::new(
+                    ErrorKind::InvalidData,
+                    "The pos_hint property requires two pos hints separated by a \",\". If you \
+                    want to set only one, use pos_hint_x or pos_hint_y instead.",
+                )
+            })?;
             load_base_properties::load_horizontal_pos_hint_property(
                 x_str,
                 scheduler,
@@ -194,9 +201,13 @@ pub fn load_common_property(
             state,
         )?,
         "auto_scale" => {
-            let (width_str, height_str) = property_value.split_once(',').unwrap_or_else(
-                || panic!("The auto_scale property requires two \
-        bool values. If you want to set only one, use auto_scale_height or auto_scale_width instead."));
+            let (width_str, height_str) = property_value.split_once(',').ok_or_else(|| {
+                Error::new(
+                    ErrorKind::InvalidData,
+                    "The auto_scale property requires two bool values. If you want to set only \
+                    one, use auto_scale_height or auto_scale_width instead.",
+                )
+            })?;
             load_base_properties::load_bool_property(
                 width_str.trim(),
                 scheduler,
@@ -357,6 +368,91 @@ pub fn load_common_property(
             property_name,
             state,
         )?,
+        "dock" => load_base_properties::load_dock_position_property(
+            property_value.trim(),
+            scheduler,
+            path.clone(),
+            property_name,
+            state,
+        )?,
+        "collapsed" => load_base_properties::load_bool_property(
+            property_value.trim(),
+            scheduler,
+            path.clone(),
+            property_name,
+            state,
+        )?,
+        "weight" => load_base_properties::load_f64_property(
+            property_value.trim(),
+            scheduler,
+            path.clone(),
+            property_name,
+            state,
+        )?,
+        "z_index" => load_base_properties::load_usize_property(
+            property_value.trim(),
+            scheduler,
+            path.clone(),
+            property_name,
+            state,
+        )?,
+        "anchor" => {
+            if load_base_properties::resolve_property(property_value.trim(), path.clone())
+                .is_none()
+            {
+                parse_properties::parse_anchor_property(property_value.trim())?;
+            }
+            load_base_properties::load_string_property(
+                property_value.trim(),
+                scheduler,
+                path.clone(),
+                property_name,
+                state,
+            )?
+        }
+        "anchor_offset_x" => load_base_properties::load_usize_property(
+            property_value.trim(),
+            scheduler,
+            path.clone(),
+            property_name,
+            state,
+        )?,
+        "anchor_offset_y" => load_base_properties::load_usize_property(
+            property_value.trim(),
+            scheduler,
+            path.clone(),
+            property_name,
+            state,
+        )?,
+        "anchor_offset" => {
+            let (x, y) = match property_value.split_once(',') {
+                Some((i, j)) => (i, j),
+                None => {
+                    return Err(Error::new(
+                        ErrorKind::InvalidData,
+                        format!(
+                            "Could not load anchor_offset parameter: \"{}\". \
+                               It must be in the form \"anchor_offset: 2, 1\"",
+                            property_value
+                        ),
+                    ))
+                }
+            };
+            load_base_properties::load_usize_property(
+                x.trim(),
+                scheduler,
+                path.clone(),
+                "anchor_offset_x",
+                state,
+            )?;
+            load_base_properties::load_usize_property(
+                y.trim(),
+                scheduler,
+                path.clone(),
+                "anchor_offset_y",
+                state,
+            )?;
+        }
         "halign" => load_base_properties::load_halign_property(
             property_value.trim(),
             scheduler,
@@ -371,6 +467,20 @@ pub fn load_common_property(
             property_name,
             state,
         )?,
+        "auto_colors" => load_base_properties::load_bool_property(
+            property_value.trim(),
+            scheduler,
+            path.clone(),
+            property_name,
+            state,
+        )?,
+        "auto_contrast" => load_base_properties::load_bool_property(
+            property_value.trim(),
+            scheduler,
+            path.clone(),
+            property_name,
+            state,
+        )?,
         "fg_color" => load_base_properties::load_color_property(
             property_value.trim(),
             scheduler,
@@ -483,6 +593,27 @@ pub fn load_common_property(
             property_name,
             state,
         )?,
+        "filler_alt_bg_color" => load_base_properties::load_color_property(
+            property_value.trim(),
+            scheduler,
+            path.clone(),
+            property_name,
+            state,
+        )?,
+        "filler_header_fg_color" => load_base_properties::load_color_property(
+            property_value.trim(),
+            scheduler,
+            path.clone(),
+            property_name,
+            state,
+        )?,
+        "filler_header_bg_color" => load_base_properties::load_color_property(
+            property_value.trim(),
+            scheduler,
+            path.clone(),
+            property_name,
+            state,
+        )?,
         "cursor_color" => load_base_properties::load_color_property(
             property_value.trim(),
             scheduler,
@@ -490,6 +621,62 @@ pub fn load_common_property(
             property_name,
             state,
         )?,
+        "selection_style" => {
+            if load_base_properties::resolve_property(property_value.trim(), path.clone())
+                .is_none()
+            {
+                parse_properties::parse_selection_style_property(property_value.trim())?;
+            }
+            load_base_properties::load_string_property(
+                property_value.trim(),
+                scheduler,
+                path.clone(),
+                property_name,
+                state,
+            )?
+        }
+        "selection_marker" => load_base_properties::load_string_property(
+            property_value.trim(),
+            scheduler,
+            path.clone(),
+            property_name,
+            state,
+        )?,
+        "hover_fg_color" => load_base_properties::load_color_property(
+            property_value.trim(),
+            scheduler,
+            path.clone(),
+            property_name,
+            state,
+        )?,
+        "hover_bg_color" => load_base_properties::load_color_property(
+            property_value.trim(),
+            scheduler,
+            path.clone(),
+            property_name,
+            state,
+        )?,
+        "pressed_fg_color" => load_base_properties::load_color_property(
+            property_value.trim(),
+            scheduler,
+            path.clone(),
+            property_name,
+            state,
+        )?,
+        "pressed_bg_color" => load_base_properties::load_color_property(
+            property_value.trim(),
+            scheduler,
+            path.clone(),
+            property_name,
+            state,
+        )?,
+        "focused_border" => load_base_properties::load_bool_property(
+            property_value.trim(),
+            scheduler,
+            path.clone(),
+            property_name,
+            state,
+        )?,
         "border" => load_base_properties::load_bool_property(
             property_value.trim(),
             scheduler,
@@ -553,6 +740,24 @@ pub fn load_common_property(
             property_name,
             state,
         )?,
+        "refresh_every" => {
+            let (interval, task) =
+                parse_properties::parse_refresh_every_property(property_value.trim())?;
+            scheduler.schedule_refresh_task(&path, &task, interval);
+        }
+        "track_dirty" => {
+            if parse_properties::parse_bool_property(property_value.trim())? {
+                // Create the property of the form now, so other widgets can bind to it
+                scheduler.new_bool_property(&get_dirty_property_name(&path), false);
+                scheduler.backend.dirty_forms_to_track.push(path.clone());
+            }
+        }
+        "group" => {
+            for group in property_value.split(',').map(|x| x.trim()).filter(|x| !x.is_empty()) {
+                scheduler.add_to_group(&path, group);
+            }
+        }
+        "action" => scheduler.bind_action(&path, property_value.trim()),
         _ => return Ok(false),
     }
     Ok(true)
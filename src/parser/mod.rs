@@ -8,3 +8,9 @@ pub mod load_base_properties;
 pub mod load_common_properties;
 /// A module to parse .ez files
 pub mod parse_lang;
+/// A module to check .ez files for mistakes without running the UI
+pub mod lint;
+/// A module exposing the structure of .ez files with source spans for external tools
+pub mod syntax;
+/// A module to format .ez files in a consistent style
+pub mod format;
@@ -2,57 +2,318 @@
 //! Module containing functions to parse .ez files and generate [EzWidgetDefinition] objects that
 //! can be used to initialize actual widgets.
 use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::fs;
 use std::io::Error;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crossterm::style::Color;
 
 use unicode_segmentation::UnicodeSegmentation;
 
-use crate::parser::ez_definition::{EzWidgetDefinition, Templates};
+use crate::dialogs::{create_dialog_properties, get_dialog_templates};
+use crate::parser::ez_definition::{
+    check_template_inheritance, ConfigLine, EzWidgetDefinition, Templates,
+};
+use crate::parser::lint::collect_ez_files;
 use crate::run::definitions::StateTree;
+use crate::run::terminal::{is_light_color, Terminal};
 use crate::run::tree::initialize_state_tree;
-use crate::scheduler::definitions::CustomDataMap;
+use crate::scheduler::definitions::{CustomDataMap, StartupProfile};
 use crate::scheduler::scheduler::{Scheduler, SchedulerFrontend};
+use crate::states::definitions::Theme;
 use crate::widgets::layout::layout::Layout;
 
 include!(concat!(env!("OUT_DIR"), "/ez_file_gen.rs"));
 
+/// Variables defined in .ez files, mapping the name of each variable to its' value. See
+/// [collect_variables].
+pub type EzVariables = HashMap<String, String>;
+
+/// Prefix of a line that defines a variable, e.g. "@define primary_color: 50,120,255".
+pub const DEFINE_PREFIX: &str = "@define";
+
+/// A mistake in an .ez file that prevented it from being loaded, see [load_ui_checked].
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct ParseError {
+    /// Path to the file that contains the mistake, if known
+    pub file: Option<String>,
+
+    /// Line of the mistake (starting from 1), if known
+    pub line: Option<usize>,
+
+    /// Column of the mistake in the line (starting from 0), if known
+    pub column: Option<usize>,
+
+    /// Human readable description of the mistake
+    pub message: String,
+}
+impl ParseError {
+    /// Create an error for a mistake at a line (starting from 1) and column (starting from 0) of a
+    /// file. Line 0 means the mistake is not in a file, e.g. in a config line created from code,
+    /// in which case the error has no location.
+    pub fn new(file: &str, line: usize, column: usize, message: String) -> Self {
+        if line == 0 {
+            return ParseError {
+                file: None,
+                line: None,
+                column: None,
+                message,
+            };
+        }
+        ParseError {
+            file: Some(file.to_string()),
+            line: Some(line),
+            column: Some(column),
+            message,
+        }
+    }
+}
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let file = self.file.as_deref().unwrap_or("<unknown file>");
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => {
+                write!(f, "{}:{}:{}: {}", file, line, column + 1, self.message)
+            }
+            (Some(line), None) => write!(f, "{}:{}: {}", file, line, self.message),
+            _ => write!(f, "{}: {}", file, self.message),
+        }
+    }
+}
+impl std::error::Error for ParseError {}
+
+/// Error returned by the checked load functions, e.g. [load_ui_checked].
+#[derive(Debug)]
+pub enum EzError {
+    /// An .ez file contains a mistake
+    Parse(ParseError),
+
+    /// An .ez file could not be read
+    Io(Error),
+}
+impl Display for EzError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EzError::Parse(i) => write!(f, "{}", i),
+            EzError::Io(i) => write!(f, "{}", i),
+        }
+    }
+}
+impl std::error::Error for EzError {}
+impl From<ParseError> for EzError {
+    fn from(error: ParseError) -> Self {
+        EzError::Parse(error)
+    }
+}
+impl From<Error> for EzError {
+    fn from(error: Error) -> Self {
+        EzError::Io(error)
+    }
+}
+
 /// Load a file path into a root layout. Return the root widget, state tree and a new scheduler.
-/// These will be needed to run the ui.
+/// These will be needed to run the ui. Panics if the .ez files contain a mistake, see
+/// [load_ui_checked] for a version that returns an error instead.
 pub fn load_ui<'a>() -> (Layout, StateTree, SchedulerFrontend, CustomDataMap) {
     let contents = ez_config(); // ez_config is generated from build.rs
-    let (root_widget, scheduler) = load_ez_text(contents).unwrap();
+    let (root_widget, scheduler) = load_ez_text(contents).unwrap_or_else(|e| panic!("{}", e));
+    finish_loading(root_widget, scheduler)
+}
+
+/// Like [load_ui], but returns a [ParseError] instead of panicking if the .ez files contain a
+/// mistake.
+///
+/// # Example:
+///
+/// ```
+/// use ez_term::*;
+///
+/// let (root_widget, state_tree, scheduler, custom_data) = match load_ui_checked() {
+///     Ok(i) => i,
+///     Err(e) => {
+///         eprintln!("Could not load UI: {}", e);
+///         std::process::exit(1);
+///     }
+/// };
+/// ```
+pub fn load_ui_checked() -> Result<
+    (Layout, StateTree, SchedulerFrontend, CustomDataMap),
+    EzError,
+> {
+    load_ez_text_checked(ez_config())
+}
+
+/// Load the text of .ez files into a UI, returning a [ParseError] if the files contain a mistake.
+/// The files map the path of each file (used in errors) to its' text. Unlike [load_ui], which
+/// loads the .ez files that were compiled into the binary, this can load files supplied at
+/// runtime, e.g. by users of a UI designer.
+pub fn load_ez_text_checked(files: HashMap<String, String>) -> Result<
+    (Layout, StateTree, SchedulerFrontend, CustomDataMap),
+    EzError,
+> {
+    let (root_widget, scheduler) = load_ez_text(files)?;
+    Ok(finish_loading(root_widget, scheduler))
+}
+
+/// Read .ez files from disk and load them into a UI, returning an error if a file cannot be read
+/// or contains a mistake. Paths can be files or folders; folders are searched for .ez files
+/// recursively. See [load_ez_text_checked].
+///
+/// # Example:
+///
+/// ```
+/// use ez_term::*;
+///
+/// match load_ez_files_checked(&["./user_ui"]) {
+///     Ok((root_widget, state_tree, scheduler, custom_data)) =>
+///         run(root_widget, state_tree, scheduler, custom_data),
+///     Err(EzError::Parse(e)) => eprintln!("Mistake in {:?} at line {:?}: {}",
+///         e.file, e.line, e.message),
+///     Err(e) => eprintln!("{}", e),
+/// }
+/// ```
+pub fn load_ez_files_checked<P: AsRef<Path>>(paths: &[P]) -> Result<
+    (Layout, StateTree, SchedulerFrontend, CustomDataMap),
+    EzError,
+> {
+    let mut ez_files = Vec::new();
+    for path in paths {
+        collect_ez_files(path.as_ref(), &mut ez_files);
+    }
+    let mut files = HashMap::new();
+    for file in ez_files {
+        let text = fs::read_to_string(&file)?;
+        files.insert(file, text);
+    }
+    load_ez_text_checked(files)
+}
+
+/// Create the state tree for a loaded root widget and set up the custom properties every UI has.
+pub fn finish_loading(
+    root_widget: Layout,
+    mut scheduler: SchedulerFrontend,
+) -> (Layout, StateTree, SchedulerFrontend, CustomDataMap) {
+    let started = Instant::now();
     let state_tree = initialize_state_tree(&root_widget);
+    scheduler
+        .backend
+        .startup_profile
+        .add_phase("create state tree", started);
+    // Set by detect_terminal_background
+    scheduler.new_color_property("terminal_bg_color", Color::Reset);
+    scheduler.new_bool_property("terminal_is_light", false);
+    scheduler.new_bool_property("app_focused", true);
+    let profile = &mut scheduler.backend.startup_profile;
+    profile.widgets = state_tree.get_all().len();
+    profile.properties = scheduler.backend.properties.len();
+    profile.bound_properties = scheduler.backend.property_updaters.len();
     (root_widget, state_tree, scheduler, CustomDataMap::new())
 }
 
+/// Detect the background color of a terminal and store it in the "terminal_bg_color" and
+/// "terminal_is_light" custom properties. On a light terminal, widget colors that were left at
+/// their defaults are replaced by the colors of [Theme::light]. Detection is opt-in, as it
+/// briefly waits for the terminal to answer; call it after loading the UI and before running it.
+/// See [Terminal::query_background_color].
+///
+/// # Example:
+///
+/// ```
+/// use ez_term::*;
+/// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+///
+/// detect_terminal_background(&mut Terminal::stdout(), &mut state_tree, &mut scheduler);
+///
+/// run(root_widget, state_tree, scheduler, custom_data);
+/// ```
+pub fn detect_terminal_background(
+    terminal: &mut Terminal,
+    state_tree: &mut StateTree,
+    scheduler: &mut SchedulerFrontend,
+) {
+    let started = Instant::now();
+    let background = terminal.query_background_color(Duration::from_millis(100));
+    let is_light = background.map(is_light_color).unwrap_or(false);
+    scheduler
+        .get_property_mut("terminal_bg_color")
+        .as_color_mut()
+        .set(background.unwrap_or(Color::Reset));
+    scheduler
+        .get_property_mut("terminal_is_light")
+        .as_bool_mut()
+        .set(is_light);
+    if is_light {
+        let (dark, light) = (Theme::dark(), Theme::light());
+        for state in state_tree.get_all_mut() {
+            light.apply_over(&dark, state.as_generic_mut().get_color_config_mut());
+        }
+    }
+    scheduler
+        .backend
+        .startup_profile
+        .add_phase("detect terminal background", started);
+}
+
 /// Load a string from an Ez file into a root widget. Parse the first level and interpret the
-/// widget definition found there as the root widget (must be a layout or panic). Then parse the
-/// root widget definition into the actual widget, which will parse sub-widgets, who will parse
-/// their sub-widgets, etc. Thus recursively loading the UI.
-pub fn load_ez_text(files: HashMap<String, String>) -> Result<(Layout, SchedulerFrontend), Error> {
+/// widget definition found there as the root widget (must be a layout). Then parse the root
+/// widget definition into the actual widget, which will parse sub-widgets, who will parse their
+/// sub-widgets, etc. Thus recursively loading the UI. Returns an error for the first mistake
+/// found in the files.
+pub fn load_ez_text(
+    files: HashMap<String, String>,
+) -> Result<(Layout, SchedulerFrontend), ParseError> {
 
+    let mut profile = StartupProfile::default();
     let mut widgets: Vec<EzWidgetDefinition> = Vec::new();
-    let mut templates = Templates::new();
+    // Built-in templates come first so templates in the Ez files can replace them
+    let mut templates = get_dialog_templates();
+
+    let started = Instant::now();
+
+    // Variables can be used in any file, so collect the variables of all files first
+    let mut variables = EzVariables::new();
+    let mut file_lines = Vec::new();
     for (path, config) in files {
-        let (_, loaded_widgets, loaded_templates) = parse_level(
-            config.lines().into_iter().map(|x| x.to_string()).collect(),
-            0,
-            0,
-            path,
-        )
-        .unwrap();
+        let mut lines = config
+            .lines()
+            .enumerate()
+            .map(|(i, x)| interpolate_env_vars(x, &path, i + 1))
+            .collect::<Result<Vec<String>, ParseError>>()?;
+        collect_variables(&mut lines, &path, &mut variables)?;
+        file_lines.push((path, lines));
+    }
+    profile.add_phase("read files", started);
+
+    let started = Instant::now();
+    for (path, lines) in file_lines {
+        let lines = lines
+            .iter()
+            .enumerate()
+            .map(|(i, x)| resolve_variables(x, &variables, &path, i + 1))
+            .collect::<Result<Vec<String>, ParseError>>()?;
+        let (_, loaded_widgets, loaded_templates) = parse_level(lines, 0, 0, path)?;
         widgets.extend(loaded_widgets);
         templates.extend(loaded_templates);
     }
     if widgets.len() > 1 {
-        panic!(
+        return Err(widgets[1].error(format!(
             "There can be only one root widget but {} were found ({:?}). If you meant to use \
-        multiple screens, create one root layout with \"mode: screen\" and add the screen \
-        layouts to this root.",
+            multiple screens, create one root layout with \"mode: screen\" and add the screen \
+            layouts to this root.",
             widgets.len(),
             widgets
-        );
+        )));
     }
-    let mut root_widget = widgets.pop().unwrap();
+    check_template_inheritance(&templates)?;
+    let mut root_widget = widgets.pop().ok_or_else(|| ParseError {
+        file: None,
+        line: None,
+        column: None,
+        message: "No root widget was found. Define a root layout like \"- Layout:\"."
+            .to_string(),
+    })?;
     root_widget.is_root = true;
 
     // Ensure root widget is a [Layout], or a template inherited from [Layout]
@@ -63,158 +324,366 @@ pub fn load_ez_text(files: HashMap<String, String>) -> Result<(Layout, Scheduler
                 type_name = &templates.get(type_name).unwrap().type_name;
             } else {
                 if type_name.to_lowercase() != "layout" {
-                    panic!("Root widget of an Ez file must be a layout");
+                    return Err(root_widget
+                        .error("Root widget of an Ez file must be a layout".to_string()));
                 }
                 break;
             }
         }
     }
 
+    profile.add_phase("parse definitions", started);
+
+    let started = Instant::now();
     let mut scheduler = Scheduler::new();
     scheduler.templates = templates.clone();
     let mut scheduler_frontend = SchedulerFrontend::default();
     scheduler_frontend.backend = scheduler;
+    create_dialog_properties(&mut scheduler_frontend);
     let initialized_root_widget =
-        root_widget.parse(&mut scheduler_frontend, String::new(), 0, None);
+        root_widget.parse(&mut scheduler_frontend, String::new(), 0, None)?;
     let mut root = initialized_root_widget.as_layout().to_owned();
     root.state.set_templates(templates);
+    profile.add_phase("create widgets", started);
+    scheduler_frontend.backend.startup_profile = profile;
 
     Ok((root, scheduler_frontend))
 }
 
+/// Replace environment variables in a line of an Ez file by their value. Variables look like
+/// "${NAME}", or "${NAME:-fallback}" to use a fallback value when the variable is not set or
+/// empty. Write "$${" for a literal "${". Comment lines are left alone. Returns an error if a
+/// variable is not set and has no fallback, or if a variable is not closed by "}".
+pub fn interpolate_env_vars(
+    line: &str,
+    file: &str,
+    line_number: usize,
+) -> Result<String, ParseError> {
+    if !line.contains("${") || line.trim().starts_with("//") {
+        return Ok(line.to_string());
+    }
+    let mut result = String::new();
+    let mut rest = line;
+    while let Some(start) = rest.find("${") {
+        if rest[..start].ends_with('$') {
+            result.push_str(&rest[..start - 1]);
+            result.push_str("${");
+            rest = &rest[start + 2..];
+            continue;
+        }
+        result.push_str(&rest[..start]);
+        let column = get_column(line, line.len() - rest.len() + start);
+        let end = match rest[start..].find('}') {
+            Some(i) => i + start,
+            None => {
+                return Err(ParseError::new(
+                    file,
+                    line_number,
+                    column,
+                    "Environment variable is not closed by \"}\".".to_string(),
+                ))
+            }
+        };
+        let variable = &rest[start + 2..end];
+        let (name, fallback) = match variable.split_once(":-") {
+            Some((name, fallback)) => (name.trim(), Some(fallback)),
+            None => (variable.trim(), None),
+        };
+        let value = match (std::env::var(name), fallback) {
+            (Ok(value), Some(fallback)) if value.is_empty() => fallback.to_string(),
+            (Ok(value), _) => value,
+            (Err(_), Some(fallback)) => fallback.to_string(),
+            (Err(_), None) => {
+                return Err(ParseError::new(
+                    file,
+                    line_number,
+                    column,
+                    format!(
+                        "Environment variable \"{}\" is not set. Set it or give it a fallback \
+                        value like \"${{{}:-fallback}}\".",
+                        name, name
+                    ),
+                ))
+            }
+        };
+        result.push_str(&value);
+        rest = &rest[end + 1..];
+    }
+    result.push_str(rest);
+    Ok(result)
+}
+
+/// Collect the variables defined in the lines of an .ez file and add them to the given variables.
+/// Variables are defined at the top level of a file with lines like
+/// "@define primary_color: 50,120,255", and can be used as the value of any property (or part of
+/// it) with "$primary_color", see [resolve_variables]. Definitions can use variables defined above
+/// them in the same file. The definition lines are emptied so they are not parsed as config; line
+/// numbers stay the same. Returns an error if a definition is invalid, or if a variable is defined
+/// twice with different values.
+pub fn collect_variables(
+    lines: &mut [String],
+    file: &str,
+    variables: &mut EzVariables,
+) -> Result<(), ParseError> {
+    for (i, line) in lines.iter_mut().enumerate() {
+        let definition = match line.trim_start().strip_prefix(DEFINE_PREFIX) {
+            Some(i) if i.starts_with(' ') => i.trim(),
+            _ => continue,
+        };
+        let indentation = get_column(line, line.len() - line.trim_start().len());
+        let error = |message: &str| ParseError::new(file, i + 1, indentation, message.to_string());
+        if indentation != 0 {
+            return Err(error(
+                "Variables can only be defined at the top level of a file, like \
+                \"@define name: value\".",
+            ));
+        }
+        let (name, value) = definition.split_once(':').ok_or_else(|| {
+            error("Variable definitions must look like \"@define name: value\".")
+        })?;
+        let name = name.trim();
+        if !is_variable_name(name) {
+            return Err(error(&format!(
+                "\"{}\" is not a valid variable name; use letters, digits and underscores, \
+                starting with a letter or underscore.",
+                name
+            )));
+        }
+        let value = resolve_variables(value.trim(), variables, file, i + 1)?;
+        match variables.get(name) {
+            Some(existing) if existing != &value => {
+                return Err(error(&format!(
+                    "Variable \"{}\" was already defined with a different value: \"{}\".",
+                    name, existing
+                )))
+            }
+            _ => {
+                variables.insert(name.to_string(), value);
+            }
+        }
+        line.clear();
+    }
+    Ok(())
+}
+
+/// Replace variables ("$name") in a line of an .ez file by their values. Variables are defined
+/// with "@define", see [collect_variables]. A "$name" that is not a defined variable is left alone
+/// (e.g. a price in the text of a label), unless it is the whole value of a property, in which
+/// case this returns an error as it is most likely a typo. Write "$$name" for a literal "$name".
+/// Comment lines and widget definitions are left alone.
+pub fn resolve_variables(
+    line: &str,
+    variables: &EzVariables,
+    file: &str,
+    line_number: usize,
+) -> Result<String, ParseError> {
+    if !line.contains('$') || line.trim().starts_with("//") || line.trim().starts_with('-') {
+        return Ok(line.to_string());
+    }
+    if let Some((_, value)) = line.split_once(':') {
+        if let Some(name) = value.trim().strip_prefix('$') {
+            if is_variable_name(name) && !variables.contains_key(name) {
+                return Err(ParseError::new(
+                    file,
+                    line_number,
+                    get_column(line, line.rfind('$').unwrap()),
+                    format!(
+                        "Variable \"{}\" is not defined. Define it at the top level of an .ez \
+                        file, like \"@define {}: value\", or write \"$${}\" for a literal \"${}\".",
+                        name, name, name, name
+                    ),
+                ));
+            }
+        }
+    }
+    let mut result = String::new();
+    let mut rest = line;
+    while let Some(start) = rest.find('$') {
+        result.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        if let Some(escaped) = after.strip_prefix('$') {
+            if escaped.starts_with(is_variable_start) {
+                result.push('$');
+                rest = escaped;
+                continue;
+            }
+        }
+        let length = after
+            .find(|x: char| !(x.is_ascii_alphanumeric() || x == '_'))
+            .unwrap_or(after.len());
+        let name = &after[..length];
+        match variables.get(name) {
+            Some(value) if is_variable_name(name) => result.push_str(value),
+            _ => {
+                result.push('$');
+                result.push_str(name);
+            }
+        }
+        rest = &after[length..];
+    }
+    result.push_str(rest);
+    Ok(result)
+}
+
+/// Get the column (starting from 0) of a byte index in a line, counting characters.
+fn get_column(line: &str, index: usize) -> usize {
+    line[..index].chars().count()
+}
+
+/// Check whether a name can be used as a variable name: letters, digits and underscores, starting
+/// with a letter or underscore.
+fn is_variable_name(name: &str) -> bool {
+    name.starts_with(is_variable_start)
+        && name.chars().all(|x| x.is_ascii_alphanumeric() || x == '_')
+}
+
+fn is_variable_start(character: char) -> bool {
+    character.is_ascii_alphabetic() || character == '_'
+}
+
 /// Parse a single indentation level of a config file. Returns a Vec of config lines, a Vec
 /// of [EzWidgetDefinition] of widgets found on that level, and a Vec of [EzWidgetDefinition] of
-/// templates found on that level
+/// templates found on that level. Returns an error for the first mistake found on the level.
 pub fn parse_level(
     config_lines: Vec<String>,
     indentation_offset: usize,
     line_offset: usize,
     file: String,
-) -> Result<(Vec<String>, Vec<EzWidgetDefinition>, Templates), Error> {
+) -> Result<(Vec<ConfigLine>, Vec<EzWidgetDefinition>, Templates), ParseError> {
     // All lines before the first widget definition are considered config lines for the widget
     // on this indentation level
     let mut config = Vec::new();
     let mut parsing_config = true;
     let mut parsing_template: Option<String> = None;
     // All top level widgets on this indentation level
-    let mut level = Vec::new();
-    let mut templates = HashMap::new();
+    let mut level: Vec<EzWidgetDefinition> = Vec::new();
+    let mut templates: Templates = HashMap::new();
 
-    for (i, line) in config_lines.clone().into_iter().enumerate() {
-        // Skip empty lines and comments
-        // find all widget definitions, they start with -
-        if line.trim().starts_with("//") || line.trim().is_empty() {
-            continue;
+    for (i, line) in config_lines.iter().enumerate() {
+        let line_number = i + line_offset + 1;
+        let error = |column: usize, message: String| {
+            ParseError::new(&file, line_number, indentation_offset + column, message)
+        };
+        let content_line = if line.trim().starts_with("//") || line.trim().is_empty() {
+            // Skip empty lines and comments. In the content of a widget they are kept (empty),
+            // so the line numbers of the content stay correct.
+            if parsing_config {
+                continue;
+            }
+            String::new()
         } else {
-            for (j, char) in line.graphemes(true).enumerate() {
-                if char != " " {
-                    if parsing_config && j != 0 {
-                        panic!(
-                            "Error at line {0} in file {1}:\n \"{2}\".\n Invalid indentation \
-                        between lines \
-                        {3} and {0}. Indentation level of line {0} should be {4} but it is {5}.",
-                            i + line_offset + 1,
-                            file,
-                            line,
-                            i + line_offset,
-                            indentation_offset,
-                            indentation_offset + j
-                        );
-                    }
-                    if j % 4 != 0 {
-                        panic!(
-                            "Error at line {} in file {}:\n\
-                        \"{}\"\n. Invalid indentation. indentation must be \
-                            in multiples of four.",
-                            i + 1 + line_offset,
-                            file,
-                            line
-                        );
-                    }
-                    if !parsing_config && !line.starts_with('-') && j < 4 {
-                        panic!(
-                            "Error at Line {0} in file {1}:\n \"{2}\".\n This line must be \
-                         indented. Try this:\n{3}{4}\n{5}{2}",
-                            i + 1 + line_offset,
-                            file,
-                            line,
-                            " ".repeat(indentation_offset),
-                            config_lines[i - 1],
-                            " ".repeat(indentation_offset + 4)
-                        );
-                    }
-                    break;
-                }
+            let indentation = line.graphemes(true).take_while(|x| *x == " ").count();
+            if parsing_config && indentation != 0 {
+                return Err(error(
+                    indentation,
+                    format!(
+                        "Invalid indentation between lines {} and {}. Indentation level of line \
+                        {} should be {} but it is {}.",
+                        line_number - 1,
+                        line_number,
+                        line_number,
+                        indentation_offset,
+                        indentation_offset + indentation
+                    ),
+                ));
             }
-        }
-        // Find widget definitions which starts with -
-        if line.starts_with('-') {
-            // We encountered a widget, so config section of this level is over.
-            parsing_config = false;
-            // A new widget definition. Get it's type and ID
-            let type_name = line
-                .strip_prefix('-')
-                .unwrap()
-                .trim()
-                .strip_suffix(':')
-                .unwrap_or_else(|| {
-                    panic!(
-                        "Error at line {}: \"{}\". Widget definition should be \
-                followed by a \":\"",
-                        i + line_offset + 1,
+            if indentation % 4 != 0 {
+                return Err(error(
+                    indentation,
+                    "Invalid indentation. Indentation must be in multiples of four.".to_string(),
+                ));
+            }
+            if !parsing_config && !line.starts_with('-') && indentation < 4 {
+                return Err(error(
+                    indentation,
+                    format!(
+                        "This line must be indented. Try this:\n{}{}\n{}{}",
+                        " ".repeat(indentation_offset),
+                        config_lines[i - 1],
+                        " ".repeat(indentation_offset + 4),
                         line
-                    )
-                })
-                .to_string();
-
-            if type_name.starts_with('<') {
-                // This is a template
-                let (type_name, proto_type) = type_name.strip_prefix('<').unwrap()
-                    .strip_suffix('>').unwrap_or_else(
-                        || panic!("Error at line {} in file {}: \"{}\". Expected '>' to close layout template.",
-                            i + line_offset + 1, file, line)).split_once('@').unwrap_or_else(
-                    || panic!("Error at line {} in file {}: \"{}\". Expected '@' to separate template name from \
-                    type name.", i + line_offset + 1, file, line));
-                let def = EzWidgetDefinition::new(
-                    proto_type.to_string(),
-                    file.clone(),
-                    indentation_offset + 4,
-                    i + 1 + line_offset,
-                );
-                templates.insert(type_name.to_string(), def);
-                parsing_template = Some(type_name.to_string());
-            } else {
-                // This is a regular widget definition
-                // Add to level, all next lines that are not widget definitions append to this widget
-                level.push(EzWidgetDefinition::new(
-                    type_name.to_string(),
-                    file.clone(),
-                    indentation_offset + 4,
-                    i + 1 + line_offset,
+                    ),
                 ));
-                parsing_template = None;
             }
-        } else if parsing_config {
-            config.push(line);
-        } else {
-            // Line was not a new widget definition, so it must be config/content of the current one
-            let new_line = line.strip_prefix("    ").unwrap_or_else(|| {
-                panic!(
-                    "Error at line {} in file {}: \"{}\". Could not strip indentation.",
-                    i + line_offset + 1,
-                    file,
-                    line
-                )
-            });
-            if let Some(name) = &parsing_template {
-                templates
-                    .get_mut(name)
+
+            // Find widget definitions which starts with -
+            if line.starts_with('-') {
+                // We encountered a widget, so config section of this level is over.
+                parsing_config = false;
+                // A new widget definition. Get it's type and ID
+                let type_name = line
+                    .strip_prefix('-')
                     .unwrap()
-                    .content
-                    .push(new_line.to_string());
-            } else {
-                level.last_mut().unwrap().content.push(new_line.to_string());
+                    .trim()
+                    .strip_suffix(':')
+                    .ok_or_else(|| {
+                        error(
+                            line.trim_end().chars().count(),
+                            "Widget definition should be followed by a \":\"".to_string(),
+                        )
+                    })?
+                    .to_string();
+
+                if let Some(template) = type_name.strip_prefix('<') {
+                    // This is a template
+                    let name_column = line.find('<').unwrap() + 1;
+                    let (type_name, proto_type) = template
+                        .strip_suffix('>')
+                        .ok_or_else(|| {
+                            error(
+                                line.trim_end().chars().count() - 1,
+                                "Expected '>' to close template.".to_string(),
+                            )
+                        })?
+                        .split_once('@')
+                        .ok_or_else(|| {
+                            error(
+                                name_column,
+                                "Expected '@' to separate template name from type name."
+                                    .to_string(),
+                            )
+                        })?;
+                    let def = EzWidgetDefinition::new(
+                        proto_type.to_string(),
+                        file.clone(),
+                        indentation_offset + 4,
+                        line_number,
+                    );
+                    templates.insert(type_name.to_string(), def);
+                    parsing_template = Some(type_name.to_string());
+                } else {
+                    // This is a regular widget definition
+                    // Add to level, all next lines that are not widget definitions append to this
+                    // widget
+                    level.push(EzWidgetDefinition::new(
+                        type_name,
+                        file.clone(),
+                        indentation_offset + 4,
+                        line_number,
+                    ));
+                    parsing_template = None;
+                }
+                continue;
             }
+            if parsing_config {
+                config.push(ConfigLine::new(
+                    line.clone(),
+                    &file,
+                    line_number,
+                    indentation_offset,
+                ));
+                continue;
+            }
+            // Line was not a new widget definition, so it must be config/content of the current
+            // one
+            line.strip_prefix("    ")
+                .ok_or_else(|| error(0, "Could not strip indentation.".to_string()))?
+                .to_string()
+        };
+        if let Some(name) = &parsing_template {
+            templates.get_mut(name).unwrap().content.push(content_line);
+        } else {
+            level.last_mut().unwrap().content.push(content_line);
         }
     }
     Ok((config, level, templates))
@@ -4,12 +4,14 @@
 //! loading 'black' to a Crossterm Color::Black object.
 use std::io::{Error, ErrorKind};
 use std::str::FromStr;
+use std::time::Duration;
 
 use crossterm::style::Color;
+use qrcodegen::QrCodeEcc;
 
 use crate::states::definitions::{
-    HorizontalAlignment, HorizontalPosHint, LayoutMode, LayoutOrientation, VerticalAlignment,
-    VerticalPosHint,
+    Anchor, CursorStyle, DockPosition, FillerPattern, HorizontalAlignment, HorizontalPosHint,
+    LayoutMode, LayoutOrientation, SelectionStyle, VerticalAlignment, VerticalPosHint,
 };
 
 pub fn parse_color_property(value: &str) -> Result<Color, Error> {
@@ -165,10 +167,15 @@ pub fn parse_horizontal_pos_hint_property(value: &str) -> Result<HorizontalPosHi
     let (keyword, fraction);
     if to_parse.contains(':') {
         let (keyword_str, fraction_str) = to_parse.split_once(':').unwrap();
-        fraction = fraction_str
-            .trim()
-            .parse()
-            .unwrap_or_else(|_| panic!("Could not parse pos hint: {}", value));
+        fraction = match fraction_str.trim().parse() {
+            Ok(i) => i,
+            Err(_) => {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("Could not parse pos hint: {}", value),
+                ))
+            }
+        };
         keyword = keyword_str.trim();
     } else {
         keyword = value.trim();
@@ -246,12 +253,13 @@ pub fn parse_layout_mode_property(value: &str) -> Result<LayoutMode, Error> {
         "float" => Ok(LayoutMode::Float),
         "screen" => Ok(LayoutMode::Screen),
         "tab" => Ok(LayoutMode::Tab),
+        "dock" => Ok(LayoutMode::Dock),
         _ => {
             return Err(Error::new(
                 ErrorKind::InvalidData,
                 format!(
                     "Layout mode property must be box, stack, table,\
-                          float, screen or tab. Not : {}",
+                          float, screen, tab or dock. Not : {}",
                     value
                 ),
             ))
@@ -259,6 +267,169 @@ pub fn parse_layout_mode_property(value: &str) -> Result<LayoutMode, Error> {
     }
 }
 
+/// Convenience function use by children of dock layouts to load a dock property defined in a .ez
+/// file. Looks like: "left"
+pub fn parse_dock_position_property(value: &str) -> Result<DockPosition, Error> {
+    match value.trim() {
+        "left" => Ok(DockPosition::Left),
+        "right" => Ok(DockPosition::Right),
+        "top" => Ok(DockPosition::Top),
+        "bottom" => Ok(DockPosition::Bottom),
+        "center" => Ok(DockPosition::Center),
+        "float" => Ok(DockPosition::Float),
+        _ => Err(Error::new(
+            ErrorKind::InvalidData,
+            format!(
+                "Dock property must be left, right, top, bottom, center or float, not: {}",
+                value
+            ),
+        )),
+    }
+}
+
+/// Convenience function used by widgets to load a selection style property defined in a .ez file.
+/// Looks like: "underline"
+pub fn parse_selection_style_property(value: &str) -> Result<SelectionStyle, Error> {
+    match value.trim() {
+        "colors" => Ok(SelectionStyle::Colors),
+        "invert" => Ok(SelectionStyle::Invert),
+        "underline" => Ok(SelectionStyle::Underline),
+        "marker" => Ok(SelectionStyle::Marker),
+        "border" => Ok(SelectionStyle::Border),
+        _ => Err(Error::new(
+            ErrorKind::InvalidData,
+            format!(
+                "Selection style must be colors, invert, underline, marker or border, not: {}",
+                value
+            ),
+        )),
+    }
+}
+
+/// Convenience function used by text inputs to load a cursor style property defined in a .ez file.
+/// Looks like: "underline"
+pub fn parse_cursor_style_property(value: &str) -> Result<CursorStyle, Error> {
+    match value.trim() {
+        "block" => Ok(CursorStyle::Block),
+        "underline" => Ok(CursorStyle::Underline),
+        "bar" => Ok(CursorStyle::Bar),
+        _ => Err(Error::new(
+            ErrorKind::InvalidData,
+            format!("Cursor style must be block, underline or bar, not: {}", value),
+        )),
+    }
+}
+
+/// Convenience function used by layouts to load a filler pattern property defined in a .ez file.
+/// Looks like: "checkerboard"
+pub fn parse_filler_pattern_property(value: &str) -> Result<FillerPattern, Error> {
+    match value.trim() {
+        "solid" => Ok(FillerPattern::Solid),
+        "checkerboard" => Ok(FillerPattern::Checkerboard),
+        "horizontal_stripes" => Ok(FillerPattern::HorizontalStripes),
+        "vertical_stripes" => Ok(FillerPattern::VerticalStripes),
+        "horizontal_gradient" => Ok(FillerPattern::HorizontalGradient),
+        "vertical_gradient" => Ok(FillerPattern::VerticalGradient),
+        _ => Err(Error::new(
+            ErrorKind::InvalidData,
+            format!(
+                "Filler pattern must be solid, checkerboard, horizontal_stripes, \
+                vertical_stripes, horizontal_gradient or vertical_gradient, not: {}",
+                value
+            ),
+        )),
+    }
+}
+
+/// Convenience function used by widgets in a float layout to load an anchor property defined in a
+/// .ez file. Looks like: "top_right"
+pub fn parse_anchor_property(value: &str) -> Result<Anchor, Error> {
+    match value.trim() {
+        "none" => Ok(Anchor::None),
+        "top_left" => Ok(Anchor::TopLeft),
+        "top" => Ok(Anchor::Top),
+        "top_right" => Ok(Anchor::TopRight),
+        "left" => Ok(Anchor::Left),
+        "center" => Ok(Anchor::Center),
+        "right" => Ok(Anchor::Right),
+        "bottom_left" => Ok(Anchor::BottomLeft),
+        "bottom" => Ok(Anchor::Bottom),
+        "bottom_right" => Ok(Anchor::BottomRight),
+        _ => Err(Error::new(
+            ErrorKind::InvalidData,
+            format!(
+                "Anchor must be none, top_left, top, top_right, left, center, right, \
+                bottom_left, bottom or bottom_right, not: {}",
+                value
+            ),
+        )),
+    }
+}
+
+/// Convenience function used by QR codes to load an error correction property defined in a .ez
+/// file. Looks like: "quartile"
+pub fn parse_error_correction_property(value: &str) -> Result<QrCodeEcc, Error> {
+    match value.trim() {
+        "low" => Ok(QrCodeEcc::Low),
+        "medium" => Ok(QrCodeEcc::Medium),
+        "quartile" => Ok(QrCodeEcc::Quartile),
+        "high" => Ok(QrCodeEcc::High),
+        _ => Err(Error::new(
+            ErrorKind::InvalidData,
+            format!(
+                "Error correction must be low, medium, quartile or high, not: {}",
+                value
+            ),
+        )),
+    }
+}
+
+/// Convenience function to load a duration defined in a .ez file. Looks like: "500ms", "5s" or
+/// "1.5m".
+pub fn parse_duration(value: &str) -> Result<Duration, Error> {
+    let value = value.trim();
+    let (number, multiplier) = if let Some(i) = value.strip_suffix("ms") {
+        (i, 0.001)
+    } else if let Some(i) = value.strip_suffix('s') {
+        (i, 1.0)
+    } else if let Some(i) = value.strip_suffix('m') {
+        (i, 60.0)
+    } else if let Some(i) = value.strip_suffix('h') {
+        (i, 3600.0)
+    } else {
+        (value, 0.0)
+    };
+    match number.trim().parse::<f64>() {
+        Ok(i) if multiplier > 0.0 && i > 0.0 => Ok(Duration::from_secs_f64(i * multiplier)),
+        _ => Err(Error::new(
+            ErrorKind::InvalidData,
+            format!(
+                "Duration must be a positive number followed by ms, s, m or h (e.g. \"5s\"), \
+                not: {}",
+                value
+            ),
+        )),
+    }
+}
+
+/// Convenience function to load a refresh_every property defined in a .ez file. Looks like:
+/// "5s -> my_refresh_task"
+pub fn parse_refresh_every_property(value: &str) -> Result<(Duration, String), Error> {
+    match value.split_once("->") {
+        Some((interval, task)) if !task.trim().is_empty() => {
+            Ok((parse_duration(interval)?, task.trim().to_string()))
+        }
+        _ => Err(Error::new(
+            ErrorKind::InvalidData,
+            format!(
+                "refresh_every must be an interval and a task name, e.g. \
+                \"5s -> my_refresh_task\", not: {}",
+                value
+            ),
+        )),
+    }
+}
+
 /// Convenience function use by layouts to load an orientation property defined in a .ez file.
 /// Looks like: "horizontal"
 pub fn parse_layout_orientation_property(value: &str) -> Result<LayoutOrientation, Error> {
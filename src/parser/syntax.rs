@@ -0,0 +1,425 @@
+//! # Syntax
+//!
+//! Module exposing the structure of .ez files for external tools such as formatters, language
+//! servers and GUI designers. [parse_ez] turns the text of an .ez file into an [EzSyntaxTree] of
+//! variables, widget definitions, templates and properties, each with the span it occupies in the
+//! source.
+//!
+//! Unlike [load_ui], which panics on the first mistake, parsing never fails: mistakes are
+//! collected in [EzSyntaxTree.errors] and the rest of the file is still parsed, which is what an
+//! editor needs while the user is typing. Property values are kept as written; they are not
+//! interpreted (see [validate_ez] for checking values).
+
+/// A range in the source of an .ez file. Lines start at 1 (like in error messages of the parser),
+/// columns start at 0 and count characters. The end column is exclusive.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub struct EzSpan {
+    pub start_line: usize,
+    pub start_column: usize,
+    pub end_line: usize,
+    pub end_column: usize,
+}
+impl EzSpan {
+    pub fn new(start_line: usize, start_column: usize, end_line: usize, end_column: usize) -> Self {
+        EzSpan {
+            start_line,
+            start_column,
+            end_line,
+            end_column,
+        }
+    }
+
+    /// Check whether a position falls inside this span.
+    pub fn contains(&self, line: usize, column: usize) -> bool {
+        (line > self.start_line || (line == self.start_line && column >= self.start_column))
+            && (line < self.end_line || (line == self.end_line && column < self.end_column))
+    }
+}
+
+/// A mistake found while parsing an .ez file.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct EzSyntaxError {
+    /// Where the mistake is
+    pub span: EzSpan,
+
+    /// Human readable description of the mistake
+    pub message: String,
+
+    /// Whether [format_ez] can fix the mistake, e.g. indentation that is not four spaces per level
+    pub fixable: bool,
+}
+impl EzSyntaxError {
+    pub fn new(span: EzSpan, message: &str, fixable: bool) -> Self {
+        EzSyntaxError {
+            span,
+            message: message.to_string(),
+            fixable,
+        }
+    }
+}
+use crate::parser::parse_lang::DEFINE_PREFIX;
+
+/// A "name: value" line of a widget or template definition, or of a variable definition
+/// ("@define name: value").
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+pub struct EzPropertyNode {
+    /// Name of the property, e.g. "height"
+    pub name: String,
+
+    /// Value of the property with surrounding whitespace removed, e.g. "parent.height - 2"
+    pub value: String,
+
+    /// Everything after the ":", exactly as written. Some properties (such as "text") keep
+    /// whitespace that [value] removes
+    pub raw_value: String,
+
+    /// Comment lines directly above the property, without the leading "//"
+    pub comments: Vec<String>,
+
+    /// Span of the whole line, without indentation
+    pub span: EzSpan,
+
+    /// Span of the name
+    pub name_span: EzSpan,
+
+    /// Span of [raw_value]
+    pub value_span: EzSpan,
+}
+
+/// A widget definition ("- Label:") or template definition ("- <MyLabel@Label>:") along with its'
+/// properties and child widgets.
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+pub struct EzWidgetNode {
+    /// Type of the widget, e.g. "Label", or the name of a template it is based on. For template
+    /// definitions this is the type the template is based on
+    pub type_name: String,
+
+    /// Name of the template if this is a template definition, e.g. "MyLabel" for
+    /// "- <MyLabel@Label>:"
+    pub template_name: Option<String>,
+
+    /// Properties in the order they were written
+    pub properties: Vec<EzPropertyNode>,
+
+    /// Child widgets in the order they were written
+    pub children: Vec<EzWidgetNode>,
+
+    /// Comment lines directly above the definition, without the leading "//"
+    pub comments: Vec<String>,
+
+    /// Span of the definition line ("- Label:"), without indentation
+    pub header_span: EzSpan,
+
+    /// Span from the definition line up to and including the last line of its' content
+    pub span: EzSpan,
+}
+impl EzWidgetNode {
+    /// Get a property by name. If the property was written multiple times the last one is
+    /// returned, as that is the one that is used.
+    pub fn get_property(&self, name: &str) -> Option<&EzPropertyNode> {
+        self.properties.iter().rev().find(|x| x.name == name)
+    }
+
+    /// Get the ID of the widget, if it has one.
+    pub fn get_id(&self) -> Option<&str> {
+        self.get_property("id").map(|x| x.value.as_str())
+    }
+
+    /// Check whether this is a template definition rather than a widget.
+    pub fn is_template(&self) -> bool {
+        self.template_name.is_some()
+    }
+}
+
+/// The structure of an .ez file, as returned by [parse_ez].
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+pub struct EzSyntaxTree {
+    /// Widgets defined at the top level of the file. A UI has a single root layout, but it may be
+    /// defined in any of the .ez files, so a file can contain no widgets or just templates
+    pub widgets: Vec<EzWidgetNode>,
+
+    /// Templates defined at the top level of the file
+    pub templates: Vec<EzWidgetNode>,
+
+    /// Variables defined at the top level of the file ("@define name: value"), in the order they
+    /// were written. The span of a variable covers the whole line, including "@define"
+    pub variables: Vec<EzPropertyNode>,
+
+    /// Comment lines after the last definition, without the leading "//"
+    pub trailing_comments: Vec<String>,
+
+    /// Mistakes found while parsing
+    pub errors: Vec<EzSyntaxError>,
+}
+impl EzSyntaxTree {
+    /// Get the innermost widget or template definition at a position, e.g. the position of the
+    /// cursor in an editor. Returns the path of child indexes from a top level definition along
+    /// with the definition itself; the first index refers to [widgets] or [templates] depending on
+    /// [EzWidgetNode.is_template].
+    pub fn get_node_at(&self, line: usize, column: usize) -> Option<(Vec<usize>, &EzWidgetNode)> {
+        for top_level in [&self.widgets, &self.templates] {
+            for (i, node) in top_level.iter().enumerate() {
+                if !node.span.contains(line, column) {
+                    continue;
+                }
+                let (mut path, mut node) = (vec![i], node);
+                while let Some((j, child)) = node
+                    .children
+                    .iter()
+                    .enumerate()
+                    .find(|(_, x)| x.span.contains(line, column))
+                {
+                    path.push(j);
+                    node = child;
+                }
+                return Some((path, node));
+            }
+        }
+        None
+    }
+}
+
+/// Parse the text of an .ez file into an [EzSyntaxTree]. Never fails; mistakes such as invalid
+/// indentation are collected in [EzSyntaxTree.errors] and parsing continues on the next line.
+/// Environment variables (${NAME}) are not interpolated so that tools see the file as written.
+///
+/// # Example:
+///
+/// ```
+/// use ez_term::*;
+///
+/// let tree = parse_ez("- Layout:\n    mode: box\n    - Label:\n        id: my_label\n");
+/// let label = &tree.widgets[0].children[0];
+/// assert_eq!(label.get_id(), Some("my_label"));
+/// assert_eq!(label.header_span.start_line, 3);
+/// assert!(tree.errors.is_empty());
+/// ```
+pub fn parse_ez(source: &str) -> EzSyntaxTree {
+    let mut tree = EzSyntaxTree::default();
+    // Definitions that can still receive content, from the top level down, along with their
+    // indentation
+    let mut open: Vec<(usize, EzWidgetNode)> = Vec::new();
+    let mut comments = Vec::new();
+
+    for (i, line) in source.lines().enumerate() {
+        let line_number = i + 1;
+        let content = line.trim();
+        let length = line.chars().count();
+        if content.is_empty() {
+            continue;
+        }
+        let indentation = line.chars().take_while(|x| *x == ' ').count();
+        let content_span = EzSpan::new(line_number, indentation, line_number, length);
+        if let Some(comment) = content.strip_prefix("//") {
+            comments.push(comment.to_string());
+            continue;
+        }
+        if line.chars().nth(indentation) == Some('\t') {
+            tree.errors.push(EzSyntaxError::new(
+                content_span,
+                "Indentation must use spaces, not tabs",
+                false,
+            ));
+            continue;
+        }
+
+        // A line closes all definitions that are indented as far or further
+        while open.last().map_or(false, |x| x.0 >= indentation) {
+            close_node(&mut open, &mut tree);
+        }
+        let expected = open.last().map_or(0, |x| x.0 + 4);
+        if indentation != expected && (!open.is_empty() || content.starts_with('-')) {
+            tree.errors.push(EzSyntaxError::new(
+                content_span,
+                &format!(
+                    "Invalid indentation. Indentation of this line should be {}",
+                    expected
+                ),
+                true,
+            ));
+        }
+
+        let define = content
+            .strip_prefix(DEFINE_PREFIX)
+            .filter(|x| x.starts_with(' '));
+        if let Some(definition) = define {
+            if indentation != 0 {
+                tree.errors.push(EzSyntaxError::new(
+                    content_span,
+                    "Variables can only be defined at the top level of a file",
+                    false,
+                ));
+                comments.clear();
+                continue;
+            }
+            while !open.is_empty() {
+                close_node(&mut open, &mut tree);
+            }
+            let name_start = length - definition.trim_start().chars().count();
+            let mut variable = parse_property(line, line_number, name_start, &mut tree.errors);
+            variable.span = content_span;
+            variable.comments = std::mem::take(&mut comments);
+            tree.variables.push(variable);
+            continue;
+        }
+
+        if content.starts_with('-') {
+            let node = parse_header(content, content_span, &mut tree.errors);
+            let mut node = EzWidgetNode {
+                comments: std::mem::take(&mut comments),
+                ..node
+            };
+            node.span = content_span;
+            if node.is_template() && !open.is_empty() {
+                tree.errors.push(EzSyntaxError::new(
+                    content_span,
+                    "Templates can only be defined at the top level of a file; this template is \
+                    ignored",
+                    false,
+                ));
+            }
+            open.push((indentation, node));
+        } else {
+            if open.is_empty() {
+                tree.errors.push(EzSyntaxError::new(
+                    content_span,
+                    "Property does not belong to a widget definition",
+                    false,
+                ));
+                comments.clear();
+                continue;
+            }
+            let property = parse_property(line, line_number, indentation, &mut tree.errors);
+            let owner = &mut open.last_mut().unwrap().1;
+            if !owner.children.is_empty() {
+                tree.errors.push(EzSyntaxError::new(
+                    content_span,
+                    "Properties must be defined before the child widgets",
+                    false,
+                ));
+            }
+            owner.properties.push(EzPropertyNode {
+                comments: std::mem::take(&mut comments),
+                ..property
+            });
+        }
+        // The line belongs to all definitions that are still open
+        for (_, node) in open.iter_mut() {
+            node.span.end_line = line_number;
+            node.span.end_column = length;
+        }
+    }
+    while !open.is_empty() {
+        close_node(&mut open, &mut tree);
+    }
+    tree.trailing_comments = comments;
+    tree
+}
+
+/// Close the innermost open definition, adding it to its' parent or to the top level.
+fn close_node(open: &mut Vec<(usize, EzWidgetNode)>, tree: &mut EzSyntaxTree) {
+    let (_, node) = open.pop().unwrap();
+    match open.last_mut() {
+        Some((_, parent)) => parent.children.push(node),
+        None if node.is_template() => tree.templates.push(node),
+        None => tree.widgets.push(node),
+    }
+}
+
+/// Parse a definition line such as "- Label:" or "- <MyLabel@Label>:".
+fn parse_header(content: &str, span: EzSpan, errors: &mut Vec<EzSyntaxError>) -> EzWidgetNode {
+    let mut node = EzWidgetNode {
+        header_span: span,
+        ..Default::default()
+    };
+    let definition = content.strip_prefix('-').unwrap().trim();
+    let definition = match definition.strip_suffix(':') {
+        Some(i) => i.trim(),
+        None => {
+            errors.push(EzSyntaxError::new(
+                span,
+                "Widget definition should be followed by a \":\"",
+                false,
+            ));
+            definition
+        }
+    };
+    if let Some(template) = definition.strip_prefix('<') {
+        let template = template.strip_suffix('>').unwrap_or_else(|| {
+            errors.push(EzSyntaxError::new(
+                span,
+                "Expected '>' to close template definition",
+                false,
+            ));
+            template
+        });
+        match template.split_once('@') {
+            Some((name, type_name)) => {
+                node.template_name = Some(name.trim().to_string());
+                node.type_name = type_name.trim().to_string();
+            }
+            None => {
+                errors.push(EzSyntaxError::new(
+                    span,
+                    "Expected '@' to separate template name from type name",
+                    false,
+                ));
+                node.template_name = Some(template.trim().to_string());
+            }
+        }
+    } else {
+        node.type_name = definition.to_string();
+    }
+    if node.type_name.is_empty() {
+        errors.push(EzSyntaxError::new(
+            span,
+            "Widget definition is missing a widget type",
+            false,
+        ));
+    }
+    node
+}
+
+/// Parse a property line such as "height: 10".
+fn parse_property(
+    line: &str,
+    line_number: usize,
+    indentation: usize,
+    errors: &mut Vec<EzSyntaxError>,
+) -> EzPropertyNode {
+    let length = line.chars().count();
+    let span = EzSpan::new(line_number, indentation, line_number, length);
+    let content: String = line.chars().skip(indentation).collect();
+    let (name, raw_value) = match content.split_once(':') {
+        Some((name, value)) => (name.to_string(), value.to_string()),
+        None => {
+            errors.push(EzSyntaxError::new(
+                span,
+                "Property must contain a \":\", e.g. \"property: value\"",
+                false,
+            ));
+            (content.clone(), String::new())
+        }
+    };
+    let name_length = name.chars().count();
+    let name_trimmed = name.trim_end().chars().count();
+    EzPropertyNode {
+        name: name.trim().to_string(),
+        value: raw_value.trim().to_string(),
+        raw_value,
+        comments: Vec::new(),
+        span,
+        name_span: EzSpan::new(
+            line_number,
+            indentation,
+            line_number,
+            indentation + name_trimmed,
+        ),
+        value_span: EzSpan::new(
+            line_number,
+            (indentation + name_length + 1).min(length),
+            line_number,
+            length,
+        ),
+    }
+}
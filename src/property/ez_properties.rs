@@ -1,11 +1,13 @@
 //! A module implementing the [EzProperties] enum.
+use std::sync::mpsc::Receiver;
+
 use crossterm::style::Color;
 
 use crate::property::ez_property::EzProperty;
 use crate::property::ez_values::EzValues;
 use crate::states::definitions::{
-    HorizontalAlignment, HorizontalPosHint, LayoutMode, LayoutOrientation, VerticalAlignment,
-    VerticalPosHint,
+    DockPosition, HorizontalAlignment, HorizontalPosHint, LayoutMode, LayoutOrientation,
+    VerticalAlignment, VerticalPosHint,
 };
 
 /// An enum that contains all possible implementations of the generic [EzProperty].
@@ -44,6 +46,9 @@ pub enum EzProperties {
     /// [LayoutMode] EzProperty
     LayoutOrientation(EzProperty<LayoutOrientation>),
 
+    /// [DockPosition] EzProperty
+    DockPosition(EzProperty<DockPosition>),
+
     /// [VerticalAlignment] EzProperty
     VerticalAlignment(EzProperty<VerticalAlignment>),
 
@@ -58,6 +63,9 @@ pub enum EzProperties {
 
     /// [SizeHint] EzProperty
     SizeHint(EzProperty<Option<f64>>),
+
+    /// List of strings EzProperty
+    StringList(EzProperty<Vec<String>>),
 }
 impl EzProperties {
     pub fn get_generic_value(&self) -> EzValues {
@@ -75,6 +83,8 @@ impl EzProperties {
             EzValues::LayoutMode(i.value.clone())
         } else if let EzProperties::LayoutOrientation(i) = self {
             EzValues::LayoutOrientation(i.value.clone())
+        } else if let EzProperties::DockPosition(i) = self {
+            EzValues::DockPosition(i.value)
         } else if let EzProperties::VerticalAlignment(i) = self {
             EzValues::VerticalAlignment(i.value)
         } else if let EzProperties::HorizontalAlignment(i) = self {
@@ -85,11 +95,54 @@ impl EzProperties {
             EzValues::VerticalPosHint(i.value)
         } else if let EzProperties::SizeHint(i) = self {
             EzValues::SizeHint(i.value)
+        } else if let EzProperties::StringList(i) = self {
+            EzValues::StringList(i.value.clone())
         } else {
             panic!("Unimplemented variant")
         }
     }
 
+    /// Set the value of the property from an [EzValues] of the same type. Returns true if the
+    /// value changed. Panics if the value is of a different type than the property.
+    pub fn set_from_ez_value(&mut self, value: EzValues) -> bool {
+        match self {
+            EzProperties::Usize(i) => i.set_from_ez_value(value),
+            EzProperties::F64(i) => i.set_from_ez_value(value),
+            EzProperties::Bool(i) => i.set_from_ez_value(value),
+            EzProperties::String(i) => i.set_from_ez_value(value),
+            EzProperties::Color(i) => i.set_from_ez_value(value),
+            EzProperties::LayoutMode(i) => i.set_from_ez_value(value),
+            EzProperties::LayoutOrientation(i) => i.set_from_ez_value(value),
+            EzProperties::DockPosition(i) => i.set_from_ez_value(value),
+            EzProperties::VerticalAlignment(i) => i.set_from_ez_value(value),
+            EzProperties::HorizontalAlignment(i) => i.set_from_ez_value(value),
+            EzProperties::VerticalPosHint(i) => i.set_from_ez_value(value),
+            EzProperties::HorizontalPosHint(i) => i.set_from_ez_value(value),
+            EzProperties::SizeHint(i) => i.set_from_ez_value(value),
+            EzProperties::StringList(i) => i.set_from_ez_value(value),
+        }
+    }
+
+    /// Create the channel of the wrapped property, see [EzProperty.create_channel].
+    pub fn create_channel(&self) -> Option<Receiver<EzValues>> {
+        match self {
+            EzProperties::Usize(i) => i.create_channel(),
+            EzProperties::F64(i) => i.create_channel(),
+            EzProperties::Bool(i) => i.create_channel(),
+            EzProperties::String(i) => i.create_channel(),
+            EzProperties::Color(i) => i.create_channel(),
+            EzProperties::LayoutMode(i) => i.create_channel(),
+            EzProperties::LayoutOrientation(i) => i.create_channel(),
+            EzProperties::DockPosition(i) => i.create_channel(),
+            EzProperties::VerticalAlignment(i) => i.create_channel(),
+            EzProperties::HorizontalAlignment(i) => i.create_channel(),
+            EzProperties::VerticalPosHint(i) => i.create_channel(),
+            EzProperties::HorizontalPosHint(i) => i.create_channel(),
+            EzProperties::SizeHint(i) => i.create_channel(),
+            EzProperties::StringList(i) => i.create_channel(),
+        }
+    }
+
     /// Get a [EzProperty<usize>] ref from this enum. You must be sure this is a usize property
     /// or it will panic.
     pub fn as_usize(&self) -> &EzProperty<usize> {
@@ -230,6 +283,26 @@ impl EzProperties {
         }
     }
 
+    /// Get a [EzProperty<DockPosition>] ref from this enum. You must be sure this is a
+    /// DockPosition property or it will panic.
+    pub fn as_dock_position(&self) -> &EzProperty<DockPosition> {
+        if let EzProperties::DockPosition(i) = self {
+            i
+        } else {
+            panic!("Wrong property, expected DockPosition")
+        }
+    }
+
+    /// Get a mutable ref [EzProperty<DockPosition>] from this enum. You must be sure this is a
+    /// DockPosition property or it will panic.
+    pub fn as_dock_position_mut(&mut self) -> &mut EzProperty<DockPosition> {
+        if let EzProperties::DockPosition(i) = self {
+            i
+        } else {
+            panic!("Wrong property, expected DockPosition")
+        }
+    }
+
     /// Get a mutable ref [EzProperty<VerticalAlignment>] from this enum. You must be sure this
     /// is a VerticalAlignment property or it will panic.
     pub fn as_vertical_alignment(&self) -> &EzProperty<VerticalAlignment> {
@@ -329,4 +402,24 @@ impl EzProperties {
             panic!("Wrong property, expected SizeHintProperty")
         }
     }
+
+    /// Get a [EzProperty<Vec<String>>] ref from this enum. You must be sure this is a StringList
+    /// property or it will panic.
+    pub fn as_string_list(&self) -> &EzProperty<Vec<String>> {
+        if let EzProperties::StringList(i) = self {
+            i
+        } else {
+            panic!("Wrong property, expected StringListProperty")
+        }
+    }
+
+    /// Get a mutable ref [EzProperty<Vec<String>>] from this enum. You must be sure this is a
+    /// StringList property or it will panic.
+    pub fn as_string_list_mut(&mut self) -> &mut EzProperty<Vec<String>> {
+        if let EzProperties::StringList(i) = self {
+            i
+        } else {
+            panic!("Wrong property, expected StringListProperty")
+        }
+    }
 }
@@ -1,9 +1,12 @@
 //! # Ez Property
 //!
 //! A module implementing the generic [EzProperty] struct.
+use std::cell::RefCell;
 use std::cmp::Ordering;
+use std::fmt::{Debug, Formatter};
 use std::ops::{Add, Sub};
 use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex};
 
 use crossterm::style::Color;
 
@@ -11,8 +14,8 @@ use crate::property::ez_values::EzValues;
 use crate::scheduler::definitions::GenericFunction;
 use crate::scheduler::scheduler::SchedulerFrontend;
 use crate::states::definitions::{
-    HorizontalAlignment, HorizontalPosHint, LayoutMode, LayoutOrientation, VerticalAlignment,
-    VerticalPosHint,
+    DockPosition, HorizontalAlignment, HorizontalPosHint, LayoutMode, LayoutOrientation,
+    VerticalAlignment, VerticalPosHint,
 };
 
 /// A struct wrapping a property of a widget state.
@@ -34,12 +37,37 @@ pub struct EzProperty<T> {
     /// height.
     pub locked: bool,
 
+    /// Validates or corrects every new value before it is set. Shared by all clones of this
+    /// property. See [set_validator].
+    validator: PropertyValidator<T>,
+
     /// Sender for the channel belonging to this property. When a new value is set, the new value
     /// will be send over this channel. At runtime the [Scheduler] will own the receiver of this
     /// channel; if any other properties are subscribed to this property, new values received by
-    /// the scheduler will be synced to the subscribers.
-    tx: Sender<EzValues>,
+    /// the scheduler will be synced to the subscribers. The channel is only created once the
+    /// property is bound (see [create_channel]) and is shared by all clones of this property.
+    tx: PropertySender,
+}
+/// Sender of the channel of an [EzProperty], None until the channel is created.
+type PropertySender = Arc<Mutex<Option<Sender<EzValues>>>>;
+
+/// Senders used by properties set in a thread started by the [Scheduler]. The first sends the
+/// name and new value of the property to the main thread, the second wakes up the main thread.
+pub(crate) type ThreadPropertySenders = (Sender<(String, EzValues)>, Sender<bool>);
+
+thread_local! {
+    /// Set for threads started by the [Scheduler]. Properties set in such a thread send their new
+    /// value to the main thread through these senders instead of their own channel, so only
+    /// properties that are actually set need to be synced.
+    static THREAD_PROPERTY_SENDERS: RefCell<Option<ThreadPropertySenders>> =
+        const { RefCell::new(None) };
+}
+
+/// Make all properties set in the current thread sync their new value to the main thread.
+pub(crate) fn set_thread_property_senders(senders: ThreadPropertySenders) {
+    THREAD_PROPERTY_SENDERS.with(|x| *x.borrow_mut() = Some(senders));
 }
+
 impl<T> EzProperty<T>
 where
     EzValues: From<T>,
@@ -47,14 +75,34 @@ where
     /// Create a new EzProperty. If this property belongs to a widget state the name must be a path
     /// to the widget state, followed by the property name.
     pub fn new(name: String, value: T) -> (Self, Receiver<EzValues>) {
-        let (tx, rx): (Sender<EzValues>, Receiver<EzValues>) = channel();
-        let property = EzProperty {
+        let property = EzProperty::new_lazy(name, value);
+        let rx = property.create_channel().unwrap();
+        (property, rx)
+    }
+
+    /// Create a new EzProperty without a channel. New values are not sent anywhere until
+    /// [create_channel] is called. The [Scheduler] creates properties this way and only creates
+    /// channels for properties that are bound, as most widget properties never are.
+    pub fn new_lazy(name: String, value: T) -> Self {
+        EzProperty {
             name,
             value,
-            tx,
+            tx: Arc::new(Mutex::new(None)),
             locked: false,
-        };
-        (property, rx)
+            validator: PropertyValidator::default(),
+        }
+    }
+
+    /// Create the channel of this property, through which new values are sent from now on.
+    /// Returns the receiver of the new channel, or None if the channel already existed.
+    pub fn create_channel(&self) -> Option<Receiver<EzValues>> {
+        let mut tx = self.tx.lock().unwrap();
+        if tx.is_some() {
+            return None;
+        }
+        let (sender, receiver) = channel();
+        *tx = Some(sender);
+        Some(receiver)
     }
 
     /// Get a ref to the name of this property
@@ -76,11 +124,29 @@ where
         if self.locked {
             return false;
         }
+        let new = match self.validator.validate(new) {
+            Some(i) => i,
+            None => return false,
+        };
         if new != self.value {
             self.value = new.clone();
-            self.tx
-                .send(EzValues::from(new))
-                .unwrap_or_else(|e| panic!("Error setting value \"{}\": {}.", self.name, e));
+            let sent_to_main = THREAD_PROPERTY_SENDERS.with(|x| match x.borrow().as_ref() {
+                Some((values, wake)) => {
+                    // The main thread may have exited already, in which case there is nothing
+                    // left to sync to.
+                    let _ = values.send((self.name.clone(), EzValues::from(new.clone())));
+                    let _ = wake.send(true);
+                    true
+                }
+                None => false,
+            });
+            if sent_to_main {
+                return true;
+            }
+            if let Some(tx) = self.tx.lock().unwrap().as_ref() {
+                tx.send(EzValues::from(new))
+                    .unwrap_or_else(|e| panic!("Error setting value \"{}\": {}.", self.name, e));
+            }
             true
         } else {
             false
@@ -95,11 +161,85 @@ where
         self.locked = other.locked;
     }
 
+    /// Set a validator that is applied to every new value of this property before it is set, so
+    /// subscribers never see an invalid value. The validator returns the value to set, which may
+    /// be corrected (e.g. clamped to a range), or None to reject the new value and keep the
+    /// current one. The validator is shared by all clones of this property, so it also applies
+    /// when the value is set from a thread or through a binding. The current value is validated
+    /// immediately.
+    ///
+    /// # Example:
+    ///
+    /// We'll keep the width of a label between 10 and 50, and only accept uppercase text:
+    /// ```
+    /// use ez_term::*;
+    ///
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// let state = state_tree.get_mut("my_label").as_label_mut();
+    /// state.get_size_mut().width.set_validator(|width| Some(width.clamp(10, 50)));
+    /// state.text.set_validator(|text: String| Some(text.to_uppercase()));
+    ///
+    /// run(root_widget, state_tree, scheduler, custom_data);
+    /// ```
+    pub fn set_validator<F>(&mut self, validator: F)
+    where
+        F: Fn(T) -> Option<T> + Send + 'static,
+        T: PartialEq + Clone,
+    {
+        *self.validator.inner.lock().unwrap() = Some(Box::new(validator));
+        let current = self.value.clone();
+        if let Some(new) = self.validator.validate(current) {
+            self.set(new);
+        }
+    }
+
+    /// Remove the validator of this property (and all its' clones), if any.
+    pub fn clear_validator(&mut self) {
+        *self.validator.inner.lock().unwrap() = None;
+    }
+
     /// Bind a custom callback to this property which will be called when the value changes
     pub fn bind(&self, callback: GenericFunction, scheduler: &mut SchedulerFrontend) {
         scheduler.bind_property_callback(self.name.as_str(), callback);
     }
 }
+/// Returns the value to set, or None to reject the value. See [EzProperty.set_validator].
+pub type ValidatorFunction<T> = Box<dyn Fn(T) -> Option<T> + Send>;
+
+/// Validator of an [EzProperty]; see [EzProperty.set_validator].
+pub struct PropertyValidator<T> {
+    inner: Arc<Mutex<Option<ValidatorFunction<T>>>>,
+}
+impl<T> PropertyValidator<T> {
+    /// Pass a new value through the validator. Returns None if the value was rejected.
+    fn validate(&self, value: T) -> Option<T> {
+        match self.inner.lock().unwrap().as_ref() {
+            Some(validator) => validator(value),
+            None => Some(value),
+        }
+    }
+}
+impl<T> Default for PropertyValidator<T> {
+    fn default() -> Self {
+        PropertyValidator {
+            inner: Arc::new(Mutex::new(None)),
+        }
+    }
+}
+impl<T> Clone for PropertyValidator<T> {
+    fn clone(&self) -> Self {
+        PropertyValidator {
+            inner: self.inner.clone(),
+        }
+    }
+}
+impl<T> Debug for PropertyValidator<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let set = self.inner.lock().unwrap().is_some();
+        write!(f, "PropertyValidator({})", if set { "set" } else { "none" })
+    }
+}
 impl EzProperty<usize> {
     pub fn set_from_ez_value(&mut self, value: EzValues) -> bool {
         self.set(value.as_usize().to_owned())
@@ -135,6 +275,11 @@ impl EzProperty<LayoutOrientation> {
         self.set(value.as_layout_orientation().to_owned())
     }
 }
+impl EzProperty<DockPosition> {
+    pub fn set_from_ez_value(&mut self, value: EzValues) -> bool {
+        self.set(value.as_dock_position())
+    }
+}
 impl EzProperty<HorizontalAlignment> {
     pub fn set_from_ez_value(&mut self, value: EzValues) -> bool {
         self.set(value.as_horizontal_alignment().to_owned())
@@ -160,6 +305,11 @@ impl EzProperty<HorizontalPosHint> {
         self.set(value.as_horizontal_pos_hint())
     }
 }
+impl EzProperty<Vec<String>> {
+    pub fn set_from_ez_value(&mut self, value: EzValues) -> bool {
+        self.set(value.as_string_list())
+    }
+}
 impl<T> PartialEq for EzProperty<T>
 where
     T: PartialEq,
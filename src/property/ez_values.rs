@@ -4,8 +4,8 @@
 use crossterm::style::Color;
 
 use crate::states::definitions::{
-    HorizontalAlignment, HorizontalPosHint, LayoutMode, LayoutOrientation, VerticalAlignment,
-    VerticalPosHint,
+    DockPosition, HorizontalAlignment, HorizontalPosHint, LayoutMode, LayoutOrientation,
+    VerticalAlignment, VerticalPosHint,
 };
 
 /// An enum containing a variant for each value type an [EzProperty] may have.
@@ -17,7 +17,7 @@ use crate::states::definitions::{
 /// ```
 /// let number: usize = *ez_values.as_usize();
 /// ```
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum EzValues {
     Usize(usize),
     F64(f64),
@@ -26,11 +26,13 @@ pub enum EzValues {
     Color(Color),
     LayoutMode(LayoutMode),
     LayoutOrientation(LayoutOrientation),
+    DockPosition(DockPosition),
     HorizontalAlignment(HorizontalAlignment),
     VerticalAlignment(VerticalAlignment),
     SizeHint(Option<f64>),
     VerticalPosHint(VerticalPosHint),
     HorizontalPosHint(HorizontalPosHint),
+    StringList(Vec<String>),
 }
 impl EzValues {
     pub fn as_usize(&self) -> usize {
@@ -64,6 +66,8 @@ impl EzValues {
             format!("{:?}", i)
         } else if let EzValues::LayoutOrientation(i) = self {
             format!("{:?}", i)
+        } else if let EzValues::DockPosition(i) = self {
+            format!("{:?}", i)
         } else if let EzValues::HorizontalAlignment(i) = self {
             format!("{:?}", i)
         } else if let EzValues::VerticalAlignment(i) = self {
@@ -74,6 +78,8 @@ impl EzValues {
             format!("{:?}", i)
         } else if let EzValues::HorizontalPosHint(i) = self {
             format!("{:?}", i)
+        } else if let EzValues::StringList(i) = self {
+            i.join(", ")
         } else {
             panic!("Cannot convert value to string")
         }
@@ -111,6 +117,14 @@ impl EzValues {
         }
     }
 
+    pub fn as_dock_position(&self) -> DockPosition {
+        if let EzValues::DockPosition(i) = self {
+            *i
+        } else {
+            panic!("Wrong property, expected DockPosition")
+        }
+    }
+
     pub fn as_vertical_alignment(&self) -> VerticalAlignment {
         if let EzValues::VerticalAlignment(i) = self {
             i.clone()
@@ -150,6 +164,14 @@ impl EzValues {
             panic!("Wrong property, expected SizeHintProperty")
         }
     }
+
+    pub fn as_string_list(&self) -> Vec<String> {
+        if let EzValues::StringList(i) = self {
+            i.clone()
+        } else {
+            panic!("Wrong property, expected StringListProperty")
+        }
+    }
 }
 impl From<usize> for EzValues {
     fn from(inner: usize) -> EzValues {
@@ -186,6 +208,11 @@ impl From<LayoutOrientation> for EzValues {
         EzValues::LayoutOrientation(inner)
     }
 }
+impl From<DockPosition> for EzValues {
+    fn from(inner: DockPosition) -> EzValues {
+        EzValues::DockPosition(inner)
+    }
+}
 impl From<VerticalAlignment> for EzValues {
     fn from(inner: VerticalAlignment) -> EzValues {
         EzValues::VerticalAlignment(inner)
@@ -211,3 +238,8 @@ impl From<Option<f64>> for EzValues {
         EzValues::SizeHint(inner)
     }
 }
+impl From<Vec<String>> for EzValues {
+    fn from(inner: Vec<String>) -> EzValues {
+        EzValues::StringList(inner)
+    }
+}
@@ -0,0 +1,287 @@
+//! # Command line overrides
+//!
+//! A module implementing [CliOverrides], which parses simple command line arguments that start
+//! the app in a particular UI state: the screen to show and property values to set. This is
+//! useful for launchers and tests, e.g.:
+//!
+//! ```text
+//! my_app --screen settings --set properties.debug=true --set my_label.text=Hello
+//! ```
+use std::io::{Error, ErrorKind};
+
+use crate::parser::parse_properties::{
+    parse_bool_property, parse_color_property, parse_dock_position_property,
+    parse_halign_property, parse_horizontal_pos_hint_property, parse_layout_mode_property,
+    parse_layout_orientation_property, parse_size_hint_property, parse_valign_property,
+    parse_vertical_pos_hint_property,
+};
+use crate::property::ez_values::EzValues;
+use crate::run::definitions::StateTree;
+use crate::scheduler::scheduler::SchedulerFrontend;
+use crate::scheduler::scheduler_funcs::get_screen_parent;
+use crate::states::definitions::LayoutMode;
+use crate::states::ez_state::{EzState, GenericState};
+
+/// Prefix of [CliOverrides] property targets that refer to a custom property rather than a widget
+/// property.
+const CUSTOM_PROPERTY_PREFIX: &str = "properties.";
+
+/// UI overrides parsed from command line arguments. Supported arguments:
+///
+/// - "--screen <id>": show the screen with this ID (or path) on startup. Can be passed multiple
+///   times to select screens in different screen layouts.
+/// - "--set <target>=<value>": set a property on startup. The target is either
+///   "properties.<name>" for a custom property, or "<widget id or path>.<property>" for a widget
+///   property. The value is parsed the same way as in an .ez file; lists are comma separated.
+///
+/// Both can also be passed as "--screen=<id>" and "--set=<target>=<value>". All other arguments
+/// are kept in [rest], so the app can parse its' own arguments from there.
+///
+/// # Example:
+///
+/// ```
+/// use ez_term::*;
+///
+/// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+/// scheduler.new_bool_property("debug", false);
+///
+/// let applied = CliOverrides::from_env()
+///     .and_then(|overrides| overrides.apply(&mut state_tree, &mut scheduler));
+/// match applied {
+///     Ok(()) => run(root_widget, state_tree, scheduler, custom_data),
+///     Err(e) => eprintln!("{}", e),
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct CliOverrides {
+    /// IDs or paths of the screens to show, in the order they were passed
+    pub screens: Vec<String>,
+
+    /// Properties to set as (target, value), in the order they were passed
+    pub properties: Vec<(String, String)>,
+
+    /// Arguments that are not overrides
+    pub rest: Vec<String>,
+}
+impl CliOverrides {
+    /// Parse overrides from arguments. Do not include the program name. Returns an error if
+    /// "--screen" or "--set" is missing its' value, or if a "--set" value is not
+    /// "<target>=<value>".
+    pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Self, Error> {
+        let mut overrides = CliOverrides::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let (flag, value) = match arg.split_once('=') {
+                Some((flag, value)) if flag == "--screen" || flag == "--set" => {
+                    (flag.to_string(), value.to_string())
+                }
+                _ if arg == "--screen" || arg == "--set" => {
+                    let value = args.next().ok_or_else(|| {
+                        invalid(&format!("command line argument {} needs a value", arg))
+                    })?;
+                    (arg, value)
+                }
+                _ => {
+                    overrides.rest.push(arg);
+                    continue;
+                }
+            };
+            if flag == "--screen" {
+                overrides.screens.push(value);
+            } else {
+                let (target, value) = value.split_once('=').ok_or_else(|| {
+                    invalid(&format!(
+                        "command line argument --set must look like \"<target>=<value>\", not: {}",
+                        value
+                    ))
+                })?;
+                overrides
+                    .properties
+                    .push((target.trim().to_string(), value.to_string()));
+            }
+        }
+        Ok(overrides)
+    }
+
+    /// Parse overrides from the arguments the app was started with. See [parse].
+    pub fn from_env() -> Result<Self, Error> {
+        CliOverrides::parse(std::env::args().skip(1))
+    }
+
+    /// Apply the overrides: show the screens and set the properties. Call this after [load_ui]
+    /// and after creating custom properties, before [run]. Returns an error if a screen, widget or
+    /// property does not exist, or if a value cannot be parsed; overrides before the failing one
+    /// have been applied by then.
+    pub fn apply(
+        &self,
+        state_tree: &mut StateTree,
+        scheduler: &mut SchedulerFrontend,
+    ) -> Result<(), Error> {
+        for screen in self.screens.iter() {
+            let path = resolve_screen(screen, state_tree).map_err(|e| {
+                invalid(&format!(
+                    "could not show command line screen \"{}\": {}",
+                    screen, e
+                ))
+            })?;
+            let (layout, id) = get_screen_parent(&path, state_tree);
+            state_tree
+                .get_mut(&layout)
+                .as_layout_mut()
+                .set_active_screen(&id);
+            scheduler.update_widget(&layout);
+        }
+        for (target, value) in self.properties.iter() {
+            let error = |e: Error| {
+                invalid(&format!(
+                    "could not set command line property \"{}\": {}",
+                    target, e
+                ))
+            };
+            match resolve_target(target, state_tree, scheduler).map_err(error)? {
+                Target::Custom(name) => {
+                    let property = scheduler.get_property_mut(&name);
+                    let value =
+                        parse_value_like(&property.get_generic_value(), value).map_err(error)?;
+                    property.set_from_ez_value(value);
+                }
+                Target::Widget(path, name) => {
+                    let state = state_tree.get_mut(&path).as_generic_mut();
+                    let value = parse_value_like(&get_widget_property(state, &name), value)
+                        .map_err(error)?;
+                    match state.get_color_config_mut().get_property_mut(&name) {
+                        Some(property) => property.set_from_ez_value(value),
+                        None => state.update_property(&name, value),
+                    };
+                    scheduler.update_widget(&path);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A property a command line override or control command refers to.
+pub(crate) enum Target {
+    /// Name of a custom property
+    Custom(String),
+
+    /// Path of a widget and the name of one of its' properties
+    Widget(String, String),
+}
+
+/// Resolve "properties.<name>" or "<widget id or path>.<property>" to an existing property.
+pub(crate) fn resolve_target(
+    target: &str,
+    state_tree: &StateTree,
+    scheduler: &SchedulerFrontend,
+) -> Result<Target, Error> {
+    if let Some(name) = target.strip_prefix(CUSTOM_PROPERTY_PREFIX) {
+        return if scheduler.backend.properties.contains_key(name) {
+            Ok(Target::Custom(name.to_string()))
+        } else {
+            Err(invalid(&format!("custom property \"{}\" does not exist", name)))
+        };
+    }
+    let (widget, name) = target.rsplit_once('.').ok_or_else(|| {
+        invalid(&format!(
+            "property \"{}\" must look like \"<widget>.<property>\" or \"properties.<name>\"",
+            target
+        ))
+    })?;
+    let path = resolve_widget(widget, state_tree)?;
+    if !scheduler
+        .backend
+        .properties
+        .contains_key(&format!("{}/{}", path, name))
+    {
+        return Err(invalid(&format!(
+            "widget \"{}\" has no property \"{}\"",
+            widget, name
+        )));
+    }
+    Ok(Target::Widget(path, name.to_string()))
+}
+
+/// Resolve a widget ID or path to the path of an existing widget.
+pub(crate) fn resolve_widget(widget: &str, state_tree: &StateTree) -> Result<String, Error> {
+    match state_tree.try_get(widget) {
+        Some(i) => Ok(i.as_generic().get_path().clone()),
+        None => Err(invalid(&format!("widget \"{}\" does not exist", widget))),
+    }
+}
+
+/// Get the value of a widget property. Color properties are read from the color config, as not
+/// every widget state offers all of them through [GenericState.get_property].
+pub(crate) fn get_widget_property(state: &dyn GenericState, name: &str) -> EzValues {
+    match state.get_color_config().get_property(name) {
+        Some(property) => EzValues::Color(property.value),
+        None => state.get_property(name),
+    }
+}
+
+/// Resolve a screen ID or path to the path of an existing widget in a layout in screen mode.
+pub(crate) fn resolve_screen(screen: &str, state_tree: &StateTree) -> Result<String, Error> {
+    let path = resolve_widget(screen, state_tree)?;
+    let is_screen = match state_tree.try_get(path.rsplit_once('/').unwrap().0) {
+        Some(i) => {
+            matches!(i.obj, EzState::Layout(_)) && i.as_layout().get_mode() == &LayoutMode::Screen
+        }
+        None => false,
+    };
+    if !is_screen {
+        return Err(invalid(&format!("widget \"{}\" is not a screen", screen)));
+    }
+    Ok(path)
+}
+
+/// Create an error for a mistake in a command line override or control command.
+pub(crate) fn invalid(message: &str) -> Error {
+    Error::new(ErrorKind::InvalidInput, message)
+}
+
+/// Parse a value written as in an .ez file into the same type as the current value of a property;
+/// lists are comma separated. Also used by the control server.
+pub(crate) fn parse_value_like(current: &EzValues, value: &str) -> Result<EzValues, Error> {
+    let value = value.trim();
+    let value = match current {
+        EzValues::Usize(_) => EzValues::Usize(value.parse().map_err(|_| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!("must be a positive number, not: {}", value),
+            )
+        })?),
+        EzValues::F64(_) => EzValues::F64(value.parse().map_err(|_| {
+            Error::new(ErrorKind::InvalidData, format!("must be a number, not: {}", value))
+        })?),
+        EzValues::Bool(_) => EzValues::Bool(parse_bool_property(value)?),
+        EzValues::String(_) => EzValues::String(value.to_string()),
+        EzValues::Color(_) => EzValues::Color(parse_color_property(value)?),
+        EzValues::LayoutMode(_) => EzValues::LayoutMode(parse_layout_mode_property(value)?),
+        EzValues::LayoutOrientation(_) => {
+            EzValues::LayoutOrientation(parse_layout_orientation_property(value)?)
+        }
+        EzValues::DockPosition(_) => EzValues::DockPosition(parse_dock_position_property(value)?),
+        EzValues::HorizontalAlignment(_) => {
+            EzValues::HorizontalAlignment(parse_halign_property(value)?)
+        }
+        EzValues::VerticalAlignment(_) => {
+            EzValues::VerticalAlignment(parse_valign_property(value)?)
+        }
+        EzValues::SizeHint(_) => EzValues::SizeHint(parse_size_hint_property(value)?),
+        EzValues::HorizontalPosHint(_) => {
+            EzValues::HorizontalPosHint(parse_horizontal_pos_hint_property(value)?)
+        }
+        EzValues::VerticalPosHint(_) => {
+            EzValues::VerticalPosHint(parse_vertical_pos_hint_property(value)?)
+        }
+        EzValues::StringList(_) => EzValues::StringList(
+            value
+                .split(',')
+                .map(|x| x.trim().to_string())
+                .filter(|x| !x.is_empty())
+                .collect(),
+        ),
+    };
+    Ok(value)
+}
@@ -0,0 +1,236 @@
+//! # Control
+//!
+//! A module implementing the control server, which lets external programs such as scripts and
+//! end-to-end tests drive a running UI by sending text commands over a TCP connection or a unix
+//! socket. Only available with the "control" feature. Start a server with
+//! [SchedulerFrontend.start_control_server] or [SchedulerFrontend.start_control_socket].
+//!
+//! Every command is a single line. The server answers every command with a single line that
+//! starts with "ok" or "error", so scripts can send a command and wait for the answer:
+//!
+//! - "set <target> <value>": set a property. The target is either "properties.<name>" for a
+//!   custom property, or "<widget id or path>.<property>" for a widget property (the same as
+//!   command line overrides, see [CliOverrides]). The value is written the same way as in an .ez
+//!   file; lists are comma separated.
+//! - "get <target>": get the value of a property. Answers "ok <value>".
+//! - "press <widget id or path>": press a widget as if the user clicked it or pressed enter on it.
+//! - "screen <screen id or path>": show a screen.
+//! - "dump": get the values of all properties. Answers "ok <json>", where the JSON object maps
+//!   custom property names and widget paths to values; widget values are objects of property
+//!   names to values.
+//!
+//! ```text
+//! $ echo "set my_label.text Hello" | nc -q 1 localhost 7070
+//! ok
+//! ```
+use std::io::{BufRead, BufReader, Error, Write};
+use std::net::TcpListener;
+#[cfg(unix)]
+use std::os::unix::net::UnixListener;
+use std::sync::mpsc::{channel, Sender};
+use std::thread;
+
+use serde_json::{Map, Value};
+
+use crate::run::cli::{
+    get_widget_property, invalid, parse_value_like, resolve_screen, resolve_target, resolve_widget,
+    Target,
+};
+use crate::run::definitions::{CallbackTree, StateTree};
+use crate::scheduler::definitions::CustomDataMap;
+use crate::scheduler::scheduler::SchedulerFrontend;
+use crate::widgets::layout::layout::Layout;
+
+/// A command received by the control server, along with the channel to send the answer to.
+pub struct ControlRequest {
+    command: String,
+    answer: Sender<String>,
+}
+
+/// Accept connections on a TCP listener in a background thread. Each connection is served on its'
+/// own thread.
+pub(crate) fn serve_tcp(listener: TcpListener, requests: Sender<ControlRequest>) {
+    thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let requests = requests.clone();
+            thread::spawn(move || {
+                if let Ok(reader) = stream.try_clone() {
+                    serve_connection(BufReader::new(reader), stream, requests);
+                }
+            });
+        }
+    });
+}
+
+/// Accept connections on a unix socket in a background thread. Each connection is served on its'
+/// own thread.
+#[cfg(unix)]
+pub(crate) fn serve_unix(listener: UnixListener, requests: Sender<ControlRequest>) {
+    thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let requests = requests.clone();
+            thread::spawn(move || {
+                if let Ok(reader) = stream.try_clone() {
+                    serve_connection(BufReader::new(reader), stream, requests);
+                }
+            });
+        }
+    });
+}
+
+/// Pass every line read from a connection to the main thread as a command and write back the
+/// answer. Returns when the connection or the UI is closed.
+fn serve_connection<R: BufRead, W: Write>(
+    reader: R,
+    mut writer: W,
+    requests: Sender<ControlRequest>,
+) {
+    for line in reader.lines() {
+        let command = match line {
+            Ok(i) => i,
+            Err(_) => return,
+        };
+        if command.trim().is_empty() {
+            continue;
+        }
+        let (answer, answer_receiver) = channel();
+        if requests.send(ControlRequest { command, answer }).is_err() {
+            return;
+        }
+        let answer = match answer_receiver.recv() {
+            Ok(i) => i,
+            Err(_) => return,
+        };
+        if writeln!(writer, "{}", answer).is_err() {
+            return;
+        }
+    }
+}
+
+/// Handle the commands received by the control server since the last frame.
+pub(crate) fn handle_control_requests(
+    root_widget: &Layout,
+    state_tree: &mut StateTree,
+    callback_tree: &mut CallbackTree,
+    scheduler: &mut SchedulerFrontend,
+    custom_data: &mut CustomDataMap,
+) {
+    let requests: Vec<ControlRequest> = match scheduler.backend.control_requests {
+        Some((_, ref receiver)) => receiver.try_iter().collect(),
+        None => return,
+    };
+    for request in requests {
+        let result = handle_command(
+            &request.command,
+            root_widget,
+            state_tree,
+            callback_tree,
+            scheduler,
+            custom_data,
+        );
+        let answer = match result {
+            Ok(i) if i.is_empty() => "ok".to_string(),
+            Ok(i) => format!("ok {}", i),
+            Err(e) => format!("error {}", e),
+        };
+        // The connection may have been closed in the mean time, which is fine
+        let _ = request.answer.send(answer);
+    }
+}
+
+/// Execute a single command, returning the value to answer with.
+fn handle_command(
+    command: &str,
+    root_widget: &Layout,
+    state_tree: &mut StateTree,
+    callback_tree: &mut CallbackTree,
+    scheduler: &mut SchedulerFrontend,
+    custom_data: &mut CustomDataMap,
+) -> Result<String, Error> {
+    let command = command.trim();
+    let (name, argument) = match command.split_once(' ') {
+        Some((name, argument)) => (name, argument.trim()),
+        None => (command, ""),
+    };
+    match name {
+        "set" => {
+            let (target, value) = argument
+                .split_once(' ')
+                .ok_or_else(|| invalid("\"set\" needs a target and a value"))?;
+            match resolve_target(target, state_tree, scheduler)? {
+                Target::Custom(name) => {
+                    let property = scheduler.get_property_mut(&name);
+                    let value = parse_value_like(&property.get_generic_value(), value)?;
+                    property.set_from_ez_value(value);
+                }
+                Target::Widget(path, name) => {
+                    let state = state_tree.get_mut(&path).as_generic_mut();
+                    let value = parse_value_like(&get_widget_property(state, &name), value)?;
+                    match state.get_color_config_mut().get_property_mut(&name) {
+                        Some(property) => property.set_from_ez_value(value),
+                        None => state.update_property(&name, value),
+                    };
+                    scheduler.update_widget(&path);
+                }
+            }
+            Ok(String::new())
+        }
+        "get" => {
+            let value = match resolve_target(argument, state_tree, scheduler)? {
+                Target::Custom(name) => scheduler.get_property(&name).get_generic_value(),
+                Target::Widget(path, name) => {
+                    get_widget_property(state_tree.get(&path).as_generic(), &name)
+                }
+            };
+            Ok(value.as_string())
+        }
+        "press" => {
+            let path = resolve_widget(argument, state_tree)?;
+            if state_tree.get(&path).as_generic().get_disabled() {
+                return Err(invalid(&format!("widget \"{}\" is disabled", argument)));
+            }
+            let widget = root_widget
+                .get_child_by_path(&path)
+                .ok_or_else(|| invalid(&format!("widget \"{}\" cannot be pressed", argument)))?;
+            widget
+                .as_ez_object()
+                .on_press(state_tree, callback_tree, scheduler, custom_data);
+            Ok(String::new())
+        }
+        "screen" => {
+            let path = resolve_screen(argument, state_tree)?;
+            scheduler.show_screen(&path, state_tree);
+            Ok(String::new())
+        }
+        "dump" => Ok(dump_properties(state_tree, scheduler).to_string()),
+        _ => Err(invalid(&format!(
+            "unknown command \"{}\", expected set, get, press, screen or dump",
+            name
+        ))),
+    }
+}
+
+/// Get the values of all custom properties and widget properties as a JSON object.
+fn dump_properties(state_tree: &StateTree, scheduler: &SchedulerFrontend) -> Value {
+    let mut dump = Map::new();
+    for (name, property) in scheduler.backend.properties.iter() {
+        match name.rsplit_once('/') {
+            Some((path, property_name)) => {
+                if let Some(state) = state_tree.try_get(path) {
+                    let value = get_widget_property(state.as_generic(), property_name).as_string();
+                    let widget = dump
+                        .entry(path.to_string())
+                        .or_insert_with(|| Value::Object(Map::new()));
+                    if let Value::Object(properties) = widget {
+                        properties.insert(property_name.to_string(), Value::String(value));
+                    }
+                }
+            }
+            None => {
+                let value = property.get_generic_value().as_string();
+                dump.insert(name.clone(), Value::String(value));
+            }
+        }
+    }
+    Value::Object(dump)
+}
@@ -4,6 +4,7 @@
 use crossterm::style::{Color, StyledContent, Stylize};
 use std::cmp::max;
 
+use crate::property::ez_values::EzValues;
 use crate::run::tree::Tree;
 use crate::states::definitions::StateSize;
 use crate::states::ez_state::EzState;
@@ -150,6 +151,62 @@ pub type PixelMap = Vec<Vec<Pixel>>;
 /// called on the widget, the new state will be drawn on screen on the next frame.
 pub type StateTree = Tree<EzState>;
 
+/// Values of widgets captured with [StateTree.snapshot], which can be put back with
+/// [StateTree.restore].
+#[derive(Clone, Debug, Default)]
+pub struct StateSnapshot {
+    /// Captured values as (widget path, property name, value)
+    pub values: Vec<(String, String, EzValues)>,
+}
+impl StateSnapshot {
+    /// Get the captured value of a property of a widget, if it was captured.
+    pub fn get(&self, widget_path: &str, property: &str) -> Option<&EzValues> {
+        self.values
+            .iter()
+            .find(|(path, name, _)| path == widget_path && name == property)
+            .map(|(_, _, value)| value)
+    }
+}
+
 /// A wrapper around a <WidgetPath, [CallbackConfig]> HashMap. Can be used to access callbacks
 /// bound to a widget. A [CallbackConfig] for a wiget can be updated through the [Scheduler].
 pub type CallbackTree = Tree<CallbackConfig>;
+
+/// Values of a widget state that were changed when the UI entered compact mode because the
+/// terminal was too small (see [small_terminal]). Used to restore the widget when the terminal is
+/// large enough again.
+#[derive(Clone, Debug)]
+pub struct CompactBackup {
+    /// Full path of the widget the values belong to
+    pub path: String,
+
+    /// Whether the widget had a border
+    pub border: bool,
+
+    /// Padding of the widget as (top, bottom, left, right)
+    pub padding: (usize, usize, usize, usize),
+
+    /// Whether the widget was collapsed. Only layouts with [collapsible_on_small] are collapsed.
+    /// The size hints, auto scaling and size below are only set for collapsed widgets.
+    pub collapsed: bool,
+
+    /// Size hints of the widget as (x, y)
+    pub size_hint: (Option<f64>, Option<f64>),
+
+    /// Auto scaling of the widget as (width, height)
+    pub auto_scale: (bool, bool),
+
+    /// Size of the widget
+    pub size: Size,
+}
+
+/// State of the UI when the terminal is too small to show it normally. The required size is the
+/// size the terminal needs to be to leave compact mode.
+#[derive(Clone, Debug, Default)]
+pub struct CompactMode {
+    /// Size the terminal needs to show the UI with all borders, padding and collapsible layouts
+    pub required_size: Size,
+
+    /// Original values of all widgets changed by compact mode
+    pub backups: Vec<CompactBackup>,
+}
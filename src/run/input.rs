@@ -2,22 +2,31 @@
 //!
 //! This module has functions that handle user input through keyboard and mouse.
 use std::mem::replace;
-use std::process::exit;
 
 use crate::{Context, KeyMap};
-use crossterm::event::{Event, KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+use crossterm::style::Color;
+use crossterm::event::{
+    Event, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
 
 use crate::run::definitions::{CallbackTree, Coordinates, IsizeCoordinates, StateTree};
-use crate::run::select::{get_widget_by_position, select_next, select_previous};
-use crate::run::terminal::{initialize_terminal, write_to_screen};
+use crate::run::select::{
+    focus_next, focus_previous, get_paths_by_position, get_stacked_widgets_by_position,
+    get_target_by_position, get_widget_by_position, select_next, select_previous,
+    widget_is_hidden,
+};
+use crate::run::small_terminal::get_root_contents;
+use crate::run::terminal::Terminal;
 use crate::run::tree::ViewTree;
-use crate::scheduler::definitions::CustomDataMap;
+use crate::scheduler::definitions::{CustomDataMap, EventPhase};
 use crate::scheduler::scheduler::SchedulerFrontend;
-use crate::states::ez_state::EzState;
-use crate::widgets::ez_object::{EzObject, EzObjects};
+use crate::states::definitions::InteractionState;
+use crate::states::ez_state::{EzState, GenericState};
+use crate::widgets::ez_object::EzObjects;
+use crate::widgets::button::Button;
+use crate::widgets::{data_grid, list_view, text_box};
 use crate::widgets::layout::layout::Layout;
 
-use super::terminal::shutdown_terminal;
 
 /// Try to handle an event by passing it to the active modal if any. The modal will return whether
 /// it consumed the event or not.
@@ -33,17 +42,23 @@ pub fn handle_modal_event(
         return false;
     }
     let modal = root_widget.state.get_modal();
-    let mut consumed =
-        modal
-            .as_ez_object()
-            .handle_event(event, state_tree, callback_tree, scheduler, custom_data);
+    let mut consumed = modal.as_ez_object().handle_event(
+        event.clone(),
+        state_tree,
+        callback_tree,
+        scheduler,
+        custom_data,
+    );
     if !consumed {
         if let EzObjects::Layout(layout) = modal {
             for child in layout.get_widgets_recursive() {
-                consumed =
-                    child
-                        .as_ez_object()
-                        .handle_event(event, state_tree, callback_tree, scheduler, custom_data);
+                consumed = child.as_ez_object().handle_event(
+                    event.clone(),
+                    state_tree,
+                    callback_tree,
+                    scheduler,
+                    custom_data,
+                );
                 if consumed {
                     return true;
                 }
@@ -53,6 +68,70 @@ pub fn handle_modal_event(
     consumed
 }
 
+/// Get the paths of the widgets an event travels through to reach its' target, starting with the
+/// root layout. The target of a mouse event is the deepest widget under the mouse; the target of
+/// other events is the selected widget, or the root layout if nothing is selected.
+pub fn get_event_path(
+    event: &Event,
+    root_widget: &Layout,
+    state_tree: &StateTree,
+    selected_widget: &str,
+) -> Vec<String> {
+    let target = if let Event::Mouse(mouse_event) = event {
+        let mouse_position =
+            Coordinates::new(mouse_event.column as usize, mouse_event.row as usize);
+        get_target_by_position(mouse_position, state_tree)
+    } else if !selected_widget.is_empty() && state_tree.try_get(selected_widget).is_some() {
+        selected_widget.to_string()
+    } else {
+        root_widget.path.clone()
+    };
+
+    let mut path = Vec::new();
+    let mut current = String::new();
+    for step in target.split('/').filter(|x| !x.is_empty()) {
+        current = format!("{}/{}", current, step);
+        path.push(current.clone());
+    }
+    path
+}
+
+/// Pass an event to the on_capture or on_bubble callbacks (depending on the phase) of the widgets
+/// in the path, in order. Returns true if a callback consumed the event or stopped its'
+/// propagation.
+pub fn propagate_event(
+    phase: EventPhase,
+    path: &[String],
+    state_tree: &mut StateTree,
+    callback_tree: &mut CallbackTree,
+    scheduler: &mut SchedulerFrontend,
+    custom_data: &mut CustomDataMap,
+) -> bool {
+    scheduler.backend.event_control.phase = phase;
+    for widget_path in path {
+        match state_tree.try_get(widget_path) {
+            Some(state) if !state.as_generic().get_disabled() => (),
+            _ => continue,
+        }
+        let callbacks = match callback_tree.try_get_mut(widget_path) {
+            Some(i) => &mut i.obj,
+            None => continue,
+        };
+        let callback = match phase {
+            EventPhase::Capture => &mut callbacks.on_capture,
+            EventPhase::Bubble => &mut callbacks.on_bubble,
+            EventPhase::Target => continue,
+        };
+        if let Some(ref mut i) = callback {
+            let context = Context::new(widget_path.clone(), state_tree, scheduler, custom_data);
+            if i(context) || scheduler.backend.event_control.is_propagation_stopped() {
+                return true;
+            }
+        }
+    }
+    false
+}
+
 /// Try to handle an event as a global keybind. Examples are up/down keys for navigating menu,
 /// left/right clicks, etc. If the event is bound globally, it will be consumed.
 pub fn handle_global_event(
@@ -91,10 +170,95 @@ pub fn handle_global_event(
             selected_widget,
             hovered_widget,
         ),
+        Event::Paste(text) => handle_paste_event(
+            text,
+            state_tree,
+            root_widget,
+            callback_tree,
+            scheduler,
+            custom_data,
+            selected_widget,
+        ),
+        Event::FocusGained => handle_focus_event(true, state_tree, scheduler, custom_data),
+        Event::FocusLost => handle_focus_event(false, state_tree, scheduler, custom_data),
         _ => false,
     }
 }
 
+/// Handle text being pasted into the terminal. The paste is offered to the selected widget first;
+/// if it does not consume it, the global on_paste callback set on the scheduler is called.
+fn handle_paste_event(
+    text: String,
+    state_tree: &mut StateTree,
+    root_widget: &Layout,
+    callback_tree: &mut CallbackTree,
+    scheduler: &mut SchedulerFrontend,
+    custom_data: &mut CustomDataMap,
+    selected_widget: &str,
+) -> bool {
+    if !selected_widget.is_empty()
+        && !state_tree.get(selected_widget).as_generic().get_disabled()
+    {
+        if let Some(widget) = root_widget.get_child_by_path(selected_widget) {
+            let consumed = widget.as_ez_object().on_paste(
+                state_tree,
+                callback_tree,
+                scheduler,
+                text.clone(),
+                custom_data,
+            );
+            if consumed {
+                return true;
+            }
+        }
+    }
+    if let Some(mut callback) = scheduler.backend.on_paste.take() {
+        let context = Context::new("/root".to_string(), state_tree, scheduler, custom_data);
+        let consumed = callback(context, text);
+        // Restore the callback unless it was replaced from within the callback
+        if scheduler.backend.on_paste.is_none() {
+            scheduler.backend.on_paste = Some(callback);
+        }
+        return consumed;
+    }
+    false
+}
+
+/// Handle the terminal window gaining or losing focus. Updates the "app_focused" property and
+/// calls the on_focus or on_blur callback set on the scheduler if any.
+fn handle_focus_event(
+    focused: bool,
+    state_tree: &mut StateTree,
+    scheduler: &mut SchedulerFrontend,
+    custom_data: &mut CustomDataMap,
+) -> bool {
+    if scheduler.backend.properties.contains_key("app_focused") {
+        scheduler
+            .get_property_mut("app_focused")
+            .as_bool_mut()
+            .set(focused);
+    }
+    let callback = if focused {
+        scheduler.backend.on_focus.take()
+    } else {
+        scheduler.backend.on_blur.take()
+    };
+    if let Some(mut callback) = callback {
+        let context = Context::new("/root".to_string(), state_tree, scheduler, custom_data);
+        callback(context);
+        // Restore the callback unless it was replaced from within the callback
+        let slot = if focused {
+            &mut scheduler.backend.on_focus
+        } else {
+            &mut scheduler.backend.on_blur
+        };
+        if slot.is_none() {
+            *slot = Some(callback);
+        }
+    }
+    true
+}
+
 /// Global key handler. If a key event matches one of these keys it will be consumed and not passed
 /// on any further.
 fn handle_key_event(
@@ -107,6 +271,21 @@ fn handle_key_event(
     scheduler: &mut SchedulerFrontend,
     selected_widget: &mut String,
 ) -> bool {
+    // Text boxes use enter and the arrow keys to edit text and data grids and list views use the
+    // arrow keys to select rows, so let them handle those keys
+    let captured = match state_tree.try_get(selected_widget).map(|x| &x.obj) {
+        Some(EzState::TextBox(state)) => !state.get_disabled() && text_box::captures_key(state, key),
+        Some(EzState::DataGrid(state)) => {
+            !state.get_disabled() && data_grid::captures_key(state, key)
+        }
+        Some(EzState::ListView(state)) => {
+            !state.get_disabled() && list_view::captures_key(state, key)
+        }
+        _ => false,
+    };
+    if captured {
+        return false;
+    }
     let consumed = match key.code {
         KeyCode::Down => {
             select_next(
@@ -128,6 +307,15 @@ fn handle_key_event(
             );
             true
         }
+        // Keys bound by the user take priority over Tab traversal
+        KeyCode::Tab if !global_keymap.contains(key.code, key.modifiers) => {
+            focus_next(state_tree, root_widget, scheduler, selected_widget);
+            true
+        }
+        KeyCode::BackTab if !global_keymap.contains(key.code, key.modifiers) => {
+            focus_previous(state_tree, root_widget, scheduler, selected_widget);
+            true
+        }
         KeyCode::Enter => {
             if !selected_widget.is_empty()
                 && !state_tree.get(&selected_widget).as_generic().get_disabled()
@@ -141,8 +329,8 @@ fn handle_key_event(
             true
         }
         KeyCode::Esc => {
-            shutdown_terminal().unwrap();
-            exit(0);
+            scheduler.exit();
+            true
         }
         _ => false,
     };
@@ -151,6 +339,20 @@ fn handle_key_event(
         let callback = global_keymap.get_mut(key.code, key.modifiers).unwrap();
         return callback(context, key.code, key.modifiers);
     }
+    // The terminal does not send signals for these keys in raw mode, so we handle them ourselves
+    if !consumed && key.modifiers == KeyModifiers::CONTROL {
+        match key.code {
+            KeyCode::Char('c') => {
+                scheduler.exit();
+                return true;
+            }
+            KeyCode::Char('z') => {
+                scheduler.backend.suspend_requested = true;
+                return true;
+            }
+            _ => (),
+        }
+    }
     false
 }
 
@@ -192,8 +394,17 @@ fn handle_mouse_event(
             dragging,
             last_dragging_pos,
         );
+    } else if let MouseEventKind::Down(MouseButton::Left) = event.kind {
+        return handle_mouse_down_event(
+            event,
+            state_tree,
+            root_widget,
+            callback_tree,
+            scheduler,
+            custom_data,
+        );
     } else if let MouseEventKind::Up(button) = event.kind {
-        return handle_mouse_press_event(
+        let consumed = handle_mouse_press_event(
             event,
             button,
             state_tree,
@@ -202,6 +413,15 @@ fn handle_mouse_event(
             scheduler,
             custom_data
         );
+        // The mouse might have been released outside of the button that was pushed down
+        if button == MouseButton::Left {
+            Button::release_mouse_presses(state_tree, callback_tree, scheduler, custom_data);
+        }
+        return consumed;
+    } else if event.modifiers.contains(KeyModifiers::SHIFT)
+        && matches!(event.kind, MouseEventKind::ScrollUp | MouseEventKind::ScrollDown)
+    {
+        return handle_mouse_horizontal_scroll_event(event, state_tree, root_widget, scheduler);
     } else if let MouseEventKind::ScrollUp = event.kind {
         return handle_mouse_scroll_up_event(
             event,
@@ -224,6 +444,35 @@ fn handle_mouse_event(
     false
 }
 
+/// Pass a left mouse button being pushed down to the widgets under the mouse, so widgets that
+/// distinguish pressing from releasing (e.g. buttons) can react to it.
+fn handle_mouse_down_event(
+    event: MouseEvent,
+    state_tree: &mut StateTree,
+    root_widget: &Layout,
+    callback_tree: &mut CallbackTree,
+    scheduler: &mut SchedulerFrontend,
+    custom_data: &mut CustomDataMap,
+) -> bool {
+    let mouse_position = Coordinates::new(event.column as usize, event.row as usize);
+    for widget in get_widget_by_position(mouse_position, root_widget, state_tree) {
+        let abs = state_tree
+            .get(&widget.get_path())
+            .as_generic()
+            .get_absolute_position();
+        let relative_position = Coordinates::new(
+            mouse_position.x - abs.usize_x(),
+            mouse_position.y - abs.usize_y(),
+        );
+        if widget.on_left_mouse_down(state_tree, callback_tree, scheduler, relative_position,
+                                     custom_data)
+            || scheduler.backend.event_control.is_propagation_stopped() {
+            return true;
+        }
+    }
+    false
+}
+
 fn handle_mouse_press_event(
     event: MouseEvent,
     button: MouseButton,
@@ -255,7 +504,7 @@ fn handle_mouse_press_event(
             }
             _ => false,
         };
-        if consumed {
+        if consumed || scheduler.backend.event_control.is_propagation_stopped() {
             return true;
         }
     }
@@ -273,6 +522,7 @@ fn handle_mouse_hover_event(
     hovered_widget: &mut String,
 ) -> bool {
     let mouse_position = Coordinates::new(event.column as usize, event.row as usize);
+    update_hovered_children(mouse_position, state_tree, scheduler);
 
     if !hovered_widget.is_empty() {
         let maybe_state = state_tree.try_get(hovered_widget);
@@ -307,7 +557,7 @@ fn handle_mouse_hover_event(
     if !selected_widget.is_empty() && !scheduler.backend.deselect {
         let maybe_state = state_tree.try_get(selected_widget);
         if let Some(i) = maybe_state {
-            if let EzState::TextInput(_) = i.obj {
+            if let EzState::TextInput(_) | EzState::TextBox(_) = i.obj {
             } else {
                 scheduler.deselect_widget();
             }
@@ -318,6 +568,109 @@ fn handle_mouse_hover_event(
     true
 }
 
+/// Keep track of the widget under the mouse and the widget the left mouse button is held down
+/// on, so they are drawn in their hover and pressed colors (see [InteractionState]). Called for
+/// every mouse event, whether a widget consumes it or not.
+pub(crate) fn update_interaction_states(
+    event: &MouseEvent,
+    state_tree: &mut StateTree,
+    scheduler: &mut SchedulerFrontend,
+) {
+    let mouse_position = Coordinates::new(event.column as usize, event.row as usize);
+    match event.kind {
+        MouseEventKind::Moved | MouseEventKind::Drag(_) => {
+            let target = get_target_by_position(mouse_position, state_tree);
+            set_interaction_widget(false, Some(target), state_tree, scheduler);
+        }
+        MouseEventKind::Down(MouseButton::Left) => {
+            let target = get_target_by_position(mouse_position, state_tree);
+            set_interaction_widget(false, Some(target.clone()), state_tree, scheduler);
+            set_interaction_widget(true, Some(target), state_tree, scheduler);
+        }
+        MouseEventKind::Up(MouseButton::Left) => {
+            set_interaction_widget(true, None, state_tree, scheduler);
+        }
+        _ => (),
+    }
+}
+
+/// Move the hovered or held state from the widget that has it to another widget, or to no widget.
+/// Widgets are only redrawn if they have colors for the state.
+fn set_interaction_widget(
+    held: bool,
+    path: Option<String>,
+    state_tree: &mut StateTree,
+    scheduler: &mut SchedulerFrontend,
+) {
+    let current = if held {
+        &mut scheduler.backend.mouse_held_widget
+    } else {
+        &mut scheduler.backend.mouse_hovered_widget
+    };
+    if *current == path {
+        return;
+    }
+    let old = replace(current, path.clone());
+    let interaction = if held {
+        InteractionState::Pressed
+    } else {
+        InteractionState::Hovered
+    };
+    for (path, value) in [(old, false), (path, true)] {
+        let state = match path.and_then(|x| state_tree.try_get_mut(&x)) {
+            Some(i) => i.obj.as_generic_mut(),
+            None => continue,
+        };
+        if held {
+            state.set_held(value);
+        } else {
+            state.set_hovered(value);
+        }
+        let colors = state.get_color_config().get_interaction_colors(interaction);
+        if colors != (Color::Reset, Color::Reset) {
+            state.update(scheduler);
+        }
+    }
+}
+
+/// Keep track of which child is under the mouse cursor for each layout that has
+/// [highlight_child_on_hover] enabled, and redraw the layout when that child changes.
+fn update_hovered_children(
+    mouse_position: Coordinates,
+    state_tree: &mut StateTree,
+    scheduler: &mut SchedulerFrontend,
+) {
+    let mut changed = Vec::new();
+    for state in state_tree.get_all() {
+        if let EzState::Layout(ref layout) = state {
+            if !layout.get_highlight_child_on_hover() {
+                continue;
+            }
+            let hovered = state_tree
+                .get(layout.get_path())
+                .get_children()
+                .into_iter()
+                .map(|child| child.obj.as_generic())
+                .find(|child| {
+                    child.collides(mouse_position)
+                        && !widget_is_hidden(child.get_path().clone(), state_tree)
+                })
+                .map(|child| child.get_path().clone())
+                .unwrap_or_default();
+            if &hovered != layout.get_hovered_child() {
+                changed.push((layout.get_path().clone(), hovered));
+            }
+        }
+    }
+    for (path, hovered) in changed {
+        state_tree
+            .get_mut(&path)
+            .as_layout_mut()
+            .set_hovered_child(hovered);
+        scheduler.update_widget(&path);
+    }
+}
+
 fn handle_mouse_drag_event(
     event: MouseEvent,
     state_tree: &mut StateTree,
@@ -330,6 +683,21 @@ fn handle_mouse_drag_event(
 ) -> bool {
     let mouse_position = Coordinates::new(event.column as usize, event.row as usize);
 
+    if dragging.is_none()
+        && scheduler.backend.drag_payload.is_none()
+        && handle_drag_start(
+            mouse_position,
+            state_tree,
+            root_widget,
+            callback_tree,
+            scheduler,
+            custom_data,
+            dragging,
+            last_dragging_pos,
+        )
+    {
+        return true;
+    }
     if let Some(ref path) = dragging {
         let widget = root_widget.get_child_by_path(&path).unwrap().as_ez_object();
         let abs = state_tree.get(path).as_generic().get_absolute_position();
@@ -345,7 +713,8 @@ fn handle_mouse_drag_event(
             relative_position,
             custom_data,
         );
-        if consumed {
+        // While a payload is dragged the drag stays with the widget it started on
+        if consumed || scheduler.backend.drag_payload.is_some() {
             dragging.replace(widget.get_path());
             last_dragging_pos.x = relative_position.x;
             last_dragging_pos.y = relative_position.y;
@@ -379,6 +748,92 @@ fn handle_mouse_drag_event(
     true
 }
 
+/// Start a drag and drop if the widget on top under the mouse (or one of its' parents) returns a
+/// [DragPayload] from its' on_drag_start callback. That widget becomes the dragged widget, so it
+/// keeps receiving on_drag events until the payload is dropped, see [handle_drop].
+fn handle_drag_start(
+    mouse_position: Coordinates,
+    state_tree: &mut StateTree,
+    root_widget: &Layout,
+    callback_tree: &mut CallbackTree,
+    scheduler: &mut SchedulerFrontend,
+    custom_data: &mut CustomDataMap,
+    dragging: &mut Option<String>,
+    last_dragging_pos: &mut IsizeCoordinates,
+) -> bool {
+    for widget in get_stacked_widgets_by_position(mouse_position, root_widget, state_tree) {
+        let abs = state_tree
+            .get(&widget.get_path())
+            .as_generic()
+            .get_absolute_position();
+        let relative_position = IsizeCoordinates::new(
+            mouse_position.x as isize - abs.usize_x() as isize,
+            mouse_position.y as isize - abs.usize_y() as isize,
+        );
+        if let Some(mut payload) = widget.on_drag_start(
+            state_tree,
+            callback_tree,
+            scheduler,
+            relative_position,
+            custom_data,
+        ) {
+            payload.source = widget.get_path();
+            scheduler.backend.drag_payload = Some(payload);
+            widget.on_drag(
+                state_tree,
+                callback_tree,
+                scheduler,
+                None,
+                relative_position,
+                custom_data,
+            );
+            dragging.replace(widget.get_path());
+            last_dragging_pos.x = relative_position.x;
+            last_dragging_pos.y = relative_position.y;
+            return true;
+        }
+    }
+    false
+}
+
+/// Offer the payload of the drag and drop in progress (if any) to the widget on top at the
+/// position the mouse button was released, and then to its' parents, until one of their on_drop
+/// callbacks accepts it.
+pub fn handle_drop(
+    mouse_position: Coordinates,
+    state_tree: &mut StateTree,
+    root_widget: &Layout,
+    callback_tree: &mut CallbackTree,
+    scheduler: &mut SchedulerFrontend,
+    custom_data: &mut CustomDataMap,
+) -> bool {
+    let payload = match scheduler.backend.drag_payload.take() {
+        Some(i) => i,
+        None => return false,
+    };
+    for widget in get_stacked_widgets_by_position(mouse_position, root_widget, state_tree) {
+        let abs = state_tree
+            .get(&widget.get_path())
+            .as_generic()
+            .get_absolute_position();
+        let relative_position = IsizeCoordinates::new(
+            mouse_position.x as isize - abs.usize_x() as isize,
+            mouse_position.y as isize - abs.usize_y() as isize,
+        );
+        if widget.on_drop(
+            state_tree,
+            callback_tree,
+            scheduler,
+            payload.clone(),
+            relative_position,
+            custom_data,
+        ) {
+            return true;
+        }
+    }
+    false
+}
+
 fn handle_mouse_scroll_up_event(
     event: MouseEvent,
     state_tree: &mut StateTree,
@@ -391,7 +846,7 @@ fn handle_mouse_scroll_up_event(
     let mouse_position = Coordinates::new(event.column as usize, event.row as usize);
     for widget in get_widget_by_position(mouse_position, root_widget, state_tree) {
         let consumed = widget.on_scroll_up(state_tree, callback_tree, scheduler, custom_data);
-        if consumed {
+        if consumed || scheduler.backend.event_control.is_propagation_stopped() {
             return consumed;
         }
     }
@@ -410,19 +865,55 @@ fn handle_mouse_scroll_down_event(
     let mouse_position = Coordinates::new(event.column as usize, event.row as usize);
     for widget in get_widget_by_position(mouse_position, root_widget, state_tree) {
         let consumed = widget.on_scroll_down(state_tree, callback_tree, scheduler, custom_data);
-        if consumed {
+        if consumed || scheduler.backend.event_control.is_propagation_stopped() {
             return consumed;
         }
     }
     consumed
 }
 
+/// Scroll the innermost layout under the mouse that can scroll horizontally. Used for the mouse
+/// wheel while shift is held, as terminals do not report horizontal wheel events.
+fn handle_mouse_horizontal_scroll_event(
+    event: MouseEvent,
+    state_tree: &mut StateTree,
+    root_widget: &Layout,
+    scheduler: &mut SchedulerFrontend,
+) -> bool {
+    let mouse_position = Coordinates::new(event.column as usize, event.row as usize);
+    let backwards = event.kind == MouseEventKind::ScrollUp;
+    for path in get_paths_by_position(mouse_position, state_tree)
+        .into_iter()
+        .chain(std::iter::once(root_widget.path.clone()))
+    {
+        if let EzState::Layout(ref state) = state_tree.get(&path).obj {
+            if !state.get_scrolling_config().get_is_scrolling_x() {
+                continue;
+            }
+        } else {
+            continue;
+        }
+        let layout = if path == root_widget.path {
+            root_widget
+        } else if let Some(EzObjects::Layout(i)) = root_widget.get_child_by_path(&path) {
+            i
+        } else {
+            continue;
+        };
+        layout.handle_wheel_scroll(state_tree, scheduler, true, backwards);
+        return true;
+    }
+    false
+}
+
 /// Handle a resize event by setting the size of the root widget to the new window size, updating
 /// the sizes/positions of all children and generating a new view tree of the right size.
 pub fn handle_resize(
     view_tree: &mut ViewTree,
     state_tree: &mut StateTree,
     root_widget: &mut Layout,
+    scheduler: &mut SchedulerFrontend,
+    terminal: &mut Terminal,
     new_width: usize,
     new_height: usize,
 ) {
@@ -441,12 +932,13 @@ pub fn handle_resize(
     let state = state_tree.get_mut(&root_widget.path).as_generic_mut();
     state.get_size_mut().set_width(new_width as usize);
     state.get_size_mut().set_height(new_height as usize);
-    let contents = root_widget.get_contents(state_tree);
+    let contents = get_root_contents(root_widget, state_tree, scheduler);
     root_widget.propagate_absolute_positions(state_tree);
     // We need to re-initialize the terminal, because on Windows the hidden cursor will come back
     // on resize.
-    initialize_terminal().unwrap();
+    terminal.initialize().unwrap();
+    scheduler.backend.terminal_cursor = None;
     view_tree.initialize(new_width, new_height);
     view_tree.write_content(Coordinates::new(0, 0), contents);
-    write_to_screen(view_tree);
+    terminal.write_to_screen(view_tree);
 }
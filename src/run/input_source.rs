@@ -0,0 +1,79 @@
+//! # Input sources
+//!
+//! A module implementing input sources: sources of events other than the terminal, such as a
+//! gamepad or another thread. Events of input sources are routed exactly like terminal events
+//! (through the input filter, modals, global key binds and widgets), so a gamepad can for example
+//! send arrow keys and enter to navigate the UI. Add a source with
+//! [SchedulerFrontend.add_input_source], or get an [EventInjector] with
+//! [SchedulerFrontend.get_event_injector] to send events from any thread.
+use std::sync::mpsc::{channel, Receiver, Sender};
+
+use crossterm::event::Event;
+
+/// A source of events other than the terminal. The run loop polls every source once per loop
+/// iteration, before reading terminal input.
+///
+/// # Example:
+///
+/// A source that translates the buttons of a (made up) gamepad to keys:
+/// ```
+/// use ez_term::*;
+///
+/// struct Gamepad;
+/// impl Gamepad {
+///     fn next_button(&mut self) -> Option<char> { None }
+/// }
+///
+/// impl InputSource for Gamepad {
+///     fn poll_event(&mut self) -> Option<Event> {
+///         let code = match self.next_button()? {
+///             'a' => KeyCode::Enter,
+///             'd' => KeyCode::Down,
+///             'u' => KeyCode::Up,
+///             _ => return None,
+///         };
+///         Some(Event::Key(KeyEvent::new(code, KeyModifiers::NONE)))
+///     }
+/// }
+///
+/// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+/// scheduler.add_input_source(Box::new(Gamepad));
+/// run(root_widget, state_tree, scheduler, custom_data);
+/// ```
+pub trait InputSource: Send {
+    /// Get the next event of this source, if one is available. Must not block, as it is called
+    /// from the run loop.
+    fn poll_event(&mut self) -> Option<Event>;
+}
+
+/// Handle to send events into the UI from any thread, as if they came from the terminal. Get one
+/// with [SchedulerFrontend.get_event_injector]; it can be cloned freely. Useful for input
+/// libraries that read devices on their own thread, or to drive the UI from another thread.
+#[derive(Clone, Debug)]
+pub struct EventInjector {
+    sender: Sender<Event>,
+}
+impl EventInjector {
+    /// Send an event into the UI. It is handled on one of the next iterations of the run loop.
+    /// Events sent after the UI has stopped are dropped.
+    pub fn inject(&self, event: Event) {
+        let _ = self.sender.send(event);
+    }
+}
+
+/// [InputSource] that receives the events of an [EventInjector].
+pub struct ChannelInputSource {
+    receiver: Receiver<Event>,
+}
+impl ChannelInputSource {
+    /// Create a source along with the injector that sends events into it.
+    pub fn new() -> (Self, EventInjector) {
+        let (sender, receiver) = channel();
+        (ChannelInputSource { receiver }, EventInjector { sender })
+    }
+}
+impl InputSource for ChannelInputSource {
+    fn poll_event(&mut self) -> Option<Event> {
+        self.receiver.try_recv().ok()
+    }
+}
@@ -1,8 +1,11 @@
 /// A module containing functions that handle user input.
 mod input;
 
+/// A module implementing sources of input other than the terminal
+pub mod input_source;
+
 /// A module that interfaces with the actual terminal (using Crossterm)
-mod terminal;
+pub mod terminal;
 
 /// A module containing functions that handle selecting widgets
 pub mod select;
@@ -10,8 +13,27 @@ pub mod select;
 /// A module containing the run loop and supporting functions
 pub mod run;
 
+/// A module containing functions that degrade the UI gracefully on terminals that are too small
+pub mod small_terminal;
+
 /// A module containing structs for the StateTree, WidgetTree, CallbackTree and ViewTree
 pub mod tree;
 
 /// A module containing definitions used by run modules
 pub mod definitions;
+
+/// A module containing a test harness to drive the UI without a terminal
+pub mod testing;
+
+/// A module handling signals such as SIGINT and SIGTSTP
+pub mod signals;
+
+/// A module implementing command line overrides for the initial UI state
+pub mod cli;
+
+/// A module implementing layout tuning mode, a developer tool to move and resize widgets
+pub mod tuning;
+
+/// A module implementing a server that lets external programs drive the running UI
+#[cfg(feature = "control")]
+pub mod control;
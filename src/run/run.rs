@@ -7,23 +7,44 @@ use std::time::{Duration, Instant};
 
 use crossterm::event::{MouseButton, MouseEvent};
 use crossterm::{
-    event::{poll, read, Event, MouseEventKind},
+    event::{Event, MouseEventKind},
     Result,
 };
 
+use crate::dialogs::get_dialog_callback_config;
 use crate::run::definitions::{CallbackTree, Coordinates, IsizeCoordinates, StateTree};
-use crate::run::terminal::{redraw_changed_widgets, write_to_screen};
+use crate::run::signals::handle_signals;
+#[cfg(unix)]
+use crate::run::signals::listen_for_signals;
+use crate::run::select::get_selected_path;
+use crate::run::small_terminal::get_root_contents;
+use crate::run::terminal::{
+    redraw_background_widgets, redraw_changed_cells, redraw_changed_widgets,
+    split_background_updates, Terminal,
+};
+use crate::run::tuning::handle_layout_tuning_event;
 use crate::run::tree::{clean_trees, initialize_callback_tree, ViewTree};
 use crate::scheduler::scheduler::SchedulerFrontend;
-use crate::scheduler::scheduler_funcs::{add_custom_data, add_property_callbacks, create_new_widgets, drain_property_channels, handle_next_selection, remove_widgets, run_tasks, trigger_update_funcs, update_callback_configs, update_properties, update_threads};
+use crate::scheduler::scheduler_funcs::{add_custom_data, add_property_callbacks, clone_widgets, create_new_widgets, move_widgets, swap_children, drain_property_channels, handle_next_selection, load_lazy_widgets, remove_widgets, render_widgets_to_string, run_tasks, trigger_update_funcs, trigger_update_funcs_of, update_callback_configs, update_exit_request, update_idle, update_teardown, update_modal_result, update_pressed_buttons, update_properties, update_threads, update_busy_layouts};
+use crate::scheduler::actions::update_actions;
+use crate::scheduler::dirty::update_dirty_tracking;
+use crate::scheduler::undo::update_undo_history;
+#[cfg(feature = "control")]
+use crate::run::control::handle_control_requests;
+#[cfg(feature = "tokio")]
+use crate::scheduler::async_tasks::update_async_tasks;
 use crate::states::ez_state::GenericState;
-use crate::widgets::ez_object::EzObject;
+use crate::widgets::ez_object::EzObjects;
 use crate::widgets::layout::layout::Layout;
-use crate::{CallbackConfig, KeyMap};
-use crate::scheduler::definitions::CustomDataMap;
+use crate::KeyMap;
+use crate::scheduler::definitions::{
+    Context, CustomDataMap, EventControl, EventPhase, ExitTeardown,
+};
 
-use super::input::{handle_global_event, handle_modal_event, handle_resize};
-use super::terminal::{initialize_terminal, shutdown_terminal};
+use super::input::{
+    get_event_path, handle_drop, handle_global_event, handle_modal_event, handle_resize,
+    propagate_event, update_interaction_states,
+};
 
 /// This function starts the terminal app.
 ///
@@ -37,23 +58,59 @@ use super::terminal::{initialize_terminal, shutdown_terminal};
 /// ```
 /// run(root_widget, state_tree, scheduler, custom_data);
 /// ```
-pub fn run(root_widget: Layout, state_tree: StateTree, scheduler: SchedulerFrontend,
+pub fn run(root_widget: Layout, state_tree: StateTree, mut scheduler: SchedulerFrontend,
            custom_data: CustomDataMap) {
-    initialize_terminal().unwrap();
-    let callback_tree = initialize_callback_tree(&root_widget);
-    run_loop(root_widget, state_tree, callback_tree, scheduler, custom_data).unwrap();
+    #[cfg(unix)]
+    listen_for_signals(&mut scheduler);
+    run_with_terminal(root_widget, state_tree, scheduler, custom_data, Terminal::stdout());
+    exit(0);
 }
 
-/// Gracefully stop the app, restoring the terminal to its' original state.
-pub fn stop() {
-    shutdown_terminal().unwrap();
-    exit(0);
+/// Run the app on a specific [Terminal] instead of the terminal of the process. Unlike [run] this
+/// function returns when the app exits (see [SchedulerFrontend.exit]) after restoring the
+/// terminal, and signals are not handled, as they apply to the whole process. Use it to drive
+/// multiple independent UIs from a single process, e.g. one per SSH client, each in its' own
+/// thread with its' own PTY:
+/// ```no_run
+/// use std::fs::OpenOptions;
+/// use ez_term::*;
+///
+/// // Events of the PTY (e.g. parsed from the input of the SSH client) are sent through an
+/// // injector
+/// let (input, injector) = ChannelInputSource::new();
+/// let pty = OpenOptions::new().write(true).open("/dev/pts/3").unwrap();
+/// let terminal = Terminal::new(Box::new(pty), Box::new(input), 80, 24);
+///
+/// let (root_widget, state_tree, scheduler, custom_data) = load_ui();
+/// run_with_terminal(root_widget, state_tree, scheduler, custom_data, terminal);
+/// ```
+pub fn run_with_terminal(mut root_widget: Layout, mut state_tree: StateTree,
+                         scheduler: SchedulerFrontend, custom_data: CustomDataMap,
+                         mut terminal: Terminal) {
+    if let Some((width, height)) = terminal.get_size() {
+        root_widget.state.get_size_mut().set_width(width);
+        root_widget.state.get_size_mut().set_height(height);
+        let state = state_tree.get_mut(&root_widget.path).as_generic_mut();
+        state.get_size_mut().set_width(width);
+        state.get_size_mut().set_height(height);
+    }
+    terminal.initialize().unwrap();
+    let callback_tree = initialize_callback_tree(&root_widget);
+    let teardown =
+        run_loop(root_widget, state_tree, callback_tree, scheduler, custom_data, &mut terminal)
+            .unwrap();
+    terminal.shutdown_after_exit(&teardown).unwrap();
 }
 
 /// Called just before [run]. Creates initial view- and state trees and writes initial content
 /// to the screen.
-fn initialize_widgets(root_widget: &mut Layout, state_tree: &mut StateTree) -> ViewTree {
-    let all_content = root_widget.get_contents(state_tree);
+fn initialize_widgets(
+    root_widget: &mut Layout,
+    state_tree: &mut StateTree,
+    scheduler: &mut SchedulerFrontend,
+    terminal: &mut Terminal,
+) -> ViewTree {
+    let all_content = get_root_contents(root_widget, state_tree, scheduler);
     root_widget.propagate_absolute_positions(state_tree);
 
     // Create an initial view tree so we can diff all future changes against it.
@@ -63,29 +120,33 @@ fn initialize_widgets(root_widget: &mut Layout, state_tree: &mut StateTree) -> V
         root_widget.state.get_size().get_height(),
     );
     view_tree.write_content(Coordinates::new(0, 0), all_content);
-    write_to_screen(&mut view_tree);
+    terminal.write_to_screen(&mut view_tree);
     view_tree
 }
 
-/// Support function for opening a popup. After opening the actual popup in the root layout the
-/// state tree is extended with the new modal widget state, and the same is done for the callback
-/// tree.
+/// Support function for opening a popup. After opening the actual popup in the root layout (on
+/// top of any open popups) the state tree is extended with the new modal widget state, and the
+/// same is done for the callback tree. The selected widget is deselected, and selected again when
+/// the popup is dismissed.
 pub fn open_and_register_modal(
     template: String,
     state_tree: &mut StateTree,
     scheduler: &mut SchedulerFrontend,
 ) {
-    let state = state_tree.as_layout_mut();
-    if state.has_modal() {
-        state.dismiss_modal(scheduler);
+    let selection = get_selected_path(state_tree);
+    if !selection.is_empty() {
+        scheduler.deselect_widget();
     }
+    let state = state_tree.as_layout_mut();
     state.update(scheduler);
-    let mut new_states = state.open_modal_from_template(template, scheduler);
+    let modal_path = format!("/root/{}", state.get_next_modal_id());
+    let mut new_states = state.open_modal_from_template(template, selection, scheduler);
     new_states.reverse();
     for (path, new_state) in new_states {
         state_tree.add_node(path.clone(), new_state);
-        scheduler.overwrite_callback_config(&path, CallbackConfig::default());
+        scheduler.overwrite_callback_config(&path, get_dialog_callback_config(&path));
     }
+    trigger_update_funcs_of(scheduler, state_tree, &modal_path);
 }
 
 /// Main loop of the app. Consumes Crossterm events to handle key/mouse input. The app works with
@@ -115,8 +176,15 @@ fn run_loop(
     mut callback_tree: CallbackTree,
     mut scheduler: SchedulerFrontend,
     mut custom_data: CustomDataMap,
-) -> Result<()> {
-    let mut view_tree = initialize_widgets(&mut root_widget, &mut state_tree);
+    terminal: &mut Terminal,
+) -> Result<ExitTeardown> {
+    let started = Instant::now();
+    let mut view_tree =
+        initialize_widgets(&mut root_widget, &mut state_tree, &mut scheduler, terminal);
+    scheduler
+        .backend
+        .startup_profile
+        .add_phase("initialize widgets", started);
     let last_update = Instant::now(); // Time of last screen update,
     let mut last_mouse_pos: (u16, u16) = (0, 0); // To ignore move events if pos is not different
     let mut last_key_event = Instant::now();
@@ -131,12 +199,56 @@ fn run_loop(
 
     let mut consumed;
     loop {
+        handle_signals(&mut state_tree, &mut scheduler, &mut custom_data, terminal);
+        if scheduler.backend.stopped {
+            // The teardown callback may have changed widgets in the frame it finished
+            if scheduler.backend.exit_teardown.final_frame {
+                let contents = get_root_contents(&mut root_widget, &mut state_tree, &mut scheduler);
+                view_tree.write_content(Coordinates::new(0, 0), contents);
+                terminal.write_to_screen(&mut view_tree);
+            }
+            return Ok(scheduler.backend.exit_teardown.clone());
+        }
 
-        // We check for and deal with a possible event
-        if poll(Duration::from_millis(scheduler.backend.tick_rate))? {
+        if scheduler.is_exiting() {
+            // Input is ignored while the app tears down
+            terminal.poll_event(Duration::from_millis(scheduler.backend.tick_rate))?;
+        } else if let Some(event) = scheduler.next_macro_event() {
+            // Events of a played macro are replayed before new terminal input is read
+            dispatch_event(
+                event,
+                false,
+                &root_widget,
+                &mut state_tree,
+                &mut callback_tree,
+                &mut scheduler,
+                &mut custom_data,
+                &mut selected_widget,
+                &mut hovered_widget,
+                &mut dragging,
+                &mut last_dragging_pos,
+                &mut global_keymap,
+            );
+        } else if let Some(event) = scheduler.next_input_source_event() {
+            dispatch_event(
+                event,
+                false,
+                &root_widget,
+                &mut state_tree,
+                &mut callback_tree,
+                &mut scheduler,
+                &mut custom_data,
+                &mut selected_widget,
+                &mut hovered_widget,
+                &mut dragging,
+                &mut last_dragging_pos,
+                &mut global_keymap,
+            );
+        } else if let Some(mut event) =
+            terminal.poll_event(Duration::from_millis(scheduler.backend.tick_rate))?
+        {
+            // The event can only be consumed once
             consumed = false;
-            // Get the event; it can only be consumed once
-            let mut event = read().unwrap();
 
             if let Event::Key(_) = event {
                 if last_key_event.elapsed() <
@@ -152,20 +264,17 @@ fn run_loop(
             if let Event::Mouse(mouse_event) = event {
                 if let MouseEventKind::Moved = mouse_event.kind {
                     let pos = (mouse_event.column, mouse_event.row);
-                    while let Ok(true) = poll(Duration::from_millis(1)) {
-                        let spam_event = read();
-                        if let Ok(Event::Mouse(spam_mouse_event)) = spam_event {
+                    while let Ok(Some(spam_event)) = terminal.poll_event(Duration::from_millis(1)) {
+                        if let Event::Mouse(spam_mouse_event) = spam_event {
+                            event = spam_event;
                             if let MouseEventKind::Moved = spam_mouse_event.kind {
-                                event = spam_event.unwrap();
                                 if last_mouse_pos != pos {
                                     last_mouse_pos = pos;
                                     break;
                                 }
-                            } else {
-                                event = spam_event.unwrap();
                             }
                         } else {
-                            event = spam_event.unwrap();
+                            event = spam_event;
                             break;
                         }
                     }
@@ -174,11 +283,10 @@ fn run_loop(
             if let Event::Mouse(mouse_event) = event {
                 if let MouseEventKind::Drag(_) = mouse_event.kind {
                     let pos = (mouse_event.column, mouse_event.row);
-                    while let Ok(true) = poll(Duration::from_millis(1)) {
-                        let spam_event = read();
-                        if let Ok(Event::Mouse(spam_mouse_event)) = spam_event {
+                    while let Ok(Some(spam_event)) = terminal.poll_event(Duration::from_millis(1)) {
+                        if let Event::Mouse(spam_mouse_event) = spam_event {
                             if let MouseEventKind::Drag(button) = spam_mouse_event.kind {
-                                event = spam_event.unwrap();
+                                event = spam_event;
                                 if last_mouse_pos != pos {
                                     last_mouse_pos = pos;
                                     if button != MouseButton::Left {
@@ -188,65 +296,27 @@ fn run_loop(
                                 }
                             }
                         } else {
-                            event = spam_event.unwrap();
+                            event = spam_event;
                             break;
                         }
                     }
                 }
             }
 
-            if dragging.is_some() {
-                if let Event::Mouse(mouse_event) = event {
-                    if !(mouse_event.kind == MouseEventKind::Drag(MouseButton::Left)) {
-                        handle_drag_exit(&mut state_tree, &mut callback_tree, &mut scheduler,
-                                         &mut custom_data, &mouse_event, &root_widget,
-                                         Some(last_dragging_pos), &mut dragging)
-                    }
-                }
-            }
-
-            // Modals get top priority in consuming events
-            if !consumed {
-                consumed = handle_modal_event(
-                    event,
-                    &mut state_tree,
-                    &root_widget,
-                    &mut callback_tree,
-                    &mut scheduler,
-                    &mut custom_data,
-                );
-            }
-
-            // Try to handle event as a global event
-            if !consumed {
-                consumed = handle_global_event(
-                    event,
-                    &mut state_tree,
-                    &root_widget,
-                    &mut callback_tree,
-                    &mut scheduler,
-                    &mut custom_data,
-                    &mut selected_widget,
-                    &mut dragging,
-                    &mut last_dragging_pos,
-                    &mut global_keymap,
-                    &mut hovered_widget,
-                );
-            }
-            // Try to let currently selected widget handle and consume the event
-            if !consumed && !selected_widget.is_empty() {
-                if let Some(widget) = root_widget.get_child_by_path(&selected_widget) {
-                    if !state_tree.get(&selected_widget).as_generic().get_disabled() {
-                        consumed = widget.as_ez_object().handle_event(
-                            event,
-                            &mut state_tree,
-                            &mut callback_tree,
-                            &mut scheduler,
-                            &mut custom_data
-                        );
-                    }
-                }
-            }
+            consumed = dispatch_event(
+                event.clone(),
+                consumed,
+                &root_widget,
+                &mut state_tree,
+                &mut callback_tree,
+                &mut scheduler,
+                &mut custom_data,
+                &mut selected_widget,
+                &mut hovered_widget,
+                &mut dragging,
+                &mut last_dragging_pos,
+                &mut global_keymap,
+            );
             if !consumed {
                 if let Event::Resize(width, height) = event {
                     let current_size = state_tree.get(&root_widget.path).as_generic().get_size();
@@ -257,6 +327,8 @@ fn run_loop(
                             &mut view_tree,
                             &mut state_tree,
                             &mut root_widget,
+                            &mut scheduler,
+                            terminal,
                             width as usize,
                             height as usize,
                         );
@@ -270,57 +342,82 @@ fn run_loop(
             continue;
         }
 
-        // Update scheduler
-        scheduler._check_method_channels(&mut state_tree);
-        remove_widgets(
-            &mut scheduler,
+        // Widgets updated while handling input are redrawn before background updates
+        let input_updates = scheduler.backend.widgets_to_update.clone();
+        update_scheduler(
             &mut root_widget,
             &mut state_tree,
             &mut callback_tree,
-        );
-        create_new_widgets(&mut scheduler, &mut root_widget, &mut callback_tree);
-        selected_widget = handle_next_selection(
             &mut scheduler,
             &mut custom_data,
-            &mut state_tree,
-            &root_widget,
-            &mut callback_tree,
-            selected_widget,
+            &mut selected_widget,
+            &mut global_keymap,
         );
-        add_custom_data(&mut scheduler, &mut custom_data);
-        update_callback_configs(&mut scheduler, &mut callback_tree, &mut global_keymap);
-        add_property_callbacks(&mut scheduler, &mut callback_tree);
-        run_tasks(&mut scheduler, &mut state_tree, &mut custom_data);
-        update_threads(&mut scheduler, &mut state_tree, &mut custom_data);
-        update_properties(&mut scheduler, &mut state_tree, &mut callback_tree, &mut custom_data);
-        // Update root widget state as it might contain new modals it need to access internally
-        if state_tree.as_layout().open_modal.is_some() && root_widget.state.open_modal.is_none() {
-            root_widget.state.open_modal = state_tree.as_layout().open_modal.clone();
-        } else if state_tree.as_layout().open_modal.is_none()
-            && root_widget.state.open_modal.is_some()
-        {
-            root_widget.state.open_modal = None;
-        }
 
-        // Redraw individual widgets or the entire screen in case of forced_redraw. If the entire
-        // Screen is redrawn individual widgets are not redrawn.
-        let forced_redraw = if !scheduler.backend.force_redraw {
-            redraw_changed_widgets(
-                &mut view_tree,
-                &mut state_tree,
-                &mut root_widget,
-                &mut scheduler.backend.widgets_to_update,
-                scheduler.backend.force_redraw,
-            )
-        } else {
-            true
-        };
-        if forced_redraw {
-            let contents = root_widget.get_contents(&mut state_tree);
-            view_tree.write_content(Coordinates::new(0, 0), contents);
+        // When nothing changed this frame the draw pass is skipped entirely to save CPU at idle
+        if is_frame_dirty(&mut scheduler) {
+            // Redraw individual widgets or the entire screen in case of forced_redraw. If the
+            // entire Screen is redrawn individual widgets are not redrawn. While the terminal is
+            // too small to show the UI nothing is redrawn until the next forced redraw.
+            if scheduler.backend.terminal_too_small.is_some() {
+                scheduler.backend.widgets_to_update.clear();
+                scheduler.backend.cells_to_update.clear();
+                scheduler.backend.deferred_updates.clear();
+            }
+            let forced_redraw = if !scheduler.backend.force_redraw {
+                redraw_changed_cells(
+                    &mut view_tree,
+                    &mut state_tree,
+                    &root_widget,
+                    &mut scheduler.backend.cells_to_update,
+                    &mut scheduler.backend.widgets_to_update,
+                );
+                let mut background = split_background_updates(
+                    &state_tree,
+                    &mut scheduler.backend.widgets_to_update,
+                    &mut scheduler.backend.deferred_updates,
+                    &input_updates,
+                    &selected_widget,
+                );
+                let forced_redraw = redraw_changed_widgets(
+                    &mut view_tree,
+                    &mut state_tree,
+                    &mut root_widget,
+                    &mut scheduler.backend.widgets_to_update,
+                    scheduler.backend.force_redraw,
+                );
+                let forced_redraw = forced_redraw
+                    || redraw_background_widgets(
+                        &mut background,
+                        &mut view_tree,
+                        &mut state_tree,
+                        &mut root_widget,
+                        scheduler.backend.background_update_budget,
+                        terminal,
+                    );
+                scheduler.backend.deferred_updates = background;
+                forced_redraw
+            } else {
+                true
+            };
+            scheduler.backend.cells_to_update.clear();
+            if forced_redraw {
+                scheduler.backend.deferred_updates.clear();
+                if scheduler.backend.screen_cleared {
+                    let size = state_tree.get(&root_widget.path).as_generic().get_size();
+                    view_tree.initialize(size.get_width(), size.get_height());
+                    scheduler.backend.screen_cleared = false;
+                }
+                let contents = get_root_contents(&mut root_widget, &mut state_tree, &mut scheduler);
+                view_tree.write_content(Coordinates::new(0, 0), contents);
+            }
+            terminal.write_to_screen(&mut view_tree);
+            render_widgets_to_string(&mut scheduler, &mut state_tree, &mut custom_data,
+                                     &view_tree);
+            terminal.update_cursor(&state_tree, &selected_widget,
+                                   &mut scheduler.backend.terminal_cursor);
+            scheduler.backend.force_redraw = false;
         }
-        write_to_screen(&mut view_tree);
-        scheduler.backend.force_redraw = false;
 
         // Every now and then we perform cleanup of orphaned states (e.g. modals that were closed)
         // and their properties.
@@ -340,6 +437,312 @@ fn run_loop(
 }
 
 
+/// Returns a bool representing whether anything changed since the last frame that may have to be
+/// drawn: a property changed, a task ran or input was handled (see [Scheduler.dirty]), or
+/// widgets have to be redrawn. Resets the dirty flag.
+fn is_frame_dirty(scheduler: &mut SchedulerFrontend) -> bool {
+    let backend = &mut scheduler.backend;
+    std::mem::take(&mut backend.dirty)
+        || backend.force_redraw
+        || !backend.widgets_to_update.is_empty()
+        || !backend.cells_to_update.is_empty()
+        || !backend.deferred_updates.is_empty()
+}
+
+/// Pass an event to the widgets that can consume it: an ongoing drag is ended if the event is not
+/// a left mouse drag, then the event is offered to the open modal. Otherwise the event goes
+/// through the capture, target and bubble phases (see [EventPhase]); during the target phase it is
+/// offered to the global handlers and the selected widget, in that order. Returns whether the
+/// event was consumed. If 'consumed' is
+/// already true only the drag is ended. Used by the run loop and by [TestUi].
+pub(crate) fn dispatch_event(
+    event: Event,
+    consumed: bool,
+    root_widget: &Layout,
+    state_tree: &mut StateTree,
+    callback_tree: &mut CallbackTree,
+    scheduler: &mut SchedulerFrontend,
+    custom_data: &mut CustomDataMap,
+    selected_widget: &mut String,
+    hovered_widget: &mut String,
+    dragging: &mut Option<String>,
+    last_dragging_pos: &mut IsizeCoordinates,
+    global_keymap: &mut KeyMap,
+) -> bool {
+    // Record the event if a macro is being recorded. Replayed events and resizes are not recorded
+    if !consumed && !scheduler.backend.replaying_macro && !matches!(event, Event::Resize(_, _)) {
+        if let Some((_, events)) = scheduler.backend.macro_recording.as_mut() {
+            events.push(event.clone());
+        }
+    }
+
+    scheduler.backend.dirty = true;
+
+    // Any input resets the idle timer, see SchedulerFrontend.set_on_idle
+    if matches!(event, Event::Key(_) | Event::Mouse(_) | Event::Paste(_)) {
+        scheduler.backend.last_activity = Some(scheduler.now());
+        scheduler.backend.idle_fired.clear();
+    }
+
+    // Give the input filter a chance to swallow or change the event before anything else
+    let event = if consumed {
+        event
+    } else {
+        match filter_event(event, state_tree, scheduler, custom_data) {
+            Some(i) => i,
+            None => return true,
+        }
+    };
+
+    // Keep track of held keys, so scrolling and sliders can speed up while a key is held
+    if let (false, Event::Key(key)) = (consumed, &event) {
+        let now = scheduler.now();
+        let acceleration = scheduler.backend.key_acceleration;
+        scheduler
+            .backend
+            .key_repeat
+            .register((key.code, key.modifiers), now, &acceleration);
+    }
+
+    // Remember the event while it is handled, so callbacks can access it through their context
+    scheduler.backend.current_event = Some(event.clone());
+    scheduler.backend.event_control = EventControl::new(EventPhase::Capture);
+    let consumed = handle_event(
+        event,
+        consumed,
+        root_widget,
+        state_tree,
+        callback_tree,
+        scheduler,
+        custom_data,
+        selected_widget,
+        hovered_widget,
+        dragging,
+        last_dragging_pos,
+        global_keymap,
+    );
+    scheduler.backend.current_event = None;
+    scheduler.backend.event_control = EventControl::default();
+    scheduler.backend.replaying_macro = false;
+    consumed
+}
+
+/// Pass an event to the input filter set on the scheduler, if any. Returns the event to handle,
+/// or None if the filter swallowed it.
+fn filter_event(
+    event: Event,
+    state_tree: &mut StateTree,
+    scheduler: &mut SchedulerFrontend,
+    custom_data: &mut CustomDataMap,
+) -> Option<Event> {
+    if let Some(mut filter) = scheduler.backend.input_filter.take() {
+        scheduler.backend.current_event = Some(event.clone());
+        let context = Context::new("".to_string(), state_tree, scheduler, custom_data);
+        let result = filter(context, event);
+        scheduler.backend.current_event = None;
+        // Restore the filter unless it was replaced or removed from within the filter
+        if scheduler.backend.input_filter.is_none() {
+            scheduler.backend.input_filter = Some(filter);
+        }
+        result
+    } else {
+        Some(event)
+    }
+}
+
+/// Pass an event to the widgets that can consume it, see [dispatch_event].
+fn handle_event(
+    event: Event,
+    consumed: bool,
+    root_widget: &Layout,
+    state_tree: &mut StateTree,
+    callback_tree: &mut CallbackTree,
+    scheduler: &mut SchedulerFrontend,
+    custom_data: &mut CustomDataMap,
+    selected_widget: &mut String,
+    hovered_widget: &mut String,
+    dragging: &mut Option<String>,
+    last_dragging_pos: &mut IsizeCoordinates,
+    global_keymap: &mut KeyMap,
+) -> bool {
+    if dragging.is_some() {
+        if let Event::Mouse(mouse_event) = event {
+            if mouse_event.kind != MouseEventKind::Drag(MouseButton::Left) {
+                handle_drag_exit(state_tree, callback_tree, scheduler, custom_data, &mouse_event,
+                                 root_widget, Some(*last_dragging_pos), dragging)
+            }
+        }
+    }
+    // Hover and pressed colors follow the mouse, whichever widget handles the event
+    if let Event::Mouse(mouse_event) = event {
+        update_interaction_states(&mouse_event, state_tree, scheduler);
+    }
+    if consumed {
+        return true;
+    }
+
+    // Layout tuning is a developer tool, so it comes before anything else in the app
+    if handle_layout_tuning_event(&event, root_widget, state_tree, scheduler, selected_widget) {
+        return true;
+    }
+
+    // Modals get top priority in consuming events
+    if handle_modal_event(
+        event.clone(),
+        state_tree,
+        root_widget,
+        callback_tree,
+        scheduler,
+        custom_data,
+    ) {
+        return true;
+    }
+
+    // Capture phase: the event travels from the root layout to its' target
+    let event_path = get_event_path(&event, root_widget, state_tree, selected_widget);
+    if propagate_event(EventPhase::Capture, &event_path, state_tree, callback_tree, scheduler,
+                       custom_data) {
+        return true;
+    }
+
+    // Target phase: the widgets handle the event themselves, unless a capture callback prevented
+    // this
+    if !scheduler.backend.event_control.is_default_prevented() {
+        scheduler.backend.event_control.phase = EventPhase::Target;
+        if handle_target_event(
+            event,
+            root_widget,
+            state_tree,
+            callback_tree,
+            scheduler,
+            custom_data,
+            selected_widget,
+            hovered_widget,
+            dragging,
+            last_dragging_pos,
+            global_keymap,
+        ) || scheduler.backend.event_control.is_propagation_stopped() {
+            return true;
+        }
+    }
+
+    // Bubble phase: the event travels from its' target back to the root layout
+    let bubble_path: Vec<String> = event_path.into_iter().rev().collect();
+    propagate_event(EventPhase::Bubble, &bubble_path, state_tree, callback_tree, scheduler,
+                    custom_data)
+}
+
+/// Let the widgets handle an event themselves: first as a global event (e.g. selecting the next
+/// widget or clicking the widgets under the mouse), then by the selected widget.
+fn handle_target_event(
+    event: Event,
+    root_widget: &Layout,
+    state_tree: &mut StateTree,
+    callback_tree: &mut CallbackTree,
+    scheduler: &mut SchedulerFrontend,
+    custom_data: &mut CustomDataMap,
+    selected_widget: &mut String,
+    hovered_widget: &mut String,
+    dragging: &mut Option<String>,
+    last_dragging_pos: &mut IsizeCoordinates,
+    global_keymap: &mut KeyMap,
+) -> bool {
+    // Try to handle event as a global event
+    if handle_global_event(
+        event.clone(),
+        state_tree,
+        root_widget,
+        callback_tree,
+        scheduler,
+        custom_data,
+        selected_widget,
+        dragging,
+        last_dragging_pos,
+        global_keymap,
+        hovered_widget,
+    ) {
+        return true;
+    }
+
+    // Try to let currently selected widget handle and consume the event
+    if !selected_widget.is_empty() {
+        if let Some(widget) = root_widget.get_child_by_path(selected_widget) {
+            // The state is gone if handling the event removed the widget, e.g. by dismissing the
+            // modal it was in
+            let disabled = state_tree
+                .try_get(selected_widget)
+                .map(|x| x.obj.as_generic().get_disabled());
+            if disabled == Some(false) {
+                return widget.as_ez_object().handle_event(
+                    event,
+                    state_tree,
+                    callback_tree,
+                    scheduler,
+                    custom_data
+                );
+            }
+        }
+    }
+    false
+}
+
+/// Process everything the scheduler has queued since the last frame: widget changes, selection
+/// changes, callback configs, tasks, threads and property updates. Used by the run loop and by
+/// [TestUi].
+pub(crate) fn update_scheduler(
+    root_widget: &mut Layout,
+    state_tree: &mut StateTree,
+    callback_tree: &mut CallbackTree,
+    scheduler: &mut SchedulerFrontend,
+    custom_data: &mut CustomDataMap,
+    selected_widget: &mut String,
+    global_keymap: &mut KeyMap,
+) {
+    scheduler._check_method_channels(state_tree);
+    remove_widgets(scheduler, root_widget, state_tree, callback_tree);
+    clone_widgets(scheduler, root_widget, state_tree);
+    move_widgets(scheduler, root_widget, state_tree, callback_tree, selected_widget);
+    swap_children(scheduler, root_widget, state_tree);
+    load_lazy_widgets(scheduler, state_tree);
+    create_new_widgets(scheduler, root_widget, callback_tree);
+    *selected_widget = handle_next_selection(
+        scheduler,
+        custom_data,
+        state_tree,
+        root_widget,
+        callback_tree,
+        std::mem::take(selected_widget),
+    );
+    add_custom_data(scheduler, custom_data);
+    update_callback_configs(scheduler, callback_tree, global_keymap);
+    add_property_callbacks(scheduler, callback_tree);
+    run_tasks(scheduler, state_tree, custom_data);
+    update_idle(scheduler, state_tree, callback_tree, custom_data);
+    update_undo_history(scheduler, state_tree, custom_data);
+    update_dirty_tracking(scheduler, state_tree);
+    update_actions(scheduler, state_tree);
+    #[cfg(feature = "control")]
+    handle_control_requests(root_widget, state_tree, callback_tree, scheduler, custom_data);
+    update_pressed_buttons(scheduler, state_tree, callback_tree, custom_data);
+    update_threads(scheduler, state_tree, custom_data);
+    update_busy_layouts(scheduler, state_tree);
+    #[cfg(feature = "tokio")]
+    update_async_tasks(scheduler, state_tree, custom_data);
+    update_properties(scheduler, state_tree, callback_tree, custom_data);
+    update_modal_result(scheduler, state_tree, custom_data);
+    update_exit_request(scheduler, state_tree, custom_data);
+    update_teardown(scheduler, state_tree, custom_data);
+    // Update root widget state as it might contain new modals it need to access internally
+    let modal_paths = |modals: &Vec<EzObjects>| -> Vec<String> {
+        modals.iter().map(|x| x.as_ez_object().get_path()).collect()
+    };
+    if modal_paths(&state_tree.as_layout().open_modals)
+        != modal_paths(&root_widget.state.open_modals)
+    {
+        root_widget.state.open_modals = state_tree.as_layout().open_modals.clone();
+    }
+}
+
 fn handle_drag_exit(
     state_tree: &mut StateTree,
     callback_tree: &mut CallbackTree,
@@ -350,6 +753,14 @@ fn handle_drag_exit(
     last_dragging_pos: Option<IsizeCoordinates>,
     dragging: &mut Option<String>) {
 
+    handle_drop(
+        Coordinates::new(mouse_event.column as usize, mouse_event.row as usize),
+        state_tree,
+        root_widget,
+        callback_tree,
+        scheduler,
+        custom_data,
+    );
     let widget_path = dragging.as_ref().unwrap();
     let abs = state_tree
         .get(&widget_path)
@@ -3,12 +3,15 @@
 //!
 //! Widgets can be selected by keyboard (next/previous widget) or mouse (widget under mouse_pos).
 //! This module provides functions to handle that.
-use crate::run::definitions::{CallbackTree, Coordinates, StateTree};
+use std::cmp::Reverse;
+
+use crate::run::definitions::{CallbackTree, Coordinates, Size, StateTree};
 use crate::scheduler::definitions::CustomDataMap;
 use crate::scheduler::scheduler::SchedulerFrontend;
 use crate::states::definitions::LayoutMode;
 use crate::states::ez_state::{EzState, GenericState};
-use crate::widgets::ez_object::EzObject;
+use crate::states::layout_state::LayoutState;
+use crate::widgets::ez_object::{EzObject, EzObjects};
 use crate::widgets::layout::layout::Layout;
 
 /// Handle a widget being selected.
@@ -21,6 +24,10 @@ pub fn select_widget(
     custom_data: &mut CustomDataMap,
     mouse_pos: Option<Coordinates>,
 ) {
+    // Widgets selected by keyboard can be scrolled out of view
+    if mouse_pos.is_none() {
+        scroll_into_view(path, state_tree, root_widget, scheduler);
+    }
     let state = state_tree.get_mut(path).as_generic_mut();
     state.set_selected(true);
     state.update(scheduler);
@@ -31,6 +38,38 @@ pub fn select_widget(
         .on_select(state_tree, callback_tree, scheduler, mouse_pos, custom_data);
 }
 
+/// Scroll each scrolling layout a widget is in so the widget is visible, starting with the
+/// innermost layout.
+pub fn scroll_into_view(
+    path: &str,
+    state_tree: &mut StateTree,
+    root_widget: &Layout,
+    scheduler: &mut SchedulerFrontend,
+) {
+    let mut parent_path = path;
+    while let Some((parent, _)) = parent_path.rsplit_once('/') {
+        if parent.is_empty() {
+            break;
+        }
+        let layout = if parent == root_widget.path {
+            Some(root_widget)
+        } else if let Some(EzObjects::Layout(i)) = root_widget.get_child_by_path(parent) {
+            Some(i)
+        } else {
+            None
+        };
+        if let Some(layout) = layout {
+            let state = state_tree.get(path).as_generic();
+            let (pos, size) = (
+                state.get_absolute_position(),
+                Size::from_state_size(state.get_size()),
+            );
+            layout.scroll_into_view(state_tree, scheduler, pos, size);
+        }
+        parent_path = parent;
+    }
+}
+
 /// Handle a widget being deselected.
 pub fn deselect_widget(
     path: &str,
@@ -53,6 +92,35 @@ pub fn deselect_widget(
     }
 }
 
+/// Get the path of the layout that keyboard selection and mouse events are limited to: the modal on
+/// top if any modal is open, otherwise the root layout.
+pub fn get_active_layout_path(state_tree: &StateTree) -> String {
+    let root_state = state_tree.as_layout();
+    if root_state.has_modal() {
+        root_state.get_modal().as_ez_object().get_path()
+    } else {
+        "/root".to_string()
+    }
+}
+
+/// Get the path of the currently selected widget, or an empty string if no widget is selected.
+pub fn get_selected_path(state_tree: &StateTree) -> String {
+    state_tree
+        .get_all()
+        .iter()
+        .find(|x| x.as_generic().get_selected())
+        .map(|x| x.as_generic().get_path().clone())
+        .unwrap_or_default()
+}
+
+/// Bool representing whether a path is the path of a layout or of one of its' descendants.
+fn is_in_layout(path: &str, layout_path: &str) -> bool {
+    path == layout_path
+        || path
+            .strip_prefix(layout_path)
+            .map_or(false, |x| x.starts_with('/'))
+}
+
 /// Select the next widget by selection order as defined in each selectable widget. If the last
 /// widget is currently selected wrap around and select the first. This function can always be
 /// called safely.
@@ -63,11 +131,7 @@ pub fn select_next(
     scheduler: &mut SchedulerFrontend,
     current_selection: &mut String,
 ) {
-    let path_prefix = if state_tree.as_layout().has_modal() {
-        "/root/modal".to_string()
-    } else {
-        "/root".to_string()
-    };
+    let path_prefix = get_active_layout_path(state_tree);
 
     let mut current_selection_order = if !current_selection.is_empty() {
         state_tree
@@ -102,7 +166,7 @@ pub fn find_next_selection(
     let mut next_widget: Option<String> = None;
     for state in state_tree.get_all().iter() {
         let state = state.as_generic();
-        if !state.get_path().starts_with(path_prefix) {
+        if !is_in_layout(state.get_path(), path_prefix) {
             continue;
         };
         let widget_order = state.get_selection_order();
@@ -112,7 +176,8 @@ pub fn find_next_selection(
             && widget_order > current_selection
             && (next_order == 0 || widget_order < next_order)
             && !widget_is_hidden(state.get_path().to_string(), state_tree)
-            && is_in_view(state.get_path().to_string(), state_tree)
+            && !widget_is_busy(state.get_path(), state_tree)
+            && is_in_view(state.get_path().to_string(), state_tree, true)
         {
             next_order = widget_order;
             next_widget = Some(state.get_path().to_string());
@@ -121,6 +186,94 @@ pub fn find_next_selection(
     next_widget
 }
 
+/// Select the next widget in document order (the order in which widgets are declared) when Tab
+/// is pressed. Unlike [select_next] this does not use selection order, so every selectable widget
+/// can be reached. After the last widget the first one is selected again, unless
+/// [LayoutState.focus_wrap] of the root layout is disabled. This function can always be called
+/// safely.
+pub fn focus_next(
+    state_tree: &StateTree,
+    root_widget: &Layout,
+    scheduler: &mut SchedulerFrontend,
+    current_selection: &str,
+) {
+    move_focus(true, state_tree, root_widget, scheduler, current_selection);
+}
+
+/// Select the previous widget in document order when Shift-Tab is pressed. Before the first widget
+/// the last one is selected again, unless [LayoutState.focus_wrap] of the root layout is disabled.
+/// This function can always be called safely.
+pub fn focus_previous(
+    state_tree: &StateTree,
+    root_widget: &Layout,
+    scheduler: &mut SchedulerFrontend,
+    current_selection: &str,
+) {
+    move_focus(false, state_tree, root_widget, scheduler, current_selection);
+}
+
+/// Select the widget after (or before) the current selection in the focus order, see
+/// [get_focus_order]. Without a selection the first (or last) widget is selected.
+fn move_focus(
+    forward: bool,
+    state_tree: &StateTree,
+    root_widget: &Layout,
+    scheduler: &mut SchedulerFrontend,
+    current_selection: &str,
+) {
+    let order = get_focus_order(state_tree, root_widget);
+    if order.is_empty() {
+        return;
+    }
+    let wrap = state_tree.as_layout().get_focus_wrap();
+    let last = order.len() - 1;
+    let next = match order.iter().position(|x| x == current_selection) {
+        Some(i) if forward && i < last => Some(i + 1),
+        Some(i) if !forward && i > 0 => Some(i - 1),
+        Some(_) if !wrap => None,
+        Some(_) | None if forward => Some(0),
+        Some(_) | None => Some(last),
+    };
+    if let Some(i) = next {
+        scheduler.set_selected_widget(&order[i], None);
+    }
+}
+
+/// Get the full paths of the widgets that can be selected with Tab, in document order. Widgets
+/// that are disabled, hidden (e.g. on an inactive tab) or outside of the open modal are skipped.
+pub fn get_focus_order(state_tree: &StateTree, root_widget: &Layout) -> Vec<String> {
+    let mut paths = Vec::new();
+    let root_state = state_tree.as_layout();
+    if root_state.has_modal() {
+        collect_document_order(root_state.get_modal(), &mut paths);
+    } else {
+        for child in root_widget.children.iter() {
+            collect_document_order(child, &mut paths);
+        }
+    }
+    paths
+        .into_iter()
+        .filter(|path| {
+            state_tree.try_get(path).map_or(false, |x| {
+                let state = x.obj.as_generic();
+                state.is_selectable() && !state.get_disabled()
+            }) && !widget_is_hidden(path.clone(), state_tree)
+                && !widget_is_busy(path, state_tree)
+                && is_in_view(path.clone(), state_tree, true)
+        })
+        .collect()
+}
+
+/// Add the path of a widget and the paths of all its' descendants to a list in document order.
+fn collect_document_order(object: &EzObjects, paths: &mut Vec<String>) {
+    paths.push(object.as_ez_object().get_path());
+    if let EzObjects::Layout(i) = object {
+        for child in i.children.iter() {
+            collect_document_order(child, paths);
+        }
+    }
+}
+
 /// Select the previous widget by selection order as defined in each selectable widget. If the first
 /// widget is currently selected wrap around and select the last. This function can always be
 /// called safely.
@@ -131,11 +284,7 @@ pub fn select_previous(
     scheduler: &mut SchedulerFrontend,
     current_selection: &mut String,
 ) {
-    let path_prefix = if state_tree.as_layout().has_modal() {
-        "/root/modal".to_string()
-    } else {
-        "/root".to_string()
-    };
+    let path_prefix = get_active_layout_path(state_tree);
 
     let mut current_selection_order = if !current_selection.is_empty() {
         state_tree
@@ -170,7 +319,7 @@ pub fn find_previous_selection(
     let mut previous_widget: Option<String> = None;
     for state in state_tree.get_all().iter() {
         let state = state.as_generic();
-        if !state.get_path().starts_with(path_prefix) {
+        if !is_in_layout(state.get_path(), path_prefix) {
             continue;
         }
         let widget_order = state.get_selection_order();
@@ -180,7 +329,8 @@ pub fn find_previous_selection(
             && (current_selection == 0 || widget_order < current_selection)
             && (previous_order == 0 || widget_order > previous_order)
             && !widget_is_hidden(state.get_path().to_string(), state_tree)
-            && is_in_view(state.get_path().to_string(), state_tree)
+            && !widget_is_busy(state.get_path(), state_tree)
+            && is_in_view(state.get_path().to_string(), state_tree, true)
         {
             previous_order = state.get_selection_order();
             previous_widget = Some(state.get_path().to_string());
@@ -196,47 +346,98 @@ pub fn get_widget_by_position<'a>(
     root_widget: &'a Layout,
     state_tree: &StateTree,
 ) -> Vec<&'a dyn EzObject> {
-    let path_prefix = if state_tree.as_layout().has_modal() {
-        "/root/modal".to_string()
-    } else {
-        "/root".to_string()
-    };
+    let mut results = Vec::new();
+    for path in get_paths_by_position(pos, state_tree) {
+        if let Some(i) = root_widget.get_child_by_path(&path) {
+            results.push(i.as_ez_object());
+        }
+    }
+    results.push(root_widget as &dyn EzObject); // Every pos collides the root widget
+    results
+}
+
+/// Get the paths of the widgets under a screen position coordinate, not including the root
+/// layout. If a modal is active only the modal is searched.
+pub fn get_paths_by_position(pos: Coordinates, state_tree: &StateTree) -> Vec<String> {
+    let path_prefix = get_active_layout_path(state_tree);
     let mut results = Vec::new();
     for state in state_tree.get_all() {
         let generic_state = state.as_generic();
-        if !generic_state.get_path().starts_with(&path_prefix)
+        if !is_in_layout(generic_state.get_path(), &path_prefix)
             || generic_state.get_path() == "/root"
             || generic_state.get_disabled()
             || widget_is_hidden(generic_state.get_path().clone(), state_tree)
+            || widget_is_busy(generic_state.get_path(), state_tree)
         {
             continue;
         }
-        if let EzState::Layout(ref i) = state {
-            if i.collides(pos) {
-                results.push(
-                    root_widget
-                        .get_child_by_path(generic_state.get_path())
-                        .unwrap()
-                        .as_ez_object(),
-                );
-            }
-        } else if state.as_generic().collides_effective(pos) {
-            if let Some(i) = root_widget.get_child_by_path(generic_state.get_path()) {
-                results.push(i.as_ez_object());
-            }
+        let collides = if let EzState::Layout(ref i) = state {
+            i.collides(pos)
         } else {
-            if state.as_generic().get_path().ends_with("map") {}
+            generic_state.collides_effective(pos)
+        };
+        if collides {
+            results.push(generic_state.get_path().clone());
         }
     }
-    results.push(root_widget as &dyn EzObject); // Every pos collides the root widget
     results
 }
 
+/// Get the path of the widget that mouse events on a screen position coordinate are targeted at:
+/// the deepest widget under the position, or the root layout if there is none. Where children of
+/// a float layout overlap, the one with the highest z index (and its' children) wins.
+pub fn get_target_by_position(pos: Coordinates, state_tree: &StateTree) -> String {
+    get_paths_by_position(pos, state_tree)
+        .into_iter()
+        .max_by_key(|x| get_z_index_chain(x, state_tree))
+        .unwrap_or_else(|| "/root".to_string())
+}
+
+/// Get the widgets under a screen position coordinate, starting with the widget drawn on top and
+/// ending with the root layout. The parents of the widget on top come before widgets in branches
+/// drawn under it, see [get_z_index_chain]. Used to offer a dropped [DragPayload] to the widget
+/// under the mouse first and to its' parents after.
+pub fn get_stacked_widgets_by_position<'a>(
+    pos: Coordinates,
+    root_widget: &'a Layout,
+    state_tree: &StateTree,
+) -> Vec<&'a dyn EzObject> {
+    let mut paths = get_paths_by_position(pos, state_tree);
+    paths.sort_by_key(|x| Reverse(get_z_index_chain(x, state_tree)));
+    let mut results = Vec::new();
+    for path in paths {
+        if let Some(i) = root_widget.get_child_by_path(&path) {
+            results.push(i.as_ez_object());
+        }
+    }
+    results.push(root_widget as &dyn EzObject);
+    results
+}
+
+/// Get the z indices of a widget and each of its' parents below the root layout, starting at the
+/// top. Comparing these chains orders widgets by the z index of the branch they are in first and
+/// by depth second, as a chain that extends another one compares greater.
+fn get_z_index_chain(path: &str, state_tree: &StateTree) -> Vec<usize> {
+    let mut chain = Vec::new();
+    let mut current = "/root".to_string();
+    for id in path.trim_start_matches("/root/").split('/') {
+        current = format!("{}/{}", current, id);
+        chain.push(
+            state_tree
+                .try_get(&current)
+                .map_or(0, |x| x.as_generic().get_z_index()),
+        );
+    }
+    chain
+}
+
 /// Determine whether a widget (by path) is in view. We start with the root widget and make our
 /// way down to the widget in question. We check whether the absolute pos of each widget is within
 /// the bounds of the window. If we encounter a scrollview along the way, we will check if each
-/// subsequent object is in bounds of the scrollview instead.
-pub fn is_in_view(path: String, state_tree: &StateTree) -> bool {
+/// subsequent object is in bounds of the scrollview instead. If [include_scrolled_out] is true, the
+/// whole content of a scrollview counts as in view, as it can be scrolled into view (e.g. to find
+/// widgets that can be selected by keyboard, see [scroll_into_view]).
+pub fn is_in_view(path: String, state_tree: &StateTree, include_scrolled_out: bool) -> bool {
     // If the widget belongs to a tab or screen that is not active, it is not in view
     let window_size = state_tree.as_generic().get_size().clone();
 
@@ -254,7 +455,10 @@ pub fn is_in_view(path: String, state_tree: &StateTree) -> bool {
     loop {
         // Loop from root widget to subwidget until we complete the full path or something is not in view
 
-        if working_path == "/root/modal" {
+        if working_path
+            .strip_prefix("/root/")
+            .map_or(false, LayoutState::is_modal_id)
+        {
             working_path = format!("{}/{}", working_path, paths.pop().unwrap());
             continue;
         }
@@ -351,33 +555,25 @@ pub fn is_in_view(path: String, state_tree: &StateTree) -> bool {
             // This is not the end of the path so this obj must be a layout. This means we have to
             // check if it is scrolling. If it is, we must check if each subsequent subwidget is in
             // this scrollview.
-            if state
-                .as_layout()
-                .get_scrolling_config()
-                .get_is_scrolling_x()
-            {
-                let scroll_start_x = state
-                    .as_layout()
-                    .get_scrolling_config()
-                    .get_absolute_scroll_start_x(state.as_generic().get_effective_size().width);
-                visible_width = Some((
-                    scroll_start_x,
-                    scroll_start_x + state.as_layout().get_effective_size().width,
-                ));
+            let scrolling_config = state.as_layout().get_scrolling_config();
+            let effective_size = state.as_layout().get_effective_size();
+            if scrolling_config.get_is_scrolling_x() {
+                visible_width = Some(if include_scrolled_out {
+                    (0, scrolling_config.get_original_width().max(effective_size.width))
+                } else {
+                    let scroll_start_x =
+                        scrolling_config.get_absolute_scroll_start_x(effective_size.width);
+                    (scroll_start_x, scroll_start_x + effective_size.width)
+                });
             }
-            if state
-                .as_layout()
-                .get_scrolling_config()
-                .get_is_scrolling_y()
-            {
-                let scroll_start_y = state
-                    .as_layout()
-                    .get_scrolling_config()
-                    .get_absolute_scroll_start_y(state.as_generic().get_effective_size().height);
-                visible_height = Some((
-                    scroll_start_y,
-                    scroll_start_y + state.as_layout().get_effective_size().height,
-                ));
+            if scrolling_config.get_is_scrolling_y() {
+                visible_height = Some(if include_scrolled_out {
+                    (0, scrolling_config.get_original_height().max(effective_size.height))
+                } else {
+                    let scroll_start_y =
+                        scrolling_config.get_absolute_scroll_start_y(effective_size.height);
+                    (scroll_start_y, scroll_start_y + effective_size.height)
+                });
             }
             working_path = format!("{}/{}", working_path, paths.pop().unwrap());
         } else {
@@ -387,6 +583,21 @@ pub fn is_in_view(path: String, state_tree: &StateTree) -> bool {
     }
 }
 
+/// Check if a widget is in a busy layout (see [LayoutState.set_busy]), which means it cannot be
+/// interacted with. The busy layout itself can be.
+pub fn widget_is_busy(widget_path: &str, state_tree: &StateTree) -> bool {
+    let mut check_parent = widget_path;
+    while let Some((parent, _)) = check_parent.rsplit_once('/') {
+        if let Some(EzState::Layout(i)) = state_tree.try_get(parent).map(|x| &x.obj) {
+            if i.get_busy() {
+                return true;
+            }
+        }
+        check_parent = parent;
+    }
+    false
+}
+
 /// Check if a widget is hidden, for example if it belongs to a tab or screan that is not active.
 pub fn widget_is_hidden(widget_path: String, state_tree: &StateTree) -> bool {
     if widget_path.starts_with("/root/modal") {
@@ -401,6 +612,12 @@ pub fn widget_is_hidden(widget_path: String, state_tree: &StateTree) -> bool {
         {
             return true;
         }
+        if parent_state.get_minimized()
+            || (parent_state.get_mode() == &LayoutMode::Dock
+                && state_tree.get(&check_child).as_generic().get_collapsed())
+        {
+            return true;
+        }
         if parent_state.get_mode() == &LayoutMode::Tab {
             if let EzState::Layout(_) = state_tree.get(&check_child).obj {
                 if parent_state.get_active_tab() != check_child {
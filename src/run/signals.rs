@@ -0,0 +1,104 @@
+//! # Signals
+//!
+//! A module handling signals sent to the app. SIGTSTP suspends the app: the terminal is restored
+//! before the app is stopped and the UI takes over the terminal again when the app is resumed.
+//! SIGINT and SIGTERM run the callbacks registered with [SchedulerFrontend.add_shutdown_callback]
+//! and then exit gracefully.
+//!
+//! While the UI runs the terminal is in raw mode, so pressing ctrl+c or ctrl+z does not send a
+//! signal. Unless these keys are bound as global keys, ctrl+z suspends the app the same as SIGTSTP
+//! and ctrl+c is a quit key like escape, see [SchedulerFrontend.exit].
+#[cfg(unix)]
+use std::sync::mpsc::channel;
+#[cfg(unix)]
+use std::thread;
+
+#[cfg(unix)]
+use signal_hook::consts::{SIGINT, SIGTERM, SIGTSTP};
+#[cfg(unix)]
+use signal_hook::iterator::Signals;
+
+use crate::run::definitions::StateTree;
+use crate::run::terminal::Terminal;
+use crate::scheduler::definitions::{Context, CustomDataMap};
+use crate::scheduler::scheduler::SchedulerFrontend;
+
+/// Start listening for SIGINT, SIGTERM and SIGTSTP in a background thread. Received signals are
+/// handled by the run loop through [handle_signals].
+#[cfg(unix)]
+pub(crate) fn listen_for_signals(scheduler: &mut SchedulerFrontend) {
+    let mut signals = Signals::new([SIGINT, SIGTERM, SIGTSTP])
+        .unwrap_or_else(|e| panic!("Could not listen for signals: {}", e));
+    let (sender, receiver) = channel();
+    scheduler.backend.signal_receiver = Some(receiver);
+    thread::spawn(move || {
+        for signal in signals.forever() {
+            if sender.send(signal).is_err() {
+                return;
+            }
+        }
+    });
+}
+
+/// Handle the signals received since the last frame, and suspend the app if ctrl+z was pressed.
+pub(crate) fn handle_signals(
+    state_tree: &mut StateTree,
+    scheduler: &mut SchedulerFrontend,
+    custom_data: &mut CustomDataMap,
+    terminal: &mut Terminal,
+) {
+    if std::mem::take(&mut scheduler.backend.suspend_requested) {
+        suspend(scheduler, terminal);
+    }
+    #[cfg(unix)]
+    {
+        let signals: Vec<i32> = match scheduler.backend.signal_receiver {
+            Some(ref receiver) => receiver.try_iter().collect(),
+            None => return,
+        };
+        for signal in signals {
+            if signal == SIGTSTP {
+                suspend(scheduler, terminal);
+            } else {
+                shutdown(state_tree, scheduler, custom_data);
+            }
+        }
+    }
+    #[cfg(not(unix))]
+    let _ = (state_tree, scheduler, custom_data, terminal);
+}
+
+/// Suspend the app, restoring the terminal first. When the app is resumed the terminal is
+/// prepared for the UI again and the screen is redrawn. Does nothing on platforms without job
+/// control, or if the UI does not run on the terminal of the process (see [Terminal]).
+pub(crate) fn suspend(scheduler: &mut SchedulerFrontend, terminal: &mut Terminal) {
+    #[cfg(unix)]
+    if terminal.is_stdout() {
+        terminal.shutdown().unwrap();
+        // Stops the process until it receives SIGCONT, even though we handle SIGTSTP ourselves
+        signal_hook::low_level::emulate_default_handler(SIGTSTP)
+            .unwrap_or_else(|e| panic!("Could not suspend the app: {}", e));
+        terminal.initialize().unwrap();
+        scheduler.backend.terminal_cursor = None;
+        scheduler.backend.screen_cleared = true;
+        scheduler.force_redraw();
+    }
+    #[cfg(not(unix))]
+    let _ = (scheduler, terminal);
+}
+
+/// Run the shutdown callbacks, then exit gracefully. The terminal is restored after the callbacks
+/// have run. The callback set with [SchedulerFrontend.set_on_exit] is not called, as the exit
+/// cannot be cancelled.
+pub(crate) fn shutdown(
+    state_tree: &mut StateTree,
+    scheduler: &mut SchedulerFrontend,
+    custom_data: &mut CustomDataMap,
+) {
+    let callbacks = std::mem::take(&mut scheduler.backend.shutdown_callbacks);
+    for mut callback in callbacks {
+        let context = Context::new(String::new(), state_tree, scheduler, custom_data);
+        callback(context);
+    }
+    scheduler.force_exit();
+}
@@ -0,0 +1,306 @@
+//! # Small terminal
+//!
+//! A module containing functions that keep the UI usable when the terminal is smaller than the
+//! UI needs. When the terminal gets too small, all borders and padding are dropped and layouts
+//! with [collapsible_on_small] are collapsed (compact mode). If the UI still does not fit, a
+//! "terminal too small" message with the required size is shown instead of the UI. The same
+//! message is shown if the terminal is smaller than the [min_terminal_size] of the root layout.
+use std::cmp::max;
+
+use crate::run::definitions::{
+    CompactBackup, CompactMode, Pixel, PixelMap, Size, StateTree,
+};
+use crate::run::tree::Tree;
+use crate::scheduler::scheduler::SchedulerFrontend;
+use crate::states::definitions::{Anchor, LayoutMode, LayoutOrientation};
+use crate::states::ez_state::{EzState, GenericState};
+use crate::states::layout_state::LayoutState;
+use crate::widgets::ez_object::EzObject;
+use crate::widgets::layout::layout::Layout;
+
+/// Get the contents of the root layout for a full redraw. Enters or leaves compact mode first
+/// depending on the current terminal size, and returns a "terminal too small" message instead of
+/// the UI if it does not fit even in compact mode, or if the terminal is smaller than the
+/// [min_terminal_size] of the root layout.
+pub fn get_root_contents(
+    root_widget: &mut Layout,
+    state_tree: &mut StateTree,
+    scheduler: &mut SchedulerFrontend,
+) -> PixelMap {
+    let terminal_size = state_tree
+        .get(&root_widget.path)
+        .as_generic()
+        .get_size()
+        .clone();
+    let terminal_size = Size::new(terminal_size.get_width(), terminal_size.get_height());
+
+    let root_state = state_tree.get(&root_widget.path).as_layout();
+    let min_size = Size::new(
+        root_state.get_min_terminal_width(),
+        root_state.get_min_terminal_height(),
+    );
+    if !fits(terminal_size, min_size) {
+        scheduler.backend.terminal_too_small = Some(min_size);
+        return get_too_small_message(state_tree, root_widget, terminal_size, min_size);
+    }
+
+    match scheduler.backend.compact_mode.as_ref() {
+        Some(compact) if fits(terminal_size, compact.required_size) => {
+            leave_compact_mode(state_tree, scheduler)
+        }
+        Some(_) => (),
+        None => {
+            let required_size = get_minimum_size(state_tree.get(&root_widget.path), false, true);
+            if !fits(terminal_size, required_size) {
+                enter_compact_mode(state_tree, scheduler, required_size);
+            }
+        }
+    }
+
+    scheduler.backend.terminal_too_small = None;
+    if scheduler.backend.compact_mode.is_some() {
+        let required_size = get_minimum_size(state_tree.get(&root_widget.path), true, true);
+        if !fits(terminal_size, required_size) {
+            scheduler.backend.terminal_too_small = Some(required_size);
+            return get_too_small_message(state_tree, root_widget, terminal_size, required_size);
+        }
+    }
+    let contents = root_widget.get_contents(state_tree);
+    // Widgets anchored to the terminal in a nested float layout are placed using the absolute
+    // position of that layout, which is only known after the first pass.
+    if has_nested_anchored_widgets(state_tree, &root_widget.path) {
+        return root_widget.get_contents(state_tree);
+    }
+    contents
+}
+
+/// Check whether any widget with an [Anchor] is not a direct child of the root layout.
+fn has_nested_anchored_widgets(state_tree: &StateTree, root_path: &str) -> bool {
+    state_tree.get_all().iter().any(|state| {
+        let state = state.as_generic();
+        state.get_anchor_config().get_anchor() != Anchor::None
+            && state.get_path().rsplit_once('/').map(|(parent, _)| parent) != Some(root_path)
+    })
+}
+
+/// Check whether a size fits in another size.
+fn fits(available: Size, required: Size) -> bool {
+    available.width >= required.width && available.height >= required.height
+}
+
+/// Get the smallest size a widget needs to show all of its' content. Fixed size widgets need
+/// their size, widgets with size hints need their borders, padding and (for layouts) the minimum
+/// size of their children. In compact mode borders, padding and layouts with
+/// [collapsible_on_small] are not counted.
+pub fn get_minimum_size(node: &Tree<EzState>, compact: bool, is_root: bool) -> Size {
+    let state = node.obj.as_generic();
+    if compact && !is_root {
+        if let EzState::Layout(ref layout_state) = node.obj {
+            if layout_state.get_collapsible_on_small() {
+                return Size::new(0, 0);
+            }
+        }
+    }
+
+    let mut content = match node.obj {
+        EzState::Layout(ref layout_state) => {
+            let mut content = Size::new(0, 0);
+            let horizontal = matches!(layout_state.get_mode(), LayoutMode::Box)
+                && matches!(layout_state.get_orientation(), LayoutOrientation::Horizontal);
+            let vertical = matches!(layout_state.get_mode(), LayoutMode::Box)
+                && matches!(layout_state.get_orientation(), LayoutOrientation::Vertical);
+            for child in node.get_children() {
+                if is_root && LayoutState::is_modal_id(&child.id) {
+                    continue;
+                }
+                let child_size = get_minimum_size(child, compact, false);
+                content.width = if horizontal {
+                    content.width + child_size.width
+                } else {
+                    max(content.width, child_size.width)
+                };
+                content.height = if vertical {
+                    content.height + child_size.height
+                } else {
+                    max(content.height, child_size.height)
+                };
+            }
+            if layout_state.get_scrolling_config().get_scroll_x() {
+                content.width = 0;
+            }
+            if layout_state.get_scrolling_config().get_scroll_y() {
+                content.height = 0;
+            }
+            content
+        }
+        _ => Size::new(1, 1),
+    };
+    if !compact {
+        let border = if state.get_border_config().get_border() { 2 } else { 0 };
+        let padding = state.get_padding();
+        content.width += padding.get_padding_left() + padding.get_padding_right() + border;
+        content.height += padding.get_padding_top() + padding.get_padding_bottom() + border;
+    }
+    if is_root {
+        return content;
+    }
+
+    let is_layout = matches!(node.obj, EzState::Layout(_));
+    let width = if state.get_size_hint().get_size_hint_x().is_none()
+        && (!state.get_auto_scale().get_auto_scale_width() || !is_layout)
+    {
+        state.get_size().get_width()
+    } else {
+        content.width
+    };
+    let height = if state.get_size_hint().get_size_hint_y().is_none()
+        && (!state.get_auto_scale().get_auto_scale_height() || !is_layout)
+    {
+        state.get_size().get_height()
+    } else {
+        content.height
+    };
+    Size::new(width, height)
+}
+
+/// Drop all borders and padding and collapse layouts with [collapsible_on_small]. The original
+/// values are stored on the scheduler to be restored by [leave_compact_mode].
+fn enter_compact_mode(
+    state_tree: &mut StateTree,
+    scheduler: &mut SchedulerFrontend,
+    required_size: Size,
+) {
+    let mut backups = Vec::new();
+    for state in state_tree.get_all_mut() {
+        let collapse = match state {
+            EzState::Layout(ref layout_state) => {
+                layout_state.get_collapsible_on_small() && layout_state.get_path() != "/root"
+            }
+            _ => false,
+        };
+        let state = state.as_generic_mut();
+        let padding = state.get_padding();
+        let mut backup = CompactBackup {
+            path: state.get_path().clone(),
+            border: state.get_border_config().get_border(),
+            padding: (
+                padding.get_padding_top(),
+                padding.get_padding_bottom(),
+                padding.get_padding_left(),
+                padding.get_padding_right(),
+            ),
+            collapsed: collapse,
+            size_hint: (None, None),
+            auto_scale: (false, false),
+            size: Size::new(0, 0),
+        };
+        state.get_border_config_mut().set_border(false);
+        state.get_padding_mut().set_padding_top(0);
+        state.get_padding_mut().set_padding_bottom(0);
+        state.get_padding_mut().set_padding_left(0);
+        state.get_padding_mut().set_padding_right(0);
+        if collapse {
+            backup.size_hint = (
+                state.get_size_hint().get_size_hint_x(),
+                state.get_size_hint().get_size_hint_y(),
+            );
+            backup.auto_scale = (
+                state.get_auto_scale().get_auto_scale_width(),
+                state.get_auto_scale().get_auto_scale_height(),
+            );
+            backup.size = Size::new(state.get_size().get_width(), state.get_size().get_height());
+            state.get_size_hint_mut().set_size_hint_x(None);
+            state.get_size_hint_mut().set_size_hint_y(None);
+            state.get_auto_scale_mut().set_auto_scale_width(false);
+            state.get_auto_scale_mut().set_auto_scale_height(false);
+            force_size(state, Size::new(0, 0));
+        }
+        backups.push(backup);
+    }
+    scheduler.backend.compact_mode = Some(CompactMode {
+        required_size,
+        backups,
+    });
+}
+
+/// Restore the borders, padding and collapsed layouts changed by [enter_compact_mode]. Widgets
+/// that were removed in the meantime are skipped.
+fn leave_compact_mode(state_tree: &mut StateTree, scheduler: &mut SchedulerFrontend) {
+    let compact = match scheduler.backend.compact_mode.take() {
+        Some(i) => i,
+        None => return,
+    };
+    for backup in compact.backups {
+        let state = match state_tree.try_get_mut(&backup.path) {
+            Some(i) => i.as_generic_mut(),
+            None => continue,
+        };
+        state.get_border_config_mut().set_border(backup.border);
+        state.get_padding_mut().set_padding_top(backup.padding.0);
+        state.get_padding_mut().set_padding_bottom(backup.padding.1);
+        state.get_padding_mut().set_padding_left(backup.padding.2);
+        state.get_padding_mut().set_padding_right(backup.padding.3);
+        if backup.collapsed {
+            state.get_size_hint_mut().set_size_hint_x(backup.size_hint.0);
+            state.get_size_hint_mut().set_size_hint_y(backup.size_hint.1);
+            state.get_auto_scale_mut().set_auto_scale_width(backup.auto_scale.0);
+            state.get_auto_scale_mut().set_auto_scale_height(backup.auto_scale.1);
+            force_size(state, backup.size);
+        }
+    }
+}
+
+/// Set the size of a widget, even if the user set a fixed width or height in the .ez file.
+fn force_size(state: &mut dyn GenericState, size: Size) {
+    let state_size = state.get_size_mut();
+    let (width_locked, height_locked) = (state_size.width.locked, state_size.height.locked);
+    state_size.width.locked = false;
+    state_size.height.locked = false;
+    state_size.width.set(size.width);
+    state_size.height.set(size.height);
+    state_size.width.locked = width_locked;
+    state_size.height.locked = height_locked;
+}
+
+/// Create a PixelMap the size of the terminal with a centered message telling the user the
+/// terminal is too small, along with the required and current size. Uses the colors of the root
+/// layout.
+pub fn get_too_small_message(
+    state_tree: &StateTree,
+    root_widget: &Layout,
+    terminal_size: Size,
+    required_size: Size,
+) -> PixelMap {
+    let colors = state_tree.get(&root_widget.path).as_generic().get_color_config();
+    let (fg_color, bg_color) = (colors.get_fg_color(), colors.get_bg_color());
+    let lines = [
+        "Terminal too small".to_string(),
+        format!(
+            "Please enlarge it to at least {}x{}",
+            required_size.width, required_size.height
+        ),
+        format!("Current size: {}x{}", terminal_size.width, terminal_size.height),
+    ];
+
+    let mut contents =
+        vec![
+            vec![Pixel::new(" ".to_string(), fg_color, bg_color); terminal_size.height];
+            terminal_size.width
+        ];
+    let start_y = terminal_size.height.saturating_sub(lines.len()) / 2;
+    for (i, line) in lines.iter().enumerate() {
+        let y = start_y + i;
+        if y >= terminal_size.height {
+            break;
+        }
+        let chars: Vec<char> = line.chars().collect();
+        let start_x = terminal_size.width.saturating_sub(chars.len()) / 2;
+        for (j, char) in chars.iter().enumerate() {
+            if start_x + j >= terminal_size.width {
+                break;
+            }
+            contents[start_x + j][y].symbol = char.to_string();
+        }
+    }
+    contents
+}
@@ -2,52 +2,404 @@
 //!
 //! A module containing functions that interact with the terminal or draw things on screen.
 use std::io::{stdout, Write};
+use std::thread;
+use std::time::{Duration, Instant};
 
-use crossterm::style::PrintStyledContent;
+use crossterm::style::{Color, Print, PrintStyledContent};
 use crossterm::{
-    cursor::{self, Hide, Show},
-    event::{DisableMouseCapture, EnableMouseCapture},
+    cursor::{self, CursorShape, DisableBlinking, EnableBlinking, Hide, MoveTo, SetCursorShape, Show},
+    event::{
+        poll, read, DisableBracketedPaste, DisableFocusChange, DisableMouseCapture,
+        EnableBracketedPaste, EnableFocusChange, EnableMouseCapture, Event,
+    },
     execute,
-    terminal::{disable_raw_mode, enable_raw_mode, Clear, ClearType},
+    terminal::{disable_raw_mode, enable_raw_mode, is_raw_mode_enabled, size, Clear, ClearType},
     ExecutableCommand, QueueableCommand, Result,
 };
 
-use crate::run::definitions::StateTree;
+use crate::run::definitions::{Coordinates, StateTree};
+use crate::run::input_source::InputSource;
 use crate::run::select::widget_is_hidden;
 use crate::run::tree::ViewTree;
+use crate::scheduler::definitions::ExitTeardown;
+use crate::states::definitions::{CursorStyle, LayoutMode};
+use crate::states::ez_state::{EzState, GenericState};
 use crate::widgets::ez_object::EzObject;
 use crate::widgets::layout::layout::Layout;
 
-/// Prepare state of the terminal for drawing the UI.
-pub fn initialize_terminal() -> Result<()> {
-    enable_raw_mode()?;
-    execute!(stdout(), EnableMouseCapture)?;
-    stdout().execute(Hide)?;
-    stdout().execute(Clear(ClearType::All))?;
-    Ok(())
+/// Handle to the terminal a UI is drawn on and reads its' input from. Each running UI owns its' own
+/// terminal, so a single process can drive multiple independent UIs, e.g. one per SSH client on
+/// different PTYs. Pass it to [run_with_terminal]; [run] uses [Terminal::stdout].
+pub struct Terminal {
+    /// Where the UI is drawn
+    output: Box<dyn Write + Send>,
+
+    /// Where events are read from. None to read from the terminal of the process (stdin)
+    input: Option<Box<dyn InputSource>>,
+
+    /// Size of the terminal in columns and rows. None to use the size of the terminal of the
+    /// process
+    size: Option<(usize, usize)>,
+
+    /// Event read by [Terminal::has_pending_event] that was not handled yet
+    pending: Option<Event>,
 }
+impl Terminal {
+    /// The terminal of the process: draws on stdout and reads events from stdin. Raw mode is
+    /// enabled while the UI runs.
+    pub fn stdout() -> Self {
+        Terminal {
+            output: Box::new(stdout()),
+            input: None,
+            size: None,
+            pending: None,
+        }
+    }
+
+    /// A terminal that draws on any writer (e.g. the master side of a PTY) and reads events from
+    /// an [InputSource], with an initial size in columns and rows. Send [Event::Resize] through
+    /// the input source when the size changes. Raw mode is not enabled by ez_term, as it only
+    /// applies to the terminal of the process; put the PTY in raw mode yourself if needed.
+    pub fn new(
+        output: Box<dyn Write + Send>,
+        input: Box<dyn InputSource>,
+        width: usize,
+        height: usize,
+    ) -> Self {
+        Terminal {
+            output,
+            input: Some(input),
+            size: Some((width, height)),
+            pending: None,
+        }
+    }
+
+    /// Get the size of the terminal in columns and rows if it was set, see [Terminal::new].
+    pub fn get_size(&self) -> Option<(usize, usize)> {
+        self.size
+    }
+
+    /// Returns a bool representing whether this is the terminal of the process.
+    pub fn is_stdout(&self) -> bool {
+        self.input.is_none()
+    }
+
+    /// Prepare state of the terminal for drawing the UI.
+    pub fn initialize(&mut self) -> Result<()> {
+        if self.is_stdout() {
+            enable_raw_mode()?;
+        }
+        execute!(self.output, EnableMouseCapture, EnableFocusChange, EnableBracketedPaste)?;
+        self.output.execute(Hide)?;
+        self.output.execute(Clear(ClearType::All))?;
+        Ok(())
+    }
 
-/// Set terminal to original state.
-pub fn shutdown_terminal() -> Result<()> {
-    stdout().queue(DisableMouseCapture)?.queue(Show)?.flush()?;
-    stdout().execute(Clear(ClearType::All))?;
-    disable_raw_mode()?;
-    Ok(())
+    /// Set terminal to original state.
+    pub fn shutdown(&mut self) -> Result<()> {
+        self.restore(true)
+    }
+
+    /// Set terminal to original state after the app exited, as configured by the [ExitTeardown]
+    /// settings: the screen is cleared or the cursor is moved below the final frame, then the exit
+    /// message is printed.
+    pub fn shutdown_after_exit(&mut self, teardown: &ExitTeardown) -> Result<()> {
+        self.restore(teardown.clear_screen)?;
+        if !teardown.clear_screen {
+            let height = match self.size {
+                Some((_, height)) => height,
+                None => size()?.1 as usize,
+            };
+            self.output
+                .queue(MoveTo(0, height.saturating_sub(1) as u16))?
+                .queue(Print("\r\n"))?;
+        }
+        if let Some(ref message) = teardown.message {
+            self.output.queue(Print(format!("{}\r\n", message)))?;
+        }
+        self.output.flush()?;
+        Ok(())
+    }
+
+    /// Restore the terminal, optionally clearing the screen.
+    fn restore(&mut self, clear_screen: bool) -> Result<()> {
+        self.output
+            .queue(DisableMouseCapture)?
+            .queue(DisableFocusChange)?
+            .queue(DisableBracketedPaste)?
+            .queue(Show)?
+            // Restore the default cursor shape of the terminal in case a text input changed it
+            .queue(Print("\x1b[0 q"))?
+            .flush()?;
+        if clear_screen {
+            self.output.execute(Clear(ClearType::All))?;
+        }
+        if self.is_stdout() {
+            disable_raw_mode()?;
+        }
+        Ok(())
+    }
+
+    /// Query the background color of the terminal using the OSC 11 escape sequence. Returns None
+    /// if the terminal did not answer within the timeout. Falls back to the COLORFGBG environment
+    /// variable that some terminals set. Only the terminal of the process can be queried; None is
+    /// returned for other terminals, as their answers would arrive through their input source.
+    /// Call this before the UI runs, as the answer is read from stdin directly.
+    pub fn query_background_color(&mut self, timeout: Duration) -> Option<Color> {
+        if !self.is_stdout() {
+            return None;
+        }
+        query_osc_background_color(timeout).or_else(|| {
+            let colorfgbg = std::env::var("COLORFGBG").ok()?;
+            let background = colorfgbg.rsplit(';').next()?.parse::<u8>().ok()?;
+            Some(Color::AnsiValue(background))
+        })
+    }
+
+    /// Returns a bool representing whether an event is waiting to be handled, without waiting
+    /// for one.
+    pub fn has_pending_event(&mut self) -> bool {
+        if self.pending.is_none() {
+            self.pending = self.poll_event(Duration::ZERO).ok().flatten();
+        }
+        self.pending.is_some()
+    }
+
+    /// Wait up to a timeout for the next event. Returns None if no event arrived in time.
+    pub fn poll_event(&mut self, timeout: Duration) -> Result<Option<Event>> {
+        if let Some(event) = self.pending.take() {
+            return Ok(Some(event));
+        }
+        let input = match self.input {
+            Some(ref mut i) => i,
+            None => {
+                return if poll(timeout)? { Ok(Some(read()?)) } else { Ok(None) };
+            }
+        };
+        let start = Instant::now();
+        loop {
+            if let Some(event) = input.poll_event() {
+                return Ok(Some(event));
+            }
+            if start.elapsed() >= timeout {
+                return Ok(None);
+            }
+            thread::sleep(timeout.min(Duration::from_millis(1)));
+        }
+    }
+
+    /// Write content to screen. Only writes differences between an frame and the new frame.
+    pub fn write_to_screen(&mut self, view_tree: &mut ViewTree) {
+        self.output.execute(cursor::SavePosition).unwrap();
+        for (coord, content) in view_tree.get_changed() {
+            self.output
+                .queue(cursor::MoveTo(coord.x as u16, coord.y as u16))
+                .unwrap()
+                .queue(PrintStyledContent(content.clone()))
+                .unwrap();
+        }
+        self.output.flush().unwrap();
+        self.output.execute(cursor::RestorePosition).unwrap();
+        view_tree.clear_changed();
+    }
+
+    /// Show the terminal cursor at the cursor position of the selected text input if it uses the
+    /// terminal cursor, or hide the terminal cursor otherwise. Only writes to the terminal if the
+    /// cursor is different from the current cursor, which is kept up to date.
+    pub fn update_cursor(
+        &mut self,
+        state_tree: &StateTree,
+        selected_widget: &str,
+        current: &mut Option<TerminalCursor>,
+    ) {
+        let cursor = get_terminal_cursor(state_tree, selected_widget);
+        if cursor == *current {
+            return;
+        }
+        match cursor {
+            Some(cursor) => {
+                let shape = match cursor.style {
+                    CursorStyle::Block => CursorShape::Block,
+                    CursorStyle::Underline => CursorShape::UnderScore,
+                    CursorStyle::Bar => CursorShape::Line,
+                };
+                self.output
+                    .queue(MoveTo(cursor.position.x as u16, cursor.position.y as u16))
+                    .unwrap()
+                    .queue(SetCursorShape(shape))
+                    .unwrap();
+                if cursor.blinking {
+                    self.output.queue(EnableBlinking).unwrap();
+                } else {
+                    self.output.queue(DisableBlinking).unwrap();
+                }
+                self.output.queue(Show).unwrap().flush().unwrap();
+            }
+            None => {
+                self.output.execute(Hide).unwrap();
+            }
+        }
+        *current = cursor;
+    }
+}
+impl Default for Terminal {
+    fn default() -> Self {
+        Terminal::stdout()
+    }
+}
+
+/// Returns a bool representing whether a color is considered light, e.g. the background color of
+/// a terminal with a light theme.
+pub fn is_light_color(color: Color) -> bool {
+    match color {
+        Color::Rgb { r, g, b } => {
+            (0.299 * r as f64 + 0.587 * g as f64 + 0.114 * b as f64) / 255.0 > 0.5
+        }
+        Color::AnsiValue(i) => i == 7 || (9..=15).contains(&i) || i >= 250,
+        Color::White | Color::Grey | Color::Yellow | Color::Cyan => true,
+        _ => false,
+    }
 }
 
-/// Write content to screen. Only writes differences between an frame and the new frame.
-pub fn write_to_screen(view_tree: &mut ViewTree) {
-    stdout().execute(cursor::SavePosition).unwrap();
-    for (coord, content) in view_tree.get_changed() {
-        stdout()
-            .queue(cursor::MoveTo(coord.x as u16, coord.y as u16))
-            .unwrap()
-            .queue(PrintStyledContent(content.clone()))
-            .unwrap();
+/// How long to keep reading after the query timed out, so replies of a slow terminal are
+/// consumed instead of showing up as key presses.
+#[cfg(unix)]
+const QUERY_DRAIN_TIMEOUT: Duration = Duration::from_millis(500);
+
+#[cfg(unix)]
+fn query_osc_background_color(timeout: Duration) -> Option<Color> {
+    if unsafe { libc::isatty(0) == 0 || libc::isatty(1) == 0 } {
+        return None;
     }
-    stdout().flush().unwrap();
-    stdout().execute(cursor::RestorePosition).unwrap();
-    view_tree.clear_changed();
+    let was_raw = is_raw_mode_enabled().ok()?;
+    if !was_raw {
+        enable_raw_mode().ok()?;
+    }
+    let response = read_osc_response(timeout);
+    if !was_raw {
+        disable_raw_mode().ok()?;
+    }
+    parse_osc_color(&response?)
+}
+
+#[cfg(not(unix))]
+fn query_osc_background_color(_timeout: Duration) -> Option<Color> {
+    None
+}
+
+/// Write the OSC 11 query followed by a primary device attributes (DA1) query, and read from
+/// stdin until the DA1 answer arrives. Terminals answer in order and all of them answer DA1, so
+/// once it arrives the OSC 11 answer (if any) has been read as well; nothing is left to be read as
+/// input by the UI later. If the terminal does not answer within the timeout, the answers are
+/// still waited for (and discarded) for [QUERY_DRAIN_TIMEOUT].
+#[cfg(unix)]
+fn read_osc_response(timeout: Duration) -> Option<String> {
+    let mut out = stdout();
+    out.write_all(b"\x1b]11;?\x07\x1b[c").ok()?;
+    out.flush().ok()?;
+
+    let start = std::time::Instant::now();
+    let mut response = Vec::new();
+    while !has_device_attributes(&response) {
+        let remaining = match (timeout + QUERY_DRAIN_TIMEOUT).checked_sub(start.elapsed()) {
+            Some(i) => i,
+            None => break,
+        };
+        let mut poll_fd = libc::pollfd {
+            fd: 0,
+            events: libc::POLLIN,
+            revents: 0,
+        };
+        if unsafe { libc::poll(&mut poll_fd, 1, remaining.as_millis() as i32) } <= 0 {
+            break;
+        }
+        let mut buffer = [0u8; 64];
+        let read = unsafe { libc::read(0, buffer.as_mut_ptr() as *mut libc::c_void, 64) };
+        if read <= 0 {
+            break;
+        }
+        response.extend_from_slice(&buffer[..read as usize]);
+    }
+    if !has_device_attributes(&response) {
+        // The terminal does not answer queries; discard anything that arrived partially
+        unsafe { libc::tcflush(0, libc::TCIFLUSH) };
+    }
+    if start.elapsed() > timeout {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&response).to_string())
+}
+
+/// Returns a bool representing whether a DA1 answer such as "\x1b[?62;22c" was read.
+#[cfg(unix)]
+fn has_device_attributes(response: &[u8]) -> bool {
+    match response.windows(3).rposition(|x| x == b"\x1b[?") {
+        Some(i) => response[i..].contains(&b'c'),
+        None => false,
+    }
+}
+
+/// Parse an OSC 11 answer such as "\x1b]11;rgb:ffff/ffff/ffff\x07" into a color.
+fn parse_osc_color(response: &str) -> Option<Color> {
+    let rgb = response.split("rgb:").nth(1)?;
+    let rgb = rgb.split(['\x07', '\x1b']).next()?;
+    let mut channels = rgb.split('/').map(|x| {
+        // Channels have 1 to 4 hex digits; anything else is not a valid answer
+        if x.is_empty() || x.len() > 4 {
+            return None;
+        }
+        let value = u32::from_str_radix(x, 16).ok()?;
+        let max = 16u32.pow(x.len() as u32) - 1;
+        Some((value * 255 / max) as u8)
+    });
+    Some(Color::Rgb {
+        r: channels.next()??,
+        g: channels.next()??,
+        b: channels.next()??,
+    })
+}
+
+/// The actual terminal cursor as shown at the cursor position of a text input. See
+/// [TextInputState.set_terminal_cursor].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TerminalCursor {
+    /// Position of the cursor on screen
+    pub position: Coordinates,
+
+    pub style: CursorStyle,
+
+    pub blinking: bool,
+}
+
+/// Get the terminal cursor of the selected widget, if it is a text input using the terminal
+/// cursor.
+fn get_terminal_cursor(state_tree: &StateTree, selected_widget: &str) -> Option<TerminalCursor> {
+    if selected_widget.is_empty() {
+        return None;
+    }
+    let state = match &state_tree.try_get(selected_widget)?.obj {
+        EzState::TextInput(i) => i,
+        _ => return None,
+    };
+    if !state.get_terminal_cursor()
+        || !state.get_selected()
+        || widget_is_hidden(selected_widget.to_string(), state_tree)
+    {
+        return None;
+    }
+    let border = if state.get_border_config().get_border() { 1 } else { 0 };
+    let position = state.get_absolute_position();
+    let x = position.x
+        + (state.get_padding().get_padding_left() + border + state.get_cursor_pos().x) as isize;
+    let y = position.y + (state.get_padding().get_padding_top() + border) as isize;
+    if x < 0 || y < 0 {
+        return None;
+    }
+    Some(TerminalCursor {
+        position: Coordinates::new(x as usize, y as usize),
+        style: state.get_cursor_style(),
+        blinking: state.get_cursor_blink_interval() > 0,
+    })
 }
 
 /// Redraw widgets to the ViewTree (ViewTree diffs are written to screen at each frame). If
@@ -68,6 +420,144 @@ pub fn redraw_changed_widgets(
     force_redraw
 }
 
+/// Split the widgets to redraw this frame into urgent and background updates. Urgent updates are
+/// widgets updated while handling input, and the selected widget along with its' parents and
+/// children; they stay in 'changed_widgets'. Background updates are returned with the updates
+/// deferred in earlier frames first. While a modal is open everything is urgent, as the whole
+/// screen is redrawn anyway.
+pub fn split_background_updates(
+    state_tree: &StateTree,
+    changed_widgets: &mut Vec<String>,
+    deferred: &mut Vec<String>,
+    input_updates: &[String],
+    selected_widget: &str,
+) -> Vec<String> {
+    let mut background: Vec<String> = deferred
+        .drain(..)
+        .filter(|x| state_tree.contains(x) && !changed_widgets.contains(x))
+        .collect();
+    if state_tree.as_layout().has_modal() {
+        changed_widgets.append(&mut background);
+        return Vec::new();
+    }
+    let is_urgent = |path: &String| {
+        input_updates.contains(path)
+            || (!selected_widget.is_empty()
+                && (path == selected_widget
+                    || selected_widget.starts_with(&format!("{}/", path))
+                    || path.starts_with(&format!("{}/", selected_widget))))
+    };
+    let (urgent, other): (Vec<String>, Vec<String>) =
+        changed_widgets.drain(..).partition(is_urgent);
+    *changed_widgets = urgent;
+    background.extend(other);
+    background
+}
+
+/// Redraw background updates to a ViewTree, oldest first, until the budget is used up or an event
+/// is waiting. At least one widget is redrawn. Widgets that were not redrawn are left in 'paths'.
+/// Returns whether the whole screen has to be redrawn instead.
+pub fn redraw_background_widgets(
+    paths: &mut Vec<String>,
+    view_tree: &mut ViewTree,
+    state_tree: &mut StateTree,
+    root_widget: &mut Layout,
+    budget: Duration,
+    terminal: &mut Terminal,
+) -> bool {
+    let started = Instant::now();
+    let mut redrawn = 0;
+    while !paths.is_empty() {
+        if redrawn > 0 && (started.elapsed() >= budget || terminal.has_pending_event()) {
+            break;
+        }
+        let mut next = vec![paths.remove(0)];
+        if redraw_widgets(&mut next, view_tree, state_tree, root_widget) {
+            paths.clear();
+            return true;
+        }
+        redrawn += 1;
+    }
+    false
+}
+
+/// Redraw single cells of widgets to a ViewTree. Widgets that are also redrawn completely this
+/// frame are skipped. If a widget cannot provide single cells, is inside a scroll view, or might
+/// be covered by other content (an open modal, or overlapping children of a float layout), it is
+/// redrawn completely instead by adding it to the changed widgets.
+pub fn redraw_changed_cells(
+    view_tree: &mut ViewTree,
+    state_tree: &mut StateTree,
+    root_widget: &Layout,
+    changed_cells: &mut Vec<(String, Vec<Coordinates>)>,
+    changed_widgets: &mut Vec<String>,
+) {
+    for (path, cells) in changed_cells.drain(..) {
+        if changed_widgets.contains(&path) || state_tree.try_get(&path).is_none()
+            || widget_is_hidden(path.clone(), state_tree)
+        {
+            continue;
+        }
+        let widget = match root_widget.get_child_by_path(&path) {
+            Some(i) => i.as_ez_object(),
+            None => continue,
+        };
+        // Cells are written without checking what is drawn on top of them
+        if state_tree.as_layout().has_modal() || is_in_float_layout(&path, state_tree) {
+            changed_widgets.push(path);
+            continue;
+        }
+        let state = state_tree.get(&path).as_generic();
+        // Single cells don't include the selection style, see EzObject.get_styled_contents
+        if state.get_infinite_size().width || state.get_infinite_size().height
+            || state.get_selected()
+        {
+            changed_widgets.push(path);
+            continue;
+        }
+        let border = if state.get_border_config().get_border() { 1 } else { 0 };
+        let content_position = state.get_absolute_position();
+        let offset_x = content_position.x + (state.get_padding().get_padding_left() + border) as isize;
+        let offset_y = content_position.y + (state.get_padding().get_padding_top() + border) as isize;
+        let size = state.get_effective_size();
+
+        let mut pixels = Vec::new();
+        for cell in cells.iter().filter(|x| x.x < size.width && x.y < size.height) {
+            match widget.get_cell(state_tree, *cell) {
+                Some(pixel) => pixels.push((*cell, pixel)),
+                None => {
+                    pixels.clear();
+                    changed_widgets.push(path.clone());
+                    break;
+                }
+            }
+        }
+        for (cell, pixel) in pixels {
+            let x = offset_x + cell.x as isize;
+            let y = offset_y + cell.y as isize;
+            if x >= 0 && y >= 0 {
+                view_tree.write_content(Coordinates::new(x as usize, y as usize),
+                                        vec![vec![pixel]]);
+            }
+        }
+    }
+}
+
+/// Bool representing whether a widget is inside a float layout, where siblings (and their
+/// children) can overlap it.
+fn is_in_float_layout(path: &str, state_tree: &StateTree) -> bool {
+    let mut check_parent = path;
+    while let Some((parent, _)) = check_parent.rsplit_once('/') {
+        if let Some(EzState::Layout(i)) = state_tree.try_get(parent).map(|x| &x.obj) {
+            if i.get_mode() == &LayoutMode::Float {
+                return true;
+            }
+        }
+        check_parent = parent;
+    }
+    false
+}
+
 /// Redraw a list of widgets to a ViewTree.
 pub fn redraw_widgets(
     paths: &mut Vec<String>,
@@ -0,0 +1,289 @@
+//! # Testing
+//!
+//! This module implements the [TestUi] struct, which allows testing an app without a terminal.
+use crossterm::event::{
+    Event, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
+
+use crate::run::definitions::{CallbackTree, Coordinates, IsizeCoordinates, StateTree};
+use crate::run::run::{dispatch_event, update_scheduler};
+use crate::run::small_terminal::get_root_contents;
+use crate::run::tree::{initialize_callback_tree, ViewTree};
+use crate::scheduler::definitions::CustomDataMap;
+use crate::scheduler::scheduler::SchedulerFrontend;
+use crate::scheduler::scheduler_funcs::{render_widgets_to_string, trigger_update_funcs};
+use crate::states::definitions::KeyMap;
+use crate::states::ez_state::GenericState;
+use crate::widgets::layout::layout::Layout;
+
+/// Runs a UI without a terminal, so the callbacks of an app can be tested. Events sent to the
+/// test UI go through the same code path as events from a real terminal; after each event one
+/// frame of the scheduler is processed (scheduled tasks that are due, created widgets, property
+/// updates, etc.). Afterwards the [StateTree] can be inspected to assert on the changes.
+///
+/// # Example:
+///
+/// We'll test that clicking a button changes the text of a label:
+/// ```
+/// use ez_term::*;
+/// let (root_widget, state_tree, mut scheduler, custom_data) = load_ui();
+///
+/// let on_press = |context: Context| {
+///     let state = context.state_tree.get_mut("my_label").as_label_mut();
+///     state.set_text("Pressed!".to_string());
+///     state.update(context.scheduler);
+///     true
+/// };
+/// scheduler.update_callback_config("my_button", CallbackConfig::from_on_press(Box::new(on_press)));
+///
+/// let mut ui = TestUi::new(root_widget, state_tree, scheduler, custom_data);
+/// ui.click_widget("my_button");
+/// assert_eq!(ui.state_tree.get("my_label").as_label().get_text(), "Pressed!");
+/// ```
+pub struct TestUi {
+    pub root_widget: Layout,
+
+    pub state_tree: StateTree,
+
+    pub callback_tree: CallbackTree,
+
+    pub scheduler: SchedulerFrontend,
+
+    pub custom_data: CustomDataMap,
+
+    /// Full path of the currently selected widget, or empty if nothing is selected
+    pub selected_widget: String,
+
+    /// Full path of the currently hovered widget, or empty if nothing is hovered
+    pub hovered_widget: String,
+
+    dragging: Option<String>,
+
+    last_dragging_pos: IsizeCoordinates,
+
+    global_keymap: KeyMap,
+}
+impl TestUi {
+    /// Create a test UI from the objects returned by [load_ui]. The UI keeps the size of the root
+    /// layout; use [resize] to test other sizes.
+    pub fn new(
+        root_widget: Layout,
+        state_tree: StateTree,
+        scheduler: SchedulerFrontend,
+        custom_data: CustomDataMap,
+    ) -> Self {
+        let callback_tree = initialize_callback_tree(&root_widget);
+        let mut ui = TestUi {
+            root_widget,
+            state_tree,
+            callback_tree,
+            scheduler,
+            custom_data,
+            selected_widget: String::new(),
+            hovered_widget: String::new(),
+            dragging: None,
+            last_dragging_pos: IsizeCoordinates::new(0, 0),
+            global_keymap: KeyMap::new(),
+        };
+        // Like the run loop, sync bound properties with the properties they are bound to
+        trigger_update_funcs(&mut ui.scheduler, &mut ui.state_tree);
+        ui.update();
+        ui
+    }
+
+    /// Process one frame: everything queued on the scheduler is handled and the layout is
+    /// recalculated, so widget positions are up to date. Like the run loop, events of macros that
+    /// were played and events of input sources (see [SchedulerFrontend.add_input_source]) are
+    /// handled with a frame after each event.
+    pub fn update(&mut self) {
+        loop {
+            update_scheduler(
+                &mut self.root_widget,
+                &mut self.state_tree,
+                &mut self.callback_tree,
+                &mut self.scheduler,
+                &mut self.custom_data,
+                &mut self.selected_widget,
+                &mut self.global_keymap,
+            );
+            let contents =
+                get_root_contents(&mut self.root_widget, &mut self.state_tree, &mut self.scheduler);
+            self.root_widget
+                .propagate_absolute_positions(&mut self.state_tree);
+            if !self.scheduler.backend.widgets_to_render.is_empty() {
+                // There is no screen; draw the frame to a view tree to render widgets from
+                let size = self.root_widget.state.get_size();
+                let mut view_tree = ViewTree::default();
+                view_tree.initialize(size.get_width(), size.get_height());
+                view_tree.write_content(Coordinates::new(0, 0), contents);
+                render_widgets_to_string(
+                    &mut self.scheduler,
+                    &mut self.state_tree,
+                    &mut self.custom_data,
+                    &view_tree,
+                );
+            }
+            self.scheduler.backend.widgets_to_update.clear();
+            self.scheduler.backend.cells_to_update.clear();
+            self.scheduler.backend.force_redraw = false;
+
+            let event = self
+                .scheduler
+                .next_macro_event()
+                .or_else(|| self.scheduler.next_input_source_event());
+            match event {
+                Some(event) => {
+                    dispatch_event(
+                        event,
+                        false,
+                        &self.root_widget,
+                        &mut self.state_tree,
+                        &mut self.callback_tree,
+                        &mut self.scheduler,
+                        &mut self.custom_data,
+                        &mut self.selected_widget,
+                        &mut self.hovered_widget,
+                        &mut self.dragging,
+                        &mut self.last_dragging_pos,
+                        &mut self.global_keymap,
+                    );
+                }
+                None => break,
+            }
+        }
+    }
+
+    /// Send an event as if it came from the terminal, then process one frame. Returns whether
+    /// the event was consumed.
+    pub fn send_event(&mut self, event: Event) -> bool {
+        if let Event::Resize(width, height) = event {
+            self.resize(width as usize, height as usize);
+            return true;
+        }
+        let consumed = dispatch_event(
+            event,
+            false,
+            &self.root_widget,
+            &mut self.state_tree,
+            &mut self.callback_tree,
+            &mut self.scheduler,
+            &mut self.custom_data,
+            &mut self.selected_widget,
+            &mut self.hovered_widget,
+            &mut self.dragging,
+            &mut self.last_dragging_pos,
+            &mut self.global_keymap,
+        );
+        self.update();
+        consumed
+    }
+
+    /// Press a key without modifiers.
+    pub fn press_key(&mut self, key: KeyCode) -> bool {
+        self.press_key_with_modifiers(key, KeyModifiers::NONE)
+    }
+
+    /// Press a key while holding modifiers (e.g. KeyModifiers::SHIFT).
+    pub fn press_key_with_modifiers(&mut self, key: KeyCode, modifiers: KeyModifiers) -> bool {
+        self.send_event(Event::Key(KeyEvent::new(key, modifiers)))
+    }
+
+    /// Type a string as individual key presses.
+    pub fn type_text(&mut self, text: &str) {
+        for char in text.chars() {
+            self.press_key(KeyCode::Char(char));
+        }
+    }
+
+    /// Paste text as if it was pasted into the terminal.
+    pub fn paste(&mut self, text: &str) -> bool {
+        self.send_event(Event::Paste(text.to_string()))
+    }
+
+    /// Left click on an absolute position on the screen.
+    pub fn click(&mut self, x: u16, y: u16) -> bool {
+        self.send_mouse_event(MouseEventKind::Down(MouseButton::Left), x, y);
+        self.send_mouse_event(MouseEventKind::Up(MouseButton::Left), x, y)
+    }
+
+    /// Right click on an absolute position on the screen.
+    pub fn right_click(&mut self, x: u16, y: u16) -> bool {
+        self.send_mouse_event(MouseEventKind::Down(MouseButton::Right), x, y);
+        self.send_mouse_event(MouseEventKind::Up(MouseButton::Right), x, y)
+    }
+
+    /// Left click on the top left position of a widget (by ID or path).
+    pub fn click_widget(&mut self, widget: &str) -> bool {
+        let position = self
+            .state_tree
+            .get(widget)
+            .as_generic()
+            .get_effective_absolute_position();
+        self.click(position.x as u16, position.y as u16)
+    }
+
+    /// Scroll up with the mouse on an absolute position on the screen.
+    pub fn scroll_up(&mut self, x: u16, y: u16) -> bool {
+        self.send_mouse_event(MouseEventKind::ScrollUp, x, y)
+    }
+
+    /// Scroll down with the mouse on an absolute position on the screen.
+    pub fn scroll_down(&mut self, x: u16, y: u16) -> bool {
+        self.send_mouse_event(MouseEventKind::ScrollDown, x, y)
+    }
+
+    /// Scroll left with the mouse (the wheel with shift held) on an absolute position on the
+    /// screen.
+    pub fn scroll_left(&mut self, x: u16, y: u16) -> bool {
+        self.send_event(Event::Mouse(MouseEvent {
+            kind: MouseEventKind::ScrollUp,
+            column: x,
+            row: y,
+            modifiers: KeyModifiers::SHIFT,
+        }))
+    }
+
+    /// Scroll right with the mouse (the wheel with shift held) on an absolute position on the
+    /// screen.
+    pub fn scroll_right(&mut self, x: u16, y: u16) -> bool {
+        self.send_event(Event::Mouse(MouseEvent {
+            kind: MouseEventKind::ScrollDown,
+            column: x,
+            row: y,
+            modifiers: KeyModifiers::SHIFT,
+        }))
+    }
+
+    /// Move the mouse to an absolute position on the screen.
+    pub fn hover(&mut self, x: u16, y: u16) -> bool {
+        self.send_mouse_event(MouseEventKind::Moved, x, y)
+    }
+
+    /// Drag with the left mouse button through a list of absolute positions, releasing the
+    /// button on the last position.
+    pub fn drag(&mut self, path: &[(u16, u16)]) {
+        for (x, y) in path.iter() {
+            self.send_mouse_event(MouseEventKind::Drag(MouseButton::Left), *x, *y);
+        }
+        if let Some((x, y)) = path.last() {
+            self.send_mouse_event(MouseEventKind::Up(MouseButton::Left), *x, *y);
+        }
+    }
+
+    /// Resize the root layout as if the terminal was resized.
+    pub fn resize(&mut self, width: usize, height: usize) {
+        let state = self.state_tree.get_mut(&self.root_widget.path).as_generic_mut();
+        state.get_size_mut().set_width(width);
+        state.get_size_mut().set_height(height);
+        self.update();
+    }
+
+    fn send_mouse_event(&mut self, kind: MouseEventKind, x: u16, y: u16) -> bool {
+        self.send_event(Event::Mouse(MouseEvent {
+            kind,
+            column: x,
+            row: y,
+            modifiers: KeyModifiers::NONE,
+        }))
+    }
+}
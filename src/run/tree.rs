@@ -7,8 +7,12 @@ use std::iter::Peekable;
 
 use crossterm::style::StyledContent;
 
-use crate::run::definitions::{CallbackTree, Coordinates, Pixel, PixelMap, StateTree};
+use crate::run::definitions::{
+    CallbackTree, Coordinates, IsizeCoordinates, Pixel, PixelMap, Size, StateSnapshot, StateTree,
+};
+use crate::run::select::{get_target_by_position, widget_is_hidden};
 use crate::scheduler::scheduler::SchedulerFrontend;
+use crate::scheduler::scheduler_funcs::cancel_widget_tasks;
 use crate::states::button_state::ButtonState;
 use crate::states::canvas_state::CanvasState;
 use crate::states::checkbox_state::CheckboxState;
@@ -18,8 +22,16 @@ use crate::states::label_state::LabelState;
 use crate::states::layout_state::LayoutState;
 use crate::states::progress_bar_state::ProgressBarState;
 use crate::states::radio_button_state::RadioButtonState;
+use crate::states::rating_state::RatingState;
 use crate::states::slider_state::SliderState;
+use crate::states::stepper_state::StepperState;
+use crate::states::data_grid_state::DataGridState;
+use crate::states::list_view_state::ListViewState;
+use crate::states::text_box_state::TextBoxState;
 use crate::states::text_input_state::TextInputState;
+use crate::states::ticker_state::TickerState;
+use crate::states::qr_code_state::QrCodeState;
+use crate::states::heatmap_state::HeatmapState;
 use crate::widgets::ez_object::EzObject;
 use crate::widgets::layout::layout::Layout;
 use crate::{CallbackConfig, GenericState};
@@ -66,7 +78,12 @@ impl<T> Tree<T> {
     }
 
     fn _extend(&mut self, mut steps: Vec<&str>, tree: Tree<T>) {
-        self.id_cache.extend(tree.id_cache.clone());
+        // IDs cached by the new tree are relative to its' root, make them relative to this node
+        for (id, relative_steps) in tree.id_cache.iter() {
+            let mut cached_steps: Vec<String> = steps.iter().map(|x| x.to_string()).collect();
+            cached_steps.extend(relative_steps.iter().cloned());
+            self.id_cache.insert(id.clone(), cached_steps);
+        }
         self.id_cache.insert(
             steps.last().unwrap().to_string(),
             steps.iter().map(|x| x.to_string()).collect(),
@@ -436,7 +453,210 @@ impl<T> Tree<T> {
         Some(node)
     }
 }
+/// Ends a transaction (see [Tree::transaction]) when dropped, so a panic within the transaction
+/// does not leave updates of later frames being collected.
+struct TransactionGuard<'a>(&'a mut SchedulerFrontend);
+impl Drop for TransactionGuard<'_> {
+    fn drop(&mut self) {
+        self.0.backend.transaction_updates = None;
+    }
+}
+
 impl Tree<EzState> {
+    /// Change many widgets at once. Widget updates (e.g. state.update(scheduler)) requested
+    /// within the closure are not scheduled right away, but collected and scheduled once when the
+    /// closure ends. Duplicate updates are merged, and updates of widgets inside another updated
+    /// layout are dropped as the layout redraws them anyway. From a thread this also sends a
+    /// single batch of updates to the main thread instead of one message per update. Transactions
+    /// can be nested; updates are scheduled when the outermost transaction ends.
+    ///
+    /// Property values are always sent to subscribers on the next frame (only the latest value
+    /// of each property is used), so changes within a transaction are coalesced as well.
+    ///
+    /// # Example:
+    ///
+    /// We'll reset the text of many labels:
+    /// ```
+    /// use ez_term::*;
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// state_tree.transaction(&mut scheduler, |state_tree, scheduler| {
+    ///     for i in 0..100 {
+    ///         let state = state_tree.get_mut(&format!("label_{}", i)).as_label_mut();
+    ///         state.set_text(String::new());
+    ///         state.update(scheduler);
+    ///     }
+    /// });
+    /// ```
+    pub fn transaction<F>(&mut self, scheduler: &mut SchedulerFrontend, func: F)
+    where
+        F: FnOnce(&mut StateTree, &mut SchedulerFrontend),
+    {
+        if scheduler.backend.transaction_updates.is_some() {
+            func(self, scheduler);
+            return;
+        }
+        scheduler.backend.transaction_updates = Some(Vec::new());
+        let guard = TransactionGuard(scheduler);
+        func(self, guard.0);
+        let updates = guard
+            .0
+            .backend
+            .transaction_updates
+            .take()
+            .unwrap_or_default();
+        // Widgets inside an updated layout are redrawn by the layout anyway
+        let batch = updates
+            .iter()
+            .filter(|path| !updates.iter().any(|x| path.starts_with(&format!("{}/", x))))
+            .cloned()
+            .collect();
+        guard.0._update_widgets(batch);
+    }
+
+    /// Capture the current values of widgets, to restore them later with [restore]. Useful for
+    /// settings dialogs with a "Cancel" button: take a snapshot when the dialog opens and
+    /// restore it when the user cancels. Pass the IDs or paths of widgets or layouts; for a
+    /// layout all widgets in it are captured. The value the user enters is captured of each
+    /// widget (e.g. the text of a TextInput or the value of a Slider); widgets without such a
+    /// value are skipped. Properties of other widgets can be added to the snapshot with
+    /// [snapshot_property].
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use ez_term::*;
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// let snapshot = state_tree.snapshot(&["settings_layout"]);
+    /// // ... the user edits the settings and presses cancel:
+    /// state_tree.restore(&snapshot, &mut scheduler);
+    /// ```
+    pub fn snapshot(&self, paths: &[&str]) -> StateSnapshot {
+        let mut snapshot = StateSnapshot::default();
+        for path in paths {
+            for state in self.get(path).get_all() {
+                if let Some(property) = state.get_value_property() {
+                    let state = state.as_generic();
+                    snapshot.values.push((
+                        state.get_path().clone(),
+                        property.to_string(),
+                        state.get_property(property),
+                    ));
+                }
+            }
+        }
+        snapshot
+    }
+
+    /// Add the current value of any property of a widget to a snapshot, e.g. a color or the text
+    /// of a label. The property name is the name used in .ez files for the underlying value
+    /// (e.g. "fg_color", not "colors").
+    pub fn snapshot_property(&self, snapshot: &mut StateSnapshot, widget: &str, property: &str) {
+        let state = self.get(widget).as_generic();
+        snapshot.values.push((
+            state.get_path().clone(),
+            property.to_string(),
+            state.get_property(property),
+        ));
+    }
+
+    /// Put back the values of a snapshot taken with [snapshot] and redraw the widgets. Widgets
+    /// that were removed since the snapshot was taken are skipped.
+    pub fn restore(&mut self, snapshot: &StateSnapshot, scheduler: &mut SchedulerFrontend) {
+        self.transaction(scheduler, |state_tree, scheduler| {
+            for (path, property, value) in snapshot.values.iter() {
+                if let Some(node) = state_tree.try_get_mut(path) {
+                    let state = node.obj.as_generic_mut();
+                    state.update_property(property, value.clone());
+                    state.update(scheduler);
+                }
+            }
+        });
+    }
+
+    /// Get the path of the widget at a screen position, i.e. the widget a mouse event on that
+    /// position would be passed to: the deepest widget under the position. Disabled and hidden
+    /// widgets are skipped, and if a modal is open only the modal is searched. Returns the path
+    /// of the root layout if there is no other widget under the position, or None if the position
+    /// is outside the screen. Useful for e.g. custom drag and drop or context menus.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use ez_term::*;
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// if let Some(path) = state_tree.get_widget_at(Coordinates::new(10, 5)) {
+    ///     println!("{} is under the mouse", path);
+    /// }
+    /// ```
+    pub fn get_widget_at(&self, pos: Coordinates) -> Option<String> {
+        let size = self.as_generic().get_size();
+        if pos.x >= size.get_width() || pos.y >= size.get_height() {
+            return None;
+        }
+        Some(get_target_by_position(pos, self))
+    }
+
+    /// Get the part of a widget that is visible on the screen, as the absolute position of its'
+    /// top left corner and its' size. The widget is clipped by the content area of each of its'
+    /// parent layouts (so scrolled out parts are left out) and by the screen. Returns None if no
+    /// part of the widget is visible, for example because it is hidden or scrolled out of view.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use ez_term::*;
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// if let Some((pos, size)) = state_tree.get_visible_rect("my_label") {
+    ///     println!("{}x{} visible at {},{}", size.width, size.height, pos.x, pos.y);
+    /// }
+    /// ```
+    pub fn get_visible_rect(&self, widget: &str) -> Option<(Coordinates, Size)> {
+        let state = self.get(widget).as_generic();
+        let path = state.get_path();
+        if path != "/root" && widget_is_hidden(path.clone(), self) {
+            return None;
+        }
+        let pos = state.get_absolute_position();
+        let size = state.get_size();
+        let (mut left, mut top) = (pos.x, pos.y);
+        let mut right = left + size.get_width() as isize;
+        let mut bottom = top + size.get_height() as isize;
+
+        let mut clip = |pos: IsizeCoordinates, size: Size| {
+            left = left.max(pos.x);
+            top = top.max(pos.y);
+            right = right.min(pos.x + size.width as isize);
+            bottom = bottom.min(pos.y + size.height as isize);
+        };
+        let mut parent_path = path.as_str();
+        while let Some((parent, _)) = parent_path.rsplit_once('/') {
+            if let Some(node) = self.try_get(parent) {
+                let parent_state = node.as_generic();
+                clip(
+                    parent_state.get_effective_absolute_position(),
+                    parent_state.get_effective_size(),
+                );
+            }
+            parent_path = parent;
+        }
+        let screen_size = self.as_generic().get_size();
+        clip(
+            IsizeCoordinates::new(0, 0),
+            Size::new(screen_size.get_width(), screen_size.get_height()),
+        );
+        if left >= right || top >= bottom {
+            return None;
+        }
+        Some((
+            Coordinates::new(left as usize, top as usize),
+            Size::new((right - left) as usize, (bottom - top) as usize),
+        ))
+    }
+
     /// Cast this state as a layout state ref, you must be sure you have one.
     pub fn as_generic(&self) -> &dyn GenericState {
         self.obj.as_generic()
@@ -555,6 +775,96 @@ impl Tree<EzState> {
         }
     }
 
+    /// Cast this state as a Rating widget state ref, you must be sure you have one.
+    pub fn as_rating(&self) -> &RatingState {
+        if let EzState::Rating(ref i) = self.obj {
+            i
+        } else {
+            panic!("RatingState is the wrong type for {}.", self.id)
+        }
+    }
+
+    /// Cast this state as a mutable Rating widget state ref, you must be sure you have one.
+    pub fn as_rating_mut(&mut self) -> &mut RatingState {
+        if let EzState::Rating(ref mut i) = self.obj {
+            i
+        } else {
+            panic!("RatingState is the wrong type for {}.", self.id)
+        }
+    }
+
+    /// Cast this state as a Stepper widget state ref, you must be sure you have one.
+    pub fn as_stepper(&self) -> &StepperState {
+        if let EzState::Stepper(ref i) = self.obj {
+            i
+        } else {
+            panic!("StepperState is the wrong type for {}.", self.id)
+        }
+    }
+
+    /// Cast this state as a mutable Stepper widget state ref, you must be sure you have one.
+    pub fn as_stepper_mut(&mut self) -> &mut StepperState {
+        if let EzState::Stepper(ref mut i) = self.obj {
+            i
+        } else {
+            panic!("StepperState is the wrong type for {}.", self.id)
+        }
+    }
+
+    /// Cast this state as a Ticker widget state ref, you must be sure you have one.
+    pub fn as_ticker(&self) -> &TickerState {
+        if let EzState::Ticker(ref i) = self.obj {
+            i
+        } else {
+            panic!("TickerState is the wrong type for {}.", self.id)
+        }
+    }
+
+    /// Cast this state as a mutable Ticker widget state ref, you must be sure you have one.
+    pub fn as_ticker_mut(&mut self) -> &mut TickerState {
+        if let EzState::Ticker(ref mut i) = self.obj {
+            i
+        } else {
+            panic!("TickerState is the wrong type for {}.", self.id)
+        }
+    }
+
+    /// Cast this state as a QrCode widget state ref, you must be sure you have one.
+    pub fn as_qr_code(&self) -> &QrCodeState {
+        if let EzState::QrCode(ref i) = self.obj {
+            i
+        } else {
+            panic!("QrCodeState is the wrong type for {}.", self.id)
+        }
+    }
+
+    /// Cast this state as a mutable QrCode widget state ref, you must be sure you have one.
+    pub fn as_qr_code_mut(&mut self) -> &mut QrCodeState {
+        if let EzState::QrCode(ref mut i) = self.obj {
+            i
+        } else {
+            panic!("QrCodeState is the wrong type for {}.", self.id)
+        }
+    }
+
+    /// Cast this state as a Heatmap widget state ref, you must be sure you have one.
+    pub fn as_heatmap(&self) -> &HeatmapState {
+        if let EzState::Heatmap(ref i) = self.obj {
+            i
+        } else {
+            panic!("HeatmapState is the wrong type for {}.", self.id)
+        }
+    }
+
+    /// Cast this state as a mutable Heatmap widget state ref, you must be sure you have one.
+    pub fn as_heatmap_mut(&mut self) -> &mut HeatmapState {
+        if let EzState::Heatmap(ref mut i) = self.obj {
+            i
+        } else {
+            panic!("HeatmapState is the wrong type for {}.", self.id)
+        }
+    }
+
     /// Cast this state as a Checkbox widget state ref, you must be sure you have one.
     pub fn as_checkbox(&self) -> &CheckboxState {
         if let EzState::Checkbox(ref i) = self.obj {
@@ -644,6 +954,60 @@ impl Tree<EzState> {
             panic!("TextInputState is the wrong type for {}.", self.id)
         }
     }
+
+    /// Cast this state as a TextBox widget state ref, you must be sure you have one.
+    pub fn as_text_box(&self) -> &TextBoxState {
+        if let EzState::TextBox(ref i) = self.obj {
+            i
+        } else {
+            panic!("TextBoxState is the wrong type for {}.", self.id)
+        }
+    }
+
+    /// Cast this state as a mutable TextBox widget state ref, you must be sure you have one.
+    pub fn as_text_box_mut(&mut self) -> &mut TextBoxState {
+        if let EzState::TextBox(ref mut i) = self.obj {
+            i
+        } else {
+            panic!("TextBoxState is the wrong type for {}.", self.id)
+        }
+    }
+
+    /// Cast this state as a DataGrid widget state ref, you must be sure you have one.
+    pub fn as_data_grid(&self) -> &DataGridState {
+        if let EzState::DataGrid(ref i) = self.obj {
+            i
+        } else {
+            panic!("DataGridState is the wrong type for {}.", self.id)
+        }
+    }
+
+    /// Cast this state as a mutable DataGrid widget state ref, you must be sure you have one.
+    pub fn as_data_grid_mut(&mut self) -> &mut DataGridState {
+        if let EzState::DataGrid(ref mut i) = self.obj {
+            i
+        } else {
+            panic!("DataGridState is the wrong type for {}.", self.id)
+        }
+    }
+
+    /// Cast this state as a ListView widget state ref, you must be sure you have one.
+    pub fn as_list_view(&self) -> &ListViewState {
+        if let EzState::ListView(ref i) = self.obj {
+            i
+        } else {
+            panic!("ListViewState is the wrong type for {}.", self.id)
+        }
+    }
+
+    /// Cast this state as a mutable ListView widget state ref, you must be sure you have one.
+    pub fn as_list_view_mut(&mut self) -> &mut ListViewState {
+        if let EzState::ListView(ref mut i) = self.obj {
+            i
+        } else {
+            panic!("ListViewState is the wrong type for {}.", self.id)
+        }
+    }
 }
 
 /// Wrapper around a grid of StyledContent representing the entire screen currently being displayed.
@@ -692,6 +1056,23 @@ impl ViewTree {
         }
     }
 
+    /// Get the symbols displayed in an area of the screen as plain text, one line per row with
+    /// trailing whitespace trimmed. Parts of the area that are off screen are left out.
+    pub fn get_text(&self, position: IsizeCoordinates, width: usize, height: usize) -> String {
+        let screen_height = self.screen.first().map_or(0, |x| x.len()) as isize;
+        let x_range = position.x.max(0)..(position.x + width as isize).min(self.width() as isize);
+        let y_range = position.y.max(0)..(position.y + height as isize).min(screen_height);
+        let mut lines = Vec::new();
+        for y in y_range {
+            let line: String = x_range
+                .clone()
+                .map(|x| self.screen[x as usize][y as usize].content().as_str())
+                .collect();
+            lines.push(line.trim_end().to_string());
+        }
+        lines.join("\n")
+    }
+
     /// Get the current width of the view tree.
     pub fn width(&self) -> usize {
         return self.screen.len();
@@ -754,6 +1135,7 @@ pub fn clean_trees(
                 .get(&path)
                 .as_generic()
                 .clean_up_properties(scheduler);
+            cancel_widget_tasks(scheduler, &path);
             state_tree.remove_node(path.clone());
             if !scheduler.backend.properties.contains_key(&path) {
                 callback_tree.remove_node(path);
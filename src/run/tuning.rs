@@ -0,0 +1,195 @@
+//! # Layout tuning
+//!
+//! A module implementing layout tuning mode, a developer tool to tune the position and size of
+//! widgets with the keyboard instead of editing the .ez file and restarting the app for every
+//! change. Start it with [SchedulerFrontend.start_layout_tuning]. While it is active:
+//!
+//! - Clicking a widget makes it the tuning target (the click is not passed to the widget). If no
+//!   widget was clicked, the selected widget is the target.
+//! - Alt + arrow keys move the target by one cell. Moving only has an effect on widgets in a float
+//!   layout; the pos hint of the target is removed so the new position is used.
+//! - Ctrl + arrow keys make the target narrower, wider, shorter or taller by one cell; the size
+//!   hint of the target is removed on that axis so the new size is used.
+//!
+//! Every change is recorded as suggested .ez property lines for each tuned widget, which can be
+//! retrieved with [SchedulerFrontend.get_layout_tuning_suggestions] or written to a file
+//! automatically after every change.
+use std::collections::BTreeMap;
+use std::fs;
+
+use crossterm::event::{Event, KeyCode, KeyModifiers, MouseButton, MouseEventKind};
+
+use crate::run::definitions::{Coordinates, StateTree};
+use crate::run::select::get_widget_by_position;
+use crate::scheduler::scheduler::SchedulerFrontend;
+use crate::widgets::layout::layout::Layout;
+
+/// State of layout tuning mode, see the module documentation.
+#[derive(Clone, Debug, Default)]
+pub struct LayoutTuning {
+    /// Path of the widget that is tuned. Empty to tune the selected widget
+    target: String,
+
+    /// Widgets that were tuned by path, with what was changed
+    tuned: BTreeMap<String, TunedWidget>,
+
+    /// File the suggestions are written to after every change, if any
+    output_file: Option<String>,
+}
+
+/// What was changed about a tuned widget.
+#[derive(Clone, Copy, Debug, Default)]
+struct TunedWidget {
+    moved: bool,
+    width: bool,
+    height: bool,
+}
+
+impl LayoutTuning {
+    pub fn new(output_file: Option<String>) -> Self {
+        LayoutTuning {
+            output_file,
+            ..Default::default()
+        }
+    }
+
+    /// Get the path of the widget that is tuned.
+    pub fn get_target(&self) -> &String {
+        &self.target
+    }
+
+    /// Get the suggested .ez property lines of every tuned widget. Each widget starts with a
+    /// comment line containing its' path.
+    pub fn get_suggestions(&self, state_tree: &StateTree) -> String {
+        let mut suggestions = Vec::new();
+        for (path, tuned) in self.tuned.iter() {
+            let state = match state_tree.try_get(path) {
+                Some(i) => i.obj.as_generic(),
+                None => continue,
+            };
+            let mut lines = vec![format!("// {}", path)];
+            if tuned.moved {
+                lines.push(format!("x: {}", state.get_position().get_x()));
+                lines.push(format!("y: {}", state.get_position().get_y()));
+                lines.push("pos_hint: none, none".to_string());
+            }
+            if tuned.width {
+                lines.push("size_hint_x: none".to_string());
+                lines.push(format!("width: {}", state.get_size().get_width()));
+            }
+            if tuned.height {
+                lines.push("size_hint_y: none".to_string());
+                lines.push(format!("height: {}", state.get_size().get_height()));
+            }
+            suggestions.push(lines.join("\n"));
+        }
+        suggestions.join("\n\n")
+    }
+
+    /// Move or resize the target widget for a key, if the key is a tuning key. Returns whether
+    /// the widget was changed.
+    fn tune(
+        &mut self,
+        path: &str,
+        code: KeyCode,
+        modifiers: KeyModifiers,
+        state_tree: &mut StateTree,
+    ) -> bool {
+        let state = state_tree.get_mut(path).as_generic_mut();
+        let mut tuned = self.tuned.get(path).copied().unwrap_or_default();
+        if modifiers == KeyModifiers::ALT {
+            let (x, y) = (state.get_position().get_x(), state.get_position().get_y());
+            match code {
+                KeyCode::Left => state.set_x(x.saturating_sub(1)),
+                KeyCode::Right => state.set_x(x + 1),
+                KeyCode::Up => state.set_y(y.saturating_sub(1)),
+                KeyCode::Down => state.set_y(y + 1),
+                _ => return false,
+            }
+            state.set_pos_hint_x(None);
+            state.set_pos_hint_y(None);
+            tuned.moved = true;
+        } else if modifiers == KeyModifiers::CONTROL {
+            let (width, height) = (state.get_size().get_width(), state.get_size().get_height());
+            match code {
+                KeyCode::Left => state.set_width(width.saturating_sub(1).max(1)),
+                KeyCode::Right => state.set_width(width + 1),
+                KeyCode::Up => state.set_height(height.saturating_sub(1).max(1)),
+                KeyCode::Down => state.set_height(height + 1),
+                _ => return false,
+            }
+            if matches!(code, KeyCode::Left | KeyCode::Right) {
+                state.set_size_hint_x(None);
+                tuned.width = true;
+            } else {
+                state.set_size_hint_y(None);
+                tuned.height = true;
+            }
+        } else {
+            return false;
+        }
+        self.tuned.insert(path.to_string(), tuned);
+        true
+    }
+
+    /// Write the suggestions to the output file, if any.
+    fn write_suggestions(&self, state_tree: &StateTree) {
+        if let Some(ref output_file) = self.output_file {
+            fs::write(output_file, self.get_suggestions(state_tree)).unwrap_or_else(|e| {
+                panic!("Could not write layout tuning file {}: {}", output_file, e)
+            });
+        }
+    }
+}
+
+/// Handle an event in layout tuning mode. Returns whether the event was consumed; events that are
+/// not used for tuning are passed on as usual.
+pub fn handle_layout_tuning_event(
+    event: &Event,
+    root_widget: &Layout,
+    state_tree: &mut StateTree,
+    scheduler: &mut SchedulerFrontend,
+    selected_widget: &str,
+) -> bool {
+    let mut tuning = match scheduler.backend.layout_tuning.take() {
+        Some(i) => i,
+        None => return false,
+    };
+    let consumed = match event {
+        Event::Mouse(mouse_event) if mouse_event.kind == MouseEventKind::Down(MouseButton::Left) => {
+            let position = Coordinates::new(mouse_event.column as usize, mouse_event.row as usize);
+            if let Some(widget) = get_widget_by_position(position, root_widget, state_tree)
+                .into_iter()
+                .max_by_key(|x| x.get_path().matches('/').count())
+            {
+                tuning.target = widget.get_path();
+            }
+            true
+        }
+        // The rest of the click is not passed on either
+        Event::Mouse(mouse_event) => matches!(
+            mouse_event.kind,
+            MouseEventKind::Up(MouseButton::Left) | MouseEventKind::Drag(MouseButton::Left)
+        ),
+        Event::Key(key) => {
+            let path = if !tuning.target.is_empty() && state_tree.contains(&tuning.target) {
+                tuning.target.clone()
+            } else {
+                selected_widget.to_string()
+            };
+            if !path.is_empty()
+                && path != "/root"
+                && tuning.tune(&path, key.code, key.modifiers, state_tree)
+            {
+                tuning.write_suggestions(state_tree);
+                scheduler.force_redraw();
+                true
+            } else {
+                false
+            }
+        }
+        _ => false,
+    };
+    scheduler.backend.layout_tuning = Some(tuning);
+    consumed
+}
@@ -0,0 +1,212 @@
+//! # Actions
+//!
+//! This module implements [Action]s: named commands of an app, such as "Save", that carry a label,
+//! an optional key, whether they are enabled and a callback. Widgets bind to an action with the
+//! "action" property or [SchedulerFrontend.bind_action]; buttons and labels show the label and key
+//! of their action, bound widgets run it when pressed, and they are disabled while the action is.
+//! This keeps every button, menu item and key that runs the same command consistent. Actions are
+//! registered with [SchedulerFrontend.register_action], and enabled or disabled with
+//! [SchedulerFrontend.set_action_enabled] or their custom bool property (see
+//! [get_action_property_name]).
+use crossterm::event::{KeyCode, KeyModifiers};
+
+use crate::run::definitions::StateTree;
+use crate::scheduler::definitions::{Context, GenericFunction};
+use crate::scheduler::scheduler::SchedulerFrontend;
+use crate::states::definitions::create_keymap_modifiers;
+use crate::states::ez_state::EzState;
+
+/// A named command of an app, see the [actions] module. Register it with
+/// [SchedulerFrontend.register_action].
+///
+/// # Example:
+///
+/// ```
+/// use ez_term::*;
+///
+/// let save = Action::new_with_key(
+///     "Save",
+///     KeyCode::Char('s'),
+///     Some(vec![KeyModifiers::CONTROL]),
+///     Box::new(|context: Context| {
+///         // Save the document
+///         true
+///     }),
+/// );
+/// ```
+pub struct Action {
+    /// Text shown by the widgets bound to the action, e.g. "Save"
+    pub label: String,
+
+    /// Key and modifiers that run the action from anywhere in the app, if any. Shown as a hint
+    /// after the label, see [get_key_hint]
+    pub key: Option<(KeyCode, KeyModifiers)>,
+
+    /// Called when the action runs. None while it is running
+    func: Option<GenericFunction>,
+
+    /// Whether the action was enabled when its' widgets were last updated. None if they were not
+    /// updated yet
+    pub(crate) applied_enabled: Option<bool>,
+}
+impl Action {
+    /// Create an action without a key.
+    ///
+    /// # Parameters:
+    ///
+    /// - Label: &str
+    /// - Callback: Box<dyn FnMut(Context) -> bool>
+    pub fn new(label: &str, func: GenericFunction) -> Self {
+        Action {
+            label: label.to_string(),
+            key: None,
+            func: Some(func),
+            applied_enabled: None,
+        }
+    }
+
+    /// Create an action that also runs when a key is pressed anywhere in the app.
+    ///
+    /// # Parameters:
+    ///
+    /// - Label: &str
+    /// - Key: [KeyCode]
+    /// - Modifiers: Option<Vec<[KeyModifiers]>>
+    /// - Callback: Box<dyn FnMut(Context) -> bool>
+    pub fn new_with_key(
+        label: &str,
+        key: KeyCode,
+        modifiers: Option<Vec<KeyModifiers>>,
+        func: GenericFunction,
+    ) -> Self {
+        let mut action = Action::new(label, func);
+        action.key = Some((key, create_keymap_modifiers(modifiers)));
+        action
+    }
+
+    /// Get the key of the action as shown to the user, e.g. "Ctrl+S", or None if the action has
+    /// no key.
+    pub fn get_key_hint(&self) -> Option<String> {
+        self.key.map(|(key, modifiers)| format_key(key, modifiers))
+    }
+
+    /// Get the text shown by widgets bound to the action: the label followed by the key hint if
+    /// the action has a key, e.g. "Save (Ctrl+S)".
+    pub fn get_display_text(&self) -> String {
+        match self.get_key_hint() {
+            Some(hint) => format!("{} ({})", self.label, hint),
+            None => self.label.clone(),
+        }
+    }
+}
+
+/// Get the name of the custom bool property that holds whether an action is enabled. Bind it to
+/// widget properties, or set it from a thread, like any custom property.
+pub fn get_action_property_name(name: &str) -> String {
+    format!("action_{}_enabled", name)
+}
+
+/// Get a key as shown to the user, e.g. "Ctrl+Shift+S" or "F5".
+pub fn format_key(key: KeyCode, modifiers: KeyModifiers) -> String {
+    let mut text = String::new();
+    for (modifier, name) in [
+        (KeyModifiers::CONTROL, "Ctrl+"),
+        (KeyModifiers::ALT, "Alt+"),
+        (KeyModifiers::SHIFT, "Shift+"),
+    ] {
+        if modifiers.contains(modifier) {
+            text.push_str(name);
+        }
+    }
+    let key = match key {
+        KeyCode::Char(' ') => "Space".to_string(),
+        KeyCode::Char(i) => i.to_uppercase().to_string(),
+        KeyCode::F(i) => format!("F{}", i),
+        KeyCode::Esc => "Esc".to_string(),
+        KeyCode::PageUp => "PgUp".to_string(),
+        KeyCode::PageDown => "PgDn".to_string(),
+        other => format!("{:?}", other),
+    };
+    text.push_str(&key);
+    text
+}
+
+/// Run an action if it is registered and enabled. Returns the result of its' callback, or false
+/// if it did not run. This is what bound widgets and the key of the action do; use it to run an
+/// action from a callback of your own.
+///
+/// # Example:
+///
+/// ```
+/// use ez_term::*;
+///
+/// let on_keyboard_enter = |context: Context| run_action("save", context);
+/// ```
+pub fn run_action(name: &str, context: Context) -> bool {
+    if !context.scheduler.is_action_enabled(name) {
+        return false;
+    }
+    let mut func = match context
+        .scheduler
+        .backend
+        .actions
+        .get_mut(name)
+        .and_then(|x| x.func.take())
+    {
+        Some(i) => i,
+        None => return false,
+    };
+    let Context {
+        widget_path,
+        state_tree,
+        scheduler,
+        custom_data,
+        ..
+    } = context;
+    let result = func(Context::new(widget_path, state_tree, scheduler, custom_data));
+    // The action may have been registered again by its' own callback
+    if let Some(action) = scheduler.backend.actions.get_mut(name) {
+        if action.func.is_none() {
+            action.func = Some(func);
+        }
+    }
+    result
+}
+
+/// Update the widgets bound to actions that were registered, bound or enabled or disabled since
+/// the last frame: show the label and key of the action on buttons and labels, and disable the
+/// widgets while the action is disabled.
+pub(crate) fn update_actions(scheduler: &mut SchedulerFrontend, state_tree: &mut StateTree) {
+    let mut to_refresh = std::mem::take(&mut scheduler.backend.actions_to_refresh);
+    for (name, action) in scheduler.backend.actions.iter() {
+        if action.applied_enabled != Some(scheduler.is_action_enabled(name)) {
+            to_refresh.push(name.clone());
+        }
+    }
+    to_refresh.sort();
+    to_refresh.dedup();
+    for name in to_refresh {
+        let enabled = scheduler.is_action_enabled(&name);
+        let text = match scheduler.backend.actions.get_mut(&name) {
+            Some(action) => {
+                action.applied_enabled = Some(enabled);
+                action.get_display_text()
+            }
+            // Widgets can be bound before the action is registered
+            None => continue,
+        };
+        for path in scheduler.get_action_widgets(&name) {
+            let state = match state_tree.try_get_mut(&path) {
+                Some(i) => &mut i.obj,
+                None => continue,
+            };
+            match state {
+                EzState::Button(i) => i.set_text(text.clone()),
+                EzState::Label(i) => i.set_text(text.clone()),
+                _ => {}
+            }
+            state.as_generic_mut().set_disabled(!enabled);
+            state.as_generic().update(scheduler);
+        }
+    }
+}
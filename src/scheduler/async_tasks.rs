@@ -0,0 +1,173 @@
+//! # Async tasks
+//!
+//! This module implements running futures on a tokio runtime owned by the scheduler. The output of
+//! a future is delivered to a callback on the main thread, so the callback can manipulate the UI
+//! through a [Context]. Only available with the "tokio" feature.
+use std::future::Future;
+use std::sync::mpsc::{channel, TryRecvError};
+
+use tokio::runtime::{Builder, Handle, Runtime};
+use tokio::task::JoinHandle;
+
+use crate::run::definitions::StateTree;
+use crate::scheduler::definitions::{Context, CustomDataMap};
+use crate::scheduler::scheduler::SchedulerFrontend;
+
+/// Callback receiving the output of a future scheduled with [SchedulerFrontend.schedule_async].
+pub type AsyncCallback<T> = Box<dyn FnOnce(Context, T) + Send>;
+
+/// A future running on the tokio runtime of the scheduler. This struct is not directly used by
+/// the end-user, but created when they schedule a future through the [SchedulerFrontend].
+pub struct AsyncTask {
+    /// Name which can be used to cancel the future
+    pub name: String,
+
+    /// Handle of the spawned future, used to cancel it.
+    pub handle: JoinHandle<()>,
+
+    /// Called every frame; passes the output of the future to the callback and returns true once
+    /// the future is done.
+    pub deliver: Box<dyn FnMut(Context) -> bool + Send>,
+}
+
+impl SchedulerFrontend {
+    /// Run a future on the tokio runtime of the scheduler and pass its output to a callback on the
+    /// main thread. The futures of all calls share a small pool of worker threads, so many async
+    /// requests (e.g. through an async HTTP client) can run at the same time without spawning a
+    /// thread for each one. The callback receives a [Context] so it can update the UI with the
+    /// result. If the future panics or is cancelled the callback is not called. The runtime is
+    /// started on first use. Cannot be used from a threaded context. Only available with the
+    /// "tokio" feature.
+    ///
+    /// # Parameters:
+    ///
+    /// - Name: &str
+    /// - Future: impl Future + Send + 'static
+    /// - On result callback: Box<FnOnce(Context, Output)>
+    ///
+    /// # Example:
+    ///
+    /// We'll fetch some data asynchronously and show it in a label:
+    /// ```
+    /// use ez_term::*;
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// async fn fetch_weather() -> String {
+    ///     // Use your async HTTP client of choice here
+    ///     "Sunny".to_string()
+    /// }
+    ///
+    /// let on_result = |context: Context, weather: String| {
+    ///     let state = context.state_tree.get_mut("my_label").as_label_mut();
+    ///     state.set_text(weather);
+    /// };
+    /// scheduler.schedule_async("weather", fetch_weather(), Box::new(on_result));
+    ///
+    /// run(root_widget, state_tree, scheduler, custom_data);
+    /// ```
+    pub fn schedule_async<F>(&mut self, name: &str, future: F, on_result: AsyncCallback<F::Output>)
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static,
+    {
+        if self.synced {
+            panic!(
+                "Cannot schedule async task \"{}\" from a threaded context; pass a handle from \
+                get_async_handle to the thread instead.",
+                name
+            );
+        }
+        let (sender, receiver) = channel();
+        let handle = self.get_async_runtime().spawn(async move {
+            // The receiver is gone if the UI stopped; the output is not needed anymore then
+            let _ = sender.send(future.await);
+        });
+        let mut on_result = Some(on_result);
+        let deliver = move |context: Context| match receiver.try_recv() {
+            Ok(output) => {
+                if let Some(func) = on_result.take() {
+                    func(context, output);
+                }
+                true
+            }
+            Err(TryRecvError::Empty) => false,
+            Err(TryRecvError::Disconnected) => true,
+        };
+        self.backend.async_tasks.push(AsyncTask {
+            name: name.to_string(),
+            handle,
+            deliver: Box::new(deliver),
+        });
+    }
+
+    /// Cancel a future scheduled with [schedule_async]. Its callback will not be called. This
+    /// function is always safe to call, if there's no future to cancel it will not panic. Only
+    /// available with the "tokio" feature.
+    ///
+    /// # Parameters:
+    ///
+    /// - Name: &str
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use ez_term::*;
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// scheduler.schedule_async("my_request", async { 42 }, Box::new(|_: Context, _: i32| {}));
+    /// scheduler.cancel_async("my_request");
+    /// ```
+    pub fn cancel_async(&mut self, name: &str) {
+        self.backend.async_tasks.retain(|task| {
+            if task.name == name {
+                task.handle.abort();
+            }
+            task.name != name
+        });
+    }
+
+    /// Get a handle to the tokio runtime of the scheduler, starting it if needed. Use it to create
+    /// clients that need a runtime, or move it into a threaded function to spawn futures from
+    /// there. Cannot be used from a threaded context. Only available with the "tokio" feature.
+    pub fn get_async_handle(&mut self) -> Handle {
+        if self.synced {
+            panic!("Cannot get the async runtime from a threaded context.");
+        }
+        self.get_async_runtime().handle().clone()
+    }
+
+    /// Get the tokio runtime of the scheduler, starting it on first use.
+    fn get_async_runtime(&mut self) -> &Runtime {
+        self.backend.async_runtime.get_or_insert_with(|| {
+            Builder::new_multi_thread()
+                .worker_threads(2)
+                .thread_name("ez_term-async")
+                .enable_all()
+                .build()
+                .unwrap_or_else(|e| panic!("Could not start async runtime: {}", e))
+        })
+    }
+}
+
+/// Pass the output of any finished futures to their callbacks.
+pub fn update_async_tasks(
+    scheduler: &mut SchedulerFrontend,
+    state_tree: &mut StateTree,
+    custom_data: &mut CustomDataMap,
+) {
+    if scheduler.backend.async_tasks.is_empty() {
+        return;
+    }
+    let mut remaining = Vec::new();
+    for mut task in std::mem::take(&mut scheduler.backend.async_tasks) {
+        let context = Context::new(String::new(), state_tree, scheduler, custom_data);
+        if (task.deliver)(context) {
+            scheduler.backend.dirty = true;
+        } else {
+            remaining.push(task);
+        }
+    }
+    // Callbacks may have scheduled new futures
+    remaining.append(&mut scheduler.backend.async_tasks);
+    scheduler.backend.async_tasks = remaining;
+}
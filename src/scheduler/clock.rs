@@ -0,0 +1,80 @@
+//! # Clock
+//!
+//! This module implements the time sources used by the [Scheduler] to decide when scheduled tasks
+//! should run. By default the system clock is used; tests can use a [MockClock] to advance time
+//! manually.
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// A source of time for the scheduler. Implement this to control when scheduled tasks run.
+pub trait Clock: Send {
+    /// Get the current time
+    fn now(&self) -> Instant;
+}
+
+/// The real system clock. This is the default clock of the scheduler.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemClock;
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// A clock that only moves forward when told to. Clones share the same time, so you can keep a
+/// clone to advance the time after passing the clock to [SchedulerFrontend.set_clock].
+///
+/// # Example:
+///
+/// We'll test a task that is scheduled to run after 5 seconds, without waiting 5 seconds:
+/// ```
+/// use std::time::Duration;
+/// use ez_term::*;
+/// let (root_widget, state_tree, mut scheduler, custom_data) = load_ui();
+///
+/// let clock = MockClock::new();
+/// scheduler.set_clock(Box::new(clock.clone()));
+///
+/// let my_task = |context: Context| {
+///     context.state_tree.get_mut("my_label").as_label_mut().set_text("Done".to_string());
+/// };
+/// scheduler.schedule_once("my_task", Box::new(my_task), Duration::from_secs(5));
+///
+/// let mut ui = TestUi::new(root_widget, state_tree, scheduler, custom_data);
+/// clock.advance(Duration::from_secs(5));
+/// ui.update();
+/// assert_eq!(ui.state_tree.get("my_label").as_label().get_text(), "Done");
+/// ```
+#[derive(Clone, Debug)]
+pub struct MockClock {
+    start: Instant,
+    elapsed: Arc<Mutex<Duration>>,
+}
+impl MockClock {
+    pub fn new() -> Self {
+        MockClock {
+            start: Instant::now(),
+            elapsed: Arc::new(Mutex::new(Duration::ZERO)),
+        }
+    }
+
+    /// Move the time of this clock (and all its' clones) forward.
+    pub fn advance(&self, duration: Duration) {
+        *self.elapsed.lock().unwrap() += duration;
+    }
+
+    /// Get the time that has passed on this clock since it was created.
+    pub fn get_elapsed(&self) -> Duration {
+        *self.elapsed.lock().unwrap()
+    }
+}
+impl Default for MockClock {
+    fn default() -> Self {
+        MockClock::new()
+    }
+}
+impl Clock for MockClock {
+    fn now(&self) -> Instant {
+        self.start + self.get_elapsed()
+    }
+}
@@ -0,0 +1,224 @@
+//! # Data source
+//!
+//! This module implements the [DataSource] struct, which loads CSV or JSON files into rows and
+//! columns that can be displayed by table-like widgets.
+use std::fs;
+use std::io::{Error, ErrorKind};
+use std::time::SystemTime;
+
+use serde_json::Value;
+
+/// Supported file formats of a [DataSource].
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum DataFormat {
+    /// Comma separated values. The first row contains the column names. Values may be quoted
+    /// with double quotes to include commas, line breaks or (doubled) quotes.
+    Csv,
+
+    /// Either an array of objects, in which case the keys become the columns, or an array of
+    /// arrays, in which case the first array contains the column names.
+    Json,
+}
+
+/// Rows and columns loaded by a [DataSource]. All values are converted to strings.
+#[derive(PartialEq, Clone, Debug, Default)]
+pub struct DataTable {
+    /// Names of the columns
+    pub columns: Vec<String>,
+
+    /// Values of each row, in the same order as [columns]
+    pub rows: Vec<Vec<String>>,
+}
+impl DataTable {
+    /// Get the index of a column by its' name
+    pub fn get_column_index(&self, column: &str) -> Option<usize> {
+        self.columns.iter().position(|x| x == column)
+    }
+
+    /// Get all values of a single column by its' name
+    pub fn get_column(&self, column: &str) -> Option<Vec<String>> {
+        let index = self.get_column_index(column)?;
+        Some(
+            self.rows
+                .iter()
+                .map(|row| row.get(index).cloned().unwrap_or_default())
+                .collect(),
+        )
+    }
+}
+
+/// A CSV or JSON file that can be loaded into a [DataTable]. The data source remembers when the
+/// file was last modified, so it can be reloaded only when the file changes. Use
+/// [SchedulerFrontend.bind_data_source] to automatically refresh widgets when the file changes.
+#[derive(Clone, Debug)]
+pub struct DataSource {
+    /// Path to the file to load
+    pub path: String,
+
+    /// Format of the file
+    pub format: DataFormat,
+
+    /// When the file was modified when it was last loaded successfully
+    last_modified: Option<SystemTime>,
+
+    /// The data that was last loaded successfully
+    table: DataTable,
+}
+impl DataSource {
+    /// Create a data source for a file in a specific format. The file is not loaded until [load]
+    /// is called.
+    pub fn new(path: &str, format: DataFormat) -> Self {
+        DataSource {
+            path: path.to_string(),
+            format,
+            last_modified: None,
+            table: DataTable::default(),
+        }
+    }
+
+    /// Create a data source for a file, using the file extension (.csv or .json) to determine
+    /// the format.
+    pub fn from_file(path: &str) -> Result<Self, Error> {
+        let format = if path.to_lowercase().ends_with(".csv") {
+            DataFormat::Csv
+        } else if path.to_lowercase().ends_with(".json") {
+            DataFormat::Json
+        } else {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "Cannot determine data format of {}, extension must be .csv or .json",
+                    path
+                ),
+            ));
+        };
+        Ok(DataSource::new(path, format))
+    }
+
+    /// Returns a bool representing whether the file was modified since it was last loaded
+    /// successfully. Also returns true if it was never loaded.
+    pub fn has_changed(&self) -> bool {
+        match fs::metadata(&self.path).and_then(|x| x.modified()) {
+            Ok(modified) => self.last_modified != Some(modified),
+            Err(_) => false,
+        }
+    }
+
+    /// (Re)load the file. If loading fails the previously loaded data is kept.
+    pub fn load(&mut self) -> Result<&DataTable, Error> {
+        let modified = fs::metadata(&self.path)?.modified().ok();
+        let text = fs::read_to_string(&self.path)?;
+        self.table = match self.format {
+            DataFormat::Csv => parse_csv(&text),
+            DataFormat::Json => parse_json(&text)?,
+        };
+        self.last_modified = modified;
+        Ok(&self.table)
+    }
+
+    /// Get the data that was last loaded successfully
+    pub fn get_table(&self) -> &DataTable {
+        &self.table
+    }
+}
+
+/// Parse CSV text into a [DataTable]. The first record contains the column names.
+pub fn parse_csv(text: &str) -> DataTable {
+    let mut records: Vec<Vec<String>> = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = text.chars().peekable();
+    while let Some(char) = chars.next() {
+        if in_quotes {
+            if char == '"' {
+                if chars.peek() == Some(&'"') {
+                    field.push('"');
+                    chars.next();
+                } else {
+                    in_quotes = false;
+                }
+            } else {
+                field.push(char);
+            }
+            continue;
+        }
+        match char {
+            '"' => in_quotes = true,
+            ',' => record.push(std::mem::take(&mut field)),
+            '\r' => (),
+            '\n' => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            }
+            _ => field.push(char),
+        }
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+    records.retain(|x| !(x.len() == 1 && x[0].is_empty()));
+
+    let mut records = records.into_iter();
+    DataTable {
+        columns: records.next().unwrap_or_default(),
+        rows: records.collect(),
+    }
+}
+
+/// Parse JSON text into a [DataTable]. See [DataFormat::Json] for the supported layouts.
+pub fn parse_json(text: &str) -> Result<DataTable, Error> {
+    let value: Value = serde_json::from_str(text)
+        .map_err(|e| Error::new(ErrorKind::InvalidData, format!("Invalid JSON: {}", e)))?;
+    let items = value.as_array().ok_or_else(|| {
+        Error::new(
+            ErrorKind::InvalidData,
+            "JSON data source must contain an array of objects or arrays",
+        )
+    })?;
+
+    let mut table = DataTable::default();
+    if items.iter().all(|x| x.is_object()) {
+        for item in items {
+            for key in item.as_object().unwrap().keys() {
+                if !table.columns.contains(key) {
+                    table.columns.push(key.clone());
+                }
+            }
+        }
+        for item in items {
+            let object = item.as_object().unwrap();
+            table.rows.push(
+                table
+                    .columns
+                    .iter()
+                    .map(|x| object.get(x).map(json_to_string).unwrap_or_default())
+                    .collect(),
+            );
+        }
+    } else if items.iter().all(|x| x.is_array()) {
+        let mut items = items.iter().map(|x| x.as_array().unwrap());
+        if let Some(header) = items.next() {
+            table.columns = header.iter().map(json_to_string).collect();
+        }
+        table.rows = items
+            .map(|x| x.iter().map(json_to_string).collect())
+            .collect();
+    } else {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            "JSON data source must contain an array of objects or arrays",
+        ));
+    }
+    Ok(table)
+}
+
+/// Convert a JSON value to the string shown in a table. Strings are shown without quotes.
+fn json_to_string(value: &Value) -> String {
+    match value {
+        Value::String(i) => i.clone(),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
@@ -3,13 +3,17 @@
 //! This module implements definitions for the [Scheduler] struct.
 use std::any::Any;
 use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
-use crossterm::event::{KeyCode, KeyModifiers};
+use crossterm::event::{Event, KeyCode, KeyModifiers};
 
 use crate::property::ez_properties::EzProperties;
 use crate::property::ez_values::EzValues;
 use crate::run::definitions::{Coordinates, IsizeCoordinates, StateTree};
+use crate::scheduler::data_source::DataTable;
 use crate::scheduler::scheduler::SchedulerFrontend;
+use crate::states::ez_state::StateType;
 
 /// Custom data that the end-user can add to the scheduler to access in callbacks
 pub trait CustomData {
@@ -26,7 +30,9 @@ pub type EzPropertiesMap = HashMap<String, EzProperties>;
 pub type KeyboardCallbackFunction = Box<dyn FnMut(Context, KeyCode, KeyModifiers) -> bool + Send>;
 
 /// This is used for binding mouse event callbacks to widgets, meaning that any callback functions
-/// user makes should use this signature.
+/// user makes should use this signature. The mouse position is relative to the top left corner of
+/// the widget, including its' border and padding. Use [GenericState.local_to_content_position] or
+/// [GenericState.local_to_absolute_position] to convert it.
 pub type MouseCallbackFunction = Box<dyn FnMut(Context, Coordinates) -> bool + Send>;
 
 /// This is used for callbacks that may or may not have been initiated by mouse. 'on_select' uses
@@ -39,6 +45,23 @@ pub type OptionalMouseCallbackFunction =
 pub type MouseDragCallbackFunction =
     Box<dyn FnMut(Context, Option<IsizeCoordinates>, IsizeCoordinates) -> bool + Send>;
 
+/// Used for on_drag_start callbacks. Receives the position the drag started on; return a
+/// [DragPayload] to start a drag and drop, or None to let the drag pass on to other widgets.
+pub type DragStartCallbackFunction =
+    Box<dyn FnMut(Context, IsizeCoordinates) -> Option<DragPayload> + Send>;
+
+/// Used for on_drop callbacks. Receives the dropped [DragPayload] and the position it was dropped
+/// on; return true to accept the drop.
+pub type DropCallbackFunction =
+    Box<dyn FnMut(Context, DragPayload, IsizeCoordinates) -> bool + Send>;
+
+/// Used for on_paste callbacks. Receives the pasted text; return true to consume the paste.
+pub type PasteCallbackFunction = Box<dyn FnMut(Context, String) -> bool + Send>;
+
+/// Used for the global input filter. Receives every event before it is routed; return the
+/// (possibly changed) event to pass it on, or None to swallow it.
+pub type InputFilterFunction = Box<dyn FnMut(Context, Event) -> Option<Event> + Send>;
+
 /// Used for callbacks and scheduled tasks that don't require special parameter such as KeyCodes
 /// or mouse positions. Used e.g. for [on_value_change] and [on_keyboard_enter].
 pub type GenericFunction = Box<dyn FnMut(Context) -> bool + Send>;
@@ -56,10 +79,29 @@ pub type GenericRecurringTask = Box<dyn FnMut(Context) -> bool + Send>;
 /// changes, an [EzPropertyUpdates] func will be called to do the actual sync.
 pub type EzPropertyUpdater = Box<dyn FnMut(&mut StateTree, EzValues) + Send>;
 
+/// Computes the value of a computed property from the values of its' dependencies, in the order
+/// the dependencies were passed to [SchedulerFrontend.new_computed_property].
+pub type ComputedPropertyFunction = Box<dyn FnMut(&[EzValues]) -> EzValues + Send>;
+
 /// Func that can be spawned as a background thread. Receives a dict of all [EzProperties].
 /// Ez properties can be bound to widgets, so updating an EzProperty in a thread can update the UI.
 pub type EzThread = Box<dyn FnOnce(ThreadedContext) + Send>;
 
+/// Called when a bound [DataSource] was (re)loaded. Receives the freshly loaded [DataTable].
+pub type DataSourceCallback = Box<dyn FnMut(Context, &DataTable) + Send>;
+
+/// Called when a modal opened with [SchedulerFrontend.open_modal_with_result] is closed. Receives
+/// the result set by the modal, or None if the modal was dismissed without a result.
+pub type ModalResultFunction<T> = Box<dyn FnOnce(Context, Option<T>) + Send>;
+
+/// Resolves the result of the modal opened with [SchedulerFrontend.open_modal_with_result]. Not
+/// used directly by end-users.
+pub type ModalResultResolver = Box<dyn FnOnce(Context, Option<Box<dyn Any + Send>>) + Send>;
+
+/// Called with the plain text a widget is displayed as, when it is rendered with
+/// [SchedulerFrontend.render_widget_to_string].
+pub type RenderCallback = Box<dyn FnOnce(Context, String) + Send>;
+
 pub type CustomDataMap = HashMap<String, Box<dyn CustomData + Send>>;
 
 /// This object is provided to callbacks. You can use it to gain access to the [StateTree] and the
@@ -89,6 +131,11 @@ pub struct Context<'a, 'b, 'c> {
 
     /// Custom user data
     pub custom_data: &'c mut CustomDataMap,
+
+    /// The terminal event that caused the callback to be called, if any. For example the mouse
+    /// click or key press that pressed a button. None for callbacks not caused by an event, such
+    /// as scheduled tasks and property callbacks.
+    pub event: Option<Event>,
 }
 impl<'a, 'b, 'c> Context<'a, 'b, 'c> {
     pub fn new(
@@ -97,15 +144,528 @@ impl<'a, 'b, 'c> Context<'a, 'b, 'c> {
         scheduler: &'b mut SchedulerFrontend,
         custom_data: &'c mut CustomDataMap,
     ) -> Self {
+        let event = scheduler.backend.current_event.clone();
         Context {
             widget_path,
             state_tree,
             scheduler,
             custom_data,
+            event,
+        }
+    }
+
+    /// Get the routing control of the event that is currently being handled. Use it to stop the
+    /// event from being passed to more widgets, or to prevent widgets from handling it. See
+    /// [EventControl].
+    ///
+    /// # Example:
+    ///
+    /// We'll make a layout swallow all clicks on its' children while it's in a "locked" mode:
+    /// ```
+    /// use ez_term::*;
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// let on_capture = |mut context: Context| {
+    ///     if let Some(Event::Mouse(_)) = context.event {
+    ///         context.event_control().prevent_default();
+    ///         context.event_control().stop_propagation();
+    ///     }
+    ///     false
+    /// };
+    /// scheduler.update_callback_config("my_layout", CallbackConfig::from_on_capture(Box::new(on_capture)));
+    /// ```
+    pub fn event_control(&mut self) -> &mut EventControl {
+        &mut self.scheduler.backend.event_control
+    }
+
+    /// Get the modifiers (shift, control, alt) that were held during the event that caused the
+    /// callback to be called. Returns KeyModifiers::NONE if there is no key or mouse event.
+    ///
+    /// # Example:
+    ///
+    /// We'll make a button do something different when it's pressed with control held:
+    /// ```
+    /// use ez_term::*;
+    /// let on_press = |context: Context| {
+    ///     if context.get_modifiers().contains(KeyModifiers::CONTROL) {
+    ///         // Ctrl+click or Ctrl+Enter
+    ///     } else {
+    ///         // Normal click or Enter
+    ///     }
+    ///     true
+    /// };
+    /// ```
+    pub fn get_modifiers(&self) -> KeyModifiers {
+        match &self.event {
+            Some(Event::Key(key)) => key.modifiers,
+            Some(Event::Mouse(mouse)) => mouse.modifiers,
+            _ => KeyModifiers::NONE,
+        }
+    }
+
+    /// Get the full path of the widget this context refers to, e.g. "/root/layout/my_button".
+    pub fn widget_path(&self) -> &str {
+        &self.widget_path
+    }
+
+    /// Get the ID of the widget that triggered the callback this context was passed to, e.g.
+    /// "my_button". Useful when the same callback is bound to multiple widgets.
+    pub fn trigger_widget(&self) -> String {
+        match self.widget_path.rsplit_once('/') {
+            Some((_, id)) => id.to_string(),
+            None => self.widget_path.clone(),
+        }
+    }
+
+    /// Get the state of a widget (by ID or path) as a specific state type. Returns an error
+    /// describing the problem if the widget does not exist or is of a different type.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use ez_term::*;
+    /// let on_press = |context: Context| {
+    ///     match context.get_state_as::<LabelState>("my_label") {
+    ///         Ok(state) => println!("Label text: {}", state.get_text()),
+    ///         Err(error) => println!("{}", error),
+    ///     }
+    ///     true
+    /// };
+    /// ```
+    pub fn get_state_as<T: StateType>(&self, widget: &str) -> Result<&T, String> {
+        let state = &self
+            .state_tree
+            .try_get(widget)
+            .ok_or_else(|| format!("Widget \"{}\" does not exist", widget))?
+            .obj;
+        let widget_type = state.get_widget_type();
+        state.try_as::<T>().ok_or_else(|| {
+            format!(
+                "Widget \"{}\" is a {}, not a {}",
+                widget,
+                widget_type,
+                T::WIDGET_TYPE
+            )
+        })
+    }
+
+    /// Get the mutable state of a widget (by ID or path) as a specific state type. Returns an
+    /// error describing the problem if the widget does not exist or is of a different type.
+    pub fn get_state_as_mut<T: StateType>(&mut self, widget: &str) -> Result<&mut T, String> {
+        let state = &mut self
+            .state_tree
+            .try_get_mut(widget)
+            .ok_or_else(|| format!("Widget \"{}\" does not exist", widget))?
+            .obj;
+        let widget_type = state.get_widget_type();
+        state.try_as_mut::<T>().ok_or_else(|| {
+            format!(
+                "Widget \"{}\" is a {}, not a {}",
+                widget,
+                widget_type,
+                T::WIDGET_TYPE
+            )
+        })
+    }
+}
+
+/// Phase of the routing of an event. Events first travel from the root layout to the target
+/// widget (capture), then the target widget handles the event (target), and afterwards it travels
+/// back from the target widget to the root layout (bubble). For mouse events the target is the
+/// deepest widget under the mouse; for other events it is the selected widget (or the root layout
+/// if nothing is selected).
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum EventPhase {
+    /// The event travels from the root layout to the target widget. Only the on_capture callbacks
+    /// of widgets are called during this phase.
+    Capture,
+
+    /// The target widget (and widgets under the mouse) handle the event as they always do, e.g. a
+    /// button calls its' on_press callback.
+    Target,
+
+    /// The event travels from the target widget back to the root layout. Only the on_bubble
+    /// callbacks of widgets are called during this phase.
+    Bubble,
+}
+impl Default for EventPhase {
+    fn default() -> Self {
+        EventPhase::Target
+    }
+}
+
+/// Controls the routing of the event that is currently being handled. Callbacks can access it
+/// through [Context.event_control]. Returning true from a callback still consumes the event;
+/// this allows handling an event partially, e.g. letting a layout react to a click while still
+/// allowing the clicked button to be pressed.
+#[derive(PartialEq, Clone, Copy, Debug, Default)]
+pub struct EventControl {
+    /// The current phase of the event. See [EventPhase].
+    pub phase: EventPhase,
+
+    propagation_stopped: bool,
+
+    default_prevented: bool,
+}
+impl EventControl {
+    /// Reset the control for a new event.
+    pub fn new(phase: EventPhase) -> Self {
+        EventControl {
+            phase,
+            propagation_stopped: false,
+            default_prevented: false,
+        }
+    }
+
+    /// Do not pass the event to any more widgets after the current callback.
+    pub fn stop_propagation(&mut self) {
+        self.propagation_stopped = true;
+    }
+
+    /// Skip the target phase: the widgets will not handle the event themselves (e.g. a button
+    /// will not be pressed), but the event will still bubble unless propagation is stopped. Only
+    /// has effect during the capture phase.
+    pub fn prevent_default(&mut self) {
+        self.default_prevented = true;
+    }
+
+    /// Returns a bool representing whether [stop_propagation] was called for this event.
+    pub fn is_propagation_stopped(&self) -> bool {
+        self.propagation_stopped
+    }
+
+    /// Returns a bool representing whether [prevent_default] was called for this event.
+    pub fn is_default_prevented(&self) -> bool {
+        self.default_prevented
+    }
+}
+
+/// What is carried during a drag and drop. Created by the on_drag_start callback of the widget the
+/// drag started on, and passed to the on_drop callback of the widget it is dropped on. While
+/// dragging, the payload can be read with [SchedulerFrontend.get_drag_payload], e.g. to highlight
+/// the widgets it can be dropped on.
+#[derive(PartialEq, Clone, Debug)]
+pub struct DragPayload {
+    /// Full path of the widget the drag started on. Filled in when the drag starts.
+    pub source: String,
+
+    /// What is dragged, e.g. the ID of a task or the index of a list item.
+    pub data: String,
+}
+impl DragPayload {
+    /// Create a payload carrying data, to return from an on_drag_start callback.
+    pub fn new(data: &str) -> Self {
+        DragPayload {
+            source: String::new(),
+            data: data.to_string(),
         }
     }
 }
 
+/// How scrolling and slider adjustment speed up while a key is held down. The curve gives the
+/// amount of extra steps per second for the amount of seconds the key has been held. See
+/// [KeyAcceleration].
+#[derive(Clone, Copy, Debug)]
+pub enum AccelerationCurve {
+    /// Held keys do not speed up: every key event moves a single step.
+    None,
+
+    /// The extra steps per second grow evenly with the time the key is held (rate * seconds).
+    Linear,
+
+    /// The extra steps per second grow with the square of the time the key is held
+    /// (rate * seconds * seconds). Starts slower than linear, but gets faster after a second.
+    Quadratic,
+
+    /// A custom curve, called with the seconds the key has been held. Returns the extra steps per
+    /// second; the rate is not applied.
+    Custom(fn(f64) -> f64),
+}
+
+/// Settings for speeding up scrolling and slider adjustment while a key is held down. Because the
+/// speed depends on the time the key has been held rather than on the amount of key events, it
+/// does not change with the key repeat rate of the terminal or the tick rate of the UI. Set with
+/// [SchedulerFrontend.set_key_acceleration].
+#[derive(Clone, Copy, Debug)]
+pub struct KeyAcceleration {
+    /// The shape of the acceleration. See [AccelerationCurve].
+    pub curve: AccelerationCurve,
+
+    /// Multiplier for the linear and quadratic curves.
+    pub rate: f64,
+
+    /// Maximum amount of steps a single key event can move.
+    pub max_steps: usize,
+
+    /// Key events of the same key that follow each other within this amount of milliseconds count
+    /// as the key being held. Must be longer than the delay before the terminal starts repeating a
+    /// held key.
+    pub hold_timeout: u64,
+}
+impl Default for KeyAcceleration {
+    fn default() -> Self {
+        KeyAcceleration {
+            curve: AccelerationCurve::Linear,
+            rate: 6.0,
+            max_steps: 10,
+            hold_timeout: 600,
+        }
+    }
+}
+impl KeyAcceleration {
+    /// Get the amount of extra steps per second for a key held for the given amount of seconds.
+    pub fn get_extra_steps_per_second(&self, held_for: f64) -> f64 {
+        match self.curve {
+            AccelerationCurve::None => 0.0,
+            AccelerationCurve::Linear => self.rate * held_for,
+            AccelerationCurve::Quadratic => self.rate * held_for * held_for,
+            AccelerationCurve::Custom(curve) => curve(held_for).max(0.0),
+        }
+    }
+}
+
+/// Keeps track of the key that is held down, to work out the amount of steps the current key
+/// event should move. See [SchedulerFrontend.get_key_repeat_steps].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct KeyRepeat {
+    key: Option<(KeyCode, KeyModifiers)>,
+
+    /// When the key started repeating; the acceleration starts from this moment, so the delay of
+    /// the terminal before it repeats a held key does not count.
+    repeating_since: Option<Instant>,
+
+    last_event: Option<Instant>,
+
+    /// Fractional steps that are carried over to the next key event
+    carry: f64,
+
+    steps: usize,
+}
+impl KeyRepeat {
+    /// Register a key event at the given time.
+    pub(crate) fn register(
+        &mut self,
+        key: (KeyCode, KeyModifiers),
+        now: Instant,
+        acceleration: &KeyAcceleration,
+    ) {
+        let held = match (self.key, self.last_event) {
+            (Some(last_key), Some(last_event)) => {
+                last_key == key
+                    && now.saturating_duration_since(last_event)
+                        <= Duration::from_millis(acceleration.hold_timeout)
+            }
+            _ => false,
+        };
+        let last_event = self.last_event.replace(now);
+        self.steps = 1;
+        if !held {
+            self.key = Some(key);
+            self.repeating_since = None;
+            self.carry = 0.0;
+            return;
+        }
+        let repeating_since = match self.repeating_since {
+            Some(i) => i,
+            None => {
+                self.repeating_since = Some(now);
+                return;
+            }
+        };
+        let held_for = now.saturating_duration_since(repeating_since).as_secs_f64();
+        let elapsed = now.saturating_duration_since(last_event.unwrap()).as_secs_f64();
+        self.carry += acceleration.get_extra_steps_per_second(held_for) * elapsed;
+        let extra = self.carry.floor();
+        self.carry -= extra;
+        let max_steps = acceleration.max_steps.max(1);
+        self.steps = (1 + extra as usize).min(max_steps);
+        if self.steps == max_steps {
+            self.carry = 0.0;
+        }
+    }
+
+    /// Get the amount of steps the last key event should move.
+    pub fn get_steps(&self) -> usize {
+        self.steps.max(1)
+    }
+}
+
+/// Settings for what happens between the app exiting (see [SchedulerFrontend.force_exit]) and the
+/// terminal being restored. Set with [SchedulerFrontend.set_exit_teardown]; see
+/// [SchedulerFrontend.exit] for the order of the teardown steps.
+#[derive(Clone, Debug)]
+pub struct ExitTeardown {
+    /// Maximum time the teardown callback set with [SchedulerFrontend.set_on_teardown] may run,
+    /// e.g. to fade out the UI. The app exits after this time even if the callback is not done.
+    pub timeout: Duration,
+
+    /// Whether to redraw the whole screen one last time after the teardown, so every change made
+    /// while tearing down is visible. Mostly useful if the screen is not cleared.
+    pub final_frame: bool,
+
+    /// Whether to clear the screen when the terminal is restored. If false the final frame stays
+    /// visible and the shell continues below it.
+    pub clear_screen: bool,
+
+    /// Message printed to the terminal after it is restored, e.g. "Goodbye!".
+    pub message: Option<String>,
+}
+impl Default for ExitTeardown {
+    fn default() -> Self {
+        ExitTeardown {
+            timeout: Duration::from_secs(1),
+            final_frame: true,
+            clear_screen: true,
+            message: None,
+        }
+    }
+}
+
+/// A custom property whose value is derived from other properties. Created with
+/// [SchedulerFrontend.new_computed_property].
+pub struct ComputedProperty {
+    /// Name of the custom property that holds the computed value
+    pub name: String,
+
+    /// Names of the properties the value is computed from
+    pub dependencies: Vec<String>,
+
+    /// Last known values of the dependencies
+    pub values: Vec<EzValues>,
+
+    /// Whether a dependency changed since the value was last computed
+    pub dirty: bool,
+
+    pub compute: ComputedPropertyFunction,
+}
+
+/// Timings of the phases of loading and starting the UI, to find out what makes startup of a
+/// large UI slow. Get it with [SchedulerFrontend.get_startup_profile]; it can be printed directly.
+/// Phases are added in the order they ran. The "initialize widgets" phase is only added once
+/// [run] starts, so read the profile from a callback to see it.
+#[derive(Clone, Debug, Default)]
+pub struct StartupProfile {
+    /// Name and duration of each phase
+    pub phases: Vec<(String, Duration)>,
+
+    /// Amount of widgets in the loaded UI
+    pub widgets: usize,
+
+    /// Amount of properties created for the loaded UI (including custom properties)
+    pub properties: usize,
+
+    /// Amount of properties that are bound to another property
+    pub bound_properties: usize,
+}
+impl StartupProfile {
+    /// Time the given phase and add it to the profile.
+    pub fn add_phase(&mut self, name: &str, started: Instant) {
+        self.phases.push((name.to_string(), started.elapsed()));
+    }
+
+    /// Get the duration of a phase, if it ran.
+    pub fn get_phase(&self, name: &str) -> Option<Duration> {
+        self.phases.iter().find(|(x, _)| x == name).map(|(_, x)| *x)
+    }
+
+    /// Get the total duration of all phases.
+    pub fn get_total(&self) -> Duration {
+        self.phases.iter().map(|(_, x)| *x).sum()
+    }
+}
+impl std::fmt::Display for StartupProfile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "Startup: {} widgets, {} properties ({} bound)",
+            self.widgets, self.properties, self.bound_properties
+        )?;
+        for (name, duration) in self.phases.iter() {
+            writeln!(f, "  {:<28}{:>10.1?}", name, duration)?;
+        }
+        write!(f, "  {:<28}{:>10.1?}", "total", self.get_total())
+    }
+}
+
+/// Handle to the result of a modal opened with [SchedulerFrontend.open_modal_with_result]. The
+/// modal sets its' result with [SchedulerFrontend.set_modal_result]; the handle is resolved when
+/// the modal is closed, with None if the modal was dismissed without a result. Bind a callback with
+/// [on_result], or check the handle yourself, e.g. from a recurring task.
+pub struct ModalResult<T> {
+    inner: Arc<Mutex<ModalResultState<T>>>,
+}
+impl<T> Clone for ModalResult<T> {
+    fn clone(&self) -> Self {
+        ModalResult {
+            inner: self.inner.clone(),
+        }
+    }
+}
+impl<T> Default for ModalResult<T> {
+    fn default() -> Self {
+        ModalResult {
+            inner: Arc::new(Mutex::new(ModalResultState {
+                done: false,
+                value: None,
+                callback: None,
+            })),
+        }
+    }
+}
+impl<T: Send + 'static> ModalResult<T> {
+    /// Bind a callback that is called when the modal is closed. Replaces any previously bound
+    /// callback. Has no effect if the modal was already closed; bind it right after opening the
+    /// modal.
+    pub fn on_result(&self, callback: ModalResultFunction<T>) {
+        let mut state = self.inner.lock().unwrap();
+        if !state.done {
+            state.callback = Some(callback);
+        }
+    }
+
+    /// Returns a bool representing whether the modal was closed.
+    pub fn is_done(&self) -> bool {
+        self.inner.lock().unwrap().done
+    }
+
+    /// Take the result set by the modal, if any. Returns None if the modal is still open, was
+    /// dismissed without a result, or the result was already taken or passed to the [on_result]
+    /// callback.
+    pub fn take(&self) -> Option<T> {
+        self.inner.lock().unwrap().value.take()
+    }
+
+    /// Create the resolver stored by the scheduler until the modal is closed.
+    pub(crate) fn get_resolver(&self) -> ModalResultResolver {
+        let handle = self.clone();
+        Box::new(move |context: Context, value: Option<Box<dyn Any + Send>>| {
+            let value = value.map(|x| {
+                *x.downcast::<T>().unwrap_or_else(|_| {
+                    panic!(
+                        "Modal result has the wrong type, expected {}",
+                        std::any::type_name::<T>()
+                    )
+                })
+            });
+            let callback = {
+                let mut state = handle.inner.lock().unwrap();
+                state.done = true;
+                state.callback.take()
+            };
+            match callback {
+                Some(callback) => callback(context, value),
+                None => handle.inner.lock().unwrap().value = value,
+            }
+        })
+    }
+}
+
+struct ModalResultState<T> {
+    done: bool,
+    value: Option<T>,
+    callback: Option<ModalResultFunction<T>>,
+}
+
 /// This object is provided to threaded functions callbacks. You can use it to gain access to
 /// the [StateTree] and the [Scheduler].
 /// # Change widget states
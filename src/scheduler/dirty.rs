@@ -0,0 +1,79 @@
+//! # Dirty tracking
+//!
+//! This module implements tracking whether the values of input widgets differ from the values
+//! they had when tracking started, e.g. to enable a "Save" button or to warn about unsaved
+//! changes. Forms are tracked with [SchedulerFrontend.track_dirty] or the "track_dirty" property
+//! of a layout. Every tracked widget, and the form itself, gets a custom bool property named
+//! "<path>/is_dirty" that is true while its' value differs from the initial value.
+use crate::run::definitions::StateTree;
+use crate::scheduler::definitions::Context;
+use crate::scheduler::scheduler::SchedulerFrontend;
+
+/// Get the name of the custom bool property that holds whether a tracked widget or form is dirty.
+pub fn get_dirty_property_name(path: &str) -> String {
+    format!("{}/is_dirty", path)
+}
+
+/// Start tracking a form: remember the current values of its' input widgets and create the
+/// is_dirty properties. Forms that are already tracked are only refreshed.
+pub fn start_dirty_tracking(form: &str, scheduler: &mut SchedulerFrontend, state_tree: &StateTree) {
+    let form = state_tree.get(form).as_generic().get_path().clone();
+    let already_tracked = scheduler.backend.dirty_forms.contains_key(&form);
+    let snapshot = state_tree.snapshot(&[&form]);
+    if !already_tracked {
+        for (path, property, _) in snapshot.values.iter() {
+            let form = form.clone();
+            scheduler.bind_property_callback(
+                &format!("{}/{}", path, property),
+                Box::new(move |context: Context| {
+                    refresh_dirty_form(&form, context.scheduler, context.state_tree);
+                    true
+                }),
+            );
+        }
+    }
+    scheduler.backend.dirty_forms.insert(form.clone(), snapshot);
+    refresh_dirty_form(&form, scheduler, state_tree);
+}
+
+/// Compare the current values of the widgets of a tracked form to their initial values and set
+/// the is_dirty properties of the widgets and the form.
+pub fn refresh_dirty_form(form: &str, scheduler: &mut SchedulerFrontend, state_tree: &StateTree) {
+    let snapshot = match scheduler.backend.dirty_forms.get(form) {
+        Some(i) => i.clone(),
+        None => return,
+    };
+    let mut form_dirty = false;
+    for (path, property, initial) in snapshot.values.iter() {
+        let dirty = state_tree
+            .try_get(path)
+            .map_or(false, |x| &x.obj.as_generic().get_property(property) != initial);
+        set_dirty_property(path, dirty, scheduler);
+        form_dirty |= dirty;
+    }
+    set_dirty_property(form, form_dirty, scheduler);
+}
+
+/// Set the is_dirty property of a widget or form, creating it if it does not exist yet. The
+/// property is only set when its' value changes, so subscribers are not called every time a
+/// value in the form changes.
+fn set_dirty_property(path: &str, dirty: bool, scheduler: &mut SchedulerFrontend) {
+    let name = get_dirty_property_name(path);
+    if !scheduler.backend.properties.contains_key(&name) {
+        scheduler.new_bool_property(&name, false);
+    }
+    let property = scheduler.backend.properties.get_mut(&name).unwrap().as_bool_mut();
+    if property.value != dirty {
+        property.set(dirty);
+    }
+}
+
+/// Start tracking forms that had the "track_dirty" property set in an .ez file. They are tracked
+/// once the UI is loaded, so the values loaded from the .ez file are the initial values.
+pub fn update_dirty_tracking(scheduler: &mut SchedulerFrontend, state_tree: &StateTree) {
+    for form in std::mem::take(&mut scheduler.backend.dirty_forms_to_track) {
+        if state_tree.contains(&form) {
+            start_dirty_tracking(&form, scheduler, state_tree);
+        }
+    }
+}
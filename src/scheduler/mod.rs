@@ -6,3 +6,32 @@ pub mod scheduler_funcs;
 
 /// This module implements definitons for the [Scheduler] struct
 pub mod definitions;
+
+/// This module implements data sources that load CSV or JSON files for table-like widgets
+pub mod data_source;
+
+/// This module implements saving and loading persisted properties to and from settings files
+pub mod persistence;
+
+/// A module implementing multi-step dialogs
+pub mod wizard;
+
+/// This module implements commands that can be undone and redone
+pub mod undo;
+
+/// This module implements tracking whether input widgets differ from their initial values
+pub mod dirty;
+
+/// This module implements named actions that buttons and keys can be bound to
+pub mod actions;
+
+/// This module implements CPU, memory and network usage properties
+#[cfg(feature = "metrics")]
+pub mod system_metrics;
+
+/// This module implements running futures on a tokio runtime
+#[cfg(feature = "tokio")]
+pub mod async_tasks;
+
+/// This module implements the clocks the [Scheduler] uses to run scheduled tasks
+pub mod clock;
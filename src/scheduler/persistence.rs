@@ -0,0 +1,159 @@
+//! # Persistence
+//!
+//! This module implements loading and saving persisted properties (see
+//! [SchedulerFrontend.persist_property]) from and to a settings file. Files ending in ".toml" are
+//! written as flat TOML with one quoted key per property; all other files are written as a JSON
+//! object.
+use std::collections::HashMap;
+use std::fs;
+use std::time::Duration;
+
+use serde_json::{Map, Number, Value};
+
+use crate::property::ez_properties::EzProperties;
+use crate::property::ez_values::EzValues;
+use crate::scheduler::scheduler::SchedulerFrontend;
+
+/// Time to wait after the last change of a persisted property before the settings file is written,
+/// so that e.g. dragging a slider does not write the file on every frame.
+pub const PERSIST_DELAY: Duration = Duration::from_millis(500);
+
+/// Name of the task that writes a settings file.
+pub fn get_persist_task_name(path: &str) -> String {
+    format!("persist_property/{}", path)
+}
+
+/// Check whether a settings file should be written as TOML rather than JSON.
+fn is_toml(path: &str) -> bool {
+    path.to_lowercase().ends_with(".toml")
+}
+
+/// Load all values of a settings file. A missing or unreadable file results in no values.
+pub fn load_settings(path: &str) -> HashMap<String, Value> {
+    let text = match fs::read_to_string(path) {
+        Ok(i) => i,
+        Err(_) => return HashMap::new(),
+    };
+    if is_toml(path) {
+        parse_toml(&text)
+    } else {
+        match serde_json::from_str::<Value>(&text) {
+            Ok(Value::Object(map)) => map.into_iter().collect(),
+            _ => HashMap::new(),
+        }
+    }
+}
+
+/// Parse a flat TOML file of "key = value" lines. Only the scalar values that can be persisted
+/// are supported; tables, arrays and lines that cannot be parsed are skipped.
+fn parse_toml(text: &str) -> HashMap<String, Value> {
+    let mut values = HashMap::new();
+    for line in text.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with('[') {
+            continue;
+        }
+        let (key, value) = if let Some(quoted) = line.strip_prefix('"') {
+            let end = match quoted.find('"') {
+                Some(i) => i,
+                None => continue,
+            };
+            let value = match quoted[end + 1..].trim_start().strip_prefix('=') {
+                Some(i) => i.trim(),
+                None => continue,
+            };
+            (quoted[..end].to_string(), value)
+        } else {
+            match line.split_once('=') {
+                Some((key, value)) => (key.trim().to_string(), value.trim()),
+                None => continue,
+            }
+        };
+        if let Ok(value) = serde_json::from_str::<Value>(value) {
+            values.insert(key, value);
+        }
+    }
+    values
+}
+
+/// Convert the value of a property to a settings value. Panics if the property type cannot be
+/// persisted.
+pub fn property_to_value(name: &str, property: &EzProperties) -> Value {
+    match property.get_generic_value() {
+        EzValues::Usize(i) => Value::Number(Number::from(i)),
+        EzValues::F64(i) => Number::from_f64(i).map(Value::Number).unwrap_or(Value::Null),
+        EzValues::Bool(i) => Value::Bool(i),
+        EzValues::String(i) => Value::String(i),
+        _ => panic!(
+            "Cannot persist property \"{}\": only usize, f64, bool and String properties can be \
+            persisted",
+            name
+        ),
+    }
+}
+
+/// Set a property to a value loaded from a settings file. Values of the wrong type are ignored so
+/// that an edited or outdated settings file does not crash the app.
+pub fn set_property_from_value(property: &mut EzProperties, value: &Value) {
+    match property {
+        EzProperties::Usize(i) => {
+            if let Some(value) = value.as_u64() {
+                i.set(value as usize);
+            }
+        }
+        EzProperties::F64(i) => {
+            if let Some(value) = value.as_f64() {
+                i.set(value);
+            }
+        }
+        EzProperties::Bool(i) => {
+            if let Some(value) = value.as_bool() {
+                i.set(value);
+            }
+        }
+        EzProperties::String(i) => {
+            if let Some(value) = value.as_str() {
+                i.set(value.to_string());
+            }
+        }
+        _ => (),
+    }
+}
+
+/// Write the current values of all properties persisted to a settings file. Values in the file
+/// that do not belong to a persisted property are kept. Errors writing the file are ignored; the
+/// values will be written again on the next change.
+pub fn save_settings(path: &str, scheduler: &SchedulerFrontend) {
+    let names = match scheduler.backend.persisted_properties.get(path) {
+        Some(i) => i,
+        None => return,
+    };
+    let mut values = load_settings(path);
+    for name in names.iter() {
+        values.insert(
+            name.clone(),
+            property_to_value(name, scheduler.get_property(name)),
+        );
+    }
+    let mut keys: Vec<&String> = values.keys().collect();
+    keys.sort();
+
+    let text = if is_toml(path) {
+        let mut text = String::new();
+        for key in keys {
+            text.push_str(&format!(
+                "{} = {}\n",
+                Value::String(key.clone()),
+                values[key]
+            ));
+        }
+        text
+    } else {
+        let mut map = Map::new();
+        for key in keys {
+            map.insert(key.clone(), values[key].clone());
+        }
+        serde_json::to_string_pretty(&Value::Object(map)).unwrap_or_default()
+    };
+    let _ = fs::write(path, text);
+}
@@ -1,30 +1,63 @@
 //! # Scheduler
 //!
 //! A module implementing the Scheduler struct.
-use crossterm::event::{KeyCode, KeyModifiers};
-use std::collections::HashMap;
+use crossterm::event::{Event, KeyCode, KeyModifiers};
+use std::any::Any;
+use std::collections::{HashMap, VecDeque};
 use std::mem::swap;
-use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::mpsc::{channel, Receiver, Sender, TryRecvError};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
 use std::thread::JoinHandle;
 use std::time::{Duration, Instant};
 
 use crossterm::style::Color;
 
-use crate::parser::ez_definition::Templates;
+use crate::parser::ez_definition::{
+    check_template_inheritance, EzWidgetDefinition, TemplateInstance, Templates,
+};
+use crate::parser::parse_lang::parse_level;
+use crate::parser::parse_properties::parse_dock_position_property;
 use crate::property::ez_properties::EzProperties;
-use crate::property::ez_property::EzProperty;
+use crate::property::ez_property::{EzProperty, ThreadPropertySenders};
 use crate::property::ez_values::EzValues;
-use crate::run::definitions::{Coordinates, StateTree};
-use crate::run::run::{open_and_register_modal, stop};
+use crate::run::definitions::{CompactMode, Coordinates, Size, StateSnapshot, StateTree};
+use crate::run::run::open_and_register_modal;
+use crate::run::input_source::{ChannelInputSource, EventInjector, InputSource};
+use crate::run::terminal::TerminalCursor;
+use crate::run::tuning::LayoutTuning;
+use crate::scheduler::clock::Clock;
+use crate::scheduler::data_source::DataSource;
+use crate::scheduler::actions::{get_action_property_name, run_action, Action};
+use crate::scheduler::dirty::{get_dirty_property_name, start_dirty_tracking};
+use crate::scheduler::persistence::{
+    get_persist_task_name, load_settings, property_to_value, save_settings,
+    set_property_from_value, PERSIST_DELAY,
+};
+#[cfg(feature = "control")]
+use crate::run::control::{serve_tcp, ControlRequest};
+#[cfg(all(feature = "control", unix))]
+use crate::run::control::serve_unix;
+#[cfg(feature = "tokio")]
+use crate::scheduler::async_tasks::AsyncTask;
+use crate::scheduler::undo::{update_undo_properties, UndoAction, UndoHistory, UndoableCommand};
+use crate::scheduler::wizard::{show_wizard_step, wizard_back, wizard_next, Wizard, WizardPaths};
+use crate::scheduler::scheduler_funcs::{
+    cancel_widget_tasks, find_screen_layout, get_checked_boxes, get_new_states, get_screen_parent,
+    get_file_identity, get_sibling_z_indices, read_file_from,
+};
 use crate::scheduler::definitions::{
-    EzPropertyUpdater, EzThread, GenericFunction, GenericRecurringTask, GenericTask,
-    KeyboardCallbackFunction,
+    Context, DataSourceCallback, DragPayload, EventControl, EzPropertyUpdater, EzThread, GenericFunction,
+    ExitTeardown, GenericRecurringTask, GenericTask, InputFilterFunction, KeyAcceleration, KeyRepeat,
+    KeyboardCallbackFunction, ComputedProperty, ComputedPropertyFunction, ModalResult, ModalResultResolver, PasteCallbackFunction, RenderCallback,
+    StartupProfile, ThreadedContext,
 };
 use crate::states::definitions::{
-    create_keymap_modifiers, HorizontalAlignment, HorizontalPosHint, LayoutMode, LayoutOrientation,
-    VerticalAlignment, VerticalPosHint,
+    create_keymap_modifiers, DockPosition, HorizontalAlignment, HorizontalPosHint, LayoutMode,
+    LayoutOrientation, SelectionStyle, Theme, VerticalAlignment, VerticalPosHint,
 };
-use crate::states::ez_state::EzState;
+use crate::states::ez_state::{EzState, GenericState};
+use crate::states::layout_state::LayoutState;
 use crate::widgets::ez_object::EzObjects;
 use crate::{CallbackConfig, CustomData, EzPropertiesMap};
 
@@ -39,11 +72,14 @@ pub struct SchedulerFrontend {
     syncing: usize,
 
     /// Whether this is a synced frontend (i.e. running in a thread)
-    synced: bool,
+    pub(crate) synced: bool,
 
     sync_thread_sender: Option<Sender<bool>>,
     sync_thread_receiver: Option<Receiver<bool>>,
 
+    thread_property_sender: Option<Sender<(String, EzValues)>>,
+    thread_property_receiver: Option<Receiver<(String, EzValues)>>,
+
     schedule_once_sender: Option<Sender<(String, GenericTask, Duration)>>,
     schedule_once_receiver: Option<Receiver<(String, GenericTask, Duration)>>,
 
@@ -71,8 +107,8 @@ pub struct SchedulerFrontend {
     update_callback_config_sender: Option<Sender<(String, CallbackConfig)>>,
     update_callback_config_receiver: Option<Receiver<(String, CallbackConfig)>>,
 
-    create_widget_sender: Option<Sender<(EzObjects, StateTree)>>,
-    create_widget_receiver: Option<Receiver<(EzObjects, StateTree)>>,
+    create_widget_sender: Option<Sender<(EzObjects, StateTree, Option<usize>)>>,
+    create_widget_receiver: Option<Receiver<(EzObjects, StateTree, Option<usize>)>>,
     new_properties_sender: Option<Sender<HashMap<String, EzProperties>>>,
     new_properties_receiver: Option<Receiver<HashMap<String, EzProperties>>>,
     new_receivers_sender: Option<Sender<HashMap<String, Receiver<EzValues>>>>,
@@ -81,6 +117,15 @@ pub struct SchedulerFrontend {
     remove_widget_sender: Option<Sender<String>>,
     remove_widget_receiver: Option<Receiver<String>>,
 
+    clone_widget_sender: Option<Sender<(String, String, String)>>,
+    clone_widget_receiver: Option<Receiver<(String, String, String)>>,
+
+    move_widget_sender: Option<Sender<(String, String, Option<usize>)>>,
+    move_widget_receiver: Option<Receiver<(String, String, Option<usize>)>>,
+
+    swap_children_sender: Option<Sender<(String, String, String)>>,
+    swap_children_receiver: Option<Receiver<(String, String, String)>>,
+
     new_usize_property_sender: Option<Sender<(String, usize)>>,
     new_usize_property_receiver: Option<Receiver<(String, usize)>>,
 
@@ -102,6 +147,9 @@ pub struct SchedulerFrontend {
     new_layout_orientation_property_sender: Option<Sender<(String, LayoutOrientation)>>,
     new_layout_orientation_property_receiver: Option<Receiver<(String, LayoutOrientation)>>,
 
+    new_dock_position_property_sender: Option<Sender<(String, DockPosition)>>,
+    new_dock_position_property_receiver: Option<Receiver<(String, DockPosition)>>,
+
     new_horizontal_alignment_property_sender: Option<Sender<(String, HorizontalAlignment)>>,
     new_horizontal_alignment_property_receiver: Option<Receiver<(String, HorizontalAlignment)>>,
 
@@ -123,6 +171,9 @@ pub struct SchedulerFrontend {
     update_widget_sender: Option<Sender<String>>,
     update_widget_receiver: Option<Receiver<String>>,
 
+    update_widgets_sender: Option<Sender<Vec<String>>>,
+    update_widgets_receiver: Option<Receiver<Vec<String>>>,
+
     force_redraw_sender: Option<Sender<bool>>,
     force_redraw_receiver: Option<Receiver<bool>>,
 
@@ -156,6 +207,10 @@ pub struct SchedulerFrontend {
     ask_sync_properties_sender: Option<Sender<bool>>,
     sync_properties_receiver: Option<Receiver<EzPropertiesMap>>,
     sync_properties_main: Vec<(Receiver<bool>, Sender<EzPropertiesMap>)>,
+
+    /// Stop flags of looping background threads (see [poll_url] and [watch_file]) by name.
+    /// Shared with synced frontends so a thread can start and cancel them as well.
+    background_tasks: Arc<Mutex<HashMap<String, Arc<AtomicBool>>>>,
 }
 
 impl SchedulerFrontend {
@@ -207,6 +262,78 @@ impl SchedulerFrontend {
         self.backend.keyboard_cooldown = cooldown
     }
 
+    /// Set how much time each frame may spend on redrawing background updates. Widgets updated
+    /// while handling input, and the selected widget along with its' parents and children, are
+    /// always redrawn first. Other widgets (e.g. updated by tasks or threads) are redrawn after
+    /// them until the budget is used up or new input arrives; the rest is deferred to the next
+    /// frames, so keystrokes don't lag when many widgets update at once. At least one background
+    /// update is redrawn each frame. Defaults to 8 milliseconds.
+    ///
+    /// # Parameters:
+    ///
+    /// - Budget: Duration
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use ez_term::*;
+    ///
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// scheduler.set_background_update_budget(Duration::from_millis(4));
+    ///
+    /// run(root_widget, state_tree, scheduler, custom_data);
+    /// ```
+    pub fn set_background_update_budget(&mut self, budget: Duration) {
+        self.backend.background_update_budget = budget
+    }
+
+    /// Get how much time each frame may spend on redrawing background updates, see
+    /// [set_background_update_budget].
+    pub fn get_background_update_budget(&self) -> Duration {
+        self.backend.background_update_budget
+    }
+
+    /// Set how scrolling and slider adjustment speed up while a key is held down. By default the
+    /// amount of extra steps per second grows linearly with the time the key is held, up to 10
+    /// steps per key event. Use [AccelerationCurve::None] to move a single step per key event.
+    ///
+    /// # Parameters:
+    ///
+    /// - Acceleration: [KeyAcceleration]
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use ez_term::*;
+    ///
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// scheduler.set_key_acceleration(KeyAcceleration {
+    ///     curve: AccelerationCurve::Quadratic,
+    ///     max_steps: 25,
+    ///     ..Default::default()
+    /// });
+    ///
+    /// run(root_widget, state_tree, scheduler, custom_data);
+    /// ```
+    pub fn set_key_acceleration(&mut self, acceleration: KeyAcceleration) {
+        self.backend.key_acceleration = acceleration;
+    }
+
+    /// Get the settings for speeding up held keys. See [set_key_acceleration].
+    pub fn get_key_acceleration(&self) -> KeyAcceleration {
+        self.backend.key_acceleration
+    }
+
+    /// Get the amount of steps the key event that is currently being handled should move, which
+    /// grows while the key is held down. Scrolling layouts and sliders use this; custom keyboard
+    /// callbacks can use it as well to speed up with held keys. See [set_key_acceleration].
+    pub fn get_key_repeat_steps(&self) -> usize {
+        self.backend.key_repeat.get_steps()
+    }
+
     /// Method that allows you to schedule a closure or function for single execution after a delay
     /// (which can be 0).
     /// Only intended for code that returns immediately (like manipulating the UI); to run blocking
@@ -239,7 +366,7 @@ impl SchedulerFrontend {
     /// ```
     pub fn schedule_once(&mut self, name: &str, func: GenericTask, after: Duration) {
         if !self.synced {
-            let task = Task::new(name.to_string(), func, after);
+            let task = Task::new(name.to_string(), func, after, self.now());
             self.backend.tasks.push(task);
         } else {
             self.sync_thread_sender
@@ -366,6 +493,111 @@ impl SchedulerFrontend {
         }
     }
 
+    /// Register a task that widgets can refresh themselves with, by name. In an .ez file a widget
+    /// runs a registered task on an interval with "refresh_every: <interval> -> <task name>"; the
+    /// interval is a number followed by ms, s, m or h. The task receives a [Context] of the widget
+    /// (see "context.widget_path"), so one task can refresh multiple widgets. Returning false from
+    /// the task stops refreshing that widget. Refreshing also stops when the widget is removed.
+    ///
+    /// # Parameters:
+    ///
+    /// - Task name: &str
+    /// - Function: Box<FnMut(Context) -> bool>
+    ///
+    /// # Example:
+    ///
+    /// We'll show the current time in a label, updated every second. First the code:
+    /// ```
+    /// use std::time::SystemTime;
+    /// use ez_term::*;
+    ///
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// let show_time = |context: Context| {
+    ///     let seconds = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap();
+    ///     let state = context.state_tree.get_mut(&context.widget_path).as_label_mut();
+    ///     state.set_text(format!("{}", seconds.as_secs()));
+    ///     state.update(context.scheduler);
+    ///     true
+    /// };
+    /// scheduler.register_refresh_task("show_time", Box::new(show_time));
+    ///
+    /// run(root_widget, state_tree, scheduler, custom_data);
+    /// ```
+    /// Now the .ez file:
+    /// ```
+    /// - Layout:
+    ///     - Label:
+    ///         id: clock
+    ///         refresh_every: 1s -> show_time
+    /// ```
+    pub fn register_refresh_task(&mut self, name: &str, func: GenericRecurringTask) {
+        self.backend.refresh_tasks.insert(name.to_string(), func);
+    }
+
+    /// Run a task registered with [register_refresh_task] for a widget on an interval. This is
+    /// what "refresh_every" in an .ez file does; use it for widgets created from code. The task
+    /// does not have to be registered yet, but it must be before the interval first passes.
+    ///
+    /// # Parameters:
+    ///
+    /// - Widget path: &str
+    /// - Task name: &str
+    /// - Interval: std::Duration
+    pub fn schedule_refresh_task(&mut self, widget_path: &str, name: &str, interval: Duration) {
+        let widget_path = widget_path.to_string();
+        let name = name.to_string();
+        // Named after the widget, so the task is cancelled when the widget is removed
+        let task_name = format!("{}/refresh_every", widget_path);
+        let refresh = move |context: Context| {
+            let mut func = context
+                .scheduler
+                .backend
+                .refresh_tasks
+                .remove(&name)
+                .unwrap_or_else(|| {
+                    panic!(
+                        "Widget \"{}\" refreshes with task \"{}\", but no task with that name \
+                        was registered. Use scheduler.register_refresh_task for this.",
+                        widget_path, name
+                    )
+                });
+            let keep_refreshing = func(Context::new(
+                widget_path.clone(),
+                context.state_tree,
+                context.scheduler,
+                context.custom_data,
+            ));
+            context
+                .scheduler
+                .backend
+                .refresh_tasks
+                .entry(name.clone())
+                .or_insert(func);
+            keep_refreshing
+        };
+        self.schedule_recurring(&task_name, Box::new(refresh), interval);
+    }
+
+    /// Replace the clock used to decide when scheduled tasks run. Mostly useful for tests: with a
+    /// [MockClock] time can be advanced manually, so scheduled tasks can be triggered without
+    /// sleeping. See [MockClock] for an example. Must be called from the main thread.
+    ///
+    /// # Parameters:
+    ///
+    /// - Clock: Box<dyn [Clock]>
+    pub fn set_clock(&mut self, clock: Box<dyn Clock>) {
+        self.backend.clock = Some(clock);
+    }
+
+    /// Get the current time according to the clock of the scheduler. See [set_clock].
+    pub fn now(&self) -> Instant {
+        match &self.backend.clock {
+            Some(clock) => clock.now(),
+            None => Instant::now(),
+        }
+    }
+
     /// Cancel a recurring task. This function is always safe to call, if there's no task to cancel it
     /// will not panic. An alternative way to cancel a recurring task is to return false from the
     /// scheduled function (which is recommended for most use cases. This function lets you cancel a
@@ -400,6 +632,9 @@ impl SchedulerFrontend {
     /// run(root_widget, state_tree, scheduler, custom_data);
     /// ```
     pub fn cancel_recurring_task(&mut self, name: &str) {
+        if let Some(stop) = self.background_tasks.lock().unwrap().remove(name) {
+            stop.store(true, Ordering::Relaxed);
+        }
         if !self.synced {
             let mut to_cancel = None;
             for (i, task) in self.backend.recurring_tasks.iter().enumerate() {
@@ -424,6 +659,272 @@ impl SchedulerFrontend {
         }
     }
 
+    /// Bind a CSV or JSON [DataSource] to a callback. The file is loaded immediately and then
+    /// checked for changes on an interval; whenever it changed it is reloaded and the callback is
+    /// called with the new data, so a table-like widget can be refreshed from it. Files are
+    /// loaded in a background thread, so large files do not block the UI; the callback is called
+    /// on the main thread. If the file cannot be loaded (e.g. because it is being written) the
+    /// previous data is kept and loading is retried on the next interval. The binding is stopped
+    /// when the app exits, or with [cancel_recurring_task] and the passed name.
+    ///
+    /// # Parameters:
+    ///
+    /// - Name: &str
+    /// - Data source: [DataSource]
+    /// - Interval: std::Duration
+    /// - Callback: Box<FnMut(Context, &DataTable)>
+    ///
+    /// # Example:
+    ///
+    /// We'll show the amount of rows in a CSV file in a label:
+    /// ```
+    /// use std::time::Duration;
+    /// use ez_term::*;
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// let source = DataSource::from_file("data.csv").unwrap();
+    /// let on_change = |context: Context, table: &DataTable| {
+    ///     let state = context.state_tree.get_mut("my_label").as_label_mut();
+    ///     state.set_text(format!("{} rows", table.rows.len()));
+    ///     state.update(context.scheduler);
+    /// };
+    /// scheduler.bind_data_source("my_data", source, Duration::from_secs(1), Box::new(on_change));
+    ///
+    /// run(root_widget, state_tree, scheduler, custom_data);
+    /// ```
+    pub fn bind_data_source(
+        &mut self,
+        name: &str,
+        mut source: DataSource,
+        interval: Duration,
+        mut on_change: DataSourceCallback,
+    ) {
+        let (sender, receiver) = channel();
+        let stop = self.register_background_task(name);
+        let load = move |_context: ThreadedContext| loop {
+            if source.has_changed() {
+                if let Ok(table) = source.load() {
+                    if sender.send(table.clone()).is_err() {
+                        return;
+                    }
+                }
+            }
+            // Sleep in steps so a stopped binding does not linger for a whole interval
+            let started = Instant::now();
+            while started.elapsed() < interval {
+                if stop.load(Ordering::Relaxed) {
+                    return;
+                }
+                std::thread::sleep(interval.min(Duration::from_millis(250)));
+            }
+        };
+        self.schedule_threaded(Box::new(load), None);
+
+        // Hand loaded tables to the callback on the main thread; only the latest one matters
+        let task = move |context: Context| {
+            let mut latest = None;
+            loop {
+                match receiver.try_recv() {
+                    Ok(table) => latest = Some(table),
+                    Err(TryRecvError::Empty) => break,
+                    Err(TryRecvError::Disconnected) => {
+                        if let Some(table) = latest {
+                            on_change(context, &table);
+                        }
+                        return false;
+                    }
+                }
+            }
+            if let Some(table) = latest {
+                on_change(context, &table);
+            }
+            true
+        };
+        self.schedule_recurring(name, Box::new(task), interval.min(Duration::from_millis(100)));
+    }
+
+    /// Periodically fetch a URL from a background thread and write the response body into a new
+    /// string property, so widgets can be bound to remote data. A second string property named
+    /// "{name}_status" is created as well; it contains "ok" after a successful request, or
+    /// "error: " followed by the reason after a failed one. On failure the last successfully
+    /// fetched body is kept. The URL is polled until the app exits, or until
+    /// [cancel_recurring_task] is called with the name. Only available with the "http" feature;
+    /// plain HTTP only.
+    ///
+    /// # Parameters:
+    ///
+    /// - Name: &str
+    /// - URL: &str
+    /// - Interval: std::Duration
+    ///
+    /// # Example:
+    ///
+    /// We'll show the response of a local API in a label, and the status of the last request in
+    /// another. First the code:
+    /// ```
+    /// use std::time::Duration;
+    /// use ez_term::*;
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// scheduler.poll_url("weather", "http://localhost:8080/weather", Duration::from_secs(30));
+    ///
+    /// run(root_widget, state_tree, scheduler, custom_data);
+    /// ```
+    /// Now the .ez file:
+    /// ```
+    /// - Layout:
+    ///     - Label:
+    ///         text: properties.weather
+    ///     - Label:
+    ///         text: properties.weather_status
+    /// ```
+    #[cfg(feature = "http")]
+    pub fn poll_url(
+        &mut self,
+        name: &str,
+        url: &str,
+        interval: Duration,
+    ) -> (EzProperty<String>, EzProperty<String>) {
+        let mut body = self.new_string_property(name, String::new());
+        let mut status =
+            self.new_string_property(format!("{}_status", name).as_str(), String::new());
+        let (body_ref, status_ref) = (body.clone(), status.clone());
+        let url = url.to_string();
+        let stop = self.register_background_task(name);
+        let poll = move |_context: ThreadedContext| loop {
+            let response = ureq::get(&url)
+                .timeout(interval)
+                .call()
+                .map_err(|e| e.to_string())
+                .and_then(|x| x.into_string().map_err(|e| e.to_string()));
+            if stop.load(Ordering::Relaxed) {
+                return;
+            }
+            match response {
+                Ok(text) => {
+                    body.set(text);
+                    status.set("ok".to_string());
+                }
+                Err(error) => {
+                    status.set(format!("error: {}", error));
+                }
+            }
+            // Sleep in steps so a stopped poll does not linger for a whole interval
+            let started = Instant::now();
+            while started.elapsed() < interval {
+                if stop.load(Ordering::Relaxed) {
+                    return;
+                }
+                std::thread::sleep(interval.min(Duration::from_millis(250)));
+            }
+        };
+        self.schedule_threaded(Box::new(poll), None);
+        (body_ref, status_ref)
+    }
+
+    /// Follow a file from a background thread (like 'tail -f') and append new lines to a new
+    /// string property. Lines are separated by newlines, so the property can be bound to e.g. the
+    /// text of a label to create a log view. Only the last 'max_lines' lines are kept. If the file
+    /// is truncated or replaced (e.g. by log rotation) it is read again from the start. If the
+    /// file does not exist yet it is waited for. The file is watched until the app exits, or until
+    /// [cancel_recurring_task] is called with the property name.
+    ///
+    /// # Parameters:
+    ///
+    /// - File path: &str
+    /// - Property name: &str
+    /// - Max lines: usize
+    ///
+    /// # Example:
+    ///
+    /// We'll show the last 100 lines of a log file in a label. First the code:
+    /// ```
+    /// use ez_term::*;
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// scheduler.watch_file("/var/log/my_app.log", "my_log", 100);
+    ///
+    /// run(root_widget, state_tree, scheduler, custom_data);
+    /// ```
+    /// Now the .ez file:
+    /// ```
+    /// - Layout:
+    ///     - Label:
+    ///         auto_scale: true, true
+    ///         text: properties.my_log
+    /// ```
+    pub fn watch_file(&mut self, path: &str, name: &str, max_lines: usize) -> EzProperty<String> {
+        let mut property = self.new_string_property(name, String::new());
+        let property_ref = property.clone();
+        let path = path.to_string();
+        let stop = self.register_background_task(name);
+        let watch = move |_context: ThreadedContext| {
+            let mut lines: Vec<String> = Vec::new();
+            let mut position = 0;
+            let mut identity = None;
+            loop {
+                std::thread::sleep(Duration::from_millis(250));
+                if stop.load(Ordering::Relaxed) {
+                    return;
+                }
+                let metadata = match std::fs::metadata(&path) {
+                    Ok(i) => i,
+                    Err(_) => continue,
+                };
+                let new_identity = get_file_identity(&metadata);
+                if new_identity != identity || metadata.len() < position {
+                    // File was truncated or replaced; start over
+                    identity = new_identity;
+                    position = 0;
+                }
+                if metadata.len() == position {
+                    continue;
+                }
+                let new_text = match read_file_from(&path, position) {
+                    Ok(i) => i,
+                    Err(_) => continue,
+                };
+                // Only consume complete lines; a partially written line is read again next time
+                let end = match new_text.iter().rposition(|x| *x == b'\n') {
+                    Some(i) => i + 1,
+                    None => continue,
+                };
+                position += end as u64;
+                for line in String::from_utf8_lossy(&new_text[..end]).lines() {
+                    lines.push(line.to_string());
+                }
+                if lines.len() > max_lines {
+                    lines.drain(0..lines.len() - max_lines);
+                }
+                if stop.load(Ordering::Relaxed) {
+                    return;
+                }
+                property.set(lines.join("\n"));
+            }
+        };
+        self.schedule_threaded(Box::new(watch), None);
+        property_ref
+    }
+
+    /// Create the stop flag of a looping background thread. A thread that was already running
+    /// under the same name is stopped.
+    fn register_background_task(&mut self, name: &str) -> Arc<AtomicBool> {
+        let stop = Arc::new(AtomicBool::new(false));
+        let mut background_tasks = self.background_tasks.lock().unwrap();
+        if let Some(old) = background_tasks.insert(name.to_string(), stop.clone()) {
+            old.store(true, Ordering::Relaxed);
+        }
+        stop
+    }
+
+    /// Stop all looping background threads. Called when the app exits, as the properties they
+    /// write to can no longer be set after that.
+    pub fn _stop_background_tasks(&mut self) {
+        for (_, stop) in self.background_tasks.lock().unwrap().drain() {
+            stop.store(true, Ordering::Relaxed);
+        }
+    }
+
     /// Method that allows you to schedule a closure or function for threaded execution. This allows
     /// you to run code that does not return immediately (like your app code). You can use the
     /// state tree from the threaded function to manipulate the UI, but the scheduler will not be
@@ -479,10 +980,67 @@ impl SchedulerFrontend {
         }
     }
 
+    /// Schedule a closure or function for threaded execution like [schedule_threaded], and keep a
+    /// layout busy while it runs: the layout is dimmed with a spinner on top, and its' children
+    /// cannot be interacted with (see [LayoutState.set_busy]). The layout is no longer busy when
+    /// the function is done, right before the on_finish callback is called. The rest of the UI
+    /// can be used as normal in the meantime.
+    ///
+    /// # Parameters:
+    ///
+    /// - Layout: &str: ID or path of the layout to keep busy
+    /// - State tree: &mut [StateTree]
+    /// - Threaded function: Box<dyn FnOnce(HashMap<String, EzProperty>, StateTree) + Send>
+    /// - On_finish callback function: Option<Box<FnMut(Context)>>>
+    ///
+    /// # Example:
+    ///
+    /// We'll load the content of a layout in the background:
+    /// ```
+    /// use std::time::Duration;
+    /// use ez_term::*;
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// fn load_results(context: ThreadedContext) {
+    ///     std::thread::sleep(Duration::from_secs(3));
+    /// }
+    ///
+    /// state_tree.get_mut("results_layout").as_layout_mut().set_busy_text("Loading...");
+    /// scheduler.schedule_threaded_busy("results_layout", &mut state_tree,
+    ///                                  Box::new(load_results), None);
+    /// run(root_widget, state_tree, scheduler, custom_data);
+    /// ```
+    pub fn schedule_threaded_busy(
+        &mut self,
+        layout: &str,
+        state_tree: &mut StateTree,
+        threaded_func: EzThread,
+        mut on_finish: Option<GenericTask>,
+    ) {
+        let state = state_tree.get_mut(layout).as_layout_mut();
+        state.set_busy(true);
+        state.update(self);
+        let path = state.get_path().clone();
+        let clear_busy = move |context: Context| {
+            if let Some(i) = context.state_tree.try_get_mut(&path) {
+                let state = i.obj.as_layout_mut();
+                state.set_busy(false);
+                state.update(context.scheduler);
+            }
+            if let Some(func) = on_finish.as_mut() {
+                func(context);
+            }
+        };
+        self.schedule_threaded(threaded_func, Some(Box::new(clear_busy)));
+    }
+
     /// Method that allows you to open a modal (e.g. a popup). To open a modal you need to define a
     /// Layout template in an .ez file. You can then spawn an instance of the template as a modal using
     /// this method. The ID of the layout spawned as a modal will be 'modal', its full path will be
-    /// '/root/modal'.
+    /// '/root/modal'. If a modal is already open the new modal opens on top of it (e.g. a
+    /// confirmation popup on top of a settings popup); it will have ID 'modal_2' (then 'modal_3',
+    /// etc.). Only the modal on top receives input. The selected widget is deselected, and selected
+    /// again when the new modal is dismissed.
     ///
     /// # Parameters:
     ///
@@ -546,8 +1104,9 @@ impl SchedulerFrontend {
         }
     }
 
-    /// Dismiss the open modal. Can always be called safely even if one no longer exists (though this
-    /// does trigger a screen redraw so try to avoid that).
+    /// Dismiss the modal on top, showing the modal below it if any. The widget that was selected
+    /// when the dismissed modal was opened is selected again. Can always be called safely even if
+    /// no modal is open (though this does trigger a screen redraw so try to avoid that).
     /// For a tutorial on modals see: [Managing popups](#scheduler_modals)
     ///
     /// # Parameters:
@@ -567,17 +1126,26 @@ impl SchedulerFrontend {
     /// ```
     pub fn dismiss_modal(&mut self, state_tree: &mut StateTree) {
         if !self.synced {
-            state_tree.as_layout_mut().dismiss_modal(self);
+            if !state_tree.as_layout().has_modal() {
+                state_tree.as_layout_mut().dismiss_modal(self);
+                return;
+            }
+            let modal_path = state_tree.as_layout().get_modal().as_ez_object().get_path();
+            let selection = state_tree.as_layout_mut().dismiss_modal(self);
 
             let mut removed_paths = Vec::new();
-            let removed = state_tree.remove_node("/modal".to_string());
+            let removed = state_tree.remove_node(modal_path);
             for state in removed.get_all() {
                 removed_paths.push(state.as_generic().get_path());
                 state.as_generic().clean_up_properties(self);
+                cancel_widget_tasks(self, state.as_generic().get_path());
             }
             self.backend
                 .widgets_to_update
                 .retain(|x| !removed_paths.contains(&x));
+            if !selection.is_empty() && state_tree.contains(&selection) {
+                self.set_selected_widget(&selection, None);
+            }
         } else {
             self.sync_thread_sender
                 .as_ref()
@@ -592,46 +1160,168 @@ impl SchedulerFrontend {
         }
     }
 
-    /// Replace the entire CallbackConfig of a widget on the next frame. You can pass an empty
-    /// CallbackConfig to remove all callbacks for a widget.
+    /// Open a popup from a template, like [open_modal], and get a handle to a typed result set by
+    /// the modal. The modal sets the result with [set_modal_result], e.g. from the on_press
+    /// callback of an "OK" button. The handle is resolved when the modal is closed, with None if
+    /// the modal was dismissed without a result. The popup can be opened on top of other modals,
+    /// including other popups with a result (e.g. a confirm dialog from a wizard); each handle is
+    /// resolved when its' own popup closes. Must be called from the main thread.
+    /// For a tutorial on modals see: [Managing popups](#scheduler_modals)
     ///
     /// # Parameters:
     ///
-    /// - Widget ID or path: &str
-    /// - New CallbackConfig: CallbackConfig
+    /// - Template name: &str
+    /// - State tree: &mut StateTree
     ///
     /// # Example:
     ///
-    /// We'll remove any existing callbacks by overwriting with an empty CallbackConfig for a button
-    /// with id "my_button".
+    /// We'll ask the user to confirm and print the answer:
     /// ```
     /// use ez_term::*;
     ///
     /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
     ///
-    /// let callback_config = CallbackConfig::default();
-    /// scheduler.overwrite_callback_config("my_button", callback_config);
+    /// let result = scheduler.open_modal_with_result::<bool>("ConfirmPopup", &mut state_tree);
+    /// result.on_result(Box::new(|context: Context, confirmed: Option<bool>| {
+    ///     println!("Confirmed: {}", confirmed.unwrap_or(false));
+    /// }));
+    ///
+    /// fn confirm(mut context: Context) -> bool {
+    ///     context.scheduler.set_modal_result(true, context.state_tree);
+    ///     true
+    /// }
+    /// scheduler.update_callback_config("confirm_button",
+    ///                                  CallbackConfig::from_on_press(Box::new(confirm)));
+    ///
     /// run(root_widget, state_tree, scheduler, custom_data);
     /// ```
-    pub fn overwrite_callback_config(&mut self, for_widget: &str, callback_config: CallbackConfig) {
-        if !self.synced {
-            self.backend
-                .new_callback_configs
-                .push((for_widget.to_string(), callback_config));
-        } else {
-            self.sync_thread_sender
-                .as_ref()
-                .unwrap()
-                .send(true)
-                .unwrap();
-            self.overwrite_callback_config_sender
-                .as_ref()
-                .unwrap()
-                .send((for_widget.to_string(), callback_config))
-                .unwrap()
-        }
-    }
-
+    pub fn open_modal_with_result<T: Send + 'static>(
+        &mut self,
+        template: &str,
+        state_tree: &mut StateTree,
+    ) -> ModalResult<T> {
+        let result = ModalResult::default();
+        self.open_modal(template, state_tree);
+        let depth = state_tree.as_layout().get_modals().len();
+        // A result left behind by an earlier modal at this depth is not the result of this one
+        self.backend.modal_results.remove(&depth);
+        self.backend
+            .modal_result_resolvers
+            .push((depth, result.get_resolver()));
+        result
+    }
+
+    /// Set the result of the modal opened with [open_modal_with_result] and dismiss the modal on
+    /// top, which should be that modal.
+    /// The type of the result must match the type the modal was opened with. Must be called
+    /// from the main thread.
+    ///
+    /// # Parameters:
+    ///
+    /// - Result: T
+    /// - State tree: &mut StateTree
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use ez_term::*;
+    ///
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// fn choose_file(mut context: Context) -> bool {
+    ///     let path = context.state_tree.get("path_input").as_text_input().get_text();
+    ///     context.scheduler.set_modal_result(path, context.state_tree);
+    ///     true
+    /// }
+    /// scheduler.update_callback_config("choose_button",
+    ///                                  CallbackConfig::from_on_press(Box::new(choose_file)));
+    ///
+    /// run(root_widget, state_tree, scheduler, custom_data);
+    /// ```
+    pub fn set_modal_result<T: Send + 'static>(&mut self, result: T, state_tree: &mut StateTree) {
+        let depth = state_tree.as_layout().get_modals().len();
+        self.backend.modal_results.insert(depth, Box::new(result));
+        self.dismiss_modal(state_tree);
+    }
+
+    /// Render a widget to plain text exactly as it is displayed on screen, e.g. to export a table
+    /// or chart to a file or the clipboard. The widget is read from the screen after the next
+    /// frame is drawn, including its' border and padding, and passed to the callback. Colors are
+    /// discarded and trailing whitespace is trimmed from each line. Parts of the widget that are
+    /// not on screen (e.g. scrolled out of view) are left out; anything drawn over the widget
+    /// (e.g. a modal) is included. Must be called from the main thread.
+    ///
+    /// # Parameters:
+    ///
+    /// - Widget ID or path: &str
+    /// - Callback: RenderCallback
+    ///
+    /// # Example:
+    ///
+    /// We'll save a table to a file when a button is pressed:
+    /// ```
+    /// use ez_term::*;
+    ///
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// fn export(mut context: Context) -> bool {
+    ///     context.scheduler.render_widget_to_string("my_table", Box::new(
+    ///         |context: Context, text: String| {
+    ///             std::fs::write("table.txt", text).unwrap();
+    ///         }));
+    ///     true
+    /// }
+    /// scheduler.update_callback_config("export_button",
+    ///                                  CallbackConfig::from_on_press(Box::new(export)));
+    ///
+    /// run(root_widget, state_tree, scheduler, custom_data);
+    /// ```
+    pub fn render_widget_to_string(&mut self, widget: &str, callback: RenderCallback) {
+        self.backend
+            .widgets_to_render
+            .push((widget.to_string(), callback));
+    }
+
+    /// Replace the entire CallbackConfig of a widget on the next frame. You can pass an empty
+    /// CallbackConfig to remove all callbacks for a widget.
+    ///
+    /// # Parameters:
+    ///
+    /// - Widget ID or path: &str
+    /// - New CallbackConfig: CallbackConfig
+    ///
+    /// # Example:
+    ///
+    /// We'll remove any existing callbacks by overwriting with an empty CallbackConfig for a button
+    /// with id "my_button".
+    /// ```
+    /// use ez_term::*;
+    ///
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// let callback_config = CallbackConfig::default();
+    /// scheduler.overwrite_callback_config("my_button", callback_config);
+    /// run(root_widget, state_tree, scheduler, custom_data);
+    /// ```
+    pub fn overwrite_callback_config(&mut self, for_widget: &str, callback_config: CallbackConfig) {
+        if !self.synced {
+            self.backend
+                .new_callback_configs
+                .push((for_widget.to_string(), callback_config));
+        } else {
+            self.sync_thread_sender
+                .as_ref()
+                .unwrap()
+                .send(true)
+                .unwrap();
+            self.overwrite_callback_config_sender
+                .as_ref()
+                .unwrap()
+                .send((for_widget.to_string(), callback_config))
+                .unwrap()
+        }
+    }
+
     /// Update the CallbackConfig of a widget on the next frame. Any callback set on the new callback
     /// config will be set on the existing one (overwriting if one already exists). Any existing
     /// callbacks that are *not* set on the new config are left intact. In other words, this allows
@@ -710,7 +1400,7 @@ impl SchedulerFrontend {
     ///
     ///     new_states.as_label_mut().set_text(format!("Hello world {}!", x));
     ///
-    ///     scheduler.create_widget(new_widget, new_states, &mut state_tree);
+    ///     scheduler.create_widget(new_widget, new_states, None, &mut state_tree);
     ///
     /// }
     ///
@@ -733,13 +1423,11 @@ impl SchedulerFrontend {
         let base_type;
         let new_widget;
         if self.backend.templates.contains_key(widget_type) {
-            new_widget = self
-                .backend
-                .templates
-                .get_mut(widget_type)
-                .unwrap()
-                .clone()
-                .parse(self, path.to_string(), 0, Some(vec![format!("id: {}", id)]));
+            // Parse an instance of the template, so it is created again if the template is
+            // reloaded
+            new_widget = EzWidgetDefinition::new(widget_type.to_string(), String::new(), 0, 0)
+                .parse(self, path.to_string(), 0, Some(vec![format!("id: {}", id).into()]))
+                .unwrap_or_else(|e| panic!("Could not create widget \"{}\": {}", id, e));
         } else {
             base_type = widget_type.to_string();
             let new_state = EzState::from_string(&base_type, new_path.to_string(), self);
@@ -751,24 +1439,169 @@ impl SchedulerFrontend {
                 new_state,
             );
         };
-        let mut new_states = StateTree::new(id.to_string(), new_widget.as_ez_object().get_state());
-        if let EzObjects::Layout(ref i) = new_widget {
-            for child in i.get_widgets_recursive() {
-                let relative_path = child
-                    .as_ez_object()
-                    .get_path()
-                    .split_once(new_widget.as_ez_object().get_id().as_str())
-                    .unwrap()
-                    .1
-                    .to_string();
-                let widget_path =
-                    format!("{}{}", new_widget.as_ez_object().get_id(), relative_path);
-                new_states.add_node(widget_path, child.as_ez_object().get_state());
-            }
-        }
+        let new_states = get_new_states(&new_widget);
         (new_widget, new_states)
     }
 
+    /// Get the path of a slot of a template instance, to add widgets to the slot from code. Slots
+    /// are layouts in a template with a "slot" property; in an .ez file, children of a template
+    /// instance can be put in a slot with "in_slot: <name>". Panics if the widget has no slot with
+    /// this name.
+    ///
+    /// # Parameters:
+    ///
+    /// - ID or path of the template instance: &str
+    /// - Name of the slot: &str
+    /// - State tree: &mut StateTree
+    ///
+    /// # Example:
+    ///
+    /// Given a "Card" template with a "body" slot, we'll add a label to the body of a card:
+    ///
+    /// ```
+    /// use ez_term::*;
+    ///
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// let body = scheduler.get_slot("my_card", "body", &mut state_tree);
+    /// let (new_widget, mut new_states) =
+    ///         scheduler.prepare_create_widget("Label", "card_text", &body, &mut state_tree);
+    /// new_states.as_label_mut().set_text("Hello world!".to_string());
+    /// scheduler.create_widget(new_widget, new_states, None, &mut state_tree);
+    ///
+    /// run(root_widget, state_tree, scheduler, custom_data);
+    /// ```
+    pub fn get_slot(&self, widget: &str, slot: &str, state_tree: &mut StateTree) -> String {
+        let widget_path = state_tree.get(widget).as_generic().get_path().clone();
+        let mut slots: Vec<String> = state_tree
+            .get_all()
+            .into_iter()
+            .filter_map(|state| match state {
+                EzState::Layout(i)
+                    if i.get_slot() == slot
+                        && i.get_path().starts_with(&format!("{}/", widget_path)) =>
+                {
+                    Some(i.get_path().clone())
+                }
+                _ => None,
+            })
+            .collect();
+        // Prefer the slot closest to the widget, in case it contains other template instances
+        slots.sort_by_key(|x| x.matches('/').count());
+        slots.into_iter().next().unwrap_or_else(|| {
+            panic!(
+                "Widget \"{}\" has no layout with \"slot: {}\"",
+                widget, slot
+            )
+        })
+    }
+
+    /// Reparse a single template from new source, so widgets created from it afterwards (from
+    /// code, as a modal, or as part of another template) use the new definition. The source must
+    /// define only this template, in the same syntax as an .ez file. Existing instances of the
+    /// template (including instances of templates extending it) are created again from the new
+    /// definition if "reinstantiate" is true; they lose their current state and callbacks bound
+    /// to them from code, like any newly created widget. Panics if the template does not exist or
+    /// the source is invalid.
+    ///
+    /// # Parameters:
+    ///
+    /// - Name of the template: &str
+    /// - New source of the template: &str
+    /// - Whether to create existing instances again: bool
+    /// - State tree: &mut StateTree
+    ///
+    /// # Example:
+    ///
+    /// We'll make all "MyButton" widgets red, including the ones that already exist:
+    ///
+    /// ```
+    /// use ez_term::*;
+    ///
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// scheduler.reload_template(
+    ///     "MyButton",
+    ///     "- <MyButton@Button>:\n    text: Click me\n    bg_color: red\n",
+    ///     true,
+    ///     &mut state_tree,
+    /// );
+    ///
+    /// run(root_widget, state_tree, scheduler, custom_data);
+    /// ```
+    pub fn reload_template(
+        &mut self,
+        name: &str,
+        new_source: &str,
+        reinstantiate: bool,
+        state_tree: &mut StateTree,
+    ) {
+        if !self.backend.templates.contains_key(name) {
+            panic!("Could not reload template \"{}\": it does not exist", name)
+        }
+        let lines = new_source.lines().map(|x| x.to_string()).collect();
+        let (_, widgets, mut templates) = parse_level(lines, 0, 0, format!("<{}>", name))
+            .unwrap_or_else(|e| panic!("Could not reload template \"{}\": {}", name, e));
+        let template = templates.remove(name).unwrap_or_else(|| {
+            panic!(
+                "Could not reload template \"{}\": the new source does not define it",
+                name
+            )
+        });
+        if !widgets.is_empty() || !templates.is_empty() {
+            panic!(
+                "Could not reload template \"{}\": the new source should only define this \
+                template",
+                name
+            )
+        }
+        self.backend.templates.insert(name.to_string(), template);
+        check_template_inheritance(&self.backend.templates)
+            .unwrap_or_else(|e| panic!("Could not reload template \"{}\": {}", name, e));
+        // Modals are created from the templates of the root layout
+        state_tree
+            .as_layout_mut()
+            .set_templates(self.backend.templates.clone());
+
+        if !reinstantiate {
+            return;
+        }
+        let instances: Vec<String> = self
+            .backend
+            .template_instances
+            .iter()
+            .filter(|(path, instance)| {
+                instance.templates.iter().any(|x| x == name)
+                    && path.as_str() != "/root"
+                    && state_tree.try_get(path).is_some()
+            })
+            .map(|(path, _)| path.clone())
+            .collect();
+        // Instances inside other instances are created again along with them
+        let is_nested = |path: &String| {
+            instances
+                .iter()
+                .any(|x| path.starts_with(&format!("{}/", x)))
+        };
+        let mut to_create: Vec<&String> = instances.iter().filter(|x| !is_nested(x)).collect();
+        to_create.sort();
+        for path in to_create {
+            let instance = self.backend.template_instances.get(path).unwrap().clone();
+            let new_widget = instance
+                .definition
+                .clone()
+                .parse(
+                    self,
+                    instance.parent_path,
+                    instance.order,
+                    Some(instance.config),
+                )
+                .unwrap_or_else(|e| panic!("Could not reload template \"{}\": {}", name, e));
+            let new_states = get_new_states(&new_widget);
+            self.create_widget(new_widget, new_states, None, state_tree);
+        }
+    }
+
     /// Finish creating a widget from a template or base widget type and add it to a layout.
     /// This allows you to create widgets from code.
     ///
@@ -776,6 +1609,8 @@ impl SchedulerFrontend {
     ///
     /// - new_widget: EzObject (get it from the 'prepare_create_widget' method)
     /// - new_states: StateTree (get it from the 'prepare_create_widget' method)
+    /// - Position among the children of the parent layout, or None to add it as the last child:
+    ///   Option<usize>
     /// - State tree: &mut StateTree
     ///
     /// # Example:
@@ -798,7 +1633,7 @@ impl SchedulerFrontend {
     ///
     ///     new_states.as_label_mut().set_text(format!("Hello world {}!", x));
     ///
-    ///     scheduler.create_widget(new_widget, new_states, &mut state_tree);
+    ///     scheduler.create_widget(new_widget, new_states, None, &mut state_tree);
     ///
     /// }
     ///
@@ -808,12 +1643,13 @@ impl SchedulerFrontend {
         &mut self,
         new_widget: EzObjects,
         new_states: StateTree,
+        index: Option<usize>,
         state_tree: &mut StateTree,
     ) {
         let path = new_states.obj.as_generic().get_path().clone();
         if !self.synced {
             state_tree.extend(path, new_states);
-            self.backend.widgets_to_create.push(new_widget);
+            self.backend.widgets_to_create.push((new_widget, index));
             if let Some(ref recv) = self.new_properties_receiver {
                 if let Ok(i) = recv.try_recv() {
                     self.backend.properties.extend(i);
@@ -836,7 +1672,7 @@ impl SchedulerFrontend {
             self.create_widget_sender
                 .as_ref()
                 .unwrap()
-                .send((new_widget, new_states))
+                .send((new_widget, new_states, index))
                 .unwrap();
             self.new_receivers_sender
                 .as_ref()
@@ -880,7 +1716,7 @@ impl SchedulerFrontend {
         if !self.synced {
             if name == "root" || name == "/root" {
                 panic!("Cannot remove the root layout")
-            } else if name == "modal" || name == "/root/modal" {
+            } else if LayoutState::is_modal_id(name.strip_prefix("/root/").unwrap_or(name)) {
                 panic!("Cannot remove modal widget; use scheduler.dismiss_modal instead.")
             }
             let name_string = name.to_string();
@@ -901,844 +1737,3122 @@ impl SchedulerFrontend {
         }
     }
 
-    fn get_update_func(&mut self, name: &str) {
-        if name.contains('/') {
-            let (widget, property_name) = name.rsplit_once('/').unwrap();
-            let (widget, property_name) = (widget.to_string(), property_name.to_string());
-            let updater = move |state_tree: &mut StateTree, val: EzValues| {
-                state_tree
-                    .get_mut(&widget)
-                    .as_generic_mut()
-                    .update_property(&property_name, val);
-            };
-            if !self.backend.property_updaters.contains_key(name) {
-                self.backend
-                    .property_updaters
-                    .insert(name.to_string(), Box::new(updater));
-            }
-        }
-    }
-
-    /// Create a custom property. You can bind this property to widget properties of the same type;
-    /// then when you update the custom property, the widget will update automatically as well.
-    /// The name of custom properties may not contain any '/'.
-    /// For a tutorial on this see: [Creating custom properties](#scheduler_properties).
+    /// Clone an existing widget (or a layout including all of its' children) and add the clone
+    /// to a layout under a new ID. The current state values of the original are copied to the
+    /// clone; callbacks are not copied. The states of the clone are added to the state tree
+    /// immediately, so you can change them right away; the clone itself appears on the next frame.
     ///
-    /// ## Parameters:
+    /// # Parameters:
     ///
-    /// - Name of the new property: &str
-    /// - Value of the new property: usize
+    /// - ID or path of widget to clone: &str
+    /// - ID of new widget: &str
+    /// - ID or path of parent layout: &str
+    /// - State tree: &mut StateTree
     ///
-    /// ## Example:
+    /// # Example:
     ///
-    /// We'll create a custom property and bind it to a widget in an .ez file.
-    /// First the code:
+    /// We'll duplicate a layout "my_row" into the layout "my_table" whenever a button is pressed:
     /// ```
     /// use ez_term::*;
     ///
     /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
     ///
-    /// scheduler.new_usize_property("my_property", 1);
+    /// let mut counter = 0;
+    /// let duplicate = move |context: Context| {
+    ///     counter += 1;
+    ///     let new_id = format!("my_row_{}", counter);
+    ///     context.scheduler.clone_widget("my_row", &new_id, "my_table", context.state_tree);
+    ///     true
+    /// };
+    /// scheduler.update_callback_config("duplicate_button",
+    ///                                  CallbackConfig::from_on_press(Box::new(duplicate)));
     ///
     /// run(root_widget, state_tree, scheduler, custom_data);
     /// ```
-    ///
-    /// Now the .ez file:
-    ///
-    /// ```
-    /// - Layout:
-    ///     - ProgressBar:
-    ///         value: properties.my_property
-    /// ```
-    pub fn new_usize_property(&mut self, name: &str, value: usize) -> EzProperty<usize> {
-        let (property, receiver) = EzProperty::new(name.to_string(), value);
-        self.backend
-            .properties
-            .insert(name.to_string(), EzProperties::Usize(property.clone()));
-        self.backend
-            .property_receivers
-            .insert(name.to_string(), receiver);
-        self.get_update_func(name);
-        property
+    pub fn clone_widget(
+        &mut self,
+        source: &str,
+        new_id: &str,
+        parent: &str,
+        state_tree: &mut StateTree,
+    ) {
+        if !self.synced {
+            let source_path = state_tree.get(source).as_generic().get_path().clone();
+            let parent_path = state_tree.get(parent).as_generic().get_path().clone();
+            let new_path = format!("{}/{}", parent_path, new_id);
+
+            // Parents must be added to the new tree before their children
+            let mut source_states: Vec<EzState> =
+                state_tree.get(&source_path).get_all().into_iter().cloned().collect();
+            source_states.sort_by_key(|x| x.as_generic().get_path().matches('/').count());
+
+            let mut new_states = StateTree::new(
+                new_id.to_string(),
+                source_states.remove(0).clone_to_path(new_path.clone(), self),
+            );
+            for state in source_states {
+                let relative_path = state
+                    .as_generic()
+                    .get_path()
+                    .strip_prefix(&source_path)
+                    .unwrap()
+                    .to_string();
+                let new_state =
+                    state.clone_to_path(format!("{}{}", new_path, relative_path), self);
+                new_states.add_node(format!("{}{}", new_id, relative_path), new_state);
+            }
+            state_tree.extend(new_path.clone(), new_states);
+            self.backend.widgets_to_clone.push((source_path, new_path));
+        } else {
+            self.sync_thread_sender
+                .as_ref()
+                .unwrap()
+                .send(true)
+                .unwrap();
+            self.clone_widget_sender
+                .as_ref()
+                .unwrap()
+                .send((source.to_string(), new_id.to_string(), parent.to_string()))
+                .unwrap();
+        }
     }
 
-    /// Create a custom property. You can bind this property to widget properties of the same type;
-    /// then when you update the custom property, the widget will update automatically as well.
-    /// The name of custom properties may not contain any '/'.
-    /// For a tutorial on this see: [Creating custom properties](#scheduler_properties).
+    /// Move a widget (or a layout including all of its' children) to a different layout on the next
+    /// frame. The state, properties (including bindings) and callbacks of the widget are kept. The
+    /// ID of the widget stays the same, but its' path changes to reflect the new parent. You can
+    /// also move a widget within the same layout to change its' position among its' siblings.
     ///
-    /// ## Parameters:
+    /// # Parameters:
     ///
-    /// - Name of the new property: &str
-    /// - Value of the new property: f64
+    /// - ID or path of widget to move: &str
+    /// - ID or path of the new parent layout: &str
+    /// - Position among the children of the new parent, or None to add it as the last child:
+    ///   Option<usize>
     ///
-    /// ## Example:
+    /// # Example:
     ///
-    /// We'll create a custom property and bind it to a widget in an .ez file.
-    /// First the code:
+    /// We'll move a label from its' current layout to become the first child of "my_other_layout":
     /// ```
     /// use ez_term::*;
     ///
     /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
     ///
-    /// scheduler.new_f64_property("my_property", 0.5);
+    /// scheduler.move_widget("my_label", "my_other_layout", Some(0));
     ///
     /// run(root_widget, state_tree, scheduler, custom_data);
     /// ```
-    /// Now the .ez file:
-    /// ```
-    /// - Layout:
-    ///     scroll_y: true
-    ///     scroll_start_y: properties.my_property
-    /// ```
-    pub fn new_f64_property(&mut self, name: &str, value: f64) -> EzProperty<f64> {
-        let (property, receiver) = EzProperty::new(name.to_string(), value);
-        self.backend
-            .properties
-            .insert(name.to_string(), EzProperties::F64(property.clone()));
-        self.backend
-            .property_receivers
-            .insert(name.to_string(), receiver);
-        self.get_update_func(name);
-        property
+    pub fn move_widget(&mut self, name: &str, new_parent: &str, index: Option<usize>) {
+        if !self.synced {
+            if name == "root" || name == "/root" {
+                panic!("Cannot move the root layout")
+            } else if LayoutState::is_modal_id(name.strip_prefix("/root/").unwrap_or(name)) {
+                panic!("Cannot move modal widget")
+            }
+            self.backend.widgets_to_move.push((
+                name.to_string(),
+                new_parent.to_string(),
+                index,
+            ));
+        } else {
+            self.sync_thread_sender
+                .as_ref()
+                .unwrap()
+                .send(true)
+                .unwrap();
+            self.move_widget_sender
+                .as_ref()
+                .unwrap()
+                .send((name.to_string(), new_parent.to_string(), index))
+                .unwrap();
+        }
     }
 
-    /// Create a custom property. You can bind this property to widget properties of the same type;
-    /// then when you update the custom property, the widget will update automatically as well.
-    /// The name of custom properties may not contain any '/'.
-    /// For a tutorial on this see: [Creating custom properties](#scheduler_properties).
+    /// Swap the positions of two children of a layout on the next frame. Useful for reordering
+    /// widgets in a box or table layout. To move a single child to a specific position, use
+    /// [move_widget] with the current parent of the widget as the new parent.
     ///
-    /// ## Parameters:
+    /// # Parameters:
     ///
-    /// - Name of the new property: &str
-    /// - Value of the new property: String
+    /// - ID or path of the layout: &str
+    /// - ID of the first child: &str
+    /// - ID of the second child: &str
     ///
-    /// ## Example:
+    /// # Example:
     ///
-    /// We'll create a custom property and bind it to a widget in an .ez file.
-    /// First the code:
     /// ```
     /// use ez_term::*;
     ///
     /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
     ///
-    /// scheduler.new_string_property("my_property", "Hello world!".to_string());
+    /// scheduler.swap_children("my_layout", "my_label", "my_button");
     ///
     /// run(root_widget, state_tree, scheduler, custom_data);
     /// ```
-    /// Now the .ez file:
-    /// ```
-    /// - Layout:
-    ///     - Label:
-    ///         text: properties.my_property
-    /// ```
-    pub fn new_string_property(&mut self, name: &str, value: String) -> EzProperty<String> {
-        let (property, receiver) = EzProperty::new(name.to_string(), value);
-        self.backend
-            .properties
-            .insert(name.to_string(), EzProperties::String(property.clone()));
-        self.backend
-            .property_receivers
-            .insert(name.to_string(), receiver);
-        self.get_update_func(name);
-        property
+    pub fn swap_children(&mut self, layout: &str, child_1: &str, child_2: &str) {
+        if !self.synced {
+            self.backend.children_to_swap.push((
+                layout.to_string(),
+                child_1.to_string(),
+                child_2.to_string(),
+            ));
+        } else {
+            self.sync_thread_sender
+                .as_ref()
+                .unwrap()
+                .send(true)
+                .unwrap();
+            self.swap_children_sender
+                .as_ref()
+                .unwrap()
+                .send((layout.to_string(), child_1.to_string(), child_2.to_string()))
+                .unwrap();
+        }
     }
 
-    /// Create a custom property. You can bind this property to widget properties of the same type;
-    /// then when you update the custom property, the widget will update automatically as well.
-    /// The name of custom properties may not contain any '/'.
-    /// For a tutorial on this see: [Creating custom properties](#scheduler_properties).
+    /// Create a new screen from a template or base widget type and add it to a layout in screen
+    /// mode. This allows you to create screens from code instead of declaring all of them in the
+    /// .ez file up front. The screen is added to the first layout in screen mode found in the
+    /// UI; if there are multiple screen layouts use [add_screen_to] instead. The template must be
+    /// a layout. Use [show_screen] to switch to the new screen.
     ///
-    /// ## Parameters:
+    /// # Parameters:
     ///
-    /// - Name of the new property: &str
-    /// - Value of the new property: bool
+    /// - ID of new screen: &str
+    /// - Template or widget type: &str
+    /// - State tree: &mut StateTree
     ///
-    /// ## Example:
+    /// # Example:
     ///
-    /// We'll create a custom property and bind it to a widget in an .ez file.
-    /// First the code:
     /// ```
     /// use ez_term::*;
     ///
     /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
     ///
-    /// scheduler.new_bool_property("my_property", true);
+    /// scheduler.add_screen("settings", "SettingsScreen", &mut state_tree);
+    /// scheduler.show_screen("settings", &mut state_tree);
     ///
     /// run(root_widget, state_tree, scheduler, custom_data);
     /// ```
-    /// Now the .ez file:
-    /// ```
-    /// - Layout:
-    ///     - Button:
-    ///         disabled: properties.my_property
-    /// ```
-    pub fn new_bool_property(&mut self, name: &str, value: bool) -> EzProperty<bool> {
-        let (property, receiver) = EzProperty::new(name.to_string(), value);
-        self.backend
-            .properties
-            .insert(name.to_string(), EzProperties::Bool(property.clone()));
-        self.backend
-            .property_receivers
-            .insert(name.to_string(), receiver);
-        self.get_update_func(name);
-        property
+    pub fn add_screen(&mut self, id: &str, template: &str, state_tree: &mut StateTree) {
+        let layout = find_screen_layout(state_tree)
+            .unwrap_or_else(|| panic!("Cannot add screen \"{}\": there is no layout in screen \
+                                       mode", id));
+        self.add_screen_to(&layout, id, template, state_tree);
     }
 
-    /// Create a custom property. You can bind this property to widget properties of the same type;
-    /// then when you update the custom property, the widget will update automatically as well.
-    /// The name of custom properties may not contain any '/'.
-    /// For a tutorial on this see: [Creating custom properties](#scheduler_properties).
+    /// Create a new screen from a template or base widget type and add it to a specific layout
+    /// in screen mode. See [add_screen].
     ///
-    /// ## Parameters:
+    /// # Parameters:
     ///
-    /// - Name of the new property: &str
-    /// - Value of the new property: Color
+    /// - ID or path of the layout in screen mode: &str
+    /// - ID of new screen: &str
+    /// - Template or widget type: &str
+    /// - State tree: &mut StateTree
     ///
-    /// ## Example:
+    /// # Example:
     ///
-    /// We'll create a custom property and bind it to a widget in an .ez file.
-    /// First the code:
     /// ```
     /// use ez_term::*;
     ///
     /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
     ///
-    /// scheduler.new_color_property("my_property", Color::Yellow);
+    /// scheduler.add_screen_to("my_screen_layout", "settings", "SettingsScreen",
+    ///                         &mut state_tree);
     ///
     /// run(root_widget, state_tree, scheduler, custom_data);
     /// ```
-    /// Now the .ez file:
-    /// ```
-    /// - Layout:
-    ///     - Label:
-    ///         fg_color: properties.my_property
-    /// ```
-    pub fn new_color_property(&mut self, name: &str, value: Color) -> EzProperty<Color> {
-        let (property, receiver) = EzProperty::new(name.to_string(), value);
-        self.backend
-            .properties
-            .insert(name.to_string(), EzProperties::Color(property.clone()));
-        self.backend
-            .property_receivers
-            .insert(name.to_string(), receiver);
-        self.get_update_func(name);
-        property
+    pub fn add_screen_to(
+        &mut self,
+        layout: &str,
+        id: &str,
+        template: &str,
+        state_tree: &mut StateTree,
+    ) {
+        if state_tree.get(layout).as_layout().get_mode() != &LayoutMode::Screen {
+            panic!("Cannot add screen \"{}\": layout \"{}\" is not in screen mode", id, layout)
+        }
+        let (new_widget, new_states) = self.prepare_create_widget(template, id, layout, state_tree);
+        if !matches!(new_widget, EzObjects::Layout(_)) {
+            panic!("Cannot add screen \"{}\": template \"{}\" is not a layout", id, template)
+        }
+        self.create_widget(new_widget, new_states, None, state_tree);
     }
 
-    /// Create a custom property. You can bind this property to widget properties of the same type;
-    /// then when you update the custom property, the widget will update automatically as well.
-    /// The name of custom properties may not contain any '/'.
-    /// For a tutorial on this see: [Creating custom properties](#scheduler_properties).
+    /// Remove a screen from its' layout on the next frame. If the screen was active, the first
+    /// remaining screen is shown instead.
     ///
-    /// ## Parameters:
+    /// # Parameters:
     ///
-    /// - Name of the new property: &str
-    /// - Value of the new property: LayoutMode
+    /// - ID or path of the screen: &str
+    /// - State tree: &mut StateTree
     ///
-    /// ## Example:
+    /// # Example:
     ///
-    /// We'll create a custom property and bind it to a widget in an .ez file.
-    /// First the code:
     /// ```
     /// use ez_term::*;
     ///
     /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
     ///
-    /// scheduler.new_layout_mode_property("my_property", LayoutMode::Table);
+    /// scheduler.remove_screen("settings", &mut state_tree);
     ///
     /// run(root_widget, state_tree, scheduler, custom_data);
     /// ```
-    /// Now the .ez file:
-    /// ```
-    /// - Layout:
-    ///     mode: properties.my_property
-    /// ```
-    pub fn new_layout_mode_property(
-        &mut self,
-        name: &str,
-        value: LayoutMode,
-    ) -> EzProperty<LayoutMode> {
-        let (property, receiver) = EzProperty::new(name.to_string(), value);
-        self.backend
-            .properties
-            .insert(name.to_string(), EzProperties::LayoutMode(property.clone()));
+    pub fn remove_screen(&mut self, screen: &str, state_tree: &mut StateTree) {
+        let (layout, id) = get_screen_parent(screen, state_tree);
+        let state = state_tree.get_mut(&layout).as_layout_mut();
+        if state.get_active_screen() == id {
+            state.set_active_screen("");
+        }
         self.backend
-            .property_receivers
-            .insert(name.to_string(), receiver);
-        self.get_update_func(name);
-        property
+            .screen_history
+            .retain(|(x, y)| x != &layout || y != &id);
+        self.remove_widget(&format!("{}/{}", layout, id));
+        self.update_widget(&layout);
     }
 
-    /// Create a custom property. You can bind this property to widget properties of the same type;
-    /// then when you update the custom property, the widget will update automatically as well.
-    /// The name of custom properties may not contain any '/'.
-    /// For a tutorial on this see: [Creating custom properties](#scheduler_properties).
+    /// Show a screen, switching its' layout to it. The screen that was shown before is
+    /// remembered, so you can return to it with [go_back].
     ///
-    /// ## Parameters:
+    /// # Parameters:
     ///
-    /// - Name of the new property: &str
-    /// - Value of the new property: LayoutOrientation
+    /// - ID or path of the screen: &str
+    /// - State tree: &mut StateTree
     ///
-    /// ## Example:
+    /// # Example:
     ///
-    /// We'll create a custom property and bind it to a widget in an .ez file.
-    /// First the code:
     /// ```
     /// use ez_term::*;
     ///
     /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
     ///
-    /// scheduler.new_layout_orientation_property("my_property", LayoutOrientation::Vertical);
+    /// fn open_settings(mut context: Context) -> bool {
+    ///     context.scheduler.show_screen("settings", context.state_tree);
+    ///     true
+    /// }
+    /// scheduler.update_callback_config("settings_button",
+    ///                                  CallbackConfig::from_on_press(Box::new(open_settings)));
     ///
     /// run(root_widget, state_tree, scheduler, custom_data);
     /// ```
-    /// Now the .ez file:
-    /// ```
-    /// - Layout:
-    ///     orientation: properties.my_property
-    /// ```
-    pub fn new_layout_orientation_property(
-        &mut self,
-        name: &str,
-        value: LayoutOrientation,
-    ) -> EzProperty<LayoutOrientation> {
-        let (property, receiver) = EzProperty::new(name.to_string(), value);
-        self.backend.properties.insert(
-            name.to_string(),
-            EzProperties::LayoutOrientation(property.clone()),
-        );
-        self.backend
-            .property_receivers
-            .insert(name.to_string(), receiver);
-        self.get_update_func(name);
-        property
+    pub fn show_screen(&mut self, screen: &str, state_tree: &mut StateTree) {
+        let (layout, id) = get_screen_parent(screen, state_tree);
+        let state = state_tree.get_mut(&layout).as_layout_mut();
+        let previous = state.get_active_screen();
+        if previous == id {
+            return;
+        }
+        state.set_active_screen(&id);
+        self.backend.screen_history.push((layout.clone(), previous));
+        self.update_widget(&layout);
     }
 
-    /// Create a custom property. You can bind this property to widget properties of the same type;
-    /// then when you update the custom property, the widget will update automatically as well.
-    /// The name of custom properties may not contain any '/'.
-    /// For a tutorial on this see: [Creating custom properties](#scheduler_properties).
+    /// Return to the screen that was shown before the last call to [show_screen]. Returns false
+    /// if there is no screen to return to.
     ///
-    /// ## Parameters:
+    /// # Parameters:
     ///
-    /// - Name of the new property: &str
-    /// - Value of the new property: HorizontalAlignment
+    /// - State tree: &mut StateTree
     ///
-    /// ## Example:
+    /// # Example:
     ///
-    /// We'll create a custom property and bind it to a widget in an .ez file.
-    /// First the code:
     /// ```
     /// use ez_term::*;
     ///
     /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
     ///
-    /// scheduler.new_horizontal_alignment_property("my_property", HorizontalAlignment::Center);
+    /// fn back(mut context: Context) -> bool {
+    ///     context.scheduler.go_back(context.state_tree);
+    ///     true
+    /// }
+    /// scheduler.update_callback_config("back_button",
+    ///                                  CallbackConfig::from_on_press(Box::new(back)));
     ///
     /// run(root_widget, state_tree, scheduler, custom_data);
     /// ```
-    /// Now the .ez file:
-    /// ```
-    /// - Layout:
-    ///     - Label:
-    ///         halign: properties.my_property
-    /// ```
-    pub fn new_vertical_alignment_property(
-        &mut self,
-        name: &str,
-        value: VerticalAlignment,
-    ) -> EzProperty<VerticalAlignment> {
-        let (property, receiver) = EzProperty::new(name.to_string(), value);
-        self.backend.properties.insert(
-            name.to_string(),
-            EzProperties::VerticalAlignment(property.clone()),
-        );
-        self.backend
-            .property_receivers
-            .insert(name.to_string(), receiver);
-        self.get_update_func(name);
-        property
+    pub fn go_back(&mut self, state_tree: &mut StateTree) -> bool {
+        while let Some((layout, screen)) = self.backend.screen_history.pop() {
+            if let Some(state) = state_tree.try_get_mut(&layout) {
+                state.as_layout_mut().set_active_screen(&screen);
+                self.update_widget(&layout);
+                return true;
+            }
+        }
+        false
     }
 
-    /// Create a custom property. You can bind this property to widget properties of the same type;
-    /// then when you update the custom property, the widget will update automatically as well.
-    /// The name of custom properties may not contain any '/'.
-    /// For a tutorial on this see: [Creating custom properties](#scheduler_properties).
+    /// Get the ID of the screen that is currently shown by a layout in screen mode.
     ///
-    /// ## Parameters:
+    /// # Parameters:
     ///
-    /// - Name of the new property: &str
-    /// - Value of the new property: VerticalAlignment
+    /// - ID or path of the layout in screen mode: &str
+    /// - State tree: &StateTree
     ///
-    /// ## Example:
+    /// # Example:
     ///
-    /// We'll create a custom property and bind it to a widget in an .ez file.
-    /// First the code:
     /// ```
     /// use ez_term::*;
     ///
     /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
     ///
-    /// scheduler.new_vertical_alignment_property("my_property", VerticalAlignment::Middle);
+    /// let screen = scheduler.get_active_screen("my_screen_layout", &state_tree);
     ///
     /// run(root_widget, state_tree, scheduler, custom_data);
     /// ```
-    /// Now the .ez file:
-    /// ```
-    /// - Layout:
-    ///     - Label:
-    ///         valign: properties.my_property
-    /// ```
-    pub fn new_horizontal_alignment_property(
-        &mut self,
-        name: &str,
-        value: HorizontalAlignment,
-    ) -> EzProperty<HorizontalAlignment> {
-        let (property, receiver) = EzProperty::new(name.to_string(), value);
-        self.backend.properties.insert(
-            name.to_string(),
-            EzProperties::HorizontalAlignment(property.clone()),
-        );
-        self.backend
-            .property_receivers
-            .insert(name.to_string(), receiver);
-        self.get_update_func(name);
-        property
+    pub fn get_active_screen(&self, layout: &str, state_tree: &StateTree) -> String {
+        state_tree.get(layout).as_layout().get_active_screen()
     }
 
-    /// Create a custom property. You can bind this property to widget properties of the same type;
-    /// then when you update the custom property, the widget will update automatically as well.
-    /// The name of custom properties may not contain any '/'.
-    /// For a tutorial on this see: [Creating custom properties](#scheduler_properties).
+    /// Create a [Wizard] (multi-step dialog) and add it to a layout. The wizard is a vertical box
+    /// layout with the passed ID, containing a progress label ("{id}_progress"), a layout in screen
+    /// mode holding the steps ("{id}_steps") and a layout with the "Back" and "Next" buttons
+    /// ("{id}_back" and "{id}_next"). The first step is shown initially.
     ///
-    /// ## Parameters:
+    /// # Parameters:
     ///
-    /// - Name of the new property: &str
-    /// - Value of the new property: HorizontalPosHint
+    /// - ID of the wizard: &str
+    /// - ID or path of the parent layout: &str
+    /// - Wizard: [Wizard]
+    /// - State tree: &mut StateTree
     ///
-    /// ## Example:
+    /// # Example:
     ///
-    /// We'll create a custom property and bind it to a widget in an .ez file.
-    /// First the code:
     /// ```
     /// use ez_term::*;
     ///
     /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
     ///
-    /// scheduler.new_horizontal_pos_hint_property("my_property",
-    ///                                            Some((HorizontalAlignment::Right, 0.75)));
+    /// let mut wizard = Wizard::new();
+    /// wizard.add_step("account", "AccountStep", "Account");
+    /// wizard.add_step("options", "OptionsStep", "Options");
+    /// wizard.set_on_finish(Box::new(|context: Context, result: WizardResult| {
+    ///     context.scheduler.remove_widget("setup");
+    /// }));
+    /// scheduler.create_wizard("setup", "my_layout", wizard, &mut state_tree);
     ///
     /// run(root_widget, state_tree, scheduler, custom_data);
     /// ```
-    /// Now the .ez file:
-    /// ```
-    /// - Layout:
-    ///     mode: float
-    ///     - Label:
-    ///         pos_hint_x: properties.my_property
-    /// ```
-    pub fn new_horizontal_pos_hint_property(
+    pub fn create_wizard(
         &mut self,
-        name: &str,
-        value: HorizontalPosHint,
-    ) -> EzProperty<HorizontalPosHint> {
-        let (property, receiver) = EzProperty::new(name.to_string(), value);
-        self.backend.properties.insert(
-            name.to_string(),
-            EzProperties::HorizontalPosHint(property.clone()),
-        );
-        self.backend
-            .property_receivers
-            .insert(name.to_string(), receiver);
-        self.get_update_func(name);
-        property
+        id: &str,
+        parent: &str,
+        wizard: Wizard,
+        state_tree: &mut StateTree,
+    ) {
+        if wizard.steps.is_empty() {
+            panic!("Cannot create wizard \"{}\": it has no steps", id)
+        }
+        let (new_widget, mut new_states) =
+            self.prepare_create_widget("Layout", id, parent, state_tree);
+        let wizard_path = new_states.obj.as_generic().get_path().clone();
+        new_states
+            .as_layout_mut()
+            .set_orientation(LayoutOrientation::Vertical);
+        self.create_widget(new_widget, new_states, None, state_tree);
+
+        let progress_id = format!("{}_progress", id);
+        let (new_widget, mut new_states) =
+            self.prepare_create_widget("Label", &progress_id, &wizard_path, state_tree);
+        new_states.as_generic_mut().set_size_hint_y(None);
+        new_states.as_generic_mut().set_height(1);
+        self.create_widget(new_widget, new_states, None, state_tree);
+
+        let steps_id = format!("{}_steps", id);
+        let (new_widget, mut new_states) =
+            self.prepare_create_widget("Layout", &steps_id, &wizard_path, state_tree);
+        new_states.as_layout_mut().set_mode(LayoutMode::Screen);
+        new_states.as_generic_mut().set_weight(1.0);
+        new_states
+            .as_layout_mut()
+            .set_active_screen(&wizard.steps[0].id);
+        self.create_widget(new_widget, new_states, None, state_tree);
+        let steps_path = format!("{}/{}", wizard_path, steps_id);
+        for step in wizard.steps.iter() {
+            self.add_screen_to(&steps_path, &step.id, &step.template, state_tree);
+        }
+
+        let buttons_id = format!("{}_buttons", id);
+        let (new_widget, mut new_states) =
+            self.prepare_create_widget("Layout", &buttons_id, &wizard_path, state_tree);
+        new_states.as_generic_mut().set_size_hint_y(None);
+        new_states.as_generic_mut().set_height(3);
+        self.create_widget(new_widget, new_states, None, state_tree);
+        let buttons_path = format!("{}/{}", wizard_path, buttons_id);
+        for (button_id, text) in [(format!("{}_back", id), "Back"), (format!("{}_next", id), "Next")] {
+            let (new_widget, mut new_states) =
+                self.prepare_create_widget("Button", &button_id, &buttons_path, state_tree);
+            new_states.as_button_mut().set_text(text.to_string());
+            new_states.as_generic_mut().set_size_hint_x(None);
+            new_states.as_generic_mut().set_width(10);
+            self.create_widget(new_widget, new_states, None, state_tree);
+        }
+
+        let paths = WizardPaths {
+            progress: format!("{}/{}", wizard_path, progress_id),
+            wizard: wizard_path,
+            steps: steps_path,
+            back: format!("{}/{}_back", buttons_path, id),
+            next: format!("{}/{}_next", buttons_path, id),
+        };
+        show_wizard_step(&wizard, &paths, 0, state_tree, self);
+        let wizard = Arc::new(Mutex::new(wizard));
+
+        let (back_wizard, back_paths) = (wizard.clone(), paths.clone());
+        let on_back = move |context: Context| {
+            wizard_back(&back_wizard, &back_paths, context);
+            true
+        };
+        self.update_callback_config(&paths.back, CallbackConfig::from_on_press(Box::new(on_back)));
+        let next_path = paths.next.clone();
+        let on_next = move |context: Context| {
+            wizard_next(&wizard, &paths, context);
+            true
+        };
+        self.update_callback_config(&next_path, CallbackConfig::from_on_press(Box::new(on_next)));
     }
 
-    /// Create a custom property. You can bind this property to widget properties of the same type;
-    /// then when you update the custom property, the widget will update automatically as well.
-    /// The name of custom properties may not contain any '/'.
-    /// For a tutorial on this see: [Creating custom properties](#scheduler_properties).
+    /// Get the current arrangement of the children of a dock layout as a string, so it can be
+    /// saved and restored later using [load_dock_arrangement]. This allows users to rearrange
+    /// panels and find them the same way the next time the app is started.
     ///
-    /// ## Parameters:
+    /// # Parameters:
     ///
-    /// - Name of the new property: &str
-    /// - Value of the new property: VerticalPosHint
+    /// - ID or path of the dock layout: &str
+    /// - State tree: &StateTree
     ///
-    /// ## Example:
+    /// # Example:
     ///
-    /// We'll create a custom property and bind it to a widget in an .ez file.
-    /// First the code:
     /// ```
     /// use ez_term::*;
     ///
     /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
     ///
-    /// scheduler.new_vertical_pos_hint_property("my_property",
-    ///                                            Some((VerticalAlignment::Middle, 0.75)));
+    /// let arrangement = scheduler.save_dock_arrangement("my_dock_layout", &state_tree);
+    /// std::fs::write("panels.txt", arrangement).unwrap();
     ///
     /// run(root_widget, state_tree, scheduler, custom_data);
     /// ```
-    /// Now the .ez file:
-    /// ```
-    /// - Layout:
-    ///     mode: float
-    ///     - Label:
-    ///         pos_hint_y: properties.my_property
-    /// ```
-    pub fn new_vertical_pos_hint_property(
+    pub fn save_dock_arrangement(&self, layout: &str, state_tree: &StateTree) -> String {
+        let mut arrangement = Vec::new();
+        for child in state_tree.get(layout).get_children() {
+            let state = child.obj.as_generic();
+            arrangement.push(format!(
+                "{}:{}:{}:{}:{}:{}:{}",
+                state.get_id(),
+                format!("{:?}", state.get_dock()).to_lowercase(),
+                state.get_collapsed(),
+                state.get_size().get_width(),
+                state.get_size().get_height(),
+                state.get_position().get_x(),
+                state.get_position().get_y(),
+            ));
+        }
+        arrangement.sort();
+        arrangement.join("\n")
+    }
+
+    /// Restore an arrangement of the children of a dock layout that was created with
+    /// [save_dock_arrangement]. Children that no longer exist are skipped. Returns an error if the
+    /// arrangement is invalid (e.g. a file that was edited or is outdated), in which case nothing
+    /// is changed.
+    ///
+    /// # Parameters:
+    ///
+    /// - ID or path of the dock layout: &str
+    /// - Arrangement: &str
+    /// - State tree: &mut StateTree
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use ez_term::*;
+    ///
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// if let Ok(arrangement) = std::fs::read_to_string("panels.txt") {
+    ///     if let Err(e) =
+    ///         scheduler.load_dock_arrangement("my_dock_layout", &arrangement, &mut state_tree)
+    ///     {
+    ///         eprintln!("Ignoring saved panels: {}", e);
+    ///     }
+    /// }
+    ///
+    /// run(root_widget, state_tree, scheduler, custom_data);
+    /// ```
+    pub fn load_dock_arrangement(
+        &mut self,
+        layout: &str,
+        arrangement: &str,
+        state_tree: &mut StateTree,
+    ) -> Result<(), std::io::Error> {
+        let layout_path = state_tree.get(layout).as_generic().get_path().clone();
+        let invalid = |line: &str| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("Invalid dock arrangement line: {}", line),
+            )
+        };
+        // Parse everything first, so an invalid arrangement is not applied halfway
+        let mut children = Vec::new();
+        for line in arrangement.lines().filter(|x| !x.trim().is_empty()) {
+            let parts: Vec<&str> = line.trim().split(':').collect();
+            if parts.len() != 7 {
+                return Err(invalid(line));
+            }
+            let dock = parse_dock_position_property(parts[1]).map_err(|_| invalid(line))?;
+            let values = parts[3..]
+                .iter()
+                .map(|x| x.parse::<usize>().map_err(|_| invalid(line)))
+                .collect::<Result<Vec<usize>, std::io::Error>>()?;
+            children.push((
+                format!("{}/{}", layout_path, parts[0]),
+                dock,
+                parts[2] == "true",
+                values,
+            ));
+        }
+        for (child_path, dock, collapsed, values) in children {
+            if !state_tree.contains(&child_path) {
+                continue;
+            }
+            let state = state_tree.get_mut(&child_path).as_generic_mut();
+            state.set_dock(dock);
+            state.set_collapsed(collapsed);
+            if dock == DockPosition::Left || dock == DockPosition::Right {
+                state.set_size_hint_x(None);
+                state.set_width(values[0]);
+            } else if dock == DockPosition::Top || dock == DockPosition::Bottom {
+                state.set_size_hint_y(None);
+                state.set_height(values[1]);
+            } else if dock == DockPosition::Float {
+                state.set_size_hint_x(None);
+                state.set_size_hint_y(None);
+                state.set_pos_hint_x(None);
+                state.set_pos_hint_y(None);
+                state.set_width(values[0]);
+                state.set_height(values[1]);
+                state.set_x(values[2]);
+                state.set_y(values[3]);
+            }
+            state.update(self);
+        }
+        self.update_widget(&layout_path);
+        Ok(())
+    }
+
+    /// Add a widget to a group. Groups are sets of related widgets (e.g. all panels of a
+    /// dashboard) that can be updated, restyled or disabled at once without listing their paths.
+    /// A widget can be in any number of groups. In an .ez file use the "group" property instead,
+    /// e.g. "group: stats" or "group: stats, live". Groups changed from a thread are not synced
+    /// back.
+    ///
+    /// # Parameters:
+    ///
+    /// - Widget path: &str (full path, not an ID)
+    /// - Group name: &str
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use ez_term::*;
+    ///
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// scheduler.add_to_group("/root/layout/cpu_label", "stats");
+    /// scheduler.add_to_group("/root/layout/memory_label", "stats");
+    ///
+    /// run(root_widget, state_tree, scheduler, custom_data);
+    /// ```
+    pub fn add_to_group(&mut self, widget_path: &str, group: &str) {
+        let members = self.backend.groups.entry(group.to_string()).or_default();
+        if !members.iter().any(|x| x == widget_path) {
+            members.push(widget_path.to_string());
+        }
+    }
+
+    /// Remove a widget from a group. Widgets are removed from their groups automatically when
+    /// the widget is removed.
+    ///
+    /// # Parameters:
+    ///
+    /// - Widget path: &str (full path, not an ID)
+    /// - Group name: &str
+    pub fn remove_from_group(&mut self, widget_path: &str, group: &str) {
+        if let Some(members) = self.backend.groups.get_mut(group) {
+            members.retain(|x| x != widget_path);
+        }
+    }
+
+    /// Get the full paths of the widgets in a group, in the order they were added. Returns an
+    /// empty list for a group without widgets.
+    ///
+    /// # Parameters:
+    ///
+    /// - Group name: &str
+    pub fn get_group(&self, group: &str) -> Vec<String> {
+        self.backend.groups.get(group).cloned().unwrap_or_default()
+    }
+
+    /// Schedule all widgets in a group to be redrawn on the next frame, e.g. after changing
+    /// custom data they display.
+    ///
+    /// # Parameters:
+    ///
+    /// - Group name: &str
+    pub fn update_group(&mut self, group: &str) {
+        for path in self.get_group(group) {
+            self.update_widget(&path);
+        }
+    }
+
+    /// Set a property on all widgets in a group and redraw them. The property name is the name
+    /// used in .ez files for the underlying value (e.g. "fg_color", "text", "border"), and the
+    /// value must be of the type of that property. Panics if a widget in the group does not have
+    /// the property.
+    ///
+    /// # Parameters:
+    ///
+    /// - Group name: &str
+    /// - Property name: &str
+    /// - Value: [EzValues]
+    /// - State tree: &mut StateTree
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use ez_term::*;
+    ///
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// // Show all stats in red
+    /// scheduler.set_group_property("stats", "fg_color", EzValues::Color(Color::Red),
+    ///                              &mut state_tree);
+    ///
+    /// run(root_widget, state_tree, scheduler, custom_data);
+    /// ```
+    pub fn set_group_property(
         &mut self,
+        group: &str,
         name: &str,
-        value: VerticalPosHint,
-    ) -> EzProperty<VerticalPosHint> {
-        let (property, receiver) = EzProperty::new(name.to_string(), value);
-        self.backend.properties.insert(
-            name.to_string(),
-            EzProperties::VerticalPosHint(property.clone()),
+        value: EzValues,
+        state_tree: &mut StateTree,
+    ) {
+        for path in self.get_group(group) {
+            if let Some(node) = state_tree.try_get_mut(&path) {
+                let state = node.obj.as_generic_mut();
+                state.update_property(name, value.clone());
+                state.update(self);
+            }
+        }
+    }
+
+    /// Disable all widgets in a group, so they cannot be interacted with. See [enable_group].
+    ///
+    /// # Parameters:
+    ///
+    /// - Group name: &str
+    /// - State tree: &mut StateTree
+    pub fn disable_group(&mut self, group: &str, state_tree: &mut StateTree) {
+        self.set_group_disabled(group, true, state_tree);
+    }
+
+    /// Enable all widgets in a group that were disabled, e.g. with [disable_group].
+    ///
+    /// # Parameters:
+    ///
+    /// - Group name: &str
+    /// - State tree: &mut StateTree
+    pub fn enable_group(&mut self, group: &str, state_tree: &mut StateTree) {
+        self.set_group_disabled(group, false, state_tree);
+    }
+
+    fn set_group_disabled(&mut self, group: &str, disabled: bool, state_tree: &mut StateTree) {
+        for path in self.get_group(group) {
+            if let Some(node) = state_tree.try_get_mut(&path) {
+                let state = node.obj.as_generic_mut();
+                state.set_disabled(disabled);
+                state.update(self);
+            }
+        }
+    }
+
+    /// Draw a child of a float layout on top of all its' siblings, e.g. a panel that was clicked
+    /// or is being dragged. Its' z index is raised above the highest z index of its' siblings.
+    /// See [send_to_back].
+    ///
+    /// # Parameters:
+    ///
+    /// - Widget: &str
+    /// - State tree: &mut StateTree
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use ez_term::*;
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// scheduler.bring_to_front("my_panel", &mut state_tree);
+    /// ```
+    pub fn bring_to_front(&mut self, widget: &str, state_tree: &mut StateTree) {
+        let path = state_tree.get(widget).as_generic().get_path().clone();
+        let highest = get_sibling_z_indices(&path, state_tree).into_iter().max();
+        let state = state_tree.get_mut(&path).as_generic_mut();
+        if let Some(highest) = highest {
+            if state.get_z_index() <= highest {
+                state.set_z_index(highest + 1);
+            }
+        }
+        state.update(self);
+    }
+
+    /// Draw a child of a float layout below all its' siblings. Its' z index is set to 0, and
+    /// siblings are raised if needed to stay above it. See [bring_to_front].
+    ///
+    /// # Parameters:
+    ///
+    /// - Widget: &str
+    /// - State tree: &mut StateTree
+    pub fn send_to_back(&mut self, widget: &str, state_tree: &mut StateTree) {
+        let path = state_tree.get(widget).as_generic().get_path().clone();
+        let lowest = get_sibling_z_indices(&path, state_tree).into_iter().min();
+        let state = state_tree.get_mut(&path).as_generic_mut();
+        match lowest {
+            Some(lowest) if lowest > 0 => state.set_z_index(lowest - 1),
+            Some(_) => {
+                state.set_z_index(0);
+                let parent = path.rsplit_once('/').unwrap().0;
+                for sibling in state_tree.get_mut(parent).get_children_mut() {
+                    let sibling = sibling.as_generic_mut();
+                    if sibling.get_path() != &path {
+                        sibling.set_z_index(sibling.get_z_index() + 1);
+                    }
+                }
+            }
+            None => state.set_z_index(0),
+        }
+        state_tree.get(&path).as_generic().update(self);
+    }
+
+    /// Register an [Action] under a name, so widgets can bind to it (see [bind_action]) and it can
+    /// be enabled and disabled (see [set_action_enabled]). If the action has a key, the key runs
+    /// it from anywhere in the app while it is enabled. Registering an action with an existing
+    /// name replaces it, but keeps whether it is enabled. New actions are enabled. Must be called
+    /// from the main thread (e.g. before running the UI or from a callback).
+    ///
+    /// # Parameters:
+    ///
+    /// - Name: &str
+    /// - Action: [Action]
+    ///
+    /// # Example:
+    ///
+    /// We'll register a save action, bind it to a button and disable it until there is something
+    /// to save:
+    /// ```
+    /// use ez_term::*;
+    ///
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// let save = |context: Context| {
+    ///     // Save the document, then wait for the next change
+    ///     context.scheduler.set_action_enabled("save", false);
+    ///     true
+    /// };
+    /// scheduler.register_action("save", Action::new_with_key(
+    ///     "Save", KeyCode::Char('s'), Some(vec![KeyModifiers::CONTROL]), Box::new(save)));
+    /// scheduler.set_action_enabled("save", false);
+    /// scheduler.bind_action("/root/toolbar/save_button", "save");
+    ///
+    /// run(root_widget, state_tree, scheduler, custom_data);
+    /// ```
+    /// The button can also be bound in the .ez file:
+    /// ```
+    /// - Button:
+    ///     id: save_button
+    ///     action: save
+    /// ```
+    pub fn register_action(&mut self, name: &str, action: Action) {
+        let property_name = get_action_property_name(name);
+        if !self.backend.properties.contains_key(&property_name) {
+            self.new_bool_property(&property_name, true);
+        }
+        if let Some((key, modifiers)) = action.key {
+            let modifiers = [KeyModifiers::CONTROL, KeyModifiers::ALT, KeyModifiers::SHIFT]
+                .into_iter()
+                .filter(|x| modifiers.contains(*x))
+                .collect();
+            let name = name.to_string();
+            self.bind_global_key(
+                key,
+                Some(modifiers),
+                Box::new(move |context: Context, _keycode: KeyCode, _modifiers: KeyModifiers| {
+                    run_action(&name, context)
+                }),
+            );
+        }
+        self.backend.actions.insert(name.to_string(), action);
+        self.backend.actions_to_refresh.push(name.to_string());
+    }
+
+    /// Get a registered [Action], e.g. to show its' label and key hint in a help screen.
+    ///
+    /// # Parameters:
+    ///
+    /// - Name: &str
+    pub fn get_action(&self, name: &str) -> Option<&Action> {
+        self.backend.actions.get(name)
+    }
+
+    /// Bind a widget to an action: the widget runs the action when pressed and is disabled while
+    /// the action is. Buttons and labels show the label and key hint of the action as their text.
+    /// A widget can be bound before its' action is registered; it is updated once the action is.
+    /// In an .ez file use the "action" property instead, e.g. "action: save".
+    ///
+    /// # Parameters:
+    ///
+    /// - Widget path: &str (full path, not an ID)
+    /// - Action name: &str
+    pub fn bind_action(&mut self, widget_path: &str, name: &str) {
+        let widgets = self
+            .backend
+            .action_widgets
+            .entry(name.to_string())
+            .or_default();
+        if !widgets.iter().any(|x| x == widget_path) {
+            widgets.push(widget_path.to_string());
+        }
+        let action_name = name.to_string();
+        self.update_callback_config(
+            widget_path,
+            CallbackConfig::from_on_press(Box::new(move |context: Context| {
+                run_action(&action_name, context)
+            })),
         );
+        self.backend.actions_to_refresh.push(name.to_string());
+    }
+
+    /// Get the full paths of the widgets bound to an action, in the order they were bound.
+    ///
+    /// # Parameters:
+    ///
+    /// - Action name: &str
+    pub fn get_action_widgets(&self, name: &str) -> Vec<String> {
         self.backend
-            .property_receivers
-            .insert(name.to_string(), receiver);
-        self.get_update_func(name);
-        property
+            .action_widgets
+            .get(name)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Enable or disable an action. While an action is disabled its' key does nothing and the
+    /// widgets bound to it are disabled; they are updated on the next frame. This sets the custom
+    /// bool property of the action (see [get_action_property_name]), so it can also be done from
+    /// a thread by setting that property.
+    ///
+    /// # Parameters:
+    ///
+    /// - Action name: &str
+    /// - Enabled: bool
+    pub fn set_action_enabled(&mut self, name: &str, enabled: bool) {
+        let property_name = get_action_property_name(name);
+        if !self.backend.properties.contains_key(&property_name) {
+            self.new_bool_property(&property_name, enabled);
+        }
+        let property = self
+            .backend
+            .properties
+            .get_mut(&property_name)
+            .unwrap()
+            .as_bool_mut();
+        if property.value != enabled {
+            property.set(enabled);
+        }
+    }
+
+    /// Returns a bool representing whether an action is registered and enabled.
+    ///
+    /// # Parameters:
+    ///
+    /// - Action name: &str
+    pub fn is_action_enabled(&self, name: &str) -> bool {
+        self.backend.actions.contains_key(name)
+            && self
+                .backend
+                .properties
+                .get(&get_action_property_name(name))
+                .map_or(true, |x| x.as_bool().value)
+    }
+
+    /// Track whether the input widgets of a form (e.g. a settings layout) differ from their
+    /// current values. Every input widget in the form, and the form itself, gets a custom bool
+    /// property named "<full path>/is_dirty" that is true while the value differs. Bind a
+    /// callback to the property of the form to e.g. enable a "Save" button. In an .ez file set
+    /// "track_dirty: true" on the layout instead; it is tracked from the values in the file.
+    /// Call [mark_clean] after saving.
+    ///
+    /// # Parameters:
+    ///
+    /// - ID or path of the form (a layout or a single widget): &str
+    /// - State tree: &StateTree
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use ez_term::*;
+    ///
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// scheduler.track_dirty("settings_layout", &state_tree);
+    /// let form = state_tree.get("settings_layout").as_generic().get_path().clone();
+    /// scheduler.bind_property_callback(
+    ///     &format!("{}/is_dirty", form),
+    ///     Box::new(|context: Context| {
+    ///         let dirty = context.scheduler.is_dirty("settings_layout", context.state_tree);
+    ///         let state = context.state_tree.get_mut("save_button").as_button_mut();
+    ///         state.set_disabled(!dirty);
+    ///         state.update(context.scheduler);
+    ///         true
+    ///     }),
+    /// );
+    ///
+    /// run(root_widget, state_tree, scheduler, custom_data);
+    /// ```
+    pub fn track_dirty(&mut self, form: &str, state_tree: &StateTree) {
+        start_dirty_tracking(form, self, state_tree);
+    }
+
+    /// Use the current values of a tracked form as its' initial values, so it is no longer
+    /// dirty. Call this after saving the form. See [track_dirty].
+    ///
+    /// # Parameters:
+    ///
+    /// - ID or path of the form: &str
+    /// - State tree: &StateTree
+    pub fn mark_clean(&mut self, form: &str, state_tree: &StateTree) {
+        let path = state_tree.get(form).as_generic().get_path().clone();
+        if !self.backend.dirty_forms.contains_key(&path) {
+            panic!("Cannot mark form \"{}\" clean, it is not tracked. Use track_dirty first.", form)
+        }
+        start_dirty_tracking(&path, self, state_tree);
+    }
+
+    /// Returns a bool representing whether a tracked form, or a widget in a tracked form, differs
+    /// from its' initial values. Returns false for widgets that are not tracked. See
+    /// [track_dirty].
+    ///
+    /// # Parameters:
+    ///
+    /// - ID or path of the form or widget: &str
+    /// - State tree: &StateTree
+    pub fn is_dirty(&self, widget: &str, state_tree: &StateTree) -> bool {
+        let path = state_tree.get(widget).as_generic().get_path().clone();
+        self.backend
+            .properties
+            .get(&get_dirty_property_name(&path))
+            .map_or(false, |x| x.as_bool().value)
+    }
+
+    /// Used internally when a widget moves to a different path. Points any bindings to and from
+    /// the properties of the widget at the old path to the properties of the widget at the new
+    /// path. Call before the properties at the old path are cleaned up.
+    pub fn _move_property_bindings(&mut self, old_path: &str, new_path: &str) {
+        let rename = |name: &str| -> Option<String> {
+            let (widget, property) = name.rsplit_once('/')?;
+            if widget == old_path {
+                Some(format!("{}/{}", new_path, property))
+            } else {
+                None
+            }
+        };
+
+        // Other properties bound to properties of the moved widget
+        let moved_keys: Vec<String> = self
+            .backend
+            .property_subscribers
+            .keys()
+            .filter(|x| rename(x).is_some())
+            .cloned()
+            .collect();
+        for key in moved_keys {
+            let subscribers = self.backend.property_subscribers.remove(&key).unwrap();
+            let new_name = rename(&key).unwrap();
+            self.backend
+                .property_subscribers
+                .insert(new_name.clone(), subscribers);
+            // The properties at the new path were registered before they had subscribers, so
+            // they have no channel yet
+            self._create_property_channel(&new_name);
+        }
+
+        // Properties of the moved widget bound to other properties
+        let mut new_subscribers = Vec::new();
+        for subscribers in self.backend.property_subscribers.values_mut() {
+            for subscriber in subscribers.iter_mut() {
+                if let Some(new_name) = rename(subscriber) {
+                    self.backend.property_updaters.remove(subscriber.as_str());
+                    new_subscribers.push(new_name.clone());
+                    *subscriber = new_name;
+                }
+            }
+        }
+        for name in new_subscribers {
+            self._get_update_func(&name);
+        }
+
+        let mut new_callbacks = Vec::new();
+        for name in self.backend.property_callbacks.iter_mut() {
+            if let Some(new_name) = rename(name) {
+                new_callbacks.push(new_name.clone());
+                *name = new_name;
+            }
+        }
+        for name in new_callbacks {
+            self._create_property_channel(&name);
+        }
+        self.backend.widgets_to_update.retain(|x| x != old_path);
+    }
+
+    /// Register a newly created property. Its' channel is only created right away if other
+    /// properties are already subscribed to it; otherwise that happens once it is bound.
+    fn add_property(&mut self, name: &str, property: EzProperties) {
+        self.backend.properties.insert(name.to_string(), property);
+        // A property replacing one with the same name (e.g. of a widget that is created again)
+        // needs its' own channel
+        self.backend.property_receivers.remove(name);
+        if self.backend.property_subscribers.contains_key(name) {
+            self._create_property_channel(name);
+        }
+    }
+
+    /// Used internally to create the channel of a property, so its' new values can be received
+    /// and passed on to subscribers and callbacks. Returns whether the property has a channel,
+    /// which is false if the property does not exist (yet).
+    pub fn _create_property_channel(&mut self, name: &str) -> bool {
+        if self.backend.property_receivers.contains_key(name) {
+            return true;
+        }
+        let receiver = match self.backend.properties.get(name) {
+            Some(property) => property.create_channel(),
+            None => return false,
+        };
+        if let Some(receiver) = receiver {
+            self.backend
+                .property_receivers
+                .insert(name.to_string(), receiver);
+        }
+        true
+    }
+
+    /// Used internally to create the function that sets a new value on a widget property when a
+    /// property it is subscribed to changes. Does nothing if the updater already exists or if the
+    /// property is not a widget property.
+    pub fn _get_update_func(&mut self, name: &str) {
+        if name.contains('/') {
+            let (widget, property_name) = name.rsplit_once('/').unwrap();
+            let (widget, property_name) = (widget.to_string(), property_name.to_string());
+            let updater = move |state_tree: &mut StateTree, val: EzValues| {
+                state_tree
+                    .get_mut(&widget)
+                    .as_generic_mut()
+                    .update_property(&property_name, val);
+            };
+            if !self.backend.property_updaters.contains_key(name) {
+                self.backend
+                    .property_updaters
+                    .insert(name.to_string(), Box::new(updater));
+            }
+        }
     }
+
     /// Create a custom property. You can bind this property to widget properties of the same type;
     /// then when you update the custom property, the widget will update automatically as well.
     /// The name of custom properties may not contain any '/'.
     /// For a tutorial on this see: [Creating custom properties](#scheduler_properties).
     ///
-    /// ## Parameters:
+    /// ## Parameters:
+    ///
+    /// - Name of the new property: &str
+    /// - Value of the new property: usize
+    ///
+    /// ## Example:
+    ///
+    /// We'll create a custom property and bind it to a widget in an .ez file.
+    /// First the code:
+    /// ```
+    /// use ez_term::*;
+    ///
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// scheduler.new_usize_property("my_property", 1);
+    ///
+    /// run(root_widget, state_tree, scheduler, custom_data);
+    /// ```
+    ///
+    /// Now the .ez file:
+    ///
+    /// ```
+    /// - Layout:
+    ///     - ProgressBar:
+    ///         value: properties.my_property
+    /// ```
+    pub fn new_usize_property(&mut self, name: &str, value: usize) -> EzProperty<usize> {
+        let property = EzProperty::new_lazy(name.to_string(), value);
+        self.add_property(name, EzProperties::Usize(property.clone()));
+        property
+    }
+
+    /// Create a custom property. You can bind this property to widget properties of the same type;
+    /// then when you update the custom property, the widget will update automatically as well.
+    /// The name of custom properties may not contain any '/'.
+    /// For a tutorial on this see: [Creating custom properties](#scheduler_properties).
+    ///
+    /// ## Parameters:
+    ///
+    /// - Name of the new property: &str
+    /// - Value of the new property: f64
+    ///
+    /// ## Example:
+    ///
+    /// We'll create a custom property and bind it to a widget in an .ez file.
+    /// First the code:
+    /// ```
+    /// use ez_term::*;
+    ///
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// scheduler.new_f64_property("my_property", 0.5);
+    ///
+    /// run(root_widget, state_tree, scheduler, custom_data);
+    /// ```
+    /// Now the .ez file:
+    /// ```
+    /// - Layout:
+    ///     scroll_y: true
+    ///     scroll_start_y: properties.my_property
+    /// ```
+    pub fn new_f64_property(&mut self, name: &str, value: f64) -> EzProperty<f64> {
+        let property = EzProperty::new_lazy(name.to_string(), value);
+        self.add_property(name, EzProperties::F64(property.clone()));
+        property
+    }
+
+    /// Create a custom property. You can bind this property to widget properties of the same type;
+    /// then when you update the custom property, the widget will update automatically as well.
+    /// The name of custom properties may not contain any '/'.
+    /// For a tutorial on this see: [Creating custom properties](#scheduler_properties).
+    ///
+    /// ## Parameters:
+    ///
+    /// - Name of the new property: &str
+    /// - Value of the new property: String
+    ///
+    /// ## Example:
+    ///
+    /// We'll create a custom property and bind it to a widget in an .ez file.
+    /// First the code:
+    /// ```
+    /// use ez_term::*;
+    ///
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// scheduler.new_string_property("my_property", "Hello world!".to_string());
+    ///
+    /// run(root_widget, state_tree, scheduler, custom_data);
+    /// ```
+    /// Now the .ez file:
+    /// ```
+    /// - Layout:
+    ///     - Label:
+    ///         text: properties.my_property
+    /// ```
+    pub fn new_string_property(&mut self, name: &str, value: String) -> EzProperty<String> {
+        let property = EzProperty::new_lazy(name.to_string(), value);
+        self.add_property(name, EzProperties::String(property.clone()));
+        property
+    }
+
+    /// Create a custom property. You can bind this property to widget properties of the same type;
+    /// then when you update the custom property, the widget will update automatically as well.
+    /// The name of custom properties may not contain any '/'.
+    /// For a tutorial on this see: [Creating custom properties](#scheduler_properties).
+    ///
+    /// ## Parameters:
+    ///
+    /// - Name of the new property: &str
+    /// - Value of the new property: bool
+    ///
+    /// ## Example:
+    ///
+    /// We'll create a custom property and bind it to a widget in an .ez file.
+    /// First the code:
+    /// ```
+    /// use ez_term::*;
+    ///
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// scheduler.new_bool_property("my_property", true);
+    ///
+    /// run(root_widget, state_tree, scheduler, custom_data);
+    /// ```
+    /// Now the .ez file:
+    /// ```
+    /// - Layout:
+    ///     - Button:
+    ///         disabled: properties.my_property
+    /// ```
+    pub fn new_bool_property(&mut self, name: &str, value: bool) -> EzProperty<bool> {
+        let property = EzProperty::new_lazy(name.to_string(), value);
+        self.add_property(name, EzProperties::Bool(property.clone()));
+        property
+    }
+
+    /// Create a custom property. You can bind this property to widget properties of the same type;
+    /// then when you update the custom property, the widget will update automatically as well.
+    /// The name of custom properties may not contain any '/'.
+    /// For a tutorial on this see: [Creating custom properties](#scheduler_properties).
+    ///
+    /// ## Parameters:
+    ///
+    /// - Name of the new property: &str
+    /// - Value of the new property: Color
+    ///
+    /// ## Example:
+    ///
+    /// We'll create a custom property and bind it to a widget in an .ez file.
+    /// First the code:
+    /// ```
+    /// use ez_term::*;
+    ///
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// scheduler.new_color_property("my_property", Color::Yellow);
+    ///
+    /// run(root_widget, state_tree, scheduler, custom_data);
+    /// ```
+    /// Now the .ez file:
+    /// ```
+    /// - Layout:
+    ///     - Label:
+    ///         fg_color: properties.my_property
+    /// ```
+    pub fn new_color_property(&mut self, name: &str, value: Color) -> EzProperty<Color> {
+        let property = EzProperty::new_lazy(name.to_string(), value);
+        self.add_property(name, EzProperties::Color(property.clone()));
+        property
+    }
+
+    /// Create a custom property. You can bind this property to widget properties of the same type;
+    /// then when you update the custom property, the widget will update automatically as well.
+    /// The name of custom properties may not contain any '/'.
+    /// For a tutorial on this see: [Creating custom properties](#scheduler_properties).
+    ///
+    /// ## Parameters:
+    ///
+    /// - Name of the new property: &str
+    /// - Value of the new property: LayoutMode
+    ///
+    /// ## Example:
+    ///
+    /// We'll create a custom property and bind it to a widget in an .ez file.
+    /// First the code:
+    /// ```
+    /// use ez_term::*;
+    ///
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// scheduler.new_layout_mode_property("my_property", LayoutMode::Table);
+    ///
+    /// run(root_widget, state_tree, scheduler, custom_data);
+    /// ```
+    /// Now the .ez file:
+    /// ```
+    /// - Layout:
+    ///     mode: properties.my_property
+    /// ```
+    pub fn new_layout_mode_property(
+        &mut self,
+        name: &str,
+        value: LayoutMode,
+    ) -> EzProperty<LayoutMode> {
+        let property = EzProperty::new_lazy(name.to_string(), value);
+        self.add_property(name, EzProperties::LayoutMode(property.clone()));
+        property
+    }
+
+    /// Create a custom property. You can bind this property to widget properties of the same type;
+    /// then when you update the custom property, the widget will update automatically as well.
+    /// The name of custom properties may not contain any '/'.
+    /// For a tutorial on this see: [Creating custom properties](#scheduler_properties).
+    ///
+    /// ## Parameters:
+    ///
+    /// - Name of the new property: &str
+    /// - Value of the new property: LayoutOrientation
+    ///
+    /// ## Example:
+    ///
+    /// We'll create a custom property and bind it to a widget in an .ez file.
+    /// First the code:
+    /// ```
+    /// use ez_term::*;
+    ///
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// scheduler.new_layout_orientation_property("my_property", LayoutOrientation::Vertical);
+    ///
+    /// run(root_widget, state_tree, scheduler, custom_data);
+    /// ```
+    /// Now the .ez file:
+    /// ```
+    /// - Layout:
+    ///     orientation: properties.my_property
+    /// ```
+    pub fn new_layout_orientation_property(
+        &mut self,
+        name: &str,
+        value: LayoutOrientation,
+    ) -> EzProperty<LayoutOrientation> {
+        let property = EzProperty::new_lazy(name.to_string(), value);
+        self.add_property(name, EzProperties::LayoutOrientation(property.clone()));
+        property
+    }
+
+    /// Create a custom property. You can bind this property to widget properties of the same type;
+    /// then when you update the custom property, the widget will update automatically as well.
+    /// The name of custom properties may not contain any '/'.
+    /// For a tutorial on this see: [Creating custom properties](#scheduler_properties).
+    ///
+    /// ## Parameters:
+    ///
+    /// - Name of the new property: &str
+    /// - Value of the new property: DockPosition
+    ///
+    /// ## Example:
+    ///
+    /// We'll create a custom property and bind it to a widget in an .ez file.
+    /// First the code:
+    /// ```
+    /// use ez_term::*;
+    ///
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// scheduler.new_dock_position_property("my_property", DockPosition::Left);
+    ///
+    /// run(root_widget, state_tree, scheduler, custom_data);
+    /// ```
+    /// Now the .ez file:
+    /// ```
+    /// - Layout:
+    ///     mode: dock
+    ///     - Layout:
+    ///         dock: properties.my_property
+    /// ```
+    pub fn new_dock_position_property(
+        &mut self,
+        name: &str,
+        value: DockPosition,
+    ) -> EzProperty<DockPosition> {
+        let property = EzProperty::new_lazy(name.to_string(), value);
+        self.add_property(name, EzProperties::DockPosition(property.clone()));
+        property
+    }
+
+    /// Create a custom property. You can bind this property to widget properties of the same type;
+    /// then when you update the custom property, the widget will update automatically as well.
+    /// The name of custom properties may not contain any '/'.
+    /// For a tutorial on this see: [Creating custom properties](#scheduler_properties).
+    ///
+    /// ## Parameters:
+    ///
+    /// - Name of the new property: &str
+    /// - Value of the new property: HorizontalAlignment
+    ///
+    /// ## Example:
+    ///
+    /// We'll create a custom property and bind it to a widget in an .ez file.
+    /// First the code:
+    /// ```
+    /// use ez_term::*;
+    ///
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// scheduler.new_horizontal_alignment_property("my_property", HorizontalAlignment::Center);
+    ///
+    /// run(root_widget, state_tree, scheduler, custom_data);
+    /// ```
+    /// Now the .ez file:
+    /// ```
+    /// - Layout:
+    ///     - Label:
+    ///         halign: properties.my_property
+    /// ```
+    pub fn new_vertical_alignment_property(
+        &mut self,
+        name: &str,
+        value: VerticalAlignment,
+    ) -> EzProperty<VerticalAlignment> {
+        let property = EzProperty::new_lazy(name.to_string(), value);
+        self.add_property(name, EzProperties::VerticalAlignment(property.clone()));
+        property
+    }
+
+    /// Create a custom property. You can bind this property to widget properties of the same type;
+    /// then when you update the custom property, the widget will update automatically as well.
+    /// The name of custom properties may not contain any '/'.
+    /// For a tutorial on this see: [Creating custom properties](#scheduler_properties).
+    ///
+    /// ## Parameters:
+    ///
+    /// - Name of the new property: &str
+    /// - Value of the new property: VerticalAlignment
+    ///
+    /// ## Example:
+    ///
+    /// We'll create a custom property and bind it to a widget in an .ez file.
+    /// First the code:
+    /// ```
+    /// use ez_term::*;
+    ///
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// scheduler.new_vertical_alignment_property("my_property", VerticalAlignment::Middle);
+    ///
+    /// run(root_widget, state_tree, scheduler, custom_data);
+    /// ```
+    /// Now the .ez file:
+    /// ```
+    /// - Layout:
+    ///     - Label:
+    ///         valign: properties.my_property
+    /// ```
+    pub fn new_horizontal_alignment_property(
+        &mut self,
+        name: &str,
+        value: HorizontalAlignment,
+    ) -> EzProperty<HorizontalAlignment> {
+        let property = EzProperty::new_lazy(name.to_string(), value);
+        self.add_property(name, EzProperties::HorizontalAlignment(property.clone()));
+        property
+    }
+
+    /// Create a custom property. You can bind this property to widget properties of the same type;
+    /// then when you update the custom property, the widget will update automatically as well.
+    /// The name of custom properties may not contain any '/'.
+    /// For a tutorial on this see: [Creating custom properties](#scheduler_properties).
+    ///
+    /// ## Parameters:
+    ///
+    /// - Name of the new property: &str
+    /// - Value of the new property: HorizontalPosHint
+    ///
+    /// ## Example:
+    ///
+    /// We'll create a custom property and bind it to a widget in an .ez file.
+    /// First the code:
+    /// ```
+    /// use ez_term::*;
+    ///
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// scheduler.new_horizontal_pos_hint_property("my_property",
+    ///                                            Some((HorizontalAlignment::Right, 0.75)));
+    ///
+    /// run(root_widget, state_tree, scheduler, custom_data);
+    /// ```
+    /// Now the .ez file:
+    /// ```
+    /// - Layout:
+    ///     mode: float
+    ///     - Label:
+    ///         pos_hint_x: properties.my_property
+    /// ```
+    pub fn new_horizontal_pos_hint_property(
+        &mut self,
+        name: &str,
+        value: HorizontalPosHint,
+    ) -> EzProperty<HorizontalPosHint> {
+        let property = EzProperty::new_lazy(name.to_string(), value);
+        self.add_property(name, EzProperties::HorizontalPosHint(property.clone()));
+        property
+    }
+
+    /// Create a custom property. You can bind this property to widget properties of the same type;
+    /// then when you update the custom property, the widget will update automatically as well.
+    /// The name of custom properties may not contain any '/'.
+    /// For a tutorial on this see: [Creating custom properties](#scheduler_properties).
+    ///
+    /// ## Parameters:
+    ///
+    /// - Name of the new property: &str
+    /// - Value of the new property: VerticalPosHint
+    ///
+    /// ## Example:
+    ///
+    /// We'll create a custom property and bind it to a widget in an .ez file.
+    /// First the code:
+    /// ```
+    /// use ez_term::*;
+    ///
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// scheduler.new_vertical_pos_hint_property("my_property",
+    ///                                            Some((VerticalAlignment::Middle, 0.75)));
+    ///
+    /// run(root_widget, state_tree, scheduler, custom_data);
+    /// ```
+    /// Now the .ez file:
+    /// ```
+    /// - Layout:
+    ///     mode: float
+    ///     - Label:
+    ///         pos_hint_y: properties.my_property
+    /// ```
+    pub fn new_vertical_pos_hint_property(
+        &mut self,
+        name: &str,
+        value: VerticalPosHint,
+    ) -> EzProperty<VerticalPosHint> {
+        let property = EzProperty::new_lazy(name.to_string(), value);
+        self.add_property(name, EzProperties::VerticalPosHint(property.clone()));
+        property
+    }
+    /// Create a custom property. You can bind this property to widget properties of the same type;
+    /// then when you update the custom property, the widget will update automatically as well.
+    /// The name of custom properties may not contain any '/'.
+    /// For a tutorial on this see: [Creating custom properties](#scheduler_properties).
+    ///
+    /// ## Parameters:
+    ///
+    /// - Name of the new property: &str
+    /// - Value of the new property: SizeHint
+    ///
+    /// ## Example:
+    ///
+    /// We'll create a custom property and bind it to a widget in an .ez file.
+    /// First the code:
+    /// ```
+    /// use ez_term::*;
+    ///
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// scheduler.new_size_hint_property("my_property", Some(0.75));
+    ///
+    /// run(root_widget, state_tree, scheduler, custom_data);
+    /// ```
+    /// Now the .ez file:
+    /// ```
+    /// - Layout:
+    ///     mode: float
+    ///     - Label:
+    ///         size_hint_x: properties.my_property
+    /// ```
+    pub fn new_size_hint_property(
+        &mut self,
+        name: &str,
+        value: Option<f64>,
+    ) -> EzProperty<Option<f64>> {
+        let property = EzProperty::new_lazy(name.to_string(), value);
+        self.add_property(name, EzProperties::SizeHint(property.clone()));
+        property
+    }
+
+    /// Create a custom property containing a list of strings. Widget properties are never lists,
+    /// so this property cannot be bound to widgets; use it from code, e.g. with
+    /// [new_checkbox_group_property].
+    /// The name of custom properties may not contain any '/'.
+    ///
+    /// ## Parameters:
+    ///
+    /// - Name of the new property: &str
+    /// - Value of the new property: Vec<String>
+    ///
+    /// ## Example:
+    ///
+    /// ```
+    /// use ez_term::*;
+    ///
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// scheduler.new_string_list_property("my_property", vec!["a".to_string(), "b".to_string()]);
+    ///
+    /// run(root_widget, state_tree, scheduler, custom_data);
+    /// ```
+    pub fn new_string_list_property(
+        &mut self,
+        name: &str,
+        value: Vec<String>,
+    ) -> EzProperty<Vec<String>> {
+        let property = EzProperty::new_lazy(name.to_string(), value);
+        self.add_property(name, EzProperties::StringList(property.clone()));
+        property
+    }
+
+    /// Create a custom property containing the IDs of the checked checkboxes of a group, in the
+    /// order the checkboxes were passed. The property updates automatically when a checkbox is
+    /// toggled, so a "select many options" form only needs to read (or bind a callback to) this
+    /// one property. Setting the property from code checks exactly the checkboxes in the new
+    /// value. Panics if one of the checkboxes does not exist or is not a checkbox.
+    ///
+    /// ## Parameters:
+    ///
+    /// - Name of the new property: &str
+    /// - IDs or paths of the checkboxes in the group: &[&str]
+    /// - State tree: &StateTree
+    ///
+    /// ## Example:
+    ///
+    /// ```
+    /// use ez_term::*;
+    ///
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// scheduler.new_checkbox_group_property(
+    ///     "toppings",
+    ///     &["cheese_checkbox", "ham_checkbox", "pineapple_checkbox"],
+    ///     &state_tree,
+    /// );
+    ///
+    /// fn print_toppings(context: Context) -> bool {
+    ///     let toppings = context.scheduler.get_property("toppings").as_string_list().get();
+    ///     let state = context.state_tree.get_mut("toppings_label").as_label_mut();
+    ///     state.set_text(toppings.join(", "));
+    ///     true
+    /// }
+    /// scheduler.bind_property_callback("toppings", Box::new(print_toppings));
+    ///
+    /// run(root_widget, state_tree, scheduler, custom_data);
+    /// ```
+    pub fn new_checkbox_group_property(
+        &mut self,
+        name: &str,
+        checkboxes: &[&str],
+        state_tree: &StateTree,
+    ) -> EzProperty<Vec<String>> {
+        let checkboxes: Vec<(String, String)> = checkboxes
+            .iter()
+            .map(|x| {
+                let path = state_tree.get(x).as_checkbox().get_path().clone();
+                let id = path.rsplit_once('/').unwrap().1.to_string();
+                (id, path)
+            })
+            .collect();
+        let property =
+            self.new_string_list_property(name, get_checked_boxes(&checkboxes, state_tree));
+
+        for (_, path) in checkboxes.iter() {
+            let name = name.to_string();
+            let checkboxes = checkboxes.clone();
+            let update_group = move |context: Context| {
+                let checked = get_checked_boxes(&checkboxes, context.state_tree);
+                context
+                    .scheduler
+                    .get_property_mut(&name)
+                    .as_string_list_mut()
+                    .set(checked);
+                true
+            };
+            self.bind_property_callback(&format!("{}/active", path), Box::new(update_group));
+        }
+
+        let property_name = name.to_string();
+        let update_checkboxes = move |context: Context| {
+            let checked = context
+                .scheduler
+                .get_property(&property_name)
+                .as_string_list()
+                .value
+                .clone();
+            for (id, path) in checkboxes.iter() {
+                let state = context.state_tree.get_mut(path).as_checkbox_mut();
+                if state.get_active() != checked.contains(id) {
+                    state.set_active(checked.contains(id));
+                    context.scheduler.update_widget(path);
+                }
+            }
+            true
+        };
+        self.bind_property_callback(name, Box::new(update_checkboxes));
+        property
+    }
+
+    /// Create a custom property whose value is computed from other properties. Whenever one of the
+    /// dependencies changes the value is recomputed, in the same frame. Like any custom property
+    /// it can be bound to widget properties from an .ez file. The type of the property is the
+    /// type of the value returned by the compute function, which must always return the same type.
+    /// Dependencies can be custom properties (by name), other computed properties, or widget
+    /// properties (by full path, e.g. "/root/layout/my_label/width"). Panics if a dependency does
+    /// not exist yet or if the name is already used.
+    ///
+    /// ## Parameters:
+    ///
+    /// - Name of the new property: &str
+    /// - Names of the dependencies: Vec<&str>
+    /// - Compute function: Box<dyn FnMut(&[EzValues]) -> EzValues>; receives the values of the
+    ///   dependencies in the order they were passed
+    ///
+    /// ## Example:
+    ///
+    /// We'll show the total price of an order in a label. First the code:
+    /// ```
+    /// use ez_term::*;
+    ///
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// scheduler.new_usize_property("amount", 3);
+    /// scheduler.new_f64_property("price", 2.5);
+    /// let total = |values: &[EzValues]| {
+    ///     let total = values[0].as_usize() as f64 * values[1].as_f64();
+    ///     EzValues::String(format!("Total: {:.2}", total))
+    /// };
+    /// scheduler.new_computed_property("total", vec!["amount", "price"], Box::new(total));
+    ///
+    /// run(root_widget, state_tree, scheduler, custom_data);
+    /// ```
+    /// Now the .ez file:
+    /// ```
+    /// - Layout:
+    ///     - Label:
+    ///         text: properties.total
+    /// ```
+    pub fn new_computed_property(
+        &mut self,
+        name: &str,
+        dependencies: Vec<&str>,
+        mut compute: ComputedPropertyFunction,
+    ) -> EzProperties {
+        if self.backend.properties.contains_key(name) {
+            panic!("Cannot create computed property \"{}\", the name is already used", name);
+        }
+        // Dependencies must exist already, so computed properties can never depend on each other
+        // in a cycle
+        let dependencies: Vec<String> = dependencies.iter().map(|x| x.to_string()).collect();
+        let values: Vec<EzValues> = dependencies
+            .iter()
+            .map(|x| {
+                self.backend
+                    .properties
+                    .get(x)
+                    .unwrap_or_else(|| {
+                        panic!(
+                            "Computed property \"{}\" depends on \"{}\", which does not exist",
+                            name, x
+                        )
+                    })
+                    .get_generic_value()
+            })
+            .collect();
+        match compute(&values) {
+            EzValues::Usize(i) => {
+                self.new_usize_property(name, i);
+            }
+            EzValues::F64(i) => {
+                self.new_f64_property(name, i);
+            }
+            EzValues::Bool(i) => {
+                self.new_bool_property(name, i);
+            }
+            EzValues::String(i) => {
+                self.new_string_property(name, i);
+            }
+            EzValues::Color(i) => {
+                self.new_color_property(name, i);
+            }
+            EzValues::LayoutMode(i) => {
+                self.new_layout_mode_property(name, i);
+            }
+            EzValues::LayoutOrientation(i) => {
+                self.new_layout_orientation_property(name, i);
+            }
+            EzValues::DockPosition(i) => {
+                self.new_dock_position_property(name, i);
+            }
+            EzValues::HorizontalAlignment(i) => {
+                self.new_horizontal_alignment_property(name, i);
+            }
+            EzValues::VerticalAlignment(i) => {
+                self.new_vertical_alignment_property(name, i);
+            }
+            EzValues::SizeHint(i) => {
+                self.new_size_hint_property(name, i);
+            }
+            EzValues::VerticalPosHint(i) => {
+                self.new_vertical_pos_hint_property(name, i);
+            }
+            EzValues::HorizontalPosHint(i) => {
+                self.new_horizontal_pos_hint_property(name, i);
+            }
+            EzValues::StringList(i) => {
+                self.new_string_list_property(name, i);
+            }
+        }
+        self.backend.computed_properties.push(ComputedProperty {
+            name: name.to_string(),
+            dependencies,
+            values,
+            dirty: false,
+            compute,
+        });
+        self.get_property(name).clone()
+    }
+
+    pub fn new_data(
+        &mut self,
+        name: &str,
+        data: Box<dyn CustomData + Send>,
+    ) {
+        self.backend
+            .new_custom_data
+            .insert(name.to_string(), data);
+    }
+
+    /// Get a reference to a custom property.
+    ///
+    /// # Parameters:
+    ///
+    /// - Name of the custom property: &str
+    ///
+    /// # Example:
+    ///
+    /// We'll retrieve a custom property:
+    ///
+    /// ```
+    /// use ez_term::*;
+    ///
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// scheduler.new_usize_property("my_property", 10);
+    /// let property = scheduler.get_property("my_property");
+    ///
+    /// run(root_widget, state_tree, scheduler, custom_data);
+    /// ```
+    pub fn get_property(&self, name: &str) -> &EzProperties {
+        self.backend
+            .properties
+            .get(name)
+            .unwrap_or_else(|| panic!("Could not find property: {}", name))
+    }
+
+    /// Get a mutable reference to a  custom property created earlier.
+    ///
+    /// # Parameters:
+    ///
+    /// - Name of the custom property: &str
+    ///
+    /// # Example:
+    ///
+    /// We'll retrieve a custom property and modify it:
+    /// ```
+    /// use ez_term::*;
+    ///
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// scheduler.new_usize_property("my_property", 10);
+    /// let property = scheduler.get_property_mut("my_property");
+    /// property.set(20);
+    ///
+    /// run(root_widget, state_tree, scheduler, custom_data);
+    /// ```
+    pub fn get_property_mut(&mut self, name: &str) -> &mut EzProperties {
+        self.backend
+            .properties
+            .get_mut(name)
+            .unwrap_or_else(|| panic!("Could not find property: {}", name))
+    }
+
+    /// As an end-user, use 'property.bind' or 'scheduler.bind_property.
+    /// Subscribe one property to another, ensuring the subscriber will always have the value of the
+    /// property it is subscribed to on the next frame. An update func is required which will be
+    /// called when the property subscribed to changes. The update func receives the new value and
+    /// is responsible for setting the appropriate field on the subscriber.
+    /// Panics if the subscription would create a cycle (e.g. two properties subscribed to each
+    /// other), as updates would never stop propagating.
+    pub fn subscribe_to_property(&mut self, name: &str, subscriber: String) {
+        if !self.synced {
+            if let Some(path) = self.find_subscription_path(&subscriber, name) {
+                panic!(
+                    "Cannot subscribe property \"{}\" to \"{}\", it would create a cycle: {} -> {}",
+                    subscriber,
+                    name,
+                    name,
+                    path.join(" -> ")
+                );
+            }
+            if !self.backend.property_subscribers.contains_key(name) {
+                self.backend
+                    .property_subscribers
+                    .insert(name.to_string(), Vec::new());
+            }
+            // Updaters and channels are only created for properties that are actually bound, as
+            // most widget properties never are and creating them for large UIs slows down startup.
+            self._get_update_func(&subscriber);
+            self._create_property_channel(name);
+            self.backend
+                .property_subscribers
+                .get_mut(name)
+                .unwrap()
+                .push(subscriber);
+        } else {
+            self.sync_thread_sender
+                .as_ref()
+                .unwrap()
+                .send(true)
+                .unwrap();
+            self.subscribe_to_property_sender
+                .as_ref()
+                .unwrap()
+                .send((name.to_string(), subscriber))
+                .unwrap();
+        }
+    }
+
+    /// Find a chain of subscriptions through which updates of property 'from' reach property 'to'.
+    /// Returns the properties in the chain, starting with 'from' and ending with 'to'.
+    fn find_subscription_path(&self, from: &str, to: &str) -> Option<Vec<String>> {
+        let mut stack = vec![vec![from.to_string()]];
+        let mut visited = vec![from.to_string()];
+        while let Some(path) = stack.pop() {
+            let last = path.last().unwrap();
+            if last == to {
+                return Some(path);
+            }
+            if let Some(subscribers) = self.backend.property_subscribers.get(last) {
+                for subscriber in subscribers {
+                    if !visited.contains(subscriber) {
+                        visited.push(subscriber.clone());
+                        let mut new_path = path.clone();
+                        new_path.push(subscriber.clone());
+                        stack.push(new_path);
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// Schedule a widget to be redrawn on the next frame. If you are working with a widget state,
+    /// it is usually more convenient to call "state.update" instead of this method. This method
+    /// only accepts a full widget path, not an ID.
+    ///
+    /// # Parameters:
+    ///
+    /// - Path of the widget: &str
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use ez_term::*;
+    ///
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// scheduler.update_widget("/root/my_layout/my_label");
+    ///
+    /// run(root_widget, state_tree, scheduler, custom_data);
+    /// ```
+    pub fn update_widget(&mut self, path: &str) {
+        if let Some(ref mut updates) = self.backend.transaction_updates {
+            if !updates.iter().any(|x| x == path) {
+                updates.push(path.to_string());
+            }
+            return;
+        }
+        if !self.synced {
+            if path.starts_with("/root/modal") {
+                self.backend.force_redraw = true;
+                return;
+            }
+            let path = path.to_string();
+            if !self.backend.widgets_to_update.contains(&path) {
+                self.backend.widgets_to_update.push(path);
+            }
+        } else {
+            self.sync_thread_sender
+                .as_ref()
+                .unwrap()
+                .send(true)
+                .unwrap();
+            self.update_widget_sender
+                .as_ref()
+                .unwrap()
+                .send(path.to_string())
+                .unwrap();
+        }
+    }
+
+    /// Schedule many widgets to be redrawn on the next frame. From a thread they are sent to the
+    /// main thread as a single message. No need to use this as an end-user; use
+    /// [StateTree.transaction] instead.
+    pub fn _update_widgets(&mut self, paths: Vec<String>) {
+        if !self.synced || self.backend.transaction_updates.is_some() {
+            for path in paths.iter() {
+                self.update_widget(path);
+            }
+        } else if !paths.is_empty() {
+            self.sync_thread_sender
+                .as_ref()
+                .unwrap()
+                .send(true)
+                .unwrap();
+            self.update_widgets_sender
+                .as_ref()
+                .unwrap()
+                .send(paths)
+                .unwrap();
+        }
+    }
+
+    /// Schedule a partial redraw of a widget: only the passed cells are redrawn on the next frame
+    /// instead of the whole widget. The cells are positions relative to the content of the
+    /// widget, i.e. excluding border and padding. Useful for large widgets of which only a small
+    /// part changes, e.g. a single pixel of a canvas. Widgets that do not support partial redraws
+    /// are redrawn completely, as are widgets inside a scroll view. From a thread the whole widget
+    /// is always redrawn.
+    ///
+    /// # Parameters:
+    ///
+    /// - Widget path: &str
+    /// - Cells: Vec<Coordinates>
+    ///
+    /// # Example:
+    ///
+    /// We'll change a single pixel of a canvas:
+    /// ```
+    /// use ez_term::*;
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// let state = state_tree.get_mut("my_canvas").as_canvas_mut();
+    /// state.set_pixel(3, 2, Pixel::new("X".to_string(), Color::Red, Color::Black));
+    /// state.update_cells(vec![Coordinates::new(3, 2)], &mut scheduler);
+    /// ```
+    pub fn update_widget_cells(&mut self, path: &str, cells: Vec<Coordinates>) {
+        if self.synced || self.backend.transaction_updates.is_some() {
+            self.update_widget(path);
+            return;
+        }
+        if let Some((_, existing)) = self
+            .backend
+            .cells_to_update
+            .iter_mut()
+            .find(|(x, _)| x == path)
+        {
+            existing.extend(cells);
+        } else {
+            self.backend.cells_to_update.push((path.to_string(), cells));
+        }
+    }
+
+    /// Forces a global screen redraw (though only changed pixels will actually be redrawn). While this
+    /// method if exposed to give you the option to use it, this is generally not recommended for
+    /// performance reasons. It's preferred to call updates on changed widgets, rather than global
+    /// redraws.
+    ///
+    /// # Parameters:
+    ///
+    /// This method takes no parameters.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use ez_term::*;
+    ///
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// scheduler.force_redraw();
+    ///
+    /// run(root_widget, state_tree, scheduler, custom_data);
+    /// ```
+    pub fn force_redraw(&mut self) {
+        if !self.synced {
+            self.backend.force_redraw = true;
+        } else {
+            self.sync_thread_sender
+                .as_ref()
+                .unwrap()
+                .send(true)
+                .unwrap();
+            self.force_redraw_sender
+                .as_ref()
+                .unwrap()
+                .send(true)
+                .unwrap();
+        }
+    }
+
+    /// Bind a callback to a property (can be a widget property or a custom property). Whenever the
+    /// property value changes, the callback is called. This method only takes a full property path
+    /// (e.g. "/root/layout/my_label/width"); it is usually more convenient to get the property from
+    /// the widget state, and then call "property.bind".
+    ///
+    /// # Parameters:
+    ///
+    /// - property path: &str
+    /// - callback function: Box<dyn FnMut(Context)>
+    ///
+    /// # Example:
+    ///
+    /// Let's bind a property; we'll show how to do it from the scheduler, and how to do it from the
+    /// property itself. We'll create a callback that displays the width of a label in its text:
+    ///
+    /// ```
+    /// use ez_term::*;
+    ///
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// fn my_callback(context: Context) {
+    ///     let state = context.state_tree.get_mut("my_label").as_label_mut();
+    ///     let width = state.get_size().get_width();
+    ///     state.set_text(format!("My width is: {}", width));
+    /// }
+    ///
+    /// // bind from scheduler
+    /// scheduler.bind_property_callback("/root/layout/my_label/width", Box::new(my_callback));
+    ///
+    /// // bind from property
+    ///     let state = state_tree.get_mut("my_label").as_label_mut();
+    ///     state.size.width.bind(Box::new(my_callback), &mut scheduler);
+    ///
+    ///
+    /// run(root_widget, state_tree, scheduler, custom_data);
+    /// ```
+    pub fn bind_property_callback(&mut self, name: &str, callback: GenericFunction) {
+        if !self.synced {
+            self._create_property_channel(name);
+            let name = if !name.contains('/') {
+                format!("/root/{}", name)
+            } else {
+                name.to_string()
+            };
+            if self.backend.property_callbacks.contains(&name) {
+                self.backend.new_property_callbacks.push((name, callback));
+            } else {
+                let mut config = CallbackConfig::default();
+                config.property_callbacks.push(callback);
+                self.overwrite_callback_config(&name, config);
+                self.backend.property_callbacks.push(name);
+            }
+        } else {
+            self.sync_thread_sender
+                .as_ref()
+                .unwrap()
+                .send(true)
+                .unwrap();
+            self.bind_property_sender
+                .as_ref()
+                .unwrap()
+                .send((name.to_string(), callback))
+                .unwrap();
+        }
+    }
+
+    /// Persist a custom property to a settings file, so it keeps its value between runs of the
+    /// app. If the file contains a value for the property it is loaded immediately; afterwards the
+    /// file is written whenever the property changes (at most once every half second). Multiple
+    /// properties can be persisted to the same file. Files ending in ".toml" are written as TOML,
+    /// all other files as JSON. Only usize, f64, bool and String properties can be persisted.
+    /// Call this before [run], after creating the property.
+    ///
+    /// # Parameters:
+    ///
+    /// - Name of the custom property: &str
+    /// - Path to the settings file: &str
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use ez_term::*;
+    ///
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// scheduler.new_usize_property("settings/volume", 50);
+    /// scheduler.persist_property("settings/volume", "settings.json");
+    ///
+    /// run(root_widget, state_tree, scheduler, custom_data);
+    /// ```
+    pub fn persist_property(&mut self, name: &str, path: &str) {
+        let property = self.get_property_mut(name);
+        // Panics early if the property type cannot be persisted
+        property_to_value(name, property);
+        if let Some(value) = load_settings(path).get(name) {
+            set_property_from_value(property, value);
+        }
+
+        let names = self
+            .backend
+            .persisted_properties
+            .entry(path.to_string())
+            .or_default();
+        if names.contains(&name.to_string()) {
+            return;
+        }
+        names.push(name.to_string());
+
+        let path = path.to_string();
+        let persist = move |context: Context| {
+            let task_name = get_persist_task_name(&path);
+            let path = path.clone();
+            context.scheduler.cancel_task(&task_name);
+            context.scheduler.schedule_once(
+                &task_name,
+                Box::new(move |context: Context| save_settings(&path, context.scheduler)),
+                PERSIST_DELAY,
+            );
+            true
+        };
+        self.bind_property_callback(name, Box::new(persist));
+    }
+
+    /// Bind a callback to a custom key being pressed anywhere in the UI. Global key binds take
+    /// priority over widget key binds.
+    ///
+    /// # Parameters:
+    ///
+    /// - key: KeyCode
+    /// - modifiers: Option<Vec<KeyModifiers>>
+    /// - callback function: Box<dyn FnMut(Context, KeyCode, KeyModifiers)>
+    ///
+    /// # Example:
+    ///
+    /// We'll bind the key combination "shift + A" to change a label text:
+    /// ```
+    /// use ez_term::*;
+    ///
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// fn my_callback(context: Context) {
+    ///     let state = context.state_tree.get_mut("my_label").as_label_mut();
+    ///     state.set_text("Shift A was pressed!".to_string());
+    /// }
+    ///
+    /// scheduler.bind_global_key(KeyCode::Char('a'), Some(vec!(KeyModifiers::SHIFT)),
+    ///                           Box::new(my_callback));
+    ///
+    /// run(root_widget, state_tree, scheduler, custom_data);
+    pub fn bind_global_key(
+        &mut self,
+        key: KeyCode,
+        modifiers: Option<Vec<KeyModifiers>>,
+        callback: KeyboardCallbackFunction,
+    ) {
+        if !self.synced {
+            let modifiers = create_keymap_modifiers(modifiers);
+            self.backend
+                .update_global_keymap
+                .insert((key, modifiers), callback);
+        } else {
+            self.sync_thread_sender
+                .as_ref()
+                .unwrap()
+                .send(true)
+                .unwrap();
+            self.bind_global_key_sender
+                .as_ref()
+                .unwrap()
+                .send((key, modifiers, callback))
+                .unwrap();
+        }
+    }
+
+    /// Register a color [Theme] under a name. Registered themes can be applied to all widgets with
+    /// [set_theme], or cycled through with [cycle_theme] or a key bound with
+    /// [bind_theme_cycle_key]. Registering a theme with an existing name replaces it.
+    ///
+    /// # Parameters:
+    ///
+    /// - Name: &str
+    /// - Theme: [Theme]
+    ///
+    /// # Example:
+    ///
+    /// We'll register a light theme and cycle themes with F2:
+    /// ```
+    /// use ez_term::*;
+    ///
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// let mut light = Theme::new();
+    /// light.set_color("fg_color", Color::Black);
+    /// light.set_color("bg_color", Color::White);
+    /// light.set_color("border_fg_color", Color::DarkGrey);
+    /// light.set_color("border_bg_color", Color::White);
+    /// light.set_color("filler_bg_color", Color::White);
+    /// scheduler.register_theme("light", light);
+    /// scheduler.bind_theme_cycle_key(KeyCode::F(2), None);
+    ///
+    /// run(root_widget, state_tree, scheduler, custom_data);
+    /// ```
+    pub fn register_theme(&mut self, name: &str, theme: Theme) {
+        if let Some(i) = self.backend.themes.iter().position(|(x, _)| x == name) {
+            self.backend.themes[i].1 = theme;
+        } else {
+            self.backend.themes.push((name.to_string(), theme));
+        }
+    }
+
+    /// Apply a registered [Theme] to all widgets, redrawing the entire screen. Must be called from
+    /// the main thread (e.g. from a callback or scheduled task).
+    ///
+    /// # Parameters:
+    ///
+    /// - Name: &str
+    /// - State tree: &mut [StateTree]
+    ///
+    /// # Example:
+    ///
+    /// We'll switch to the "light" theme when a button is pressed:
+    /// ```
+    /// use ez_term::*;
+    ///
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// let my_callback = |context: Context| {
+    ///     context.scheduler.set_theme("light", context.state_tree);
+    ///     true
+    /// };
+    /// let new_callback_config = CallbackConfig::from_on_press(Box::new(my_callback));
+    /// scheduler.update_callback_config("my_button", new_callback_config);
+    ///
+    /// run(root_widget, state_tree, scheduler, custom_data);
+    /// ```
+    pub fn set_theme(&mut self, name: &str, state_tree: &mut StateTree) {
+        let index = self
+            .backend
+            .themes
+            .iter()
+            .position(|(x, _)| x == name)
+            .unwrap_or_else(|| panic!("Could not find theme: {}", name));
+        self.apply_theme(index, state_tree);
+    }
+
+    /// Apply the next registered [Theme] to all widgets, starting over after the last one. Must
+    /// be called from the main thread (e.g. from a callback or scheduled task).
+    ///
+    /// # Parameters:
+    ///
+    /// - State tree: &mut [StateTree]
+    pub fn cycle_theme(&mut self, state_tree: &mut StateTree) {
+        if self.backend.themes.is_empty() {
+            return;
+        }
+        let index = match self.backend.current_theme {
+            Some(i) => (i + 1) % self.backend.themes.len(),
+            None => 0,
+        };
+        self.apply_theme(index, state_tree);
+    }
+
+    /// Get the name of the theme that was last applied, if any.
+    pub fn get_current_theme(&self) -> Option<String> {
+        self.backend
+            .current_theme
+            .map(|i| self.backend.themes[i].0.clone())
+    }
+
+    /// Bind a global key that cycles through the registered themes.
+    ///
+    /// # Parameters:
+    ///
+    /// - key: KeyCode
+    /// - modifiers: Option<Vec<KeyModifiers>>
+    pub fn bind_theme_cycle_key(&mut self, key: KeyCode, modifiers: Option<Vec<KeyModifiers>>) {
+        let callback = |context: Context, _key: KeyCode, _modifiers: KeyModifiers| {
+            context.scheduler.cycle_theme(context.state_tree);
+            true
+        };
+        self.bind_global_key(key, modifiers, Box::new(callback));
+    }
+
+    fn apply_theme(&mut self, index: usize, state_tree: &mut StateTree) {
+        let theme = &self.backend.themes[index].1;
+        for state in state_tree.get_all_mut() {
+            theme.apply(state.as_generic_mut().get_color_config_mut());
+        }
+        self.backend.current_theme = Some(index);
+        self.force_redraw();
+    }
+
+    /// Set how all widgets show that they are selected, redrawing the entire screen. Widgets can
+    /// also set their own style with the "selection_style" property. Must be called from the
+    /// main thread (e.g. before running the UI or from a callback).
+    ///
+    /// # Parameters:
+    ///
+    /// - Style: [SelectionStyle]
+    /// - Marker: Option<&str>: symbol drawn for [SelectionStyle::Marker]; None to keep the
+    ///   current marker of each widget
+    /// - State tree: &mut [StateTree]
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use ez_term::*;
+    ///
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// scheduler.set_selection_style(SelectionStyle::Marker, Some(">"), &mut state_tree);
+    ///
+    /// run(root_widget, state_tree, scheduler, custom_data);
+    /// ```
+    pub fn set_selection_style(
+        &mut self,
+        style: SelectionStyle,
+        marker: Option<&str>,
+        state_tree: &mut StateTree,
+    ) {
+        for state in state_tree.get_all_mut() {
+            let colors = state.as_generic_mut().get_color_config_mut();
+            colors.set_selection_style(style);
+            if let Some(marker) = marker {
+                colors.set_selection_marker(marker);
+            }
+        }
+        self.force_redraw();
+    }
+
+    /// Set a callback that is called when the terminal window gains focus. Whether the window is
+    /// focused is also available as the "app_focused" bool property. Not all terminals report
+    /// focus changes. Must be called from the main thread (e.g. before running the UI or from a
+    /// callback).
+    ///
+    /// # Parameters:
+    ///
+    /// - Callback: Box<FnMut(Context) -> bool>
+    ///
+    /// # Example:
+    ///
+    /// We'll pause a recurring animation while the terminal window is unfocused:
+    /// ```
+    /// use std::time::Duration;
+    /// use ez_term::*;
+    ///
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// fn animate(context: Context) -> bool {
+    ///     // Draw the next frame of the animation
+    ///     true
+    /// }
+    /// scheduler.schedule_recurring("animation", Box::new(animate), Duration::from_millis(100));
+    ///
+    /// let on_blur = |context: Context| {
+    ///     context.scheduler.cancel_recurring_task("animation");
+    ///     true
+    /// };
+    /// scheduler.set_on_blur(Box::new(on_blur));
+    ///
+    /// let on_focus = |context: Context| {
+    ///     context.scheduler.schedule_recurring("animation", Box::new(animate),
+    ///                                          Duration::from_millis(100));
+    ///     true
+    /// };
+    /// scheduler.set_on_focus(Box::new(on_focus));
+    ///
+    /// run(root_widget, state_tree, scheduler, custom_data);
+    /// ```
+    pub fn set_on_focus(&mut self, callback: GenericFunction) {
+        self.backend.on_focus = Some(callback);
+    }
+
+    /// Set a callback that is called once when there has been no input (keys, mouse or pastes) for
+    /// the idle timeout (see [set_idle_timeout]). After new input it can be called again. Useful
+    /// for screensavers or locking the app. Widgets can have their own on_idle callback as well,
+    /// see [CallbackConfig]. Must be called from the main thread.
+    ///
+    /// # Parameters:
+    ///
+    /// - Callback: Box<FnMut(Context) -> bool>
+    ///
+    /// # Example:
+    ///
+    /// We'll show a lock screen after five minutes without input:
+    /// ```
+    /// use std::time::Duration;
+    /// use ez_term::*;
+    ///
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// let on_idle = |context: Context| {
+    ///     context.scheduler.open_modal("LockScreen", context.state_tree);
+    ///     true
+    /// };
+    /// scheduler.set_idle_timeout(Duration::from_secs(300));
+    /// scheduler.set_on_idle(Box::new(on_idle));
+    ///
+    /// run(root_widget, state_tree, scheduler, custom_data);
+    /// ```
+    pub fn set_on_idle(&mut self, callback: GenericFunction) {
+        self.backend.on_idle = Some(callback);
+    }
+
+    /// Set how long there must be no input before the app is idle, see [set_on_idle]. Also used
+    /// for on_idle callbacks of widgets that do not set their own idle_timeout. Defaults to one
+    /// minute.
+    ///
+    /// # Parameters:
+    ///
+    /// - Timeout: Duration
+    pub fn set_idle_timeout(&mut self, timeout: Duration) {
+        self.backend.idle_timeout = timeout;
+    }
+
+    /// Get how long there must be no input before the app is idle, see [set_idle_timeout].
+    pub fn get_idle_timeout(&self) -> Duration {
+        self.backend.idle_timeout
+    }
+
+    /// Get the timings of the phases of loading and starting the UI, along with the amount of
+    /// widgets and properties that were created. Useful to find out what slows down the startup of
+    /// a large UI. The profile can be printed directly.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use ez_term::*;
+    ///
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// let profile = scheduler.get_startup_profile();
+    /// eprintln!("{}", profile);
+    /// if let Some(duration) = profile.get_phase("create widgets") {
+    ///     assert!(duration <= profile.get_total());
+    /// }
+    /// ```
+    pub fn get_startup_profile(&self) -> &StartupProfile {
+        &self.backend.startup_profile
+    }
+
+    /// Start layout tuning mode: a developer tool to move and resize widgets with the keyboard
+    /// while the app is running. Click a widget (or select it) and use Alt + arrow keys to move it
+    /// or Ctrl + arrow keys to resize it. The resulting positions and sizes are recorded as
+    /// suggested .ez property lines; see [get_layout_tuning_suggestions]. If an output file is
+    /// passed, the suggestions are also written to it after every change. Must be called from the
+    /// main thread (e.g. before running the UI or from a callback).
+    ///
+    /// # Parameters:
     ///
-    /// - Name of the new property: &str
-    /// - Value of the new property: SizeHint
+    /// - Output file: Option<&str>
     ///
-    /// ## Example:
+    /// # Example:
     ///
-    /// We'll create a custom property and bind it to a widget in an .ez file.
-    /// First the code:
+    /// We'll tune the layout when the app is started with "--tune", writing the suggestions to a
+    /// file that can be copied into the .ez file:
     /// ```
     /// use ez_term::*;
     ///
     /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
     ///
-    /// scheduler.new_size_hint_property("my_property", Some(0.75));
+    /// if std::env::args().any(|x| x == "--tune") {
+    ///     scheduler.start_layout_tuning(Some("layout_tuning.ez"));
+    /// }
     ///
     /// run(root_widget, state_tree, scheduler, custom_data);
     /// ```
-    /// Now the .ez file:
-    /// ```
-    /// - Layout:
-    ///     mode: float
-    ///     - Label:
-    ///         size_hint_x: properties.my_property
-    /// ```
-    pub fn new_size_hint_property(
-        &mut self,
-        name: &str,
-        value: Option<f64>,
-    ) -> EzProperty<Option<f64>> {
-        let (property, receiver) = EzProperty::new(name.to_string(), value);
-        self.backend
-            .properties
-            .insert(name.to_string(), EzProperties::SizeHint(property.clone()));
-        self.backend
-            .property_receivers
-            .insert(name.to_string(), receiver);
-        self.get_update_func(name);
-        property
+    pub fn start_layout_tuning(&mut self, output_file: Option<&str>) {
+        self.backend.layout_tuning = Some(LayoutTuning::new(output_file.map(|x| x.to_string())));
+        self.force_redraw();
     }
 
-    pub fn new_data(
-        &mut self,
-        name: &str,
-        data: Box<dyn CustomData + Send>,
-    ) {
-        self.backend
-            .new_custom_data
-            .insert(name.to_string(), data);
+    /// Stop layout tuning mode, see [start_layout_tuning]. The suggestions are discarded.
+    pub fn stop_layout_tuning(&mut self) {
+        self.backend.layout_tuning = None;
     }
 
-    /// Get a reference to a custom property.
+    /// Returns a bool representing whether layout tuning mode is active, see
+    /// [start_layout_tuning].
+    pub fn is_layout_tuning(&self) -> bool {
+        self.backend.layout_tuning.is_some()
+    }
+
+    /// Get the suggested .ez property lines for every widget tuned in layout tuning mode, see
+    /// [start_layout_tuning]. The lines of each widget are preceded by a comment containing its'
+    /// path. Returns an empty string if layout tuning mode is not active or nothing was tuned.
+    pub fn get_layout_tuning_suggestions(&self, state_tree: &StateTree) -> String {
+        match self.backend.layout_tuning {
+            Some(ref tuning) => tuning.get_suggestions(state_tree),
+            None => String::new(),
+        }
+    }
+
+    /// Set a callback that is called when the terminal window loses focus. See [set_on_focus] for
+    /// more info.
     ///
     /// # Parameters:
     ///
-    /// - Name of the custom property: &str
+    /// - Callback: Box<FnMut(Context) -> bool>
+    pub fn set_on_blur(&mut self, callback: GenericFunction) {
+        self.backend.on_blur = Some(callback);
+    }
+
+    /// Set a global callback that is called when text is pasted into the terminal. Pasted text is
+    /// first offered to the selected widget (text inputs insert it by default); this callback is
+    /// only called if the selected widget did not consume it, or if no widget is selected. Must
+    /// be called from the main thread (e.g. before running the UI or from a callback).
     ///
-    /// # Example:
+    /// # Parameters:
     ///
-    /// We'll retrieve a custom property:
+    /// - Callback: Box<FnMut(Context, String) -> bool>
+    ///
+    /// # Example:
     ///
+    /// We'll show any text pasted outside of a text input in a label:
     /// ```
     /// use ez_term::*;
     ///
     /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
     ///
-    /// scheduler.new_usize_property("my_property", 10);
-    /// let property = scheduler.get_property("my_property");
+    /// let on_paste = |context: Context, text: String| {
+    ///     let state = context.state_tree.get_mut("my_label").as_label_mut();
+    ///     state.set_text(text);
+    ///     state.update(context.scheduler);
+    ///     true
+    /// };
+    /// scheduler.set_on_paste(Box::new(on_paste));
     ///
     /// run(root_widget, state_tree, scheduler, custom_data);
     /// ```
-    pub fn get_property(&self, name: &str) -> &EzProperties {
-        self.backend
-            .properties
-            .get(name)
-            .unwrap_or_else(|| panic!("Could not find property: {}", name))
+    pub fn set_on_paste(&mut self, callback: PasteCallbackFunction) {
+        self.backend.on_paste = Some(callback);
     }
 
-    /// Get a mutable reference to a  custom property created earlier.
+    /// Set a filter that receives every terminal event before it is passed to any widget, modal
+    /// or global key bind. The filter can swallow an event by returning None, or change it by
+    /// returning a different event. Useful for app-wide input schemes, e.g. remapping keys or
+    /// implementing a leader key. Only one filter can be set; setting a new one replaces the old
+    /// one. Must be called from the main thread (e.g. before running the UI or from a callback).
     ///
     /// # Parameters:
     ///
-    /// - Name of the custom property: &str
+    /// - Filter: Box<FnMut(Context, Event) -> Option<Event>>
     ///
     /// # Example:
     ///
-    /// We'll retrieve a custom property and modify it:
+    /// We'll translate WASD keys to arrow keys, and swallow the 'q' key:
     /// ```
     /// use ez_term::*;
     ///
     /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
     ///
-    /// scheduler.new_usize_property("my_property", 10);
-    /// let property = scheduler.get_property_mut("my_property");
-    /// property.set(20);
+    /// let input_filter = |context: Context, event: Event| {
+    ///     if let Event::Key(key) = event {
+    ///         let new_code = match key.code {
+    ///             KeyCode::Char('w') => KeyCode::Up,
+    ///             KeyCode::Char('a') => KeyCode::Left,
+    ///             KeyCode::Char('s') => KeyCode::Down,
+    ///             KeyCode::Char('d') => KeyCode::Right,
+    ///             KeyCode::Char('q') => return None,
+    ///             other => other,
+    ///         };
+    ///         return Some(Event::Key(KeyEvent::new(new_code, key.modifiers)));
+    ///     }
+    ///     Some(event)
+    /// };
+    /// scheduler.set_input_filter(Box::new(input_filter));
     ///
     /// run(root_widget, state_tree, scheduler, custom_data);
     /// ```
-    pub fn get_property_mut(&mut self, name: &str) -> &mut EzProperties {
-        self.backend
-            .properties
-            .get_mut(name)
-            .unwrap_or_else(|| panic!("Could not find property: {}", name))
+    pub fn set_input_filter(&mut self, filter: InputFilterFunction) {
+        self.backend.input_filter = Some(filter);
     }
 
-    /// As an end-user, use 'property.bind' or 'scheduler.bind_property.
-    /// Subscribe one property to another, ensuring the subscriber will always have the value of the
-    /// property it is subscribed to on the next frame. An update func is required which will be
-    /// called when the property subscribed to changes. The update func receives the new value and
-    /// is responsible for setting the appropriate field on the subscriber.
-    pub fn subscribe_to_property(&mut self, name: &str, subscriber: String) {
-        if !self.synced {
-            if !self.backend.property_subscribers.contains_key(name) {
-                self.backend
-                    .property_subscribers
-                    .insert(name.to_string(), Vec::new());
-            }
-            self.backend
-                .property_subscribers
-                .get_mut(name)
-                .unwrap()
-                .push(subscriber);
-        } else {
-            self.sync_thread_sender
-                .as_ref()
-                .unwrap()
-                .send(true)
-                .unwrap();
-            self.subscribe_to_property_sender
-                .as_ref()
-                .unwrap()
-                .send((name.to_string(), subscriber))
-                .unwrap();
-        }
+    /// Remove the input filter set with [set_input_filter], if any. Must be called from the main
+    /// thread (e.g. before running the UI or from a callback).
+    pub fn remove_input_filter(&mut self) {
+        self.backend.input_filter = None;
     }
 
-    /// Schedule a widget to be redrawn on the next frame. If you are working with a widget state,
-    /// it is usually more convenient to call "state.update" instead of this method. This method
-    /// only accepts a full widget path, not an ID.
+    /// Get the payload of the drag and drop that is currently in progress, if any. A drag and drop
+    /// starts when a widget returns a [DragPayload] from its' on_drag_start callback, and ends
+    /// when the mouse button is released. Useful for giving feedback while dragging, e.g.
+    /// highlighting the widgets the payload can be dropped on from an on_drag callback.
+    ///
+    /// # Example:
+    ///
+    /// We'll show what is being dragged in a label:
+    /// ```
+    /// use ez_term::*;
+    ///
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// let on_drag = |context: Context, previous_pos: Option<IsizeCoordinates>,
+    ///                mouse_pos: IsizeCoordinates| {
+    ///     if let Some(payload) = context.scheduler.get_drag_payload() {
+    ///         let state = context.state_tree.get_mut("status_label").as_label_mut();
+    ///         state.set_text(format!("Moving {}", payload.data));
+    ///         state.update(context.scheduler);
+    ///     }
+    ///     true
+    /// };
+    /// scheduler.update_callback_config("task_1", CallbackConfig::from_on_drag(Box::new(on_drag)));
+    ///
+    /// run(root_widget, state_tree, scheduler, custom_data);
+    /// ```
+    pub fn get_drag_payload(&self) -> Option<DragPayload> {
+        self.backend.drag_payload.clone()
+    }
+
+    /// Cancel the drag and drop that is currently in progress, if any. The payload will not be
+    /// dropped when the mouse button is released; the dragged widget still receives its' on_drag
+    /// and on_drag_exit callbacks as with a normal drag.
+    pub fn cancel_drag(&mut self) {
+        self.backend.drag_payload = None;
+    }
+
+    /// Add a source of events other than the terminal, e.g. a gamepad. Events of the source are
+    /// routed exactly like terminal events. See [InputSource]. Must be called from the main thread
+    /// (e.g. before running the UI or from a callback).
     ///
     /// # Parameters:
     ///
-    /// - Path of the widget: &str
+    /// - Source: Box<dyn InputSource>
     ///
     /// # Example:
     ///
+    /// We'll press enter on the selected widget once:
     /// ```
     /// use ez_term::*;
     ///
+    /// struct PressOnce(bool);
+    /// impl InputSource for PressOnce {
+    ///     fn poll_event(&mut self) -> Option<Event> {
+    ///         if self.0 {
+    ///             return None;
+    ///         }
+    ///         self.0 = true;
+    ///         Some(Event::Key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE)))
+    ///     }
+    /// }
+    ///
     /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    /// scheduler.add_input_source(Box::new(PressOnce(false)));
+    /// run(root_widget, state_tree, scheduler, custom_data);
+    /// ```
+    pub fn add_input_source(&mut self, source: Box<dyn InputSource>) {
+        self.backend.input_sources.push(source);
+    }
+
+    /// Get a handle to send events into the UI from any thread, as if they came from the
+    /// terminal. See [EventInjector]. Every call returns a handle to the same source. Must be
+    /// called from the main thread (e.g. before running the UI or from a callback); the handle
+    /// itself can be sent to other threads.
     ///
-    /// scheduler.update_widget("/root/my_layout/my_label");
+    /// # Example:
+    ///
+    /// We'll send a key press from a thread that reads a device:
+    /// ```
+    /// use ez_term::*;
+    ///
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
     ///
+    /// let injector = scheduler.get_event_injector();
+    /// std::thread::spawn(move || {
+    ///     injector.inject(Event::Key(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE)));
+    /// });
     /// run(root_widget, state_tree, scheduler, custom_data);
     /// ```
-    pub fn update_widget(&mut self, path: &str) {
-        if !self.synced {
-            if path.starts_with("/root/modal") {
-                self.backend.force_redraw = true;
-                return;
-            }
-            let path = path.to_string();
-            if !self.backend.widgets_to_update.contains(&path) {
-                self.backend.widgets_to_update.push(path);
-            }
-        } else {
-            self.sync_thread_sender
-                .as_ref()
-                .unwrap()
-                .send(true)
-                .unwrap();
-            self.update_widget_sender
-                .as_ref()
-                .unwrap()
-                .send(path.to_string())
-                .unwrap();
+    pub fn get_event_injector(&mut self) -> EventInjector {
+        if let Some(ref injector) = self.backend.event_injector {
+            return injector.clone();
         }
+        let (source, injector) = ChannelInputSource::new();
+        self.add_input_source(Box::new(source));
+        self.backend.event_injector = Some(injector.clone());
+        injector
     }
 
-    /// Forces a global screen redraw (though only changed pixels will actually be redrawn). While this
-    /// method if exposed to give you the option to use it, this is generally not recommended for
-    /// performance reasons. It's preferred to call updates on changed widgets, rather than global
-    /// redraws.
+    /// Get the next event of the input sources added with [add_input_source], if any. Used by the
+    /// run loop and by [TestUi].
+    pub(crate) fn next_input_source_event(&mut self) -> Option<Event> {
+        self.backend
+            .input_sources
+            .iter_mut()
+            .find_map(|source| source.poll_event())
+    }
+
+    /// Start recording the input events of the user (key presses, mouse clicks, etc.) into a
+    /// macro, which can be replayed later with [play_macro]. Recording continues until
+    /// [stop_macro_recording] is called; the event during which recording is stopped (e.g. the
+    /// key bound to stopping the recording) is not part of the macro. Recording a macro with the
+    /// name of an existing macro replaces it when the recording is stopped. If another macro was
+    /// being recorded, that recording is stopped first.
     ///
     /// # Parameters:
     ///
-    /// This method takes no parameters.
+    /// - Name of the macro: &str
     ///
     /// # Example:
     ///
+    /// We'll bind F2 to starting or stopping a recording, and F3 to replaying it:
     /// ```
     /// use ez_term::*;
     ///
     /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
     ///
-    /// scheduler.force_redraw();
+    /// let toggle_recording = |context: Context, _keycode: KeyCode, _modifiers: KeyModifiers| {
+    ///     if context.scheduler.is_recording_macro() {
+    ///         context.scheduler.stop_macro_recording();
+    ///     } else {
+    ///         context.scheduler.start_macro_recording("my_macro");
+    ///     }
+    ///     true
+    /// };
+    /// scheduler.bind_global_key(KeyCode::F(2), None, Box::new(toggle_recording));
+    ///
+    /// let replay = |context: Context, _keycode: KeyCode, _modifiers: KeyModifiers| {
+    ///     if context.scheduler.has_macro("my_macro") {
+    ///         context.scheduler.play_macro("my_macro");
+    ///     }
+    ///     true
+    /// };
+    /// scheduler.bind_global_key(KeyCode::F(3), None, Box::new(replay));
     ///
     /// run(root_widget, state_tree, scheduler, custom_data);
     /// ```
-    pub fn force_redraw(&mut self) {
-        if !self.synced {
-            self.backend.force_redraw = true;
-        } else {
-            self.sync_thread_sender
-                .as_ref()
-                .unwrap()
-                .send(true)
-                .unwrap();
-            self.force_redraw_sender
-                .as_ref()
-                .unwrap()
-                .send(true)
-                .unwrap();
+    pub fn start_macro_recording(&mut self, name: &str) {
+        self.stop_macro_recording();
+        self.backend.macro_recording = Some((name.to_string(), Vec::new()));
+    }
+
+    /// Stop recording the macro started with [start_macro_recording] and store it, so it can be
+    /// replayed with [play_macro]. Does nothing if no macro is being recorded.
+    pub fn stop_macro_recording(&mut self) {
+        if let Some((name, mut events)) = self.backend.macro_recording.take() {
+            // The event that is being handled caused the recording to stop, so it is not part of
+            // the macro
+            if self.backend.current_event.is_some() && !self.backend.replaying_macro {
+                events.pop();
+            }
+            self.backend.macros.insert(name, events);
         }
     }
 
-    /// Bind a callback to a property (can be a widget property or a custom property). Whenever the
-    /// property value changes, the callback is called. This method only takes a full property path
-    /// (e.g. "/root/layout/my_label/width"); it is usually more convenient to get the property from
-    /// the widget state, and then call "property.bind".
+    /// Check whether a macro is currently being recorded.
+    pub fn is_recording_macro(&self) -> bool {
+        self.backend.macro_recording.is_some()
+    }
+
+    /// Check whether a macro with this name was recorded.
+    pub fn has_macro(&self, name: &str) -> bool {
+        self.backend.macros.contains_key(name)
+    }
+
+    /// Replay a macro recorded with [start_macro_recording]. The recorded events are handled one
+    /// per frame, as if the user sent them again, so widgets and callbacks respond to them as
+    /// usual. New terminal input is handled after all events of the macro were replayed. Panics
+    /// if no macro with this name was recorded.
     ///
     /// # Parameters:
     ///
-    /// - property path: &str
-    /// - callback function: Box<dyn FnMut(Context)>
+    /// - Name of the macro: &str
+    pub fn play_macro(&mut self, name: &str) {
+        let events = self
+            .backend
+            .macros
+            .get(name)
+            .unwrap_or_else(|| panic!("Cannot play macro \"{}\": it was never recorded", name));
+        self.backend.macro_events.extend(events.iter().cloned());
+    }
+
+    /// Remove a recorded macro. Does nothing if no macro with this name was recorded.
+    pub fn remove_macro(&mut self, name: &str) {
+        self.backend.macros.remove(name);
+    }
+
+    /// Run a command that the user can undo, e.g. deleting a row from a table. The command is
+    /// executed at the start of the next frame and added to the undo history; commands that were
+    /// undone can no longer be redone after this. Use [undo] and [redo] to move through the
+    /// history, or [bind_undo_keys] to let the user do so with the keyboard.
     ///
-    /// # Example:
+    /// The custom bool properties "can_undo" and "can_redo" are kept up to date with the history,
+    /// so widgets can bind to them (e.g. "properties.can_undo"). They are created by the first
+    /// command, so do not create custom properties with these names yourself. Must be called from
+    /// the main thread (e.g. from a callback).
     ///
-    /// Let's bind a property; we'll show how to do it from the scheduler, and how to do it from the
-    /// property itself. We'll create a callback that displays the width of a label in its text:
+    /// # Parameters:
+    ///
+    /// - Command: [UndoableCommand]
+    ///
+    /// # Example:
     ///
+    /// We'll make a button add a row to a list, which can be undone:
     /// ```
     /// use ez_term::*;
     ///
     /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
     ///
-    /// fn my_callback(context: Context) {
-    ///     let state = context.state_tree.get_mut("my_label").as_label_mut();
-    ///     let width = state.get_size().get_width();
-    ///     state.set_text(format!("My width is: {}", width));
-    /// }
-    ///
-    /// // bind from scheduler
-    /// scheduler.bind_property_callback("/root/layout/my_label/width", Box::new(my_callback));
-    ///
-    /// // bind from property
-    ///     let state = state_tree.get_mut("my_label").as_label_mut();
-    ///     state.size.width.bind(Box::new(my_callback), &mut scheduler);
-    ///
+    /// let add_row = |context: Context| {
+    ///     let command = UndoableCommand::new(
+    ///         "Add row",
+    ///         Box::new(|context: Context| {
+    ///             let (row, states) = context.scheduler.prepare_create_widget(
+    ///                 "ListRow", "row", "my_list", context.state_tree);
+    ///             context.scheduler.create_widget(row, states, None, context.state_tree);
+    ///         }),
+    ///         Box::new(|context: Context| {
+    ///             context.scheduler.remove_widget("row");
+    ///         }),
+    ///     );
+    ///     context.scheduler.execute_undoable(command);
+    ///     true
+    /// };
+    /// scheduler.update_callback_config("add_button", CallbackConfig::from_on_press(Box::new(add_row)));
+    /// scheduler.bind_undo_keys();
     ///
     /// run(root_widget, state_tree, scheduler, custom_data);
     /// ```
-    pub fn bind_property_callback(&mut self, name: &str, callback: GenericFunction) {
-        if !self.synced {
-            if self.backend.property_callbacks.contains(&name.to_string()) {
-                self.backend
-                    .new_property_callbacks
-                    .push((name.to_string(), callback));
-            } else {
-                let mut config = CallbackConfig::default();
-                config.property_callbacks.push(callback);
-                let name = if !name.contains('/') {
-                    format!("/root/{}", name)
-                } else {
-                    name.to_string()
-                };
-                self.overwrite_callback_config(&name, config);
-                self.backend.property_callbacks.push(name);
-            }
-        } else {
-            self.sync_thread_sender
-                .as_ref()
-                .unwrap()
-                .send(true)
-                .unwrap();
-            self.bind_property_sender
-                .as_ref()
-                .unwrap()
-                .send((name.to_string(), callback))
-                .unwrap();
+    pub fn execute_undoable(&mut self, command: UndoableCommand) {
+        self.backend
+            .undo_history
+            .actions
+            .push(UndoAction::Execute(command));
+    }
+
+    /// Undo the last command run with [execute_undoable] at the start of the next frame. Does
+    /// nothing if there is nothing to undo. Must be called from the main thread.
+    pub fn undo(&mut self) {
+        self.backend.undo_history.actions.push(UndoAction::Undo);
+    }
+
+    /// Redo the last command undone with [undo] at the start of the next frame. Does nothing if
+    /// there is nothing to redo. Must be called from the main thread.
+    pub fn redo(&mut self) {
+        self.backend.undo_history.actions.push(UndoAction::Redo);
+    }
+
+    /// Check whether there is a command to undo.
+    pub fn can_undo(&self) -> bool {
+        !self.backend.undo_history.undo_stack.is_empty()
+    }
+
+    /// Check whether there is a command to redo.
+    pub fn can_redo(&self) -> bool {
+        !self.backend.undo_history.redo_stack.is_empty()
+    }
+
+    /// Get the name of the command that [undo] would undo, e.g. to show "Undo Delete row" in a
+    /// menu.
+    pub fn get_undo_name(&self) -> Option<String> {
+        self.backend
+            .undo_history
+            .undo_stack
+            .last()
+            .map(|x| x.name.clone())
+    }
+
+    /// Get the name of the command that [redo] would redo.
+    pub fn get_redo_name(&self) -> Option<String> {
+        self.backend
+            .undo_history
+            .redo_stack
+            .last()
+            .map(|x| x.name.clone())
+    }
+
+    /// Set the maximum amount of commands that can be undone; the oldest commands are forgotten
+    /// first. 0 (the default) means there is no maximum.
+    pub fn set_undo_limit(&mut self, limit: usize) {
+        let history = &mut self.backend.undo_history;
+        history.limit = limit;
+        if limit > 0 && history.undo_stack.len() > limit {
+            let excess = history.undo_stack.len() - limit;
+            history.undo_stack.drain(..excess);
         }
     }
 
-    /// Bind a callback to a custom key being pressed anywhere in the UI. Global key binds take
-    /// priority over widget key binds.
+    /// Forget all commands that can be undone or redone, e.g. after saving a file.
+    pub fn clear_undo_history(&mut self) {
+        self.backend.undo_history.undo_stack.clear();
+        self.backend.undo_history.redo_stack.clear();
+        update_undo_properties(self);
+    }
+
+    /// Bind the common undo and redo keys as global key binds: ctrl+z to [undo] and ctrl+y to
+    /// [redo]. Global key binds take priority over widget key binds.
+    pub fn bind_undo_keys(&mut self) {
+        let undo = |context: Context, _keycode: KeyCode, _modifiers: KeyModifiers| {
+            context.scheduler.undo();
+            true
+        };
+        let redo = |context: Context, _keycode: KeyCode, _modifiers: KeyModifiers| {
+            context.scheduler.redo();
+            true
+        };
+        self.bind_global_key(
+            KeyCode::Char('z'),
+            Some(vec![KeyModifiers::CONTROL]),
+            Box::new(undo),
+        );
+        self.bind_global_key(
+            KeyCode::Char('y'),
+            Some(vec![KeyModifiers::CONTROL]),
+            Box::new(redo),
+        );
+    }
+
+    /// Start a control server on a TCP address, which lets external programs such as scripts and
+    /// end-to-end tests drive the running UI: set and get properties, press widgets, switch
+    /// screens and dump the state of the UI. See the [control] module for the commands. Anyone
+    /// who can connect to the address can control the UI, so bind to a local address (e.g.
+    /// "127.0.0.1:7070") unless you mean to control the UI from another machine. Only available
+    /// with the "control" feature. Returns an error if the address cannot be bound.
     ///
     /// # Parameters:
     ///
-    /// - key: KeyCode
-    /// - modifiers: Option<Vec<KeyModifiers>>
-    /// - callback function: Box<dyn FnMut(Context, KeyCode, KeyModifiers)>
+    /// - Address: &str
     ///
     /// # Example:
     ///
-    /// We'll bind the key combination "shift + A" to change a label text:
     /// ```
     /// use ez_term::*;
     ///
     /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
-    ///
-    /// fn my_callback(context: Context) {
-    ///     let state = context.state_tree.get_mut("my_label").as_label_mut();
-    ///     state.set_text("Shift A was pressed!".to_string());
+    /// if std::env::var("MY_APP_CONTROL").is_ok() {
+    ///     scheduler.start_control_server("127.0.0.1:7070").unwrap();
     /// }
     ///
-    /// scheduler.bind_global_key(KeyCode::Char('a'), Some(vec!(KeyModifiers::SHIFT)),
-    ///                           Box::new(my_callback));
-    ///
     /// run(root_widget, state_tree, scheduler, custom_data);
-    pub fn bind_global_key(
-        &mut self,
-        key: KeyCode,
-        modifiers: Option<Vec<KeyModifiers>>,
-        callback: KeyboardCallbackFunction,
-    ) {
-        if !self.synced {
-            let modifiers = create_keymap_modifiers(modifiers);
-            self.backend
-                .update_global_keymap
-                .insert((key, modifiers), callback);
-        } else {
-            self.sync_thread_sender
-                .as_ref()
-                .unwrap()
-                .send(true)
-                .unwrap();
-            self.bind_global_key_sender
-                .as_ref()
-                .unwrap()
-                .send((key, modifiers, callback))
-                .unwrap();
+    /// ```
+    #[cfg(feature = "control")]
+    pub fn start_control_server(&mut self, address: &str) -> Result<(), std::io::Error> {
+        let listener = std::net::TcpListener::bind(address)?;
+        serve_tcp(listener, self.get_control_sender());
+        Ok(())
+    }
+
+    /// Start a control server on a unix socket, see [start_control_server]. A socket left behind
+    /// at the path by an earlier run is replaced. Only available on unix with the "control"
+    /// feature. Returns an error if the socket cannot be created.
+    ///
+    /// # Parameters:
+    ///
+    /// - Path of the socket: &str
+    #[cfg(all(feature = "control", unix))]
+    pub fn start_control_socket(&mut self, path: &str) -> Result<(), std::io::Error> {
+        use std::os::unix::fs::FileTypeExt;
+
+        if let Ok(metadata) = std::fs::metadata(path) {
+            if metadata.file_type().is_socket() {
+                std::fs::remove_file(path)?;
+            }
         }
+        let listener = std::os::unix::net::UnixListener::bind(path)?;
+        serve_unix(listener, self.get_control_sender());
+        Ok(())
+    }
+
+    /// Get the sender control servers pass received commands to, creating the channel if this is
+    /// the first server.
+    #[cfg(feature = "control")]
+    fn get_control_sender(&mut self) -> Sender<ControlRequest> {
+        self.backend
+            .control_requests
+            .get_or_insert_with(channel)
+            .0
+            .clone()
+    }
+
+    /// Get the next event of a played macro, if any, and mark it as replayed so it is not recorded
+    /// again. Used by the run loop and by [TestUi]; the mark is removed once the event is handled.
+    pub(crate) fn next_macro_event(&mut self) -> Option<Event> {
+        let event = self.backend.macro_events.pop_front();
+        self.backend.replaying_macro = event.is_some();
+        event
     }
 
     /// Remove one specific global key bind.
@@ -1890,7 +5004,24 @@ impl SchedulerFrontend {
     }
 
     /// Exit the program gracefully. EzTerm makes several changes to the terminal to display the UI,
-    /// so if you do not exit gracefully it may leave the terminal in an unusable state.
+    /// so if you do not exit gracefully it may leave the terminal in an unusable state. If a
+    /// callback was set with [set_on_exit] it is called first (on the next frame), and it may
+    /// cancel the exit. Use [force_exit] to exit without calling it. The global quit keys
+    /// (escape and ctrl+c) exit through this method as well.
+    ///
+    /// The app never stops in the middle of a frame. Exiting happens in this order:
+    ///
+    /// 1. The callback set with [set_on_exit] is called on the next frame, if any. If it returns
+    ///    false the exit is cancelled.
+    /// 2. The frame in which the app exits is finished. From then on input is ignored, while
+    ///    scheduled tasks, property updates and redraws go on as usual.
+    /// 3. The callback set with [set_on_teardown] is called every frame until it returns true or
+    ///    the timeout of the [ExitTeardown] settings passes, e.g. to fade out the UI.
+    /// 4. Pending property writes are flushed: property changes are applied to the widgets and
+    ///    persisted properties are written to their settings files.
+    /// 5. A final frame is drawn, if enabled in the [ExitTeardown] settings.
+    /// 6. The terminal is restored, clearing the screen if enabled, and the exit message of the
+    ///    [ExitTeardown] settings is printed, if any.
     ///
     /// # Parameters:
     ///
@@ -1905,9 +5036,42 @@ impl SchedulerFrontend {
     ///
     /// scheduler.exit();
     /// ```
-    pub fn exit(&self) {
+    pub fn exit(&mut self) {
+        if self.backend.exit_started.is_some() {
+            return;
+        }
+        if self.synced {
+            self.exit_sender.as_ref().unwrap().send(false).unwrap();
+        } else if self.backend.on_exit.is_some() {
+            self.backend.exit_requested = true;
+        } else {
+            self.force_exit();
+        }
+    }
+
+    /// Exit the program gracefully without calling the callback set with [set_on_exit], e.g. after
+    /// the user confirmed they want to exit without saving. Only this UI is stopped: the terminal
+    /// is restored after the teardown (see [exit]), after which [run] exits the process and
+    /// [run_with_terminal] returns. Does nothing if the app is already exiting.
+    ///
+    /// # Parameters:
+    ///
+    /// This method takes no parameters.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use ez_term::*;
+    ///
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// scheduler.force_exit();
+    /// ```
+    pub fn force_exit(&mut self) {
         if !self.synced {
-            stop();
+            if self.backend.exit_started.is_none() {
+                self.backend.exit_started = Some(self.now());
+            }
         } else {
             self.sync_thread_sender
                 .as_ref()
@@ -1918,6 +5082,129 @@ impl SchedulerFrontend {
         }
     }
 
+    /// Set a callback that is called when the app is about to exit through [exit] or one of the
+    /// global quit keys, before the terminal is restored. Return true to exit, or false to cancel
+    /// the exit; e.g. to open a "Save changes?" modal that calls [force_exit] when the user
+    /// confirms. Replaces any callback set before.
+    ///
+    /// # Parameters:
+    ///
+    /// - Callback: Box<dyn FnMut(Context) -> bool>
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use ez_term::*;
+    ///
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// scheduler.set_on_exit(Box::new(|context: Context| {
+    ///     let unsaved_changes = context.scheduler.get_property("unsaved_changes").as_bool().value;
+    ///     if unsaved_changes {
+    ///         context.scheduler.open_modal("SaveChangesPopup", context.state_tree);
+    ///     }
+    ///     !unsaved_changes
+    /// }));
+    /// ```
+    pub fn set_on_exit(&mut self, callback: GenericFunction) {
+        self.backend.on_exit = Some(callback);
+    }
+
+    /// Set a callback that is called every frame while the app is exiting, after the exit can no
+    /// longer be cancelled, until it returns true or the timeout of the [ExitTeardown] settings
+    /// passes. Input is ignored meanwhile, but widgets are still redrawn, so it can be used to
+    /// show progress or fade out the UI. See [exit] for the order of the teardown steps.
+    ///
+    /// # Parameters:
+    ///
+    /// - Callback: Box<dyn FnMut(Context) -> bool>
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use ez_term::*;
+    ///
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// // Dim the root layout a bit every frame until it is black
+    /// scheduler.set_on_teardown(Box::new(|context: Context| {
+    ///     let state = context.state_tree.as_layout_mut();
+    ///     let level = match state.get_color_config().get_bg_color() {
+    ///         Color::Rgb { r, .. } => r.saturating_sub(16),
+    ///         _ => 0,
+    ///     };
+    ///     state.get_color_config_mut().bg_color.set(Color::Rgb { r: level, g: level, b: level });
+    ///     state.update(context.scheduler);
+    ///     level == 0
+    /// }));
+    /// ```
+    pub fn set_on_teardown(&mut self, callback: GenericFunction) {
+        self.backend.on_teardown = Some(callback);
+    }
+
+    /// Set what happens between the app exiting and the terminal being restored, see
+    /// [ExitTeardown] and [exit].
+    ///
+    /// # Parameters:
+    ///
+    /// - Teardown: [ExitTeardown]
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use ez_term::*;
+    ///
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// // Leave the last frame on the screen and say goodbye below it
+    /// scheduler.set_exit_teardown(ExitTeardown {
+    ///     timeout: Duration::from_millis(500),
+    ///     clear_screen: false,
+    ///     message: Some("Goodbye!".to_string()),
+    ///     ..Default::default()
+    /// });
+    /// ```
+    pub fn set_exit_teardown(&mut self, teardown: ExitTeardown) {
+        self.backend.exit_teardown = teardown;
+    }
+
+    /// Get what happens between the app exiting and the terminal being restored. See
+    /// [set_exit_teardown].
+    pub fn get_exit_teardown(&self) -> ExitTeardown {
+        self.backend.exit_teardown.clone()
+    }
+
+    /// Returns a bool representing whether the app is exiting, i.e. [force_exit] was called and
+    /// the teardown (see [exit]) has started.
+    pub fn is_exiting(&self) -> bool {
+        self.backend.exit_started.is_some()
+    }
+
+    /// Register a callback that is called when the app is asked to shut down by SIGINT or SIGTERM
+    /// (see the [signals] module), e.g. to save unsaved work. Callbacks
+    /// are called in the order they were registered, before the terminal is restored; the app
+    /// exits after the last callback returns.
+    ///
+    /// # Parameters:
+    ///
+    /// - Callback: Box<dyn FnMut(Context)>
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use ez_term::*;
+    ///
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// scheduler.add_shutdown_callback(Box::new(|context: Context| {
+    ///     // Save unsaved work
+    /// }));
+    /// ```
+    pub fn add_shutdown_callback(&mut self, callback: GenericTask) {
+        self.backend.shutdown_callbacks.push(callback);
+    }
+
     pub fn is_syncing(&self) -> bool {
         self.syncing != 0
     }
@@ -2014,6 +5301,14 @@ impl SchedulerFrontend {
         }
         if !something_to_do { return }
 
+        while let Ok((name, value)) = self
+            .thread_property_receiver
+            .as_ref()
+            .unwrap()
+            .try_recv()
+        {
+            self.set_thread_property(&name, value, state_tree);
+        }
         while let Ok((name, func, after)) = self.schedule_once_receiver.as_ref().unwrap().try_recv()
         {
             self.schedule_once(&name, func, after);
@@ -2059,14 +5354,29 @@ impl SchedulerFrontend {
         {
             self.update_callback_config(for_widget.as_str(), callback_config);
         }
-        while let Ok((new_widget, new_states)) =
+        while let Ok((new_widget, new_states, index)) =
             self.create_widget_receiver.as_ref().unwrap().try_recv()
         {
-            self.create_widget(new_widget, new_states, state_tree);
+            self.create_widget(new_widget, new_states, index, state_tree);
         }
         while let Ok(name) = self.remove_widget_receiver.as_ref().unwrap().try_recv() {
             self.remove_widget(name.as_str());
         }
+        while let Ok((source, new_id, parent)) =
+            self.clone_widget_receiver.as_ref().unwrap().try_recv()
+        {
+            self.clone_widget(&source, &new_id, &parent, state_tree);
+        }
+        while let Ok((name, new_parent, index)) =
+            self.move_widget_receiver.as_ref().unwrap().try_recv()
+        {
+            self.move_widget(&name, &new_parent, index);
+        }
+        while let Ok((layout, child_1, child_2)) =
+            self.swap_children_receiver.as_ref().unwrap().try_recv()
+        {
+            self.swap_children(&layout, &child_1, &child_2);
+        }
         while let Ok((name, value)) = self
             .new_usize_property_receiver
             .as_ref()
@@ -2113,6 +5423,14 @@ impl SchedulerFrontend {
         {
             self.new_layout_orientation_property(name.as_str(), value);
         }
+        while let Ok((name, value)) = self
+            .new_dock_position_property_receiver
+            .as_ref()
+            .unwrap()
+            .try_recv()
+        {
+            self.new_dock_position_property(name.as_str(), value);
+        }
         while let Ok((name, value)) = self
             .new_horizontal_alignment_property_receiver
             .as_ref()
@@ -2164,6 +5482,9 @@ impl SchedulerFrontend {
         while let Ok(name) = self.update_widget_receiver.as_ref().unwrap().try_recv() {
             self.update_widget(name.as_str());
         }
+        while let Ok(paths) = self.update_widgets_receiver.as_ref().unwrap().try_recv() {
+            self._update_widgets(paths);
+        }
         while let Ok(_) = self.force_redraw_receiver.as_ref().unwrap().try_recv() {
             self.force_redraw();
         }
@@ -2193,13 +5514,41 @@ impl SchedulerFrontend {
         while let Ok(_) = self.deselect_widget_receiver.as_ref().unwrap().try_recv() {
             self.deselect_widget();
         }
-        while let Ok(_) = self.exit_receiver.as_ref().unwrap().try_recv() {
-            self.exit();
+        while let Ok(force) = self.exit_receiver.as_ref().unwrap().try_recv() {
+            if force {
+                self.force_exit();
+            } else {
+                self.exit();
+            }
         }
         self.check_sync_properties();
         self.check_sync_state_tree(state_tree);
     }
 
+    /// Set a property value received from a thread on the main thread. Widget properties are set
+    /// on the main state tree, custom properties on the main scheduler. Setting the value sends
+    /// it through the channel of the property if it has one, so subscribers are updated as well.
+    fn set_thread_property(&mut self, name: &str, value: EzValues, state_tree: &mut StateTree) {
+        if let Some((path, property)) = name.rsplit_once('/') {
+            if let Some(state) = state_tree.try_get_mut(path) {
+                if state.as_generic_mut().update_property(property, value) {
+                    self.update_widget(path);
+                }
+            }
+        } else if let Some(property) = self.backend.properties.get_mut(name) {
+            property.set_from_ez_value(value);
+        }
+    }
+
+    /// Get the senders through which properties set in a thread sync their new values to the
+    /// main thread. Only available on a synced scheduler.
+    pub(crate) fn thread_property_senders(&self) -> Option<ThreadPropertySenders> {
+        Some((
+            self.thread_property_sender.clone()?,
+            self.sync_thread_sender.clone()?,
+        ))
+    }
+
     /// Method to stop a synced scheduler. No need to use this as an end-user; schedule_threaded
     /// will do the work for you.
     pub fn _stop_sync_to_thread(&mut self) {
@@ -2212,6 +5561,7 @@ impl SchedulerFrontend {
         let mut synced_frontend = SchedulerFrontend::default();
         self.syncing += 1;
         synced_frontend.synced = true;
+        synced_frontend.background_tasks = self.background_tasks.clone();
 
         if self.sync_thread_receiver.is_none() {
             let (sender, receiver) = channel();
@@ -2220,6 +5570,13 @@ impl SchedulerFrontend {
         }
         synced_frontend.sync_thread_sender = self.sync_thread_sender.clone();
 
+        if self.thread_property_receiver.is_none() {
+            let (sender, receiver) = channel();
+            self.thread_property_receiver = Some(receiver);
+            self.thread_property_sender = Some(sender.clone());
+        }
+        synced_frontend.thread_property_sender = self.thread_property_sender.clone();
+
         if self.schedule_once_receiver.is_none() {
             let (sender, receiver) = channel();
             self.schedule_once_receiver = Some(receiver);
@@ -2312,6 +5669,27 @@ impl SchedulerFrontend {
         }
         synced_frontend.remove_widget_sender = self.remove_widget_sender.clone();
 
+        if self.clone_widget_receiver.is_none() {
+            let (sender, receiver) = channel();
+            self.clone_widget_receiver = Some(receiver);
+            self.clone_widget_sender = Some(sender.clone());
+        }
+        synced_frontend.clone_widget_sender = self.clone_widget_sender.clone();
+
+        if self.move_widget_receiver.is_none() {
+            let (sender, receiver) = channel();
+            self.move_widget_receiver = Some(receiver);
+            self.move_widget_sender = Some(sender.clone());
+        }
+        synced_frontend.move_widget_sender = self.move_widget_sender.clone();
+
+        if self.swap_children_receiver.is_none() {
+            let (sender, receiver) = channel();
+            self.swap_children_receiver = Some(receiver);
+            self.swap_children_sender = Some(sender.clone());
+        }
+        synced_frontend.swap_children_sender = self.swap_children_sender.clone();
+
         if self.new_usize_property_receiver.is_none() {
             let (sender, receiver) = channel();
             self.new_usize_property_receiver = Some(receiver);
@@ -2363,6 +5741,14 @@ impl SchedulerFrontend {
         synced_frontend.new_layout_orientation_property_sender =
             self.new_layout_orientation_property_sender.clone();
 
+        if self.new_dock_position_property_receiver.is_none() {
+            let (sender, receiver) = channel();
+            self.new_dock_position_property_receiver = Some(receiver);
+            self.new_dock_position_property_sender = Some(sender.clone());
+        }
+        synced_frontend.new_dock_position_property_sender =
+            self.new_dock_position_property_sender.clone();
+
         if self.new_horizontal_alignment_property_receiver.is_none() {
             let (sender, receiver) = channel();
             self.new_horizontal_alignment_property_receiver = Some(receiver);
@@ -2416,6 +5802,13 @@ impl SchedulerFrontend {
         }
         synced_frontend.update_widget_sender = self.update_widget_sender.clone();
 
+        if self.update_widgets_receiver.is_none() {
+            let (sender, receiver) = channel();
+            self.update_widgets_receiver = Some(receiver);
+            self.update_widgets_sender = Some(sender.clone());
+        }
+        synced_frontend.update_widgets_sender = self.update_widgets_sender.clone();
+
         if self.force_redraw_receiver.is_none() {
             let (sender, receiver) = channel();
             self.force_redraw_receiver = Some(receiver);
@@ -2486,6 +5879,7 @@ impl SchedulerFrontend {
 
         synced_frontend.backend.properties = self.backend.properties.clone();
         synced_frontend.backend.templates = self.backend.templates.clone();
+        synced_frontend.backend.groups = self.backend.groups.clone();
         synced_frontend
     }
 }
@@ -2500,10 +5894,36 @@ pub struct Scheduler {
     /// Cool down period for keyboard events, to prevent spam events.
     pub keyboard_cooldown: u64,
 
+    /// Settings for speeding up held keys. Use [set_key_acceleration] for this.
+    pub key_acceleration: KeyAcceleration,
+
+    /// The key that is currently held down. See [get_key_repeat_steps].
+    pub key_repeat: KeyRepeat,
+
     /// List of widgets that will be redrawn on the next frame. Don't use this directly, use
     /// [update_widget] or EzState.update instead.
     pub widgets_to_update: Vec<String>,
 
+    /// List of <Widget path, Cells> that will be partially redrawn on the next frame. Use
+    /// [update_widget_cells] for this.
+    pub cells_to_update: Vec<(String, Vec<Coordinates>)>,
+
+    /// Background updates that did not fit in the budget of an earlier frame, oldest first. See
+    /// [set_background_update_budget].
+    pub deferred_updates: Vec<String>,
+
+    /// Time each frame may spend on redrawing background updates. See
+    /// [set_background_update_budget].
+    pub background_update_budget: Duration,
+
+    /// Whether a property changed, a task ran or input was handled since the last frame. Frames
+    /// in which this is false and no widgets have to be redrawn skip the draw pass entirely.
+    pub dirty: bool,
+
+    /// Widget updates collected during a [StateTree.transaction]. None if no transaction is
+    /// running.
+    pub transaction_updates: Option<Vec<String>>,
+
     /// If true, the entire screen will be redrawn on the next frame. Only differences compared to
     /// the previous frame. Use [force_redraw] to set this.
     pub force_redraw: bool,
@@ -2533,12 +5953,35 @@ pub struct Scheduler {
     /// Templates defined in the .ez files. Used by [create_widget]
     pub templates: Templates,
 
+    /// Widgets created from templates by path, so they can be created again when their template
+    /// is reloaded. See [reload_template].
+    pub template_instances: HashMap<String, TemplateInstance>,
+
+    /// Paths of the template instances currently being parsed, innermost last.
+    pub instantiating: Vec<String>,
+
+    /// List of <Layout path, Definitions> of the content of lazy layouts that were not shown yet.
+    /// The content is created the first time the layout is the active tab or screen.
+    pub lazy_widgets: Vec<(String, Vec<EzWidgetDefinition>)>,
+
     /// List of new widgets that will be created on the next frame. Use [create_widget] for this.
-    pub widgets_to_create: Vec<EzObjects>,
+    pub widgets_to_create: Vec<(EzObjects, Option<usize>)>,
 
     /// List of new widgets that will be removed on the next frame. Use [remove_widget] for this.
     pub widgets_to_remove: Vec<String>,
 
+    /// List of <Source path, New path>. The widgets will be cloned on the next frame. Use
+    /// [clone_widget] for this.
+    pub widgets_to_clone: Vec<(String, String)>,
+
+    /// List of <Widget, New parent, Index>. The widgets will be moved on the next frame. Use
+    /// [move_widget] for this.
+    pub widgets_to_move: Vec<(String, String, Option<usize>)>,
+
+    /// List of <Layout, Child, Child>. The positions of the children will be swapped on the next
+    /// frame. Use [swap_children] for this.
+    pub children_to_swap: Vec<(String, String, String)>,
+
     /// List of <Widget path, [CallbackConfig]. Every frame this list is checked, and the widget
     /// belonging to the widget path will have its' [CallbackConfig] replaced with the new one.
     pub new_callback_configs: Vec<(String, CallbackConfig)>,
@@ -2589,12 +6032,215 @@ pub struct Scheduler {
 
     /// Entire global keymap will be cleared on the next frame.
     pub clear_global_keymap: bool,
+
+    /// List of <Name, [Theme]> registered by the user. Use [register_theme] for this.
+    pub themes: Vec<(String, Theme)>,
+
+    /// Index of the theme that was last applied, if any.
+    pub current_theme: Option<usize>,
+
+    /// Called when the terminal window gains focus. Use [set_on_focus] for this.
+    pub on_focus: Option<GenericFunction>,
+
+    /// Called when the terminal window loses focus. Use [set_on_blur] for this.
+    pub on_blur: Option<GenericFunction>,
+
+    /// Called once when there has been no input for [idle_timeout]. Use [set_on_idle] for this.
+    pub on_idle: Option<GenericFunction>,
+
+    /// How long there must be no input before on_idle callbacks are called. Use
+    /// [set_idle_timeout] for this.
+    pub idle_timeout: Duration,
+
+    /// Time of the last input, None before the first frame.
+    pub last_activity: Option<Instant>,
+
+    /// IDs or paths of widgets that were given an on_idle callback.
+    pub idle_widgets: Vec<String>,
+
+    /// On_idle callbacks that were called since the last input, by widget path ("" for the
+    /// callback set with [set_on_idle]).
+    pub idle_fired: Vec<String>,
+
+    /// Called when text is pasted and the selected widget (if any) did not consume it. Use
+    /// [set_on_paste] for this.
+    pub on_paste: Option<PasteCallbackFunction>,
+
+    /// Called with every event before it is routed to widgets. Use [set_input_filter] for this.
+    pub input_filter: Option<InputFilterFunction>,
+
+    /// Payload of the drag and drop that is currently in progress, if any. Set by the input handler
+    /// from an on_drag_start callback; use [get_drag_payload] to read it.
+    pub drag_payload: Option<DragPayload>,
+
+    /// Sources of events other than the terminal. Use [add_input_source] for this.
+    pub input_sources: Vec<Box<dyn InputSource>>,
+
+    /// Handle to the input source created by [get_event_injector], if any.
+    pub event_injector: Option<EventInjector>,
+
+    /// Paths of buttons that are currently held down, checked every frame for long presses and
+    /// releases.
+    pub pressed_buttons: Vec<String>,
+
+    /// Path of the widget under the mouse, which is drawn in its' hover colors. Also set when the
+    /// widget has no hover callbacks, unlike the hovered widget of the run loop.
+    pub mouse_hovered_widget: Option<String>,
+
+    /// Path of the widget the left mouse button was pushed down on, which is drawn in its'
+    /// pressed colors until the mouse button is released.
+    pub mouse_held_widget: Option<String>,
+
+    /// When the spinners of busy layouts last moved to their next frame. None while no layout is
+    /// busy.
+    pub last_busy_frame: Option<Instant>,
+
+    /// Source of time used to decide when scheduled tasks run. None means the system clock is
+    /// used. Use [set_clock] for this.
+    pub clock: Option<Box<dyn Clock>>,
+
+    /// The event that is currently being handled, if any. Passed to the [Context] of callbacks.
+    pub current_event: Option<Event>,
+
+    /// Routing control of the event that is currently being handled. See [EventControl].
+    pub event_control: EventControl,
+
+    /// Set when the terminal is too small to show the UI with borders, padding and collapsible
+    /// layouts; holds the original widget values to restore.
+    pub compact_mode: Option<CompactMode>,
+
+    /// Set to the required size when the terminal is too small to show the UI even in compact
+    /// mode. A "terminal too small" message is shown instead of the UI while this is set.
+    pub terminal_too_small: Option<Size>,
+
+    /// Screens that were shown before each call to [show_screen] as (screen layout path, screen
+    /// ID), used by [go_back].
+    pub screen_history: Vec<(String, String)>,
+
+    /// Resolvers of the handles returned by [open_modal_with_result], as (number of open modals
+    /// right after the modal was opened, resolver). A handle is resolved once fewer modals are
+    /// open. Ordered from the bottom modal to the top one.
+    pub modal_result_resolvers: Vec<(usize, ModalResultResolver)>,
+
+    /// Results set by modals through [set_modal_result], by the number of open modals at the time
+    /// (i.e. the depth of the modal that set it).
+    pub modal_results: HashMap<usize, Box<dyn Any + Send>>,
+
+    /// Widgets to render to plain text after the next frame is drawn, as (widget path, callback).
+    /// See [render_widget_to_string].
+    pub widgets_to_render: Vec<(String, RenderCallback)>,
+
+    /// Tasks that widgets can refresh themselves with, by name. Use [register_refresh_task] for
+    /// this.
+    pub refresh_tasks: HashMap<String, GenericRecurringTask>,
+
+    /// Actions by name. Use [register_action] for this.
+    pub actions: HashMap<String, Action>,
+
+    /// Full paths of the widgets bound to each action by action name. Use [bind_action] or the
+    /// "action" property for this.
+    pub action_widgets: HashMap<String, Vec<String>>,
+
+    /// Names of actions whose widgets are updated on the next frame.
+    pub actions_to_refresh: Vec<String>,
+
+    /// Full paths of widgets by group name. Use [add_to_group] or the "group" property for this.
+    pub groups: HashMap<String, Vec<String>>,
+
+    /// Initial values of forms tracked with [track_dirty] by full path of the form
+    pub dirty_forms: HashMap<String, StateSnapshot>,
+
+    /// Full paths of forms with the "track_dirty" property that are tracked once the UI is loaded
+    pub dirty_forms_to_track: Vec<String>,
+
+    /// Custom properties computed from other properties. Use [new_computed_property] for this.
+    pub computed_properties: Vec<ComputedProperty>,
+
+    /// Timings of the phases of loading and starting the UI. See [get_startup_profile].
+    pub startup_profile: StartupProfile,
+
+    /// State of layout tuning mode if it is active. See [start_layout_tuning].
+    pub layout_tuning: Option<LayoutTuning>,
+
+    /// Names of the properties persisted through [persist_property] by settings file path.
+    pub persisted_properties: HashMap<String, Vec<String>>,
+
+    /// Name and events of the macro that is currently being recorded, if any. Use
+    /// [start_macro_recording] for this.
+    pub macro_recording: Option<(String, Vec<Event>)>,
+
+    /// Recorded macros by name.
+    pub macros: HashMap<String, Vec<Event>>,
+
+    /// Events of played macros that are waiting to be replayed, one event per frame.
+    pub macro_events: VecDeque<Event>,
+
+    /// Whether the event that is currently being handled is replayed from a macro. Replayed events
+    /// are not recorded.
+    pub replaying_macro: bool,
+
+    /// Commands that can be undone and redone. Use [execute_undoable] for this.
+    pub undo_history: UndoHistory,
+
+    /// Channel through which control servers pass received commands to the main thread. Use
+    /// [start_control_server] or [start_control_socket] for this.
+    #[cfg(feature = "control")]
+    pub control_requests: Option<(Sender<ControlRequest>, Receiver<ControlRequest>)>,
+
+    /// Runtime running the futures scheduled with [schedule_async]; started on first use.
+    #[cfg(feature = "tokio")]
+    pub async_runtime: Option<tokio::runtime::Runtime>,
+
+    /// Futures scheduled with [schedule_async] whose output was not delivered yet.
+    #[cfg(feature = "tokio")]
+    pub async_tasks: Vec<AsyncTask>,
+
+    /// Called before the app exits because of SIGINT or SIGTERM. Use [add_shutdown_callback] for
+    /// this.
+    pub shutdown_callbacks: Vec<GenericTask>,
+
+    /// Receives the signals caught while the UI is running, see the [signals] module.
+    #[cfg(unix)]
+    pub signal_receiver: Option<Receiver<i32>>,
+
+    /// Called before the app exits through [exit]; may cancel the exit. Use [set_on_exit] for this.
+    pub on_exit: Option<GenericFunction>,
+
+    /// Whether [exit] was called since the last frame while an [on_exit] callback is set.
+    pub exit_requested: bool,
+
+    /// The terminal cursor as currently shown, if a text input uses it. See
+    /// [TextInputState.set_terminal_cursor].
+    pub terminal_cursor: Option<TerminalCursor>,
+
+    /// Whether the terminal was cleared outside of the UI, e.g. while the app was suspended. The
+    /// whole screen is rewritten on the next forced redraw.
+    pub screen_cleared: bool,
+
+    /// Whether ctrl+z was pressed since the last frame. The app is suspended by the run loop.
+    pub suspend_requested: bool,
+
+    /// When the app exited through [force_exit]. Input is ignored from then on while the app tears
+    /// down, see [exit].
+    pub exit_started: Option<Instant>,
+
+    /// Called every frame while the app tears down. Use [set_on_teardown] for this.
+    pub on_teardown: Option<GenericFunction>,
+
+    /// What happens between exiting and restoring the terminal. Use [set_exit_teardown] for this.
+    pub exit_teardown: ExitTeardown,
+
+    /// Whether the teardown is done. The run loop draws the final frame and restores the
+    /// terminal of this UI on the next frame.
+    pub stopped: bool,
 }
 impl Scheduler {
     pub fn new() -> Self {
         let mut scheduler = Scheduler::default();
         scheduler.tick_rate = ((1.0 / 60.0) * 1000.0) as u64;
         scheduler.keyboard_cooldown = 50;
+        scheduler.background_update_budget = Duration::from_millis(8);
+        scheduler.idle_timeout = Duration::from_secs(60);
         scheduler
     }
 }
@@ -2619,13 +6265,13 @@ pub struct Task {
 }
 
 impl Task {
-    pub fn new(name: String, func: GenericTask, delay: Duration) -> Self {
+    pub fn new(name: String, func: GenericTask, delay: Duration, created: Instant) -> Self {
         Task {
             name,
             func,
             delay,
             canceled: false,
-            created: Instant::now(),
+            created,
         }
     }
 
@@ -2,16 +2,30 @@
 //!
 //! This module contains supporting functions for the [Scheduler] struct.
 use std::collections::HashMap;
+use std::fs::{File, Metadata};
+use std::io::{Read, Seek, SeekFrom};
 use std::mem::replace;
+#[cfg(unix)]
+use std::os::unix::fs::MetadataExt;
 use std::thread::{spawn, JoinHandle};
-use std::time::Instant;
+use std::time::Duration;
+#[cfg(not(unix))]
+use std::time::SystemTime;
 
+use crate::property::ez_property::set_thread_property_senders;
 use crate::run::definitions::{CallbackTree, StateTree};
-use crate::run::select::{deselect_widget, select_widget};
+use crate::run::tree::ViewTree;
+use crate::run::select::{deselect_widget, get_selected_path, select_widget, widget_is_busy};
 use crate::scheduler::definitions::{CustomDataMap, ThreadedContext};
+use crate::scheduler::dirty::get_dirty_property_name;
+use crate::scheduler::persistence::save_settings;
 use crate::scheduler::scheduler::SchedulerFrontend;
+use crate::states::button_state::ButtonState;
+use crate::states::ez_state::{EzState, GenericState};
+use crate::states::layout_state::LayoutState;
+use crate::widgets::button::{Button, KEY_RELEASE_TIMEOUT};
 use crate::widgets::ez_object::EzObjects;
-use crate::widgets::layout::layout::Layout;
+use crate::widgets::layout::layout::{Layout, BUSY_SPINNER_INTERVAL};
 use crate::{CallbackConfig, Context, CustomData, EzObject, KeyMap, LayoutMode};
 
 /// Check if any new thread are ready to be spawned, or if any spawned threads are ready to be
@@ -27,12 +41,15 @@ pub fn update_threads(scheduler: &mut SchedulerFrontend, state_tree: &mut StateT
 pub fn start_new_threads(scheduler: &mut SchedulerFrontend, state_tree: &mut StateTree) {
     while !scheduler.backend.threads_to_start.is_empty() {
         let (thread_func, on_finish) = scheduler.backend.threads_to_start.pop().unwrap();
-        let context = ThreadedContext::new(
-            "".to_string(),
-            state_tree.clone(),
-            scheduler._sync_to_thread(),
-        );
-        let handle: JoinHandle<()> = spawn(move || thread_func(context));
+        let synced_frontend = scheduler._sync_to_thread();
+        let senders = synced_frontend.thread_property_senders();
+        let context = ThreadedContext::new("".to_string(), state_tree.clone(), synced_frontend);
+        let handle: JoinHandle<()> = spawn(move || {
+            if let Some(senders) = senders {
+                set_thread_property_senders(senders);
+            }
+            thread_func(context)
+        });
         scheduler.backend.thread_handles.push((handle, on_finish))
     }
 }
@@ -53,6 +70,8 @@ pub fn check_finished_threads(scheduler: &mut SchedulerFrontend, state_tree: &mu
             func(context);
         }
         handle.join().unwrap();
+        // Receive whatever the thread sent after the last check, before no longer syncing
+        scheduler._check_method_channels(state_tree);
         scheduler._stop_sync_to_thread();
     }
 }
@@ -60,6 +79,7 @@ pub fn check_finished_threads(scheduler: &mut SchedulerFrontend, state_tree: &mu
 /// Check if any scheduled tasks are ready to be run, or if any RunOnce tasks were scheduled by the
 /// user.
 pub fn run_tasks(scheduler: &mut SchedulerFrontend, state_tree: &mut StateTree, custom_data: &mut CustomDataMap) {
+    let now = scheduler.now();
     let mut remaining_tasks = Vec::new();
     while !scheduler.backend.tasks.is_empty() {
         let mut task = scheduler.backend.tasks.remove(0);
@@ -68,9 +88,10 @@ pub fn run_tasks(scheduler: &mut SchedulerFrontend, state_tree: &mut StateTree,
         }
         let context = Context::new(String::new(), state_tree, scheduler, custom_data);
 
-        let elapsed = task.created.elapsed();
+        let elapsed = now.saturating_duration_since(task.created);
         if elapsed >= task.delay {
             (task.func)(context);
+            scheduler.backend.dirty = true;
         } else {
             remaining_tasks.push(task);
         }
@@ -86,11 +107,12 @@ pub fn run_tasks(scheduler: &mut SchedulerFrontend, state_tree: &mut StateTree,
         }
 
         if let Some(time) = task.last_execution {
-            let elapsed = time.elapsed();
+            let elapsed = now.saturating_duration_since(time);
             // Interval elapsed, execute task and reschedule if it returned true
             if elapsed >= task.interval {
                 let result = (task.func)(context);
-                task.last_execution = Some(Instant::now());
+                scheduler.backend.dirty = true;
+                task.last_execution = Some(now);
                 if result {
                     remaining_tasks.push(task);
                 }
@@ -101,7 +123,8 @@ pub fn run_tasks(scheduler: &mut SchedulerFrontend, state_tree: &mut StateTree,
         // Task has not been executed before, do so immediately
         } else {
             let result = (task.func)(context);
-            task.last_execution = Some(Instant::now());
+            scheduler.backend.dirty = true;
+            task.last_execution = Some(now);
             if result {
                 remaining_tasks.push(task);
             }
@@ -118,7 +141,7 @@ pub fn create_new_widgets(
 ) {
     let widgets_to_create = scheduler.backend.widgets_to_create.clone();
     scheduler.backend.widgets_to_create.clear();
-    for new_widget in widgets_to_create {
+    for (new_widget, index) in widgets_to_create {
         let widget_path = new_widget.as_ez_object().get_path();
         let (parent_path, _) = widget_path.rsplit_once('/').unwrap();
 
@@ -128,10 +151,10 @@ pub fn create_new_widgets(
                 callback_tree.add_node(child.as_ez_object().get_path(), CallbackConfig::default());
             }
         }
-        if parent_path == "/root" {
-            root_widget.add_child(new_widget, scheduler);
+        let parent = if parent_path == "/root" {
+            &mut *root_widget
         } else {
-            let parent = root_widget
+            root_widget
                 .get_child_by_path_mut(parent_path)
                 .unwrap_or_else(|| {
                     panic!(
@@ -139,14 +162,221 @@ pub fn create_new_widgets(
                         parent_path
                     )
                 })
-                .as_layout_mut();
+                .as_layout_mut()
+        };
+        if let Some(index) = index {
+            parent.insert_child(new_widget, index, scheduler);
+        } else {
             parent.add_child(new_widget, scheduler);
+        }
+
+        scheduler.force_redraw();
+    }
+}
+
+/// Create the states of a new widget and its' children, for [SchedulerFrontend.create_widget].
+pub fn get_new_states(new_widget: &EzObjects) -> StateTree {
+    let id = new_widget.as_ez_object().get_id();
+    let mut new_states = StateTree::new(id.clone(), new_widget.as_ez_object().get_state());
+    if let EzObjects::Layout(ref i) = new_widget {
+        for child in i.get_widgets_recursive() {
+            let relative_path = child
+                .as_ez_object()
+                .get_path()
+                .split_once(id.as_str())
+                .unwrap()
+                .1
+                .to_string();
+            let widget_path = format!("{}{}", id, relative_path);
+            new_states.add_node(widget_path, child.as_ez_object().get_state());
+        }
+    }
+    new_states
+}
+
+/// Create the content of lazy layouts that became the active tab or screen. Layouts that were
+/// removed before they were shown are forgotten.
+pub fn load_lazy_widgets(scheduler: &mut SchedulerFrontend, state_tree: &mut StateTree) {
+    let mut i = 0;
+    // Content can contain lazy layouts itself, which are added to the end of the list
+    while i < scheduler.backend.lazy_widgets.len() {
+        let path = &scheduler.backend.lazy_widgets[i].0;
+        match is_lazy_layout_shown(state_tree, path) {
+            Some(false) => {
+                i += 1;
+                continue;
+            }
+            Some(true) => (),
+            None => {
+                scheduler.backend.lazy_widgets.remove(i);
+                continue;
+            }
+        }
+        let (path, definitions) = scheduler.backend.lazy_widgets.remove(i);
+        for (order, mut definition) in definitions.into_iter().enumerate() {
+            let new_widget = definition
+                .parse(scheduler, path.clone(), order, None)
+                .unwrap_or_else(|e| panic!("Could not load lazy widget: {}", e));
+            let new_states = get_new_states(&new_widget);
+            scheduler.create_widget(new_widget, new_states, None, state_tree);
+        }
+    }
+}
+
+/// Whether a lazy layout is the active tab or screen of its' parent. Layouts that are not in a
+/// tab or screen layout are always shown. None if the layout does not exist (anymore).
+fn is_lazy_layout_shown(state_tree: &StateTree, path: &str) -> Option<bool> {
+    state_tree.try_get(path)?;
+    let (parent, id) = path.rsplit_once('/')?;
+    let parent = match state_tree.try_get(parent) {
+        Some(i) => i.obj.as_layout(),
+        None => return Some(true),
+    };
+    Some(match parent.get_mode() {
+        LayoutMode::Tab => parent.get_active_tab() == path || parent.get_active_tab() == id,
+        LayoutMode::Screen => parent.get_active_screen() == id,
+        _ => true,
+    })
+}
+
+/// Check if there are any widgets to clone. The states of the clones were already created by
+/// [SchedulerFrontend.clone_widget]; here the widget objects are created and queued for creation.
+pub fn clone_widgets(
+    scheduler: &mut SchedulerFrontend,
+    root_widget: &Layout,
+    state_tree: &StateTree,
+) {
+    let widgets_to_clone = scheduler.backend.widgets_to_clone.clone();
+    scheduler.backend.widgets_to_clone.clear();
+    for (source_path, new_path) in widgets_to_clone {
+        // The source might have been created on this same frame
+        let source = if let Some(i) = root_widget.get_child_by_path(&source_path) {
+            i.clone()
+        } else if let Some(i) = scheduler
+            .backend
+            .widgets_to_create
+            .iter()
+            .find(|(x, _)| x.as_ez_object().get_path() == source_path)
+        {
+            i.0.clone()
+        } else {
+            panic!(
+                "Could not clone widget: {}. It could not be found.",
+                source_path
+            )
         };
+        let new_id = new_path.rsplit_once('/').unwrap().1.to_string();
+        let new_widget = source.clone_to_path(new_id, new_path, state_tree, scheduler);
+        scheduler.backend.widgets_to_create.push((new_widget, None));
+    }
+}
+
+/// Check if there are any widgets to move to a different layout or position. States are recreated
+/// under the new path with their current values, and bindings and callbacks are carried over.
+pub fn move_widgets(
+    scheduler: &mut SchedulerFrontend,
+    root_widget: &mut Layout,
+    state_tree: &mut StateTree,
+    callback_tree: &mut CallbackTree,
+    selected_widget: &mut String,
+) {
+    let widgets_to_move = scheduler.backend.widgets_to_move.clone();
+    scheduler.backend.widgets_to_move.clear();
+    for (name, new_parent, index) in widgets_to_move {
+        let old_path = state_tree.get(&name).as_generic().get_path().clone();
+        let new_parent_path = state_tree.get(&new_parent).as_generic().get_path().clone();
+        let (old_parent_path, id) = old_path.rsplit_once('/').unwrap();
+        let (old_parent_path, id) = (old_parent_path.to_string(), id.to_string());
+        let new_path = format!("{}/{}", new_parent_path, id);
+        if new_parent_path == old_path || new_parent_path.starts_with(&format!("{}/", old_path)) {
+            panic!("Cannot move widget {} into itself", old_path)
+        }
+
+        let old_parent = get_layout_by_path_mut(root_widget, &old_parent_path);
+        let widget = old_parent
+            .get_child(&id)
+            .unwrap_or_else(|| {
+                panic!("Could not move widget: {}. It could not be found.", old_path)
+            })
+            .clone();
+        old_parent.remove_child(&id);
+        scheduler.update_widget(&old_parent_path);
+
+        if old_parent_path == new_parent_path {
+            let index = index.unwrap_or(usize::MAX);
+            get_layout_by_path_mut(root_widget, &new_parent_path)
+                .insert_child(widget, index, scheduler);
+            scheduler.force_redraw();
+            continue;
+        }
+
+        // Recreate states under the new path. Parents must be added before their children.
+        let old_states = state_tree.remove_node(old_path.clone());
+        let mut old_states: Vec<EzState> = old_states.get_all().into_iter().cloned().collect();
+        old_states.sort_by_key(|x| x.as_generic().get_path().matches('/').count());
+        let mut new_states: Option<StateTree> = None;
+        for old_state in old_states.iter() {
+            let old_state_path = old_state.as_generic().get_path().clone();
+            let relative_path = old_state_path.strip_prefix(&old_path).unwrap().to_string();
+            let new_state_path = format!("{}{}", new_path, relative_path);
+            let mut new_state = old_state.clone_to_path(new_state_path.clone(), scheduler);
+            scheduler._move_property_bindings(&old_state_path, &new_state_path);
+            if selected_widget == &old_state_path {
+                new_state.as_generic_mut().set_selected(true);
+                *selected_widget = new_state_path.clone();
+            }
+            if let Some(ref mut tree) = new_states {
+                tree.add_node(format!("{}{}", id, relative_path), new_state);
+            } else {
+                new_states = Some(StateTree::new(id.clone(), new_state));
+            }
+        }
+        for old_state in old_states {
+            old_state.as_generic().clean_up_properties(scheduler);
+        }
+        state_tree.extend(new_path.clone(), new_states.unwrap());
+
+        if callback_tree.try_get(&old_path).is_some() {
+            let callbacks = callback_tree.remove_node(old_path.clone());
+            callback_tree.extend(new_path.clone(), callbacks);
+        }
+
+        let new_widget = widget.clone_to_path(id, new_path, state_tree, scheduler);
+        let index = index.unwrap_or(usize::MAX);
+        get_layout_by_path_mut(root_widget, &new_parent_path)
+            .insert_child(new_widget, index, scheduler);
+        scheduler.update_widget(&new_parent_path);
+        scheduler.force_redraw();
+    }
+}
 
+/// Check if there are any children of layouts that should swap positions.
+pub fn swap_children(
+    scheduler: &mut SchedulerFrontend,
+    root_widget: &mut Layout,
+    state_tree: &StateTree,
+) {
+    let children_to_swap = scheduler.backend.children_to_swap.clone();
+    scheduler.backend.children_to_swap.clear();
+    for (layout, child_1, child_2) in children_to_swap {
+        let path = state_tree.get(&layout).as_generic().get_path().clone();
+        get_layout_by_path_mut(root_widget, &path).swap_children(&child_1, &child_2);
+        scheduler.update_widget(&path);
         scheduler.force_redraw();
     }
 }
 
+/// Get a mutable ref to a layout by its' full path, including the root layout itself.
+fn get_layout_by_path_mut<'a>(root_widget: &'a mut Layout, path: &str) -> &'a mut Layout {
+    if path == "/root" {
+        return root_widget;
+    }
+    root_widget
+        .get_child_by_path_mut(path)
+        .unwrap_or_else(|| panic!("Could not find layout: {}", path))
+        .as_layout_mut()
+}
+
 pub fn remove_widgets(
     scheduler: &mut SchedulerFrontend,
     root_widget: &mut Layout,
@@ -157,21 +387,25 @@ pub fn remove_widgets(
         let name = scheduler.backend.widgets_to_remove.pop().unwrap();
         let full_path = state_tree.get(&name).as_generic().get_path().clone();
 
-        for widget in scheduler.backend.widgets_to_create.iter() {
+        for (widget, _) in scheduler.backend.widgets_to_create.iter() {
             if widget.as_ez_object().get_path() == full_path {
                 continue 'outer
             }
         }
         let (parent, id) = full_path.rsplit_once('/').unwrap();
-        let parent_widget = root_widget
-            .get_child_by_path_mut(parent)
-            .unwrap_or_else(|| {
-                panic!(
-                    "Could not remove widget: {}. It could not be found.",
-                    full_path
-                )
-            })
-            .as_layout_mut();
+        let parent_widget = if parent == root_widget.path {
+            &mut *root_widget
+        } else {
+            root_widget
+                .get_child_by_path_mut(parent)
+                .unwrap_or_else(|| {
+                    panic!(
+                        "Could not remove widget: {}. It could not be found.",
+                        full_path
+                    )
+                })
+                .as_layout_mut()
+        };
 
         parent_widget.remove_child(id);
         let parent_state = state_tree.get_mut(parent).as_layout();
@@ -185,15 +419,33 @@ pub fn remove_widgets(
                 .get_path()
                 .clone();
             parent_widget.remove_child(&header_id);
-            let removed = state_tree.remove_node(header_path);
+            let removed = state_tree.remove_node(header_path.clone());
             removed.obj.as_generic().clean_up_properties(scheduler);
+            cancel_widget_tasks(scheduler, &header_path);
         }
         scheduler.update_widget(parent_widget.get_path().as_str());
 
         let removed_state = state_tree.remove_node(full_path.clone());
         for child in removed_state.get_all() {
             child.as_generic().clean_up_properties(scheduler);
+            clean_up_property(scheduler, &get_dirty_property_name(child.as_generic().get_path()));
+            cancel_widget_tasks(scheduler, child.as_generic().get_path());
         }
+        let prefix = format!("{}/", full_path);
+        scheduler
+            .backend
+            .template_instances
+            .retain(|path, _| path != &full_path && !path.starts_with(&prefix));
+        for members in scheduler.backend.groups.values_mut() {
+            members.retain(|path| path != &full_path && !path.starts_with(&prefix));
+        }
+        for widgets in scheduler.backend.action_widgets.values_mut() {
+            widgets.retain(|path| path != &full_path && !path.starts_with(&prefix));
+        }
+        scheduler
+            .backend
+            .dirty_forms
+            .retain(|path, _| path != &full_path && !path.starts_with(&prefix));
         callback_tree.remove_node(full_path.clone());
     }
 }
@@ -206,6 +458,7 @@ pub fn update_callback_configs(
 ) {
     while !scheduler.backend.new_callback_configs.is_empty() {
         let (path, callback_config) = scheduler.backend.new_callback_configs.remove(0);
+        register_idle_widget(scheduler, &path, &callback_config);
         if let Some(i) = callback_tree.try_get_mut(&path) {
             i.obj = callback_config;
         } else {
@@ -214,6 +467,7 @@ pub fn update_callback_configs(
     }
     while !scheduler.backend.updated_callback_configs.is_empty() {
         let (path_or_id, callback_config) = scheduler.backend.updated_callback_configs.remove(0);
+        register_idle_widget(scheduler, &path_or_id, &callback_config);
         callback_tree
             .get_mut(&path_or_id)
             .obj
@@ -234,6 +488,151 @@ pub fn update_callback_configs(
     }
 }
 
+/// Remember widgets with an on_idle callback, so they can be checked every frame without going
+/// through the whole callback tree.
+fn register_idle_widget(scheduler: &mut SchedulerFrontend, widget: &str, config: &CallbackConfig) {
+    if config.on_idle.is_some() && !scheduler.backend.idle_widgets.iter().any(|x| x == widget) {
+        scheduler.backend.idle_widgets.push(widget.to_string());
+    }
+}
+
+/// Call the on_idle callbacks of which the idle timeout passed since the last input. Each callback
+/// is called once until there is new input.
+pub fn update_idle(
+    scheduler: &mut SchedulerFrontend,
+    state_tree: &mut StateTree,
+    callback_tree: &mut CallbackTree,
+    custom_data: &mut CustomDataMap,
+) {
+    if scheduler.backend.on_idle.is_none() && scheduler.backend.idle_widgets.is_empty() {
+        return;
+    }
+    let now = scheduler.now();
+    let idle_for = now.saturating_duration_since(*scheduler.backend.last_activity.get_or_insert(now));
+    let timeout = scheduler.backend.idle_timeout;
+
+    if idle_for >= timeout && !scheduler.backend.idle_fired.iter().any(|x| x.is_empty()) {
+        if let Some(mut callback) = scheduler.backend.on_idle.take() {
+            scheduler.backend.idle_fired.push(String::new());
+            let context = Context::new("/root".to_string(), state_tree, scheduler, custom_data);
+            callback(context);
+            // Restore the callback unless it was replaced from within the callback
+            if scheduler.backend.on_idle.is_none() {
+                scheduler.backend.on_idle = Some(callback);
+            }
+        }
+    }
+
+    let mut removed = Vec::new();
+    for widget in scheduler.backend.idle_widgets.clone() {
+        let (path, config) = match (state_tree.try_get(&widget), callback_tree.try_get_mut(&widget))
+        {
+            (Some(state), Some(config)) => (state.as_generic().get_path().clone(), &mut config.obj),
+            _ => {
+                removed.push(widget);
+                continue;
+            }
+        };
+        if scheduler.backend.idle_fired.contains(&path)
+            || idle_for < config.idle_timeout.unwrap_or(timeout)
+        {
+            continue;
+        }
+        if let Some(ref mut callback) = config.on_idle {
+            scheduler.backend.idle_fired.push(path.clone());
+            callback(Context::new(path, state_tree, scheduler, custom_data));
+        }
+    }
+    scheduler.backend.idle_widgets.retain(|x| !removed.contains(x));
+}
+
+/// Check all buttons that are held down. Buttons held with the mouse become a long press when
+/// held longer than their long_press_time; buttons held with the enter key are released when the
+/// key stopped repeating. Buttons with a repeat_interval repeat their on_press callback while
+/// held.
+pub fn update_pressed_buttons(
+    scheduler: &mut SchedulerFrontend,
+    state_tree: &mut StateTree,
+    callback_tree: &mut CallbackTree,
+    custom_data: &mut CustomDataMap,
+) {
+    let now = scheduler.now();
+    for path in scheduler.backend.pressed_buttons.clone() {
+        let state = match state_tree
+            .try_get(&path)
+            .and_then(|x| x.obj.try_as::<ButtonState>())
+        {
+            Some(i) => i,
+            None => {
+                scheduler.backend.pressed_buttons.retain(|x| x != &path);
+                continue;
+            }
+        };
+        let repeat_interval = Duration::from_millis(state.get_repeat_interval() as u64);
+        let last_repeat = state.get_last_repeat().or(state.get_pressed_since());
+        if !repeat_interval.is_zero()
+            && last_repeat.map_or(false, |x| now.saturating_duration_since(x) >= repeat_interval)
+        {
+            Button::repeat_press(&path, state_tree, callback_tree, scheduler, custom_data);
+        }
+        // The callback might have removed the button
+        let state = match state_tree
+            .try_get(&path)
+            .and_then(|x| x.obj.try_as::<ButtonState>())
+        {
+            Some(i) => i,
+            None => continue,
+        };
+        if state.get_pressed_by_key() {
+            let last_key_press = state.get_last_key_press().unwrap_or(now);
+            if now.saturating_duration_since(last_key_press) > KEY_RELEASE_TIMEOUT {
+                Button::release_press(&path, state_tree, callback_tree, scheduler, custom_data);
+            }
+        } else if let Some(pressed_since) = state.get_pressed_since() {
+            let long_press_time = Duration::from_millis(state.get_long_press_time() as u64);
+            if !state.get_long_pressed()
+                && now.saturating_duration_since(pressed_since) >= long_press_time
+            {
+                Button::trigger_long_press(&path, state_tree, callback_tree, scheduler,
+                                           custom_data);
+            }
+        }
+    }
+}
+
+/// Animate the spinners of busy layouts (see [LayoutState.set_busy]). The selected widget is
+/// deselected if it is in a busy layout, as busy layouts cannot be interacted with.
+pub fn update_busy_layouts(scheduler: &mut SchedulerFrontend, state_tree: &mut StateTree) {
+    let now = scheduler.now();
+    let next_frame = scheduler
+        .backend
+        .last_busy_frame
+        .map_or(true, |x| now.duration_since(x) >= BUSY_SPINNER_INTERVAL);
+    let mut busy = false;
+    for state in state_tree.get_all_mut() {
+        if let EzState::Layout(ref mut i) = state {
+            if i.get_busy() {
+                busy = true;
+                if next_frame {
+                    i.set_busy_frame(i.get_busy_frame() + 1);
+                    scheduler.update_widget(i.get_path());
+                }
+            }
+        }
+    }
+    if !busy {
+        scheduler.backend.last_busy_frame = None;
+        return;
+    }
+    if next_frame {
+        scheduler.backend.last_busy_frame = Some(now);
+    }
+    let selected = get_selected_path(state_tree);
+    if !selected.is_empty() && widget_is_busy(&selected, state_tree) {
+        scheduler.deselect_widget();
+    }
+}
+
 /// Check all EzProperty that have at least one subscriber and check if they've send a new
 /// value. If so, call the update func of all subscribers and any registered user callbacks.
 pub fn update_properties(
@@ -245,51 +644,120 @@ pub fn update_properties(
     let mut to_update = Vec::new();
     let mut to_callback: Vec<String> = Vec::new();
 
-    let mut subscribed_properties: Vec<&String> = scheduler
+    // Updating a subscriber sets a new value on it, which its' own subscribers need to receive
+    // as well. Keep propagating until no more values change, so chains settle in a single frame.
+    let mut rounds = 0;
+    loop {
+        let changed = propagate_property_values(scheduler, state_tree, &mut to_update);
+        if changed.is_empty() {
+            break;
+        }
+        scheduler.backend.dirty = true;
+        // New values of computed properties are propagated in the next round
+        update_computed_properties(scheduler);
+        for name in changed.iter() {
+            for callback_name in scheduler.backend.property_callbacks.iter() {
+                if &get_callback_property_name(scheduler, callback_name) == name
+                    && !to_callback.contains(callback_name)
+                {
+                    to_callback.push(callback_name.clone());
+                }
+            }
+        }
+        rounds += 1;
+        if rounds >= MAX_PROPERTY_UPDATE_ROUNDS {
+            panic!(
+                "Property updates did not settle after {} rounds in a single frame. These \
+                properties keep updating each other: {}",
+                MAX_PROPERTY_UPDATE_ROUNDS,
+                changed.join(", ")
+            );
+        }
+    }
+    for name in to_callback {
+        for callback in callback_tree
+            .get_mut(&name)
+            .obj
+            .property_callbacks
+            .iter_mut()
+        {
+            let context = Context::new(name.to_string(), state_tree, scheduler, custom_data);
+            callback(context);
+        }
+    }
+
+    scheduler.backend.widgets_to_update.extend(to_update);
+}
+
+/// Maximum amount of times per frame new property values are propagated to subscribers. Only
+/// reached if properties keep changing each other.
+const MAX_PROPERTY_UPDATE_ROUNDS: usize = 100;
+
+/// Receive new values of all subscribed properties and pass them on to their subscribers. Returns
+/// the names of the properties that had a new value.
+fn propagate_property_values(
+    scheduler: &mut SchedulerFrontend,
+    state_tree: &mut StateTree,
+    to_update: &mut Vec<String>,
+) -> Vec<String> {
+    let mut changed = Vec::new();
+    let mut subscribed_properties: Vec<String> = scheduler
         .backend
         .property_subscribers
         .keys()
+        .cloned()
         .collect();
-    subscribed_properties.extend(
-        scheduler
-            .backend
-            .property_subscribers
-            .keys()
-            .collect::<Vec<&String>>()
-    );
-    if scheduler.is_syncing() {
-        subscribed_properties.extend(
-            scheduler.backend.properties
-                .keys()
-                .filter(|x| {
-                    let widget = &x
-                        .rsplit_once("/");
-                    if widget.is_none() { return false }
-                    scheduler.backend.widgets_to_update.contains(&widget.unwrap().0.to_string())
-                }
-                ).collect::<Vec<&String>>())
+    // Properties with callbacks bound to them must be checked as well, even if no other property
+    // is subscribed to them
+    let callback_names: Vec<String> = scheduler
+        .backend
+        .property_callbacks
+        .iter()
+        .map(|x| get_callback_property_name(scheduler, x))
+        .collect();
+    for name in callback_names {
+        if !subscribed_properties.contains(&name) && scheduler._create_property_channel(&name) {
+            subscribed_properties.push(name);
+        }
+    }
+    // As must the dependencies of computed properties
+    let dependencies: Vec<String> = scheduler
+        .backend
+        .computed_properties
+        .iter()
+        .flat_map(|x| x.dependencies.iter().cloned())
+        .collect();
+    for dependency in dependencies {
+        if !subscribed_properties.contains(&dependency)
+            && scheduler._create_property_channel(&dependency)
+        {
+            subscribed_properties.push(dependency);
+        }
     }
 
     for name in subscribed_properties {
-        let (widget_path, property_name) = if scheduler.is_syncing() && name.starts_with("/root") {
-            let (path, id) = name.rsplit_once('/').unwrap();
-            (Some(path), Some(id))
-        } else {
-            (None, None)
-        };
+        // Channels are created when a property is bound; properties created afterwards by
+        // threads or lazy layouts may not have one yet.
+        scheduler._create_property_channel(&name);
         let mut new_val = None;
         // Drain all new values if any, we only care about the latest.
         while let Ok(new) = scheduler
             .backend
             .property_receivers
-            .get(name)
+            .get(&name)
             .unwrap_or_else(|| panic!("Could not get property receiver for {}", name))
             .try_recv()
         {
             new_val = Some(new);
         }
         if let Some(val) = new_val {
-            if let Some(i) = scheduler.backend.property_subscribers.get(name) {
+            for computed in scheduler.backend.computed_properties.iter_mut() {
+                if let Some(i) = computed.dependencies.iter().position(|x| x == &name) {
+                    computed.values[i] = val.clone();
+                    computed.dirty = true;
+                }
+            }
+            if let Some(i) = scheduler.backend.property_subscribers.get(&name) {
                 for subscriber in i {
                     scheduler
                         .backend
@@ -303,36 +771,54 @@ pub fn update_properties(
                     }
                 }
             }
-            if scheduler.backend.property_callbacks.contains(&name) {
-                to_callback.push(name.clone());
-            }
-            if widget_path.is_some() {
-                let state = state_tree.try_get_mut(widget_path.unwrap());
-                if let Some(found_state) = state {
-                    found_state
-                        .as_generic_mut()
-                        .update_property(property_name.unwrap(), val);
-                }
+            if !changed.contains(&name) {
+                changed.push(name);
             }
         }
     }
-    for name in to_callback {
-        for callback in callback_tree
-            .get_mut(&name)
-            .obj
-            .property_callbacks
-            .iter_mut()
-        {
-            let context = Context::new(name.to_string(), state_tree, scheduler, custom_data);
-            callback(context);
+    changed
+}
+
+/// Recompute the value of computed properties of which a dependency changed.
+fn update_computed_properties(scheduler: &mut SchedulerFrontend) {
+    let mut new_values = Vec::new();
+    for computed in scheduler.backend.computed_properties.iter_mut() {
+        if computed.dirty {
+            computed.dirty = false;
+            new_values.push((computed.name.clone(), (computed.compute)(&computed.values)));
+        }
+    }
+    for (name, value) in new_values {
+        if let Some(property) = scheduler.backend.properties.get_mut(&name) {
+            property.set_from_ez_value(value);
         }
     }
+}
 
-    scheduler.backend.widgets_to_update.extend(to_update);
+/// Get the name of the property a property callback is bound to. Callbacks of custom properties
+/// are stored under "/root/<name>" while the property itself is called "<name>".
+fn get_callback_property_name(scheduler: &SchedulerFrontend, callback_name: &str) -> String {
+    match callback_name.strip_prefix("/root/") {
+        Some(name) if !name.contains('/') && scheduler.backend.properties.contains_key(name) => {
+            name.to_string()
+        }
+        _ => callback_name.to_string(),
+    }
 }
 
 /// Execute update func for each property subscribed to another.
 pub fn trigger_update_funcs(scheduler: &mut SchedulerFrontend, state_tree: &mut StateTree) {
+    trigger_update_funcs_of(scheduler, state_tree, "");
+}
+
+/// Execute update func for each subscribed property of which the path starts with a prefix, e.g.
+/// the properties of widgets that were just created, so they get the current value of the
+/// property they are bound to.
+pub fn trigger_update_funcs_of(
+    scheduler: &mut SchedulerFrontend,
+    state_tree: &mut StateTree,
+    path_prefix: &str,
+) {
     for (property, subscribers) in scheduler.backend.property_subscribers.iter() {
         let val = if property.contains('/') {
             let (widget, property_name) = property.rsplit_once('/').unwrap();
@@ -343,7 +829,7 @@ pub fn trigger_update_funcs(scheduler: &mut SchedulerFrontend, state_tree: &mut
         } else {
             scheduler.get_property(property).get_generic_value()
         };
-        for subscriber in subscribers {
+        for subscriber in subscribers.iter().filter(|x| x.starts_with(path_prefix)) {
             let updater = scheduler
                 .backend
                 .property_updaters
@@ -402,6 +888,167 @@ pub fn clean_up_property(scheduler: &mut SchedulerFrontend, name: &str) {
 
     scheduler.backend.property_receivers.remove(name);
     scheduler.backend.property_subscribers.remove(name);
+
+    // The property may also be bound to another property, e.g. a widget of a dismissed modal
+    scheduler.backend.property_updaters.remove(name);
+    for subscribers in scheduler.backend.property_subscribers.values_mut() {
+        subscribers.retain(|x| x != name);
+    }
+}
+
+/// Cancel all tasks tied to a widget: tasks scheduled on its' state (see
+/// [GenericState.schedule_once]) and internal tasks named after the widget path. Called
+/// automatically when a widget is removed.
+pub fn cancel_widget_tasks(scheduler: &mut SchedulerFrontend, path: &str) {
+    let prefix = format!("{}/", path);
+    let belongs_to_widget = |name: &str| name == path || name.starts_with(&prefix);
+    scheduler.backend.tasks.retain(|x| !belongs_to_widget(&x.name));
+    scheduler.backend.recurring_tasks.retain(|x| !belongs_to_widget(&x.name));
+}
+
+/// Find the path of the layout in screen mode closest to the root layout, if any. Used by
+/// [SchedulerFrontend.add_screen].
+pub fn find_screen_layout(state_tree: &StateTree) -> Option<String> {
+    let mut to_check = vec![state_tree];
+    while !to_check.is_empty() {
+        let mut next = Vec::new();
+        for node in to_check {
+            if let EzState::Layout(ref i) = node.obj {
+                if i.get_mode() == &LayoutMode::Screen {
+                    return Some(node.obj.as_generic().get_path().clone());
+                }
+            }
+            next.extend(
+                node.get_children()
+                    .into_iter()
+                    .filter(|x| !LayoutState::is_modal_id(&x.id)),
+            );
+        }
+        to_check = next;
+    }
+    None
+}
+
+/// Get the path of the screen layout a screen belongs to, along with the ID of the screen. Panics
+/// if the parent of the widget is not a layout in screen mode.
+pub fn get_screen_parent(screen: &str, state_tree: &StateTree) -> (String, String) {
+    let path = state_tree.get(screen).as_generic().get_path().clone();
+    let (layout, id) = path.rsplit_once('/').unwrap();
+    match state_tree.try_get(layout) {
+        Some(i) if matches!(i.obj, EzState::Layout(_))
+            && i.as_layout().get_mode() == &LayoutMode::Screen =>
+        {
+            (layout.to_string(), id.to_string())
+        }
+        _ => panic!("Widget \"{}\" is not a screen: its' parent is not a layout in screen mode",
+                    screen),
+    }
+}
+
+/// Resolve the handles of modals opened with [SchedulerFrontend.open_modal_with_result] once
+/// the modals are closed, passing the result set by each modal (if any). Handles of modals on top
+/// are resolved first.
+pub fn update_modal_result(
+    scheduler: &mut SchedulerFrontend,
+    state_tree: &mut StateTree,
+    custom_data: &mut CustomDataMap,
+) {
+    while let Some((depth, _)) = scheduler.backend.modal_result_resolvers.last() {
+        let depth = *depth;
+        if state_tree.as_layout().get_modals().len() >= depth {
+            return;
+        }
+        let (_, resolver) = scheduler.backend.modal_result_resolvers.pop().unwrap();
+        let result = scheduler.backend.modal_results.remove(&depth);
+        // Results of modals above this one were not asked for by anyone
+        scheduler.backend.modal_results.retain(|x, _| *x < depth);
+        let context = Context::new(String::new(), state_tree, scheduler, custom_data);
+        resolver(context, result);
+    }
+}
+
+/// Render the widgets passed to [SchedulerFrontend.render_widget_to_string] to plain text from
+/// what is currently on screen, and pass the text to their callbacks.
+pub fn render_widgets_to_string(
+    scheduler: &mut SchedulerFrontend,
+    state_tree: &mut StateTree,
+    custom_data: &mut CustomDataMap,
+    view_tree: &ViewTree,
+) {
+    for (widget, callback) in std::mem::take(&mut scheduler.backend.widgets_to_render) {
+        let state = state_tree.get(&widget).as_generic();
+        let text = view_tree.get_text(
+            state.get_absolute_position(),
+            state.get_size().get_width(),
+            state.get_size().get_height(),
+        );
+        let path = state.get_path().clone();
+        let context = Context::new(path, state_tree, scheduler, custom_data);
+        callback(context, text);
+    }
+}
+
+/// If an exit was requested since the last frame, ask the callback set with
+/// [SchedulerFrontend.set_on_exit] whether the app may exit, and exit if it may.
+pub fn update_exit_request(
+    scheduler: &mut SchedulerFrontend,
+    state_tree: &mut StateTree,
+    custom_data: &mut CustomDataMap,
+) {
+    if !scheduler.backend.exit_requested {
+        return;
+    }
+    scheduler.backend.exit_requested = false;
+    let mut callback = match scheduler.backend.on_exit.take() {
+        Some(i) => i,
+        None => return scheduler.force_exit(),
+    };
+    let context = Context::new(String::new(), state_tree, scheduler, custom_data);
+    let exit = callback(context);
+    // The callback may have replaced itself
+    if scheduler.backend.on_exit.is_none() {
+        scheduler.backend.on_exit = Some(callback);
+    }
+    if exit {
+        scheduler.force_exit();
+    }
+}
+
+/// While the app is exiting, call the callback set with [SchedulerFrontend.set_on_teardown] until
+/// it returns true or the teardown timeout passes. Once the teardown is done, persisted properties
+/// are written and the run loop is told to stop.
+pub fn update_teardown(
+    scheduler: &mut SchedulerFrontend,
+    state_tree: &mut StateTree,
+    custom_data: &mut CustomDataMap,
+) {
+    let started = match scheduler.backend.exit_started {
+        Some(i) if !scheduler.backend.stopped => i,
+        _ => return,
+    };
+    let timed_out = scheduler.now().saturating_duration_since(started)
+        >= scheduler.backend.exit_teardown.timeout;
+    let done = timed_out
+        || match scheduler.backend.on_teardown.take() {
+            Some(mut callback) => {
+                let context = Context::new(String::new(), state_tree, scheduler, custom_data);
+                let done = callback(context);
+                // The callback may have replaced itself
+                if scheduler.backend.on_teardown.is_none() {
+                    scheduler.backend.on_teardown = Some(callback);
+                }
+                done
+            }
+            None => true,
+        };
+    if done {
+        // Write settings files with changes that are still waiting for PERSIST_DELAY
+        for path in scheduler.backend.persisted_properties.keys() {
+            save_settings(path, scheduler);
+        }
+        scheduler._stop_background_tasks();
+        scheduler.backend.stopped = true;
+    }
 }
 
 pub fn handle_next_selection(
@@ -454,3 +1101,53 @@ pub fn handle_next_selection(
         current_selection
     }
 }
+
+/// Read the bytes of a file starting at a position. Used by [SchedulerFrontend.watch_file] to
+/// read only the lines that were added since the last read.
+pub fn read_file_from(path: &str, position: u64) -> Result<Vec<u8>, std::io::Error> {
+    let mut file = File::open(path)?;
+    file.seek(SeekFrom::Start(position))?;
+    let mut buffer = Vec::new();
+    file.read_to_end(&mut buffer)?;
+    Ok(buffer)
+}
+
+/// Get a value identifying a file, regardless of its contents. Used by
+/// [SchedulerFrontend.watch_file] to notice when a file is replaced (e.g. by log rotation), even
+/// if the new file is already larger than the old one. On unix this is the inode number.
+#[cfg(unix)]
+pub fn get_file_identity(metadata: &Metadata) -> Option<u64> {
+    Some(metadata.ino())
+}
+
+/// Get a value identifying a file, regardless of its contents. Used by
+/// [SchedulerFrontend.watch_file] to notice when a file is replaced (e.g. by log rotation), even
+/// if the new file is already larger than the old one. Where inodes are not available this is the
+/// creation time, if the platform supports it.
+#[cfg(not(unix))]
+pub fn get_file_identity(metadata: &Metadata) -> Option<SystemTime> {
+    metadata.created().ok()
+}
+
+/// Get the IDs of the checked checkboxes of a checkbox group, given as (ID, path).
+pub fn get_checked_boxes(checkboxes: &[(String, String)], state_tree: &StateTree) -> Vec<String> {
+    checkboxes
+        .iter()
+        .filter(|(_, path)| state_tree.get(path).as_checkbox().get_active())
+        .map(|(id, _)| id.clone())
+        .collect()
+}
+
+/// Get the z indices of the siblings of a widget, not including the widget itself. Used by
+/// [SchedulerFrontend.bring_to_front] and [SchedulerFrontend.send_to_back].
+pub fn get_sibling_z_indices(path: &str, state_tree: &StateTree) -> Vec<usize> {
+    let parent = path.rsplit_once('/').unwrap().0;
+    state_tree
+        .get(parent)
+        .get_children()
+        .into_iter()
+        .map(|x| x.as_generic())
+        .filter(|x| x.get_path() != path)
+        .map(|x| x.get_z_index())
+        .collect()
+}
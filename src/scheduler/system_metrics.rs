@@ -0,0 +1,112 @@
+//! # System metrics
+//!
+//! This module implements the [SystemMetrics] struct, which provides CPU, memory and network
+//! usage as custom properties, so they can be bound to progress bars and labels. Only available
+//! with the "metrics" feature.
+use std::time::Duration;
+
+use sysinfo::{Networks, System};
+
+use crate::property::ez_property::EzProperty;
+use crate::scheduler::definitions::Context;
+use crate::scheduler::scheduler::SchedulerFrontend;
+
+/// Custom properties that are kept up to date with the usage of the system. Created with
+/// [SchedulerFrontend.track_system_metrics].
+#[derive(Clone, Debug)]
+pub struct SystemMetrics {
+    /// Usage of all CPUs combined in percent (0-100). Property name: "cpu_usage".
+    pub cpu_usage: EzProperty<usize>,
+
+    /// Used memory in percent (0-100). Property name: "memory_usage".
+    pub memory_usage: EzProperty<usize>,
+
+    /// Used memory in megabytes. Property name: "memory_used".
+    pub memory_used: EzProperty<usize>,
+
+    /// Bytes per second received on all network interfaces. Property name: "network_received".
+    pub network_received: EzProperty<usize>,
+
+    /// Bytes per second transmitted on all network interfaces. Property name:
+    /// "network_transmitted".
+    pub network_transmitted: EzProperty<usize>,
+}
+
+impl SchedulerFrontend {
+    /// Create custom properties for CPU, memory and network usage and keep them up to date on an
+    /// interval. Bind the properties to widgets to create a system dashboard; percentages can be
+    /// bound to the value of a progress bar directly. See [SystemMetrics] for the names of the
+    /// created properties. The metrics can be stopped by cancelling the "system_metrics"
+    /// recurring task. Only available with the "metrics" feature.
+    ///
+    /// # Parameters:
+    ///
+    /// - Interval: std::Duration
+    ///
+    /// # Example:
+    ///
+    /// We'll show CPU and memory usage in progress bars. First the code:
+    /// ```
+    /// use std::time::Duration;
+    /// use ez_term::*;
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// scheduler.track_system_metrics(Duration::from_secs(1));
+    ///
+    /// run(root_widget, state_tree, scheduler, custom_data);
+    /// ```
+    /// Now the .ez file:
+    /// ```
+    /// - Layout:
+    ///     mode: box
+    ///     orientation: vertical
+    ///     - ProgressBar:
+    ///         value: properties.cpu_usage
+    ///     - ProgressBar:
+    ///         value: properties.memory_usage
+    /// ```
+    pub fn track_system_metrics(&mut self, interval: Duration) -> SystemMetrics {
+        let mut metrics = SystemMetrics {
+            cpu_usage: self.new_usize_property("cpu_usage", 0),
+            memory_usage: self.new_usize_property("memory_usage", 0),
+            memory_used: self.new_usize_property("memory_used", 0),
+            network_received: self.new_usize_property("network_received", 0),
+            network_transmitted: self.new_usize_property("network_transmitted", 0),
+        };
+        let metrics_ref = metrics.clone();
+
+        let mut system = System::new();
+        let mut networks = Networks::new_with_refreshed_list();
+        let seconds = interval.as_secs_f64().max(0.001);
+        let update = move |_context: Context| {
+            system.refresh_cpu_usage();
+            system.refresh_memory();
+            networks.refresh();
+
+            metrics
+                .cpu_usage
+                .set(system.global_cpu_info().cpu_usage().round().min(100.0) as usize);
+            let total_memory = system.total_memory().max(1);
+            metrics
+                .memory_usage
+                .set((system.used_memory() * 100 / total_memory) as usize);
+            metrics
+                .memory_used
+                .set((system.used_memory() / 1024 / 1024) as usize);
+            let (received, transmitted) = networks
+                .iter()
+                .fold((0, 0), |(rx, tx), (_, data)| {
+                    (rx + data.received(), tx + data.transmitted())
+                });
+            metrics
+                .network_received
+                .set((received as f64 / seconds) as usize);
+            metrics
+                .network_transmitted
+                .set((transmitted as f64 / seconds) as usize);
+            true
+        };
+        self.schedule_recurring("system_metrics", Box::new(update), interval);
+        metrics_ref
+    }
+}
@@ -0,0 +1,146 @@
+//! # Undo
+//!
+//! This module implements [UndoableCommand] and the undo history kept by the scheduler. Commands
+//! are run with [SchedulerFrontend.execute_undoable]; the scheduler keeps track of which commands
+//! can be undone and redone.
+use crate::run::definitions::StateTree;
+use crate::scheduler::definitions::{Context, CustomDataMap};
+use crate::scheduler::scheduler::SchedulerFrontend;
+
+/// Called to do, redo or undo an [UndoableCommand].
+pub type UndoableFunction = Box<dyn FnMut(Context) + Send>;
+
+/// Name of the custom bool property that is true while there is a command to undo.
+pub const CAN_UNDO_PROPERTY: &str = "can_undo";
+
+/// Name of the custom bool property that is true while there is a command to redo.
+pub const CAN_REDO_PROPERTY: &str = "can_redo";
+
+/// An action of the user that can be undone, such as deleting a row from a table. Consists of a
+/// function that performs the action (also used to redo it) and a function that reverts it.
+/// Run commands with [SchedulerFrontend.execute_undoable].
+///
+/// # Example:
+///
+/// ```
+/// use ez_term::*;
+///
+/// let delete_row = UndoableCommand::new(
+///     "Delete row",
+///     Box::new(|context: Context| {
+///         // Delete the row
+///     }),
+///     Box::new(|context: Context| {
+///         // Put the row back
+///     }),
+/// );
+/// ```
+pub struct UndoableCommand {
+    /// Short description of the command, e.g. "Delete row". Can be shown to the user, see
+    /// [SchedulerFrontend.get_undo_name]
+    pub name: String,
+
+    do_func: UndoableFunction,
+
+    undo_func: UndoableFunction,
+}
+impl UndoableCommand {
+    /// Create a command.
+    ///
+    /// # Parameters:
+    ///
+    /// - Description of the command: &str
+    /// - Function performing the command: Box<dyn FnMut(Context)>
+    /// - Function reverting the command: Box<dyn FnMut(Context)>
+    pub fn new(name: &str, do_func: UndoableFunction, undo_func: UndoableFunction) -> Self {
+        UndoableCommand {
+            name: name.to_string(),
+            do_func,
+            undo_func,
+        }
+    }
+}
+
+/// Changes to the undo history requested through the scheduler, handled once per frame.
+pub(crate) enum UndoAction {
+    Execute(UndoableCommand),
+    Undo,
+    Redo,
+}
+
+/// Commands that can be undone and redone, kept by the scheduler.
+#[derive(Default)]
+pub struct UndoHistory {
+    /// Commands that can be undone, the last one was executed most recently
+    pub(crate) undo_stack: Vec<UndoableCommand>,
+
+    /// Commands that can be redone, the last one was undone most recently
+    pub(crate) redo_stack: Vec<UndoableCommand>,
+
+    /// Changes requested since the last frame
+    pub(crate) actions: Vec<UndoAction>,
+
+    /// Maximum amount of commands that can be undone. 0 means there is no maximum
+    pub limit: usize,
+}
+
+/// Execute, undo and redo the commands requested since the last frame, then update the
+/// [CAN_UNDO_PROPERTY] and [CAN_REDO_PROPERTY] properties.
+pub(crate) fn update_undo_history(
+    scheduler: &mut SchedulerFrontend,
+    state_tree: &mut StateTree,
+    custom_data: &mut CustomDataMap,
+) {
+    if scheduler.backend.undo_history.actions.is_empty() {
+        return;
+    }
+    let actions = std::mem::take(&mut scheduler.backend.undo_history.actions);
+    for action in actions {
+        match action {
+            UndoAction::Execute(mut command) => {
+                let context = Context::new(String::new(), state_tree, scheduler, custom_data);
+                (command.do_func)(context);
+                let history = &mut scheduler.backend.undo_history;
+                history.undo_stack.push(command);
+                history.redo_stack.clear();
+                if history.limit > 0 && history.undo_stack.len() > history.limit {
+                    history.undo_stack.remove(0);
+                }
+            }
+            UndoAction::Undo => {
+                if let Some(mut command) = scheduler.backend.undo_history.undo_stack.pop() {
+                    let context = Context::new(String::new(), state_tree, scheduler, custom_data);
+                    (command.undo_func)(context);
+                    scheduler.backend.undo_history.redo_stack.push(command);
+                }
+            }
+            UndoAction::Redo => {
+                if let Some(mut command) = scheduler.backend.undo_history.redo_stack.pop() {
+                    let context = Context::new(String::new(), state_tree, scheduler, custom_data);
+                    (command.do_func)(context);
+                    scheduler.backend.undo_history.undo_stack.push(command);
+                }
+            }
+        }
+    }
+    update_undo_properties(scheduler);
+}
+
+/// Set the [CAN_UNDO_PROPERTY] and [CAN_REDO_PROPERTY] properties, creating them if they do not
+/// exist yet.
+pub(crate) fn update_undo_properties(scheduler: &mut SchedulerFrontend) {
+    let can_undo = !scheduler.backend.undo_history.undo_stack.is_empty();
+    let can_redo = !scheduler.backend.undo_history.redo_stack.is_empty();
+    for (name, value) in [(CAN_UNDO_PROPERTY, can_undo), (CAN_REDO_PROPERTY, can_redo)] {
+        if !scheduler.backend.properties.contains_key(name) {
+            scheduler.new_bool_property(name, false);
+        }
+        scheduler
+            .backend
+            .properties
+            .get_mut(name)
+            .unwrap()
+            .as_bool_mut()
+            .set(value);
+    }
+}
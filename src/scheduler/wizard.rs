@@ -0,0 +1,205 @@
+//! # Wizard
+//!
+//! This module implements the [Wizard] struct, which describes a multi-step dialog such as a
+//! setup flow. A wizard is created from code with [SchedulerFrontend.create_wizard].
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use crate::property::ez_values::EzValues;
+use crate::run::definitions::StateTree;
+use crate::scheduler::definitions::Context;
+use crate::scheduler::scheduler::SchedulerFrontend;
+
+/// Called when the user tries to go to the next step of a [Wizard]. Receives the ID of the
+/// current step; return false to stay on the current step, e.g. because an input is invalid.
+pub type WizardValidator = Box<dyn FnMut(Context, &str) -> bool + Send>;
+
+/// Called when the user finishes the last step of a [Wizard]. Receives the values of all input
+/// widgets in all steps (see [WizardResult]).
+pub type WizardFinishFunction = Box<dyn FnMut(Context, WizardResult) + Send>;
+
+/// Values of all input widgets in all steps of a [Wizard] by widget ID: the text of text inputs,
+/// whether checkboxes and radio buttons are active, the value of sliders and the choice of
+/// dropdowns.
+pub type WizardResult = HashMap<String, EzValues>;
+
+/// A single step of a [Wizard].
+#[derive(Clone, Debug)]
+pub struct WizardStep {
+    /// ID of the step layout
+    pub id: String,
+
+    /// Template (or widget type) the step layout is created from. Must be a layout.
+    pub template: String,
+
+    /// Title of the step shown in the progress label
+    pub title: String,
+}
+
+/// A multi-step dialog. Each step is a layout created from a template; the steps are shown one at
+/// a time with "Back" and "Next" buttons (the "Next" button reads "Finish" on the last step) and a
+/// label showing the progress. Validators can be bound to steps to prevent the user from
+/// continuing, and a callback is called with the values of all steps when the user finishes.
+/// Create the wizard from code with [SchedulerFrontend.create_wizard].
+///
+/// # Example:
+///
+/// ```
+/// use ez_term::*;
+///
+/// let mut wizard = Wizard::new();
+/// wizard.add_step("account", "AccountStep", "Account");
+/// wizard.add_step("options", "OptionsStep", "Options");
+/// wizard.set_validator("account", Box::new(|context: Context, _step: &str| {
+///     !context.state_tree.get("username").as_text_input().get_text().is_empty()
+/// }));
+/// wizard.set_on_finish(Box::new(|context: Context, result: WizardResult| {
+///     println!("Username: {}", result["username"]);
+/// }));
+/// ```
+#[derive(Default)]
+pub struct Wizard {
+    /// Steps of the wizard, in order
+    pub steps: Vec<WizardStep>,
+
+    validators: HashMap<String, WizardValidator>,
+
+    on_finish: Option<WizardFinishFunction>,
+}
+impl Wizard {
+    pub fn new() -> Self {
+        Wizard::default()
+    }
+
+    /// Add a step to the end of the wizard.
+    ///
+    /// # Parameters:
+    ///
+    /// - ID of the step layout: &str
+    /// - Template the step is created from: &str
+    /// - Title shown in the progress label: &str
+    pub fn add_step(&mut self, id: &str, template: &str, title: &str) {
+        self.steps.push(WizardStep {
+            id: id.to_string(),
+            template: template.to_string(),
+            title: title.to_string(),
+        });
+    }
+
+    /// Bind a validator to a step, replacing any previous validator of that step. The validator
+    /// is called when the user presses "Next" or "Finish" on the step.
+    pub fn set_validator(&mut self, step: &str, validator: WizardValidator) {
+        self.validators.insert(step.to_string(), validator);
+    }
+
+    /// Set the callback that is called when the user finishes the last step.
+    pub fn set_on_finish(&mut self, on_finish: WizardFinishFunction) {
+        self.on_finish = Some(on_finish);
+    }
+
+    /// Get the index of the step that is currently shown.
+    fn get_current_step(&self, state_tree: &StateTree, steps_path: &str) -> usize {
+        let active = state_tree.get(steps_path).as_layout().get_active_screen();
+        self.steps.iter().position(|x| x.id == active).unwrap_or(0)
+    }
+}
+
+/// Paths of the widgets making up a wizard created by [SchedulerFrontend.create_wizard].
+#[derive(Clone, Debug)]
+pub(crate) struct WizardPaths {
+    pub wizard: String,
+    pub progress: String,
+    pub steps: String,
+    pub back: String,
+    pub next: String,
+}
+
+/// Show a step of a wizard: switch the step layout and update the progress label and buttons.
+pub(crate) fn show_wizard_step(
+    wizard: &Wizard,
+    paths: &WizardPaths,
+    step: usize,
+    state_tree: &mut StateTree,
+    scheduler: &mut SchedulerFrontend,
+) {
+    state_tree
+        .get_mut(&paths.steps)
+        .as_layout_mut()
+        .set_active_screen(&wizard.steps[step].id);
+    state_tree
+        .get_mut(&paths.progress)
+        .as_label_mut()
+        .set_text(format!(
+            "Step {} of {}: {}",
+            step + 1,
+            wizard.steps.len(),
+            wizard.steps[step].title
+        ));
+    state_tree.get_mut(&paths.back).as_generic_mut().set_disabled(step == 0);
+    let next_text = if step + 1 == wizard.steps.len() {
+        "Finish"
+    } else {
+        "Next"
+    };
+    state_tree
+        .get_mut(&paths.next)
+        .as_button_mut()
+        .set_text(next_text.to_string());
+    scheduler.update_widget(&paths.wizard);
+}
+
+/// Go back one step. Does nothing on the first step.
+pub(crate) fn wizard_back(wizard: &Arc<Mutex<Wizard>>, paths: &WizardPaths, context: Context) {
+    let wizard = wizard.lock().unwrap();
+    let step = wizard.get_current_step(context.state_tree, &paths.steps);
+    if step > 0 {
+        show_wizard_step(&wizard, paths, step - 1, context.state_tree, context.scheduler);
+    }
+}
+
+/// Validate the current step, then go to the next step or finish the wizard if this was the
+/// last step.
+pub(crate) fn wizard_next(wizard: &Arc<Mutex<Wizard>>, paths: &WizardPaths, context: Context) {
+    let mut wizard = wizard.lock().unwrap();
+    let step = wizard.get_current_step(context.state_tree, &paths.steps);
+    let step_id = wizard.steps[step].id.clone();
+    if let Some(validator) = wizard.validators.get_mut(&step_id) {
+        let valid = validator(
+            Context::new(
+                format!("{}/{}", paths.steps, step_id),
+                &mut *context.state_tree,
+                &mut *context.scheduler,
+                &mut *context.custom_data,
+            ),
+            &step_id,
+        );
+        if !valid {
+            return;
+        }
+    }
+    if step + 1 < wizard.steps.len() {
+        show_wizard_step(&wizard, paths, step + 1, context.state_tree, context.scheduler);
+        return;
+    }
+    let result = get_wizard_result(&wizard, paths, context.state_tree);
+    if let Some(ref mut on_finish) = wizard.on_finish {
+        on_finish(context, result);
+    }
+}
+
+/// Collect the values of all input widgets in all steps of a wizard.
+fn get_wizard_result(wizard: &Wizard, paths: &WizardPaths, state_tree: &StateTree) -> WizardResult {
+    let mut result = WizardResult::new();
+    for step in wizard.steps.iter() {
+        let step_path = format!("{}/{}", paths.steps, step.id);
+        for state in state_tree.get(&step_path).get_all() {
+            let property = match state.get_value_property() {
+                Some(i) => i,
+                None => continue,
+            };
+            let state = state.as_generic();
+            result.insert(state.get_id(), state.get_property(property));
+        }
+    }
+    result
+}
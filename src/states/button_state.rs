@@ -1,11 +1,13 @@
+use std::time::Instant;
+
 use crate::property::ez_property::EzProperty;
 use crate::property::ez_values::EzValues;
 use crate::run::definitions::IsizeCoordinates;
 use crate::scheduler::scheduler::SchedulerFrontend;
 use crate::scheduler::scheduler_funcs::clean_up_property;
 use crate::states::definitions::{
-    AutoScale, BorderConfig, ColorConfig, HorizontalAlignment, InfiniteSize, Padding, PosHint,
-    SizeHint, StateCoordinates, StateSize, VerticalAlignment,
+    AnchorConfig, AutoScale, BorderConfig, ColorConfig, DockPosition, HorizontalAlignment,
+    InfiniteSize, Padding, PosHint, SizeHint, StateCoordinates, StateSize, VerticalAlignment,
 };
 use crate::states::ez_state::{EzState, GenericState};
 
@@ -18,6 +20,25 @@ pub struct ButtonState {
     /// Text currently being displayed by the label
     pub text: EzProperty<String>,
 
+    /// Milliseconds the button must be held down (with the mouse, or by holding enter) before
+    /// the on_long_press callback is called
+    pub long_press_time: EzProperty<usize>,
+
+    /// Bool representing whether this is a toggle button, which stays pressed (active) after
+    /// being pressed until it is pressed again
+    pub toggle: EzProperty<bool>,
+
+    /// Bool representing whether a toggle button is currently pressed
+    pub active: EzProperty<bool>,
+
+    /// Symbol(s) displayed instead of the text. A button with an icon is compact: its' width is
+    /// the width of the icon
+    pub icon: EzProperty<String>,
+
+    /// Milliseconds between repeated on_press callbacks while the button is held down. 0 means
+    /// the callback is not repeated
+    pub repeat_interval: EzProperty<usize>,
+
     /// Position of this widget relative to its' parent [layout]
     pub position: StateCoordinates,
 
@@ -60,11 +81,50 @@ pub struct ButtonState {
     /// Global order number in which this widget will be selection when user presses down/up keys
     pub selection_order: EzProperty<usize>,
 
+    /// Whether this widget is collapsed when its' parent is a [LayoutMode::Dock] layout
+    pub collapsed: EzProperty<bool>,
+
+    /// Where this widget is placed when its' parent is a [LayoutMode::Dock] layout
+    pub dock: EzProperty<DockPosition>,
+
+    /// Share of the leftover space this widget gets in a box layout, relative to the weights of
+    /// its' siblings. 0.0 means the widget is not weighted and is sized normally
+    pub weight: EzProperty<f64>,
+
+    /// Order in which overlapping children of a [LayoutMode::Float] layout are drawn; higher
+    /// values are drawn on top. Children with the same z index are drawn in declaration order
+    pub z_index: EzProperty<usize>,
+
+    /// Terminal edge or corner this widget is kept at when its' parent is a [LayoutMode::Float]
+    /// layout, see [Anchor]
+    pub anchor: AnchorConfig,
+
     /// Bool representing whether this widget is currently selected.
     selected: bool,
 
+    /// Bool representing whether the mouse is currently over this widget.
+    hovered: bool,
+
+    /// Bool representing whether this widget is currently held down by the mouse.
+    held: bool,
+
     /// Bool representing whether this widget is currently displaying it's flash color.
     flashing: bool,
+
+    /// When the button was pressed down, if it is currently held down
+    pressed_since: Option<Instant>,
+
+    /// Bool representing whether the button is held down by the enter key rather than the mouse
+    pressed_by_key: bool,
+
+    /// When the last (repeated) enter key press was received while the button is held down
+    last_key_press: Option<Instant>,
+
+    /// Bool representing whether the on_long_press callback was called for the current press
+    long_pressed: bool,
+
+    /// When on_press was last repeated during the current press, if it was repeated
+    last_repeat: Option<Instant>,
 }
 impl ButtonState {
     pub fn new(path: String, scheduler: &mut SchedulerFrontend) -> Self {
@@ -87,11 +147,33 @@ impl ButtonState {
                 VerticalAlignment::Top,
             ),
             text: scheduler.new_string_property(format!("{}/text", path).as_str(), String::new()),
+            long_press_time: scheduler
+                .new_usize_property(format!("{}/long_press_time", path).as_str(), 500),
+            toggle: scheduler.new_bool_property(format!("{}/toggle", path).as_str(), false),
+            active: scheduler.new_bool_property(format!("{}/active", path).as_str(), false),
+            icon: scheduler.new_string_property(format!("{}/icon", path).as_str(), String::new()),
+            repeat_interval: scheduler
+                .new_usize_property(format!("{}/repeat_interval", path).as_str(), 0),
             disabled: scheduler.new_bool_property(format!("{}/disabled", path).as_str(), false),
             selected: false,
+            hovered: false,
+            held: false,
             selection_order: scheduler
                 .new_usize_property(format!("{}/selection_order", path).as_str(), 0),
+            collapsed: scheduler.new_bool_property(format!("{}/collapsed", path).as_str(), false),
+            dock: scheduler.new_dock_position_property(
+                format!("{}/dock", path).as_str(),
+                DockPosition::Center,
+            ),
+            weight: scheduler.new_f64_property(format!("{}/weight", path).as_str(), 0.0),
+            z_index: scheduler.new_usize_property(format!("{}/z_index", path).as_str(), 0),
+            anchor: AnchorConfig::new(path.clone(), scheduler),
             flashing: false,
+            pressed_since: None,
+            pressed_by_key: false,
+            last_key_press: None,
+            long_pressed: false,
+            last_repeat: None,
             border_config: BorderConfig::new(true, path.clone(), scheduler),
             colors: ColorConfig::new(path, scheduler),
         }
@@ -122,6 +204,13 @@ impl GenericState for ButtonState {
             "valign" => EzValues::VerticalAlignment(self.valign.value),
             "disabled" => EzValues::Bool(self.disabled.value),
             "selection_order" => EzValues::Usize(self.selection_order.value),
+            "collapsed" => EzValues::Bool(self.collapsed.value),
+            "dock" => EzValues::DockPosition(self.dock.value),
+            "weight" => EzValues::F64(self.weight.value),
+            "z_index" => EzValues::Usize(self.z_index.value),
+            "anchor" => EzValues::String(self.anchor.anchor.value.clone()),
+            "anchor_offset_x" => EzValues::Usize(self.anchor.anchor_offset_x.value),
+            "anchor_offset_y" => EzValues::Usize(self.anchor.anchor_offset_y.value),
             "border" => EzValues::Bool(self.border_config.border.value),
             "horizontal_symbol" => {
                 EzValues::String(self.border_config.horizontal_symbol.value.to_string())
@@ -147,6 +236,15 @@ impl GenericState for ButtonState {
             "selection_bg_color" => EzValues::Color(self.colors.selection_bg_color.value),
             "disabled_fg_color" => EzValues::Color(self.colors.disabled_fg_color.value),
             "disabled_bg_color" => EzValues::Color(self.colors.disabled_bg_color.value),
+            "auto_colors" => EzValues::Bool(self.colors.auto_colors.value),
+            "auto_contrast" => EzValues::Bool(self.colors.auto_contrast.value),
+            "selection_style" => EzValues::String(self.colors.selection_style.value.clone()),
+            "selection_marker" => EzValues::String(self.colors.selection_marker.value.clone()),
+            "hover_fg_color" => EzValues::Color(self.colors.hover_fg_color.value),
+            "hover_bg_color" => EzValues::Color(self.colors.hover_bg_color.value),
+            "pressed_fg_color" => EzValues::Color(self.colors.pressed_fg_color.value),
+            "pressed_bg_color" => EzValues::Color(self.colors.pressed_bg_color.value),
+            "focused_border" => EzValues::Bool(self.colors.focused_border.value),
             "flash_fg_color" => EzValues::Color(self.colors.flash_fg_color.value),
             "flash_bg_color" => EzValues::Color(self.colors.flash_bg_color.value),
             "tab_header_active_fg_color" => {
@@ -158,6 +256,11 @@ impl GenericState for ButtonState {
             "border_fg_color" => EzValues::Color(self.colors.border_fg_color.value),
             "border_bg_color" => EzValues::Color(self.colors.border_bg_color.value),
             "text" => EzValues::String(self.text.value.to_string()),
+            "long_press_time" => EzValues::Usize(self.long_press_time.value),
+            "toggle" => EzValues::Bool(self.toggle.value),
+            "active" => EzValues::Bool(self.active.value),
+            "icon" => EzValues::String(self.icon.value.to_string()),
+            "repeat_interval" => EzValues::Usize(self.repeat_interval.value),
             _ => panic!("Invalid property name for button state: {}", name),
         }
     }
@@ -182,6 +285,13 @@ impl GenericState for ButtonState {
             "valign" => self.valign.set_from_ez_value(value),
             "disabled" => self.disabled.set_from_ez_value(value),
             "selection_order" => self.selection_order.set_from_ez_value(value),
+            "collapsed" => self.collapsed.set_from_ez_value(value),
+            "dock" => self.dock.set_from_ez_value(value),
+            "weight" => self.weight.set_from_ez_value(value),
+            "z_index" => self.z_index.set_from_ez_value(value),
+            "anchor" => self.anchor.anchor.set_from_ez_value(value),
+            "anchor_offset_x" => self.anchor.anchor_offset_x.set_from_ez_value(value),
+            "anchor_offset_y" => self.anchor.anchor_offset_y.set_from_ez_value(value),
             "border" => self.border_config.border.set_from_ez_value(value),
             "horizontal_symbol" => self
                 .border_config
@@ -204,6 +314,15 @@ impl GenericState for ButtonState {
             "selection_bg_color" => self.colors.selection_bg_color.set_from_ez_value(value),
             "disabled_fg_color" => self.colors.disabled_fg_color.set_from_ez_value(value),
             "disabled_bg_color" => self.colors.disabled_bg_color.set_from_ez_value(value),
+            "auto_colors" => self.colors.auto_colors.set_from_ez_value(value),
+            "auto_contrast" => self.colors.auto_contrast.set_from_ez_value(value),
+            "selection_style" => self.colors.selection_style.set_from_ez_value(value),
+            "selection_marker" => self.colors.selection_marker.set_from_ez_value(value),
+            "hover_fg_color" => self.colors.hover_fg_color.set_from_ez_value(value),
+            "hover_bg_color" => self.colors.hover_bg_color.set_from_ez_value(value),
+            "pressed_fg_color" => self.colors.pressed_fg_color.set_from_ez_value(value),
+            "pressed_bg_color" => self.colors.pressed_bg_color.set_from_ez_value(value),
+            "focused_border" => self.colors.focused_border.set_from_ez_value(value),
             "tab_header_active_fg_color" => self
                 .colors
                 .tab_header_active_fg_color
@@ -217,6 +336,11 @@ impl GenericState for ButtonState {
             "flash_fg_color" => self.colors.flash_fg_color.set_from_ez_value(value),
             "flash_bg_color" => self.colors.flash_bg_color.set_from_ez_value(value),
             "text" => self.text.set_from_ez_value(value),
+            "long_press_time" => self.long_press_time.set_from_ez_value(value),
+            "toggle" => self.toggle.set_from_ez_value(value),
+            "active" => self.active.set_from_ez_value(value),
+            "icon" => self.icon.set_from_ez_value(value),
+            "repeat_interval" => self.repeat_interval.set_from_ez_value(value),
             _ => panic!("Invalid property name for button state: {}", name),
         }
     }
@@ -337,6 +461,46 @@ impl GenericState for ButtonState {
         self.selection_order.set(order);
     }
 
+    fn get_collapsed(&self) -> bool {
+        self.collapsed.value
+    }
+
+    fn set_collapsed(&mut self, collapsed: bool) {
+        self.collapsed.set(collapsed);
+    }
+
+    fn get_dock(&self) -> DockPosition {
+        self.dock.value
+    }
+
+    fn set_dock(&mut self, dock: DockPosition) {
+        self.dock.set(dock);
+    }
+
+    fn get_weight(&self) -> f64 {
+        self.weight.value
+    }
+
+    fn set_weight(&mut self, weight: f64) {
+        self.weight.set(weight);
+    }
+
+    fn get_z_index(&self) -> usize {
+        self.z_index.value
+    }
+
+    fn set_z_index(&mut self, z_index: usize) {
+        self.z_index.set(z_index);
+    }
+
+    fn get_anchor_config(&self) -> &AnchorConfig {
+        &self.anchor
+    }
+
+    fn get_anchor_config_mut(&mut self) -> &mut AnchorConfig {
+        &mut self.anchor
+    }
+
     fn set_selected(&mut self, state: bool) {
         self.selected = state;
     }
@@ -345,6 +509,24 @@ impl GenericState for ButtonState {
         self.selected
     }
 
+    fn set_hovered(&mut self, state: bool) {
+        self.hovered = state;
+    }
+
+    fn get_hovered(&self) -> bool {
+        self.hovered
+    }
+
+    fn set_held(&mut self, state: bool) {
+        self.held = state;
+    }
+
+    /// A button also looks held down while pressed by key, while flashing after a press and while
+    /// it is an active toggle button.
+    fn get_held(&self) -> bool {
+        self.held || self.pressed_since.is_some() || self.flashing || self.active.value
+    }
+
     fn copy_state_values(&mut self, other: EzState) {
         let other = other.as_button();
         self.position.x.copy_from(&other.position.x);
@@ -388,6 +570,17 @@ impl GenericState for ButtonState {
         self.valign.copy_from(&other.valign);
         self.disabled.copy_from(&other.disabled);
         self.selection_order.copy_from(&other.selection_order);
+        self.collapsed.copy_from(&other.collapsed);
+        self.dock.copy_from(&other.dock);
+        self.weight.copy_from(&other.weight);
+        self.z_index.copy_from(&other.z_index);
+        self.anchor.anchor.copy_from(&other.anchor.anchor);
+        self.anchor
+            .anchor_offset_x
+            .copy_from(&other.anchor.anchor_offset_x);
+        self.anchor
+            .anchor_offset_y
+            .copy_from(&other.anchor.anchor_offset_y);
         self.border_config
             .border
             .copy_from(&other.border_config.border);
@@ -423,6 +616,31 @@ impl GenericState for ButtonState {
         self.colors
             .disabled_bg_color
             .copy_from(&other.colors.disabled_bg_color);
+        self.colors.auto_colors.copy_from(&other.colors.auto_colors);
+        self.colors
+            .auto_contrast
+            .copy_from(&other.colors.auto_contrast);
+        self.colors
+            .selection_style
+            .copy_from(&other.colors.selection_style);
+        self.colors
+            .selection_marker
+            .copy_from(&other.colors.selection_marker);
+        self.colors
+            .hover_fg_color
+            .copy_from(&other.colors.hover_fg_color);
+        self.colors
+            .hover_bg_color
+            .copy_from(&other.colors.hover_bg_color);
+        self.colors
+            .pressed_fg_color
+            .copy_from(&other.colors.pressed_fg_color);
+        self.colors
+            .pressed_bg_color
+            .copy_from(&other.colors.pressed_bg_color);
+        self.colors
+            .focused_border
+            .copy_from(&other.colors.focused_border);
         self.colors
             .border_fg_color
             .copy_from(&other.colors.border_fg_color);
@@ -433,6 +651,11 @@ impl GenericState for ButtonState {
             .cursor_color
             .copy_from(&other.colors.cursor_color);
         self.text.copy_from(&other.text);
+        self.long_press_time.copy_from(&other.long_press_time);
+        self.toggle.copy_from(&other.toggle);
+        self.active.copy_from(&other.active);
+        self.icon.copy_from(&other.icon);
+        self.repeat_interval.copy_from(&other.repeat_interval);
     }
 
     fn clean_up_properties(&self, scheduler: &mut SchedulerFrontend) {
@@ -445,8 +668,18 @@ impl GenericState for ButtonState {
         clean_up_property(scheduler, &self.halign.name);
         clean_up_property(scheduler, &self.valign.name);
         clean_up_property(scheduler, &self.text.name);
+        clean_up_property(scheduler, &self.long_press_time.name);
+        clean_up_property(scheduler, &self.toggle.name);
+        clean_up_property(scheduler, &self.active.name);
+        clean_up_property(scheduler, &self.icon.name);
+        clean_up_property(scheduler, &self.repeat_interval.name);
         clean_up_property(scheduler, &self.disabled.name);
         clean_up_property(scheduler, &self.selection_order.name);
+        clean_up_property(scheduler, &self.collapsed.name);
+        clean_up_property(scheduler, &self.dock.name);
+        clean_up_property(scheduler, &self.weight.name);
+        clean_up_property(scheduler, &self.z_index.name);
+        self.anchor.clean_up_properties(scheduler);
         self.border_config.clean_up_properties(scheduler);
         self.colors.clean_up_properties(scheduler);
     }
@@ -467,4 +700,111 @@ impl ButtonState {
     pub fn get_flashing(&self) -> bool {
         self.flashing
     }
+
+    pub fn set_long_press_time(&mut self, milliseconds: usize) {
+        self.long_press_time.set(milliseconds);
+    }
+
+    pub fn get_long_press_time(&self) -> usize {
+        self.long_press_time.value
+    }
+
+    pub fn set_toggle(&mut self, toggle: bool) {
+        self.toggle.set(toggle);
+    }
+
+    pub fn get_toggle(&self) -> bool {
+        self.toggle.value
+    }
+
+    /// Set whether a toggle button is pressed. Does not call the on_value_change callback.
+    pub fn set_active(&mut self, active: bool) {
+        self.active.set(active);
+    }
+
+    pub fn get_active(&self) -> bool {
+        self.active.value
+    }
+
+    pub fn set_icon(&mut self, icon: String) {
+        self.icon.set(icon);
+    }
+
+    pub fn get_icon(&self) -> String {
+        self.icon.value.clone()
+    }
+
+    pub fn set_repeat_interval(&mut self, milliseconds: usize) {
+        self.repeat_interval.set(milliseconds);
+    }
+
+    pub fn get_repeat_interval(&self) -> usize {
+        self.repeat_interval.value
+    }
+
+    /// Returns a bool representing whether the button is currently held down by the mouse or the
+    /// enter key.
+    pub fn get_pressed(&self) -> bool {
+        self.pressed_since.is_some()
+    }
+
+    /// Returns when the button was pressed down, if it is currently held down.
+    pub fn get_pressed_since(&self) -> Option<Instant> {
+        self.pressed_since
+    }
+
+    /// Returns a bool representing whether the button is held down by the enter key rather than
+    /// the mouse.
+    pub fn get_pressed_by_key(&self) -> bool {
+        self.pressed_by_key
+    }
+
+    /// Returns when the last (repeated) enter key press was received while the button is held
+    /// down by the keyboard.
+    pub fn get_last_key_press(&self) -> Option<Instant> {
+        self.last_key_press
+    }
+
+    /// Returns a bool representing whether the current press has become a long press.
+    pub fn get_long_pressed(&self) -> bool {
+        self.long_pressed
+    }
+
+    /// Mark the button as held down. Used internally, called when the button is pressed.
+    pub fn set_pressed(&mut self, since: Instant, by_key: bool) {
+        self.pressed_since = Some(since);
+        self.pressed_by_key = by_key;
+        self.last_key_press = if by_key { Some(since) } else { None };
+        self.long_pressed = false;
+        self.last_repeat = None;
+    }
+
+    /// Mark the button as released. Used internally, called when the button is released.
+    pub fn set_released(&mut self) {
+        self.pressed_since = None;
+        self.pressed_by_key = false;
+        self.last_key_press = None;
+        self.long_pressed = false;
+        self.last_repeat = None;
+    }
+
+    /// Register a repeated enter key press while the button is held down. Used internally.
+    pub fn set_last_key_press(&mut self, time: Instant) {
+        self.last_key_press = Some(time);
+    }
+
+    /// Mark the current press as a long press. Used internally.
+    pub fn set_long_pressed(&mut self, long_pressed: bool) {
+        self.long_pressed = long_pressed;
+    }
+
+    /// Returns when on_press was last repeated during the current press, if it was repeated.
+    pub fn get_last_repeat(&self) -> Option<Instant> {
+        self.last_repeat
+    }
+
+    /// Register a repeated on_press callback while the button is held down. Used internally.
+    pub fn set_last_repeat(&mut self, time: Instant) {
+        self.last_repeat = Some(time);
+    }
 }
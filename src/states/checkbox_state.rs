@@ -3,8 +3,8 @@ use crate::run::definitions::IsizeCoordinates;
 use crate::scheduler::scheduler::SchedulerFrontend;
 use crate::scheduler::scheduler_funcs::clean_up_property;
 use crate::states::definitions::{
-    AutoScale, BorderConfig, ColorConfig, HorizontalAlignment, InfiniteSize, Padding, PosHint,
-    SizeHint, StateCoordinates, StateSize, VerticalAlignment,
+    AnchorConfig, AutoScale, BorderConfig, ColorConfig, DockPosition, HorizontalAlignment,
+    InfiniteSize, Padding, PosHint, SizeHint, StateCoordinates, StateSize, VerticalAlignment,
 };
 use crate::states::ez_state::{EzState, GenericState};
 use crate::EzProperty;
@@ -66,8 +66,32 @@ pub struct CheckboxState {
     /// Global order number in which this widget will be selection when user presses down/up keys
     pub selection_order: EzProperty<usize>,
 
+    /// Whether this widget is collapsed when its' parent is a [LayoutMode::Dock] layout
+    pub collapsed: EzProperty<bool>,
+
+    /// Where this widget is placed when its' parent is a [LayoutMode::Dock] layout
+    pub dock: EzProperty<DockPosition>,
+
+    /// Share of the leftover space this widget gets in a box layout, relative to the weights of
+    /// its' siblings. 0.0 means the widget is not weighted and is sized normally
+    pub weight: EzProperty<f64>,
+
+    /// Order in which overlapping children of a [LayoutMode::Float] layout are drawn; higher
+    /// values are drawn on top. Children with the same z index are drawn in declaration order
+    pub z_index: EzProperty<usize>,
+
+    /// Terminal edge or corner this widget is kept at when its' parent is a [LayoutMode::Float]
+    /// layout, see [Anchor]
+    pub anchor: AnchorConfig,
+
     /// Bool representing whether this widget is currently selected. Internal only.
     selected: bool,
+
+    /// Bool representing whether the mouse is currently over this widget.
+    hovered: bool,
+
+    /// Bool representing whether this widget is currently held down by the mouse.
+    held: bool,
 }
 impl CheckboxState {
     pub fn new(path: String, scheduler: &mut SchedulerFrontend) -> Self {
@@ -98,8 +122,18 @@ impl CheckboxState {
             ),
             disabled: scheduler.new_bool_property(format!("{}/disabled", path).as_str(), false),
             selected: false,
+            hovered: false,
+            held: false,
             selection_order: scheduler
                 .new_usize_property(format!("{}/selection_order", path).as_str(), 0),
+            collapsed: scheduler.new_bool_property(format!("{}/collapsed", path).as_str(), false),
+            dock: scheduler.new_dock_position_property(
+                format!("{}/dock", path).as_str(),
+                DockPosition::Center,
+            ),
+            weight: scheduler.new_f64_property(format!("{}/weight", path).as_str(), 0.0),
+            z_index: scheduler.new_usize_property(format!("{}/z_index", path).as_str(), 0),
+            anchor: AnchorConfig::new(path.clone(), scheduler),
             border_config: BorderConfig::new(false, path.clone(), scheduler),
             colors: ColorConfig::new(path, scheduler),
         }
@@ -130,6 +164,13 @@ impl GenericState for CheckboxState {
             "valign" => EzValues::VerticalAlignment(self.valign.value),
             "disabled" => EzValues::Bool(self.disabled.value),
             "selection_order" => EzValues::Usize(self.selection_order.value),
+            "collapsed" => EzValues::Bool(self.collapsed.value),
+            "dock" => EzValues::DockPosition(self.dock.value),
+            "weight" => EzValues::F64(self.weight.value),
+            "z_index" => EzValues::Usize(self.z_index.value),
+            "anchor" => EzValues::String(self.anchor.anchor.value.clone()),
+            "anchor_offset_x" => EzValues::Usize(self.anchor.anchor_offset_x.value),
+            "anchor_offset_y" => EzValues::Usize(self.anchor.anchor_offset_y.value),
             "border" => EzValues::Bool(self.border_config.border.value),
             "horizontal_symbol" => {
                 EzValues::String(self.border_config.horizontal_symbol.value.to_string())
@@ -155,6 +196,15 @@ impl GenericState for CheckboxState {
             "selection_bg_color" => EzValues::Color(self.colors.selection_bg_color.value),
             "disabled_fg_color" => EzValues::Color(self.colors.disabled_fg_color.value),
             "disabled_bg_color" => EzValues::Color(self.colors.disabled_bg_color.value),
+            "auto_colors" => EzValues::Bool(self.colors.auto_colors.value),
+            "auto_contrast" => EzValues::Bool(self.colors.auto_contrast.value),
+            "selection_style" => EzValues::String(self.colors.selection_style.value.clone()),
+            "selection_marker" => EzValues::String(self.colors.selection_marker.value.clone()),
+            "hover_fg_color" => EzValues::Color(self.colors.hover_fg_color.value),
+            "hover_bg_color" => EzValues::Color(self.colors.hover_bg_color.value),
+            "pressed_fg_color" => EzValues::Color(self.colors.pressed_fg_color.value),
+            "pressed_bg_color" => EzValues::Color(self.colors.pressed_bg_color.value),
+            "focused_border" => EzValues::Bool(self.colors.focused_border.value),
             "border_fg_color" => EzValues::Color(self.colors.border_fg_color.value),
             "border_bg_color" => EzValues::Color(self.colors.border_bg_color.value),
             "active" => EzValues::Bool(self.active.value),
@@ -183,6 +233,13 @@ impl GenericState for CheckboxState {
             "valign" => self.valign.set_from_ez_value(value),
             "disabled" => self.disabled.set_from_ez_value(value),
             "selection_order" => self.selection_order.set_from_ez_value(value),
+            "collapsed" => self.collapsed.set_from_ez_value(value),
+            "dock" => self.dock.set_from_ez_value(value),
+            "weight" => self.weight.set_from_ez_value(value),
+            "z_index" => self.z_index.set_from_ez_value(value),
+            "anchor" => self.anchor.anchor.set_from_ez_value(value),
+            "anchor_offset_x" => self.anchor.anchor_offset_x.set_from_ez_value(value),
+            "anchor_offset_y" => self.anchor.anchor_offset_y.set_from_ez_value(value),
             "border" => self.border_config.border.set_from_ez_value(value),
             "horizontal_symbol" => self
                 .border_config
@@ -205,6 +262,15 @@ impl GenericState for CheckboxState {
             "selection_bg_color" => self.colors.selection_bg_color.set_from_ez_value(value),
             "disabled_fg_color" => self.colors.disabled_fg_color.set_from_ez_value(value),
             "disabled_bg_color" => self.colors.disabled_bg_color.set_from_ez_value(value),
+            "auto_colors" => self.colors.auto_colors.set_from_ez_value(value),
+            "auto_contrast" => self.colors.auto_contrast.set_from_ez_value(value),
+            "selection_style" => self.colors.selection_style.set_from_ez_value(value),
+            "selection_marker" => self.colors.selection_marker.set_from_ez_value(value),
+            "hover_fg_color" => self.colors.hover_fg_color.set_from_ez_value(value),
+            "hover_bg_color" => self.colors.hover_bg_color.set_from_ez_value(value),
+            "pressed_fg_color" => self.colors.pressed_fg_color.set_from_ez_value(value),
+            "pressed_bg_color" => self.colors.pressed_bg_color.set_from_ez_value(value),
+            "focused_border" => self.colors.focused_border.set_from_ez_value(value),
             "border_fg_color" => self.colors.border_fg_color.set_from_ez_value(value),
             "border_bg_color" => self.colors.border_bg_color.set_from_ez_value(value),
             "active" => self.active.set_from_ez_value(value),
@@ -257,6 +323,17 @@ impl GenericState for CheckboxState {
         self.valign.copy_from(&other.valign);
         self.disabled.copy_from(&other.disabled);
         self.selection_order.copy_from(&other.selection_order);
+        self.collapsed.copy_from(&other.collapsed);
+        self.dock.copy_from(&other.dock);
+        self.weight.copy_from(&other.weight);
+        self.z_index.copy_from(&other.z_index);
+        self.anchor.anchor.copy_from(&other.anchor.anchor);
+        self.anchor
+            .anchor_offset_x
+            .copy_from(&other.anchor.anchor_offset_x);
+        self.anchor
+            .anchor_offset_y
+            .copy_from(&other.anchor.anchor_offset_y);
         self.border_config
             .border
             .copy_from(&other.border_config.border);
@@ -292,6 +369,31 @@ impl GenericState for CheckboxState {
         self.colors
             .disabled_bg_color
             .copy_from(&other.colors.disabled_bg_color);
+        self.colors.auto_colors.copy_from(&other.colors.auto_colors);
+        self.colors
+            .auto_contrast
+            .copy_from(&other.colors.auto_contrast);
+        self.colors
+            .selection_style
+            .copy_from(&other.colors.selection_style);
+        self.colors
+            .selection_marker
+            .copy_from(&other.colors.selection_marker);
+        self.colors
+            .hover_fg_color
+            .copy_from(&other.colors.hover_fg_color);
+        self.colors
+            .hover_bg_color
+            .copy_from(&other.colors.hover_bg_color);
+        self.colors
+            .pressed_fg_color
+            .copy_from(&other.colors.pressed_fg_color);
+        self.colors
+            .pressed_bg_color
+            .copy_from(&other.colors.pressed_bg_color);
+        self.colors
+            .focused_border
+            .copy_from(&other.colors.focused_border);
         self.colors
             .border_fg_color
             .copy_from(&other.colors.border_fg_color);
@@ -421,6 +523,46 @@ impl GenericState for CheckboxState {
         self.selection_order.set(order);
     }
 
+    fn get_collapsed(&self) -> bool {
+        self.collapsed.value
+    }
+
+    fn set_collapsed(&mut self, collapsed: bool) {
+        self.collapsed.set(collapsed);
+    }
+
+    fn get_dock(&self) -> DockPosition {
+        self.dock.value
+    }
+
+    fn set_dock(&mut self, dock: DockPosition) {
+        self.dock.set(dock);
+    }
+
+    fn get_weight(&self) -> f64 {
+        self.weight.value
+    }
+
+    fn set_weight(&mut self, weight: f64) {
+        self.weight.set(weight);
+    }
+
+    fn get_z_index(&self) -> usize {
+        self.z_index.value
+    }
+
+    fn set_z_index(&mut self, z_index: usize) {
+        self.z_index.set(z_index);
+    }
+
+    fn get_anchor_config(&self) -> &AnchorConfig {
+        &self.anchor
+    }
+
+    fn get_anchor_config_mut(&mut self) -> &mut AnchorConfig {
+        &mut self.anchor
+    }
+
     fn set_selected(&mut self, state: bool) {
         self.selected = state;
     }
@@ -429,6 +571,22 @@ impl GenericState for CheckboxState {
         self.selected
     }
 
+    fn set_hovered(&mut self, state: bool) {
+        self.hovered = state;
+    }
+
+    fn get_hovered(&self) -> bool {
+        self.hovered
+    }
+
+    fn set_held(&mut self, state: bool) {
+        self.held = state;
+    }
+
+    fn get_held(&self) -> bool {
+        self.held
+    }
+
     fn clean_up_properties(&self, scheduler: &mut SchedulerFrontend) {
         self.position.clean_up_properties(scheduler);
         self.size.clean_up_properties(scheduler);
@@ -443,6 +601,11 @@ impl GenericState for CheckboxState {
         clean_up_property(scheduler, &self.inactive_symbol.name);
         clean_up_property(scheduler, &self.disabled.name);
         clean_up_property(scheduler, &self.selection_order.name);
+        clean_up_property(scheduler, &self.collapsed.name);
+        clean_up_property(scheduler, &self.dock.name);
+        clean_up_property(scheduler, &self.weight.name);
+        clean_up_property(scheduler, &self.z_index.name);
+        self.anchor.clean_up_properties(scheduler);
         self.border_config.clean_up_properties(scheduler);
         self.colors.clean_up_properties(scheduler);
     }
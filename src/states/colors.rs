@@ -0,0 +1,232 @@
+//! # Colors
+//!
+//! Functions to derive colors from other colors, e.g. a dimmed variant of a color for disabled
+//! widgets or a readable foreground color for a background color. Colors are converted to RGB to
+//! transform them, so the results are always [Color::Rgb]; [Color::Reset] is returned as is,
+//! since the actual color is up to the terminal.
+use crossterm::style::Color;
+
+/// Minimum contrast ratio between a foreground and background color for text to be readable.
+/// This is the ratio required by WCAG for normal text.
+pub const MIN_CONTRAST_RATIO: f64 = 4.5;
+
+/// Amount of lightness a [hover_color] differs from the original color.
+const HOVER_LIGHTNESS: f64 = 0.15;
+
+/// Get the RGB values of a color. Named and ANSI colors are converted using the default xterm
+/// palette. Returns None for [Color::Reset], as its' actual value is up to the terminal.
+pub fn to_rgb(color: Color) -> Option<(u8, u8, u8)> {
+    let rgb = match color {
+        Color::Reset => return None,
+        Color::Black => (0, 0, 0),
+        Color::DarkRed => (128, 0, 0),
+        Color::DarkGreen => (0, 128, 0),
+        Color::DarkYellow => (128, 128, 0),
+        Color::DarkBlue => (0, 0, 128),
+        Color::DarkMagenta => (128, 0, 128),
+        Color::DarkCyan => (0, 128, 128),
+        Color::Grey => (192, 192, 192),
+        Color::DarkGrey => (128, 128, 128),
+        Color::Red => (255, 0, 0),
+        Color::Green => (0, 255, 0),
+        Color::Yellow => (255, 255, 0),
+        Color::Blue => (0, 0, 255),
+        Color::Magenta => (255, 0, 255),
+        Color::Cyan => (0, 255, 255),
+        Color::White => (255, 255, 255),
+        Color::Rgb { r, g, b } => (r, g, b),
+        Color::AnsiValue(i) => return Some(ansi_to_rgb(i)),
+    };
+    Some(rgb)
+}
+
+/// Get the RGB values of a color of the 256 color ANSI palette.
+fn ansi_to_rgb(value: u8) -> (u8, u8, u8) {
+    const BASE: [Color; 16] = [
+        Color::Black,
+        Color::DarkRed,
+        Color::DarkGreen,
+        Color::DarkYellow,
+        Color::DarkBlue,
+        Color::DarkMagenta,
+        Color::DarkCyan,
+        Color::Grey,
+        Color::DarkGrey,
+        Color::Red,
+        Color::Green,
+        Color::Yellow,
+        Color::Blue,
+        Color::Magenta,
+        Color::Cyan,
+        Color::White,
+    ];
+    match value {
+        0..=15 => to_rgb(BASE[value as usize]).unwrap(),
+        16..=231 => {
+            let level = |x: u8| if x == 0 { 0 } else { 55 + x * 40 };
+            let i = value - 16;
+            (level(i / 36), level(i / 6 % 6), level(i % 6))
+        }
+        _ => {
+            let grey = 8 + (value - 232) * 10;
+            (grey, grey, grey)
+        }
+    }
+}
+
+/// Convert RGB values to hue (0-360), saturation (0-1) and lightness (0-1).
+fn rgb_to_hsl((r, g, b): (u8, u8, u8)) -> (f64, f64, f64) {
+    let (r, g, b) = (r as f64 / 255.0, g as f64 / 255.0, b as f64 / 255.0);
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let lightness = (max + min) / 2.0;
+    if max == min {
+        return (0.0, 0.0, lightness);
+    }
+    let delta = max - min;
+    let saturation = if lightness > 0.5 {
+        delta / (2.0 - max - min)
+    } else {
+        delta / (max + min)
+    };
+    let hue = if max == r {
+        (g - b) / delta + if g < b { 6.0 } else { 0.0 }
+    } else if max == g {
+        (b - r) / delta + 2.0
+    } else {
+        (r - g) / delta + 4.0
+    };
+    (hue * 60.0, saturation, lightness)
+}
+
+/// Convert hue (0-360), saturation (0-1) and lightness (0-1) to an RGB color.
+fn hsl_to_color((hue, saturation, lightness): (f64, f64, f64)) -> Color {
+    let (saturation, lightness) = (saturation.clamp(0.0, 1.0), lightness.clamp(0.0, 1.0));
+    let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+    let sector = hue.rem_euclid(360.0) / 60.0;
+    let x = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+    let (r, g, b) = match sector as usize {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let offset = lightness - chroma / 2.0;
+    let channel = |x: f64| ((x + offset) * 255.0).round() as u8;
+    Color::Rgb {
+        r: channel(r),
+        g: channel(g),
+        b: channel(b),
+    }
+}
+
+/// Change the HSL values of a color.
+fn transform_hsl(color: Color, transform: impl Fn((f64, f64, f64)) -> (f64, f64, f64)) -> Color {
+    match to_rgb(color) {
+        Some(rgb) => hsl_to_color(transform(rgb_to_hsl(rgb))),
+        None => color,
+    }
+}
+
+/// Make a color lighter by increasing its' lightness by an amount between 0 and 1.
+pub fn lighten(color: Color, amount: f64) -> Color {
+    transform_hsl(color, |(h, s, l)| (h, s, l + amount))
+}
+
+/// Make a color darker by decreasing its' lightness by an amount between 0 and 1.
+pub fn darken(color: Color, amount: f64) -> Color {
+    transform_hsl(color, |(h, s, l)| (h, s, l - amount))
+}
+
+/// Make a color greyer by decreasing its' saturation by an amount between 0 and 1.
+pub fn desaturate(color: Color, amount: f64) -> Color {
+    transform_hsl(color, |(h, s, l)| (h, s - amount, l))
+}
+
+/// Mix two colors. An amount of 0 returns the first color, an amount of 1 the second color.
+pub fn blend(color: Color, other: Color, amount: f64) -> Color {
+    let (from, to) = match (to_rgb(color), to_rgb(other)) {
+        (Some(from), Some(to)) => (from, to),
+        _ => return color,
+    };
+    let amount = amount.clamp(0.0, 1.0);
+    let mix = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * amount).round() as u8;
+    Color::Rgb {
+        r: mix(from.0, to.0),
+        g: mix(from.1, to.1),
+        b: mix(from.2, to.2),
+    }
+}
+
+/// Draw a color with an opacity between 0 (invisible) and 1 (unchanged) over a background color.
+pub fn dim(color: Color, background: Color, opacity: f64) -> Color {
+    blend(color, background, 1.0 - opacity)
+}
+
+/// Get a variant of a color that stands out from it, e.g. for a widget that is hovered or
+/// selected. Dark colors are made lighter and light colors darker.
+pub fn hover_color(color: Color) -> Color {
+    match to_rgb(color) {
+        Some(rgb) if rgb_to_hsl(rgb).2 > 0.5 => darken(color, HOVER_LIGHTNESS),
+        Some(_) => lighten(color, HOVER_LIGHTNESS),
+        None => color,
+    }
+}
+
+/// Get a muted variant of a foreground color for a disabled widget: greyed out and half
+/// transparent over the background color.
+pub fn disabled_color(foreground: Color, background: Color) -> Color {
+    desaturate(dim(foreground, background, 0.5), 0.5)
+}
+
+/// Get the relative luminance of a color as defined by WCAG, between 0 (black) and 1 (white).
+pub fn relative_luminance(color: Color) -> f64 {
+    let (r, g, b) = to_rgb(color).unwrap_or((0, 0, 0));
+    let linear = |x: u8| {
+        let x = x as f64 / 255.0;
+        if x <= 0.03928 {
+            x / 12.92
+        } else {
+            ((x + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    0.2126 * linear(r) + 0.7152 * linear(g) + 0.0722 * linear(b)
+}
+
+/// Get the contrast ratio between two colors as defined by WCAG, between 1 (no contrast) and 21
+/// (black on white).
+pub fn contrast_ratio(color: Color, other: Color) -> f64 {
+    let (a, b) = (relative_luminance(color), relative_luminance(other));
+    (a.max(b) + 0.05) / (a.min(b) + 0.05)
+}
+
+/// Get a foreground color that is readable on a background color. The foreground color is kept if
+/// it has enough contrast (see [MIN_CONTRAST_RATIO]); otherwise it is made lighter or darker
+/// (whichever contrasts more with the background) until it does, keeping its' hue where possible.
+pub fn readable_fg(foreground: Color, background: Color) -> Color {
+    if to_rgb(foreground).is_none()
+        || to_rgb(background).is_none()
+        || contrast_ratio(foreground, background) >= MIN_CONTRAST_RATIO
+    {
+        return foreground;
+    }
+    let lighter = contrast_ratio(Color::White, background) >= contrast_ratio(Color::Black, background);
+    let mut candidate = foreground;
+    for _ in 0..20 {
+        candidate = if lighter {
+            lighten(candidate, 0.05)
+        } else {
+            darken(candidate, 0.05)
+        };
+        if contrast_ratio(candidate, background) >= MIN_CONTRAST_RATIO {
+            return candidate;
+        }
+    }
+    if lighter {
+        Color::White
+    } else {
+        Color::Black
+    }
+}
@@ -1,13 +1,19 @@
+use crate::parser::parse_properties::{
+    parse_anchor_property, parse_color_property, parse_selection_style_property,
+};
 use crate::property::ez_property::EzProperty;
 use crate::scheduler::definitions::{
-    GenericFunction, KeyboardCallbackFunction, MouseCallbackFunction, MouseDragCallbackFunction,
-    OptionalMouseCallbackFunction,
+    DragStartCallbackFunction, DropCallbackFunction, GenericFunction, KeyboardCallbackFunction,
+    MouseCallbackFunction, MouseDragCallbackFunction, OptionalMouseCallbackFunction,
+    PasteCallbackFunction,
 };
 use crate::scheduler::scheduler::SchedulerFrontend;
 use crate::scheduler::scheduler_funcs::clean_up_property;
+use crate::states::colors::{disabled_color, hover_color, readable_fg};
 use crossterm::event::{KeyCode, KeyModifiers};
 use crossterm::style::Color;
 use std::collections::HashMap;
+use std::time::Duration;
 
 /// The mode determining how widgets are placed in a [layout]. Default is box.
 #[derive(Clone, PartialEq, Debug)]
@@ -40,6 +46,12 @@ pub enum LayoutMode {
     /// automatically be added for each child layout, so the user can switch between tabs. The tab
     /// header will display the [id] of the child layout.
     Tab,
+
+    /// Children are docked to the edges of the layout according to their [DockPosition], in the
+    /// order they were added. Docked children can be resized by dragging the splitter next to
+    /// them, collapsed by clicking their top row, and undocked or re-docked by dragging their top
+    /// row. The remaining space goes to the child docked to the center.
+    Dock,
 }
 
 /// Used with Box, stack and table mode [layout]. Default is horizontal for box,
@@ -58,6 +70,40 @@ pub enum LayoutOrientation {
     BottomTopRightLeft,
 }
 
+/// Used by children of a [LayoutMode::Dock] layout to determine where they are placed; default is
+/// center.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum DockPosition {
+    Left,
+    Right,
+    Top,
+    Bottom,
+    Center,
+    Float,
+}
+
+/// Used by [LayoutMode::Dock] layouts to remember what is being dragged between drag events.
+#[derive(PartialEq, Clone, Debug)]
+pub enum DockDrag {
+    /// The splitter of the docked child with this path is being dragged to resize the child
+    Splitter(String),
+
+    /// The child with this path is being dragged by its' top row to move it
+    Panel(String),
+}
+
+/// Used by movable and resizable children of a [LayoutMode::Float] layout to remember what is
+/// being dragged between drag events.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum WindowDrag {
+    /// The window is being moved by its' title bar
+    Move,
+
+    /// The window is being resized by its' edges. The bools represent whether width and height
+    /// are being resized respectively.
+    Resize(bool, bool),
+}
+
 /// Property determining how content is placed horizontally in a layout; default is left.
 #[derive(PartialEq, Clone, Copy, Debug)]
 pub enum HorizontalAlignment {
@@ -441,6 +487,64 @@ impl PosHint {
     }
 }
 
+/// Composite object containing the edge or corner of the terminal a widget is anchored to and the
+/// distance it keeps from that edge, see [Anchor]. Only used when the parent is a
+/// [LayoutMode::Float] layout.
+#[derive(PartialEq, Clone, Debug)]
+pub struct AnchorConfig {
+    pub anchor: EzProperty<String>,
+    pub anchor_offset_x: EzProperty<usize>,
+    pub anchor_offset_y: EzProperty<usize>,
+}
+impl AnchorConfig {
+    pub fn new(name: String, scheduler: &mut SchedulerFrontend) -> Self {
+        AnchorConfig {
+            anchor: scheduler.new_string_property(
+                format!("{}/anchor", name).as_str(),
+                Anchor::None.get_name().to_string(),
+            ),
+            anchor_offset_x: scheduler
+                .new_usize_property(format!("{}/anchor_offset_x", name).as_str(), 0),
+            anchor_offset_y: scheduler
+                .new_usize_property(format!("{}/anchor_offset_y", name).as_str(), 0),
+        }
+    }
+
+    pub fn set_anchor(&mut self, anchor: Anchor) {
+        self.anchor.set(anchor.get_name().to_string());
+    }
+
+    pub fn get_anchor(&self) -> Anchor {
+        parse_anchor_property(&self.anchor.value).unwrap_or(Anchor::None)
+    }
+
+    /// Set the distance to the anchored left or right edge. For anchors that center the widget
+    /// horizontally, the widget is moved right by this amount.
+    pub fn set_anchor_offset_x(&mut self, offset: usize) {
+        self.anchor_offset_x.set(offset);
+    }
+
+    pub fn get_anchor_offset_x(&self) -> usize {
+        self.anchor_offset_x.value
+    }
+
+    /// Set the distance to the anchored top or bottom edge. For anchors that center the widget
+    /// vertically, the widget is moved down by this amount.
+    pub fn set_anchor_offset_y(&mut self, offset: usize) {
+        self.anchor_offset_y.set(offset);
+    }
+
+    pub fn get_anchor_offset_y(&self) -> usize {
+        self.anchor_offset_y.value
+    }
+
+    pub fn clean_up_properties(&self, scheduler: &mut SchedulerFrontend) {
+        clean_up_property(scheduler, &self.anchor.name);
+        clean_up_property(scheduler, &self.anchor_offset_x.name);
+        clean_up_property(scheduler, &self.anchor_offset_y.name);
+    }
+}
+
 /// Convenience wrapper around a callback configuration. Here is an example of how to use this
 /// object; we will set an on_press callback:
 /// ```
@@ -547,6 +651,62 @@ pub struct CallbackConfig {
     /// ```
     pub on_press: Option<GenericFunction>,
 
+    /// This callback is activated when a button is held down (with the left mouse button, or by
+    /// holding enter so the key repeats) for longer than its' long_press_time. If this callback
+    /// is set, a mouse press that became a long press does not call on_press when released, and
+    /// repeated enter presses while holding the key do not call on_press again.
+    /// To set this callback with a closure:
+    /// ```
+    /// use ez_term::*;
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// let my_callback = move |context: Context| {
+    ///
+    ///     true
+    /// };
+    /// let new_callback_config = CallbackConfig::from_on_long_press(Box::new(my_callback));
+    /// scheduler.update_callback_config("my_button", new_callback_config);
+    /// ```
+    pub on_long_press: Option<GenericFunction>,
+
+    /// This callback is activated when a button is pushed down, either with the left mouse button
+    /// or with the enter key. Together with on_release this allows building controls that act
+    /// while held down, e.g. scheduling a recurring task on press start and cancelling it on
+    /// release.
+    /// To set this callback with a closure:
+    /// ```
+    /// use ez_term::*;
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// let my_callback = move |context: Context| {
+    ///     context.scheduler.schedule_recurring("increment", Box::new(|context: Context| {
+    ///         // Increment something
+    ///         true
+    ///     }), std::time::Duration::from_millis(100));
+    ///     true
+    /// };
+    /// let new_callback_config = CallbackConfig::from_on_press_start(Box::new(my_callback));
+    /// scheduler.update_callback_config("my_button", new_callback_config);
+    /// ```
+    pub on_press_start: Option<GenericFunction>,
+
+    /// This callback is activated when a button that was pushed down is released. For the mouse
+    /// this is when the mouse button is released (anywhere on the screen); for the keyboard this
+    /// is when the enter key stopped repeating.
+    /// To set this callback with a closure:
+    /// ```
+    /// use ez_term::*;
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// let my_callback = move |context: Context| {
+    ///     context.scheduler.cancel_recurring_task("increment");
+    ///     true
+    /// };
+    /// let new_callback_config = CallbackConfig::from_on_release(Box::new(my_callback));
+    /// scheduler.update_callback_config("my_button", new_callback_config);
+    /// ```
+    pub on_release: Option<GenericFunction>,
+
     /// This callback is activated when a widget is selected and the 'enter' key is pressed on the
     /// keyboard.
     /// To set this callback with a closure:
@@ -764,6 +924,70 @@ pub struct CallbackConfig {
     /// scheduler.update_callback_config("my_label", new_callback_config);
     /// ```
     pub on_drag_exit: Option<MouseDragCallbackFunction>,
+
+    /// This callback is activated when a drag starts on a widget, before its' on_drag callback.
+    /// Return a [DragPayload] to start a drag and drop: the payload is carried along until the
+    /// mouse button is released, and then passed to the on_drop callback of the widget it is
+    /// released on. Return None to let the drag pass on to the parents of the widget. The callback
+    /// receives the position the drag started on, so a widget showing several items can tell
+    /// which item is dragged. To set this callback with a closure:
+    /// ```
+    /// use ez_term::*;
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// let my_callback = move |context: Context, mouse_pos: IsizeCoordinates| {
+    ///
+    ///     Some(DragPayload::new("task_1"))
+    /// };
+    /// let new_callback_config = CallbackConfig::from_on_drag_start(Box::new(my_callback));
+    /// scheduler.update_callback_config("my_label", new_callback_config);
+    /// ```
+    /// To set this callback with a function:
+    /// ```
+    /// use ez_term::*;
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// fn my_callback(context: Context, mouse_pos: IsizeCoordinates) -> Option<DragPayload> {
+    ///
+    ///     Some(DragPayload::new("task_1"))
+    /// };
+    /// let new_callback_config = CallbackConfig::from_on_drag_start(Box::new(my_callback));
+    /// scheduler.update_callback_config("my_label", new_callback_config);
+    /// ```
+    pub on_drag_start: Option<DragStartCallbackFunction>,
+
+    /// This callback is activated when a [DragPayload] is dropped on a widget (i.e. when the left
+    /// mouse button is released during a drag and drop). The widget on top receives the payload
+    /// first; if its' callback does not return true, the payload is offered to its' parents, so
+    /// e.g. a column layout can accept a task dropped on one of the tasks it holds. The callback
+    /// receives the payload and the position it was dropped on. To set this callback with a
+    /// closure:
+    /// ```
+    /// use ez_term::*;
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// let my_callback = move |context: Context, payload: DragPayload,
+    ///                         mouse_pos: IsizeCoordinates| {
+    ///     context.scheduler.move_widget(&payload.data, &context.widget_path, None);
+    ///     true
+    /// };
+    /// let new_callback_config = CallbackConfig::from_on_drop(Box::new(my_callback));
+    /// scheduler.update_callback_config("my_layout", new_callback_config);
+    /// ```
+    /// To set this callback with a function:
+    /// ```
+    /// use ez_term::*;
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// fn my_callback(context: Context, payload: DragPayload,
+    ///                mouse_pos: IsizeCoordinates) -> bool {
+    ///     context.scheduler.move_widget(&payload.data, &context.widget_path, None);
+    ///     true
+    /// };
+    /// let new_callback_config = CallbackConfig::from_on_drop(Box::new(my_callback));
+    /// scheduler.update_callback_config("my_layout", new_callback_config);
+    /// ```
+    pub on_drop: Option<DropCallbackFunction>,
     
     /// This callback is activated when a widget is scrolled up by the mouse. Keep in mind that
     /// when a widget is scrolled, any layouts underneath it are also scrolled. The root layout is the
@@ -856,6 +1080,125 @@ pub struct CallbackConfig {
     /// ```
     pub on_value_change: Option<GenericFunction>,
 
+    /// This callback is activated when a row of a data grid is selected, by clicking it or with
+    /// the arrow keys. Get the selected row from the state of the data grid.
+    /// To set this callback with a closure:
+    /// ```
+    /// use ez_term::*;
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// let my_callback = move |context: Context| {
+    ///     let state = context.state_tree.get(&context.widget_path).as_data_grid();
+    ///     if let Some(row) = state.get_selected_row_values() {
+    ///         println!("Selected {}", row.join(", "));
+    ///     }
+    ///     true
+    /// };
+    /// let new_callback_config = CallbackConfig::from_on_row_select(Box::new(my_callback));
+    /// scheduler.update_callback_config("my_data_grid", new_callback_config);
+    /// ```
+    pub on_row_select: Option<GenericFunction>,
+
+    /// This callback is activated when an item of a list view is selected, by clicking it or with
+    /// the arrow keys. Get the selected item from the state of the list view.
+    /// To set this callback with a closure:
+    /// ```
+    /// use ez_term::*;
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// let my_callback = move |context: Context| {
+    ///     let state = context.state_tree.get(&context.widget_path).as_list_view();
+    ///     if let Some(item) = state.get_selected_item_value() {
+    ///         println!("Selected {}", item);
+    ///     }
+    ///     true
+    /// };
+    /// let new_callback_config = CallbackConfig::from_on_item_selected(Box::new(my_callback));
+    /// scheduler.update_callback_config("my_list_view", new_callback_config);
+    /// ```
+    pub on_item_selected: Option<GenericFunction>,
+
+    /// This callback is activated when text is pasted into the terminal while this widget is
+    /// selected. The callback receives the pasted text. Return true to consume the paste; for
+    /// text inputs this prevents the text from being inserted.
+    /// To set this callback with a closure:
+    /// ```
+    /// use ez_term::*;
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// let my_callback = move |context: Context, text: String| {
+    ///
+    ///     text.contains('\n')
+    /// };
+    /// let new_callback_config = CallbackConfig::from_on_paste(Box::new(my_callback));
+    /// scheduler.update_callback_config("my_text_input", new_callback_config);
+    /// ```
+    pub on_paste: Option<PasteCallbackFunction>,
+
+    /// This callback is activated for every event that travels through this widget to its'
+    /// target during the capture phase (see [EventPhase]), before the target widget handles the
+    /// event. Useful for layouts that want to intercept events meant for their children. The
+    /// event can be found in the [Context]. Return true to consume the event.
+    /// To set this callback with a closure:
+    /// ```
+    /// use ez_term::*;
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// let my_callback = move |mut context: Context| {
+    ///     if let Some(Event::Key(key)) = context.event {
+    ///         if key.code == KeyCode::Tab {
+    ///             context.event_control().prevent_default();
+    ///         }
+    ///     }
+    ///     false
+    /// };
+    /// let new_callback_config = CallbackConfig::from_on_capture(Box::new(my_callback));
+    /// scheduler.update_callback_config("my_layout", new_callback_config);
+    /// ```
+    pub on_capture: Option<GenericFunction>,
+
+    /// This callback is activated for every event that travels from its' target back through this
+    /// widget during the bubble phase (see [EventPhase]), after the target widget handled the
+    /// event without consuming it. The event can be found in the [Context]. Return true to
+    /// consume the event.
+    /// To set this callback with a closure:
+    /// ```
+    /// use ez_term::*;
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// let my_callback = move |context: Context| {
+    ///
+    ///     true
+    /// };
+    /// let new_callback_config = CallbackConfig::from_on_bubble(Box::new(my_callback));
+    /// scheduler.update_callback_config("my_layout", new_callback_config);
+    /// ```
+    pub on_bubble: Option<GenericFunction>,
+
+    /// This callback is activated once when there has been no input (keys, mouse or pastes) for
+    /// the idle_timeout of this config, or the idle timeout of the scheduler if it is not set
+    /// (see [SchedulerFrontend.set_idle_timeout]). After new input it can be activated again.
+    /// Useful for deferring expensive refreshes until the user stops interacting.
+    /// To set this callback with a closure:
+    /// ```
+    /// use std::time::Duration;
+    /// use ez_term::*;
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// let my_callback = move |context: Context| {
+    ///
+    ///     true
+    /// };
+    /// let mut new_callback_config = CallbackConfig::from_on_idle(Box::new(my_callback));
+    /// new_callback_config.idle_timeout = Some(Duration::from_secs(2));
+    /// scheduler.update_callback_config("my_label", new_callback_config);
+    /// ```
+    pub on_idle: Option<GenericFunction>,
+
+    /// How long there must be no input before on_idle is activated. If None, the idle timeout of
+    /// the scheduler is used.
+    pub idle_timeout: Option<Duration>,
+
     /// Hash containing keyboard key and modifiers as key, and a callback as a value. As an
     /// end-user, use CallbackConfig.bind_key, or scheduler.bind_global_key.
     pub keymap: KeyMap,
@@ -965,6 +1308,88 @@ impl CallbackConfig {
         obj
     }
 
+    /// Create a [CallbackConfig] from an on_row_select callback.
+    /// the callback function signature should be: (Context)
+    /// See [Context] for more information on the context.
+    pub fn from_on_row_select(func: GenericFunction) -> Self {
+        let mut obj = CallbackConfig::default();
+        obj.on_row_select = Some(func);
+        obj
+    }
+
+    /// Create a [CallbackConfig] from an on_item_selected callback.
+    /// the callback function signature should be: (Context)
+    /// See [Context] for more information on the context.
+    pub fn from_on_item_selected(func: GenericFunction) -> Self {
+        let mut obj = CallbackConfig::default();
+        obj.on_item_selected = Some(func);
+        obj
+    }
+
+    /// Create a [CallbackConfig] from an on_paste callback.
+    /// the callback function signature should be: (Context, String)
+    /// See [Context] for more information on the context. The string is the pasted text.
+    pub fn from_on_paste(func: PasteCallbackFunction) -> Self {
+        let mut obj = CallbackConfig::default();
+        obj.on_paste = Some(func);
+        obj
+    }
+
+    /// Create a [CallbackConfig] from an on_long_press callback.
+    /// the callback function signature should be: (Context)
+    /// See [Context] for more information on the context.
+    pub fn from_on_long_press(func: GenericFunction) -> Self {
+        let mut obj = CallbackConfig::default();
+        obj.on_long_press = Some(func);
+        obj
+    }
+
+    /// Create a [CallbackConfig] from an on_press_start callback.
+    /// the callback function signature should be: (Context)
+    /// See [Context] for more information on the context.
+    pub fn from_on_press_start(func: GenericFunction) -> Self {
+        let mut obj = CallbackConfig::default();
+        obj.on_press_start = Some(func);
+        obj
+    }
+
+    /// Create a [CallbackConfig] from an on_release callback.
+    /// the callback function signature should be: (Context)
+    /// See [Context] for more information on the context.
+    pub fn from_on_release(func: GenericFunction) -> Self {
+        let mut obj = CallbackConfig::default();
+        obj.on_release = Some(func);
+        obj
+    }
+
+    /// Create a [CallbackConfig] from an on_capture callback.
+    /// the callback function signature should be: (Context)
+    /// See [Context] for more information on the context.
+    pub fn from_on_capture(func: GenericFunction) -> Self {
+        let mut obj = CallbackConfig::default();
+        obj.on_capture = Some(func);
+        obj
+    }
+
+    /// Create a [CallbackConfig] from an on_bubble callback.
+    /// the callback function signature should be: (Context)
+    /// See [Context] for more information on the context.
+    pub fn from_on_bubble(func: GenericFunction) -> Self {
+        let mut obj = CallbackConfig::default();
+        obj.on_bubble = Some(func);
+        obj
+    }
+
+    /// Create a [CallbackConfig] from an on_idle callback.
+    /// the callback function signature should be: (Context)
+    /// See [Context] for more information on the context.
+    pub fn from_on_idle(func: GenericFunction) -> Self {
+        CallbackConfig {
+            on_idle: Some(func),
+            ..Default::default()
+        }
+    }
+
     /// Create a [CallbackConfig] from an on_hover callback.
     /// the callback function signature should be: (Context, Coordinates)
     /// See [Context] for more information on the context. The coordinates are the position of
@@ -1008,6 +1433,26 @@ impl CallbackConfig {
         obj
     }
 
+    /// Create a [CallbackConfig] from an on_drag_start callback.
+    /// the callback function signature should be: (Context, Coordinates) -> Option`<DragPayload`>
+    /// See [Context] for more information on the context. The coordinates are the position the
+    /// drag started on. Return a [DragPayload] to start a drag and drop.
+    pub fn from_on_drag_start(func: DragStartCallbackFunction) -> Self {
+        let mut obj = CallbackConfig::default();
+        obj.on_drag_start = Some(func);
+        obj
+    }
+
+    /// Create a [CallbackConfig] from an on_drop callback.
+    /// the callback function signature should be: (Context, DragPayload, Coordinates)
+    /// See [Context] for more information on the context. The coordinates are the position the
+    /// [DragPayload] was dropped on. Return true to accept the drop.
+    pub fn from_on_drop(func: DropCallbackFunction) -> Self {
+        let mut obj = CallbackConfig::default();
+        obj.on_drop = Some(func);
+        obj
+    }
+
     /// Create a new CallbackConfig based on an existing [KeyMap]. Allows you to fully set a KeyMap
     /// and then derive a CallbackConfig from it.
     pub fn from_keymap(keymap: KeyMap) -> Self {
@@ -1023,6 +1468,26 @@ impl CallbackConfig {
         } else {
             self.on_value_change = other.on_value_change
         };
+        if let None = other.on_row_select {
+        } else {
+            self.on_row_select = other.on_row_select
+        };
+        if let None = other.on_item_selected {
+        } else {
+            self.on_item_selected = other.on_item_selected
+        };
+        if let None = other.on_paste {
+        } else {
+            self.on_paste = other.on_paste
+        };
+        if let None = other.on_capture {
+        } else {
+            self.on_capture = other.on_capture
+        };
+        if let None = other.on_bubble {
+        } else {
+            self.on_bubble = other.on_bubble
+        };
         if let None = other.on_deselect {
         } else {
             self.on_deselect = other.on_deselect
@@ -1035,6 +1500,18 @@ impl CallbackConfig {
         } else {
             self.on_press = other.on_press
         };
+        if let None = other.on_long_press {
+        } else {
+            self.on_long_press = other.on_long_press
+        };
+        if let None = other.on_press_start {
+        } else {
+            self.on_press_start = other.on_press_start
+        };
+        if let None = other.on_release {
+        } else {
+            self.on_release = other.on_release
+        };
         if let None = other.on_left_mouse_click {
         } else {
             self.on_left_mouse_click = other.on_left_mouse_click
@@ -1071,6 +1548,18 @@ impl CallbackConfig {
         } else {
             self.on_drag_exit = other.on_drag_exit
         };
+        if other.on_drag_start.is_some() {
+            self.on_drag_start = other.on_drag_start
+        };
+        if other.on_drop.is_some() {
+            self.on_drop = other.on_drop
+        };
+        if other.on_idle.is_some() {
+            self.on_idle = other.on_idle
+        };
+        if other.idle_timeout.is_some() {
+            self.idle_timeout = other.idle_timeout
+        };
         self.keymap.keymap.extend(other.keymap.keymap);
     }
 }
@@ -1204,6 +1693,17 @@ pub struct ScrollingConfig {
     /// scrolling to be enabled but not active (i.e. content already fits within object)
     is_scrolling_y: bool,
 
+    /// Bool representing whether a mini-map of the content is shown next to the vertical
+    /// scrollbar. Only has effect if [scroll_y] is true.
+    pub minimap: EzProperty<bool>,
+
+    /// Width of the mini-map in cells
+    pub minimap_width: EzProperty<usize>,
+
+    /// Amount of lines (or columns when scrolling horizontally) scrolled per mouse wheel tick. 0
+    /// scrolls in chunks of the view size
+    pub scroll_step: EzProperty<usize>,
+
     /// Original height of the content being scrolled
     original_height: usize,
 
@@ -1227,11 +1727,20 @@ impl ScrollingConfig {
             scheduler.new_f64_property(format!("{}/scroll_start_x", name).as_str(), scroll_start_x);
         let scroll_start_y_property =
             scheduler.new_f64_property(format!("{}/scroll_start_y", name).as_str(), scroll_start_y);
+        let minimap_property =
+            scheduler.new_bool_property(format!("{}/minimap", name).as_str(), false);
+        let minimap_width_property =
+            scheduler.new_usize_property(format!("{}/minimap_width", name).as_str(), 4);
+        let scroll_step_property =
+            scheduler.new_usize_property(format!("{}/scroll_step", name).as_str(), 0);
         ScrollingConfig {
             scroll_x: x_property,
             scroll_y: y_property,
             scroll_start_x: scroll_start_x_property,
             scroll_start_y: scroll_start_y_property,
+            minimap: minimap_property,
+            minimap_width: minimap_width_property,
+            scroll_step: scroll_step_property,
             is_scrolling_x: false,
             is_scrolling_y: false,
             original_height: 0,
@@ -1271,6 +1780,39 @@ impl ScrollingConfig {
         self.scroll_start_y.value
     }
 
+    pub fn set_minimap(&mut self, minimap: bool) {
+        self.minimap.set(minimap);
+    }
+
+    pub fn get_minimap(&self) -> bool {
+        self.minimap.value
+    }
+
+    pub fn set_minimap_width(&mut self, width: usize) {
+        self.minimap_width.set(width);
+    }
+
+    pub fn get_minimap_width(&self) -> usize {
+        self.minimap_width.value
+    }
+
+    pub fn set_scroll_step(&mut self, step: usize) {
+        self.scroll_step.set(step);
+    }
+
+    pub fn get_scroll_step(&self) -> usize {
+        self.scroll_step.value
+    }
+
+    /// Get the width taken up by the mini-map, which is 0 if no mini-map is shown.
+    pub fn get_minimap_gutter_width(&self) -> usize {
+        if self.get_scroll_y() && self.get_minimap() {
+            self.get_minimap_width()
+        } else {
+            0
+        }
+    }
+
     pub fn set_original_height(&mut self, height: usize) {
         self.original_height = height;
     }
@@ -1329,9 +1871,48 @@ impl ScrollingConfig {
             .round() as usize
     }
 
+    /// Get how far a chunked scroll (e.g. page down) moves the view on the x axis, as a fraction
+    /// of the maximum view start.
+    pub fn get_scroll_chunk_x(&self, effective_widget_width: usize) -> f64 {
+        effective_widget_width as f64 / self.get_original_width() as f64
+    }
+
+    /// Get how far a chunked scroll (e.g. page down) moves the view on the y axis, as a fraction
+    /// of the maximum view start.
+    pub fn get_scroll_chunk_y(&self, effective_widget_height: usize) -> f64 {
+        (effective_widget_height as f64 / self.get_original_height() as f64) * 0.75
+    }
+
+    /// Get how far a mouse wheel tick moves the view on the x axis, as a fraction of the maximum
+    /// view start. This is [scroll_step] columns, or a chunk if no scroll step is set.
+    pub fn get_wheel_scroll_chunk_x(&self, effective_widget_width: usize) -> f64 {
+        let max_start = self.get_max_scroll_start_x(effective_widget_width);
+        if self.get_scroll_step() == 0 || max_start == 0 {
+            self.get_scroll_chunk_x(effective_widget_width)
+        } else {
+            self.get_scroll_step() as f64 / max_start as f64
+        }
+    }
+
+    /// Get how far a mouse wheel tick moves the view on the y axis, as a fraction of the maximum
+    /// view start. This is [scroll_step] lines, or a chunk if no scroll step is set.
+    pub fn get_wheel_scroll_chunk_y(&self, effective_widget_height: usize) -> f64 {
+        let max_start = self.get_max_scroll_start_y(effective_widget_height);
+        if self.get_scroll_step() == 0 || max_start == 0 {
+            self.get_scroll_chunk_y(effective_widget_height)
+        } else {
+            self.get_scroll_step() as f64 / max_start as f64
+        }
+    }
+
     pub fn clean_up_properties(&self, scheduler: &mut SchedulerFrontend) {
         clean_up_property(scheduler, &self.scroll_x.name);
         clean_up_property(scheduler, &self.scroll_y.name);
+        clean_up_property(scheduler, &self.scroll_start_x.name);
+        clean_up_property(scheduler, &self.scroll_start_y.name);
+        clean_up_property(scheduler, &self.minimap.name);
+        clean_up_property(scheduler, &self.minimap_width.name);
+        clean_up_property(scheduler, &self.scroll_step.name);
     }
 }
 
@@ -1520,6 +2101,18 @@ pub struct ColorConfig {
     /// The [Pixel.background_color] to use for filler pixels if [fill] is true
     pub filler_bg_color: EzProperty<Color>,
 
+    /// The second [Pixel.background_color] of the [filler_pattern] of a layout, e.g. the color
+    /// of the dark squares of a checkerboard or the end color of a gradient
+    pub filler_alt_bg_color: EzProperty<Color>,
+
+    /// The [Pixel.foreground_color] to use for filler pixels in the header region of a layout
+    /// (see [filler_header_height])
+    pub filler_header_fg_color: EzProperty<Color>,
+
+    /// The [Pixel.background_color] to use for filler pixels in the header region of a layout
+    /// (see [filler_header_height])
+    pub filler_header_bg_color: EzProperty<Color>,
+
     /// The [Pixel.foreground_color] to use for border pixels
     pub border_fg_color: EzProperty<Color>,
 
@@ -1529,6 +2122,42 @@ pub struct ColorConfig {
     /// The [Pixel.background_color] to use for this widgets' content when a position has been
     /// highlighted by the blinking cursor
     pub cursor_color: EzProperty<Color>,
+
+    /// Derive the selection and disabled colors from [fg_color] and [bg_color] instead of using
+    /// the selection and disabled color properties. See [colors] for how they are derived.
+    pub auto_colors: EzProperty<bool>,
+
+    /// Make the foreground colors lighter or darker where needed to be readable on the
+    /// background colors; see [readable_fg]
+    pub auto_contrast: EzProperty<bool>,
+
+    /// How this widget is shown while it is selected, see [SelectionStyle]. Stored by name, e.g.
+    /// "underline"
+    pub selection_style: EzProperty<String>,
+
+    /// Symbol drawn in the gutter of this widget while it is selected, if the selection style is
+    /// [SelectionStyle::Marker]
+    pub selection_marker: EzProperty<String>,
+
+    /// The [Pixel.foreground_color] to use while the mouse is over this widget. [Color::Reset]
+    /// means the color does not change on hover
+    pub hover_fg_color: EzProperty<Color>,
+
+    /// The [Pixel.background_color] to use while the mouse is over this widget. [Color::Reset]
+    /// means the color does not change on hover
+    pub hover_bg_color: EzProperty<Color>,
+
+    /// The [Pixel.foreground_color] to use while this widget is held down. [Color::Reset] means
+    /// the color does not change while pressed
+    pub pressed_fg_color: EzProperty<Color>,
+
+    /// The [Pixel.background_color] to use while this widget is held down. [Color::Reset] means
+    /// the color does not change while pressed
+    pub pressed_bg_color: EzProperty<Color>,
+
+    /// Draw the border of this widget in the selection foreground color while it is selected,
+    /// whatever its' [SelectionStyle]
+    pub focused_border: EzProperty<bool>,
 }
 impl ColorConfig {
     pub fn new(name: String, scheduler: &mut SchedulerFrontend) -> Self {
@@ -1567,6 +2196,16 @@ impl ColorConfig {
             .new_color_property(format!("{}/filler_fg_color", name).as_str(), Color::White);
         let filler_background = scheduler
             .new_color_property(format!("{}/filler_bg_color", name).as_str(), Color::Black);
+        let filler_alt_background = scheduler
+            .new_color_property(format!("{}/filler_alt_bg_color", name).as_str(), Color::DarkGrey);
+        let filler_header_foreground = scheduler.new_color_property(
+            format!("{}/filler_header_fg_color", name).as_str(),
+            Color::White,
+        );
+        let filler_header_background = scheduler.new_color_property(
+            format!("{}/filler_header_bg_color", name).as_str(),
+            Color::Black,
+        );
 
         let tab_foreground = scheduler.new_color_property(
             format!("{}/tab_header_fg_color", name).as_str(),
@@ -1594,6 +2233,28 @@ impl ColorConfig {
         let cursor = scheduler
             .new_color_property(format!("{}/cursor_color", name).as_str(), Color::DarkYellow);
 
+        let auto_colors =
+            scheduler.new_bool_property(format!("{}/auto_colors", name).as_str(), false);
+        let auto_contrast =
+            scheduler.new_bool_property(format!("{}/auto_contrast", name).as_str(), false);
+        let selection_style = scheduler.new_string_property(
+            format!("{}/selection_style", name).as_str(),
+            SelectionStyle::Colors.get_name().to_string(),
+        );
+        let selection_marker = scheduler
+            .new_string_property(format!("{}/selection_marker", name).as_str(), "▶".to_string());
+
+        let hover_foreground =
+            scheduler.new_color_property(format!("{}/hover_fg_color", name).as_str(), Color::Reset);
+        let hover_background =
+            scheduler.new_color_property(format!("{}/hover_bg_color", name).as_str(), Color::Reset);
+        let pressed_foreground = scheduler
+            .new_color_property(format!("{}/pressed_fg_color", name).as_str(), Color::Reset);
+        let pressed_background = scheduler
+            .new_color_property(format!("{}/pressed_bg_color", name).as_str(), Color::Reset);
+        let focused_border =
+            scheduler.new_bool_property(format!("{}/focused_border", name).as_str(), false);
+
         ColorConfig {
             fg_color: foreground,
             bg_color: background,
@@ -1611,18 +2272,100 @@ impl ColorConfig {
             tab_header_border_bg_color: tab_border_background,
             filler_fg_color: filler_foreground,
             filler_bg_color: filler_background,
+            filler_alt_bg_color: filler_alt_background,
+            filler_header_fg_color: filler_header_foreground,
+            filler_header_bg_color: filler_header_background,
             border_fg_color: border_foreground,
             border_bg_color: border_background,
             cursor_color: cursor,
+            auto_colors,
+            auto_contrast,
+            selection_style,
+            selection_marker,
+            hover_fg_color: hover_foreground,
+            hover_bg_color: hover_background,
+            pressed_fg_color: pressed_foreground,
+            pressed_bg_color: pressed_background,
+            focused_border,
         }
     }
 
+    /// Get a color property by its' name, e.g. "fg_color". Returns None if there is no color
+    /// property with that name.
+    pub fn get_property(&self, name: &str) -> Option<&EzProperty<Color>> {
+        let property = match name {
+            "fg_color" => &self.fg_color,
+            "bg_color" => &self.bg_color,
+            "selection_fg_color" => &self.selection_fg_color,
+            "selection_bg_color" => &self.selection_bg_color,
+            "disabled_fg_color" => &self.disabled_fg_color,
+            "disabled_bg_color" => &self.disabled_bg_color,
+            "tab_header_active_fg_color" => &self.tab_header_active_fg_color,
+            "tab_header_active_bg_color" => &self.tab_header_active_bg_color,
+            "flash_fg_color" => &self.flash_fg_color,
+            "flash_bg_color" => &self.flash_bg_color,
+            "tab_header_fg_color" => &self.tab_header_fg_color,
+            "tab_header_bg_color" => &self.tab_header_bg_color,
+            "tab_header_border_fg_color" => &self.tab_header_border_fg_color,
+            "tab_header_border_bg_color" => &self.tab_header_border_bg_color,
+            "filler_fg_color" => &self.filler_fg_color,
+            "filler_bg_color" => &self.filler_bg_color,
+            "filler_alt_bg_color" => &self.filler_alt_bg_color,
+            "filler_header_fg_color" => &self.filler_header_fg_color,
+            "filler_header_bg_color" => &self.filler_header_bg_color,
+            "border_fg_color" => &self.border_fg_color,
+            "border_bg_color" => &self.border_bg_color,
+            "cursor_color" => &self.cursor_color,
+            "hover_fg_color" => &self.hover_fg_color,
+            "hover_bg_color" => &self.hover_bg_color,
+            "pressed_fg_color" => &self.pressed_fg_color,
+            "pressed_bg_color" => &self.pressed_bg_color,
+            _ => return None,
+        };
+        Some(property)
+    }
+
+    /// Get a mutable color property by its' name, e.g. "fg_color". Returns None if there is no
+    /// color property with that name.
+    pub fn get_property_mut(&mut self, name: &str) -> Option<&mut EzProperty<Color>> {
+        let property = match name {
+            "fg_color" => &mut self.fg_color,
+            "bg_color" => &mut self.bg_color,
+            "selection_fg_color" => &mut self.selection_fg_color,
+            "selection_bg_color" => &mut self.selection_bg_color,
+            "disabled_fg_color" => &mut self.disabled_fg_color,
+            "disabled_bg_color" => &mut self.disabled_bg_color,
+            "tab_header_active_fg_color" => &mut self.tab_header_active_fg_color,
+            "tab_header_active_bg_color" => &mut self.tab_header_active_bg_color,
+            "flash_fg_color" => &mut self.flash_fg_color,
+            "flash_bg_color" => &mut self.flash_bg_color,
+            "tab_header_fg_color" => &mut self.tab_header_fg_color,
+            "tab_header_bg_color" => &mut self.tab_header_bg_color,
+            "tab_header_border_fg_color" => &mut self.tab_header_border_fg_color,
+            "tab_header_border_bg_color" => &mut self.tab_header_border_bg_color,
+            "filler_fg_color" => &mut self.filler_fg_color,
+            "filler_bg_color" => &mut self.filler_bg_color,
+            "filler_alt_bg_color" => &mut self.filler_alt_bg_color,
+            "filler_header_fg_color" => &mut self.filler_header_fg_color,
+            "filler_header_bg_color" => &mut self.filler_header_bg_color,
+            "border_fg_color" => &mut self.border_fg_color,
+            "border_bg_color" => &mut self.border_bg_color,
+            "cursor_color" => &mut self.cursor_color,
+            "hover_fg_color" => &mut self.hover_fg_color,
+            "hover_bg_color" => &mut self.hover_bg_color,
+            "pressed_fg_color" => &mut self.pressed_fg_color,
+            "pressed_bg_color" => &mut self.pressed_bg_color,
+            _ => return None,
+        };
+        Some(property)
+    }
+
     pub fn set_fg_color(&mut self, color: Color) {
         self.fg_color.set(color);
     }
 
     pub fn get_fg_color(&self) -> Color {
-        self.fg_color.value
+        self.with_contrast(self.fg_color.value, self.get_bg_color())
     }
 
     pub fn set_bg_color(&mut self, color: Color) {
@@ -1638,7 +2381,12 @@ impl ColorConfig {
     }
 
     pub fn get_selection_fg_color(&self) -> Color {
-        self.selection_fg_color.value
+        let color = if self.auto_colors.value {
+            self.fg_color.value
+        } else {
+            self.selection_fg_color.value
+        };
+        self.with_contrast(color, self.get_selection_bg_color())
     }
 
     pub fn set_selection_bg_color(&mut self, color: Color) {
@@ -1646,7 +2394,11 @@ impl ColorConfig {
     }
 
     pub fn get_selection_bg_color(&self) -> Color {
-        self.selection_bg_color.value
+        if self.auto_colors.value {
+            hover_color(self.bg_color.value)
+        } else {
+            self.selection_bg_color.value
+        }
     }
 
     pub fn set_disabled_fg_color(&mut self, color: Color) {
@@ -1654,7 +2406,12 @@ impl ColorConfig {
     }
 
     pub fn get_disabled_fg_color(&self) -> Color {
-        self.disabled_fg_color.value
+        // Disabled colors are dimmed on purpose, so contrast is not enforced
+        if self.auto_colors.value {
+            disabled_color(self.fg_color.value, self.bg_color.value)
+        } else {
+            self.disabled_fg_color.value
+        }
     }
 
     pub fn set_disabled_bg_color(&mut self, color: Color) {
@@ -1662,7 +2419,103 @@ impl ColorConfig {
     }
 
     pub fn get_disabled_bg_color(&self) -> Color {
-        self.disabled_bg_color.value
+        if self.auto_colors.value {
+            self.bg_color.value
+        } else {
+            self.disabled_bg_color.value
+        }
+    }
+
+    pub fn set_auto_colors(&mut self, auto_colors: bool) {
+        self.auto_colors.set(auto_colors);
+    }
+
+    pub fn get_auto_colors(&self) -> bool {
+        self.auto_colors.value
+    }
+
+    pub fn set_auto_contrast(&mut self, auto_contrast: bool) {
+        self.auto_contrast.set(auto_contrast);
+    }
+
+    pub fn get_auto_contrast(&self) -> bool {
+        self.auto_contrast.value
+    }
+
+    pub fn set_selection_style(&mut self, style: SelectionStyle) {
+        self.selection_style.set(style.get_name().to_string());
+    }
+
+    pub fn get_selection_style(&self) -> SelectionStyle {
+        parse_selection_style_property(&self.selection_style.value)
+            .unwrap_or(SelectionStyle::Colors)
+    }
+
+    pub fn set_selection_marker(&mut self, marker: &str) {
+        self.selection_marker.set(marker.to_string());
+    }
+
+    pub fn get_selection_marker(&self) -> String {
+        self.selection_marker.value.clone()
+    }
+
+    pub fn set_hover_fg_color(&mut self, color: Color) {
+        self.hover_fg_color.set(color);
+    }
+
+    pub fn get_hover_fg_color(&self) -> Color {
+        self.hover_fg_color.value
+    }
+
+    pub fn set_hover_bg_color(&mut self, color: Color) {
+        self.hover_bg_color.set(color);
+    }
+
+    pub fn get_hover_bg_color(&self) -> Color {
+        self.hover_bg_color.value
+    }
+
+    pub fn set_pressed_fg_color(&mut self, color: Color) {
+        self.pressed_fg_color.set(color);
+    }
+
+    pub fn get_pressed_fg_color(&self) -> Color {
+        self.pressed_fg_color.value
+    }
+
+    pub fn set_pressed_bg_color(&mut self, color: Color) {
+        self.pressed_bg_color.set(color);
+    }
+
+    pub fn get_pressed_bg_color(&self) -> Color {
+        self.pressed_bg_color.value
+    }
+
+    pub fn set_focused_border(&mut self, focused_border: bool) {
+        self.focused_border.set(focused_border);
+    }
+
+    pub fn get_focused_border(&self) -> bool {
+        self.focused_border.value
+    }
+
+    /// Get the foreground and background color overrides for an [InteractionState], i.e. the
+    /// hover or pressed colors. Colors that are not overridden are [Color::Reset].
+    pub fn get_interaction_colors(&self, state: InteractionState) -> (Color, Color) {
+        match state {
+            InteractionState::Hovered => (self.hover_fg_color.value, self.hover_bg_color.value),
+            InteractionState::Pressed => (self.pressed_fg_color.value, self.pressed_bg_color.value),
+            _ => (Color::Reset, Color::Reset),
+        }
+    }
+
+    /// Make a foreground color readable on a background color if [auto_contrast] is on.
+    fn with_contrast(&self, foreground: Color, background: Color) -> Color {
+        if self.auto_contrast.value {
+            readable_fg(foreground, background)
+        } else {
+            foreground
+        }
     }
 
     pub fn set_tab_header_active_fg_color(&mut self, color: Color) {
@@ -1745,6 +2598,30 @@ impl ColorConfig {
         self.filler_bg_color.value
     }
 
+    pub fn set_filler_alt_bg_color(&mut self, color: Color) {
+        self.filler_alt_bg_color.set(color);
+    }
+
+    pub fn get_filler_alt_bg_color(&self) -> Color {
+        self.filler_alt_bg_color.value
+    }
+
+    pub fn set_filler_header_fg_color(&mut self, color: Color) {
+        self.filler_header_fg_color.set(color);
+    }
+
+    pub fn get_filler_header_fg_color(&self) -> Color {
+        self.filler_header_fg_color.value
+    }
+
+    pub fn set_filler_header_bg_color(&mut self, color: Color) {
+        self.filler_header_bg_color.set(color);
+    }
+
+    pub fn get_filler_header_bg_color(&self) -> Color {
+        self.filler_header_bg_color.value
+    }
+
     pub fn set_border_fg_color(&mut self, color: Color) {
         self.border_fg_color.set(color);
     }
@@ -1784,9 +2661,191 @@ impl ColorConfig {
         clean_up_property(scheduler, &self.tab_header_bg_color.name);
         clean_up_property(scheduler, &self.filler_fg_color.name);
         clean_up_property(scheduler, &self.filler_bg_color.name);
+        clean_up_property(scheduler, &self.filler_alt_bg_color.name);
+        clean_up_property(scheduler, &self.filler_header_fg_color.name);
+        clean_up_property(scheduler, &self.filler_header_bg_color.name);
         clean_up_property(scheduler, &self.border_fg_color.name);
         clean_up_property(scheduler, &self.border_bg_color.name);
         clean_up_property(scheduler, &self.cursor_color.name);
+        clean_up_property(scheduler, &self.auto_colors.name);
+        clean_up_property(scheduler, &self.auto_contrast.name);
+        clean_up_property(scheduler, &self.selection_style.name);
+        clean_up_property(scheduler, &self.selection_marker.name);
+        clean_up_property(scheduler, &self.hover_fg_color.name);
+        clean_up_property(scheduler, &self.hover_bg_color.name);
+        clean_up_property(scheduler, &self.pressed_fg_color.name);
+        clean_up_property(scheduler, &self.pressed_bg_color.name);
+        clean_up_property(scheduler, &self.focused_border.name);
+    }
+
+    /// Get a color by the name of its' property (e.g. "border_fg_color").
+    pub fn get_color_by_name(&self, name: &str) -> Option<Color> {
+        match name {
+            "fg_color" => Some(self.fg_color.value),
+            "bg_color" => Some(self.bg_color.value),
+            "selection_fg_color" => Some(self.selection_fg_color.value),
+            "selection_bg_color" => Some(self.selection_bg_color.value),
+            "disabled_fg_color" => Some(self.disabled_fg_color.value),
+            "disabled_bg_color" => Some(self.disabled_bg_color.value),
+            "tab_header_active_fg_color" => Some(self.tab_header_active_fg_color.value),
+            "tab_header_active_bg_color" => Some(self.tab_header_active_bg_color.value),
+            "flash_fg_color" => Some(self.flash_fg_color.value),
+            "flash_bg_color" => Some(self.flash_bg_color.value),
+            "tab_header_fg_color" => Some(self.tab_header_fg_color.value),
+            "tab_header_bg_color" => Some(self.tab_header_bg_color.value),
+            "tab_header_border_fg_color" => Some(self.tab_header_border_fg_color.value),
+            "tab_header_border_bg_color" => Some(self.tab_header_border_bg_color.value),
+            "filler_fg_color" => Some(self.filler_fg_color.value),
+            "filler_bg_color" => Some(self.filler_bg_color.value),
+            "filler_alt_bg_color" => Some(self.filler_alt_bg_color.value),
+            "filler_header_fg_color" => Some(self.filler_header_fg_color.value),
+            "filler_header_bg_color" => Some(self.filler_header_bg_color.value),
+            "border_fg_color" => Some(self.border_fg_color.value),
+            "border_bg_color" => Some(self.border_bg_color.value),
+            "cursor_color" => Some(self.cursor_color.value),
+            "hover_fg_color" => Some(self.hover_fg_color.value),
+            "hover_bg_color" => Some(self.hover_bg_color.value),
+            "pressed_fg_color" => Some(self.pressed_fg_color.value),
+            "pressed_bg_color" => Some(self.pressed_bg_color.value),
+            _ => None,
+        }
+    }
+
+    /// Set a color by the name of its' property (e.g. "border_fg_color"). Returns false if no
+    /// color with that name exists.
+    pub fn set_color_by_name(&mut self, name: &str, color: Color) -> bool {
+        match name {
+            "fg_color" => self.fg_color.set(color),
+            "bg_color" => self.bg_color.set(color),
+            "selection_fg_color" => self.selection_fg_color.set(color),
+            "selection_bg_color" => self.selection_bg_color.set(color),
+            "disabled_fg_color" => self.disabled_fg_color.set(color),
+            "disabled_bg_color" => self.disabled_bg_color.set(color),
+            "tab_header_active_fg_color" => self.tab_header_active_fg_color.set(color),
+            "tab_header_active_bg_color" => self.tab_header_active_bg_color.set(color),
+            "flash_fg_color" => self.flash_fg_color.set(color),
+            "flash_bg_color" => self.flash_bg_color.set(color),
+            "tab_header_fg_color" => self.tab_header_fg_color.set(color),
+            "tab_header_bg_color" => self.tab_header_bg_color.set(color),
+            "tab_header_border_fg_color" => self.tab_header_border_fg_color.set(color),
+            "tab_header_border_bg_color" => self.tab_header_border_bg_color.set(color),
+            "filler_fg_color" => self.filler_fg_color.set(color),
+            "filler_bg_color" => self.filler_bg_color.set(color),
+            "filler_alt_bg_color" => self.filler_alt_bg_color.set(color),
+            "filler_header_fg_color" => self.filler_header_fg_color.set(color),
+            "filler_header_bg_color" => self.filler_header_bg_color.set(color),
+            "border_fg_color" => self.border_fg_color.set(color),
+            "border_bg_color" => self.border_bg_color.set(color),
+            "cursor_color" => self.cursor_color.set(color),
+            "hover_fg_color" => self.hover_fg_color.set(color),
+            "hover_bg_color" => self.hover_bg_color.set(color),
+            "pressed_fg_color" => self.pressed_fg_color.set(color),
+            "pressed_bg_color" => self.pressed_bg_color.set(color),
+            _ => return false,
+        };
+        true
+    }
+}
+
+/// A named set of colors that can be applied to all widgets at once. Colors are set by the name
+/// of the [ColorConfig] property they apply to (e.g. "fg_color" or "border_bg_color"); colors
+/// that are not set in the theme are left alone when the theme is applied. Register themes with
+/// [SchedulerFrontend.register_theme].
+#[derive(PartialEq, Clone, Debug, Default)]
+pub struct Theme {
+    /// <Color property name, Color> pairs
+    pub colors: Vec<(String, Color)>,
+}
+impl Theme {
+    pub fn new() -> Self {
+        Theme::default()
+    }
+
+    /// Set the color of a [ColorConfig] property (e.g. "fg_color") for this theme.
+    pub fn set_color(&mut self, name: &str, color: Color) {
+        self.colors.retain(|(x, _)| x != name);
+        self.colors.push((name.to_string(), color));
+    }
+
+    /// The default colors of all widgets, intended for terminals with a dark background.
+    pub fn dark() -> Self {
+        Theme::from_colors(vec![
+            ("fg_color", Color::White),
+            ("bg_color", Color::Black),
+            ("selection_fg_color", Color::Yellow),
+            ("selection_bg_color", Color::Blue),
+            ("disabled_fg_color", Color::White),
+            ("disabled_bg_color", Color::Black),
+            ("tab_header_active_fg_color", Color::White),
+            ("tab_header_active_bg_color", Color::Black),
+            ("flash_fg_color", Color::White),
+            ("flash_bg_color", Color::White),
+            ("tab_header_fg_color", Color::White),
+            ("tab_header_bg_color", Color::Black),
+            ("tab_header_border_fg_color", Color::White),
+            ("tab_header_border_bg_color", Color::Black),
+            ("filler_fg_color", Color::White),
+            ("filler_bg_color", Color::Black),
+            ("filler_alt_bg_color", Color::DarkGrey),
+            ("filler_header_fg_color", Color::White),
+            ("filler_header_bg_color", Color::Black),
+            ("border_fg_color", Color::White),
+            ("border_bg_color", Color::Black),
+            ("cursor_color", Color::DarkYellow),
+        ])
+    }
+
+    /// Colors intended for terminals with a light background.
+    pub fn light() -> Self {
+        Theme::from_colors(vec![
+            ("fg_color", Color::Black),
+            ("bg_color", Color::White),
+            ("selection_fg_color", Color::DarkBlue),
+            ("selection_bg_color", Color::Grey),
+            ("disabled_fg_color", Color::DarkGrey),
+            ("disabled_bg_color", Color::White),
+            ("tab_header_active_fg_color", Color::Black),
+            ("tab_header_active_bg_color", Color::White),
+            ("flash_fg_color", Color::Black),
+            ("flash_bg_color", Color::Black),
+            ("tab_header_fg_color", Color::Black),
+            ("tab_header_bg_color", Color::White),
+            ("tab_header_border_fg_color", Color::Black),
+            ("tab_header_border_bg_color", Color::White),
+            ("filler_fg_color", Color::Black),
+            ("filler_bg_color", Color::White),
+            ("filler_alt_bg_color", Color::Grey),
+            ("filler_header_fg_color", Color::Black),
+            ("filler_header_bg_color", Color::White),
+            ("border_fg_color", Color::Black),
+            ("border_bg_color", Color::White),
+            ("cursor_color", Color::DarkYellow),
+        ])
+    }
+
+    fn from_colors(colors: Vec<(&str, Color)>) -> Self {
+        Theme {
+            colors: colors.into_iter().map(|(x, y)| (x.to_string(), y)).collect(),
+        }
+    }
+
+    /// Apply the colors of this theme to a [ColorConfig].
+    pub fn apply(&self, color_config: &mut ColorConfig) {
+        for (name, color) in self.colors.iter() {
+            color_config.set_color_by_name(name, *color);
+        }
+    }
+
+    /// Apply the colors of this theme to a [ColorConfig], but only to the colors that still have
+    /// the value they have in the 'from' theme. Used to adapt the default colors of widgets to
+    /// another theme, without overwriting colors the user configured.
+    pub fn apply_over(&self, from: &Theme, color_config: &mut ColorConfig) {
+        for (name, color) in self.colors.iter() {
+            let old = from.colors.iter().find(|(x, _)| x == name).map(|(_, x)| *x);
+            if old.is_some() && color_config.get_color_by_name(name) == old {
+                color_config.set_color_by_name(name, *color);
+            }
+        }
     }
 }
 
@@ -1863,3 +2922,411 @@ impl Padding {
         clean_up_property(scheduler, &self.padding_right.name);
     }
 }
+
+/// A line of text with its' own colors and attributes, shown by a label through
+/// [LabelState.set_lines]. Colors that are None fall back to the colors of the label. Combine with
+/// [StyledLine::new] to only set what you need:
+///
+/// # Example:
+///
+/// ```
+/// use ez_term::*;
+///
+/// let line = StyledLine {
+///     fg_color: Some(Color::Red),
+///     bold: true,
+///     ..StyledLine::new("3 tests failed")
+/// };
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StyledLine {
+    /// Text of the line
+    pub text: String,
+
+    /// Foreground color of the line, or None to use the foreground color of the label
+    pub fg_color: Option<Color>,
+
+    /// Background color of the line, or None to use the background color of the label
+    pub bg_color: Option<Color>,
+
+    pub bold: bool,
+
+    pub italic: bool,
+
+    pub underline: bool,
+
+    pub strike_through: bool,
+}
+impl StyledLine {
+    /// Create a line without attributes in the colors of the label.
+    pub fn new(text: &str) -> Self {
+        StyledLine {
+            text: text.to_string(),
+            ..Default::default()
+        }
+    }
+}
+
+/// A piece of text with its' own colors and attributes, shown by a label through
+/// [LabelState.set_spans]. Unlike [StyledLine]s, spans follow each other on the same line, so a
+/// single line can mix styles. Colors that are None fall back to the colors of the label. Spans
+/// can also be parsed from markup with [StyledSpan::parse_markup].
+///
+/// # Example:
+///
+/// ```
+/// use ez_term::*;
+///
+/// let spans = vec![
+///     StyledSpan {
+///         fg_color: Some(Color::Red),
+///         bold: true,
+///         ..StyledSpan::new("error")
+///     },
+///     StyledSpan::new(": file not found"),
+/// ];
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StyledSpan {
+    /// Text of the span
+    pub text: String,
+
+    /// Foreground color of the span, or None to use the foreground color of the label
+    pub fg_color: Option<Color>,
+
+    /// Background color of the span, or None to use the background color of the label
+    pub bg_color: Option<Color>,
+
+    pub bold: bool,
+
+    pub italic: bool,
+
+    pub underline: bool,
+
+    pub strike_through: bool,
+}
+impl StyledSpan {
+    /// Create a span without attributes in the colors of the label.
+    pub fn new(text: &str) -> Self {
+        StyledSpan {
+            text: text.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Parse text containing markup tags into spans. This is how the text of labels and buttons
+    /// is styled. The tags are:
+    /// - \[b\], \[i\], \[u\] and \[s\] for bold, italic, underlined and struck through text
+    /// - \[fg=color\] (or \[color=color\]) and \[bg=color\] (or \[bg_color=color\]) for
+    ///   the foreground and background color, using the same colors as .ez files (e.g. "red" or
+    ///   "255,0,0")
+    ///
+    /// Close a tag with a slash, e.g. \[/b\] or \[/fg\]; closing a color restores the color
+    /// that was set before it. \[/\] closes all tags. Brackets that do not form a known tag are
+    /// shown as is, and a backslash shows the bracket or backslash after it as is, e.g. `\[b]`
+    /// shows `[b]`. Use [escape_markup] to show any text as is.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use ez_term::*;
+    ///
+    /// let spans = StyledSpan::parse_markup("[b][fg=red]error[/fg][/b]: file not found");
+    /// assert_eq!(spans[0].text, "error");
+    /// assert_eq!(spans[0].fg_color, Some(Color::Red));
+    /// assert!(spans[0].bold);
+    /// assert_eq!(spans[1], StyledSpan::new(": file not found"));
+    /// ```
+    pub fn parse_markup(text: &str) -> Vec<StyledSpan> {
+        let mut spans = Vec::new();
+        let mut style = StyledSpan::default();
+        let mut fg_colors = Vec::new();
+        let mut bg_colors = Vec::new();
+        let mut chars = text.chars().peekable();
+        while let Some(char) = chars.next() {
+            if char == '\\' && matches!(chars.peek(), Some('[') | Some(']') | Some('\\')) {
+                style.text.push(chars.next().unwrap());
+                continue;
+            }
+            if char == '[' {
+                let mut tag_chars = chars.clone();
+                let tag: String = tag_chars
+                    .by_ref()
+                    .take_while(|x| *x != ']')
+                    .collect();
+                let (mut new_style, mut new_fg_colors, mut new_bg_colors) =
+                    (style.clone(), fg_colors.clone(), bg_colors.clone());
+                new_style.text.clear();
+                if !tag.contains('[')
+                    && apply_markup_tag(
+                        &tag,
+                        &mut new_style,
+                        &mut new_fg_colors,
+                        &mut new_bg_colors,
+                    )
+                {
+                    if !style.text.is_empty() {
+                        spans.push(style);
+                    }
+                    style = new_style;
+                    fg_colors = new_fg_colors;
+                    bg_colors = new_bg_colors;
+                    chars = tag_chars;
+                    continue;
+                }
+            }
+            style.text.push(char);
+        }
+        if !style.text.is_empty() {
+            spans.push(style);
+        }
+        spans
+    }
+
+    /// Escape text so [parse_markup] shows it as is, e.g. to show text typed by a user in a label
+    /// that uses markup.
+    pub fn escape_markup(text: &str) -> String {
+        text.replace('\\', "\\\\")
+            .replace('[', "\\[")
+            .replace(']', "\\]")
+    }
+}
+
+/// Apply a markup tag (without brackets) to a style, see [StyledSpan::parse_markup]. The color
+/// stacks hold the colors of the open color tags. Returns false if the tag is unknown.
+fn apply_markup_tag(
+    tag: &str,
+    style: &mut StyledSpan,
+    fg_colors: &mut Vec<Color>,
+    bg_colors: &mut Vec<Color>,
+) -> bool {
+    let tag = tag.trim().to_lowercase();
+    if let Some((name, value)) = tag.split_once('=') {
+        let color = match parse_color_property(value.trim()) {
+            Ok(i) => i,
+            Err(_) => return false,
+        };
+        match name.trim() {
+            "fg" | "color" => {
+                fg_colors.push(color);
+                style.fg_color = Some(color);
+            }
+            "bg" | "bg_color" => {
+                bg_colors.push(color);
+                style.bg_color = Some(color);
+            }
+            _ => return false,
+        }
+        return true;
+    }
+    let (name, open) = match tag.strip_prefix('/') {
+        Some(i) => (i, false),
+        None => (tag.as_str(), true),
+    };
+    match name {
+        "b" => style.bold = open,
+        "i" => style.italic = open,
+        "u" => style.underline = open,
+        "s" => style.strike_through = open,
+        "fg" | "color" if !open => {
+            fg_colors.pop();
+            style.fg_color = fg_colors.last().copied();
+        }
+        "bg" | "bg_color" if !open => {
+            bg_colors.pop();
+            style.bg_color = bg_colors.last().copied();
+        }
+        "" if !open => {
+            *style = StyledSpan::default();
+            fg_colors.clear();
+            bg_colors.clear();
+        }
+        _ => return false,
+    }
+    true
+}
+
+/// How a selected widget is shown, see [ColorConfig.set_selection_style]. Set it for all widgets
+/// at once with [SchedulerFrontend.set_selection_style].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SelectionStyle {
+    /// Draw the widget in the selection colors. This is the default
+    Colors,
+
+    /// Swap the foreground and background colors of the widget
+    Invert,
+
+    /// Underline the content of the widget
+    Underline,
+
+    /// Draw the selection marker (see [ColorConfig.set_selection_marker]) in the selection
+    /// foreground color left of the content. Give the widget a left padding to leave room for the
+    /// marker; otherwise it is drawn over the first column of the widget
+    Marker,
+
+    /// Draw the border of the widget in the selection foreground color. Widgets without a border
+    /// are drawn in the selection colors instead
+    Border,
+}
+impl SelectionStyle {
+    /// Get the name of the style as written in an .ez file.
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            SelectionStyle::Colors => "colors",
+            SelectionStyle::Invert => "invert",
+            SelectionStyle::Underline => "underline",
+            SelectionStyle::Marker => "marker",
+            SelectionStyle::Border => "border",
+        }
+    }
+}
+
+/// Interaction state of a widget, which decides which style overrides of its' [ColorConfig] are
+/// used to draw it. A widget is in one state at a time; when several apply, the first one listed
+/// here wins. Get it with [GenericState.get_interaction_state].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InteractionState {
+    /// Drawn in the disabled colors
+    Disabled,
+
+    /// Held down, e.g. by the left mouse button or a button flashing after a press. Drawn in the
+    /// pressed colors
+    Pressed,
+
+    /// The mouse is over the widget. Drawn in the hover colors
+    Hovered,
+
+    /// Selected. Drawn according to the [SelectionStyle], and with its' border highlighted if
+    /// [ColorConfig.focused_border] is on
+    Focused,
+
+    /// None of the above
+    Normal,
+}
+
+/// Shape of the cursor of a text input, see [TextInputState.set_cursor_style].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CursorStyle {
+    /// Highlight the cell under the cursor with the cursor color
+    Block,
+
+    /// Underline the cell under the cursor in the cursor color
+    Underline,
+
+    /// Draw a thin bar in the cursor color. As a cell cannot hold a bar and a character at the
+    /// same time, the cell is underlined instead when it holds a character
+    Bar,
+}
+impl CursorStyle {
+    /// Get the name of the style as written in an .ez file.
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            CursorStyle::Block => "block",
+            CursorStyle::Underline => "underline",
+            CursorStyle::Bar => "bar",
+        }
+    }
+}
+
+/// Pattern of the filler of a layout, see [LayoutState.set_filler_pattern]. Patterns use the
+/// filler background color and the alternative filler background color.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FillerPattern {
+    /// Fill with the filler background color. This is the default
+    Solid,
+
+    /// Alternate the two colors per position
+    Checkerboard,
+
+    /// Alternate the two colors per row
+    HorizontalStripes,
+
+    /// Alternate the two colors per column
+    VerticalStripes,
+
+    /// Blend from the filler background color on the left to the alternative color on the right
+    HorizontalGradient,
+
+    /// Blend from the filler background color at the top to the alternative color at the bottom
+    VerticalGradient,
+}
+impl FillerPattern {
+    /// Get the name of the pattern as written in an .ez file.
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            FillerPattern::Solid => "solid",
+            FillerPattern::Checkerboard => "checkerboard",
+            FillerPattern::HorizontalStripes => "horizontal_stripes",
+            FillerPattern::VerticalStripes => "vertical_stripes",
+            FillerPattern::HorizontalGradient => "horizontal_gradient",
+            FillerPattern::VerticalGradient => "vertical_gradient",
+        }
+    }
+}
+
+/// Edge or corner of the terminal a child of a [LayoutMode::Float] layout is kept at, regardless
+/// of where the layout itself is and across terminal resizes. Useful for e.g. clocks, FPS counters
+/// and notification areas. The distance to the edge is set with the anchor offsets of the widget,
+/// see [AnchorConfig]. The widget stays within its' parent layout.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Anchor {
+    /// Not anchored; the widget is placed by its' position and pos hint. This is the default
+    None,
+
+    TopLeft,
+
+    /// Top edge, centered horizontally
+    Top,
+
+    TopRight,
+
+    /// Left edge, centered vertically
+    Left,
+
+    /// Center of the terminal
+    Center,
+
+    /// Right edge, centered vertically
+    Right,
+
+    BottomLeft,
+
+    /// Bottom edge, centered horizontally
+    Bottom,
+
+    BottomRight,
+}
+impl Anchor {
+    /// Get the name of the anchor as written in an .ez file.
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            Anchor::None => "none",
+            Anchor::TopLeft => "top_left",
+            Anchor::Top => "top",
+            Anchor::TopRight => "top_right",
+            Anchor::Left => "left",
+            Anchor::Center => "center",
+            Anchor::Right => "right",
+            Anchor::BottomLeft => "bottom_left",
+            Anchor::Bottom => "bottom",
+            Anchor::BottomRight => "bottom_right",
+        }
+    }
+
+    /// Get the horizontal and vertical alignment of the anchored widget to the terminal, or None
+    /// if not anchored.
+    pub fn get_alignment(&self) -> Option<(HorizontalAlignment, VerticalAlignment)> {
+        match self {
+            Anchor::None => None,
+            Anchor::TopLeft => Some((HorizontalAlignment::Left, VerticalAlignment::Top)),
+            Anchor::Top => Some((HorizontalAlignment::Center, VerticalAlignment::Top)),
+            Anchor::TopRight => Some((HorizontalAlignment::Right, VerticalAlignment::Top)),
+            Anchor::Left => Some((HorizontalAlignment::Left, VerticalAlignment::Middle)),
+            Anchor::Center => Some((HorizontalAlignment::Center, VerticalAlignment::Middle)),
+            Anchor::Right => Some((HorizontalAlignment::Right, VerticalAlignment::Middle)),
+            Anchor::BottomLeft => Some((HorizontalAlignment::Left, VerticalAlignment::Bottom)),
+            Anchor::Bottom => Some((HorizontalAlignment::Center, VerticalAlignment::Bottom)),
+            Anchor::BottomRight => Some((HorizontalAlignment::Right, VerticalAlignment::Bottom)),
+        }
+    }
+}
@@ -1,24 +1,36 @@
 //! # Widget state:
 //! A module containing the base structs and traits for widget states.
+use std::time::Duration;
+
 use crate::property::ez_values::EzValues;
 use crossterm::style::Color;
 
 use crate::run::definitions::{Coordinates, IsizeCoordinates, Size};
+use crate::scheduler::definitions::{GenericRecurringTask, GenericTask};
 use crate::scheduler::scheduler::SchedulerFrontend;
 use crate::states::button_state::ButtonState;
 use crate::states::canvas_state::CanvasState;
 use crate::states::checkbox_state::CheckboxState;
 use crate::states::definitions::{
-    AutoScale, BorderConfig, ColorConfig, HorizontalAlignment, HorizontalPosHint, InfiniteSize,
-    Padding, PosHint, SizeHint, StateCoordinates, StateSize, VerticalAlignment, VerticalPosHint,
+    AnchorConfig, AutoScale, BorderConfig, ColorConfig, DockPosition, HorizontalAlignment,
+    HorizontalPosHint, InfiniteSize, InteractionState, Padding, PosHint, SelectionStyle, SizeHint, StateCoordinates,
+    StateSize, VerticalAlignment, VerticalPosHint,
 };
 use crate::states::dropdown_state::{DropdownState, DroppedDownMenuState};
 use crate::states::label_state::LabelState;
 use crate::states::layout_state::LayoutState;
 use crate::states::progress_bar_state::ProgressBarState;
 use crate::states::radio_button_state::RadioButtonState;
+use crate::states::rating_state::RatingState;
 use crate::states::slider_state::SliderState;
+use crate::states::stepper_state::StepperState;
+use crate::states::data_grid_state::DataGridState;
+use crate::states::list_view_state::ListViewState;
+use crate::states::text_box_state::TextBoxState;
 use crate::states::text_input_state::TextInputState;
+use crate::states::ticker_state::TickerState;
+use crate::states::qr_code_state::QrCodeState;
+use crate::states::heatmap_state::HeatmapState;
 
 /// Widget states are used to keep track of dynamic run time information of widgets, such as the
 /// text of a label, or whether a checkbox is currently checked. All callbacks receive a mutable
@@ -37,8 +49,16 @@ pub enum EzState {
     DroppedDownMenu(DroppedDownMenuState),
     RadioButton(RadioButtonState),
     TextInput(TextInputState),
+    TextBox(TextBoxState),
+    DataGrid(DataGridState),
+    ListView(ListViewState),
     Slider(SliderState),
     ProgressBar(ProgressBarState),
+    Stepper(StepperState),
+    Rating(RatingState),
+    Ticker(TickerState),
+    QrCode(QrCodeState),
+    Heatmap(HeatmapState),
 }
 impl EzState {
     /// Get an EzState based on a string containing the base widget type.
@@ -51,9 +71,17 @@ impl EzState {
             "CheckBox" => EzState::Checkbox(CheckboxState::new(path, scheduler)),
             "RadioButton" => EzState::RadioButton(RadioButtonState::new(path, scheduler)),
             "TextInput" => EzState::TextInput(TextInputState::new(path, scheduler)),
+            "TextBox" => EzState::TextBox(TextBoxState::new(path, scheduler)),
+            "DataGrid" => EzState::DataGrid(DataGridState::new(path, scheduler)),
+            "ListView" => EzState::ListView(ListViewState::new(path, scheduler)),
             "Dropdown" => EzState::Dropdown(DropdownState::new(path, scheduler)),
             "Slider" => EzState::Slider(SliderState::new(path, scheduler)),
             "ProgressBar" => EzState::ProgressBar(ProgressBarState::new(path, scheduler)),
+            "Rating" => EzState::Rating(RatingState::new(path, scheduler)),
+            "Stepper" => EzState::Stepper(StepperState::new(path, scheduler)),
+            "Ticker" => EzState::Ticker(TickerState::new(path, scheduler)),
+            "QrCode" => EzState::QrCode(QrCodeState::new(path, scheduler)),
+            "Heatmap" => EzState::Heatmap(HeatmapState::new(path, scheduler)),
             _ => panic!(
                 "Cannot create state from string \"{}\". This widget type does not exist.",
                 type_name
@@ -61,6 +89,38 @@ impl EzState {
         }
     }
 
+    /// Create a new state of the same type for a different path, with all values of this state
+    /// copied into it. Used when cloning widgets.
+    pub fn clone_to_path(&self, path: String, scheduler: &mut SchedulerFrontend) -> Self {
+        let mut new_state = match self {
+            EzState::Layout(_) => EzState::Layout(LayoutState::new(path, scheduler)),
+            EzState::Canvas(_) => EzState::Canvas(CanvasState::new(path, scheduler)),
+            EzState::Label(_) => EzState::Label(LabelState::new(path, scheduler)),
+            EzState::Button(_) => EzState::Button(ButtonState::new(path, scheduler)),
+            EzState::Checkbox(_) => EzState::Checkbox(CheckboxState::new(path, scheduler)),
+            EzState::RadioButton(_) => {
+                EzState::RadioButton(RadioButtonState::new(path, scheduler))
+            }
+            EzState::TextInput(_) => EzState::TextInput(TextInputState::new(path, scheduler)),
+            EzState::TextBox(_) => EzState::TextBox(TextBoxState::new(path, scheduler)),
+            EzState::DataGrid(_) => EzState::DataGrid(DataGridState::new(path, scheduler)),
+            EzState::ListView(_) => EzState::ListView(ListViewState::new(path, scheduler)),
+            EzState::Dropdown(_) => EzState::Dropdown(DropdownState::new(path, scheduler)),
+            EzState::Slider(_) => EzState::Slider(SliderState::new(path, scheduler)),
+            EzState::ProgressBar(_) => {
+                EzState::ProgressBar(ProgressBarState::new(path, scheduler))
+            }
+            EzState::Rating(_) => EzState::Rating(RatingState::new(path, scheduler)),
+            EzState::Stepper(_) => EzState::Stepper(StepperState::new(path, scheduler)),
+            EzState::Ticker(_) => EzState::Ticker(TickerState::new(path, scheduler)),
+            EzState::QrCode(_) => EzState::QrCode(QrCodeState::new(path, scheduler)),
+            EzState::Heatmap(_) => EzState::Heatmap(HeatmapState::new(path, scheduler)),
+            EzState::DroppedDownMenu(_) => panic!("Cannot clone a dropped down menu: {}", path),
+        };
+        new_state.as_generic_mut().copy_state_values(self.clone());
+        new_state
+    }
+
     /// Cast this enum to a generic widget state trait object, which contains methods for setting
     /// and getting fields common to all widget states. Can always be called safely.
     pub fn as_generic(&self) -> &dyn GenericState {
@@ -73,9 +133,17 @@ impl EzState {
             EzState::DroppedDownMenu(i) => i,
             EzState::RadioButton(i) => i,
             EzState::TextInput(i) => i,
+            EzState::TextBox(i) => i,
+            EzState::DataGrid(i) => i,
+            EzState::ListView(i) => i,
             EzState::Canvas(i) => i,
             EzState::Slider(i) => i,
             EzState::ProgressBar(i) => i,
+            EzState::Rating(i) => i,
+            EzState::Stepper(i) => i,
+            EzState::Ticker(i) => i,
+            EzState::QrCode(i) => i,
+            EzState::Heatmap(i) => i,
         }
     }
 
@@ -91,12 +159,70 @@ impl EzState {
             EzState::DroppedDownMenu(i) => i,
             EzState::RadioButton(i) => i,
             EzState::TextInput(i) => i,
+            EzState::TextBox(i) => i,
+            EzState::DataGrid(i) => i,
+            EzState::ListView(i) => i,
             EzState::Canvas(i) => i,
             EzState::Slider(i) => i,
             EzState::ProgressBar(i) => i,
+            EzState::Rating(i) => i,
+            EzState::Stepper(i) => i,
+            EzState::Ticker(i) => i,
+            EzState::QrCode(i) => i,
+            EzState::Heatmap(i) => i,
+        }
+    }
+
+    /// Get the name of the widget type this state belongs to, e.g. "Label".
+    pub fn get_widget_type(&self) -> &'static str {
+        match self {
+            EzState::Layout(_) => LayoutState::WIDGET_TYPE,
+            EzState::Label(_) => LabelState::WIDGET_TYPE,
+            EzState::Button(_) => ButtonState::WIDGET_TYPE,
+            EzState::Checkbox(_) => CheckboxState::WIDGET_TYPE,
+            EzState::Dropdown(_) => DropdownState::WIDGET_TYPE,
+            EzState::DroppedDownMenu(_) => DroppedDownMenuState::WIDGET_TYPE,
+            EzState::RadioButton(_) => RadioButtonState::WIDGET_TYPE,
+            EzState::TextInput(_) => TextInputState::WIDGET_TYPE,
+            EzState::TextBox(_) => TextBoxState::WIDGET_TYPE,
+            EzState::DataGrid(_) => DataGridState::WIDGET_TYPE,
+            EzState::ListView(_) => ListViewState::WIDGET_TYPE,
+            EzState::Canvas(_) => CanvasState::WIDGET_TYPE,
+            EzState::Slider(_) => SliderState::WIDGET_TYPE,
+            EzState::ProgressBar(_) => ProgressBarState::WIDGET_TYPE,
+            EzState::Rating(_) => RatingState::WIDGET_TYPE,
+            EzState::Stepper(_) => StepperState::WIDGET_TYPE,
+            EzState::Ticker(_) => TickerState::WIDGET_TYPE,
+            EzState::QrCode(_) => QrCodeState::WIDGET_TYPE,
+            EzState::Heatmap(_) => HeatmapState::WIDGET_TYPE,
+        }
+    }
+
+    /// Get the name of the property holding the value the user enters in this widget, e.g. "text"
+    /// for a TextInput. Returns None for widgets the user cannot enter a value in.
+    pub fn get_value_property(&self) -> Option<&'static str> {
+        match self {
+            EzState::TextInput(_) | EzState::TextBox(_) => Some("text"),
+            EzState::Checkbox(_) | EzState::RadioButton(_) => Some("active"),
+            EzState::Button(i) if i.get_toggle() => Some("active"),
+            EzState::Slider(_) | EzState::Stepper(_) | EzState::Rating(_) => Some("value"),
+            EzState::Dropdown(_) => Some("choice"),
+            _ => None,
         }
     }
 
+    /// Cast this state to a specific widget state type. Returns None if this state is of a
+    /// different type. See [Context.get_state_as] for an example.
+    pub fn try_as<T: StateType>(&self) -> Option<&T> {
+        T::from_state(self)
+    }
+
+    /// Cast this state to a specific mutable widget state type. Returns None if this state is of
+    /// a different type.
+    pub fn try_as_mut<T: StateType>(&mut self) -> Option<&mut T> {
+        T::from_state_mut(self)
+    }
+
     /// Cast this state as a layout state ref, you must be sure you have one.
     pub fn as_layout(&self) -> &LayoutState {
         if let EzState::Layout(i) = self {
@@ -205,6 +331,42 @@ impl EzState {
         }
     }
 
+    /// Cast this state as a Rating widget state ref, you must be sure you have one.
+    pub fn as_rating(&self) -> &RatingState {
+        if let EzState::Rating(i) = self {
+            i
+        } else {
+            panic!("wrong state.")
+        }
+    }
+
+    /// Cast this state as a mutable Rating widget state ref, you must be sure you have one.
+    pub fn as_rating_mut(&mut self) -> &mut RatingState {
+        if let EzState::Rating(i) = self {
+            i
+        } else {
+            panic!("wrong state.")
+        }
+    }
+
+    /// Cast this state as a Stepper widget state ref, you must be sure you have one.
+    pub fn as_stepper(&self) -> &StepperState {
+        if let EzState::Stepper(i) = self {
+            i
+        } else {
+            panic!("wrong state.")
+        }
+    }
+
+    /// Cast this state as a mutable Stepper widget state ref, you must be sure you have one.
+    pub fn as_stepper_mut(&mut self) -> &mut StepperState {
+        if let EzState::Stepper(i) = self {
+            i
+        } else {
+            panic!("wrong state.")
+        }
+    }
+
     /// Cast this state as a Checkbox widget state ref, you must be sure you have one.
     pub fn as_checkbox(&self) -> &CheckboxState {
         if let EzState::Checkbox(i) = self {
@@ -294,7 +456,170 @@ impl EzState {
             panic!("wrong state.")
         }
     }
+
+    /// Cast this state as a TextBox widget state ref, you must be sure you have one.
+    pub fn as_text_box(&self) -> &TextBoxState {
+        if let EzState::TextBox(i) = self {
+            i
+        } else {
+            panic!("wrong state.")
+        }
+    }
+
+    /// Cast this state as a mutable TextBox widget state ref, you must be sure you have one.
+    pub fn as_text_box_mut(&mut self) -> &mut TextBoxState {
+        if let EzState::TextBox(i) = self {
+            i
+        } else {
+            panic!("wrong state.")
+        }
+    }
+
+    /// Cast this state as a DataGrid widget state ref, you must be sure you have one.
+    pub fn as_data_grid(&self) -> &DataGridState {
+        if let EzState::DataGrid(i) = self {
+            i
+        } else {
+            panic!("wrong state.")
+        }
+    }
+
+    /// Cast this state as a mutable DataGrid widget state ref, you must be sure you have one.
+    pub fn as_data_grid_mut(&mut self) -> &mut DataGridState {
+        if let EzState::DataGrid(i) = self {
+            i
+        } else {
+            panic!("wrong state.")
+        }
+    }
+
+    /// Cast this state as a ListView widget state ref, you must be sure you have one.
+    pub fn as_list_view(&self) -> &ListViewState {
+        if let EzState::ListView(i) = self {
+            i
+        } else {
+            panic!("wrong state.")
+        }
+    }
+
+    /// Cast this state as a mutable ListView widget state ref, you must be sure you have one.
+    pub fn as_list_view_mut(&mut self) -> &mut ListViewState {
+        if let EzState::ListView(i) = self {
+            i
+        } else {
+            panic!("wrong state.")
+        }
+    }
+
+    /// Cast this state as a Ticker widget state ref, you must be sure you have one.
+    pub fn as_ticker(&self) -> &TickerState {
+        if let EzState::Ticker(i) = self {
+            i
+        } else {
+            panic!("wrong state.")
+        }
+    }
+
+    /// Cast this state as a mutable Ticker widget state ref, you must be sure you have one.
+    pub fn as_ticker_mut(&mut self) -> &mut TickerState {
+        if let EzState::Ticker(i) = self {
+            i
+        } else {
+            panic!("wrong state.")
+        }
+    }
+
+    /// Cast this state as a QrCode widget state ref, you must be sure you have one.
+    pub fn as_qr_code(&self) -> &QrCodeState {
+        if let EzState::QrCode(i) = self {
+            i
+        } else {
+            panic!("wrong state.")
+        }
+    }
+
+    /// Cast this state as a mutable QrCode widget state ref, you must be sure you have one.
+    pub fn as_qr_code_mut(&mut self) -> &mut QrCodeState {
+        if let EzState::QrCode(i) = self {
+            i
+        } else {
+            panic!("wrong state.")
+        }
+    }
+
+    /// Cast this state as a Heatmap widget state ref, you must be sure you have one.
+    pub fn as_heatmap(&self) -> &HeatmapState {
+        if let EzState::Heatmap(i) = self {
+            i
+        } else {
+            panic!("wrong state.")
+        }
+    }
+
+    /// Cast this state as a mutable Heatmap widget state ref, you must be sure you have one.
+    pub fn as_heatmap_mut(&mut self) -> &mut HeatmapState {
+        if let EzState::Heatmap(i) = self {
+            i
+        } else {
+            panic!("wrong state.")
+        }
+    }
+}
+
+/// Implemented by every specific widget state struct, so an [EzState] can be cast to it with a
+/// type parameter instead of a type specific method (e.g. "try_as::<LabelState>()" instead of
+/// "as_label()").
+pub trait StateType: Sized {
+    /// Name of the widget type, as used in .ez files
+    const WIDGET_TYPE: &'static str;
+
+    fn from_state(state: &EzState) -> Option<&Self>;
+
+    fn from_state_mut(state: &mut EzState) -> Option<&mut Self>;
+}
+
+macro_rules! impl_state_type {
+    ($state:ident, $variant:ident, $name:expr) => {
+        impl StateType for $state {
+            const WIDGET_TYPE: &'static str = $name;
+
+            fn from_state(state: &EzState) -> Option<&Self> {
+                if let EzState::$variant(i) = state {
+                    Some(i)
+                } else {
+                    None
+                }
+            }
+
+            fn from_state_mut(state: &mut EzState) -> Option<&mut Self> {
+                if let EzState::$variant(i) = state {
+                    Some(i)
+                } else {
+                    None
+                }
+            }
+        }
+    };
 }
+impl_state_type!(LayoutState, Layout, "Layout");
+impl_state_type!(LabelState, Label, "Label");
+impl_state_type!(ButtonState, Button, "Button");
+impl_state_type!(CanvasState, Canvas, "Canvas");
+impl_state_type!(CheckboxState, Checkbox, "CheckBox");
+impl_state_type!(DropdownState, Dropdown, "Dropdown");
+impl_state_type!(DroppedDownMenuState, DroppedDownMenu, "DroppedDownMenu");
+impl_state_type!(RadioButtonState, RadioButton, "RadioButton");
+impl_state_type!(TextInputState, TextInput, "TextInput");
+impl_state_type!(TextBoxState, TextBox, "TextBox");
+impl_state_type!(DataGridState, DataGrid, "DataGrid");
+impl_state_type!(ListViewState, ListView, "ListView");
+impl_state_type!(SliderState, Slider, "Slider");
+impl_state_type!(ProgressBarState, ProgressBar, "ProgressBar");
+impl_state_type!(RatingState, Rating, "Rating");
+impl_state_type!(StepperState, Stepper, "Stepper");
+impl_state_type!(TickerState, Ticker, "Ticker");
+impl_state_type!(QrCodeState, QrCode, "QrCode");
+impl_state_type!(HeatmapState, Heatmap, "Heatmap");
 
 /// State trait which contains methods for managing fields common to all widget states.
 pub trait GenericState {
@@ -564,25 +889,27 @@ pub trait GenericState {
     fn get_color_config_mut(&mut self) -> &mut ColorConfig;
 
     /// Convenience function. Get a Foreground and Background color depending on the state of
-    /// the widget (e.g. disabled, selected, etc.).
+    /// the widget (e.g. disabled, selected, etc.). While selected, the colors depend on the
+    /// [SelectionStyle] of the widget; styles that don't change the colors return the normal
+    /// colors.
     fn get_context_colors(&self) -> (Color, Color) {
-        let fg_color = if self.get_disabled() {
-            self.get_color_config().get_disabled_fg_color()
-        } else if self.get_selected() {
-            self.get_color_config().get_selection_fg_color()
-        } else {
-            self.get_color_config().get_fg_color()
-        };
-
-        let bg_color = if self.get_disabled() {
-            self.get_color_config().get_disabled_bg_color()
-        } else if self.get_selected() {
-            self.get_color_config().get_selection_bg_color()
-        } else {
-            self.get_color_config().get_bg_color()
-        };
-
-        (fg_color, bg_color)
+        let colors = self.get_color_config();
+        if self.get_disabled() {
+            return (colors.get_disabled_fg_color(), colors.get_disabled_bg_color());
+        }
+        if !self.get_selected() {
+            return (colors.get_fg_color(), colors.get_bg_color());
+        }
+        match colors.get_selection_style() {
+            SelectionStyle::Colors => {
+                (colors.get_selection_fg_color(), colors.get_selection_bg_color())
+            }
+            SelectionStyle::Border if !self.get_border_config().get_border() => {
+                (colors.get_selection_fg_color(), colors.get_selection_bg_color())
+            }
+            SelectionStyle::Invert => (colors.get_bg_color(), colors.get_fg_color()),
+            _ => (colors.get_fg_color(), colors.get_bg_color()),
+        }
     }
 
     /// Get the top left and bottom right corners of a widget in (X, Y) coordinate tuples.
@@ -635,6 +962,66 @@ pub trait GenericState {
         _collides(self.get_effective_absolute_position(), pos, &size)
     }
 
+    /// Convert an absolute position on the screen to a position relative to the top left corner
+    /// of this widget, including its' border and padding. This is the frame of reference of the
+    /// mouse positions passed to mouse callbacks. Returns None if the position is outside of this
+    /// widget.
+    fn absolute_to_local_position(&self, pos: Coordinates) -> Option<Coordinates> {
+        let size = Size::from_state_size(self.get_size());
+        _to_relative(self.get_absolute_position(), pos, &size)
+    }
+
+    /// Convert an absolute position on the screen to a position relative to the content of this
+    /// widget, i.e. inside its' border and padding. Returns None if the position is outside of
+    /// the content.
+    fn absolute_to_content_position(&self, pos: Coordinates) -> Option<Coordinates> {
+        _to_relative(self.get_effective_absolute_position(), pos, &self.get_effective_size())
+    }
+
+    /// Convert a position relative to the top left corner of this widget (e.g. the mouse position
+    /// passed to a mouse callback) to an absolute position on the screen. The result can be
+    /// negative or outside of the screen if this widget is scrolled out of view.
+    fn local_to_absolute_position(&self, pos: Coordinates) -> IsizeCoordinates {
+        let absolute = self.get_absolute_position();
+        IsizeCoordinates::new(absolute.x + pos.x as isize, absolute.y + pos.y as isize)
+    }
+
+    /// Convert a position relative to the content of this widget to an absolute position on the
+    /// screen. The result can be negative or outside of the screen if this widget is scrolled out
+    /// of view.
+    fn content_to_absolute_position(&self, pos: Coordinates) -> IsizeCoordinates {
+        let absolute = self.get_effective_absolute_position();
+        IsizeCoordinates::new(absolute.x + pos.x as isize, absolute.y + pos.y as isize)
+    }
+
+    /// Convert a position relative to the top left corner of this widget (e.g. the mouse position
+    /// passed to a mouse callback) to a position relative to its' content. Returns None if the
+    /// position is on the border or padding.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use ez_term::*;
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// let my_callback = |context: Context, mouse_pos: Coordinates| {
+    ///     let state = context.state_tree.get(&context.widget_path).as_generic();
+    ///     if let Some(pos) = state.local_to_content_position(mouse_pos) {
+    ///         println!("Clicked row {} of the content", pos.y);
+    ///     }
+    ///     true
+    /// };
+    /// scheduler.update_callback_config("my_canvas",
+    ///     CallbackConfig::from_on_left_mouse_click(Box::new(my_callback)));
+    /// ```
+    fn local_to_content_position(&self, pos: Coordinates) -> Option<Coordinates> {
+        let absolute = self.local_to_absolute_position(pos);
+        if absolute.x < 0 || absolute.y < 0 {
+            return None;
+        }
+        self.absolute_to_content_position(absolute.as_coordinates())
+    }
+
     /// Returns a bool representing whether this widget can be select by keyboard or mouse. E.g.
     /// labels cannot be selected, but checkboxes can.
     fn is_selectable(&self) -> bool {
@@ -659,22 +1046,168 @@ pub trait GenericState {
     /// will select 1, then 2, then this widget. Used for keyboard up and down keys.
     fn set_selection_order(&mut self, _order: usize) {}
 
+    /// Get the weight of this widget. In a box layout, widgets with a weight share the space left
+    /// over by their siblings proportionally to their weights. 0.0 means no weight.
+    fn get_weight(&self) -> f64;
+
+    /// Set the weight of this widget. In a box layout, widgets with a weight share the space left
+    /// over by their siblings proportionally to their weights. 0.0 means no weight. Siblings
+    /// without a weight that use the default size_hint on the axis of the layout get the size of
+    /// their content on that axis; give them a fixed size or size_hint to size them otherwise.
+    fn set_weight(&mut self, weight: f64);
+
+    /// Get the z index of this widget. In a float layout, overlapping children with a higher z
+    /// index are drawn on top of (and receive mouse events before) those with a lower one.
+    fn get_z_index(&self) -> usize;
+
+    /// Set the z index of this widget. In a float layout, overlapping children with a higher z
+    /// index are drawn on top of (and receive mouse events before) those with a lower one. Call
+    /// [update] afterwards to redraw the layout, or see [SchedulerFrontend.bring_to_front].
+    fn set_z_index(&mut self, z_index: usize);
+
+    /// Get the [AnchorConfig] of this widget, which can keep it at an edge or corner of the
+    /// terminal. Only used when the parent is a float layout.
+    fn get_anchor_config(&self) -> &AnchorConfig;
+
+    /// Get a mutable ref to the [AnchorConfig] of this widget. Only used when the parent is a
+    /// float layout.
+    fn get_anchor_config_mut(&mut self) -> &mut AnchorConfig;
+
+    /// Get the [DockPosition] of this widget. Only used when the parent is a dock layout.
+    fn get_dock(&self) -> DockPosition;
+
+    /// Set the [DockPosition] of this widget. Only used when the parent is a dock layout.
+    fn set_dock(&mut self, dock: DockPosition);
+
+    /// Get whether this widget is collapsed. Only used when the parent is a dock layout; a
+    /// collapsed widget is shown as a single row or column containing its' ID.
+    fn get_collapsed(&self) -> bool;
+
+    /// Set whether this widget is collapsed. Only used when the parent is a dock layout; a
+    /// collapsed widget is shown as a single row or column containing its' ID.
+    fn set_collapsed(&mut self, collapsed: bool);
+
     fn set_selected(&mut self, _state: bool) {}
 
     fn get_selected(&self) -> bool {
         false
     }
 
+    /// Set whether the mouse is over this widget. Set by the run loop; a hovered widget is drawn
+    /// in its' hover colors (see [ColorConfig.hover_fg_color]).
+    fn set_hovered(&mut self, _state: bool) {}
+
+    fn get_hovered(&self) -> bool {
+        false
+    }
+
+    /// Set whether this widget is held down by the left mouse button. Set by the run loop; a held
+    /// widget is drawn in its' pressed colors (see [ColorConfig.pressed_fg_color]).
+    fn set_held(&mut self, _state: bool) {}
+
+    fn get_held(&self) -> bool {
+        false
+    }
+
+    /// Get the [InteractionState] of this widget, which decides which style overrides of its'
+    /// [ColorConfig] are used to draw it.
+    fn get_interaction_state(&self) -> InteractionState {
+        if self.get_disabled() {
+            InteractionState::Disabled
+        } else if self.get_held() {
+            InteractionState::Pressed
+        } else if self.get_hovered() {
+            InteractionState::Hovered
+        } else if self.get_selected() {
+            InteractionState::Focused
+        } else {
+            InteractionState::Normal
+        }
+    }
+
     fn update(&self, scheduler: &mut SchedulerFrontend) {
         scheduler.update_widget(self.get_path())
     }
 
+    /// Redraw only some cells of this widget on the next frame, instead of the whole widget. See
+    /// [SchedulerFrontend.update_widget_cells] for more info.
+    fn update_cells(&self, cells: Vec<Coordinates>, scheduler: &mut SchedulerFrontend) {
+        scheduler.update_widget_cells(self.get_path(), cells)
+    }
+
+    /// Schedule a task that runs once after a delay, tied to the lifetime of this widget: the
+    /// task is cancelled automatically when the widget is removed. The name only has to be unique
+    /// for this widget. See [SchedulerFrontend.schedule_once] for more info.
+    ///
+    /// # Example:
+    ///
+    /// We'll reset the text of a label after 5 seconds:
+    /// ```
+    /// use std::time::Duration;
+    /// use ez_term::*;
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// let reset_text = |context: Context| {
+    ///     let state = context.state_tree.get_mut("my_label").as_label_mut();
+    ///     state.set_text("".to_string());
+    ///     state.update(context.scheduler);
+    /// };
+    /// let state = state_tree.get("my_label").as_label();
+    /// state.schedule_once("reset_text", Box::new(reset_text), Duration::from_secs(5),
+    ///                     &mut scheduler);
+    /// ```
+    fn schedule_once(
+        &self,
+        name: &str,
+        func: GenericTask,
+        after: Duration,
+        scheduler: &mut SchedulerFrontend,
+    ) {
+        scheduler.schedule_once(&format!("{}/{}", self.get_path(), name), func, after);
+    }
+
+    /// Schedule a task that runs on an interval, tied to the lifetime of this widget: the task is
+    /// cancelled automatically when the widget is removed, so it can never run against a deleted
+    /// widget. The name only has to be unique for this widget. See
+    /// [SchedulerFrontend.schedule_recurring] for more info.
+    fn schedule_recurring(
+        &self,
+        name: &str,
+        func: GenericRecurringTask,
+        interval: Duration,
+        scheduler: &mut SchedulerFrontend,
+    ) {
+        scheduler.schedule_recurring(&format!("{}/{}", self.get_path(), name), func, interval);
+    }
+
+    /// Cancel a task scheduled with [schedule_once] on this widget.
+    fn cancel_task(&self, name: &str, scheduler: &mut SchedulerFrontend) {
+        scheduler.cancel_task(&format!("{}/{}", self.get_path(), name));
+    }
+
+    /// Cancel a task scheduled with [schedule_recurring] on this widget.
+    fn cancel_recurring_task(&self, name: &str, scheduler: &mut SchedulerFrontend) {
+        scheduler.cancel_recurring_task(&format!("{}/{}", self.get_path(), name));
+    }
+
     /// Copy values from another state to this state. Used to copy from templates.
     fn copy_state_values(&mut self, other: EzState);
 
     fn clean_up_properties(&self, scheduler: &mut SchedulerFrontend);
 }
 
+/// Base func for converting an absolute position to a position relative to the top left corner of
+/// an area. Returns None if the position is outside of the area.
+fn _to_relative(pos_1: IsizeCoordinates, pos_2: Coordinates, size: &Size) -> Option<Coordinates> {
+    if !_collides(pos_1, pos_2, size) {
+        return None;
+    }
+    Some(Coordinates::new(
+        (pos_2.x as isize - pos_1.x) as usize,
+        (pos_2.y as isize - pos_1.y) as usize,
+    ))
+}
+
 /// Base func for whether a single point collides with a widget. Use [collides] or
 /// [collides_effective] depending on the situation.
 fn _collides(pos_1: IsizeCoordinates, pos_2: Coordinates, size: &Size) -> bool {
@@ -0,0 +1,920 @@
+use crossterm::style::Color;
+
+use crate::property::ez_values::EzValues;
+use crate::run::definitions::{Coordinates, IsizeCoordinates};
+use crate::scheduler::scheduler::SchedulerFrontend;
+use crate::scheduler::scheduler_funcs::clean_up_property;
+use crate::states::definitions::{
+    AnchorConfig, AutoScale, BorderConfig, ColorConfig, DockPosition, HorizontalAlignment,
+    InfiniteSize, Padding, PosHint, SizeHint, StateCoordinates, StateSize, VerticalAlignment,
+};
+use crate::states::colors::blend;
+use crate::states::ez_state::{EzState, GenericState};
+use crate::EzProperty;
+
+/// [State] implementation for [Heatmap].
+#[derive(Clone, Debug)]
+pub struct HeatmapState {
+    /// Path to the widget to which this state belongs
+    pub path: String,
+
+    /// Values of each cell by [row][column]. Internal only, use [set_values]
+    values: Vec<Vec<f64>>,
+
+    /// Labels shown in cells by [row][column], e.g. days of a calendar. Internal only, use
+    /// [set_labels]
+    labels: Vec<Vec<String>>,
+
+    /// Lowest and highest value of the color scale currently in use. Internal only
+    range: (f64, f64),
+
+    /// Color of cells with the lowest value of the scale
+    pub low_color: EzProperty<Color>,
+
+    /// Color of cells with the highest value of the scale
+    pub high_color: EzProperty<Color>,
+
+    /// Bool representing whether the color scale runs from the lowest to the highest value in
+    /// the heatmap. If false, [min_value] and [max_value] are used
+    pub auto_range: EzProperty<bool>,
+
+    /// Value that gets [low_color] when [auto_range] is false. Lower values get it too
+    pub min_value: EzProperty<f64>,
+
+    /// Value that gets [high_color] when [auto_range] is false. Higher values get it too
+    pub max_value: EzProperty<f64>,
+
+    /// Bool representing whether the value of each cell is shown in cells without a label
+    pub show_values: EzProperty<bool>,
+
+    /// Amount of decimals of values shown with [show_values]
+    pub decimals: EzProperty<usize>,
+
+    /// Width of each cell of the heatmap in terminal cells
+    pub cell_width: EzProperty<usize>,
+
+    /// Height of each cell of the heatmap in terminal cells
+    pub cell_height: EzProperty<usize>,
+
+    /// Position of this widget relative to its' parent [layout]
+    pub position: StateCoordinates,
+
+    /// Absolute position of this layout on screen. Automatically propagated, do not set manually
+    absolute_position: IsizeCoordinates,
+
+    /// Relative height/width of this widget to parent layout
+    pub size_hint: SizeHint,
+
+    /// Pos hint of this widget
+    pub pos_hint: PosHint,
+
+    /// size of this widget
+    pub size: StateSize,
+
+    /// Infinite size of this widget for x and y axes, used in scrolling
+    infinite_size: InfiniteSize,
+
+    /// Automatically adjust size of widget to content
+    pub auto_scale: AutoScale,
+
+    /// Amount of space to leave between sides of the widget and other widgets
+    pub padding: Padding,
+
+    /// Horizontal alignment of this widget
+    pub halign: EzProperty<HorizontalAlignment>,
+
+    /// Vertical alignment of this widget
+    pub valign: EzProperty<VerticalAlignment>,
+
+    /// [BorderConfig] object that will be used to draw the border if enabled
+    pub border_config: BorderConfig,
+
+    /// Object containing colors to be used by this widget in different situations
+    pub colors: ColorConfig,
+
+    /// Bool representing whether widget is disabled, i.e. cannot be interacted with
+    pub disabled: EzProperty<bool>,
+
+    /// Global order number in which this widget will be selection when user presses down/up keys
+    pub selection_order: EzProperty<usize>,
+
+    /// Whether this widget is collapsed when its' parent is a [LayoutMode::Dock] layout
+    pub collapsed: EzProperty<bool>,
+
+    /// Where this widget is placed when its' parent is a [LayoutMode::Dock] layout
+    pub dock: EzProperty<DockPosition>,
+
+    /// Share of the leftover space this widget gets in a box layout, relative to the weights of
+    /// its' siblings. 0.0 means the widget is not weighted and is sized normally
+    pub weight: EzProperty<f64>,
+
+    /// Order in which overlapping children of a [LayoutMode::Float] layout are drawn; higher
+    /// values are drawn on top. Children with the same z index are drawn in declaration order
+    pub z_index: EzProperty<usize>,
+
+    /// Terminal edge or corner this widget is kept at when its' parent is a [LayoutMode::Float]
+    /// layout, see [Anchor]
+    pub anchor: AnchorConfig,
+
+    /// Bool representing whether this widget is currently selected.
+    selected: bool,
+
+    /// Bool representing whether the mouse is currently over this widget.
+    hovered: bool,
+
+    /// Bool representing whether this widget is currently held down by the mouse.
+    held: bool,
+}
+impl HeatmapState {
+    pub fn new(path: String, scheduler: &mut SchedulerFrontend) -> Self {
+        HeatmapState {
+            path: path.clone(),
+            values: Vec::new(),
+            labels: Vec::new(),
+            range: (0.0, 0.0),
+            low_color: scheduler
+                .new_color_property(format!("{}/low_color", path).as_str(), Color::DarkBlue),
+            high_color: scheduler
+                .new_color_property(format!("{}/high_color", path).as_str(), Color::Red),
+            auto_range: scheduler.new_bool_property(format!("{}/auto_range", path).as_str(), true),
+            min_value: scheduler.new_f64_property(format!("{}/min_value", path).as_str(), 0.0),
+            max_value: scheduler.new_f64_property(format!("{}/max_value", path).as_str(), 100.0),
+            show_values: scheduler
+                .new_bool_property(format!("{}/show_values", path).as_str(), false),
+            decimals: scheduler.new_usize_property(format!("{}/decimals", path).as_str(), 0),
+            cell_width: scheduler.new_usize_property(format!("{}/cell_width", path).as_str(), 2),
+            cell_height: scheduler.new_usize_property(format!("{}/cell_height", path).as_str(), 1),
+            position: StateCoordinates::new(0, 0, path.clone(), scheduler),
+            absolute_position: IsizeCoordinates::default(),
+            size: StateSize::new(0, 0, path.clone(), scheduler),
+            infinite_size: InfiniteSize::default(),
+            size_hint: SizeHint::new(Some(1.0), Some(1.0), path.clone(), scheduler),
+            pos_hint: PosHint::new(None, None, path.clone(), scheduler),
+            auto_scale: AutoScale::new(false, false, path.clone(), scheduler),
+            padding: Padding::new(0, 0, 0, 0, path.clone(), scheduler),
+            halign: scheduler.new_horizontal_alignment_property(
+                format!("{}/halign", path).as_str(),
+                HorizontalAlignment::Left,
+            ),
+            valign: scheduler.new_vertical_alignment_property(
+                format!("{}/valign", path).as_str(),
+                VerticalAlignment::Top,
+            ),
+            selected: false,
+            hovered: false,
+            held: false,
+            disabled: scheduler.new_bool_property(format!("{}/disabled", path).as_str(), false),
+            selection_order: scheduler
+                .new_usize_property(format!("{}/selection_order", path).as_str(), 0),
+            collapsed: scheduler.new_bool_property(format!("{}/collapsed", path).as_str(), false),
+            dock: scheduler.new_dock_position_property(
+                format!("{}/dock", path).as_str(),
+                DockPosition::Center,
+            ),
+            weight: scheduler.new_f64_property(format!("{}/weight", path).as_str(), 0.0),
+            z_index: scheduler.new_usize_property(format!("{}/z_index", path).as_str(), 0),
+            anchor: AnchorConfig::new(path.clone(), scheduler),
+            border_config: BorderConfig::new(false, path.clone(), scheduler),
+            colors: ColorConfig::new(path, scheduler),
+        }
+    }
+}
+impl GenericState for HeatmapState {
+    fn get_path(&self) -> &String {
+        &self.path
+    }
+
+    fn get_property(&self, name: &str) -> EzValues {
+        match name {
+            "x" => EzValues::Usize(self.position.x.value),
+            "y" => EzValues::Usize(self.position.y.value),
+            "height" => EzValues::Usize(self.size.height.value),
+            "width" => EzValues::Usize(self.size.width.value),
+            "size_hint_x" => EzValues::SizeHint(self.size_hint.size_hint_x.value),
+            "size_hint_y" => EzValues::SizeHint(self.size_hint.size_hint_y.value),
+            "pos_hint_x" => EzValues::HorizontalPosHint(self.pos_hint.pos_hint_x.value),
+            "pos_hint_y" => EzValues::VerticalPosHint(self.pos_hint.pos_hint_y.value),
+            "auto_scale_width" => EzValues::Bool(self.auto_scale.auto_scale_width.value),
+            "auto_scale_height" => EzValues::Bool(self.auto_scale.auto_scale_height.value),
+            "padding_top" => EzValues::Usize(self.padding.padding_top.value),
+            "padding_bottom" => EzValues::Usize(self.padding.padding_bottom.value),
+            "padding_left" => EzValues::Usize(self.padding.padding_left.value),
+            "padding_right" => EzValues::Usize(self.padding.padding_right.value),
+            "halign" => EzValues::HorizontalAlignment(self.halign.value),
+            "valign" => EzValues::VerticalAlignment(self.valign.value),
+            "disabled" => EzValues::Bool(self.disabled.value),
+            "selection_order" => EzValues::Usize(self.selection_order.value),
+            "collapsed" => EzValues::Bool(self.collapsed.value),
+            "dock" => EzValues::DockPosition(self.dock.value),
+            "weight" => EzValues::F64(self.weight.value),
+            "z_index" => EzValues::Usize(self.z_index.value),
+            "anchor" => EzValues::String(self.anchor.anchor.value.clone()),
+            "anchor_offset_x" => EzValues::Usize(self.anchor.anchor_offset_x.value),
+            "anchor_offset_y" => EzValues::Usize(self.anchor.anchor_offset_y.value),
+            "border" => EzValues::Bool(self.border_config.border.value),
+            "horizontal_symbol" => {
+                EzValues::String(self.border_config.horizontal_symbol.value.to_string())
+            }
+            "vertical_symbol" => {
+                EzValues::String(self.border_config.vertical_symbol.value.to_string())
+            }
+            "top_left_symbol" => {
+                EzValues::String(self.border_config.top_left_symbol.value.to_string())
+            }
+            "top_right_symbol" => {
+                EzValues::String(self.border_config.top_right_symbol.value.to_string())
+            }
+            "bottom_left_symbol" => {
+                EzValues::String(self.border_config.bottom_left_symbol.value.to_string())
+            }
+            "bottom_right_symbol" => {
+                EzValues::String(self.border_config.bottom_right_symbol.value.to_string())
+            }
+            "fg_color" => EzValues::Color(self.colors.fg_color.value),
+            "bg_color" => EzValues::Color(self.colors.bg_color.value),
+            "selection_fg_color" => EzValues::Color(self.colors.selection_fg_color.value),
+            "selection_bg_color" => EzValues::Color(self.colors.selection_bg_color.value),
+            "disabled_fg_color" => EzValues::Color(self.colors.disabled_fg_color.value),
+            "disabled_bg_color" => EzValues::Color(self.colors.disabled_bg_color.value),
+            "auto_colors" => EzValues::Bool(self.colors.auto_colors.value),
+            "auto_contrast" => EzValues::Bool(self.colors.auto_contrast.value),
+            "selection_style" => EzValues::String(self.colors.selection_style.value.clone()),
+            "selection_marker" => EzValues::String(self.colors.selection_marker.value.clone()),
+            "hover_fg_color" => EzValues::Color(self.colors.hover_fg_color.value),
+            "hover_bg_color" => EzValues::Color(self.colors.hover_bg_color.value),
+            "pressed_fg_color" => EzValues::Color(self.colors.pressed_fg_color.value),
+            "pressed_bg_color" => EzValues::Color(self.colors.pressed_bg_color.value),
+            "focused_border" => EzValues::Bool(self.colors.focused_border.value),
+            "border_fg_color" => EzValues::Color(self.colors.border_fg_color.value),
+            "border_bg_color" => EzValues::Color(self.colors.border_bg_color.value),
+            "low_color" => EzValues::Color(self.low_color.value),
+            "high_color" => EzValues::Color(self.high_color.value),
+            "auto_range" => EzValues::Bool(self.auto_range.value),
+            "min_value" => EzValues::F64(self.min_value.value),
+            "max_value" => EzValues::F64(self.max_value.value),
+            "show_values" => EzValues::Bool(self.show_values.value),
+            "decimals" => EzValues::Usize(self.decimals.value),
+            "cell_width" => EzValues::Usize(self.cell_width.value),
+            "cell_height" => EzValues::Usize(self.cell_height.value),
+            _ => panic!("Invalid property name for heatmap state: {}", name),
+        }
+    }
+
+    fn update_property(&mut self, name: &str, value: EzValues) -> bool {
+        match name {
+            "x" => self.position.x.set_from_ez_value(value),
+            "y" => self.position.y.set_from_ez_value(value),
+            "height" => self.size.height.set_from_ez_value(value),
+            "width" => self.size.width.set_from_ez_value(value),
+            "size_hint_x" => self.size_hint.size_hint_x.set_from_ez_value(value),
+            "size_hint_y" => self.size_hint.size_hint_y.set_from_ez_value(value),
+            "pos_hint_x" => self.pos_hint.pos_hint_x.set_from_ez_value(value),
+            "pos_hint_y" => self.pos_hint.pos_hint_y.set_from_ez_value(value),
+            "auto_scale_width" => self.auto_scale.auto_scale_width.set_from_ez_value(value),
+            "auto_scale_height" => self.auto_scale.auto_scale_height.set_from_ez_value(value),
+            "padding_top" => self.padding.padding_top.set_from_ez_value(value),
+            "padding_bottom" => self.padding.padding_bottom.set_from_ez_value(value),
+            "padding_left" => self.padding.padding_left.set_from_ez_value(value),
+            "padding_right" => self.padding.padding_right.set_from_ez_value(value),
+            "halign" => self.halign.set_from_ez_value(value),
+            "valign" => self.valign.set_from_ez_value(value),
+            "disabled" => self.disabled.set_from_ez_value(value),
+            "selection_order" => self.selection_order.set_from_ez_value(value),
+            "collapsed" => self.collapsed.set_from_ez_value(value),
+            "dock" => self.dock.set_from_ez_value(value),
+            "weight" => self.weight.set_from_ez_value(value),
+            "z_index" => self.z_index.set_from_ez_value(value),
+            "anchor" => self.anchor.anchor.set_from_ez_value(value),
+            "anchor_offset_x" => self.anchor.anchor_offset_x.set_from_ez_value(value),
+            "anchor_offset_y" => self.anchor.anchor_offset_y.set_from_ez_value(value),
+            "border" => self.border_config.border.set_from_ez_value(value),
+            "horizontal_symbol" => self
+                .border_config
+                .horizontal_symbol
+                .set_from_ez_value(value),
+            "vertical_symbol" => self.border_config.vertical_symbol.set_from_ez_value(value),
+            "top_left_symbol" => self.border_config.top_left_symbol.set_from_ez_value(value),
+            "top_right_symbol" => self.border_config.top_right_symbol.set_from_ez_value(value),
+            "bottom_left_symbol" => self
+                .border_config
+                .bottom_left_symbol
+                .set_from_ez_value(value),
+            "bottom_right_symbol" => self
+                .border_config
+                .bottom_right_symbol
+                .set_from_ez_value(value),
+            "fg_color" => self.colors.fg_color.set_from_ez_value(value),
+            "bg_color" => self.colors.bg_color.set_from_ez_value(value),
+            "selection_fg_color" => self.colors.selection_fg_color.set_from_ez_value(value),
+            "selection_bg_color" => self.colors.selection_bg_color.set_from_ez_value(value),
+            "disabled_fg_color" => self.colors.disabled_fg_color.set_from_ez_value(value),
+            "disabled_bg_color" => self.colors.disabled_bg_color.set_from_ez_value(value),
+            "auto_colors" => self.colors.auto_colors.set_from_ez_value(value),
+            "auto_contrast" => self.colors.auto_contrast.set_from_ez_value(value),
+            "selection_style" => self.colors.selection_style.set_from_ez_value(value),
+            "selection_marker" => self.colors.selection_marker.set_from_ez_value(value),
+            "hover_fg_color" => self.colors.hover_fg_color.set_from_ez_value(value),
+            "hover_bg_color" => self.colors.hover_bg_color.set_from_ez_value(value),
+            "pressed_fg_color" => self.colors.pressed_fg_color.set_from_ez_value(value),
+            "pressed_bg_color" => self.colors.pressed_bg_color.set_from_ez_value(value),
+            "focused_border" => self.colors.focused_border.set_from_ez_value(value),
+            "border_fg_color" => self.colors.border_fg_color.set_from_ez_value(value),
+            "border_bg_color" => self.colors.border_bg_color.set_from_ez_value(value),
+            "low_color" => self.low_color.set_from_ez_value(value),
+            "high_color" => self.high_color.set_from_ez_value(value),
+            "auto_range" => self.auto_range.set_from_ez_value(value),
+            "min_value" => self.min_value.set_from_ez_value(value),
+            "max_value" => self.max_value.set_from_ez_value(value),
+            "show_values" => self.show_values.set_from_ez_value(value),
+            "decimals" => self.decimals.set_from_ez_value(value),
+            "cell_width" => self.cell_width.set_from_ez_value(value),
+            "cell_height" => self.cell_height.set_from_ez_value(value),
+            _ => panic!("Invalid property name for heatmap state: {}", name),
+        }
+    }
+
+    fn copy_state_values(&mut self, other: EzState) {
+        let other = other.as_heatmap();
+        self.position.x.copy_from(&other.position.x);
+        self.position.y.copy_from(&other.position.y);
+        self.size.height.copy_from(&other.size.height);
+        self.size.width.copy_from(&other.size.width);
+        self.size_hint
+            .size_hint_x
+            .copy_from(&other.size_hint.size_hint_x);
+        self.size_hint
+            .size_hint_y
+            .copy_from(&other.size_hint.size_hint_y);
+        self.pos_hint
+            .pos_hint_x
+            .copy_from(&other.pos_hint.pos_hint_x);
+        self.pos_hint
+            .pos_hint_y
+            .copy_from(&other.pos_hint.pos_hint_y);
+        self.auto_scale
+            .auto_scale_width
+            .copy_from(&other.auto_scale.auto_scale_width);
+        self.auto_scale
+            .auto_scale_height
+            .copy_from(&other.auto_scale.auto_scale_height);
+        self.padding
+            .padding_top
+            .copy_from(&other.padding.padding_top);
+        self.padding
+            .padding_bottom
+            .copy_from(&other.padding.padding_bottom);
+        self.padding
+            .padding_left
+            .copy_from(&other.padding.padding_left);
+        self.padding
+            .padding_right
+            .copy_from(&other.padding.padding_right);
+        self.padding
+            .padding_right
+            .copy_from(&other.padding.padding_right);
+        self.halign.copy_from(&other.halign);
+        self.valign.copy_from(&other.valign);
+        self.disabled.copy_from(&other.disabled);
+        self.selection_order.copy_from(&other.selection_order);
+        self.collapsed.copy_from(&other.collapsed);
+        self.dock.copy_from(&other.dock);
+        self.weight.copy_from(&other.weight);
+        self.z_index.copy_from(&other.z_index);
+        self.anchor.anchor.copy_from(&other.anchor.anchor);
+        self.anchor
+            .anchor_offset_x
+            .copy_from(&other.anchor.anchor_offset_x);
+        self.anchor
+            .anchor_offset_y
+            .copy_from(&other.anchor.anchor_offset_y);
+        self.border_config
+            .border
+            .copy_from(&other.border_config.border);
+        self.border_config
+            .horizontal_symbol
+            .copy_from(&other.border_config.horizontal_symbol);
+        self.border_config
+            .vertical_symbol
+            .copy_from(&other.border_config.vertical_symbol);
+        self.border_config
+            .top_left_symbol
+            .copy_from(&other.border_config.top_left_symbol);
+        self.border_config
+            .top_right_symbol
+            .copy_from(&other.border_config.top_right_symbol);
+        self.border_config
+            .bottom_left_symbol
+            .copy_from(&other.border_config.bottom_left_symbol);
+        self.border_config
+            .bottom_right_symbol
+            .copy_from(&other.border_config.bottom_right_symbol);
+        self.colors.fg_color.copy_from(&other.colors.fg_color);
+        self.colors.bg_color.copy_from(&other.colors.bg_color);
+        self.colors
+            .selection_fg_color
+            .copy_from(&other.colors.selection_fg_color);
+        self.colors
+            .selection_bg_color
+            .copy_from(&other.colors.selection_bg_color);
+        self.colors
+            .disabled_fg_color
+            .copy_from(&other.colors.disabled_fg_color);
+        self.colors
+            .disabled_bg_color
+            .copy_from(&other.colors.disabled_bg_color);
+        self.colors.auto_colors.copy_from(&other.colors.auto_colors);
+        self.colors
+            .auto_contrast
+            .copy_from(&other.colors.auto_contrast);
+        self.colors
+            .selection_style
+            .copy_from(&other.colors.selection_style);
+        self.colors
+            .selection_marker
+            .copy_from(&other.colors.selection_marker);
+        self.colors
+            .hover_fg_color
+            .copy_from(&other.colors.hover_fg_color);
+        self.colors
+            .hover_bg_color
+            .copy_from(&other.colors.hover_bg_color);
+        self.colors
+            .pressed_fg_color
+            .copy_from(&other.colors.pressed_fg_color);
+        self.colors
+            .pressed_bg_color
+            .copy_from(&other.colors.pressed_bg_color);
+        self.colors
+            .focused_border
+            .copy_from(&other.colors.focused_border);
+        self.colors
+            .border_fg_color
+            .copy_from(&other.colors.border_fg_color);
+        self.colors
+            .border_bg_color
+            .copy_from(&other.colors.border_bg_color);
+        self.colors
+            .cursor_color
+            .copy_from(&other.colors.cursor_color);
+        self.values = other.values.clone();
+        self.labels = other.labels.clone();
+        self.range = other.range;
+        self.low_color.copy_from(&other.low_color);
+        self.high_color.copy_from(&other.high_color);
+        self.auto_range.copy_from(&other.auto_range);
+        self.min_value.copy_from(&other.min_value);
+        self.max_value.copy_from(&other.max_value);
+        self.show_values.copy_from(&other.show_values);
+        self.decimals.copy_from(&other.decimals);
+        self.cell_width.copy_from(&other.cell_width);
+        self.cell_height.copy_from(&other.cell_height);
+    }
+
+    fn get_size_hint(&self) -> &SizeHint {
+        &self.size_hint
+    }
+
+    fn get_size_hint_mut(&mut self) -> &mut SizeHint {
+        &mut self.size_hint
+    }
+
+    fn get_pos_hint(&self) -> &PosHint {
+        &self.pos_hint
+    }
+
+    fn get_pos_hint_mut(&mut self) -> &mut PosHint {
+        &mut self.pos_hint
+    }
+
+    fn get_auto_scale(&self) -> &AutoScale {
+        &self.auto_scale
+    }
+
+    fn get_auto_scale_mut(&mut self) -> &mut AutoScale {
+        &mut self.auto_scale
+    }
+
+    fn get_size(&self) -> &StateSize {
+        &self.size
+    }
+
+    fn get_size_mut(&mut self) -> &mut StateSize {
+        &mut self.size
+    }
+
+    fn get_infinite_size(&self) -> &InfiniteSize {
+        &self.infinite_size
+    }
+
+    fn get_infinite_size_mut(&mut self) -> &mut InfiniteSize {
+        &mut self.infinite_size
+    }
+
+    fn get_position(&self) -> &StateCoordinates {
+        &self.position
+    }
+
+    fn get_position_mut(&mut self) -> &mut StateCoordinates {
+        &mut self.position
+    }
+
+    fn set_absolute_position(&mut self, pos: IsizeCoordinates) {
+        self.absolute_position = pos
+    }
+
+    fn get_absolute_position(&self) -> IsizeCoordinates {
+        self.absolute_position
+    }
+
+    fn set_halign(&mut self, alignment: HorizontalAlignment) {
+        self.halign.set(alignment);
+    }
+
+    fn get_halign(&self) -> HorizontalAlignment {
+        self.halign.value
+    }
+
+    fn set_valign(&mut self, alignment: VerticalAlignment) {
+        self.valign.set(alignment);
+    }
+
+    fn get_valign(&self) -> VerticalAlignment {
+        self.valign.value
+    }
+
+    fn get_padding(&self) -> &Padding {
+        &self.padding
+    }
+
+    fn get_padding_mut(&mut self) -> &mut Padding {
+        &mut self.padding
+    }
+
+    fn get_border_config(&self) -> &BorderConfig {
+        &self.border_config
+    }
+
+    fn get_border_config_mut(&mut self) -> &mut BorderConfig {
+        &mut self.border_config
+    }
+
+    fn get_color_config(&self) -> &ColorConfig {
+        &self.colors
+    }
+
+    fn get_color_config_mut(&mut self) -> &mut ColorConfig {
+        &mut self.colors
+    }
+
+    fn is_selectable(&self) -> bool {
+        false
+    }
+
+    fn set_disabled(&mut self, disabled: bool) {
+        self.disabled.set(disabled);
+    }
+
+    fn get_disabled(&self) -> bool {
+        self.disabled.value
+    }
+
+    fn get_selection_order(&self) -> usize {
+        self.selection_order.value
+    }
+
+    fn set_selection_order(&mut self, order: usize) {
+        self.selection_order.set(order);
+    }
+
+    fn get_collapsed(&self) -> bool {
+        self.collapsed.value
+    }
+
+    fn set_collapsed(&mut self, collapsed: bool) {
+        self.collapsed.set(collapsed);
+    }
+
+    fn get_dock(&self) -> DockPosition {
+        self.dock.value
+    }
+
+    fn set_dock(&mut self, dock: DockPosition) {
+        self.dock.set(dock);
+    }
+
+    fn get_weight(&self) -> f64 {
+        self.weight.value
+    }
+
+    fn set_weight(&mut self, weight: f64) {
+        self.weight.set(weight);
+    }
+
+    fn get_z_index(&self) -> usize {
+        self.z_index.value
+    }
+
+    fn set_z_index(&mut self, z_index: usize) {
+        self.z_index.set(z_index);
+    }
+
+    fn get_anchor_config(&self) -> &AnchorConfig {
+        &self.anchor
+    }
+
+    fn get_anchor_config_mut(&mut self) -> &mut AnchorConfig {
+        &mut self.anchor
+    }
+
+    fn set_selected(&mut self, state: bool) {
+        self.selected = state;
+    }
+
+    fn get_selected(&self) -> bool {
+        self.selected
+    }
+
+    fn set_hovered(&mut self, state: bool) {
+        self.hovered = state;
+    }
+
+    fn get_hovered(&self) -> bool {
+        self.hovered
+    }
+
+    fn set_held(&mut self, state: bool) {
+        self.held = state;
+    }
+
+    fn get_held(&self) -> bool {
+        self.held
+    }
+
+    fn clean_up_properties(&self, scheduler: &mut SchedulerFrontend) {
+        self.position.clean_up_properties(scheduler);
+        self.size.clean_up_properties(scheduler);
+        self.size_hint.clean_up_properties(scheduler);
+        self.pos_hint.clean_up_properties(scheduler);
+        self.auto_scale.clean_up_properties(scheduler);
+        self.padding.clean_up_properties(scheduler);
+        clean_up_property(scheduler, &self.halign.name);
+        clean_up_property(scheduler, &self.valign.name);
+        clean_up_property(scheduler, &self.low_color.name);
+        clean_up_property(scheduler, &self.high_color.name);
+        clean_up_property(scheduler, &self.auto_range.name);
+        clean_up_property(scheduler, &self.min_value.name);
+        clean_up_property(scheduler, &self.max_value.name);
+        clean_up_property(scheduler, &self.show_values.name);
+        clean_up_property(scheduler, &self.decimals.name);
+        clean_up_property(scheduler, &self.cell_width.name);
+        clean_up_property(scheduler, &self.cell_height.name);
+        clean_up_property(scheduler, &self.disabled.name);
+        clean_up_property(scheduler, &self.selection_order.name);
+        clean_up_property(scheduler, &self.collapsed.name);
+        clean_up_property(scheduler, &self.dock.name);
+        clean_up_property(scheduler, &self.weight.name);
+        clean_up_property(scheduler, &self.z_index.name);
+        self.anchor.clean_up_properties(scheduler);
+        self.border_config.clean_up_properties(scheduler);
+        self.colors.clean_up_properties(scheduler);
+    }
+}
+impl HeatmapState {
+    /// Set the values of each cell by [row][column]. Rows may differ in length.
+    pub fn set_values(&mut self, values: Vec<Vec<f64>>) {
+        self.values = values;
+        self.range = self.calculate_range();
+    }
+
+    /// Get the values of each cell by [row][column].
+    pub fn get_values(&self) -> &Vec<Vec<f64>> {
+        &self.values
+    }
+
+    /// Set the value of a single cell. Does nothing if the cell does not exist. Use
+    /// [update_value] to also redraw only the changed cell.
+    pub fn set_value(&mut self, row: usize, column: usize, value: f64) {
+        if let Some(old_value) = self.values.get_mut(row).and_then(|x| x.get_mut(column)) {
+            *old_value = value;
+            self.range = self.calculate_range();
+        }
+    }
+
+    /// Get the value of a single cell, if it exists.
+    pub fn get_value(&self, row: usize, column: usize) -> Option<f64> {
+        self.values.get(row)?.get(column).copied()
+    }
+
+    /// Set the value of a single cell and redraw it on the next frame. Only the terminal cells of
+    /// the changed cell are redrawn, unless the value changed the automatic range of the color
+    /// scale, in which case the whole heatmap is redrawn. Useful for monitoring large matrices
+    /// of which only a few values change at a time.
+    pub fn update_value(
+        &mut self,
+        row: usize,
+        column: usize,
+        value: f64,
+        scheduler: &mut SchedulerFrontend,
+    ) {
+        let old_range = self.get_range();
+        self.set_value(row, column, value);
+        if self.get_range() == old_range {
+            self.update_cells(self.get_cell_area(row, column), scheduler);
+        } else {
+            self.update(scheduler);
+        }
+    }
+
+    /// Set the labels shown in cells by [row][column], e.g. the days of a calendar. Cells without
+    /// a label (or with an empty label) show their value if [show_values] is true.
+    pub fn set_labels(&mut self, labels: Vec<Vec<String>>) {
+        self.labels = labels;
+    }
+
+    /// Get the labels shown in cells by [row][column].
+    pub fn get_labels(&self) -> &Vec<Vec<String>> {
+        &self.labels
+    }
+
+    /// Get the text shown in a cell: its' label, or its' value if [show_values] is true.
+    pub fn get_cell_text(&self, row: usize, column: usize) -> String {
+        match self.labels.get(row).and_then(|x| x.get(column)) {
+            Some(label) if !label.is_empty() => label.clone(),
+            _ if self.get_show_values() => match self.get_value(row, column) {
+                Some(value) => format!("{:.*}", self.get_decimals(), value),
+                None => String::new(),
+            },
+            _ => String::new(),
+        }
+    }
+
+    pub fn set_low_color(&mut self, color: Color) {
+        self.low_color.set(color);
+    }
+
+    pub fn get_low_color(&self) -> Color {
+        self.low_color.value
+    }
+
+    pub fn set_high_color(&mut self, color: Color) {
+        self.high_color.set(color);
+    }
+
+    pub fn get_high_color(&self) -> Color {
+        self.high_color.value
+    }
+
+    pub fn set_auto_range(&mut self, auto_range: bool) {
+        self.auto_range.set(auto_range);
+        self.range = self.calculate_range();
+    }
+
+    pub fn get_auto_range(&self) -> bool {
+        self.auto_range.value
+    }
+
+    pub fn set_min_value(&mut self, min_value: f64) {
+        self.min_value.set(min_value);
+        self.range = self.calculate_range();
+    }
+
+    pub fn get_min_value(&self) -> f64 {
+        self.min_value.value
+    }
+
+    pub fn set_max_value(&mut self, max_value: f64) {
+        self.max_value.set(max_value);
+        self.range = self.calculate_range();
+    }
+
+    pub fn get_max_value(&self) -> f64 {
+        self.max_value.value
+    }
+
+    pub fn set_show_values(&mut self, show_values: bool) {
+        self.show_values.set(show_values);
+    }
+
+    pub fn get_show_values(&self) -> bool {
+        self.show_values.value
+    }
+
+    pub fn set_decimals(&mut self, decimals: usize) {
+        self.decimals.set(decimals);
+    }
+
+    pub fn get_decimals(&self) -> usize {
+        self.decimals.value
+    }
+
+    pub fn set_cell_width(&mut self, cell_width: usize) {
+        self.cell_width.set(cell_width);
+    }
+
+    pub fn get_cell_width(&self) -> usize {
+        self.cell_width.value.max(1)
+    }
+
+    pub fn set_cell_height(&mut self, cell_height: usize) {
+        self.cell_height.set(cell_height);
+    }
+
+    pub fn get_cell_height(&self) -> usize {
+        self.cell_height.value.max(1)
+    }
+
+    /// Get the amount of rows and columns of the heatmap. The amount of columns is that of the
+    /// longest row.
+    pub fn get_grid_size(&self) -> (usize, usize) {
+        let columns = self.values.iter().map(|x| x.len()).max().unwrap_or(0);
+        (self.values.len(), columns)
+    }
+
+    /// Get the lowest and highest value of the color scale: those of the values if [auto_range]
+    /// is true, otherwise [min_value] and [max_value].
+    pub fn get_range(&self) -> (f64, f64) {
+        if self.get_auto_range() {
+            self.range
+        } else {
+            (self.get_min_value(), self.get_max_value())
+        }
+    }
+
+    /// Get the lowest and highest value, ignoring values that are not a number.
+    fn calculate_range(&self) -> (f64, f64) {
+        self.values
+            .iter()
+            .flatten()
+            .filter(|x| !x.is_nan())
+            .fold(None, |range: Option<(f64, f64)>, x| match range {
+                Some((low, high)) => Some((low.min(*x), high.max(*x))),
+                None => Some((*x, *x)),
+            })
+            .unwrap_or((0.0, 0.0))
+    }
+
+    /// Get the color of a value on the color scale. Values outside of the range get the color of
+    /// the nearest end. Returns None for values that are not a number.
+    pub fn get_value_color(&self, value: f64) -> Option<Color> {
+        if value.is_nan() {
+            return None;
+        }
+        let (low, high) = self.get_range();
+        let amount = if high > low {
+            ((value - low) / (high - low)).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        Some(blend(self.get_low_color(), self.get_high_color(), amount))
+    }
+
+    /// Get the position of the top left of the grid in the content of the widget, according to
+    /// the alignment of the widget.
+    pub fn get_grid_offset(&self) -> Coordinates {
+        let (rows, columns) = self.get_grid_size();
+        let width = self.get_effective_size().width;
+        let height = self.get_effective_size().height;
+        let grid_width = (columns * self.get_cell_width()).min(width);
+        let grid_height = (rows * self.get_cell_height()).min(height);
+        let x = match self.get_halign() {
+            HorizontalAlignment::Left => 0,
+            HorizontalAlignment::Right => width - grid_width,
+            HorizontalAlignment::Center => (width - grid_width) / 2,
+        };
+        let y = match self.get_valign() {
+            VerticalAlignment::Top => 0,
+            VerticalAlignment::Middle => (height - grid_height) / 2,
+            VerticalAlignment::Bottom => height - grid_height,
+        };
+        Coordinates::new(x, y)
+    }
+
+    /// Get the row and column of the heatmap cell at a position in the content of the widget, if
+    /// there is one.
+    pub fn get_cell_at(&self, pos: Coordinates) -> Option<(usize, usize)> {
+        let offset = self.get_grid_offset();
+        if pos.x < offset.x || pos.y < offset.y {
+            return None;
+        }
+        let row = (pos.y - offset.y) / self.get_cell_height();
+        let column = (pos.x - offset.x) / self.get_cell_width();
+        self.get_value(row, column).map(|_| (row, column))
+    }
+
+    /// Get the positions in the content of the widget covered by a cell of the heatmap, e.g. to
+    /// redraw it with [update_cells].
+    pub fn get_cell_area(&self, row: usize, column: usize) -> Vec<Coordinates> {
+        let offset = self.get_grid_offset();
+        let (cell_width, cell_height) = (self.get_cell_width(), self.get_cell_height());
+        let mut cells = Vec::new();
+        for y in 0..cell_height {
+            for x in 0..cell_width {
+                cells.push(Coordinates::new(
+                    offset.x + column * cell_width + x,
+                    offset.y + row * cell_height + y,
+                ));
+            }
+        }
+        cells
+    }
+}
@@ -1,14 +1,18 @@
 use std::collections::HashMap;
 
+use crossterm::style::Color;
+
 use crate::parser::ez_definition::Templates;
+use crate::parser::parse_properties::parse_filler_pattern_property;
 use crate::property::ez_values::EzValues;
 use crate::run::definitions::{IsizeCoordinates, Size};
 use crate::scheduler::scheduler::SchedulerFrontend;
 use crate::scheduler::scheduler_funcs::clean_up_property;
 use crate::states::definitions::{
-    AutoScale, BorderConfig, ColorConfig, HorizontalAlignment, InfiniteSize, LayoutMode,
-    LayoutOrientation, Padding, PosHint, ScrollingConfig, SizeHint, StateCoordinates, StateSize,
-    TableConfig, VerticalAlignment,
+    AnchorConfig, AutoScale, BorderConfig, ColorConfig, DockDrag, DockPosition, FillerPattern,
+    HorizontalAlignment, InfiniteSize, LayoutMode, LayoutOrientation, Padding, PosHint,
+    ScrollingConfig, SizeHint, StateCoordinates, StateSize, TableConfig, VerticalAlignment,
+    WindowDrag,
 };
 use crate::states::ez_state::{EzState, GenericState};
 use crate::widgets::ez_object::EzObjects;
@@ -71,6 +75,9 @@ pub struct LayoutState {
     /// Id of the active tab header button
     selected_tab_header: String,
 
+    /// What is currently being dragged if this is a dock layout
+    dock_drag: Option<DockDrag>,
+
     /// Bool representing whether this layout should be filled with [filler_symbol] in positions
     /// where it does not get other content from [get_contents]
     pub fill: EzProperty<bool>,
@@ -78,6 +85,72 @@ pub struct LayoutState {
     /// The [Pixel.Symbol] to use for filler pixels if [fill] is true
     pub filler_symbol: EzProperty<String>,
 
+    /// Pattern of the filler if [fill] is true, see [FillerPattern]. Stored by name, e.g.
+    /// "checkerboard"
+    pub filler_pattern: EzProperty<String>,
+
+    /// Number of colors a gradient [filler_pattern] steps through. 0 means a color for every
+    /// column or row
+    pub filler_gradient_steps: EzProperty<usize>,
+
+    /// Number of rows at the top of this layout that are filled with [filler_header_symbol] and
+    /// the filler header colors rather than with the [filler_pattern]
+    pub filler_header_height: EzProperty<usize>,
+
+    /// The [Pixel.Symbol] to use for filler pixels in the header region (see
+    /// [filler_header_height])
+    pub filler_header_symbol: EzProperty<String>,
+
+    /// Bool representing whether the filler is drawn through the empty positions of children.
+    /// If false the filler is only drawn where there are no children; only used in float mode
+    pub fill_behind_children: EzProperty<bool>,
+
+    /// Name of the slot this layout is in a template. Instances of the template can put child
+    /// widgets in the slot with "in_slot: <name>". Empty if this layout is not a slot
+    pub slot: EzProperty<String>,
+
+    /// Bool representing whether children of a horizontal box layout should be aligned by their
+    /// first row of text rather than by their top (or [valign])
+    pub align_baseline: EzProperty<bool>,
+
+    /// Bool representing whether this layout is hidden when the terminal is too small to show
+    /// the UI; borders and padding are dropped first
+    pub collapsible_on_small: EzProperty<bool>,
+
+    /// Minimum width of the terminal. Only used on the root layout; if the terminal is smaller a
+    /// message asking the user to enlarge it is shown instead of the UI. 0 means no minimum
+    pub min_terminal_width: EzProperty<usize>,
+
+    /// Minimum height of the terminal. Only used on the root layout; if the terminal is smaller a
+    /// message asking the user to enlarge it is shown instead of the UI. 0 means no minimum
+    pub min_terminal_height: EzProperty<usize>,
+
+    /// Bool representing whether this layout can be moved by dragging its' top row (title bar)
+    /// if its' parent is a float layout
+    pub movable: EzProperty<bool>,
+
+    /// Bool representing whether this layout can be resized by dragging its' right and bottom
+    /// edges if its' parent is a float layout
+    pub resizable: EzProperty<bool>,
+
+    /// Bool representing whether minimize and maximize buttons are drawn in the top right corner
+    /// of this layout if its' parent is a float layout
+    pub window_buttons: EzProperty<bool>,
+
+    /// Whether this layout is minimized to its' title bar
+    minimized: bool,
+
+    /// Whether this layout is maximized to the size of its' parent
+    maximized: bool,
+
+    /// Position and size (x, y, width, height) to restore when this layout is no longer
+    /// minimized or maximized
+    restore_geometry: Option<(usize, usize, usize, usize)>,
+
+    /// What is currently being dragged if this layout is movable or resizable, and the absolute
+    /// mouse position of the last drag event
+    window_drag: Option<(WindowDrag, IsizeCoordinates)>,
+
     /// [BorderConfig] object that will be used to draw the border if enabled
     pub border_config: BorderConfig,
 
@@ -96,10 +169,40 @@ pub struct LayoutState {
     /// Whether this layout can be dragged around if it is a modal
     pub can_drag: EzProperty<bool>,
 
+    /// Background color of every other child (or table row), making long lists easier to read.
+    /// [Color::Reset] means children are not striped
+    pub stripe_children: EzProperty<Color>,
+
+    /// Bool representing whether the child (or table row) under the mouse cursor is highlighted
+    pub highlight_child_on_hover: EzProperty<bool>,
+
+    /// Bool representing whether Tab selects the first widget again after the last one (and
+    /// Shift-Tab the last after the first). Only used on the root layout
+    pub focus_wrap: EzProperty<bool>,
+
+    /// Bool representing whether this layout is busy, e.g. while a background task loads its'
+    /// content. A busy layout is dimmed with a spinner on top, and its' children cannot be
+    /// interacted with
+    pub busy: EzProperty<bool>,
+
+    /// Text shown after the spinner while this layout is busy, e.g. "Loading..."
+    pub busy_text: EzProperty<String>,
+
+    /// Index of the spinner frame currently shown while this layout is busy
+    busy_frame: usize,
+
+    /// Path of the child currently under the mouse cursor if [highlight_child_on_hover] is
+    /// enabled, empty otherwise
+    hovered_child: String,
+
     /// A list of open modals. Modals are widgets that overlap other content; in other words, they
-    /// open 'in front of' other content. Only one can be shown at a time (the first on in the
-    /// list).
-    pub open_modal: Option<Box<EzObjects>>,
+    /// open 'in front of' other content. Modals can be opened on top of each other; the last one
+    /// in the list is on top and is the only one receiving input.
+    pub open_modals: Vec<EzObjects>,
+
+    /// Path of the widget that was selected when each open modal was opened (empty if none), so
+    /// the selection can be restored when the modal is dismissed.
+    pub modal_selections: Vec<String>,
 
     /// A hashmap of 'Template Name > [EzWidgetDefinition]'. Used to instantiate widget templates
     /// at runtime. E.g. when spawning popups.
@@ -111,8 +214,32 @@ pub struct LayoutState {
     /// Global order number in which this widget will be selection when user presses down/up keys
     pub selection_order: EzProperty<usize>,
 
+    /// Whether this widget is collapsed when its' parent is a [LayoutMode::Dock] layout
+    pub collapsed: EzProperty<bool>,
+
+    /// Where this widget is placed when its' parent is a [LayoutMode::Dock] layout
+    pub dock: EzProperty<DockPosition>,
+
+    /// Share of the leftover space this widget gets in a box layout, relative to the weights of
+    /// its' siblings. 0.0 means the widget is not weighted and is sized normally
+    pub weight: EzProperty<f64>,
+
+    /// Order in which overlapping children of a [LayoutMode::Float] layout are drawn; higher
+    /// values are drawn on top. Children with the same z index are drawn in declaration order
+    pub z_index: EzProperty<usize>,
+
+    /// Terminal edge or corner this widget is kept at when its' parent is a [LayoutMode::Float]
+    /// layout, see [Anchor]
+    pub anchor: AnchorConfig,
+
     /// Bool representing whether this widget is currently selected.
     selected: bool,
+
+    /// Bool representing whether the mouse is currently over this widget.
+    hovered: bool,
+
+    /// Bool representing whether this widget is currently held down by the mouse.
+    held: bool,
 }
 impl LayoutState {
     pub fn new(path: String, scheduler: &mut SchedulerFrontend) -> Self {
@@ -148,21 +275,74 @@ impl LayoutState {
             active_tab: scheduler
                 .new_string_property(format!("{}/active_tab", path).as_str(), String::new()),
             selected_tab_header: String::new(),
+            dock_drag: None,
             fill: scheduler.new_bool_property(format!("{}/fill", path).as_str(), false),
             filler_symbol: scheduler
                 .new_string_property(format!("{}/filler_symbol", path).as_str(), " ".to_string()),
+            filler_pattern: scheduler.new_string_property(
+                format!("{}/filler_pattern", path).as_str(),
+                "solid".to_string(),
+            ),
+            filler_gradient_steps: scheduler
+                .new_usize_property(format!("{}/filler_gradient_steps", path).as_str(), 0),
+            filler_header_height: scheduler
+                .new_usize_property(format!("{}/filler_header_height", path).as_str(), 0),
+            filler_header_symbol: scheduler.new_string_property(
+                format!("{}/filler_header_symbol", path).as_str(),
+                " ".to_string(),
+            ),
+            fill_behind_children: scheduler
+                .new_bool_property(format!("{}/fill_behind_children", path).as_str(), true),
+            slot: scheduler.new_string_property(format!("{}/slot", path).as_str(), String::new()),
+            align_baseline: scheduler
+                .new_bool_property(format!("{}/align_baseline", path).as_str(), false),
+            collapsible_on_small: scheduler
+                .new_bool_property(format!("{}/collapsible_on_small", path).as_str(), false),
+            min_terminal_width: scheduler
+                .new_usize_property(format!("{}/min_terminal_width", path).as_str(), 0),
+            min_terminal_height: scheduler
+                .new_usize_property(format!("{}/min_terminal_height", path).as_str(), 0),
+            movable: scheduler.new_bool_property(format!("{}/movable", path).as_str(), false),
+            resizable: scheduler.new_bool_property(format!("{}/resizable", path).as_str(), false),
+            window_buttons: scheduler
+                .new_bool_property(format!("{}/window_buttons", path).as_str(), false),
+            minimized: false,
+            maximized: false,
+            restore_geometry: None,
+            window_drag: None,
             view_size: scheduler.new_usize_property(format!("{}/view_size", path).as_str(), 0),
             view_page: scheduler.new_usize_property(format!("{}/view_page", path).as_str(), 1),
             scrolling_config: ScrollingConfig::new(false, false, 0.0, 0.0, path.clone(), scheduler),
             border_config: BorderConfig::new(false, path.clone(), scheduler),
             colors: ColorConfig::new(path.clone(), scheduler),
             can_drag: scheduler.new_bool_property(format!("{}/can_drag", path).as_str(), true),
-            open_modal: None,
+            stripe_children: scheduler
+                .new_color_property(format!("{}/stripe_children", path).as_str(), Color::Reset),
+            highlight_child_on_hover: scheduler
+                .new_bool_property(format!("{}/highlight_child_on_hover", path).as_str(), false),
+            focus_wrap: scheduler.new_bool_property(format!("{}/focus_wrap", path).as_str(), true),
+            busy: scheduler.new_bool_property(format!("{}/busy", path).as_str(), false),
+            busy_text: scheduler
+                .new_string_property(format!("{}/busy_text", path).as_str(), String::new()),
+            busy_frame: 0,
+            hovered_child: String::new(),
+            open_modals: Vec::new(),
+            modal_selections: Vec::new(),
             templates: HashMap::new(),
             disabled: scheduler.new_bool_property(format!("{}/disabled", path).as_str(), false),
             selected: false,
+            hovered: false,
+            held: false,
             selection_order: scheduler
                 .new_usize_property(format!("{}/selection_order", path).as_str(), 0),
+            collapsed: scheduler.new_bool_property(format!("{}/collapsed", path).as_str(), false),
+            dock: scheduler.new_dock_position_property(
+                format!("{}/dock", path).as_str(),
+                DockPosition::Center,
+            ),
+            weight: scheduler.new_f64_property(format!("{}/weight", path).as_str(), 0.0),
+            z_index: scheduler.new_usize_property(format!("{}/z_index", path).as_str(), 0),
+            anchor: AnchorConfig::new(path.clone(), scheduler),
         }
     }
 }
@@ -191,6 +371,13 @@ impl GenericState for LayoutState {
             "valign" => EzValues::VerticalAlignment(self.valign.value),
             "disabled" => EzValues::Bool(self.disabled.value),
             "selection_order" => EzValues::Usize(self.selection_order.value),
+            "collapsed" => EzValues::Bool(self.collapsed.value),
+            "dock" => EzValues::DockPosition(self.dock.value),
+            "weight" => EzValues::F64(self.weight.value),
+            "z_index" => EzValues::Usize(self.z_index.value),
+            "anchor" => EzValues::String(self.anchor.anchor.value.clone()),
+            "anchor_offset_x" => EzValues::Usize(self.anchor.anchor_offset_x.value),
+            "anchor_offset_y" => EzValues::Usize(self.anchor.anchor_offset_y.value),
             "border" => EzValues::Bool(self.border_config.border.value),
             "horizontal_symbol" => {
                 EzValues::String(self.border_config.horizontal_symbol.value.to_string())
@@ -216,6 +403,15 @@ impl GenericState for LayoutState {
             "selection_bg_color" => EzValues::Color(self.colors.selection_bg_color.value),
             "disabled_fg_color" => EzValues::Color(self.colors.disabled_fg_color.value),
             "disabled_bg_color" => EzValues::Color(self.colors.disabled_bg_color.value),
+            "auto_colors" => EzValues::Bool(self.colors.auto_colors.value),
+            "auto_contrast" => EzValues::Bool(self.colors.auto_contrast.value),
+            "selection_style" => EzValues::String(self.colors.selection_style.value.clone()),
+            "selection_marker" => EzValues::String(self.colors.selection_marker.value.clone()),
+            "hover_fg_color" => EzValues::Color(self.colors.hover_fg_color.value),
+            "hover_bg_color" => EzValues::Color(self.colors.hover_bg_color.value),
+            "pressed_fg_color" => EzValues::Color(self.colors.pressed_fg_color.value),
+            "pressed_bg_color" => EzValues::Color(self.colors.pressed_bg_color.value),
+            "focused_border" => EzValues::Bool(self.colors.focused_border.value),
             "tab_header_active_fg_color" => {
                 EzValues::Color(self.colors.tab_header_active_fg_color.value)
             }
@@ -226,6 +422,9 @@ impl GenericState for LayoutState {
             "border_bg_color" => EzValues::Color(self.colors.border_bg_color.value),
             "filler_fg_color" => EzValues::Color(self.colors.filler_fg_color.value),
             "filler_bg_color" => EzValues::Color(self.colors.filler_bg_color.value),
+            "filler_alt_bg_color" => EzValues::Color(self.colors.filler_alt_bg_color.value),
+            "filler_header_fg_color" => EzValues::Color(self.colors.filler_header_fg_color.value),
+            "filler_header_bg_color" => EzValues::Color(self.colors.filler_header_bg_color.value),
             "tab_header_fg_color" => EzValues::Color(self.colors.tab_header_fg_color.value),
             "tab_header_bg_color" => EzValues::Color(self.colors.tab_header_bg_color.value),
             "tab_header_border_fg_color" => {
@@ -240,18 +439,39 @@ impl GenericState for LayoutState {
             "active_tab" => EzValues::String(self.active_tab.value.to_string()),
             "tab_name" => EzValues::String(self.tab_name.value.to_string()),
             "can_drag" => EzValues::Bool(self.can_drag.value),
+            "stripe_children" => EzValues::Color(self.stripe_children.value),
+            "highlight_child_on_hover" => EzValues::Bool(self.highlight_child_on_hover.value),
+            "focus_wrap" => EzValues::Bool(self.focus_wrap.value),
+            "busy" => EzValues::Bool(self.busy.value),
+            "busy_text" => EzValues::String(self.busy_text.value.clone()),
             "fill" => EzValues::Bool(self.fill.value),
             "filler_symbol" => EzValues::String(self.filler_symbol.value.to_string()),
+            "filler_pattern" => EzValues::String(self.filler_pattern.value.to_string()),
+            "filler_gradient_steps" => EzValues::Usize(self.filler_gradient_steps.value),
+            "filler_header_height" => EzValues::Usize(self.filler_header_height.value),
+            "filler_header_symbol" => EzValues::String(self.filler_header_symbol.value.to_string()),
+            "fill_behind_children" => EzValues::Bool(self.fill_behind_children.value),
+            "slot" => EzValues::String(self.slot.value.to_string()),
+            "min_terminal_height" => EzValues::Usize(self.min_terminal_height.value),
+            "min_terminal_width" => EzValues::Usize(self.min_terminal_width.value),
+            "collapsible_on_small" => EzValues::Bool(self.collapsible_on_small.value),
+            "align_baseline" => EzValues::Bool(self.align_baseline.value),
+            "movable" => EzValues::Bool(self.movable.value),
+            "resizable" => EzValues::Bool(self.resizable.value),
+            "window_buttons" => EzValues::Bool(self.window_buttons.value),
             "view_size" => EzValues::Usize(self.view_size.value),
             "view_page" => EzValues::Usize(self.view_page.value),
             "scroll_x" => EzValues::Bool(self.scrolling_config.scroll_x.value),
             "scroll_y" => EzValues::Bool(self.scrolling_config.scroll_y.value),
             "scroll_start_x" => EzValues::F64(self.scrolling_config.scroll_start_x.value),
             "scroll_start_y" => EzValues::F64(self.scrolling_config.scroll_start_y.value),
+            "minimap" => EzValues::Bool(self.scrolling_config.minimap.value),
+            "minimap_width" => EzValues::Usize(self.scrolling_config.minimap_width.value),
+            "scroll_step" => EzValues::Usize(self.scrolling_config.scroll_step.value),
             "rows" => EzValues::Usize(self.table_config.rows.value),
             "cols" => EzValues::Usize(self.table_config.cols.value),
-            "rows_default_height" => EzValues::Usize(self.table_config.row_default_height.value),
-            "cols_default_width" => EzValues::Usize(self.table_config.col_default_width.value),
+            "row_default_height" => EzValues::Usize(self.table_config.row_default_height.value),
+            "col_default_width" => EzValues::Usize(self.table_config.col_default_width.value),
             "force_default_row_height" => {
                 EzValues::Bool(self.table_config.force_default_row_height.value)
             }
@@ -281,6 +501,13 @@ impl GenericState for LayoutState {
             "valign" => self.valign.set_from_ez_value(value),
             "disabled" => self.disabled.set_from_ez_value(value),
             "selection_order" => self.selection_order.set_from_ez_value(value),
+            "collapsed" => self.collapsed.set_from_ez_value(value),
+            "dock" => self.dock.set_from_ez_value(value),
+            "weight" => self.weight.set_from_ez_value(value),
+            "z_index" => self.z_index.set_from_ez_value(value),
+            "anchor" => self.anchor.anchor.set_from_ez_value(value),
+            "anchor_offset_x" => self.anchor.anchor_offset_x.set_from_ez_value(value),
+            "anchor_offset_y" => self.anchor.anchor_offset_y.set_from_ez_value(value),
             "border" => self.border_config.border.set_from_ez_value(value),
             "horizontal_symbol" => self
                 .border_config
@@ -303,8 +530,20 @@ impl GenericState for LayoutState {
             "selection_bg_color" => self.colors.selection_bg_color.set_from_ez_value(value),
             "disabled_fg_color" => self.colors.disabled_fg_color.set_from_ez_value(value),
             "disabled_bg_color" => self.colors.disabled_bg_color.set_from_ez_value(value),
+            "auto_colors" => self.colors.auto_colors.set_from_ez_value(value),
+            "auto_contrast" => self.colors.auto_contrast.set_from_ez_value(value),
+            "selection_style" => self.colors.selection_style.set_from_ez_value(value),
+            "selection_marker" => self.colors.selection_marker.set_from_ez_value(value),
+            "hover_fg_color" => self.colors.hover_fg_color.set_from_ez_value(value),
+            "hover_bg_color" => self.colors.hover_bg_color.set_from_ez_value(value),
+            "pressed_fg_color" => self.colors.pressed_fg_color.set_from_ez_value(value),
+            "pressed_bg_color" => self.colors.pressed_bg_color.set_from_ez_value(value),
+            "focused_border" => self.colors.focused_border.set_from_ez_value(value),
             "filler_fg_color" => self.colors.filler_fg_color.set_from_ez_value(value),
             "filler_bg_color" => self.colors.filler_bg_color.set_from_ez_value(value),
+            "filler_alt_bg_color" => self.colors.filler_alt_bg_color.set_from_ez_value(value),
+            "filler_header_fg_color" => self.colors.filler_header_fg_color.set_from_ez_value(value),
+            "filler_header_bg_color" => self.colors.filler_header_bg_color.set_from_ez_value(value),
             "tab_header_fg_color" => self.colors.tab_header_fg_color.set_from_ez_value(value),
             "tab_header_bg_color" => self.colors.tab_header_bg_color.set_from_ez_value(value),
             "tab_header_border_fg_color" => self
@@ -331,12 +570,33 @@ impl GenericState for LayoutState {
             "active_tab" => self.active_tab.set_from_ez_value(value),
             "tab_name" => self.tab_name.set_from_ez_value(value),
             "can_drag" => self.can_drag.set_from_ez_value(value),
+            "stripe_children" => self.stripe_children.set_from_ez_value(value),
+            "highlight_child_on_hover" => self.highlight_child_on_hover.set_from_ez_value(value),
+            "focus_wrap" => self.focus_wrap.set_from_ez_value(value),
+            "busy" => self.busy.set_from_ez_value(value),
+            "busy_text" => self.busy_text.set_from_ez_value(value),
             "fill" => self.fill.set_from_ez_value(value),
             "filler_symbol" => self.filler_symbol.set_from_ez_value(value),
+            "filler_pattern" => self.filler_pattern.set_from_ez_value(value),
+            "filler_gradient_steps" => self.filler_gradient_steps.set_from_ez_value(value),
+            "filler_header_height" => self.filler_header_height.set_from_ez_value(value),
+            "filler_header_symbol" => self.filler_header_symbol.set_from_ez_value(value),
+            "fill_behind_children" => self.fill_behind_children.set_from_ez_value(value),
+            "slot" => self.slot.set_from_ez_value(value),
+            "min_terminal_height" => self.min_terminal_height.set_from_ez_value(value),
+            "min_terminal_width" => self.min_terminal_width.set_from_ez_value(value),
+            "collapsible_on_small" => self.collapsible_on_small.set_from_ez_value(value),
+            "align_baseline" => self.align_baseline.set_from_ez_value(value),
+            "movable" => self.movable.set_from_ez_value(value),
+            "resizable" => self.resizable.set_from_ez_value(value),
+            "window_buttons" => self.window_buttons.set_from_ez_value(value),
             "view_size" => self.view_size.set_from_ez_value(value),
             "view_page" => self.view_page.set_from_ez_value(value),
             "scroll_x" => self.scrolling_config.scroll_x.set_from_ez_value(value),
             "scroll_y" => self.scrolling_config.scroll_y.set_from_ez_value(value),
+            "minimap" => self.scrolling_config.minimap.set_from_ez_value(value),
+            "minimap_width" => self.scrolling_config.minimap_width.set_from_ez_value(value),
+            "scroll_step" => self.scrolling_config.scroll_step.set_from_ez_value(value),
             "scroll_start_x" => self
                 .scrolling_config
                 .scroll_start_x
@@ -416,6 +676,7 @@ impl GenericState for LayoutState {
             } else {
                 0
             }
+            - self.scrolling_config.get_minimap_gutter_width() as isize
             - self.get_padding().get_padding_left() as isize
             - self.get_padding().get_padding_right() as isize;
         let width = if width_result < 0 { 0 } else { width_result };
@@ -447,7 +708,8 @@ impl GenericState for LayoutState {
             1
         } else {
             0
-        } + self.get_padding().get_padding_left()
+        } + self.scrolling_config.get_minimap_gutter_width()
+            + self.get_padding().get_padding_left()
             + self.get_padding().get_padding_right();
         self.get_size_mut().set_width(width + offset);
     }
@@ -546,6 +808,46 @@ impl GenericState for LayoutState {
         self.selection_order.set(order);
     }
 
+    fn get_collapsed(&self) -> bool {
+        self.collapsed.value
+    }
+
+    fn set_collapsed(&mut self, collapsed: bool) {
+        self.collapsed.set(collapsed);
+    }
+
+    fn get_dock(&self) -> DockPosition {
+        self.dock.value
+    }
+
+    fn set_dock(&mut self, dock: DockPosition) {
+        self.dock.set(dock);
+    }
+
+    fn get_weight(&self) -> f64 {
+        self.weight.value
+    }
+
+    fn set_weight(&mut self, weight: f64) {
+        self.weight.set(weight);
+    }
+
+    fn get_z_index(&self) -> usize {
+        self.z_index.value
+    }
+
+    fn set_z_index(&mut self, z_index: usize) {
+        self.z_index.set(z_index);
+    }
+
+    fn get_anchor_config(&self) -> &AnchorConfig {
+        &self.anchor
+    }
+
+    fn get_anchor_config_mut(&mut self) -> &mut AnchorConfig {
+        &mut self.anchor
+    }
+
     fn set_selected(&mut self, state: bool) {
         self.selected = state;
     }
@@ -554,6 +856,22 @@ impl GenericState for LayoutState {
         self.selected
     }
 
+    fn set_hovered(&mut self, state: bool) {
+        self.hovered = state;
+    }
+
+    fn get_hovered(&self) -> bool {
+        self.hovered
+    }
+
+    fn set_held(&mut self, state: bool) {
+        self.held = state;
+    }
+
+    fn get_held(&self) -> bool {
+        self.held
+    }
+
     fn copy_state_values(&mut self, other: EzState) {
         let other = other.as_layout();
         self.position.x.copy_from(&other.position.x);
@@ -597,6 +915,17 @@ impl GenericState for LayoutState {
         self.valign.copy_from(&other.valign);
         self.disabled.copy_from(&other.disabled);
         self.selection_order.copy_from(&other.selection_order);
+        self.collapsed.copy_from(&other.collapsed);
+        self.dock.copy_from(&other.dock);
+        self.weight.copy_from(&other.weight);
+        self.z_index.copy_from(&other.z_index);
+        self.anchor.anchor.copy_from(&other.anchor.anchor);
+        self.anchor
+            .anchor_offset_x
+            .copy_from(&other.anchor.anchor_offset_x);
+        self.anchor
+            .anchor_offset_y
+            .copy_from(&other.anchor.anchor_offset_y);
         self.border_config
             .border
             .copy_from(&other.border_config.border);
@@ -632,6 +961,31 @@ impl GenericState for LayoutState {
         self.colors
             .disabled_bg_color
             .copy_from(&other.colors.disabled_bg_color);
+        self.colors.auto_colors.copy_from(&other.colors.auto_colors);
+        self.colors
+            .auto_contrast
+            .copy_from(&other.colors.auto_contrast);
+        self.colors
+            .selection_style
+            .copy_from(&other.colors.selection_style);
+        self.colors
+            .selection_marker
+            .copy_from(&other.colors.selection_marker);
+        self.colors
+            .hover_fg_color
+            .copy_from(&other.colors.hover_fg_color);
+        self.colors
+            .hover_bg_color
+            .copy_from(&other.colors.hover_bg_color);
+        self.colors
+            .pressed_fg_color
+            .copy_from(&other.colors.pressed_fg_color);
+        self.colors
+            .pressed_bg_color
+            .copy_from(&other.colors.pressed_bg_color);
+        self.colors
+            .focused_border
+            .copy_from(&other.colors.focused_border);
         self.colors
             .border_fg_color
             .copy_from(&other.colors.border_fg_color);
@@ -647,8 +1001,31 @@ impl GenericState for LayoutState {
         self.active_screen.copy_from(&other.active_screen);
         self.tab_name.copy_from(&other.tab_name);
         self.can_drag.copy_from(&other.can_drag);
+        self.stripe_children.copy_from(&other.stripe_children);
+        self.highlight_child_on_hover
+            .copy_from(&other.highlight_child_on_hover);
+        self.focus_wrap.copy_from(&other.focus_wrap);
+        self.busy.copy_from(&other.busy);
+        self.busy_text.copy_from(&other.busy_text);
         self.fill.copy_from(&other.fill);
         self.filler_symbol.copy_from(&other.filler_symbol);
+        self.filler_pattern.copy_from(&other.filler_pattern);
+        self.filler_gradient_steps
+            .copy_from(&other.filler_gradient_steps);
+        self.filler_header_height
+            .copy_from(&other.filler_header_height);
+        self.filler_header_symbol
+            .copy_from(&other.filler_header_symbol);
+        self.fill_behind_children
+            .copy_from(&other.fill_behind_children);
+        self.slot.copy_from(&other.slot);
+        self.align_baseline.copy_from(&other.align_baseline);
+        self.collapsible_on_small.copy_from(&other.collapsible_on_small);
+        self.min_terminal_width.copy_from(&other.min_terminal_width);
+        self.min_terminal_height.copy_from(&other.min_terminal_height);
+        self.movable.copy_from(&other.movable);
+        self.resizable.copy_from(&other.resizable);
+        self.window_buttons.copy_from(&other.window_buttons);
         self.view_size.copy_from(&other.view_size);
         self.view_page.copy_from(&other.view_page);
         self.scrolling_config
@@ -663,6 +1040,15 @@ impl GenericState for LayoutState {
         self.scrolling_config
             .scroll_start_y
             .copy_from(&other.scrolling_config.scroll_start_y);
+        self.scrolling_config
+            .minimap
+            .copy_from(&other.scrolling_config.minimap);
+        self.scrolling_config
+            .minimap_width
+            .copy_from(&other.scrolling_config.minimap_width);
+        self.scrolling_config
+            .scroll_step
+            .copy_from(&other.scrolling_config.scroll_step);
         self.table_config.rows.copy_from(&other.table_config.rows);
         self.table_config.cols.copy_from(&other.table_config.cols);
         self.table_config
@@ -694,13 +1080,37 @@ impl GenericState for LayoutState {
         clean_up_property(scheduler, &self.active_tab.name);
         clean_up_property(scheduler, &self.tab_name.name);
         clean_up_property(scheduler, &self.can_drag.name);
+        clean_up_property(scheduler, &self.stripe_children.name);
+        clean_up_property(scheduler, &self.highlight_child_on_hover.name);
+        clean_up_property(scheduler, &self.focus_wrap.name);
+        clean_up_property(scheduler, &self.busy.name);
+        clean_up_property(scheduler, &self.busy_text.name);
         clean_up_property(scheduler, &self.view_size.name);
         clean_up_property(scheduler, &self.view_page.name);
         clean_up_property(scheduler, &self.fill.name);
         clean_up_property(scheduler, &self.filler_symbol.name);
+        clean_up_property(scheduler, &self.filler_pattern.name);
+        clean_up_property(scheduler, &self.filler_gradient_steps.name);
+        clean_up_property(scheduler, &self.filler_header_height.name);
+        clean_up_property(scheduler, &self.filler_header_symbol.name);
+        clean_up_property(scheduler, &self.fill_behind_children.name);
+        clean_up_property(scheduler, &self.slot.name);
+        clean_up_property(scheduler, &self.align_baseline.name);
+        clean_up_property(scheduler, &self.collapsible_on_small.name);
+        clean_up_property(scheduler, &self.min_terminal_width.name);
+        clean_up_property(scheduler, &self.min_terminal_height.name);
+        clean_up_property(scheduler, &self.movable.name);
+        clean_up_property(scheduler, &self.resizable.name);
+        clean_up_property(scheduler, &self.window_buttons.name);
         clean_up_property(scheduler, &self.disabled.name);
         clean_up_property(scheduler, &self.selection_order.name);
+        clean_up_property(scheduler, &self.collapsed.name);
+        clean_up_property(scheduler, &self.dock.name);
+        clean_up_property(scheduler, &self.weight.name);
+        clean_up_property(scheduler, &self.z_index.name);
+        self.anchor.clean_up_properties(scheduler);
         self.border_config.clean_up_properties(scheduler);
+        self.scrolling_config.clean_up_properties(scheduler);
         self.colors.clean_up_properties(scheduler);
     }
 }
@@ -774,6 +1184,16 @@ impl LayoutState {
         self.selected_tab_header.clone()
     }
 
+    /// Set what is currently being dragged in this dock layout
+    pub fn set_dock_drag(&mut self, drag: Option<DockDrag>) {
+        self.dock_drag = drag;
+    }
+
+    /// Get what is currently being dragged in this dock layout
+    pub fn get_dock_drag(&self) -> Option<DockDrag> {
+        self.dock_drag.clone()
+    }
+
     /// Set the [ScrollingConfig] active for this layout
     pub fn set_scrolling_config(&mut self, config: ScrollingConfig) {
         self.scrolling_config = config;
@@ -819,20 +1239,239 @@ impl LayoutState {
         self.filler_symbol.value.clone()
     }
 
-    /// Open a popup based on a template defined in the Ez file. Returns the state of the new popup
+    /// Set [filler_pattern]
+    pub fn set_filler_pattern(&mut self, pattern: FillerPattern) {
+        self.filler_pattern.set(pattern.get_name().to_string());
+    }
+
+    /// Get [filler_pattern]
+    pub fn get_filler_pattern(&self) -> FillerPattern {
+        parse_filler_pattern_property(&self.filler_pattern.value).unwrap_or(FillerPattern::Solid)
+    }
+
+    /// Set [filler_gradient_steps]
+    pub fn set_filler_gradient_steps(&mut self, steps: usize) {
+        self.filler_gradient_steps.set(steps);
+    }
+
+    /// Get [filler_gradient_steps]
+    pub fn get_filler_gradient_steps(&self) -> usize {
+        self.filler_gradient_steps.value
+    }
+
+    /// Set [filler_header_height]
+    pub fn set_filler_header_height(&mut self, height: usize) {
+        self.filler_header_height.set(height);
+    }
+
+    /// Get [filler_header_height]
+    pub fn get_filler_header_height(&self) -> usize {
+        self.filler_header_height.value
+    }
+
+    /// Set [filler_header_symbol]
+    pub fn set_filler_header_symbol(&mut self, symbol: String) {
+        self.filler_header_symbol.set(symbol);
+    }
+
+    /// Get [filler_header_symbol]
+    pub fn get_filler_header_symbol(&self) -> String {
+        self.filler_header_symbol.value.clone()
+    }
+
+    /// Set [fill_behind_children]
+    pub fn set_fill_behind_children(&mut self, enable: bool) {
+        self.fill_behind_children.set(enable);
+    }
+
+    /// Get [fill_behind_children]
+    pub fn get_fill_behind_children(&self) -> bool {
+        self.fill_behind_children.value
+    }
+
+    /// Set [slot]
+    pub fn set_slot(&mut self, slot: String) {
+        self.slot.set(slot);
+    }
+
+    /// Get [slot]
+    pub fn get_slot(&self) -> String {
+        self.slot.value.clone()
+    }
+
+    /// Set [align_baseline]
+    pub fn set_align_baseline(&mut self, enable: bool) {
+        self.align_baseline.set(enable);
+    }
+
+    /// Get [align_baseline]
+    pub fn get_align_baseline(&self) -> bool {
+        self.align_baseline.value
+    }
+
+    /// Set [min_terminal_width]
+    pub fn set_min_terminal_width(&mut self, width: usize) {
+        self.min_terminal_width.set(width);
+    }
+
+    /// Get [min_terminal_width]
+    pub fn get_min_terminal_width(&self) -> usize {
+        self.min_terminal_width.value
+    }
+
+    /// Set [min_terminal_height]
+    pub fn set_min_terminal_height(&mut self, height: usize) {
+        self.min_terminal_height.set(height);
+    }
+
+    /// Get [min_terminal_height]
+    pub fn get_min_terminal_height(&self) -> usize {
+        self.min_terminal_height.value
+    }
+
+    /// Set [collapsible_on_small]
+    pub fn set_collapsible_on_small(&mut self, enable: bool) {
+        self.collapsible_on_small.set(enable);
+    }
+
+    /// Get [collapsible_on_small]
+    pub fn get_collapsible_on_small(&self) -> bool {
+        self.collapsible_on_small.value
+    }
+
+    /// Set [movable]
+    pub fn set_movable(&mut self, movable: bool) {
+        self.movable.set(movable);
+    }
+
+    /// Get [movable]
+    pub fn get_movable(&self) -> bool {
+        self.movable.value
+    }
+
+    /// Set [resizable]
+    pub fn set_resizable(&mut self, resizable: bool) {
+        self.resizable.set(resizable);
+    }
+
+    /// Get [resizable]
+    pub fn get_resizable(&self) -> bool {
+        self.resizable.value
+    }
+
+    /// Set [window_buttons]
+    pub fn set_window_buttons(&mut self, enable: bool) {
+        self.window_buttons.set(enable);
+    }
+
+    /// Get [window_buttons]
+    pub fn get_window_buttons(&self) -> bool {
+        self.window_buttons.value
+    }
+
+    /// Minimize this layout to its' title bar, or restore it if it was already minimized. Only
+    /// works if the parent is a float layout.
+    pub fn toggle_minimized(&mut self) {
+        if self.minimized {
+            self.restore_window_geometry();
+            self.minimized = false;
+        } else {
+            if !self.maximized {
+                self.store_window_geometry();
+            }
+            self.maximized = false;
+            self.minimized = true;
+            self.set_size_hint_y(None);
+            self.set_height(1);
+        }
+    }
+
+    /// Get whether this layout is minimized to its' title bar
+    pub fn get_minimized(&self) -> bool {
+        self.minimized
+    }
+
+    /// Maximize this layout to the effective size of its' parent, or restore it if it was
+    /// already maximized. Only works if the parent is a float layout.
+    pub fn toggle_maximized(&mut self, parent_size: Size) {
+        if self.maximized {
+            self.restore_window_geometry();
+            self.maximized = false;
+        } else {
+            if !self.minimized {
+                self.store_window_geometry();
+            }
+            self.minimized = false;
+            self.maximized = true;
+            self.set_pos_hint_x(None);
+            self.set_pos_hint_y(None);
+            self.set_size_hint_x(None);
+            self.set_size_hint_y(None);
+            self.set_x(0);
+            self.set_y(0);
+            self.set_width(parent_size.width);
+            self.set_height(parent_size.height);
+        }
+    }
+
+    /// Get whether this layout is maximized to the size of its' parent
+    pub fn get_maximized(&self) -> bool {
+        self.maximized
+    }
+
+    fn store_window_geometry(&mut self) {
+        self.restore_geometry = Some((
+            self.get_position().get_x(),
+            self.get_position().get_y(),
+            self.get_size().get_width(),
+            self.get_size().get_height(),
+        ));
+    }
+
+    fn restore_window_geometry(&mut self) {
+        if let Some((x, y, width, height)) = self.restore_geometry.take() {
+            self.set_x(x);
+            self.set_y(y);
+            self.set_width(width);
+            self.set_height(height);
+        }
+    }
+
+    /// Set what is currently being dragged if this layout is a movable or resizable window
+    pub fn set_window_drag(&mut self, drag: Option<(WindowDrag, IsizeCoordinates)>) {
+        self.window_drag = drag;
+    }
+
+    /// Get what is currently being dragged if this layout is a movable or resizable window
+    pub fn get_window_drag(&self) -> Option<(WindowDrag, IsizeCoordinates)> {
+        self.window_drag
+    }
+
+    /// Open a popup based on a template defined in the Ez file, on top of any open modals. The
+    /// selection is the path of the currently selected widget (empty if none), which is selected
+    /// again when the popup is dismissed. Returns the state of the new popup
     pub fn open_modal_from_template(
         &mut self,
         template: String,
+        selection: String,
         scheduler: &mut SchedulerFrontend,
     ) -> Vec<(String, EzState)> {
         let mut popup = self.templates.get(&template).unwrap().clone();
-        let config = vec!["id: modal".to_string()];
-        let init_popup = popup.parse(scheduler, "/root".to_string(), 0, Some(config));
-        self.open_modal(init_popup)
+        let config = vec![format!("id: {}", self.get_next_modal_id()).into()];
+        let init_popup = popup
+            .parse(scheduler, "/root".to_string(), 0, Some(config))
+            .unwrap_or_else(|e| panic!("Could not open modal \"{}\": {}", template, e));
+        self.open_modal(init_popup, selection)
     }
 
-    /// Open a new modal. Returns the state of the new modal.
-    pub fn open_modal(&mut self, mut modal: EzObjects) -> Vec<(String, EzState)> {
+    /// Open a new modal on top of any open modals. The selection is the path of the currently
+    /// selected widget (empty if none), which is selected again when the modal is dismissed.
+    /// Returns the state of the new modal.
+    pub fn open_modal(
+        &mut self,
+        mut modal: EzObjects,
+        selection: String,
+    ) -> Vec<(String, EzState)> {
         // State tree must be appended with the new states
         let mut extra_state_tree = Vec::new();
         if let EzObjects::Layout(ref mut i) = modal {
@@ -846,17 +1485,21 @@ impl LayoutState {
         } else {
             panic!("Modal must be spawned from a Layout (template)")
         }
-        self.open_modal = Some(Box::new(modal));
+        self.open_modals.push(modal);
+        self.modal_selections.push(selection);
         extra_state_tree.reverse();
         extra_state_tree
     }
 
-    /// Dismiss the current modal
-    pub fn dismiss_modal(&mut self, scheduler: &mut SchedulerFrontend) {
-        self.open_modal = None;
+    /// Dismiss the modal on top. Returns the path of the widget that was selected when it was
+    /// opened, empty if none (or if no modal was open).
+    pub fn dismiss_modal(&mut self, scheduler: &mut SchedulerFrontend) -> String {
+        self.open_modals.pop();
+        let selection = self.modal_selections.pop().unwrap_or_default();
         self.update(scheduler);
         scheduler.deselect_widget();
         scheduler.force_redraw();
+        selection
     }
 
     pub fn set_view_size(&mut self, start: usize) {
@@ -883,18 +1526,141 @@ impl LayoutState {
         self.can_drag.value
     }
 
+    /// Set [stripe_children]
+    pub fn set_stripe_children(&mut self, color: Color) {
+        self.stripe_children.set(color);
+    }
+
+    /// Get [stripe_children]
+    pub fn get_stripe_children(&self) -> Color {
+        self.stripe_children.value
+    }
+
+    /// Set [highlight_child_on_hover]
+    pub fn set_highlight_child_on_hover(&mut self, enable: bool) {
+        self.highlight_child_on_hover.set(enable);
+    }
+
+    /// Get [highlight_child_on_hover]
+    pub fn get_highlight_child_on_hover(&self) -> bool {
+        self.highlight_child_on_hover.value
+    }
+
+    /// Set [focus_wrap]
+    pub fn set_focus_wrap(&mut self, enable: bool) {
+        self.focus_wrap.set(enable);
+    }
+
+    /// Get [focus_wrap]
+    pub fn get_focus_wrap(&self) -> bool {
+        self.focus_wrap.value
+    }
+
+    /// Set [busy]. To keep a layout busy while a background task runs, use
+    /// [SchedulerFrontend.schedule_threaded_busy] instead, which clears it when the task is done.
+    pub fn set_busy(&mut self, busy: bool) {
+        self.busy.set(busy);
+    }
+
+    /// Get [busy]
+    pub fn get_busy(&self) -> bool {
+        self.busy.value
+    }
+
+    /// Set [busy_text]
+    pub fn set_busy_text(&mut self, text: &str) {
+        self.busy_text.set(text.to_string());
+    }
+
+    /// Get [busy_text]
+    pub fn get_busy_text(&self) -> String {
+        self.busy_text.value.clone()
+    }
+
+    /// Set the index of the spinner frame shown while busy. Used internally to animate the
+    /// spinner.
+    pub fn set_busy_frame(&mut self, frame: usize) {
+        self.busy_frame = frame;
+    }
+
+    /// Get the index of the spinner frame shown while busy.
+    pub fn get_busy_frame(&self) -> usize {
+        self.busy_frame
+    }
+
+    /// Set the path of the child under the mouse cursor. Empty if no child is hovered.
+    pub fn set_hovered_child(&mut self, path: String) {
+        self.hovered_child = path;
+    }
+
+    /// Get the path of the child under the mouse cursor. Empty if no child is hovered.
+    pub fn get_hovered_child(&self) -> &String {
+        &self.hovered_child
+    }
+
+    /// Move a child of this layout to a different position among its' siblings on the next frame.
+    pub fn move_child(&self, id: &str, new_index: usize, scheduler: &mut SchedulerFrontend) {
+        scheduler.move_widget(
+            format!("{}/{}", self.path, id).as_str(),
+            &self.path,
+            Some(new_index),
+        );
+    }
+
+    /// Swap the positions of two children of this layout on the next frame.
+    pub fn swap_children(&self, child_1: &str, child_2: &str, scheduler: &mut SchedulerFrontend) {
+        scheduler.swap_children(&self.path, child_1, child_2);
+    }
+
     pub fn has_modal(&self) -> bool {
-        self.open_modal.is_some()
+        !self.open_modals.is_empty()
     }
 
-    /// Get reference to all open modals
+    /// Get reference to the modal on top
     pub fn get_modal(&self) -> &EzObjects {
-        self.open_modal.as_ref().unwrap()
+        self.open_modals.last().unwrap()
     }
 
-    /// Get reference to all open modals
+    /// Get mutable reference to the modal on top
     pub fn get_modal_mut(&mut self) -> &mut EzObjects {
-        self.open_modal.as_mut().unwrap()
+        self.open_modals.last_mut().unwrap()
+    }
+
+    /// Get reference to all open modals, the modal on top last
+    pub fn get_modals(&self) -> &Vec<EzObjects> {
+        &self.open_modals
+    }
+
+    /// Get reference to an open modal by its' ID (e.g. "modal" or "modal_2")
+    pub fn get_modal_by_id(&self, id: &str) -> Option<&EzObjects> {
+        self.open_modals
+            .iter()
+            .find(|x| x.as_ez_object().get_id() == id)
+    }
+
+    /// Get mutable reference to an open modal by its' ID (e.g. "modal" or "modal_2")
+    pub fn get_modal_by_id_mut(&mut self, id: &str) -> Option<&mut EzObjects> {
+        self.open_modals
+            .iter_mut()
+            .find(|x| x.as_ez_object().get_id() == id)
+    }
+
+    /// Get the ID the next modal is opened with. The first modal is "modal", modals opened on top
+    /// of it are "modal_2", "modal_3", etc.
+    pub fn get_next_modal_id(&self) -> String {
+        if self.open_modals.is_empty() {
+            "modal".to_string()
+        } else {
+            format!("modal_{}", self.open_modals.len() + 1)
+        }
+    }
+
+    /// Bool representing whether an ID is one modals are opened with (see [get_next_modal_id]).
+    pub fn is_modal_id(id: &str) -> bool {
+        id == "modal"
+            || id.strip_prefix("modal_").map_or(false, |x| {
+                !x.is_empty() && x.chars().all(|c| c.is_ascii_digit())
+            })
     }
 
     /// Set templates. Used by [ez_parser] on the root layout to keep a hold of all templates
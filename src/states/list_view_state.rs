@@ -0,0 +1,729 @@
+use crate::property::ez_property::EzProperty;
+use crate::property::ez_values::EzValues;
+use crate::run::definitions::IsizeCoordinates;
+use crate::scheduler::scheduler::SchedulerFrontend;
+use crate::scheduler::scheduler_funcs::clean_up_property;
+use crate::states::definitions::{
+    AnchorConfig, AutoScale, BorderConfig, ColorConfig, DockPosition, HorizontalAlignment,
+    InfiniteSize, Padding, PosHint, SizeHint, StateCoordinates, StateSize, VerticalAlignment,
+};
+use crate::states::ez_state::{EzState, GenericState};
+
+/// [State] implementation for [ListView].
+#[derive(Clone, Debug)]
+pub struct ListViewState {
+    /// Path to the widget to which this state belongs
+    pub path: String,
+
+    /// Whether the view follows new items appended to the end of the list, as long as the last
+    /// item was in view before they were appended. Useful for e.g. logs
+    pub auto_scroll: EzProperty<bool>,
+
+    /// Text of each item. Internal only, use [set_items], [append_item], etc.
+    items: Vec<String>,
+
+    /// Index of the selected item, if any. Internal only
+    selected_item: Option<usize>,
+
+    /// Index of the first visible item. Internal only
+    scroll_y: usize,
+
+    /// Position of this widget relative to its' parent [layout]
+    pub position: StateCoordinates,
+
+    /// Absolute position of this layout on screen. Automatically propagated, do not set manually
+    absolute_position: IsizeCoordinates,
+
+    /// Relative height/width of this widget to parent layout
+    pub size_hint: SizeHint,
+
+    /// Pos hint of this widget
+    pub pos_hint: PosHint,
+
+    /// size of this widget
+    pub size: StateSize,
+
+    /// Infinite size of this widget for x and y axes, used in scrolling
+    infinite_size: InfiniteSize,
+
+    /// Automatically adjust size of widget to content
+    pub auto_scale: AutoScale,
+
+    /// Amount of space to leave between sides of the widget and other widgets
+    pub padding: Padding,
+
+    /// Horizontal alignment of this widget
+    pub halign: EzProperty<HorizontalAlignment>,
+
+    /// Vertical alignment of this widget
+    pub valign: EzProperty<VerticalAlignment>,
+
+    /// [BorderConfig] object that will be used to draw the border if enabled
+    pub border_config: BorderConfig,
+
+    /// Object containing colors to be used by this widget in different situations
+    pub colors: ColorConfig,
+
+    /// Bool representing whether widget is disabled, i.e. cannot be interacted with
+    pub disabled: EzProperty<bool>,
+
+    /// Global order number in which this widget will be selection when user presses down/up keys
+    pub selection_order: EzProperty<usize>,
+
+    /// Whether this widget is collapsed when its' parent is a [LayoutMode::Dock] layout
+    pub collapsed: EzProperty<bool>,
+
+    /// Where this widget is placed when its' parent is a [LayoutMode::Dock] layout
+    pub dock: EzProperty<DockPosition>,
+
+    /// Share of the leftover space this widget gets in a box layout, relative to the weights of
+    /// its' siblings. 0.0 means the widget is not weighted and is sized normally
+    pub weight: EzProperty<f64>,
+
+    /// Order in which overlapping children of a [LayoutMode::Float] layout are drawn; higher
+    /// values are drawn on top. Children with the same z index are drawn in declaration order
+    pub z_index: EzProperty<usize>,
+
+    /// Terminal edge or corner this widget is kept at when its' parent is a [LayoutMode::Float]
+    /// layout, see [Anchor]
+    pub anchor: AnchorConfig,
+
+    /// Bool representing whether this widget is currently selected.
+    selected: bool,
+
+    /// Bool representing whether the mouse is currently over this widget.
+    hovered: bool,
+
+    /// Bool representing whether this widget is currently held down by the mouse.
+    held: bool,
+}
+impl ListViewState {
+    pub fn new(path: String, scheduler: &mut SchedulerFrontend) -> Self {
+        ListViewState {
+            path: path.clone(),
+            auto_scroll: scheduler
+                .new_bool_property(format!("{}/auto_scroll", path).as_str(), false),
+            items: Vec::new(),
+            selected_item: None,
+            scroll_y: 0,
+            position: StateCoordinates::new(0, 0, path.clone(), scheduler),
+            absolute_position: IsizeCoordinates::default(),
+            size: StateSize::new(0, 0, path.clone(), scheduler),
+            infinite_size: InfiniteSize::default(),
+            size_hint: SizeHint::new(Some(1.0), Some(1.0), path.clone(), scheduler),
+            pos_hint: PosHint::new(None, None, path.clone(), scheduler),
+            auto_scale: AutoScale::new(false, false, path.clone(), scheduler),
+            padding: Padding::new(0, 0, 0, 0, path.clone(), scheduler),
+            halign: scheduler.new_horizontal_alignment_property(
+                format!("{}/halign", path).as_str(),
+                HorizontalAlignment::Left,
+            ),
+            valign: scheduler.new_vertical_alignment_property(
+                format!("{}/valign", path).as_str(),
+                VerticalAlignment::Top,
+            ),
+            disabled: scheduler.new_bool_property(format!("{}/disabled", path).as_str(), false),
+            selected: false,
+            hovered: false,
+            held: false,
+            selection_order: scheduler
+                .new_usize_property(format!("{}/selection_order", path).as_str(), 0),
+            collapsed: scheduler.new_bool_property(format!("{}/collapsed", path).as_str(), false),
+            dock: scheduler.new_dock_position_property(
+                format!("{}/dock", path).as_str(),
+                DockPosition::Center,
+            ),
+            weight: scheduler.new_f64_property(format!("{}/weight", path).as_str(), 0.0),
+            z_index: scheduler.new_usize_property(format!("{}/z_index", path).as_str(), 0),
+            anchor: AnchorConfig::new(path.clone(), scheduler),
+            border_config: BorderConfig::new(false, path.clone(), scheduler),
+            colors: ColorConfig::new(path, scheduler),
+        }
+    }
+}
+impl GenericState for ListViewState {
+    fn get_path(&self) -> &String {
+        &self.path
+    }
+
+    fn get_property(&self, name: &str) -> EzValues {
+        match name {
+            "x" => EzValues::Usize(self.position.x.value),
+            "y" => EzValues::Usize(self.position.y.value),
+            "height" => EzValues::Usize(self.size.height.value),
+            "width" => EzValues::Usize(self.size.width.value),
+            "size_hint_x" => EzValues::SizeHint(self.size_hint.size_hint_x.value),
+            "size_hint_y" => EzValues::SizeHint(self.size_hint.size_hint_y.value),
+            "pos_hint_x" => EzValues::HorizontalPosHint(self.pos_hint.pos_hint_x.value),
+            "pos_hint_y" => EzValues::VerticalPosHint(self.pos_hint.pos_hint_y.value),
+            "auto_scale_width" => EzValues::Bool(self.auto_scale.auto_scale_width.value),
+            "auto_scale_height" => EzValues::Bool(self.auto_scale.auto_scale_height.value),
+            "padding_top" => EzValues::Usize(self.padding.padding_top.value),
+            "padding_bottom" => EzValues::Usize(self.padding.padding_bottom.value),
+            "padding_left" => EzValues::Usize(self.padding.padding_left.value),
+            "padding_right" => EzValues::Usize(self.padding.padding_right.value),
+            "halign" => EzValues::HorizontalAlignment(self.halign.value),
+            "valign" => EzValues::VerticalAlignment(self.valign.value),
+            "disabled" => EzValues::Bool(self.disabled.value),
+            "selection_order" => EzValues::Usize(self.selection_order.value),
+            "collapsed" => EzValues::Bool(self.collapsed.value),
+            "dock" => EzValues::DockPosition(self.dock.value),
+            "weight" => EzValues::F64(self.weight.value),
+            "z_index" => EzValues::Usize(self.z_index.value),
+            "anchor" => EzValues::String(self.anchor.anchor.value.clone()),
+            "anchor_offset_x" => EzValues::Usize(self.anchor.anchor_offset_x.value),
+            "anchor_offset_y" => EzValues::Usize(self.anchor.anchor_offset_y.value),
+            "border" => EzValues::Bool(self.border_config.border.value),
+            "horizontal_symbol" => {
+                EzValues::String(self.border_config.horizontal_symbol.value.to_string())
+            }
+            "vertical_symbol" => {
+                EzValues::String(self.border_config.vertical_symbol.value.to_string())
+            }
+            "top_left_symbol" => {
+                EzValues::String(self.border_config.top_left_symbol.value.to_string())
+            }
+            "top_right_symbol" => {
+                EzValues::String(self.border_config.top_right_symbol.value.to_string())
+            }
+            "bottom_left_symbol" => {
+                EzValues::String(self.border_config.bottom_left_symbol.value.to_string())
+            }
+            "bottom_right_symbol" => {
+                EzValues::String(self.border_config.bottom_right_symbol.value.to_string())
+            }
+            "fg_color" => EzValues::Color(self.colors.fg_color.value),
+            "bg_color" => EzValues::Color(self.colors.bg_color.value),
+            "selection_fg_color" => EzValues::Color(self.colors.selection_fg_color.value),
+            "selection_bg_color" => EzValues::Color(self.colors.selection_bg_color.value),
+            "disabled_fg_color" => EzValues::Color(self.colors.disabled_fg_color.value),
+            "disabled_bg_color" => EzValues::Color(self.colors.disabled_bg_color.value),
+            "auto_colors" => EzValues::Bool(self.colors.auto_colors.value),
+            "auto_contrast" => EzValues::Bool(self.colors.auto_contrast.value),
+            "selection_style" => EzValues::String(self.colors.selection_style.value.clone()),
+            "selection_marker" => EzValues::String(self.colors.selection_marker.value.clone()),
+            "hover_fg_color" => EzValues::Color(self.colors.hover_fg_color.value),
+            "hover_bg_color" => EzValues::Color(self.colors.hover_bg_color.value),
+            "pressed_fg_color" => EzValues::Color(self.colors.pressed_fg_color.value),
+            "pressed_bg_color" => EzValues::Color(self.colors.pressed_bg_color.value),
+            "focused_border" => EzValues::Bool(self.colors.focused_border.value),
+            "border_fg_color" => EzValues::Color(self.colors.border_fg_color.value),
+            "border_bg_color" => EzValues::Color(self.colors.border_bg_color.value),
+            "auto_scroll" => EzValues::Bool(self.auto_scroll.value),
+            _ => panic!("Invalid property name for list view state: {}", name),
+        }
+    }
+
+    fn update_property(&mut self, name: &str, value: EzValues) -> bool {
+        match name {
+            "x" => self.position.x.set_from_ez_value(value),
+            "y" => self.position.y.set_from_ez_value(value),
+            "height" => self.size.height.set_from_ez_value(value),
+            "width" => self.size.width.set_from_ez_value(value),
+            "size_hint_x" => self.size_hint.size_hint_x.set_from_ez_value(value),
+            "size_hint_y" => self.size_hint.size_hint_y.set_from_ez_value(value),
+            "pos_hint_x" => self.pos_hint.pos_hint_x.set_from_ez_value(value),
+            "pos_hint_y" => self.pos_hint.pos_hint_y.set_from_ez_value(value),
+            "auto_scale_width" => self.auto_scale.auto_scale_width.set_from_ez_value(value),
+            "auto_scale_height" => self.auto_scale.auto_scale_height.set_from_ez_value(value),
+            "padding_top" => self.padding.padding_top.set_from_ez_value(value),
+            "padding_bottom" => self.padding.padding_bottom.set_from_ez_value(value),
+            "padding_left" => self.padding.padding_left.set_from_ez_value(value),
+            "padding_right" => self.padding.padding_right.set_from_ez_value(value),
+            "halign" => self.halign.set_from_ez_value(value),
+            "valign" => self.valign.set_from_ez_value(value),
+            "disabled" => self.disabled.set_from_ez_value(value),
+            "selection_order" => self.selection_order.set_from_ez_value(value),
+            "collapsed" => self.collapsed.set_from_ez_value(value),
+            "dock" => self.dock.set_from_ez_value(value),
+            "weight" => self.weight.set_from_ez_value(value),
+            "z_index" => self.z_index.set_from_ez_value(value),
+            "anchor" => self.anchor.anchor.set_from_ez_value(value),
+            "anchor_offset_x" => self.anchor.anchor_offset_x.set_from_ez_value(value),
+            "anchor_offset_y" => self.anchor.anchor_offset_y.set_from_ez_value(value),
+            "border" => self.border_config.border.set_from_ez_value(value),
+            "horizontal_symbol" => self
+                .border_config
+                .horizontal_symbol
+                .set_from_ez_value(value),
+            "vertical_symbol" => self.border_config.vertical_symbol.set_from_ez_value(value),
+            "top_left_symbol" => self.border_config.top_left_symbol.set_from_ez_value(value),
+            "top_right_symbol" => self.border_config.top_right_symbol.set_from_ez_value(value),
+            "bottom_left_symbol" => self
+                .border_config
+                .bottom_left_symbol
+                .set_from_ez_value(value),
+            "bottom_right_symbol" => self
+                .border_config
+                .bottom_right_symbol
+                .set_from_ez_value(value),
+            "fg_color" => self.colors.fg_color.set_from_ez_value(value),
+            "bg_color" => self.colors.bg_color.set_from_ez_value(value),
+            "selection_fg_color" => self.colors.selection_fg_color.set_from_ez_value(value),
+            "selection_bg_color" => self.colors.selection_bg_color.set_from_ez_value(value),
+            "disabled_fg_color" => self.colors.disabled_fg_color.set_from_ez_value(value),
+            "disabled_bg_color" => self.colors.disabled_bg_color.set_from_ez_value(value),
+            "auto_colors" => self.colors.auto_colors.set_from_ez_value(value),
+            "auto_contrast" => self.colors.auto_contrast.set_from_ez_value(value),
+            "selection_style" => self.colors.selection_style.set_from_ez_value(value),
+            "selection_marker" => self.colors.selection_marker.set_from_ez_value(value),
+            "hover_fg_color" => self.colors.hover_fg_color.set_from_ez_value(value),
+            "hover_bg_color" => self.colors.hover_bg_color.set_from_ez_value(value),
+            "pressed_fg_color" => self.colors.pressed_fg_color.set_from_ez_value(value),
+            "pressed_bg_color" => self.colors.pressed_bg_color.set_from_ez_value(value),
+            "focused_border" => self.colors.focused_border.set_from_ez_value(value),
+            "border_fg_color" => self.colors.border_fg_color.set_from_ez_value(value),
+            "border_bg_color" => self.colors.border_bg_color.set_from_ez_value(value),
+            "auto_scroll" => self.auto_scroll.set_from_ez_value(value),
+            _ => panic!("Invalid property name for list view state: {}", name),
+        }
+    }
+
+    fn copy_state_values(&mut self, other: EzState) {
+        let other = other.as_list_view();
+        self.position.x.copy_from(&other.position.x);
+        self.position.y.copy_from(&other.position.y);
+        self.size.height.copy_from(&other.size.height);
+        self.size.width.copy_from(&other.size.width);
+        self.size_hint
+            .size_hint_x
+            .copy_from(&other.size_hint.size_hint_x);
+        self.size_hint
+            .size_hint_y
+            .copy_from(&other.size_hint.size_hint_y);
+        self.pos_hint
+            .pos_hint_x
+            .copy_from(&other.pos_hint.pos_hint_x);
+        self.pos_hint
+            .pos_hint_y
+            .copy_from(&other.pos_hint.pos_hint_y);
+        self.auto_scale
+            .auto_scale_width
+            .copy_from(&other.auto_scale.auto_scale_width);
+        self.auto_scale
+            .auto_scale_height
+            .copy_from(&other.auto_scale.auto_scale_height);
+        self.padding
+            .padding_top
+            .copy_from(&other.padding.padding_top);
+        self.padding
+            .padding_bottom
+            .copy_from(&other.padding.padding_bottom);
+        self.padding
+            .padding_left
+            .copy_from(&other.padding.padding_left);
+        self.padding
+            .padding_right
+            .copy_from(&other.padding.padding_right);
+        self.padding
+            .padding_right
+            .copy_from(&other.padding.padding_right);
+        self.halign.copy_from(&other.halign);
+        self.valign.copy_from(&other.valign);
+        self.disabled.copy_from(&other.disabled);
+        self.selection_order.copy_from(&other.selection_order);
+        self.collapsed.copy_from(&other.collapsed);
+        self.dock.copy_from(&other.dock);
+        self.weight.copy_from(&other.weight);
+        self.z_index.copy_from(&other.z_index);
+        self.anchor.anchor.copy_from(&other.anchor.anchor);
+        self.anchor
+            .anchor_offset_x
+            .copy_from(&other.anchor.anchor_offset_x);
+        self.anchor
+            .anchor_offset_y
+            .copy_from(&other.anchor.anchor_offset_y);
+        self.border_config
+            .border
+            .copy_from(&other.border_config.border);
+        self.border_config
+            .horizontal_symbol
+            .copy_from(&other.border_config.horizontal_symbol);
+        self.border_config
+            .vertical_symbol
+            .copy_from(&other.border_config.vertical_symbol);
+        self.border_config
+            .top_left_symbol
+            .copy_from(&other.border_config.top_left_symbol);
+        self.border_config
+            .top_right_symbol
+            .copy_from(&other.border_config.top_right_symbol);
+        self.border_config
+            .bottom_left_symbol
+            .copy_from(&other.border_config.bottom_left_symbol);
+        self.border_config
+            .bottom_right_symbol
+            .copy_from(&other.border_config.bottom_right_symbol);
+        self.colors.fg_color.copy_from(&other.colors.fg_color);
+        self.colors.bg_color.copy_from(&other.colors.bg_color);
+        self.colors
+            .selection_fg_color
+            .copy_from(&other.colors.selection_fg_color);
+        self.colors
+            .selection_bg_color
+            .copy_from(&other.colors.selection_bg_color);
+        self.colors
+            .disabled_fg_color
+            .copy_from(&other.colors.disabled_fg_color);
+        self.colors
+            .disabled_bg_color
+            .copy_from(&other.colors.disabled_bg_color);
+        self.colors.auto_colors.copy_from(&other.colors.auto_colors);
+        self.colors
+            .auto_contrast
+            .copy_from(&other.colors.auto_contrast);
+        self.colors
+            .selection_style
+            .copy_from(&other.colors.selection_style);
+        self.colors
+            .selection_marker
+            .copy_from(&other.colors.selection_marker);
+        self.colors
+            .hover_fg_color
+            .copy_from(&other.colors.hover_fg_color);
+        self.colors
+            .hover_bg_color
+            .copy_from(&other.colors.hover_bg_color);
+        self.colors
+            .pressed_fg_color
+            .copy_from(&other.colors.pressed_fg_color);
+        self.colors
+            .pressed_bg_color
+            .copy_from(&other.colors.pressed_bg_color);
+        self.colors
+            .focused_border
+            .copy_from(&other.colors.focused_border);
+        self.colors
+            .border_fg_color
+            .copy_from(&other.colors.border_fg_color);
+        self.colors
+            .border_bg_color
+            .copy_from(&other.colors.border_bg_color);
+        self.colors
+            .cursor_color
+            .copy_from(&other.colors.cursor_color);
+        self.auto_scroll.copy_from(&other.auto_scroll);
+        self.items = other.items.clone();
+        self.selected_item = other.selected_item;
+    }
+
+    fn get_size_hint(&self) -> &SizeHint {
+        &self.size_hint
+    }
+
+    fn get_size_hint_mut(&mut self) -> &mut SizeHint {
+        &mut self.size_hint
+    }
+
+    fn get_pos_hint(&self) -> &PosHint {
+        &self.pos_hint
+    }
+
+    fn get_pos_hint_mut(&mut self) -> &mut PosHint {
+        &mut self.pos_hint
+    }
+
+    fn get_auto_scale(&self) -> &AutoScale {
+        &self.auto_scale
+    }
+
+    fn get_auto_scale_mut(&mut self) -> &mut AutoScale {
+        &mut self.auto_scale
+    }
+
+    fn get_size(&self) -> &StateSize {
+        &self.size
+    }
+
+    fn get_size_mut(&mut self) -> &mut StateSize {
+        &mut self.size
+    }
+
+    fn get_infinite_size(&self) -> &InfiniteSize {
+        &self.infinite_size
+    }
+
+    fn get_infinite_size_mut(&mut self) -> &mut InfiniteSize {
+        &mut self.infinite_size
+    }
+
+    fn get_position(&self) -> &StateCoordinates {
+        &self.position
+    }
+
+    fn get_position_mut(&mut self) -> &mut StateCoordinates {
+        &mut self.position
+    }
+
+    fn set_absolute_position(&mut self, pos: IsizeCoordinates) {
+        self.absolute_position = pos
+    }
+
+    fn get_absolute_position(&self) -> IsizeCoordinates {
+        self.absolute_position
+    }
+
+    fn set_halign(&mut self, alignment: HorizontalAlignment) {
+        self.halign.set(alignment);
+    }
+
+    fn get_halign(&self) -> HorizontalAlignment {
+        self.halign.value
+    }
+
+    fn set_valign(&mut self, alignment: VerticalAlignment) {
+        self.valign.set(alignment);
+    }
+
+    fn get_valign(&self) -> VerticalAlignment {
+        self.valign.value
+    }
+
+    fn get_padding(&self) -> &Padding {
+        &self.padding
+    }
+
+    fn get_padding_mut(&mut self) -> &mut Padding {
+        &mut self.padding
+    }
+
+    fn get_border_config(&self) -> &BorderConfig {
+        &self.border_config
+    }
+
+    fn get_border_config_mut(&mut self) -> &mut BorderConfig {
+        &mut self.border_config
+    }
+
+    fn get_color_config(&self) -> &ColorConfig {
+        &self.colors
+    }
+
+    fn get_color_config_mut(&mut self) -> &mut ColorConfig {
+        &mut self.colors
+    }
+
+    fn is_selectable(&self) -> bool {
+        true
+    }
+
+    fn set_disabled(&mut self, disabled: bool) {
+        self.disabled.set(disabled);
+    }
+
+    fn get_disabled(&self) -> bool {
+        self.disabled.value
+    }
+
+    fn get_selection_order(&self) -> usize {
+        self.selection_order.value
+    }
+
+    fn set_selection_order(&mut self, order: usize) {
+        self.selection_order.set(order);
+    }
+
+    fn get_collapsed(&self) -> bool {
+        self.collapsed.value
+    }
+
+    fn set_collapsed(&mut self, collapsed: bool) {
+        self.collapsed.set(collapsed);
+    }
+
+    fn get_dock(&self) -> DockPosition {
+        self.dock.value
+    }
+
+    fn set_dock(&mut self, dock: DockPosition) {
+        self.dock.set(dock);
+    }
+
+    fn get_weight(&self) -> f64 {
+        self.weight.value
+    }
+
+    fn set_weight(&mut self, weight: f64) {
+        self.weight.set(weight);
+    }
+
+    fn get_z_index(&self) -> usize {
+        self.z_index.value
+    }
+
+    fn set_z_index(&mut self, z_index: usize) {
+        self.z_index.set(z_index);
+    }
+
+    fn get_anchor_config(&self) -> &AnchorConfig {
+        &self.anchor
+    }
+
+    fn get_anchor_config_mut(&mut self) -> &mut AnchorConfig {
+        &mut self.anchor
+    }
+
+    fn set_selected(&mut self, state: bool) {
+        self.selected = state;
+    }
+
+    fn get_selected(&self) -> bool {
+        self.selected
+    }
+
+    fn set_hovered(&mut self, state: bool) {
+        self.hovered = state;
+    }
+
+    fn get_hovered(&self) -> bool {
+        self.hovered
+    }
+
+    fn set_held(&mut self, state: bool) {
+        self.held = state;
+    }
+
+    fn get_held(&self) -> bool {
+        self.held
+    }
+
+    fn clean_up_properties(&self, scheduler: &mut SchedulerFrontend) {
+        self.position.clean_up_properties(scheduler);
+        self.size.clean_up_properties(scheduler);
+        self.size_hint.clean_up_properties(scheduler);
+        self.pos_hint.clean_up_properties(scheduler);
+        self.auto_scale.clean_up_properties(scheduler);
+        self.padding.clean_up_properties(scheduler);
+        clean_up_property(scheduler, &self.halign.name);
+        clean_up_property(scheduler, &self.valign.name);
+        clean_up_property(scheduler, &self.auto_scroll.name);
+        clean_up_property(scheduler, &self.disabled.name);
+        clean_up_property(scheduler, &self.selection_order.name);
+        clean_up_property(scheduler, &self.collapsed.name);
+        clean_up_property(scheduler, &self.dock.name);
+        clean_up_property(scheduler, &self.weight.name);
+        clean_up_property(scheduler, &self.z_index.name);
+        self.anchor.clean_up_properties(scheduler);
+        self.border_config.clean_up_properties(scheduler);
+        self.colors.clean_up_properties(scheduler);
+    }
+}
+impl ListViewState {
+    pub fn set_auto_scroll(&mut self, auto_scroll: bool) {
+        self.auto_scroll.set(auto_scroll);
+    }
+
+    pub fn get_auto_scroll(&self) -> bool {
+        self.auto_scroll.value
+    }
+
+    /// Replace all items. The selection is cleared.
+    pub fn set_items(&mut self, items: Vec<String>) {
+        self.items = items;
+        self.selected_item = None;
+        self.scroll_y = self.scroll_y.min(self.get_max_scroll_y());
+    }
+
+    pub fn get_items(&self) -> &Vec<String> {
+        &self.items
+    }
+
+    pub fn get_item_count(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Add an item to the end of the list. If [auto_scroll] is enabled and the last item was in
+    /// view, the view moves along to show the new item.
+    pub fn append_item(&mut self, item: String) {
+        self.append_items(vec![item]);
+    }
+
+    /// Add items to the end of the list. Appending many items at once is cheaper than appending
+    /// them one by one. If [auto_scroll] is enabled and the last item was in view, the view moves
+    /// along to show the new items.
+    pub fn append_items(&mut self, mut items: Vec<String>) {
+        let follow = self.get_auto_scroll() && self.scroll_y >= self.get_max_scroll_y();
+        self.items.append(&mut items);
+        if follow {
+            self.scroll_y = self.get_max_scroll_y();
+        }
+    }
+
+    /// Insert an item at an index, moving the items after it down. The selected item stays
+    /// selected. Panics if the index is past the end of the list.
+    pub fn insert_item(&mut self, index: usize, item: String) {
+        if index > self.items.len() {
+            panic!(
+                "Cannot insert item at index {} of list view {}, it has {} items",
+                index,
+                self.path,
+                self.items.len()
+            );
+        }
+        self.items.insert(index, item);
+        match self.selected_item {
+            Some(i) if i >= index => self.selected_item = Some(i + 1),
+            _ => (),
+        }
+    }
+
+    /// Remove the item at an index and return it. If the removed item was selected the selection
+    /// is cleared. Panics if there is no item at the index.
+    pub fn remove_item(&mut self, index: usize) -> String {
+        if index >= self.items.len() {
+            panic!(
+                "Cannot remove item at index {} of list view {}, it has {} items",
+                index,
+                self.path,
+                self.items.len()
+            );
+        }
+        let item = self.items.remove(index);
+        self.selected_item = match self.selected_item {
+            Some(i) if i == index => None,
+            Some(i) if i > index => Some(i - 1),
+            other => other,
+        };
+        self.scroll_y = self.scroll_y.min(self.get_max_scroll_y());
+        item
+    }
+
+    /// Remove all items.
+    pub fn clear_items(&mut self) {
+        self.set_items(Vec::new());
+    }
+
+    /// Select an item by its' index, or clear the selection with None. Indices past the last item
+    /// are ignored.
+    pub fn set_selected_item(&mut self, item: Option<usize>) {
+        match item {
+            Some(i) if i >= self.items.len() => (),
+            _ => self.selected_item = item,
+        }
+    }
+
+    /// Get the index of the selected item, if an item is selected.
+    pub fn get_selected_item(&self) -> Option<usize> {
+        self.selected_item
+    }
+
+    /// Get the text of the selected item, if an item is selected.
+    pub fn get_selected_item_value(&self) -> Option<String> {
+        self.selected_item.map(|i| self.items[i].clone())
+    }
+
+    pub fn set_scroll_y(&mut self, scroll_y: usize) {
+        self.scroll_y = scroll_y;
+    }
+
+    pub fn get_scroll_y(&self) -> usize {
+        self.scroll_y
+    }
+
+    /// Get the index of the first visible item when the view is scrolled to the end of the list.
+    pub fn get_max_scroll_y(&self) -> usize {
+        self.items
+            .len()
+            .saturating_sub(self.get_effective_size().height.max(1))
+    }
+}
@@ -1,12 +1,21 @@
 pub mod button_state;
 pub mod canvas_state;
 pub mod checkbox_state;
+pub mod data_grid_state;
+pub mod colors;
 pub mod definitions;
 pub mod dropdown_state;
+pub mod heatmap_state;
 pub mod ez_state;
 pub mod label_state;
+pub mod list_view_state;
 pub mod layout_state;
 pub mod progress_bar_state;
+pub mod qr_code_state;
 pub mod radio_button_state;
+pub mod rating_state;
 pub mod slider_state;
+pub mod stepper_state;
+pub mod text_box_state;
 pub mod text_input_state;
+pub mod ticker_state;
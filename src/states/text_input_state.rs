@@ -1,11 +1,13 @@
+use crate::parser::parse_properties::parse_cursor_style_property;
 use crate::property::ez_property::EzProperty;
 use crate::property::ez_values::EzValues;
 use crate::run::definitions::{Coordinates, IsizeCoordinates};
 use crate::scheduler::scheduler::SchedulerFrontend;
 use crate::scheduler::scheduler_funcs::clean_up_property;
 use crate::states::definitions::{
-    AutoScale, BorderConfig, ColorConfig, HorizontalAlignment, InfiniteSize, Padding, PosHint,
-    SizeHint, StateCoordinates, StateSize, VerticalAlignment,
+    AnchorConfig, AutoScale, BorderConfig, ColorConfig, CursorStyle, DockPosition,
+    HorizontalAlignment, InfiniteSize, Padding, PosHint, SizeHint, StateCoordinates, StateSize,
+    VerticalAlignment,
 };
 use crate::states::ez_state::{EzState, GenericState};
 use crossterm::style::Color;
@@ -52,6 +54,16 @@ pub struct TextInputState {
     /// How many characters [text] may hold
     pub max_length: EzProperty<usize>,
 
+    /// Name of the [CursorStyle] of the cursor. Use [set_cursor_style] and [get_cursor_style]
+    pub cursor_style: EzProperty<String>,
+
+    /// Milliseconds between blinks of the cursor. 0 means the cursor does not blink
+    pub cursor_blink_interval: EzProperty<usize>,
+
+    /// Whether to show the actual terminal cursor at the cursor position instead of drawing the
+    /// cursor, so tools like input methods and screen readers can follow it
+    pub terminal_cursor: EzProperty<bool>,
+
     /// [BorderConfig] object that will be used to draw the border if enabled
     pub border_config: BorderConfig,
 
@@ -64,9 +76,33 @@ pub struct TextInputState {
     /// Global order number in which this widget will be selection when user presses down/up keys
     pub selection_order: EzProperty<usize>,
 
+    /// Whether this widget is collapsed when its' parent is a [LayoutMode::Dock] layout
+    pub collapsed: EzProperty<bool>,
+
+    /// Where this widget is placed when its' parent is a [LayoutMode::Dock] layout
+    pub dock: EzProperty<DockPosition>,
+
+    /// Share of the leftover space this widget gets in a box layout, relative to the weights of
+    /// its' siblings. 0.0 means the widget is not weighted and is sized normally
+    pub weight: EzProperty<f64>,
+
+    /// Order in which overlapping children of a [LayoutMode::Float] layout are drawn; higher
+    /// values are drawn on top. Children with the same z index are drawn in declaration order
+    pub z_index: EzProperty<usize>,
+
+    /// Terminal edge or corner this widget is kept at when its' parent is a [LayoutMode::Float]
+    /// layout, see [Anchor]
+    pub anchor: AnchorConfig,
+
     /// Bool representing whether this widget is currently selected. Internal only.
     selected: bool,
 
+    /// Bool representing whether the mouse is currently over this widget.
+    hovered: bool,
+
+    /// Bool representing whether this widget is currently held down by the mouse.
+    held: bool,
+
     /// Position of cursor relative to this widget. Internal only
     cursor_pos: Coordinates,
 
@@ -76,6 +112,9 @@ pub struct TextInputState {
     /// Switch for blinking. When true displays [cursor_color] on the [cursor_pos]. Internal only
     blink_switch: bool,
 
+    /// Milliseconds since the cursor last blinked. Internal only
+    blink_timer: usize,
+
     /// If text is larger than the widget, only a part of the text can be displayed. This is the
     /// index of where to start viewing the text. Internal only.
     view_start: usize,
@@ -103,13 +142,32 @@ impl TextInputState {
             cursor_pos: Coordinates::default(),
             active_blink_task: false,
             blink_switch: false,
+            blink_timer: 0,
             view_start: 0,
             disabled: scheduler.new_bool_property(format!("{}/disabled", path).as_str(), false),
             selected: false,
+            hovered: false,
+            held: false,
             selection_order: scheduler
                 .new_usize_property(format!("{}/selection_order", path).as_str(), 0),
+            collapsed: scheduler.new_bool_property(format!("{}/collapsed", path).as_str(), false),
+            dock: scheduler.new_dock_position_property(
+                format!("{}/dock", path).as_str(),
+                DockPosition::Center,
+            ),
+            weight: scheduler.new_f64_property(format!("{}/weight", path).as_str(), 0.0),
+            z_index: scheduler.new_usize_property(format!("{}/z_index", path).as_str(), 0),
+            anchor: AnchorConfig::new(path.clone(), scheduler),
             text: scheduler.new_string_property(format!("{}/text", path).as_str(), String::new()),
             max_length: scheduler.new_usize_property(format!("{}/max_length", path).as_str(), 0),
+            cursor_style: scheduler.new_string_property(
+                format!("{}/cursor_style", path).as_str(),
+                CursorStyle::Block.get_name().to_string(),
+            ),
+            cursor_blink_interval: scheduler
+                .new_usize_property(format!("{}/cursor_blink_interval", path).as_str(), 400),
+            terminal_cursor: scheduler
+                .new_bool_property(format!("{}/terminal_cursor", path).as_str(), false),
             border_config: BorderConfig::new(false, path.clone(), scheduler),
             colors: ColorConfig::new(path, scheduler),
         };
@@ -143,6 +201,13 @@ impl GenericState for TextInputState {
             "valign" => EzValues::VerticalAlignment(self.valign.value),
             "disabled" => EzValues::Bool(self.disabled.value),
             "selection_order" => EzValues::Usize(self.selection_order.value),
+            "collapsed" => EzValues::Bool(self.collapsed.value),
+            "dock" => EzValues::DockPosition(self.dock.value),
+            "weight" => EzValues::F64(self.weight.value),
+            "z_index" => EzValues::Usize(self.z_index.value),
+            "anchor" => EzValues::String(self.anchor.anchor.value.clone()),
+            "anchor_offset_x" => EzValues::Usize(self.anchor.anchor_offset_x.value),
+            "anchor_offset_y" => EzValues::Usize(self.anchor.anchor_offset_y.value),
             "border" => EzValues::Bool(self.border_config.border.value),
             "horizontal_symbol" => {
                 EzValues::String(self.border_config.horizontal_symbol.value.to_string())
@@ -168,11 +233,23 @@ impl GenericState for TextInputState {
             "selection_bg_color" => EzValues::Color(self.colors.selection_bg_color.value),
             "disabled_fg_color" => EzValues::Color(self.colors.disabled_fg_color.value),
             "disabled_bg_color" => EzValues::Color(self.colors.disabled_bg_color.value),
+            "auto_colors" => EzValues::Bool(self.colors.auto_colors.value),
+            "auto_contrast" => EzValues::Bool(self.colors.auto_contrast.value),
+            "selection_style" => EzValues::String(self.colors.selection_style.value.clone()),
+            "selection_marker" => EzValues::String(self.colors.selection_marker.value.clone()),
+            "hover_fg_color" => EzValues::Color(self.colors.hover_fg_color.value),
+            "hover_bg_color" => EzValues::Color(self.colors.hover_bg_color.value),
+            "pressed_fg_color" => EzValues::Color(self.colors.pressed_fg_color.value),
+            "pressed_bg_color" => EzValues::Color(self.colors.pressed_bg_color.value),
+            "focused_border" => EzValues::Bool(self.colors.focused_border.value),
             "border_fg_color" => EzValues::Color(self.colors.border_fg_color.value),
             "border_bg_color" => EzValues::Color(self.colors.border_bg_color.value),
             "cursor_color" => EzValues::Color(self.colors.cursor_color.value),
             "text" => EzValues::String(self.text.value.to_string()),
             "max_length" => EzValues::Usize(self.max_length.value),
+            "cursor_style" => EzValues::String(self.cursor_style.value.to_string()),
+            "cursor_blink_interval" => EzValues::Usize(self.cursor_blink_interval.value),
+            "terminal_cursor" => EzValues::Bool(self.terminal_cursor.value),
             _ => panic!("Invalid property name for button state: {}", name),
         }
     }
@@ -197,6 +274,13 @@ impl GenericState for TextInputState {
             "valign" => self.valign.set_from_ez_value(value),
             "disabled" => self.disabled.set_from_ez_value(value),
             "selection_order" => self.selection_order.set_from_ez_value(value),
+            "collapsed" => self.collapsed.set_from_ez_value(value),
+            "dock" => self.dock.set_from_ez_value(value),
+            "weight" => self.weight.set_from_ez_value(value),
+            "z_index" => self.z_index.set_from_ez_value(value),
+            "anchor" => self.anchor.anchor.set_from_ez_value(value),
+            "anchor_offset_x" => self.anchor.anchor_offset_x.set_from_ez_value(value),
+            "anchor_offset_y" => self.anchor.anchor_offset_y.set_from_ez_value(value),
             "border" => self.border_config.border.set_from_ez_value(value),
             "horizontal_symbol" => self
                 .border_config
@@ -219,11 +303,23 @@ impl GenericState for TextInputState {
             "selection_bg_color" => self.colors.selection_bg_color.set_from_ez_value(value),
             "disabled_fg_color" => self.colors.disabled_fg_color.set_from_ez_value(value),
             "disabled_bg_color" => self.colors.disabled_bg_color.set_from_ez_value(value),
+            "auto_colors" => self.colors.auto_colors.set_from_ez_value(value),
+            "auto_contrast" => self.colors.auto_contrast.set_from_ez_value(value),
+            "selection_style" => self.colors.selection_style.set_from_ez_value(value),
+            "selection_marker" => self.colors.selection_marker.set_from_ez_value(value),
+            "hover_fg_color" => self.colors.hover_fg_color.set_from_ez_value(value),
+            "hover_bg_color" => self.colors.hover_bg_color.set_from_ez_value(value),
+            "pressed_fg_color" => self.colors.pressed_fg_color.set_from_ez_value(value),
+            "pressed_bg_color" => self.colors.pressed_bg_color.set_from_ez_value(value),
+            "focused_border" => self.colors.focused_border.set_from_ez_value(value),
             "border_fg_color" => self.colors.border_fg_color.set_from_ez_value(value),
             "border_bg_color" => self.colors.border_bg_color.set_from_ez_value(value),
             "cursor_color" => self.colors.cursor_color.set_from_ez_value(value),
             "text" => self.text.set_from_ez_value(value),
             "max_length" => self.max_length.set_from_ez_value(value),
+            "cursor_style" => self.cursor_style.set_from_ez_value(value),
+            "cursor_blink_interval" => self.cursor_blink_interval.set_from_ez_value(value),
+            "terminal_cursor" => self.terminal_cursor.set_from_ez_value(value),
             _ => panic!("Invalid property name for text input state: {}", name),
         }
     }
@@ -271,6 +367,17 @@ impl GenericState for TextInputState {
         self.valign.copy_from(&other.valign);
         self.disabled.copy_from(&other.disabled);
         self.selection_order.copy_from(&other.selection_order);
+        self.collapsed.copy_from(&other.collapsed);
+        self.dock.copy_from(&other.dock);
+        self.weight.copy_from(&other.weight);
+        self.z_index.copy_from(&other.z_index);
+        self.anchor.anchor.copy_from(&other.anchor.anchor);
+        self.anchor
+            .anchor_offset_x
+            .copy_from(&other.anchor.anchor_offset_x);
+        self.anchor
+            .anchor_offset_y
+            .copy_from(&other.anchor.anchor_offset_y);
         self.border_config
             .border
             .copy_from(&other.border_config.border);
@@ -306,6 +413,31 @@ impl GenericState for TextInputState {
         self.colors
             .disabled_bg_color
             .copy_from(&other.colors.disabled_bg_color);
+        self.colors.auto_colors.copy_from(&other.colors.auto_colors);
+        self.colors
+            .auto_contrast
+            .copy_from(&other.colors.auto_contrast);
+        self.colors
+            .selection_style
+            .copy_from(&other.colors.selection_style);
+        self.colors
+            .selection_marker
+            .copy_from(&other.colors.selection_marker);
+        self.colors
+            .hover_fg_color
+            .copy_from(&other.colors.hover_fg_color);
+        self.colors
+            .hover_bg_color
+            .copy_from(&other.colors.hover_bg_color);
+        self.colors
+            .pressed_fg_color
+            .copy_from(&other.colors.pressed_fg_color);
+        self.colors
+            .pressed_bg_color
+            .copy_from(&other.colors.pressed_bg_color);
+        self.colors
+            .focused_border
+            .copy_from(&other.colors.focused_border);
         self.colors
             .border_fg_color
             .copy_from(&other.colors.border_fg_color);
@@ -317,6 +449,10 @@ impl GenericState for TextInputState {
             .copy_from(&other.colors.cursor_color);
         self.text.copy_from(&other.text);
         self.max_length.copy_from(&other.max_length);
+        self.cursor_style.copy_from(&other.cursor_style);
+        self.cursor_blink_interval
+            .copy_from(&other.cursor_blink_interval);
+        self.terminal_cursor.copy_from(&other.terminal_cursor);
     }
 
     fn get_size_hint(&self) -> &SizeHint {
@@ -435,6 +571,46 @@ impl GenericState for TextInputState {
         self.selection_order.set(order);
     }
 
+    fn get_collapsed(&self) -> bool {
+        self.collapsed.value
+    }
+
+    fn set_collapsed(&mut self, collapsed: bool) {
+        self.collapsed.set(collapsed);
+    }
+
+    fn get_dock(&self) -> DockPosition {
+        self.dock.value
+    }
+
+    fn set_dock(&mut self, dock: DockPosition) {
+        self.dock.set(dock);
+    }
+
+    fn get_weight(&self) -> f64 {
+        self.weight.value
+    }
+
+    fn set_weight(&mut self, weight: f64) {
+        self.weight.set(weight);
+    }
+
+    fn get_z_index(&self) -> usize {
+        self.z_index.value
+    }
+
+    fn set_z_index(&mut self, z_index: usize) {
+        self.z_index.set(z_index);
+    }
+
+    fn get_anchor_config(&self) -> &AnchorConfig {
+        &self.anchor
+    }
+
+    fn get_anchor_config_mut(&mut self) -> &mut AnchorConfig {
+        &mut self.anchor
+    }
+
     fn set_selected(&mut self, state: bool) {
         self.selected = state;
     }
@@ -443,6 +619,22 @@ impl GenericState for TextInputState {
         self.selected
     }
 
+    fn set_hovered(&mut self, state: bool) {
+        self.hovered = state;
+    }
+
+    fn get_hovered(&self) -> bool {
+        self.hovered
+    }
+
+    fn set_held(&mut self, state: bool) {
+        self.held = state;
+    }
+
+    fn get_held(&self) -> bool {
+        self.held
+    }
+
     fn clean_up_properties(&self, scheduler: &mut SchedulerFrontend) {
         self.position.clean_up_properties(scheduler);
         self.size.clean_up_properties(scheduler);
@@ -454,8 +646,16 @@ impl GenericState for TextInputState {
         clean_up_property(scheduler, &self.valign.name);
         clean_up_property(scheduler, &self.text.name);
         clean_up_property(scheduler, &self.max_length.name);
+        clean_up_property(scheduler, &self.cursor_style.name);
+        clean_up_property(scheduler, &self.cursor_blink_interval.name);
+        clean_up_property(scheduler, &self.terminal_cursor.name);
         clean_up_property(scheduler, &self.disabled.name);
         clean_up_property(scheduler, &self.selection_order.name);
+        clean_up_property(scheduler, &self.collapsed.name);
+        clean_up_property(scheduler, &self.dock.name);
+        clean_up_property(scheduler, &self.weight.name);
+        clean_up_property(scheduler, &self.z_index.name);
+        self.anchor.clean_up_properties(scheduler);
         self.border_config.clean_up_properties(scheduler);
         self.colors.clean_up_properties(scheduler);
     }
@@ -505,6 +705,21 @@ impl TextInputState {
         self.blink_switch
     }
 
+    pub fn set_blink_timer(&mut self, timer: usize) {
+        self.blink_timer = timer;
+    }
+
+    pub fn get_blink_timer(&self) -> usize {
+        self.blink_timer
+    }
+
+    /// Show the cursor and restart its' blink interval, so the cursor stays visible while the user
+    /// is typing.
+    pub fn reset_cursor_blink(&mut self) {
+        self.blink_switch = true;
+        self.blink_timer = 0;
+    }
+
     pub fn set_view_start(&mut self, view_start: usize) {
         self.view_start = view_start;
     }
@@ -520,4 +735,34 @@ impl TextInputState {
     pub fn get_max_length(&self) -> usize {
         self.max_length.value
     }
+
+    pub fn set_cursor_style(&mut self, style: CursorStyle) {
+        self.cursor_style.set(style.get_name().to_string());
+    }
+
+    /// Get the style of the cursor. Falls back to [CursorStyle::Block] if the style property was
+    /// set to an unknown name.
+    pub fn get_cursor_style(&self) -> CursorStyle {
+        parse_cursor_style_property(&self.cursor_style.value).unwrap_or(CursorStyle::Block)
+    }
+
+    /// Set the milliseconds between blinks of the cursor. 0 means the cursor does not blink.
+    pub fn set_cursor_blink_interval(&mut self, interval: usize) {
+        self.cursor_blink_interval.set(interval);
+    }
+
+    pub fn get_cursor_blink_interval(&self) -> usize {
+        self.cursor_blink_interval.value
+    }
+
+    /// Set whether to show the actual terminal cursor at the cursor position instead of drawing
+    /// the cursor. The terminal cursor takes the [CursorStyle] of this text input; it blinks at
+    /// the rate of the terminal if the blink interval is not 0.
+    pub fn set_terminal_cursor(&mut self, terminal_cursor: bool) {
+        self.terminal_cursor.set(terminal_cursor);
+    }
+
+    pub fn get_terminal_cursor(&self) -> bool {
+        self.terminal_cursor.value
+    }
 }
@@ -2,17 +2,26 @@
 use std::io::{Error, ErrorKind};
 use std::time::Duration;
 
+use crossterm::style::Color;
+
+use crate::parser::ez_definition::ConfigLine;
 use crate::parser::load_base_properties;
 use crate::parser::load_common_properties::load_common_property;
+use crate::parser::parse_lang::ParseError;
 use crate::run::definitions::{CallbackTree, Coordinates, Pixel, PixelMap, StateTree};
 use crate::scheduler::scheduler::SchedulerFrontend;
 use crate::states::button_state::ButtonState;
-use crate::states::definitions::{HorizontalAlignment, VerticalAlignment};
+use crate::states::definitions::{HorizontalAlignment, InteractionState, VerticalAlignment};
 use crate::states::ez_state::{EzState, GenericState};
 use crate::widgets::ez_object::{EzObject, EzObjects};
 use crate::widgets::helper_functions::{add_border, add_padding, align_content_horizontally, align_content_vertically, format_text, wrap_text};
 use crate::Context;
-use crate::scheduler::definitions::CustomDataMap;
+use crate::scheduler::definitions::{CustomDataMap, GenericFunction};
+
+/// Time without a repeated enter key press after which a button held down with the keyboard is
+/// considered released. Terminals do not report key releases, so holding a key is detected
+/// through key repeat.
+pub const KEY_RELEASE_TIMEOUT: Duration = Duration::from_millis(700);
 
 #[derive(Clone, Debug)]
 pub struct Button {
@@ -69,6 +78,41 @@ impl EzObject for Button {
                 &parameter_name,
                 self.get_state_mut(),
             )?,
+            "long_press_time" => load_base_properties::load_usize_property(
+                parameter_value.trim(),
+                scheduler,
+                self.path.clone(),
+                &parameter_name,
+                self.get_state_mut(),
+            )?,
+            "toggle" => load_base_properties::load_bool_property(
+                parameter_value.trim(),
+                scheduler,
+                self.path.clone(),
+                &parameter_name,
+                self.get_state_mut(),
+            )?,
+            "active" => load_base_properties::load_bool_property(
+                parameter_value.trim(),
+                scheduler,
+                self.path.clone(),
+                &parameter_name,
+                self.get_state_mut(),
+            )?,
+            "icon" => load_base_properties::load_string_property(
+                parameter_value.trim(),
+                scheduler,
+                self.path.clone(),
+                &parameter_name,
+                self.get_state_mut(),
+            )?,
+            "repeat_interval" => load_base_properties::load_usize_property(
+                parameter_value.trim(),
+                scheduler,
+                self.path.clone(),
+                &parameter_name,
+                self.get_state_mut(),
+            )?,
             _ => {
                 return Err(Error::new(
                     ErrorKind::InvalidData,
@@ -107,7 +151,16 @@ impl EzObject for Button {
 
         let state = state_tree.get_mut(&self.get_path()).as_button_mut();
 
-        let (fg_color, bg_color) = if state.get_flashing() {
+        // An active toggle button looks pressed. Pressed colors, if set, replace the flash colors;
+        // they are applied when the content is styled
+        let (pressed_fg_color, pressed_bg_color) = state
+            .get_color_config()
+            .get_interaction_colors(InteractionState::Pressed);
+        let has_pressed_colors =
+            pressed_fg_color != Color::Reset || pressed_bg_color != Color::Reset;
+        let (fg_color, bg_color) = if (state.get_flashing() || state.get_active())
+            && !has_pressed_colors
+        {
             (
                 state.get_color_config().get_flash_fg_color(),
                 state.get_color_config().get_flash_bg_color(),
@@ -116,7 +169,9 @@ impl EzObject for Button {
             state.get_context_colors()
         };
 
-        let text = state.get_text();
+        // Compact icon buttons are exactly as wide as their icon
+        let compact = !state.get_icon().is_empty();
+        let text = if compact { state.get_icon() } else { state.get_text() };
 
         let write_width =
             if state.get_infinite_size().width || state.get_auto_scale().get_auto_scale_width() {
@@ -128,7 +183,8 @@ impl EzObject for Button {
         let default_pixel = Pixel::new(" ".to_string(),
                                        fg_color,bg_color);
         let (text, pixels) = format_text(text, default_pixel.clone());
-        let content_lines = wrap_text(text, write_width, pixels);
+        // Icons are a single line and are never wrapped
+        let content_lines = if compact { vec![pixels] } else { wrap_text(text, write_width, pixels) };
         let write_height =
             if state.get_infinite_size().height || state.get_auto_scale().get_auto_scale_height() {
                 content_lines.len()
@@ -150,7 +206,7 @@ impl EzObject for Button {
             }
             contents.push(new_y);
         }
-        if state.get_auto_scale().get_auto_scale_width() {
+        if compact || state.get_auto_scale().get_auto_scale_width() {
             state.set_effective_width(contents.len());
         }
         if state.get_auto_scale().get_auto_scale_height() {
@@ -203,6 +259,9 @@ impl EzObject for Button {
         scheduler: &mut SchedulerFrontend,
         custom_data: &mut CustomDataMap,
     ) -> bool {
+        if state_tree.get(&self.path).as_button().get_toggle() {
+            self.handle_toggle(state_tree, callback_tree, scheduler, custom_data);
+        }
         let consumed = self.on_press_callback(state_tree, callback_tree, scheduler, custom_data);
         if consumed {
             return consumed;
@@ -211,6 +270,82 @@ impl EzObject for Button {
         return true;
     }
 
+    fn on_keyboard_enter(
+        &self,
+        state_tree: &mut StateTree,
+        callback_tree: &mut CallbackTree,
+        scheduler: &mut SchedulerFrontend,
+        custom_data: &mut CustomDataMap,
+    ) -> bool {
+        let now = scheduler.now();
+        let state = state_tree.get(&self.path).as_button();
+        let repeated = state.get_pressed_by_key()
+            && state.get_last_key_press().map_or(false, |x| {
+                now.saturating_duration_since(x) <= KEY_RELEASE_TIMEOUT
+            });
+        if repeated {
+            // Enter is being held down, so this is a repeated key press
+            let held = now.saturating_duration_since(state.get_pressed_since().unwrap());
+            let long_press_time = Duration::from_millis(state.get_long_press_time() as u64);
+            let long_pressed = state.get_long_pressed();
+            state_tree.get_mut(&self.path).as_button_mut().set_last_key_press(now);
+            if !long_pressed && held >= long_press_time {
+                Button::trigger_long_press(&self.path, state_tree, callback_tree, scheduler,
+                                           custom_data);
+            }
+            // Repeat-on-hold buttons repeat at their own interval rather than the key repeat
+            // rate, see [update_pressed_buttons]
+            if callback_tree.get(&self.path).obj.on_long_press.is_some()
+                || state_tree.get(&self.path).as_button().get_repeat_interval() > 0
+            {
+                return true;
+            }
+        } else {
+            Button::release_press(&self.path, state_tree, callback_tree, scheduler, custom_data);
+            self.start_press(true, state_tree, callback_tree, scheduler, custom_data);
+        }
+        let consumed = self.on_keyboard_enter_callback(state_tree, callback_tree, scheduler, custom_data);
+        if !consumed {
+            return self.on_press(state_tree, callback_tree, scheduler, custom_data);
+        }
+        false
+    }
+
+    fn on_left_mouse_down(
+        &self,
+        state_tree: &mut StateTree,
+        callback_tree: &mut CallbackTree,
+        scheduler: &mut SchedulerFrontend,
+        _mouse_pos: Coordinates,
+        custom_data: &mut CustomDataMap,
+    ) -> bool {
+        Button::release_press(&self.path, state_tree, callback_tree, scheduler, custom_data);
+        self.start_press(false, state_tree, callback_tree, scheduler, custom_data);
+        true
+    }
+
+    fn on_left_mouse_click(
+        &self,
+        state_tree: &mut StateTree,
+        callback_tree: &mut CallbackTree,
+        scheduler: &mut SchedulerFrontend,
+        mouse_pos: Coordinates,
+        custom_data: &mut CustomDataMap,
+    ) -> bool {
+        let repeated = state_tree.get(&self.path).as_button().get_last_repeat().is_some();
+        let long_pressed =
+            Button::release_press(&self.path, state_tree, callback_tree, scheduler, custom_data);
+        if (long_pressed && callback_tree.get(&self.path).obj.on_long_press.is_some()) || repeated {
+            return true;
+        }
+        let consumed =
+            self.on_left_mouse_click_callback(state_tree, callback_tree, scheduler, mouse_pos, custom_data);
+        if !consumed {
+            return self.on_press(state_tree, callback_tree, scheduler, custom_data);
+        }
+        false
+    }
+
     fn on_hover(
         &self,
         state_tree: &mut StateTree,
@@ -239,16 +374,14 @@ impl EzObject for Button {
 impl Button {
     /// Initialize an instance of this object using the passed config coming from [ez_parser]
     pub fn from_config(
-        config: Vec<String>,
+        config: Vec<ConfigLine>,
         id: String,
         path: String,
         scheduler: &mut SchedulerFrontend,
-        file: String,
-        line: usize,
-    ) -> Self {
+    ) -> Result<Self, ParseError> {
         let mut obj = Button::new(id, path, scheduler);
-        obj.load_ez_config(config, scheduler, file, line).unwrap();
-        obj
+        obj.load_ez_config(config, scheduler)?;
+        Ok(obj)
     }
 
     pub fn handle_on_press(&self, state_tree: &mut StateTree, scheduler: &mut SchedulerFrontend) {
@@ -270,4 +403,132 @@ impl Button {
             Duration::from_millis(50),
         );
     }
+
+    /// Flip a toggle button between pressed and released and call the on_value_change callback.
+    fn handle_toggle(
+        &self,
+        state_tree: &mut StateTree,
+        callback_tree: &mut CallbackTree,
+        scheduler: &mut SchedulerFrontend,
+        custom_data: &mut CustomDataMap,
+    ) {
+        let state = state_tree.get_mut(&self.path).as_button_mut();
+        state.set_active(!state.get_active());
+        state.update(scheduler);
+        self.on_value_change_callback(state_tree, callback_tree, scheduler, custom_data);
+    }
+
+    /// Mark the button as held down and call the on_press_start callback.
+    fn start_press(
+        &self,
+        by_key: bool,
+        state_tree: &mut StateTree,
+        callback_tree: &mut CallbackTree,
+        scheduler: &mut SchedulerFrontend,
+        custom_data: &mut CustomDataMap,
+    ) {
+        let now = scheduler.now();
+        state_tree.get_mut(&self.path).as_button_mut().set_pressed(now, by_key);
+        if !scheduler.backend.pressed_buttons.contains(&self.path) {
+            scheduler.backend.pressed_buttons.push(self.path.clone());
+        }
+        if let Some(callbacks) = callback_tree.try_get_mut(&self.path) {
+            call_button_callback(&self.path, &mut callbacks.obj.on_press_start, state_tree,
+                                 scheduler, custom_data);
+        }
+    }
+
+    /// Release a button if it is held down and call the on_release callback. Returns whether the
+    /// press had become a long press.
+    pub fn release_press(
+        path: &str,
+        state_tree: &mut StateTree,
+        callback_tree: &mut CallbackTree,
+        scheduler: &mut SchedulerFrontend,
+        custom_data: &mut CustomDataMap,
+    ) -> bool {
+        scheduler.backend.pressed_buttons.retain(|x| x != path);
+        let state = match state_tree.try_get_mut(path) {
+            Some(i) => match i.obj.try_as_mut::<ButtonState>() {
+                Some(state) => state,
+                None => return false,
+            },
+            None => return false,
+        };
+        if !state.get_pressed() {
+            return false;
+        }
+        let long_pressed = state.get_long_pressed();
+        state.set_released();
+        if let Some(callbacks) = callback_tree.try_get_mut(path) {
+            call_button_callback(path, &mut callbacks.obj.on_release, state_tree, scheduler,
+                                 custom_data);
+        }
+        long_pressed
+    }
+
+    /// Release all buttons that are held down with the mouse. Called when the mouse button is
+    /// released, as it might be released outside of the button that was pressed.
+    pub fn release_mouse_presses(
+        state_tree: &mut StateTree,
+        callback_tree: &mut CallbackTree,
+        scheduler: &mut SchedulerFrontend,
+        custom_data: &mut CustomDataMap,
+    ) {
+        for path in scheduler.backend.pressed_buttons.clone() {
+            let by_key = state_tree
+                .try_get(&path)
+                .and_then(|x| x.obj.try_as::<ButtonState>())
+                .map_or(false, |x| x.get_pressed_by_key());
+            if !by_key {
+                Button::release_press(&path, state_tree, callback_tree, scheduler, custom_data);
+            }
+        }
+    }
+
+    /// Repeat the on_press callback of a button that is held down. Used by repeat-on-hold buttons
+    /// (see [ButtonState.set_repeat_interval]); toggle buttons are not toggled by repeats.
+    pub fn repeat_press(
+        path: &str,
+        state_tree: &mut StateTree,
+        callback_tree: &mut CallbackTree,
+        scheduler: &mut SchedulerFrontend,
+        custom_data: &mut CustomDataMap,
+    ) {
+        let now = scheduler.now();
+        state_tree.get_mut(path).as_button_mut().set_last_repeat(now);
+        if let Some(callbacks) = callback_tree.try_get_mut(path) {
+            call_button_callback(path, &mut callbacks.obj.on_press, state_tree, scheduler,
+                                 custom_data);
+        }
+    }
+
+    /// Mark the current press of a button as a long press and call the on_long_press callback.
+    pub fn trigger_long_press(
+        path: &str,
+        state_tree: &mut StateTree,
+        callback_tree: &mut CallbackTree,
+        scheduler: &mut SchedulerFrontend,
+        custom_data: &mut CustomDataMap,
+    ) {
+        state_tree.get_mut(path).as_button_mut().set_long_pressed(true);
+        if let Some(callbacks) = callback_tree.try_get_mut(path) {
+            call_button_callback(path, &mut callbacks.obj.on_long_press, state_tree, scheduler,
+                                 custom_data);
+        }
+    }
+}
+
+/// Call one of the press related callbacks of a button if it is set.
+fn call_button_callback(
+    path: &str,
+    callback: &mut Option<GenericFunction>,
+    state_tree: &mut StateTree,
+    scheduler: &mut SchedulerFrontend,
+    custom_data: &mut CustomDataMap,
+) -> bool {
+    if let Some(ref mut i) = callback {
+        return i(Context::new(path.to_string(), state_tree, scheduler, custom_data));
+    }
+    false
 }
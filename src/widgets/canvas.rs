@@ -4,11 +4,13 @@
 use std::collections::HashMap;
 use std::io::{Error, ErrorKind}; // For ez_file_gen.rs
 
+use crate::parser::ez_definition::ConfigLine;
 use crate::parser::load_base_properties;
 use unicode_segmentation::UnicodeSegmentation;
 
 use crate::parser::load_common_properties::load_common_property;
-use crate::run::definitions::{Pixel, PixelMap, StateTree};
+use crate::parser::parse_lang::ParseError;
+use crate::run::definitions::{Coordinates, Pixel, PixelMap, StateTree};
 use crate::scheduler::scheduler::SchedulerFrontend;
 use crate::states::canvas_state::CanvasState;
 use crate::states::ez_state::{EzState, GenericState};
@@ -185,6 +187,14 @@ impl EzObject for Canvas {
         contents
     }
 
+    fn get_cell(&self, state_tree: &StateTree, pos: Coordinates) -> Option<Pixel> {
+        let state = state_tree.get(&self.get_path()).as_canvas();
+        if !state.get_from_file().is_empty() {
+            return None;
+        }
+        state.get_contents().get(pos.x)?.get(pos.y).cloned()
+    }
+
     fn get_clone(&self, scheduler: &mut SchedulerFrontend) -> EzObjects {
         let mut clone = self.clone();
         let mut new_state = CanvasState::new(self.path.clone(), scheduler);
@@ -196,15 +206,13 @@ impl EzObject for Canvas {
 impl Canvas {
     /// Initialize an instance of this object using the passed config coming from [ez_parser]
     pub fn from_config(
-        config: Vec<String>,
+        config: Vec<ConfigLine>,
         id: String,
         path: String,
         scheduler: &mut SchedulerFrontend,
-        file: String,
-        line: usize,
-    ) -> Self {
+    ) -> Result<Self, ParseError> {
         let mut obj = Canvas::new(id, path, scheduler);
-        obj.load_ez_config(config, scheduler, file, line).unwrap();
-        obj
+        obj.load_ez_config(config, scheduler)?;
+        Ok(obj)
     }
 }
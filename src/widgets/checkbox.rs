@@ -2,8 +2,10 @@
 //! Widget which is either on or off and implements an on_value_change callback.
 use std::io::{Error, ErrorKind};
 
+use crate::parser::ez_definition::ConfigLine;
 use crate::parser::load_base_properties;
 use crate::parser::load_common_properties::load_common_property;
+use crate::parser::parse_lang::ParseError;
 use crate::run::definitions::{CallbackTree, Coordinates, Pixel, PixelMap, StateTree};
 use crate::scheduler::definitions::CustomDataMap;
 use crate::scheduler::scheduler::SchedulerFrontend;
@@ -208,16 +210,14 @@ impl EzObject for Checkbox {
 impl Checkbox {
     /// Initialize an instance of this object using the passed config coming from [ez_parser]
     pub fn from_config(
-        config: Vec<String>,
+        config: Vec<ConfigLine>,
         id: String,
         path: String,
         scheduler: &mut SchedulerFrontend,
-        file: String,
-        line: usize,
-    ) -> Self {
+    ) -> Result<Self, ParseError> {
         let mut obj = Checkbox::new(id, path, scheduler);
-        obj.load_ez_config(config, scheduler, file, line).unwrap();
-        obj
+        obj.load_ez_config(config, scheduler)?;
+        Ok(obj)
     }
 
     fn handle_toggle(
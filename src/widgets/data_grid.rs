@@ -0,0 +1,421 @@
+//! A widget that shows rows of values in columns with headers. Rows can be selected with the mouse
+//! or the arrow keys, and sorted by clicking a column header.
+use std::io::{Error, ErrorKind};
+
+use crossterm::event::{Event, KeyCode, KeyEvent};
+
+use crate::parser::ez_definition::ConfigLine;
+use crate::parser::load_base_properties;
+use crate::parser::load_common_properties::load_common_property;
+use crate::parser::parse_lang::ParseError;
+use crate::run::definitions::{CallbackTree, Coordinates, Pixel, PixelMap, StateTree};
+use crate::scheduler::definitions::CustomDataMap;
+use crate::scheduler::scheduler::SchedulerFrontend;
+use crate::states::data_grid_state::DataGridState;
+use crate::states::ez_state::{EzState, GenericState};
+use crate::widgets::ez_object::{EzObject, EzObjects};
+use crate::widgets::helper_functions::add_padding;
+use crate::Context;
+
+#[derive(Clone, Debug)]
+pub struct DataGrid {
+    /// ID of the widget, used to construct [path]
+    pub id: String,
+
+    /// Full path to this widget, e.g. "/root_layout/layout_2/THIS_ID"
+    pub path: String,
+
+    /// Runtime state of this widget, see [DataGridState] and [State]
+    pub state: DataGridState,
+}
+
+impl DataGrid {
+    pub fn new(id: String, path: String, scheduler: &mut SchedulerFrontend) -> Self {
+        DataGrid {
+            id,
+            path: path.clone(),
+            state: DataGridState::new(path, scheduler),
+        }
+    }
+
+    pub fn from_state(
+        id: String,
+        path: String,
+        _scheduler: &mut SchedulerFrontend,
+        state: EzState,
+    ) -> Self {
+        DataGrid {
+            id,
+            path: path.clone(),
+            state: state.as_data_grid().to_owned(),
+        }
+    }
+}
+
+impl EzObject for DataGrid {
+    fn load_ez_parameter(
+        &mut self,
+        parameter_name: String,
+        parameter_value: String,
+        scheduler: &mut SchedulerFrontend,
+    ) -> Result<(), Error> {
+        let consumed =
+            load_common_property(&parameter_name, parameter_value.clone(), self, scheduler)?;
+        if consumed {
+            return Ok(());
+        }
+        match parameter_name.as_str() {
+            "columns" => load_base_properties::load_string_list_property(
+                parameter_value.trim(),
+                scheduler,
+                self.path.clone(),
+                &parameter_name,
+                self.get_state_mut(),
+            )?,
+            "sortable" => load_base_properties::load_bool_property(
+                parameter_value.trim(),
+                scheduler,
+                self.path.clone(),
+                &parameter_name,
+                self.get_state_mut(),
+            )?,
+            // Rows are separated by semicolons and values by commas, e.g. "Alice, 30; Bob, 25"
+            "rows" => self.state.set_rows(
+                parameter_value
+                    .split(';')
+                    .filter(|x| !x.trim().is_empty())
+                    .map(|row| row.split(',').map(|x| x.trim().to_string()).collect())
+                    .collect(),
+            ),
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("Invalid parameter name for data grid: {}", parameter_name),
+                ))
+            }
+        }
+        Ok(())
+    }
+
+    fn set_id(&mut self, id: &str) {
+        self.id = id.to_string()
+    }
+
+    fn get_id(&self) -> String {
+        self.id.clone()
+    }
+
+    fn set_path(&mut self, id: &str) {
+        self.id = id.to_string()
+    }
+
+    fn get_path(&self) -> String {
+        self.path.clone()
+    }
+
+    fn get_state(&self) -> EzState {
+        EzState::DataGrid(self.state.clone())
+    }
+
+    fn get_state_mut(&mut self) -> &mut dyn GenericState {
+        &mut self.state
+    }
+
+    fn get_contents(&self, state_tree: &mut StateTree) -> PixelMap {
+        let state = state_tree.get_mut(&self.get_path()).as_data_grid_mut();
+        if state.get_auto_scale().get_auto_scale_width() {
+            state.set_effective_width(get_natural_widths(state).iter().sum());
+        }
+        if state.get_auto_scale().get_auto_scale_height() {
+            state.set_effective_height(state.get_rows().len() + 1);
+        }
+        let width = state.get_effective_size().width;
+        let height = state.get_effective_size().height;
+
+        let colors = state.get_color_config();
+        let (fg_color, bg_color) = if state.get_disabled() {
+            (colors.get_disabled_fg_color(), colors.get_disabled_bg_color())
+        } else {
+            (colors.get_fg_color(), colors.get_bg_color())
+        };
+        let (selection_fg_color, selection_bg_color) =
+            (colors.get_selection_fg_color(), colors.get_selection_bg_color());
+
+        // Build the lines first: the header and the visible rows, one cell per char
+        let column_widths = get_column_widths(state, width);
+        let mut header = Vec::new();
+        for (i, column) in state.get_columns().iter().enumerate() {
+            let indicator = match state.get_sort() {
+                Some((sorted, false)) if sorted == i => "▲",
+                Some((sorted, true)) if sorted == i => "▼",
+                _ => "",
+            };
+            header.append(&mut fit_cell(&format!("{}{}", column, indicator), column_widths[i]));
+        }
+        let mut lines = vec![header];
+        let first_row = state.get_scroll_y();
+        for row in state.get_rows().iter().skip(first_row).take(height.saturating_sub(1)) {
+            let mut line = Vec::new();
+            for (i, column_width) in column_widths.iter().enumerate() {
+                let value = row.get(i).map_or("", |x| x.as_str());
+                line.append(&mut fit_cell(value, *column_width));
+            }
+            lines.push(line);
+        }
+
+        let mut contents = PixelMap::new();
+        for x in 0..width {
+            let mut new_y = Vec::new();
+            for y in 0..height {
+                let symbol = lines
+                    .get(y)
+                    .and_then(|line| line.get(x))
+                    .map_or(" ".to_string(), |x| x.to_string());
+                let mut pixel = if y > 0 && state.get_selected_row() == Some(first_row + y - 1) {
+                    Pixel::new(symbol, selection_fg_color, selection_bg_color)
+                } else {
+                    Pixel::new(symbol, fg_color, bg_color)
+                };
+                if y == 0 {
+                    pixel.bold = true;
+                    pixel.underline = true;
+                }
+                new_y.push(pixel);
+            }
+            contents.push(new_y);
+        }
+
+        let state = state_tree.get(&self.get_path()).as_data_grid();
+        let parent_colors = state_tree
+            .get(self.get_path().rsplit_once('/').unwrap().0)
+            .as_generic()
+            .get_color_config();
+        contents = add_padding(
+            contents,
+            state.get_padding(),
+            parent_colors.get_bg_color(),
+            parent_colors.get_fg_color(),
+        );
+        contents
+    }
+
+    fn handle_event(
+        &self,
+        event: Event,
+        state_tree: &mut StateTree,
+        callback_tree: &mut CallbackTree,
+        scheduler: &mut SchedulerFrontend,
+        custom_data: &mut CustomDataMap,
+    ) -> bool {
+        if let Event::Key(key) = event {
+            let state = state_tree.get(&self.path).as_data_grid();
+            let last_row = match state.get_rows().len() {
+                0 => return false,
+                i => i - 1,
+            };
+            let page = state.get_effective_size().height.saturating_sub(1).max(1);
+            let row = match (key.code, state.get_selected_row()) {
+                (KeyCode::Down, None) | (KeyCode::Home, _) => 0,
+                (KeyCode::Down, Some(i)) => (i + 1).min(last_row),
+                (KeyCode::Up, Some(i)) => i.saturating_sub(1),
+                (KeyCode::PageDown, i) => (i.unwrap_or(0) + page).min(last_row),
+                (KeyCode::PageUp, i) => i.unwrap_or(0).saturating_sub(page),
+                (KeyCode::End, _) => last_row,
+                _ => return false,
+            };
+            self.select_row(row, state_tree, callback_tree, scheduler, custom_data);
+            return true;
+        }
+        false
+    }
+
+    fn on_left_mouse_click(
+        &self,
+        state_tree: &mut StateTree,
+        callback_tree: &mut CallbackTree,
+        scheduler: &mut SchedulerFrontend,
+        mouse_pos: Coordinates,
+        custom_data: &mut CustomDataMap,
+    ) -> bool {
+        let consumed = self.on_left_mouse_click_callback(
+            state_tree,
+            callback_tree,
+            scheduler,
+            mouse_pos,
+            custom_data,
+        );
+        if consumed {
+            return consumed;
+        }
+        scheduler.set_selected_widget(&self.path, Some(mouse_pos));
+        let state = state_tree.get_mut(&self.path).as_data_grid_mut();
+        let x = mouse_pos.x.saturating_sub(state.get_padding().get_padding_left());
+        let y = mouse_pos.y.saturating_sub(state.get_padding().get_padding_top());
+        if y == 0 {
+            if !state.get_sortable() {
+                return true;
+            }
+            let column_widths = get_column_widths(state, state.get_effective_size().width);
+            let mut end = 0;
+            if let Some(column) = column_widths.iter().position(|width| {
+                end += width;
+                x < end
+            }) {
+                let descending = state.get_sort() == Some((column, false));
+                state.sort_by(column, descending);
+                state.update(scheduler);
+            }
+        } else if state.get_scroll_y() + y - 1 < state.get_rows().len() {
+            let row = state.get_scroll_y() + y - 1;
+            self.select_row(row, state_tree, callback_tree, scheduler, custom_data);
+        }
+        true
+    }
+
+    fn on_scroll_up(
+        &self,
+        state_tree: &mut StateTree,
+        callback_tree: &mut CallbackTree,
+        scheduler: &mut SchedulerFrontend,
+        custom_data: &mut CustomDataMap,
+    ) -> bool {
+        if self.on_scroll_up_callback(state_tree, callback_tree, scheduler, custom_data) {
+            return true;
+        }
+        let state = state_tree.get_mut(&self.path).as_data_grid_mut();
+        state.set_scroll_y(state.get_scroll_y().saturating_sub(1));
+        state.update(scheduler);
+        true
+    }
+
+    fn on_scroll_down(
+        &self,
+        state_tree: &mut StateTree,
+        callback_tree: &mut CallbackTree,
+        scheduler: &mut SchedulerFrontend,
+        custom_data: &mut CustomDataMap,
+    ) -> bool {
+        if self.on_scroll_down_callback(state_tree, callback_tree, scheduler, custom_data) {
+            return true;
+        }
+        let state = state_tree.get_mut(&self.path).as_data_grid_mut();
+        let visible_rows = state.get_effective_size().height.saturating_sub(1);
+        let max_scroll = state.get_rows().len().saturating_sub(visible_rows);
+        state.set_scroll_y((state.get_scroll_y() + 1).min(max_scroll));
+        state.update(scheduler);
+        true
+    }
+
+    fn get_clone(&self, scheduler: &mut SchedulerFrontend) -> EzObjects {
+        let mut clone = self.clone();
+        let mut new_state = DataGridState::new(self.path.clone(), scheduler);
+        new_state.copy_state_values(self.get_state());
+        clone.state = new_state;
+        EzObjects::DataGrid(clone)
+    }
+}
+impl DataGrid {
+    /// Initialize an instance of this object using the passed config coming from [ez_parser]
+    pub fn from_config(
+        config: Vec<ConfigLine>,
+        id: String,
+        path: String,
+        scheduler: &mut SchedulerFrontend,
+    ) -> Result<Self, ParseError> {
+        let mut obj = DataGrid::new(id, path, scheduler);
+        obj.load_ez_config(config, scheduler)?;
+        Ok(obj)
+    }
+
+    /// Select a row, scroll it into view and call the on_row_select callback if the selection
+    /// changed.
+    fn select_row(
+        &self,
+        row: usize,
+        state_tree: &mut StateTree,
+        callback_tree: &mut CallbackTree,
+        scheduler: &mut SchedulerFrontend,
+        custom_data: &mut CustomDataMap,
+    ) {
+        let state = state_tree.get_mut(&self.path).as_data_grid_mut();
+        let changed = state.get_selected_row() != Some(row);
+        state.set_selected_row(Some(row));
+        let visible_rows = state.get_effective_size().height.saturating_sub(1).max(1);
+        if row < state.get_scroll_y() {
+            state.set_scroll_y(row);
+        } else if row >= state.get_scroll_y() + visible_rows {
+            state.set_scroll_y(row + 1 - visible_rows);
+        }
+        state.update(scheduler);
+        if changed {
+            if let Some(ref mut i) = callback_tree.get_mut(&self.path).obj.on_row_select {
+                i(Context::new(self.path.clone(), state_tree, scheduler, custom_data));
+            }
+        }
+    }
+}
+
+/// Returns a bool representing whether a data grid wants to handle a key itself rather than have
+/// it handled by the global key handler. Up and Down move the selected row, unless the first or
+/// last row is selected, so the user can still leave the data grid with them.
+pub fn captures_key(state: &DataGridState, key: KeyEvent) -> bool {
+    let rows = state.get_rows().len();
+    match (key.code, state.get_selected_row()) {
+        (KeyCode::Up, Some(i)) => i > 0,
+        (KeyCode::Down, Some(i)) => i + 1 < rows,
+        (KeyCode::Down, None) => rows > 0,
+        _ => false,
+    }
+}
+
+/// Get the width each column needs to show its' header (with room for the sort indicator) and
+/// values in full, plus a space to separate it from the next column.
+fn get_natural_widths(state: &DataGridState) -> Vec<usize> {
+    state
+        .get_columns()
+        .iter()
+        .enumerate()
+        .map(|(i, column)| {
+            let longest_value = state
+                .get_rows()
+                .iter()
+                .map(|row| row.get(i).map_or(0, |x| x.chars().count()))
+                .max()
+                .unwrap_or(0);
+            longest_value.max(column.chars().count() + 1) + 1
+        })
+        .collect()
+}
+
+/// Get the width of each column within the width of the grid. If the columns do not fit they are
+/// shrunk in proportion to their' natural width; if they fit, the last column gets the space that
+/// is left.
+pub fn get_column_widths(state: &DataGridState, width: usize) -> Vec<usize> {
+    let mut widths = get_natural_widths(state);
+    let total: usize = widths.iter().sum();
+    if total > width {
+        for column_width in widths.iter_mut() {
+            *column_width = (*column_width * width / total).max(1);
+        }
+    }
+    let total: usize = widths.iter().sum();
+    if let Some(last) = widths.last_mut() {
+        *last = (*last + width).saturating_sub(total);
+    }
+    widths
+}
+
+/// Get the chars of a cell of the given width. Values that do not fit are cut off with an
+/// ellipsis; the last char of the cell is kept empty to separate it from the next column.
+fn fit_cell(value: &str, width: usize) -> Vec<char> {
+    let space = width.saturating_sub(1);
+    let mut chars: Vec<char> = value.chars().collect();
+    if chars.len() > space {
+        chars.truncate(space.saturating_sub(1));
+        if space > 0 {
+            chars.push('…');
+        }
+    }
+    chars.resize(width, ' ');
+    chars
+}
@@ -6,13 +6,16 @@ use std::io::{Error, ErrorKind};
 
 use crossterm::event::{Event, KeyCode, MouseButton, MouseEventKind};
 
+use crate::parser::ez_definition::ConfigLine;
 use crate::parser::load_base_properties;
 use crate::parser::load_common_properties::load_common_property;
+use crate::parser::parse_lang::ParseError;
 use crate::run::definitions::{CallbackTree, Coordinates, Pixel, PixelMap, StateTree};
+use crate::run::select::get_selected_path;
 use crate::scheduler::scheduler::SchedulerFrontend;
 use crate::states::definitions::{
-    AutoScale, HorizontalAlignment, InfiniteSize, Padding, PosHint, SizeHint, StateCoordinates,
-    StateSize, VerticalAlignment,
+    AnchorConfig, AutoScale, DockPosition, HorizontalAlignment, InfiniteSize, Padding, PosHint,
+    SizeHint, StateCoordinates, StateSize, VerticalAlignment,
 };
 use crate::states::dropdown_state::{DropdownState, DroppedDownMenuState};
 use crate::states::ez_state::{EzState, GenericState};
@@ -195,11 +198,19 @@ impl EzObject for Dropdown {
         if consumed {
             return consumed;
         }
-        let modal_id = format!("modal");
-        let modal_path = format!("/root/modal");
-        if state_tree.as_layout().has_modal() {
+        // The menu opens on top of any open modals, but only from a dropdown in the modal on top
+        let root_state = state_tree.as_layout();
+        if root_state.has_modal()
+            && !self.path.starts_with(&format!(
+                "{}/",
+                root_state.get_modal().as_ez_object().get_path()
+            ))
+        {
             return false;
         }
+        let modal_id = root_state.get_next_modal_id();
+        let modal_path = format!("/root/{}", modal_id);
+        let selection = get_selected_path(state_tree);
 
         let state = state_tree.get_mut(&self.get_path()).as_dropdown_mut();
         state.disabled.set(true);
@@ -238,6 +249,14 @@ impl EzObject for Dropdown {
             ),
             scheduler.new_bool_property(format!("{}/disabled", modal_path).as_str(), false),
             scheduler.new_usize_property(format!("{}/selection_order", modal_path).as_str(), 0),
+            scheduler.new_bool_property(format!("{}/collapsed", modal_path).as_str(), false),
+            scheduler.new_dock_position_property(
+                format!("{}/dock", modal_path).as_str(),
+                DockPosition::Center,
+            ),
+            scheduler.new_f64_property(format!("{}/weight", modal_path).as_str(), 0.0),
+            scheduler.new_usize_property(format!("{}/z_index", modal_path).as_str(), 0),
+            AnchorConfig::new(modal_path.clone(), scheduler),
             state.get_absolute_position(),
             PosHint::new(None, None, modal_path.clone(), scheduler),
             0,
@@ -256,7 +275,7 @@ impl EzObject for Dropdown {
         };
         let root_state = state_tree.as_layout_mut();
         root_state.update(scheduler);
-        let new_states = root_state.open_modal(EzObjects::DroppedDownMenu(new_modal));
+        let new_states = root_state.open_modal(EzObjects::DroppedDownMenu(new_modal), selection);
         for (path, new_state) in new_states {
             state_tree.add_node(path, new_state);
             scheduler.overwrite_callback_config(&modal_path, CallbackConfig::default());
@@ -293,16 +312,14 @@ impl EzObject for Dropdown {
 impl Dropdown {
     /// Initialize an instance of this object using the passed config coming from [ez_parser]
     pub fn from_config(
-        config: Vec<String>,
+        config: Vec<ConfigLine>,
         id: String,
         path: String,
         scheduler: &mut SchedulerFrontend,
-        file: String,
-        line: usize,
-    ) -> Self {
+    ) -> Result<Self, ParseError> {
         let mut obj = Dropdown::new(id, path, scheduler);
-        obj.load_ez_config(config, scheduler, file, line).unwrap();
-        obj
+        obj.load_ez_config(config, scheduler)?;
+        Ok(obj)
     }
 }
 
@@ -499,9 +516,8 @@ impl DroppedDownMenu {
             .clone();
         let state = state_tree.get_mut(&parent).as_dropdown_mut();
         state.set_choice(&choice);
-        let state = state_tree.as_layout_mut();
-        state.dismiss_modal(scheduler);
-        state.update(scheduler);
+        state.set_disabled(false);
+        scheduler.dismiss_modal(state_tree);
         if let Some(ref mut i) = callback_tree.get_mut(&parent).obj.on_value_change {
             let context = Context::new(parent.clone(), state_tree, scheduler, custom_data);
             i(context);
@@ -553,8 +569,7 @@ impl DroppedDownMenu {
                 }
             }
         } else {
-            let state = state_tree.as_layout_mut();
-            state.dismiss_modal(scheduler);
+            scheduler.dismiss_modal(state_tree);
             let state = state_tree.get_mut(&parent).as_dropdown_mut();
             state.set_disabled(false);
         }
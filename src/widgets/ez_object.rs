@@ -5,7 +5,11 @@ use std::io::Error;
 
 use crossterm::event::Event;
 
-use crate::run::definitions::{CallbackTree, Coordinates, IsizeCoordinates, PixelMap, StateTree};
+use crate::parser::ez_definition::ConfigLine;
+use crate::parser::parse_lang::ParseError;
+use crate::run::definitions::{
+    CallbackTree, Coordinates, IsizeCoordinates, Pixel, PixelMap, StateTree,
+};
 use crate::run::tree::ViewTree;
 use crate::scheduler::scheduler::SchedulerFrontend;
 use crate::states::ez_state::{EzState, GenericState};
@@ -13,14 +17,23 @@ use crate::widgets::button::Button;
 use crate::widgets::canvas::Canvas;
 use crate::widgets::checkbox::Checkbox;
 use crate::widgets::dropdown::{Dropdown, DroppedDownMenu};
+use crate::widgets::helper_functions::{apply_interaction_style, apply_selection_style};
 use crate::widgets::label::Label;
 use crate::widgets::layout::layout::Layout;
 use crate::widgets::progress_bar::ProgressBar;
 use crate::widgets::radio_button::RadioButton;
+use crate::widgets::rating::Rating;
 use crate::widgets::slider::Slider;
+use crate::widgets::stepper::Stepper;
+use crate::widgets::data_grid::DataGrid;
+use crate::widgets::list_view::ListView;
+use crate::widgets::text_box::TextBox;
 use crate::widgets::text_input::TextInput;
+use crate::widgets::ticker::Ticker;
+use crate::widgets::qr_code::QrCode;
+use crate::widgets::heatmap::Heatmap;
 use crate::Context;
-use crate::scheduler::definitions::CustomDataMap;
+use crate::scheduler::definitions::{CustomDataMap, DragPayload};
 
 /// Enum with variants representing Layouts and each widget type. A layout is not considered a
 /// widget, so this enum gathers widgets and layouts in one place, as they do have methods in
@@ -37,8 +50,16 @@ pub enum EzObjects {
     DroppedDownMenu(DroppedDownMenu),
     RadioButton(RadioButton),
     TextInput(TextInput),
+    TextBox(TextBox),
+    DataGrid(DataGrid),
+    ListView(ListView),
     Slider(Slider),
     ProgressBar(ProgressBar),
+    Stepper(Stepper),
+    Ticker(Ticker),
+    QrCode(QrCode),
+    Heatmap(Heatmap),
+    Rating(Rating),
 }
 impl EzObjects {
     /// Get an EzObjects based on a string containing the base widget type.
@@ -59,17 +80,95 @@ impl EzObjects {
                 EzObjects::RadioButton(RadioButton::from_state(id, path, scheduler, state))
             }
             "TextInput" => EzObjects::TextInput(TextInput::from_state(id, path, scheduler, state)),
+            "TextBox" => EzObjects::TextBox(TextBox::from_state(id, path, scheduler, state)),
+            "DataGrid" => EzObjects::DataGrid(DataGrid::from_state(id, path, scheduler, state)),
+            "ListView" => EzObjects::ListView(ListView::from_state(id, path, scheduler, state)),
             "Dropdown" => EzObjects::Dropdown(Dropdown::from_state(id, path, scheduler, state)),
             "Slider" => EzObjects::Slider(Slider::from_state(id, path, scheduler, state)),
             "ProgressBar" => {
                 EzObjects::ProgressBar(ProgressBar::from_state(id, path, scheduler, state))
             }
+            "Rating" => EzObjects::Rating(Rating::from_state(id, path, scheduler, state)),
+            "Stepper" => EzObjects::Stepper(Stepper::from_state(id, path, scheduler, state)),
+            "Ticker" => EzObjects::Ticker(Ticker::from_state(id, path, scheduler, state)),
+            "QrCode" => EzObjects::QrCode(QrCode::from_state(id, path, scheduler, state)),
+            "Heatmap" => EzObjects::Heatmap(Heatmap::from_state(id, path, scheduler, state)),
             _ => panic!(
                 "Cannot create widget from string \"{}\". This widget type does not exist.",
                 type_name
             ),
         }
     }
+
+    /// Create a copy of this object at a new path. If this is a layout its' children are copied
+    /// as well, keeping their order. The states of the copies must already exist in the state tree
+    /// under the new path, see [SchedulerFrontend.clone_widget].
+    pub fn clone_to_path(
+        &self,
+        id: String,
+        path: String,
+        state_tree: &StateTree,
+        scheduler: &mut SchedulerFrontend,
+    ) -> Self {
+        let state = state_tree.get(&path).obj.clone();
+        match self {
+            EzObjects::Layout(i) => {
+                let mut layout = Layout::from_state(id, path.clone(), scheduler, state);
+                for child in i.get_children() {
+                    let child_id = child.as_ez_object().get_id();
+                    let child_path = format!("{}/{}", path, child_id);
+                    let new_child =
+                        child.clone_to_path(child_id.clone(), child_path, state_tree, scheduler);
+                    layout.child_lookup.insert(child_id, layout.children.len());
+                    layout.children.push(new_child);
+                }
+                EzObjects::Layout(layout)
+            }
+            EzObjects::Label(_) => EzObjects::Label(Label::from_state(id, path, scheduler, state)),
+            EzObjects::Button(_) => {
+                EzObjects::Button(Button::from_state(id, path, scheduler, state))
+            }
+            EzObjects::Canvas(_) => {
+                EzObjects::Canvas(Canvas::from_state(id, path, scheduler, state))
+            }
+            EzObjects::Checkbox(_) => {
+                EzObjects::Checkbox(Checkbox::from_state(id, path, scheduler, state))
+            }
+            EzObjects::Dropdown(_) => {
+                EzObjects::Dropdown(Dropdown::from_state(id, path, scheduler, state))
+            }
+            EzObjects::RadioButton(_) => {
+                EzObjects::RadioButton(RadioButton::from_state(id, path, scheduler, state))
+            }
+            EzObjects::TextInput(_) => {
+                EzObjects::TextInput(TextInput::from_state(id, path, scheduler, state))
+            }
+            EzObjects::TextBox(_) => {
+                EzObjects::TextBox(TextBox::from_state(id, path, scheduler, state))
+            }
+            EzObjects::DataGrid(_) => {
+                EzObjects::DataGrid(DataGrid::from_state(id, path, scheduler, state))
+            }
+            EzObjects::ListView(_) => {
+                EzObjects::ListView(ListView::from_state(id, path, scheduler, state))
+            }
+            EzObjects::Slider(_) => {
+                EzObjects::Slider(Slider::from_state(id, path, scheduler, state))
+            }
+            EzObjects::ProgressBar(_) => {
+                EzObjects::ProgressBar(ProgressBar::from_state(id, path, scheduler, state))
+            }
+            EzObjects::Rating(_) => {
+                EzObjects::Rating(Rating::from_state(id, path, scheduler, state))
+            }
+            EzObjects::Stepper(_) => EzObjects::Stepper(Stepper::from_state(id, path, scheduler, state)),
+            EzObjects::Ticker(_) => EzObjects::Ticker(Ticker::from_state(id, path, scheduler, state)),
+            EzObjects::QrCode(_) => EzObjects::QrCode(QrCode::from_state(id, path, scheduler, state)),
+            EzObjects::Heatmap(_) => EzObjects::Heatmap(Heatmap::from_state(id, path, scheduler, state)),
+            EzObjects::DroppedDownMenu(_) => panic!("Cannot clone a dropped down menu: {}", path),
+        }
+    }
+
     /// Cast this enum to a generic [EzObject] trait object. As this trait is implemented by both
     /// [layout] and [widget], it is safe to call on all variants.
     pub fn as_ez_object(&self) -> &dyn EzObject {
@@ -83,8 +182,16 @@ impl EzObjects {
             EzObjects::DroppedDownMenu(i) => i,
             EzObjects::RadioButton(i) => i,
             EzObjects::TextInput(i) => i,
+            EzObjects::TextBox(i) => i,
+            EzObjects::DataGrid(i) => i,
+            EzObjects::ListView(i) => i,
             EzObjects::Slider(i) => i,
             EzObjects::ProgressBar(i) => i,
+            EzObjects::Rating(i) => i,
+            EzObjects::Stepper(i) => i,
+            EzObjects::Ticker(i) => i,
+            EzObjects::QrCode(i) => i,
+            EzObjects::Heatmap(i) => i,
         }
     }
 
@@ -101,8 +208,16 @@ impl EzObjects {
             EzObjects::DroppedDownMenu(i) => i,
             EzObjects::RadioButton(i) => i,
             EzObjects::TextInput(i) => i,
+            EzObjects::TextBox(i) => i,
+            EzObjects::DataGrid(i) => i,
+            EzObjects::ListView(i) => i,
             EzObjects::Slider(i) => i,
             EzObjects::ProgressBar(i) => i,
+            EzObjects::Rating(i) => i,
+            EzObjects::Stepper(i) => i,
+            EzObjects::Ticker(i) => i,
+            EzObjects::QrCode(i) => i,
+            EzObjects::Heatmap(i) => i,
         }
     }
 
@@ -213,6 +328,96 @@ impl EzObjects {
         }
     }
 
+    /// Cast this state as a Rating widget ref, you must be sure you have one.
+    pub fn as_rating(&self) -> &Rating {
+        if let EzObjects::Rating(i) = self {
+            i
+        } else {
+            panic!("wrong EzObject.")
+        }
+    }
+
+    /// Cast this state as a mutable Rating widget ref, you must be sure you have one.
+    pub fn as_rating_mut(&mut self) -> &mut Rating {
+        if let EzObjects::Rating(i) = self {
+            i
+        } else {
+            panic!("wrong EzObject.")
+        }
+    }
+
+    /// Cast this state as a Stepper widget ref, you must be sure you have one.
+    pub fn as_stepper(&self) -> &Stepper {
+        if let EzObjects::Stepper(i) = self {
+            i
+        } else {
+            panic!("wrong EzObject.")
+        }
+    }
+
+    /// Cast this state as a mutable Stepper widget ref, you must be sure you have one.
+    pub fn as_stepper_mut(&mut self) -> &mut Stepper {
+        if let EzObjects::Stepper(i) = self {
+            i
+        } else {
+            panic!("wrong EzObject.")
+        }
+    }
+
+    /// Cast this state as a Ticker widget ref, you must be sure you have one.
+    pub fn as_ticker(&self) -> &Ticker {
+        if let EzObjects::Ticker(i) = self {
+            i
+        } else {
+            panic!("wrong EzObject.")
+        }
+    }
+
+    /// Cast this state as a mutable Ticker widget ref, you must be sure you have one.
+    pub fn as_ticker_mut(&mut self) -> &mut Ticker {
+        if let EzObjects::Ticker(i) = self {
+            i
+        } else {
+            panic!("wrong EzObject.")
+        }
+    }
+
+    /// Cast this state as a QrCode widget ref, you must be sure you have one.
+    pub fn as_qr_code(&self) -> &QrCode {
+        if let EzObjects::QrCode(i) = self {
+            i
+        } else {
+            panic!("wrong EzObject.")
+        }
+    }
+
+    /// Cast this state as a mutable QrCode widget ref, you must be sure you have one.
+    pub fn as_qr_code_mut(&mut self) -> &mut QrCode {
+        if let EzObjects::QrCode(i) = self {
+            i
+        } else {
+            panic!("wrong EzObject.")
+        }
+    }
+
+    /// Cast this state as a Heatmap widget ref, you must be sure you have one.
+    pub fn as_heatmap(&self) -> &Heatmap {
+        if let EzObjects::Heatmap(i) = self {
+            i
+        } else {
+            panic!("wrong EzObject.")
+        }
+    }
+
+    /// Cast this state as a mutable Heatmap widget ref, you must be sure you have one.
+    pub fn as_heatmap_mut(&mut self) -> &mut Heatmap {
+        if let EzObjects::Heatmap(i) = self {
+            i
+        } else {
+            panic!("wrong EzObject.")
+        }
+    }
+
     /// Cast this as a Checkbox widget ref, you must be sure you have one.
     pub fn as_checkbox(&self) -> &Checkbox {
         if let EzObjects::Checkbox(i) = self {
@@ -302,6 +507,60 @@ impl EzObjects {
             panic!("wrong EzObject.")
         }
     }
+
+    /// Cast this state as a TextBox widget ref, you must be sure you have one.
+    pub fn as_text_box(&self) -> &TextBox {
+        if let EzObjects::TextBox(i) = self {
+            i
+        } else {
+            panic!("wrong EzObject.")
+        }
+    }
+
+    /// Cast this state as a mutable TextBox widget ref, you must be sure you have one.
+    pub fn as_text_box_mut(&mut self) -> &mut TextBox {
+        if let EzObjects::TextBox(i) = self {
+            i
+        } else {
+            panic!("wrong EzObject.")
+        }
+    }
+
+    /// Cast this state as a DataGrid widget ref, you must be sure you have one.
+    pub fn as_data_grid(&self) -> &DataGrid {
+        if let EzObjects::DataGrid(i) = self {
+            i
+        } else {
+            panic!("wrong EzObject.")
+        }
+    }
+
+    /// Cast this state as a mutable DataGrid widget ref, you must be sure you have one.
+    pub fn as_data_grid_mut(&mut self) -> &mut DataGrid {
+        if let EzObjects::DataGrid(i) = self {
+            i
+        } else {
+            panic!("wrong EzObject.")
+        }
+    }
+
+    /// Cast this state as a ListView widget ref, you must be sure you have one.
+    pub fn as_list_view(&self) -> &ListView {
+        if let EzObjects::ListView(i) = self {
+            i
+        } else {
+            panic!("wrong EzObject.")
+        }
+    }
+
+    /// Cast this state as a mutable ListView widget ref, you must be sure you have one.
+    pub fn as_list_view_mut(&mut self) -> &mut ListView {
+        if let EzObjects::ListView(i) = self {
+            i
+        } else {
+            panic!("wrong EzObject.")
+        }
+    }
 }
 
 /// Trait representing both widgets and layouts implementing methods which are common to all UI
@@ -309,35 +568,37 @@ impl EzObjects {
 /// (or don't care), cast the EzObjects enum into this type using [az_ez_object].
 pub trait EzObject {
     /// Accepts config lines from the ez_parser module and prepares them to be loaded by
-    /// load_ez_parameter below.
+    /// load_ez_parameter below. Returns a [ParseError] pointing at the value of the first
+    /// parameter that could not be loaded.
     fn load_ez_config(
         &mut self,
-        config: Vec<String>,
-        scheduler: &mut SchedulerFrontend,
-        file: String,
-        line: usize,
-    ) -> Result<(), Error> {
-        for (i, line_str) in config.iter().enumerate() {
-            let total_line = line + i + 1;
-            let (parameter_name, parameter_value) = line_str.split_once(':').unwrap_or_else(|| {
-                panic!(
-                    "Config parameter must contain a \":\", \
-                e.g. \"parameter: value\". This does not contain one: \"{}\"",
-                    line_str
+        config: Vec<ConfigLine>,
+        scheduler: &mut SchedulerFrontend,
+    ) -> Result<(), ParseError> {
+        for line in config.iter() {
+            let (parameter_name, parameter_value) = line.text.split_once(':').ok_or_else(|| {
+                line.error(
+                    line.text.chars().count(),
+                    "Config parameter must contain a \":\", e.g. \"parameter: value\"."
+                        .to_string(),
                 )
-            });
+            })?;
             self.load_ez_parameter(
                 parameter_name.to_string(),
                 parameter_value.to_string(),
                 scheduler,
             )
-            .unwrap_or_else(|e| {
-                panic!(
-                    "Error on line {} of file: \"{}\".\n. \
-                Could not load property \"{}\" with error: \"{}\"",
-                    total_line, file, line_str, e
+            .map_err(|e| {
+                let indentation = parameter_value.len() - parameter_value.trim_start().len();
+                line.error(
+                    parameter_name.chars().count() + 1 + indentation,
+                    format!(
+                        "Could not load property \"{}\": {}",
+                        parameter_name.trim(),
+                        e
+                    ),
                 )
-            });
+            })?;
         }
         Ok(())
     }
@@ -380,10 +641,18 @@ pub trait EzObject {
     fn redraw(&self, view_tree: &mut ViewTree, state_tree: &mut StateTree) {
         let state = state_tree.get(&self.get_path()).as_generic();
         let pos = state.get_absolute_position();
-        let content = self.get_contents(state_tree);
+        let content = self.get_styled_contents(state_tree);
         view_tree.write_content(pos.as_coordinates(), content);
     }
 
+    /// Get the content of a single cell of this widget for a partial redraw (see
+    /// [SchedulerFrontend.update_widget_cells]). The position is relative to the content of the
+    /// widget, i.e. excluding border and padding. Widgets that can produce a single cell cheaply
+    /// overwrite this; the default returns None, in which case the whole widget is redrawn.
+    fn get_cell(&self, _state_tree: &StateTree, _pos: Coordinates) -> Option<Pixel> {
+        None
+    }
+
     /// Set the content for a widget manually. This is not implemented for most widgets, as they
     /// get their content from their state. E.g. a label gets content from its' current text.
     fn set_contents(&mut self, _contents: PixelMap) {
@@ -397,6 +666,15 @@ pub trait EzObject {
     /// gets its' content from its' text, a checkbox from whether it has been checked, etc.
     fn get_contents(&self, state_tree: &mut StateTree) -> PixelMap;
 
+    /// Gets the visual content for this widget with its' hover or pressed colors (see
+    /// [InteractionState]) and its' selection shown according to its' [SelectionStyle]. Used by
+    /// layouts to draw their children.
+    fn get_styled_contents(&self, state_tree: &mut StateTree) -> PixelMap {
+        let contents = self.get_contents(state_tree);
+        let state = state_tree.get(&self.get_path()).as_generic();
+        apply_selection_style(apply_interaction_style(contents, state), state)
+    }
+
     /// Optionally consume an event that was passed to this widget. Return true if the event should
     /// be considered consumed. Simply consults the keymap by default, but can be overloaded for
     /// more complex circumstances.
@@ -466,6 +744,54 @@ pub trait EzObject {
         false
     }
 
+    /// Called on an object when text is pasted while it is selected. This default implementation
+    /// only calls the appropriate callback. Objects can overwrite this function but must remember
+    /// to also call the callback.
+    fn on_paste(
+        &self,
+        state_tree: &mut StateTree,
+        callback_tree: &mut CallbackTree,
+        scheduler: &mut SchedulerFrontend,
+        text: String,
+        custom_data: &mut CustomDataMap,
+    ) -> bool {
+        self.on_paste_callback(state_tree, callback_tree, scheduler, text, custom_data)
+    }
+
+    /// Call the bound callback if there is any. This method can always be called safely. Used to
+    /// prevent a lot of duplicate ```if let Some(i)``` code.
+    fn on_paste_callback(
+        &self,
+        state_tree: &mut StateTree,
+        callback_tree: &mut CallbackTree,
+        scheduler: &mut SchedulerFrontend,
+        text: String,
+        custom_data: &mut CustomDataMap,
+    ) -> bool {
+        if let Some(ref mut i) = callback_tree.get_mut(&self.get_path()).obj.on_paste {
+            return i(
+                Context::new(self.get_path(), state_tree, scheduler, custom_data),
+                text,
+            );
+        };
+        false
+    }
+
+    /// Called on an object when the left mouse button is pushed down on it. The click itself is
+    /// handled when the button is released (see [on_left_mouse_click]). This default
+    /// implementation does nothing; objects that distinguish pressing from releasing (e.g.
+    /// buttons) can overwrite it.
+    fn on_left_mouse_down(
+        &self,
+        _state_tree: &mut StateTree,
+        _callback_tree: &mut CallbackTree,
+        _scheduler: &mut SchedulerFrontend,
+        _mouse_pos: Coordinates,
+        _custom_data: &mut CustomDataMap,
+    ) -> bool {
+        false
+    }
+
     /// Called on an object when it is left clicked. This default implementation only calls the
     /// appropriate callback. Objects can overwrite this function but must remember to also
     /// call the callback.
@@ -724,6 +1050,83 @@ pub trait EzObject {
         };
         false
     }
+    /// Called on an object when a drag starts on it, before [on_drag]. Returns the [DragPayload]
+    /// to carry if the object starts a drag and drop. This default implementation only calls the
+    /// appropriate callback. Objects can overwrite this function but must remember to also call
+    /// the callback.
+    fn on_drag_start(
+        &self,
+        state_tree: &mut StateTree,
+        callback_tree: &mut CallbackTree,
+        scheduler: &mut SchedulerFrontend,
+        mouse_pos: IsizeCoordinates,
+        custom_data: &mut CustomDataMap,
+    ) -> Option<DragPayload> {
+        self.on_drag_start_callback(state_tree, callback_tree, scheduler, mouse_pos, custom_data)
+    }
+
+    /// Call the bound callback if there is any. This method can always be called safely. Used to
+    /// prevent a lot of duplicate ```if let Some(i)``` code.
+    fn on_drag_start_callback(
+        &self,
+        state_tree: &mut StateTree,
+        callback_tree: &mut CallbackTree,
+        scheduler: &mut SchedulerFrontend,
+        mouse_pos: IsizeCoordinates,
+        custom_data: &mut CustomDataMap,
+    ) -> Option<DragPayload> {
+        if let Some(ref mut i) = callback_tree.get_mut(&self.get_path()).obj.on_drag_start {
+            return i(
+                Context::new(self.get_path(), state_tree, scheduler, custom_data),
+                mouse_pos,
+            );
+        };
+        None
+    }
+
+    /// Called on an object when a [DragPayload] is dropped on it. This default implementation
+    /// only calls the appropriate callback. Objects can overwrite this function but must remember
+    /// to also call the callback.
+    fn on_drop(
+        &self,
+        state_tree: &mut StateTree,
+        callback_tree: &mut CallbackTree,
+        scheduler: &mut SchedulerFrontend,
+        payload: DragPayload,
+        mouse_pos: IsizeCoordinates,
+        custom_data: &mut CustomDataMap,
+    ) -> bool {
+        self.on_drop_callback(
+            state_tree,
+            callback_tree,
+            scheduler,
+            payload,
+            mouse_pos,
+            custom_data,
+        )
+    }
+
+    /// Call the bound callback if there is any. This method can always be called safely. Used to
+    /// prevent a lot of duplicate ```if let Some(i)``` code.
+    fn on_drop_callback(
+        &self,
+        state_tree: &mut StateTree,
+        callback_tree: &mut CallbackTree,
+        scheduler: &mut SchedulerFrontend,
+        payload: DragPayload,
+        mouse_pos: IsizeCoordinates,
+        custom_data: &mut CustomDataMap,
+    ) -> bool {
+        if let Some(ref mut i) = callback_tree.get_mut(&self.get_path()).obj.on_drop {
+            return i(
+                Context::new(self.get_path(), state_tree, scheduler, custom_data),
+                payload,
+                mouse_pos,
+            );
+        };
+        false
+    }
+
     /// Called on an object when it is mouse scrolled up. This default implementation only calls the
     /// appropriate callback. Objects can overwrite this function but must remember to also call
     /// the callback.
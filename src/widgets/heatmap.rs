@@ -0,0 +1,306 @@
+//! A widget that shows a grid of values as colored cells, e.g. to monitor a matrix of metrics or
+//! to show activity per day in a calendar. Values are mapped to a color scale between a low and a
+//! high color; cells can show a label or their value. Single cells can be redrawn without
+//! redrawing the whole heatmap, see [HeatmapState.update_value].
+use std::io::{Error, ErrorKind};
+
+use crossterm::style::Color;
+
+use crate::parser::ez_definition::ConfigLine;
+use crate::parser::load_base_properties;
+use crate::parser::load_common_properties::load_common_property;
+use crate::parser::parse_lang::ParseError;
+use crate::run::definitions::{Coordinates, Pixel, PixelMap, StateTree};
+use crate::scheduler::scheduler::SchedulerFrontend;
+use crate::states::colors::{desaturate, readable_fg};
+use crate::states::ez_state::{EzState, GenericState};
+use crate::states::heatmap_state::HeatmapState;
+use crate::widgets::ez_object::{EzObject, EzObjects};
+use crate::widgets::helper_functions::{add_border, add_padding};
+
+#[derive(Clone, Debug)]
+pub struct Heatmap {
+    /// ID of the widget, used to construct [path]
+    pub id: String,
+
+    /// Full path to this widget, e.g. "/root_layout/layout_2/THIS_ID"
+    pub path: String,
+
+    /// Runtime state of this widget, see [HeatmapState] and [State]
+    pub state: HeatmapState,
+}
+
+impl Heatmap {
+    pub fn new(id: String, path: String, scheduler: &mut SchedulerFrontend) -> Self {
+        Heatmap {
+            id,
+            path: path.clone(),
+            state: HeatmapState::new(path, scheduler),
+        }
+    }
+
+    pub fn from_state(
+        id: String,
+        path: String,
+        _scheduler: &mut SchedulerFrontend,
+        state: EzState,
+    ) -> Self {
+        Heatmap {
+            id,
+            path,
+            state: state.as_heatmap().to_owned(),
+        }
+    }
+}
+
+impl EzObject for Heatmap {
+    fn load_ez_parameter(
+        &mut self,
+        parameter_name: String,
+        parameter_value: String,
+        scheduler: &mut SchedulerFrontend,
+    ) -> Result<(), Error> {
+        let consumed =
+            load_common_property(&parameter_name, parameter_value.clone(), self, scheduler)?;
+        if consumed {
+            return Ok(());
+        }
+        match parameter_name.as_str() {
+            "values" => {
+                let mut values = Vec::new();
+                for row in parameter_value.split(';').filter(|x| !x.trim().is_empty()) {
+                    let mut row_values = Vec::new();
+                    for value in row.split(',') {
+                        row_values.push(value.trim().parse::<f64>().map_err(|_| {
+                            Error::new(
+                                ErrorKind::InvalidData,
+                                format!(
+                                    "Heatmap values must be numbers separated by commas, with \
+                                    rows separated by semicolons (e.g. \"1, 2; 3, 4\"), not: {}",
+                                    parameter_value.trim()
+                                ),
+                            )
+                        })?);
+                    }
+                    values.push(row_values);
+                }
+                self.state.set_values(values)
+            }
+            "labels" => self.state.set_labels(
+                parameter_value
+                    .split(';')
+                    .filter(|x| !x.trim().is_empty())
+                    .map(|row| row.split(',').map(|x| x.trim().to_string()).collect())
+                    .collect(),
+            ),
+            "low_color" => load_base_properties::load_color_property(
+                parameter_value.trim(),
+                scheduler,
+                self.path.clone(),
+                &parameter_name,
+                self.get_state_mut(),
+            )?,
+            "high_color" => load_base_properties::load_color_property(
+                parameter_value.trim(),
+                scheduler,
+                self.path.clone(),
+                &parameter_name,
+                self.get_state_mut(),
+            )?,
+            "auto_range" => load_base_properties::load_bool_property(
+                parameter_value.trim(),
+                scheduler,
+                self.path.clone(),
+                &parameter_name,
+                self.get_state_mut(),
+            )?,
+            "min_value" => load_base_properties::load_f64_property(
+                parameter_value.trim(),
+                scheduler,
+                self.path.clone(),
+                &parameter_name,
+                self.get_state_mut(),
+            )?,
+            "max_value" => load_base_properties::load_f64_property(
+                parameter_value.trim(),
+                scheduler,
+                self.path.clone(),
+                &parameter_name,
+                self.get_state_mut(),
+            )?,
+            "show_values" => load_base_properties::load_bool_property(
+                parameter_value.trim(),
+                scheduler,
+                self.path.clone(),
+                &parameter_name,
+                self.get_state_mut(),
+            )?,
+            "decimals" => load_base_properties::load_usize_property(
+                parameter_value.trim(),
+                scheduler,
+                self.path.clone(),
+                &parameter_name,
+                self.get_state_mut(),
+            )?,
+            "cell_width" => load_base_properties::load_usize_property(
+                parameter_value.trim(),
+                scheduler,
+                self.path.clone(),
+                &parameter_name,
+                self.get_state_mut(),
+            )?,
+            "cell_height" => load_base_properties::load_usize_property(
+                parameter_value.trim(),
+                scheduler,
+                self.path.clone(),
+                &parameter_name,
+                self.get_state_mut(),
+            )?,
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("Invalid parameter name for heatmap: {}", parameter_name),
+                ))
+            }
+        }
+        Ok(())
+    }
+
+    fn set_id(&mut self, id: &str) {
+        self.id = id.to_string()
+    }
+
+    fn get_id(&self) -> String {
+        self.id.clone()
+    }
+
+    fn set_path(&mut self, id: &str) {
+        self.id = id.to_string()
+    }
+
+    fn get_path(&self) -> String {
+        self.path.clone()
+    }
+
+    fn get_state(&self) -> EzState {
+        EzState::Heatmap(self.state.clone())
+    }
+
+    fn get_state_mut(&mut self) -> &mut dyn GenericState {
+        &mut self.state
+    }
+
+    fn get_contents(&self, state_tree: &mut StateTree) -> PixelMap {
+        let state = state_tree.get_mut(&self.get_path()).as_heatmap_mut();
+        let (rows, columns) = state.get_grid_size();
+        if state.get_auto_scale().get_auto_scale_width() {
+            state.set_effective_width(columns * state.get_cell_width());
+        }
+        if state.get_auto_scale().get_auto_scale_height() {
+            state.set_effective_height(rows * state.get_cell_height());
+        }
+        let width = state.get_effective_size().width;
+        let height = state.get_effective_size().height;
+
+        let mut contents = PixelMap::new();
+        for x in 0..width {
+            let mut new_y = Vec::new();
+            for y in 0..height {
+                new_y.push(get_heatmap_pixel(state, Coordinates::new(x, y)));
+            }
+            contents.push(new_y);
+        }
+        if state.get_border_config().get_border() {
+            contents = add_border(
+                contents,
+                state.get_border_config(),
+                state.get_color_config(),
+            );
+        }
+        let state = state_tree.get(&self.get_path()).as_heatmap();
+        let parent_colors = state_tree
+            .get(self.get_path().rsplit_once('/').unwrap().0)
+            .as_generic()
+            .get_color_config();
+        contents = add_padding(
+            contents,
+            state.get_padding(),
+            parent_colors.get_bg_color(),
+            parent_colors.get_fg_color(),
+        );
+        contents
+    }
+
+    fn get_cell(&self, state_tree: &StateTree, pos: Coordinates) -> Option<Pixel> {
+        let state = state_tree.get(&self.get_path()).as_heatmap();
+        Some(get_heatmap_pixel(state, pos))
+    }
+
+    fn get_clone(&self, scheduler: &mut SchedulerFrontend) -> EzObjects {
+        let mut clone = self.clone();
+        let mut new_state = HeatmapState::new(self.path.clone(), scheduler);
+        new_state.copy_state_values(self.get_state());
+        clone.state = new_state;
+        EzObjects::Heatmap(clone)
+    }
+}
+impl Heatmap {
+    /// Initialize an instance of this object using the passed config coming from [ez_parser]
+    pub fn from_config(
+        config: Vec<ConfigLine>,
+        id: String,
+        path: String,
+        scheduler: &mut SchedulerFrontend,
+    ) -> Result<Self, ParseError> {
+        let mut obj = Heatmap::new(id, path, scheduler);
+        obj.load_ez_config(config, scheduler)?;
+        Ok(obj)
+    }
+}
+
+/// Get the pixel at a position in the content of a heatmap. Shared by full and partial redraws,
+/// so both draw a cell the same way. The text of a cell is centered on its' middle row.
+fn get_heatmap_pixel(state: &HeatmapState, pos: Coordinates) -> Pixel {
+    let (fg_color, bg_color) = if state.get_disabled() {
+        (
+            state.get_color_config().get_disabled_fg_color(),
+            state.get_color_config().get_disabled_bg_color(),
+        )
+    } else {
+        (
+            state.get_color_config().get_fg_color(),
+            state.get_color_config().get_bg_color(),
+        )
+    };
+    let (row, column) = match state.get_cell_at(pos) {
+        Some(i) => i,
+        None => return Pixel::new(" ".to_string(), fg_color, bg_color),
+    };
+    let mut color: Color = state
+        .get_value(row, column)
+        .and_then(|x| state.get_value_color(x))
+        .unwrap_or(bg_color);
+    if state.get_disabled() {
+        color = desaturate(color, 1.0);
+    }
+
+    let (cell_width, cell_height) = (state.get_cell_width(), state.get_cell_height());
+    let offset = state.get_grid_offset();
+    let x_in_cell = (pos.x - offset.x) % cell_width;
+    let y_in_cell = (pos.y - offset.y) % cell_height;
+    let text: Vec<char> = state
+        .get_cell_text(row, column)
+        .chars()
+        .take(cell_width)
+        .collect();
+    let start = (cell_width - text.len()) / 2;
+    let symbol = if y_in_cell == (cell_height - 1) / 2
+        && x_in_cell >= start
+        && x_in_cell < start + text.len()
+    {
+        text[x_in_cell - start]
+    } else {
+        ' '
+    };
+    Pixel::new(symbol.to_string(), readable_fg(fg_color, color), color)
+}
@@ -1,11 +1,11 @@
 use crate::run::definitions::{IsizeCoordinates, Pixel, PixelMap, Size};
 use crate::states::definitions::{
-    BorderConfig, ColorConfig, HorizontalAlignment, Padding, ScrollingConfig, VerticalAlignment,
+    BorderConfig, ColorConfig, HorizontalAlignment, Padding, ScrollingConfig, SelectionStyle,
+    StyledLine, StyledSpan, VerticalAlignment,
 };
 use crate::states::ez_state::{EzState, GenericState};
 use crossterm::style::Color;
 use unicode_segmentation::UnicodeSegmentation;
-use crate::parser::parse_properties::parse_color_property;
 
 /// Resize an object according to its' size hint values.
 pub fn resize_with_size_hint(state: &mut EzState, parent_width: usize, parent_height: usize) {
@@ -57,6 +57,128 @@ pub fn reposition_with_pos_hint(
     }
 }
 
+/// Set the position of a child of a float layout that has an [Anchor], keeping it at an edge or
+/// corner of the terminal. The parent position is the absolute position where the content of the
+/// float layout starts; the child is kept within the float layout.
+pub fn reposition_with_anchor(
+    parent_position: IsizeCoordinates,
+    parent_size: Size,
+    terminal_size: Size,
+    child_size: Size,
+    child_state: &mut dyn GenericState,
+) {
+    let anchor_config = child_state.get_anchor_config();
+    let (halign, valign) = match anchor_config.get_anchor().get_alignment() {
+        Some(alignment) => alignment,
+        None => return,
+    };
+    let offset_x = anchor_config.get_anchor_offset_x() as isize;
+    let offset_y = anchor_config.get_anchor_offset_y() as isize;
+    let free_x = terminal_size.width as isize - child_size.width as isize;
+    let free_y = terminal_size.height as isize - child_size.height as isize;
+
+    let x = match halign {
+        HorizontalAlignment::Left => offset_x,
+        HorizontalAlignment::Right => free_x - offset_x,
+        HorizontalAlignment::Center => free_x / 2 + offset_x,
+    } - parent_position.x;
+    let y = match valign {
+        VerticalAlignment::Top => offset_y,
+        VerticalAlignment::Bottom => free_y - offset_y,
+        VerticalAlignment::Middle => free_y / 2 + offset_y,
+    } - parent_position.y;
+
+    let max_x = parent_size.width.saturating_sub(child_size.width);
+    let max_y = parent_size.height.saturating_sub(child_size.height);
+    child_state
+        .get_position_mut()
+        .set_x((x.max(0) as usize).min(max_x));
+    child_state
+        .get_position_mut()
+        .set_y((y.max(0) as usize).min(max_y));
+}
+
+/// Show the selection of a selected widget in its' content (including border and padding) for
+/// selection styles that do not only change colors, see [SelectionStyle]. The content of widgets
+/// that are not selected is returned as is.
+pub fn apply_selection_style(mut content: PixelMap, state: &dyn GenericState) -> PixelMap {
+    if !state.get_selected() || state.get_disabled() || content.is_empty() {
+        return content;
+    }
+    let colors = state.get_color_config();
+    let has_border = state.get_border_config().get_border();
+    let padding = state.get_padding();
+    // Area within the padding, i.e. the border and the actual content
+    let left = padding.get_padding_left();
+    let top = padding.get_padding_top();
+    let right = content.len().saturating_sub(padding.get_padding_right());
+    let bottom = content[0].len().saturating_sub(padding.get_padding_bottom());
+    if left >= right || top >= bottom {
+        return content;
+    }
+    match colors.get_selection_style() {
+        SelectionStyle::Underline => {
+            let border = if has_border { 1 } else { 0 };
+            for column in content.iter_mut().take(right - border).skip(left + border) {
+                for pixel in column.iter_mut().take(bottom - border).skip(top + border) {
+                    pixel.underline = true;
+                }
+            }
+        }
+        SelectionStyle::Marker => {
+            let y = top + (bottom - top - 1) / 2;
+            if let Some(pixel) = content[left.saturating_sub(1)].get_mut(y) {
+                pixel.symbol = colors.get_selection_marker();
+                pixel.foreground_color = colors.get_selection_fg_color();
+            }
+        }
+        _ => (),
+    }
+    let highlight_border = colors.get_selection_style() == SelectionStyle::Border
+        || colors.get_focused_border();
+    if has_border && highlight_border {
+        for (x, column) in content.iter_mut().enumerate().take(right).skip(left) {
+            for (y, pixel) in column.iter_mut().enumerate().take(bottom).skip(top) {
+                if x == left || x == right - 1 || y == top || y == bottom - 1 {
+                    pixel.foreground_color = colors.get_selection_fg_color();
+                }
+            }
+        }
+    }
+    content
+}
+
+/// Draw a hovered or held widget in its' hover or pressed colors, see [InteractionState]. Only
+/// pixels in the normal colors of the widget are recolored, so its' border, padding and parts of
+/// the content with their own colors (e.g. a checked checkbox symbol) keep their colors. Colors
+/// that are not set ([Color::Reset]) are left alone.
+pub fn apply_interaction_style(mut content: PixelMap, state: &dyn GenericState) -> PixelMap {
+    let (fg_color, bg_color) = state
+        .get_color_config()
+        .get_interaction_colors(state.get_interaction_state());
+    if (fg_color == Color::Reset && bg_color == Color::Reset) || content.is_empty() {
+        return content;
+    }
+    let (normal_fg_color, normal_bg_color) = state.get_context_colors();
+    let padding = state.get_padding();
+    let border = if state.get_border_config().get_border() { 1 } else { 0 };
+    let left = padding.get_padding_left() + border;
+    let top = padding.get_padding_top() + border;
+    let right = content.len().saturating_sub(padding.get_padding_right() + border);
+    let bottom = content[0].len().saturating_sub(padding.get_padding_bottom() + border);
+    for column in content.iter_mut().take(right).skip(left) {
+        for pixel in column.iter_mut().take(bottom).skip(top) {
+            if fg_color != Color::Reset && pixel.foreground_color == normal_fg_color {
+                pixel.foreground_color = fg_color;
+            }
+            if bg_color != Color::Reset && pixel.background_color == normal_bg_color {
+                pixel.background_color = bg_color;
+            }
+        }
+    }
+    content
+}
+
 /// Add a border around a PixelMap.
 pub fn add_border(mut content: PixelMap, config: &BorderConfig, colors: &ColorConfig) -> PixelMap {
     if content.is_empty() {
@@ -281,6 +403,56 @@ pub fn align_content_vertically(
     (content, offset)
 }
 
+/// Get the baseline of the passed content, i.e. the first row that contains text. Rows taken up
+/// by padding and border are skipped. If the content contains no text at all the first row after
+/// the padding and border is considered the baseline. Used to line up the text of widgets of
+/// different heights.
+pub fn get_content_baseline(content: &PixelMap, state: &dyn GenericState) -> usize {
+    if content.is_empty() {
+        return 0;
+    }
+    let start = state.get_padding().get_padding_top()
+        + if state.get_border_config().get_border() {
+            1
+        } else {
+            0
+        };
+    let end = content[0].len().saturating_sub(
+        state.get_padding().get_padding_bottom()
+            + if state.get_border_config().get_border() {
+                1
+            } else {
+                0
+            },
+    );
+    let x_start = state.get_padding().get_padding_left()
+        + if state.get_border_config().get_border() {
+            1
+        } else {
+            0
+        };
+    let x_end = content.len().saturating_sub(
+        state.get_padding().get_padding_right()
+            + if state.get_border_config().get_border() {
+                1
+            } else {
+                0
+            },
+    );
+    if x_start >= x_end {
+        return start;
+    }
+    for y in start..end {
+        if content[x_start..x_end]
+            .iter()
+            .any(|column| y < column.len() && !column[y].symbol.trim().is_empty())
+        {
+            return y;
+        }
+    }
+    start
+}
+
 // Make list of lines, splitting into lines at line breaks in the text or when the widget width
 // has been exceeded. If the latter occurs, we will try to split on a word boundary if there is
 // any in that chunk of text, to keep things readable.
@@ -323,6 +495,10 @@ pub fn wrap_text(mut text: String, width: usize, mut pixels: Vec<Pixel>)
                 } else {
                     text = text[width..].to_string();
                 }
+                // Drop the pixels of the line breaks before the rest of the text
+                while pixels.first().map_or(false, |x| x.symbol == "\n") {
+                    pixels.remove(0);
+                }
             }
             // Chunk naturally ends on word boundary, so just push the chunk.
             else if peek.ends_with(' ') {
@@ -367,9 +543,18 @@ pub fn wrap_text(mut text: String, width: usize, mut pixels: Vec<Pixel>)
                 content_lines.push(new);
                 text = text[width..].to_string();
             }
-            // Not enough content left to fill widget width. Just push entire text
+            // Not enough content left to fill widget width. Just push entire text, starting a new
+            // line at each line break
         } else {
-            content_lines.push(pixels);
+            let mut line = Vec::new();
+            for pixel in pixels {
+                if pixel.symbol == "\n" {
+                    content_lines.push(std::mem::take(&mut line));
+                } else {
+                    line.push(pixel);
+                }
+            }
+            content_lines.push(line);
             break;
         }
     }
@@ -396,103 +581,66 @@ pub fn offset_scrolled_absolute_position(
 }
 
 
-/// Create pixels from text, consuming formatting text, e.g. \[underline\] becomes a formatted pixel.
+/// Create pixels from text, consuming markup tags, e.g. \[u\] becomes an underlined pixel. See
+/// [StyledSpan::parse_markup] for the tags.
 pub fn format_text(
     text: String,
     default: Pixel,
 ) -> (String, Vec<Pixel>) {
+    format_styled_spans(&StyledSpan::parse_markup(&text), default)
+}
 
-    let mut underline = false;
-    let mut bold = false;
-    let mut italic = false;
-    let mut strike_through = false;
-    let mut color = None;
-    let mut bg_color = None;
-
-    let mut tag_start = false;
-    let mut tag_content = String::new();
-    let mut escaped = false;
+/// Create pixels from text without consuming markup tags, in the same form as [format_text].
+pub fn format_plain_text(text: String, default: Pixel) -> (String, Vec<Pixel>) {
+    format_styled_spans(&[StyledSpan::new(&text)], default)
+}
 
+/// Create pixels from styled spans, in the same form as [format_text] so the result can be
+/// wrapped with [wrap_text].
+pub fn format_styled_spans(spans: &[StyledSpan], default: Pixel) -> (String, Vec<Pixel>) {
     let mut pixels = Vec::new();
     let mut formatted_text = String::new();
-
-    for grapheme in text.graphemes(true).into_iter() {
-
-        if grapheme == "]" && !escaped {
-            let close = if tag_content.starts_with('/') {
-                tag_content = tag_content.strip_prefix('/').unwrap().to_string();
-                true
-            } else {
-                false
-            };
-            tag_content = tag_content.to_lowercase();
-            if tag_content == "b" {
-                if !close {
-                    bold = true
-                } else {
-                    bold = false
-                }
-            } else if tag_content == "u" {
-                if !close {
-                    underline = true
-                } else {
-                    underline = false
-                }
-            } else if tag_content == "i" {
-                if !close {
-                    italic = true
-                } else {
-                    italic = false
-                }
-            } else if tag_content == "s" {
-                if !close {
-                    strike_through = true
-                } else {
-                    strike_through = false
-                }
-            } else if tag_content.starts_with("color") {
-                if !close {
-                    let color_str = tag_content.split_once("color=").unwrap().1;
-                    color = Some(parse_color_property(color_str).unwrap());
-                } else {
-                    color = None;
-                }
-            } else if tag_content.starts_with("bg_color") {
-                if !close {
-                    let color_str = tag_content.split_once("bg_color=").unwrap().1;
-                    bg_color = Some(parse_color_property(color_str).unwrap());
-                } else {
-                    bg_color = None;
-                }
-            }
-            tag_start = false;
-            tag_content.clear();
-            continue
+    for span in spans.iter() {
+        for grapheme in span.text.graphemes(true) {
+            let mut new_pixel = default.clone();
+            new_pixel.symbol = grapheme.to_string();
+            new_pixel.foreground_color = span.fg_color.unwrap_or(default.foreground_color);
+            new_pixel.background_color = span.bg_color.unwrap_or(default.background_color);
+            new_pixel.bold = span.bold;
+            new_pixel.italic = span.italic;
+            new_pixel.underline = span.underline;
+            new_pixel.strike_through = span.strike_through;
+            pixels.push(new_pixel);
+            formatted_text.push(grapheme.chars().next().unwrap())
         }
+    }
+    (formatted_text, pixels)
+}
 
-        if tag_start {
-            tag_content.push(grapheme.chars().next().unwrap());
-            continue
+/// Create pixels from styled lines, in the same form as [format_text] so the result can be
+/// wrapped with [wrap_text]. Lines are separated by a newline.
+pub fn format_styled_lines(lines: &[StyledLine], default: Pixel) -> (String, Vec<Pixel>) {
+    let mut pixels = Vec::new();
+    let mut formatted_text = String::new();
+    for (i, line) in lines.iter().enumerate() {
+        if i > 0 {
+            let mut line_break = default.clone();
+            line_break.symbol = "\n".to_string();
+            pixels.push(line_break);
+            formatted_text.push('\n');
         }
-
-        if grapheme == "[" && !escaped {
-            tag_start = true;
-            continue
+        for grapheme in line.text.graphemes(true) {
+            let mut new_pixel = default.clone();
+            new_pixel.symbol = grapheme.to_string();
+            new_pixel.foreground_color = line.fg_color.unwrap_or(default.foreground_color);
+            new_pixel.background_color = line.bg_color.unwrap_or(default.background_color);
+            new_pixel.bold = line.bold;
+            new_pixel.italic = line.italic;
+            new_pixel.underline = line.underline;
+            new_pixel.strike_through = line.strike_through;
+            pixels.push(new_pixel);
+            formatted_text.push(grapheme.chars().next().unwrap())
         }
-        escaped =  grapheme == "\\" ;
-
-        let mut new_pixel = default.clone();
-        new_pixel.symbol = grapheme.to_string();
-        if bold { new_pixel.bold = true }
-        if underline { new_pixel.underline = true }
-        if italic { new_pixel.italic = true }
-        if strike_through { new_pixel.strike_through = true }
-        if color.is_some() { new_pixel.foreground_color = color.unwrap()}
-        if bg_color.is_some() { new_pixel.background_color = color.unwrap() }
-        pixels.push(new_pixel);
-        formatted_text.push(grapheme.chars().next().unwrap())
     }
-
     (formatted_text, pixels)
-
 }
@@ -1,15 +1,20 @@
 //! A widget that displays text non-interactively.
 use std::collections::HashMap;
-use std::io::Error; // For ez_file_gen.rs
+use std::io::{Error, ErrorKind}; // Error is also used by ez_file_gen.rs
 
+use crate::parser::ez_definition::ConfigLine;
 use crate::parser::load_base_properties;
 use crate::parser::load_common_properties::load_common_property;
+use crate::parser::parse_lang::ParseError;
 use crate::run::definitions::{Pixel, PixelMap, StateTree};
 use crate::scheduler::scheduler::SchedulerFrontend;
 use crate::states::ez_state::{EzState, GenericState};
 use crate::states::label_state::LabelState;
 use crate::widgets::ez_object::{EzObject, EzObjects};
-use crate::widgets::helper_functions::{add_border, add_padding, format_text, wrap_text};
+use crate::widgets::helper_functions::{
+    add_border, add_padding, format_plain_text, format_styled_lines, format_styled_spans,
+    format_text, wrap_text,
+};
 include!(concat!(env!("OUT_DIR"), "/ez_file_gen.rs"));
 
 #[derive(Clone, Debug)]
@@ -67,6 +72,13 @@ impl EzObject for Label {
                 &parameter_name,
                 self.get_state_mut(),
             )?,
+            "markup" => load_base_properties::load_bool_property(
+                parameter_value.trim(),
+                scheduler,
+                self.path.clone(),
+                &parameter_name,
+                self.get_state_mut(),
+            )?,
             "text" => load_base_properties::load_string_property(
                 parameter_value
                     .strip_prefix(' ')
@@ -76,7 +88,12 @@ impl EzObject for Label {
                 &parameter_name,
                 self.get_state_mut(),
             )?,
-            _ => panic!("Invalid parameter name for label: {}", parameter_name),
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("Invalid parameter name for label: {}", parameter_name),
+                ))
+            }
         }
         Ok(())
     }
@@ -109,8 +126,11 @@ impl EzObject for Label {
 
         let state = state_tree.get_mut(&self.get_path()).as_label_mut();
         let text;
+        // Styled lines and spans take precedence over the text
+        if !state.get_lines().is_empty() || !state.get_spans().is_empty() {
+            text = String::new();
         // Load text from file
-        if !state.get_from_file().is_empty() {
+        } else if !state.get_from_file().is_empty() {
             let includes = ez_includes();
             let key = &state.get_from_file().replace("\\", "\\\\");
             text = includes
@@ -128,7 +148,15 @@ impl EzObject for Label {
             " ".to_string(),
             state.get_color_config().get_fg_color(),
             state.get_color_config().get_bg_color());
-        let (text, pixels) = format_text(text, default_pixel.clone());
+        let (text, pixels) = if !state.get_lines().is_empty() {
+            format_styled_lines(state.get_lines(), default_pixel.clone())
+        } else if !state.get_spans().is_empty() {
+            format_styled_spans(state.get_spans(), default_pixel.clone())
+        } else if state.get_markup() {
+            format_text(text, default_pixel.clone())
+        } else {
+            format_plain_text(text, default_pixel.clone())
+        };
 
         let chunk_size =
             if state.get_infinite_size().width || state.get_auto_scale().get_auto_scale_width() {
@@ -209,15 +237,13 @@ impl EzObject for Label {
 impl Label {
     /// Initialize an instance of this object using the passed config coming from [ez_parser]
     pub fn from_config(
-        config: Vec<String>,
+        config: Vec<ConfigLine>,
         id: String,
         path: String,
         scheduler: &mut SchedulerFrontend,
-        file: String,
-        line: usize,
-    ) -> Self {
+    ) -> Result<Self, ParseError> {
         let mut obj = Label::new(id, path, scheduler);
-        obj.load_ez_config(config, scheduler, file, line).unwrap();
-        obj
+        obj.load_ez_config(config, scheduler)?;
+        Ok(obj)
     }
 }
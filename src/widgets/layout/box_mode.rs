@@ -1,7 +1,9 @@
 use crate::run::definitions::{Coordinates, Pixel, PixelMap, StateTree};
 use crate::states::definitions::LayoutOrientation;
 use crate::widgets::ez_object::EzObject;
-use crate::widgets::helper_functions::{align_content_horizontally, align_content_vertically};
+use crate::widgets::helper_functions::{
+    align_content_horizontally, align_content_vertically, get_content_baseline,
+};
 use crate::widgets::layout::layout::Layout;
 use crate::GenericState;
 use crossterm::style::Color;
@@ -38,6 +40,8 @@ impl Layout {
         let own_infinite_size = state.get_infinite_size().clone();
         let own_colors = state.get_color_config().clone();
         let own_scrolling = state.get_scrolling_config().clone();
+        let hovered_child = state.get_hovered_child().clone();
+        let align_baseline = state.get_align_baseline();
 
         let (filler_symbol, filler_fg_color, filler_bg_color) = if state.get_fill() {
             (
@@ -55,6 +59,7 @@ impl Layout {
 
         let mut position = Coordinates::new(0, 0);
         let mut content_list = Vec::new();
+        let mut baselines = Vec::new();
         for child in self.get_children_in_view(state_tree) {
             let generic_child = child.as_ez_object();
             let state = state_tree
@@ -102,10 +107,17 @@ impl Layout {
 
             state.set_x(position.x);
             state.set_y(position.y);
-            let child_content = generic_child.get_contents(state_tree);
+            let child_content = generic_child.get_styled_contents(state_tree);
             if child_content.is_empty() {
                 continue;
             } // handle empty widget
+            let child_content = self.style_child_content(
+                state_tree,
+                &generic_child.get_path(),
+                content_list.len(),
+                hovered_child == generic_child.get_path(),
+                child_content,
+            );
             let state = state_tree
                 .get_mut(&generic_child.get_path())
                 .as_generic_mut(); // re-borrow
@@ -117,6 +129,7 @@ impl Layout {
             }
 
             position.x += child_content.len();
+            baselines.push(get_content_baseline(&child_content, state));
             content_list.push(child_content);
         }
 
@@ -125,8 +138,23 @@ impl Layout {
             .get_mut(&self.get_path())
             .as_layout()
             .get_effective_size();
+        let max_baseline = baselines.iter().copied().max().unwrap_or(0);
         let mut merged_content = PixelMap::new();
-        for (i, content) in content_list.into_iter().enumerate() {
+        for (i, mut content) in content_list.into_iter().enumerate() {
+            if align_baseline {
+                content = self.align_content_to_baseline(
+                    content,
+                    max_baseline - baselines[i],
+                    own_effective_size.height,
+                    own_infinite_size.height,
+                    state_tree
+                        .get_mut(&self.children.get(i).unwrap().as_ez_object().get_path())
+                        .as_generic_mut(),
+                    filler_symbol.clone(),
+                    filler_fg_color,
+                    filler_bg_color,
+                );
+            }
             merged_content = self.merge_horizontal_contents(
                 merged_content,
                 content,
@@ -156,6 +184,7 @@ impl Layout {
         let own_infinite_size = state.get_infinite_size().clone();
         let own_colors = state.get_color_config().clone();
         let own_scrolling = state.get_scrolling_config().clone();
+        let hovered_child = state.get_hovered_child().clone();
 
         let (filler_symbol, filler_fg_color, filler_bg_color) = if state.get_fill() {
             (
@@ -229,7 +258,14 @@ impl Layout {
 
             child_state.set_x(position.x);
             child_state.set_y(position.y);
-            let child_content = generic_child.get_contents(state_tree);
+            let child_content = generic_child.get_styled_contents(state_tree);
+            let child_content = self.style_child_content(
+                state_tree,
+                &generic_child.get_path(),
+                content_list.len(),
+                hovered_child == generic_child.get_path(),
+                child_content,
+            );
             let state = state_tree
                 .get_mut(&generic_child.get_path())
                 .as_generic_mut(); // re-borrow
@@ -307,6 +343,42 @@ impl Layout {
         merged_content
     }
 
+    /// Used when [align_baseline] is enabled on a horizontal box layout. Moves the content of a
+    /// child down by [offset] rows so its' baseline lines up with the lowest baseline in the row,
+    /// then fills out the remaining height so [valign] has no further effect.
+    pub fn align_content_to_baseline(
+        &self,
+        mut content: PixelMap,
+        offset: usize,
+        parent_height: usize,
+        parent_infinite_height: bool,
+        state: &mut dyn GenericState,
+        filler_symbol: String,
+        fill_fg_color: Color,
+        fill_bg_color: Color,
+    ) -> PixelMap {
+        if content.is_empty() {
+            return content;
+        }
+        // Never push a child out of view; move it down as far as possible instead.
+        let offset = if parent_infinite_height {
+            offset
+        } else {
+            offset.min(parent_height.saturating_sub(content[0].len()))
+        };
+        let fill_pixel = Pixel::new(filler_symbol, fill_fg_color, fill_bg_color);
+        for x in content.iter_mut() {
+            for _ in 0..offset {
+                x.insert(0, fill_pixel.clone());
+            }
+            while !parent_infinite_height && x.len() < parent_height {
+                x.push(fill_pixel.clone());
+            }
+        }
+        state.set_y(state.get_position().get_y() + offset);
+        content
+    }
+
     /// Take a [PixelMap] and merge it vertically with another [PixelMap]
     pub fn merge_vertical_contents(
         &self,
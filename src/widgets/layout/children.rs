@@ -1,3 +1,5 @@
+use std::cmp::min;
+
 use crate::run::definitions::{IsizeCoordinates, PixelMap, StateTree};
 use crate::scheduler::scheduler::SchedulerFrontend;
 use crate::states::definitions::{LayoutMode, LayoutOrientation};
@@ -25,8 +27,16 @@ impl Layout {
         if self.children.len() > 1
             && [LayoutMode::Box, LayoutMode::Table].contains(own_state.get_mode())
         {
-            let (all_default_size_hint_x, all_default_size_hint_y) =
+            let (mut all_default_size_hint_x, mut all_default_size_hint_y) =
                 self.check_default_size_hints(state_tree);
+            // Weights take over distributing space on the main axis of a box layout.
+            if own_mode == LayoutMode::Box && self.has_weighted_children(state_tree) {
+                match state_tree.get(&self.path).as_layout().get_orientation() {
+                    LayoutOrientation::Horizontal => all_default_size_hint_x = false,
+                    LayoutOrientation::Vertical => all_default_size_hint_y = false,
+                    _ => {}
+                }
+            }
             if all_default_size_hint_x {
                 if own_mode == LayoutMode::Box {
                     for child in self.get_children() {
@@ -94,6 +104,9 @@ impl Layout {
             let state = &mut state_tree.get_mut(&generic_child.get_path()).obj;
             resize_with_size_hint(state, own_width, own_height);
         }
+        if own_mode == LayoutMode::Box {
+            self.set_weighted_child_sizes(state_tree, own_width, own_height);
+        }
         for child in self.get_children() {
             if let EzObjects::Layout(i) = child {
                 i.set_child_sizes(state_tree)
@@ -101,6 +114,75 @@ impl Layout {
         }
     }
 
+    /// Check if any child has a weight set.
+    fn has_weighted_children(&self, state_tree: &StateTree) -> bool {
+        self.get_children().iter().any(|child| {
+            state_tree
+                .get(&child.as_ez_object().get_path())
+                .as_generic()
+                .get_weight()
+                > 0.0
+        })
+    }
+
+    /// Give children with a weight a share of the space left over by their siblings without a
+    /// weight, proportional to their weight. Only used by box layouts, on the axis of their
+    /// orientation. Siblings without a weight that use the default size_hint on that axis would
+    /// take up the whole axis, so they get the size of their content instead.
+    fn set_weighted_child_sizes(
+        &self,
+        state_tree: &mut StateTree,
+        own_width: usize,
+        own_height: usize,
+    ) {
+        let horizontal = match state_tree.get(&self.path).as_layout().get_orientation() {
+            LayoutOrientation::Horizontal => true,
+            LayoutOrientation::Vertical => false,
+            _ => return,
+        };
+        let own_size = if horizontal { own_width } else { own_height };
+
+        let mut used_size = 0;
+        let mut total_weight = 0.0;
+        let mut weighted_children = Vec::new();
+        for child in self.get_children_in_view(state_tree) {
+            let path = child.as_ez_object().get_path();
+            let state = state_tree.get(&path).as_generic();
+            if state.get_weight() > 0.0 {
+                total_weight += state.get_weight();
+                weighted_children.push((path, state.get_weight()));
+            } else if uses_natural_size(state, horizontal) {
+                used_size += measure_natural_size(child, state_tree, horizontal, own_size);
+            } else if horizontal {
+                used_size += state.get_size().get_width();
+            } else {
+                used_size += state.get_size().get_height();
+            }
+        }
+        if weighted_children.is_empty() {
+            return;
+        }
+
+        let leftover = own_size.saturating_sub(used_size);
+        let mut distributed = 0;
+        let last = weighted_children.len() - 1;
+        for (i, (path, weight)) in weighted_children.into_iter().enumerate() {
+            // The last weighted child gets whatever is left so rounding never leaves a gap
+            let size = if i == last {
+                leftover - distributed
+            } else {
+                (leftover as f64 * (weight / total_weight)).floor() as usize
+            };
+            distributed += size;
+            let state = state_tree.get_mut(&path).as_generic_mut();
+            if horizontal {
+                state.get_size_mut().set_width(size);
+            } else {
+                state.get_size_mut().set_height(size);
+            }
+        }
+    }
+
     /// Check if all chrildren employ default size_hints (i.e. size_hint=1) for x and y
     /// separately.
     fn check_default_size_hints(&self, state_tree: &StateTree) -> (bool, bool) {
@@ -295,6 +377,43 @@ impl Layout {
         }
     }
 
+    /// Add a child ([layout] or [EzWidget]) to this layout at a specific position among the
+    /// existing children. If the index is larger than the amount of children, the child is
+    /// appended.
+    pub fn insert_child(
+        &mut self,
+        child: EzObjects,
+        index: usize,
+        scheduler: &mut SchedulerFrontend,
+    ) {
+        let id = child.as_ez_object().get_id();
+        self.add_child(child, scheduler);
+        let current_index = *self.child_lookup.get(&id).unwrap();
+        let child = self.children.remove(current_index);
+        self.children.insert(min(index, self.children.len()), child);
+        self.update_child_lookup();
+    }
+
+    /// Swap the positions of two children of this layout.
+    pub fn swap_children(&mut self, child_1: &str, child_2: &str) {
+        let index_1 = *self.child_lookup.get(child_1).unwrap_or_else(|| {
+            panic!("Could not swap children, {} has no child {}", self.path, child_1)
+        });
+        let index_2 = *self.child_lookup.get(child_2).unwrap_or_else(|| {
+            panic!("Could not swap children, {} has no child {}", self.path, child_2)
+        });
+        self.children.swap(index_1, index_2);
+        self.update_child_lookup();
+    }
+
+    /// Rebuild the child lookup table after the order of the children changed.
+    fn update_child_lookup(&mut self) {
+        self.child_lookup.clear();
+        for (i, child) in self.children.iter().enumerate() {
+            self.child_lookup.insert(child.as_ez_object().get_id(), i);
+        }
+    }
+
     /// Remove a widget. Never remove a child directly but call this instead. It keeps the child
     /// lookup table cache up to date.
     pub fn remove_child(&mut self, id: &str) {
@@ -341,14 +460,6 @@ impl Layout {
     /// Get a specific child ref by its' [path]. Call on root layout to find any EzObject that
     /// exists
     pub fn get_child_by_path(&self, path: &str) -> Option<&EzObjects> {
-        if path == "/root/modal" && self.path == "/root" {
-            return if self.state.has_modal() {
-                Some(self.state.get_modal())
-            } else {
-                None
-            };
-        }
-
         let mut paths: Vec<&str> = path.split('/').filter(|x| !x.is_empty()).collect();
         // If user passed a path starting with this layout, take it off first.
         if paths.first().unwrap() == &self.get_id() {
@@ -357,18 +468,12 @@ impl Layout {
         paths.reverse();
 
         let first = paths.pop().unwrap();
-        let mut root = if first == "modal" {
-            if self.state.has_modal() {
-                self.state.get_modal()
-            } else {
-                return None;
-            }
+        let mut root = if let Some(modal) = self.state.get_modal_by_id(first) {
+            modal
+        } else if let Some(i) = self.get_child(first) {
+            i
         } else {
-            if let Some(i) = self.get_child(first) {
-                i
-            } else {
-                return None;
-            }
+            return None;
         };
         while !paths.is_empty() {
             if let EzObjects::Layout(layout) = root {
@@ -387,14 +492,6 @@ impl Layout {
     /// Get a specific child mutable ref by its' [path]. Call on root layout to find any
     /// [EzObject] that exists
     pub fn get_child_by_path_mut(&mut self, path: &str) -> Option<&mut EzObjects> {
-        if path == "/root/modal" && self.path == "/root" {
-            return if self.state.has_modal() {
-                Some(self.state.get_modal_mut())
-            } else {
-                None
-            };
-        }
-
         let mut paths: Vec<&str> = path.split('/').filter(|x| !x.is_empty()).collect();
         if paths.first().unwrap() == &self.get_id() {
             paths.remove(0);
@@ -402,18 +499,12 @@ impl Layout {
         paths.reverse();
 
         let first = paths.pop().unwrap();
-        let mut root = if first == "modal" {
-            if self.state.has_modal() {
-                self.state.get_modal_mut()
-            } else {
-                return None;
-            }
+        let mut root = if self.state.get_modal_by_id(first).is_some() {
+            self.state.get_modal_by_id_mut(first).unwrap()
+        } else if let Some(i) = self.get_child_mut(first) {
+            i
         } else {
-            if let Some(i) = self.get_child_mut(first) {
-                i
-            } else {
-                return None;
-            }
+            return None;
         };
         while !paths.is_empty() {
             if let Some(i) = root.as_layout_mut().get_child_mut(paths.pop().unwrap()) {
@@ -468,3 +559,48 @@ impl Layout {
         }
     }
 }
+
+/// Returns a bool representing whether a child without a weight in a box layout with weighted
+/// children should get the size of its' content on the axis of the layout, because it uses the
+/// default size_hint there.
+fn uses_natural_size(state: &dyn GenericState, horizontal: bool) -> bool {
+    if horizontal {
+        state.get_size_hint().get_size_hint_x() == Some(1.0)
+            && !state.get_size().fixed_width
+            && !state.get_auto_scale().get_auto_scale_width()
+    } else {
+        state.get_size_hint().get_size_hint_y() == Some(1.0)
+            && !state.get_size().fixed_height
+            && !state.get_auto_scale().get_auto_scale_height()
+    }
+}
+
+/// Set the size of a child on one axis to the size of its' content, by rendering it as if it was
+/// auto scaled on that axis. The auto scale setting is only changed while rendering, without
+/// notifying anything bound to it. Returns the new size.
+fn measure_natural_size(
+    child: &EzObjects,
+    state_tree: &mut StateTree,
+    horizontal: bool,
+    max_size: usize,
+) -> usize {
+    let generic_child = child.as_ez_object();
+    let path = generic_child.get_path();
+    let state = state_tree.get_mut(&path).as_generic_mut();
+    if horizontal {
+        state.get_auto_scale_mut().auto_scale_width.value = true;
+        state.get_size_mut().set_width(max_size);
+    } else {
+        state.get_auto_scale_mut().auto_scale_height.value = true;
+        state.get_size_mut().set_height(max_size);
+    }
+    generic_child.get_contents(state_tree);
+    let state = state_tree.get_mut(&path).as_generic_mut();
+    if horizontal {
+        state.get_auto_scale_mut().auto_scale_width.value = false;
+        state.get_size().get_width()
+    } else {
+        state.get_auto_scale_mut().auto_scale_height.value = false;
+        state.get_size().get_height()
+    }
+}
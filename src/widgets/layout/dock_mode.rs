@@ -0,0 +1,476 @@
+use std::cmp::{max, min};
+
+use crate::run::definitions::{IsizeCoordinates, Pixel, PixelMap, StateTree};
+use crate::scheduler::scheduler::SchedulerFrontend;
+use crate::states::definitions::{DockDrag, DockPosition};
+use crate::states::ez_state::GenericState;
+use crate::widgets::ez_object::{EzObject, EzObjects};
+use crate::widgets::layout::layout::Layout;
+
+/// Area of a dock layout that is not yet taken by docked children, relative to the layout.
+struct DockArea {
+    x: usize,
+    y: usize,
+    width: usize,
+    height: usize,
+}
+
+impl Layout {
+    /// Used by [get_contents] when the [LayoutMode] is set to [Dock]. Children are docked to the
+    /// edge of the remaining space in the order they were added, each followed by a splitter.
+    /// Collapsed children take a single row or column. Whatever space remains goes to children
+    /// docked to the center, after which floating children are drawn on top.
+    pub fn get_dock_mode_contents(&self, state_tree: &mut StateTree) -> PixelMap {
+        let own_state = state_tree.get(&self.get_path()).as_layout();
+        let own_width = own_state.get_effective_size().width;
+        let own_height = own_state.get_effective_size().height;
+
+        let (filler_symbol, filler_fg_color, filler_bg_color) = if own_state.get_fill() {
+            (
+                own_state.get_filler_symbol(),
+                own_state.colors.get_filler_fg_color(),
+                own_state.colors.get_filler_bg_color(),
+            )
+        } else {
+            (
+                " ".to_string(),
+                own_state.colors.get_fg_color(),
+                own_state.colors.get_bg_color(),
+            )
+        };
+        let filler = Pixel::new(filler_symbol, filler_fg_color, filler_bg_color);
+        let mut content = vec![vec![filler; own_height]; own_width];
+
+        let mut area = DockArea {
+            x: 0,
+            y: 0,
+            width: own_width,
+            height: own_height,
+        };
+        let mut centered = Vec::new();
+        let mut floating = Vec::new();
+        for child in self.get_children() {
+            let dock = state_tree
+                .get(&child.as_ez_object().get_path())
+                .as_generic()
+                .get_dock();
+            match dock {
+                DockPosition::Center => centered.push(child),
+                DockPosition::Float => floating.push(child),
+                _ => self.dock_child(child, dock, &mut area, &mut content, state_tree),
+            }
+        }
+
+        for child in centered {
+            let state = state_tree
+                .get_mut(&child.as_ez_object().get_path())
+                .as_generic_mut();
+            state.set_width(area.width);
+            state.set_height(area.height);
+            state.set_x(area.x);
+            state.set_y(area.y);
+            let child_content = child.as_ez_object().get_styled_contents(state_tree);
+            write_dock_content(&mut content, &child_content, &area);
+        }
+
+        for child in floating {
+            let state = state_tree
+                .get_mut(&child.as_ez_object().get_path())
+                .as_generic_mut();
+            let width = min(state.get_size().get_width(), own_width);
+            let height = min(state.get_size().get_height(), own_height);
+            state.set_width(width);
+            state.set_height(height);
+            state.set_x(min(state.get_position().get_x(), own_width - width));
+            state.set_y(min(state.get_position().get_y(), own_height - height));
+            let float_area = DockArea {
+                x: state.get_position().get_x(),
+                y: state.get_position().get_y(),
+                width,
+                height,
+            };
+            let child_content = child.as_ez_object().get_styled_contents(state_tree);
+            write_dock_content(&mut content, &child_content, &float_area);
+        }
+        content
+    }
+
+    /// Dock a single child to an edge of the remaining area, draw it (or its' collapsed strip)
+    /// and the splitter next to it, and shrink the remaining area accordingly.
+    fn dock_child(
+        &self,
+        child: &EzObjects,
+        dock: DockPosition,
+        area: &mut DockArea,
+        content: &mut PixelMap,
+        state_tree: &mut StateTree,
+    ) {
+        if area.width == 0 || area.height == 0 {
+            return;
+        }
+        let own_state = state_tree.get(&self.get_path()).as_layout();
+        let own_size = own_state.get_effective_size();
+        let fg_color = own_state.colors.get_border_fg_color();
+        let bg_color = own_state.colors.get_border_bg_color();
+        let vertical_symbol = own_state.get_border_config().get_vertical_symbol();
+        let horizontal_symbol = own_state.get_border_config().get_horizontal_symbol();
+        let horizontal = dock == DockPosition::Left || dock == DockPosition::Right;
+
+        let child_path = child.as_ez_object().get_path();
+        let state = state_tree.get_mut(&child_path).as_generic_mut();
+        if state.get_collapsed() {
+            // Collapsed children are drawn as a single row or column containing their ID
+            let title: Vec<char> = state.get_id().chars().collect();
+            let (x, y) = match dock {
+                DockPosition::Right => (area.x + area.width - 1, area.y),
+                DockPosition::Bottom => (area.x, area.y + area.height - 1),
+                _ => (area.x, area.y),
+            };
+            state.set_x(x);
+            state.set_y(y);
+            let length = if horizontal {
+                state.set_height(area.height);
+                area.height
+            } else {
+                state.set_width(area.width);
+                area.width
+            };
+            for i in 0..length {
+                let symbol = title.get(i).map_or(" ".to_string(), |x| x.to_string());
+                let pixel = Pixel::new(symbol, bg_color, fg_color);
+                if horizontal {
+                    content[x][y + i] = pixel;
+                } else {
+                    content[x + i][y] = pixel;
+                }
+            }
+            match dock {
+                DockPosition::Left => area.x += 1,
+                DockPosition::Top => area.y += 1,
+                _ => (),
+            }
+            if horizontal {
+                area.width -= 1;
+            } else {
+                area.height -= 1;
+            }
+            return;
+        }
+
+        let child_area = if horizontal {
+            let width = match state.get_size_hint().get_size_hint_x() {
+                Some(size_hint) => (size_hint * own_size.width as f64).round() as usize,
+                None => state.get_size().get_width(),
+            };
+            let width = min(width, area.width);
+            let x = if dock == DockPosition::Left {
+                area.x
+            } else {
+                area.x + area.width - width
+            };
+            DockArea {
+                x,
+                y: area.y,
+                width,
+                height: area.height,
+            }
+        } else {
+            let height = match state.get_size_hint().get_size_hint_y() {
+                Some(size_hint) => (size_hint * own_size.height as f64).round() as usize,
+                None => state.get_size().get_height(),
+            };
+            let height = min(height, area.height);
+            let y = if dock == DockPosition::Top {
+                area.y
+            } else {
+                area.y + area.height - height
+            };
+            DockArea {
+                x: area.x,
+                y,
+                width: area.width,
+                height,
+            }
+        };
+        state.set_width(child_area.width);
+        state.set_height(child_area.height);
+        state.set_x(child_area.x);
+        state.set_y(child_area.y);
+        let child_content = child.as_ez_object().get_styled_contents(state_tree);
+        write_dock_content(content, &child_content, &child_area);
+
+        // Shrink the remaining area and draw the splitter if there's room left for one
+        match dock {
+            DockPosition::Left => {
+                area.x += child_area.width;
+                area.width -= child_area.width;
+            }
+            DockPosition::Right => area.width -= child_area.width,
+            DockPosition::Top => {
+                area.y += child_area.height;
+                area.height -= child_area.height;
+            }
+            _ => area.height -= child_area.height,
+        }
+        if (horizontal && area.width == 0) || (!horizontal && area.height == 0) {
+            return;
+        }
+        match dock {
+            DockPosition::Left | DockPosition::Right => {
+                let x = if dock == DockPosition::Left {
+                    area.x
+                } else {
+                    area.x + area.width - 1
+                };
+                for pixel in content[x][area.y..area.y + area.height].iter_mut() {
+                    *pixel = Pixel::new(vertical_symbol.clone(), fg_color, bg_color);
+                }
+                if dock == DockPosition::Left {
+                    area.x += 1;
+                }
+                area.width -= 1;
+            }
+            _ => {
+                let y = if dock == DockPosition::Top {
+                    area.y
+                } else {
+                    area.y + area.height - 1
+                };
+                for column in content[area.x..area.x + area.width].iter_mut() {
+                    column[y] = Pixel::new(horizontal_symbol.clone(), fg_color, bg_color);
+                }
+                if dock == DockPosition::Top {
+                    area.y += 1;
+                }
+                area.height -= 1;
+            }
+        }
+    }
+
+    /// Convert a mouse position relative to this layout to a position relative to where its'
+    /// content starts, which is the position children of a dock layout are placed relative to.
+    fn get_dock_content_position(
+        &self,
+        state_tree: &StateTree,
+        mouse_pos: IsizeCoordinates,
+    ) -> IsizeCoordinates {
+        let state = state_tree.get(&self.get_path()).as_layout();
+        let absolute = state.get_absolute_position();
+        let effective_absolute = state.get_effective_absolute_position();
+        IsizeCoordinates::new(
+            mouse_pos.x - (effective_absolute.x - absolute.x),
+            mouse_pos.y - (effective_absolute.y - absolute.y),
+        )
+    }
+
+    /// Handle a left click on a dock layout. Clicking the strip of a collapsed child expands it,
+    /// clicking the top row of a docked child collapses it.
+    pub fn handle_dock_click(
+        &self,
+        state_tree: &mut StateTree,
+        scheduler: &mut SchedulerFrontend,
+        mouse_pos: IsizeCoordinates,
+    ) -> bool {
+        let pos = self.get_dock_content_position(state_tree, mouse_pos);
+        for child in self.get_children() {
+            let state = state_tree
+                .get_mut(&child.as_ez_object().get_path())
+                .as_generic_mut();
+            let dock = state.get_dock();
+            if dock == DockPosition::Center || dock == DockPosition::Float {
+                continue;
+            }
+            let (x, y) = (
+                state.get_position().get_x() as isize,
+                state.get_position().get_y() as isize,
+            );
+            let (width, height) = (
+                state.get_size().get_width() as isize,
+                state.get_size().get_height() as isize,
+            );
+            let hit = if state.get_collapsed() {
+                if dock == DockPosition::Left || dock == DockPosition::Right {
+                    pos.x == x && pos.y >= y && pos.y < y + height
+                } else {
+                    pos.y == y && pos.x >= x && pos.x < x + width
+                }
+            } else {
+                pos.y == y && pos.x >= x && pos.x < x + width
+            };
+            if hit {
+                state.set_collapsed(!state.get_collapsed());
+                state.update(scheduler);
+                scheduler.update_widget(&self.get_path());
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Handle a left mouse drag on a dock layout. Dragging a splitter resizes the child next to
+    /// it, dragging the top row of a child undocks it and moves it around as a floating child.
+    pub fn handle_dock_drag(
+        &self,
+        state_tree: &mut StateTree,
+        scheduler: &mut SchedulerFrontend,
+        previous_pos: Option<IsizeCoordinates>,
+        mouse_pos: IsizeCoordinates,
+    ) -> bool {
+        let pos = self.get_dock_content_position(state_tree, mouse_pos);
+        if previous_pos.is_none() {
+            let drag = self.get_dock_drag_target(state_tree, pos);
+            let consumed = drag.is_some();
+            state_tree
+                .get_mut(&self.get_path())
+                .as_layout_mut()
+                .set_dock_drag(drag);
+            return consumed;
+        }
+        let previous_pos = self.get_dock_content_position(state_tree, previous_pos.unwrap());
+        let diff_x = pos.x - previous_pos.x;
+        let diff_y = pos.y - previous_pos.y;
+        let own_size = state_tree
+            .get(&self.get_path())
+            .as_layout()
+            .get_effective_size();
+        let drag = state_tree
+            .get(&self.get_path())
+            .as_layout()
+            .get_dock_drag();
+        match drag {
+            Some(DockDrag::Splitter(path)) => {
+                let state = state_tree.get_mut(&path).as_generic_mut();
+                match state.get_dock() {
+                    DockPosition::Left | DockPosition::Right => {
+                        let diff = if state.get_dock() == DockPosition::Left {
+                            diff_x
+                        } else {
+                            -diff_x
+                        };
+                        let width = state.get_size().get_width() as isize + diff;
+                        state.set_size_hint_x(None);
+                        state.set_width(min(max(width, 1) as usize, own_size.width));
+                    }
+                    _ => {
+                        let diff = if state.get_dock() == DockPosition::Top {
+                            diff_y
+                        } else {
+                            -diff_y
+                        };
+                        let height = state.get_size().get_height() as isize + diff;
+                        state.set_size_hint_y(None);
+                        state.set_height(min(max(height, 1) as usize, own_size.height));
+                    }
+                }
+                state.update(scheduler);
+            }
+            Some(DockDrag::Panel(path)) => {
+                let state = state_tree.get_mut(&path).as_generic_mut();
+                if state.get_dock() != DockPosition::Float {
+                    state.set_dock(DockPosition::Float);
+                    state.set_size_hint_x(None);
+                    state.set_size_hint_y(None);
+                }
+                state.set_pos_hint_x(None);
+                state.set_pos_hint_y(None);
+                let max_x = own_size.width as isize - state.get_size().get_width() as isize;
+                let max_y = own_size.height as isize - state.get_size().get_height() as isize;
+                let x = state.get_position().get_x() as isize + diff_x;
+                let y = state.get_position().get_y() as isize + diff_y;
+                state.set_x(max(min(x, max_x), 0) as usize);
+                state.set_y(max(min(y, max_y), 0) as usize);
+                state.update(scheduler);
+            }
+            None => return false,
+        }
+        scheduler.update_widget(&self.get_path());
+        true
+    }
+
+    /// Find what a drag starting on the given position (relative to the content of this layout)
+    /// should move: a splitter, the top row of a child, or nothing.
+    fn get_dock_drag_target(
+        &self,
+        state_tree: &StateTree,
+        pos: IsizeCoordinates,
+    ) -> Option<DockDrag> {
+        for child in self.get_children() {
+            let path = child.as_ez_object().get_path();
+            let state = state_tree.get(&path).as_generic();
+            if state.get_collapsed() || state.get_dock() == DockPosition::Center {
+                continue;
+            }
+            let (x, y) = (
+                state.get_position().get_x() as isize,
+                state.get_position().get_y() as isize,
+            );
+            let (width, height) = (
+                state.get_size().get_width() as isize,
+                state.get_size().get_height() as isize,
+            );
+            let on_splitter = match state.get_dock() {
+                DockPosition::Left => pos.x == x + width && pos.y >= y && pos.y < y + height,
+                DockPosition::Right => pos.x == x - 1 && pos.y >= y && pos.y < y + height,
+                DockPosition::Top => pos.y == y + height && pos.x >= x && pos.x < x + width,
+                DockPosition::Bottom => pos.y == y - 1 && pos.x >= x && pos.x < x + width,
+                _ => false,
+            };
+            if on_splitter {
+                return Some(DockDrag::Splitter(path));
+            }
+            if pos.y == y && pos.x >= x && pos.x < x + width {
+                return Some(DockDrag::Panel(path));
+            }
+        }
+        None
+    }
+
+    /// Handle the end of a drag on a dock layout. A floating child released against an edge of
+    /// the layout is docked to that edge.
+    pub fn handle_dock_drag_exit(
+        &self,
+        state_tree: &mut StateTree,
+        scheduler: &mut SchedulerFrontend,
+        mouse_pos: IsizeCoordinates,
+    ) -> bool {
+        let pos = self.get_dock_content_position(state_tree, mouse_pos);
+        let own_state = state_tree.get_mut(&self.get_path()).as_layout_mut();
+        let own_size = own_state.get_effective_size();
+        let drag = own_state.get_dock_drag();
+        own_state.set_dock_drag(None);
+
+        if let Some(DockDrag::Panel(path)) = drag {
+            let state = state_tree.get_mut(&path).as_generic_mut();
+            if state.get_dock() != DockPosition::Float {
+                return true;
+            }
+            let dock = if pos.x <= 0 {
+                DockPosition::Left
+            } else if pos.x >= own_size.width as isize - 1 {
+                DockPosition::Right
+            } else if pos.y <= 0 {
+                DockPosition::Top
+            } else if pos.y >= own_size.height as isize - 1 {
+                DockPosition::Bottom
+            } else {
+                return true;
+            };
+            state.set_dock(dock);
+            state.update(scheduler);
+            scheduler.update_widget(&self.get_path());
+            return true;
+        }
+        drag.is_some()
+    }
+}
+
+/// Write the content of a child into the content of a dock layout, clipped to the given area.
+fn write_dock_content(content: &mut PixelMap, child_content: &PixelMap, area: &DockArea) {
+    for (x, column) in child_content.iter().take(area.width).enumerate() {
+        for (y, pixel) in column.iter().take(area.height).enumerate() {
+            if area.x + x < content.len() && area.y + y < content[area.x + x].len() {
+                content[area.x + x][area.y + y] = pixel.clone();
+            }
+        }
+    }
+}
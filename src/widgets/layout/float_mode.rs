@@ -1,13 +1,14 @@
-use crate::run::definitions::{Pixel, PixelMap, StateTree};
+use crate::run::definitions::{Pixel, PixelMap, Size, StateTree};
 use crate::states::ez_state::GenericState;
-use crate::widgets::ez_object::EzObject;
-use crate::widgets::helper_functions::reposition_with_pos_hint;
+use crate::widgets::ez_object::{EzObject, EzObjects};
+use crate::widgets::helper_functions::{reposition_with_anchor, reposition_with_pos_hint};
 use crate::widgets::layout::layout::Layout;
 
 impl Layout {
     /// Used by [get_contents] when the [LayoutMode] is set to [Float]. Places each child in the
     /// XY coordinates defined by that child, relative to itself, and uses
-    /// childs' [width] and [height].
+    /// childs' [width] and [height]. Children with an [Anchor] are instead placed at an edge or
+    /// corner of the terminal. Overlapping children are drawn in order of their z index.
     pub fn get_float_mode_contents(
         &self,
         mut content: PixelMap,
@@ -16,6 +17,8 @@ impl Layout {
         let own_state = state_tree.get(&self.get_path()).as_layout();
         let own_height = own_state.get_effective_size().height;
         let own_width = own_state.get_effective_size().width;
+        let own_position = own_state.get_effective_absolute_position();
+        let terminal_size = Size::from_state_size(state_tree.as_generic().get_size());
 
         let (filler_symbol, filler_fg_color, filler_bg_color) = if own_state.get_fill() {
             (
@@ -40,9 +43,19 @@ impl Layout {
             }
         }
 
+        // Children with a higher z index are drawn later, so they end up on top. The sort is
+        // stable, so children with the same z index keep their declaration order.
+        let mut children: Vec<&EzObjects> = self.get_children_in_view(state_tree).iter().collect();
+        children.sort_by_key(|x| {
+            state_tree
+                .get(&x.as_ez_object().get_path())
+                .as_generic()
+                .get_z_index()
+        });
+
         let mut biggest_write_x = 0;
         let mut biggest_write_y = 0;
-        for child in self.get_children_in_view(state_tree) {
+        for child in children {
             if content.is_empty() {
                 return content;
             } // No space left in widget
@@ -69,11 +82,20 @@ impl Layout {
                 state.get_size_mut().set_width(own_width);
             }
 
-            let child_content = generic_child.get_contents(state_tree);
+            let child_content = generic_child.get_styled_contents(state_tree);
             let state = state_tree
                 .get_mut(&generic_child.get_path())
                 .as_generic_mut(); // re-borrow
             reposition_with_pos_hint(own_width, own_height, state);
+            if !child_content.is_empty() {
+                reposition_with_anchor(
+                    own_position,
+                    Size::new(own_width, own_height),
+                    terminal_size,
+                    Size::new(child_content.len(), child_content[0].len()),
+                    state,
+                );
+            }
 
             let child_pos = state.get_position();
             for width in 0..child_content.len() {
@@ -1,12 +1,16 @@
 //! # layout
 //! Module implementing the layout struct.
+use crate::parser::ez_definition::ConfigLine;
 use crate::parser::load_base_properties;
+use crate::parser::parse_properties;
 use crate::parser::load_common_properties::load_common_property;
+use crate::parser::parse_lang::ParseError;
 use crate::run::definitions::{
     CallbackTree, Coordinates, IsizeCoordinates, Pixel, PixelMap, StateTree,
 };
 use crate::scheduler::scheduler::SchedulerFrontend;
-use crate::states::definitions::LayoutMode;
+use crate::states::colors::{blend, dim, hover_color};
+use crate::states::definitions::{FillerPattern, LayoutMode};
 use crate::states::ez_state::{EzState, GenericState};
 use crate::states::layout_state::LayoutState;
 use crate::widgets::ez_object::{EzObject, EzObjects};
@@ -15,11 +19,22 @@ use crate::widgets::helper_functions::{
 };
 use crate::Context;
 use crossterm::event::{Event, KeyCode};
+use crossterm::style::Color;
 use std::cmp::{max, min};
 use std::collections::HashMap;
 use std::io::{Error, ErrorKind};
+use std::time::Duration;
 use crate::scheduler::definitions::CustomDataMap;
 
+/// Frames of the spinner drawn over a busy layout, see [LayoutState.set_busy].
+pub const BUSY_SPINNER_FRAMES: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+/// Time each frame of the spinner of a busy layout is shown.
+pub const BUSY_SPINNER_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Opacity of the content of a busy layout under its' spinner.
+const BUSY_OPACITY: f64 = 0.4;
+
 /// A layout is where widgets live. They implements methods for hardcoding widget placement or
 /// placing them automatically in various ways.
 #[derive(Clone, Debug)]
@@ -214,6 +229,27 @@ impl EzObject for Layout {
                 &parameter_name,
                 self.get_state_mut(),
             )?,
+            "minimap" => load_base_properties::load_bool_property(
+                parameter_value.trim(),
+                scheduler,
+                self.path.clone(),
+                &parameter_name,
+                self.get_state_mut(),
+            )?,
+            "minimap_width" => load_base_properties::load_usize_property(
+                parameter_value.trim(),
+                scheduler,
+                self.path.clone(),
+                &parameter_name,
+                self.get_state_mut(),
+            )?,
+            "scroll_step" => load_base_properties::load_usize_property(
+                parameter_value.trim(),
+                scheduler,
+                self.path.clone(),
+                &parameter_name,
+                self.get_state_mut(),
+            )?,
             "scroll_start_x" => load_base_properties::load_f64_property(
                 parameter_value.trim(),
                 scheduler,
@@ -235,6 +271,41 @@ impl EzObject for Layout {
                 &parameter_name,
                 self.get_state_mut(),
             )?,
+            "stripe_children" => load_base_properties::load_color_property(
+                parameter_value.trim(),
+                scheduler,
+                self.path.clone(),
+                &parameter_name,
+                self.get_state_mut(),
+            )?,
+            "highlight_child_on_hover" => load_base_properties::load_bool_property(
+                parameter_value.trim(),
+                scheduler,
+                self.path.clone(),
+                &parameter_name,
+                self.get_state_mut(),
+            )?,
+            "focus_wrap" => load_base_properties::load_bool_property(
+                parameter_value.trim(),
+                scheduler,
+                self.path.clone(),
+                &parameter_name,
+                self.get_state_mut(),
+            )?,
+            "busy" => load_base_properties::load_bool_property(
+                parameter_value.trim(),
+                scheduler,
+                self.path.clone(),
+                &parameter_name,
+                self.get_state_mut(),
+            )?,
+            "busy_text" => load_base_properties::load_string_property(
+                parameter_value.trim(),
+                scheduler,
+                self.path.clone(),
+                &parameter_name,
+                self.get_state_mut(),
+            )?,
             "fill" => load_base_properties::load_bool_property(
                 parameter_value.trim(),
                 scheduler,
@@ -249,6 +320,133 @@ impl EzObject for Layout {
                 &parameter_name,
                 self.get_state_mut(),
             )?,
+            "filler_pattern" => {
+                if load_base_properties::resolve_property(parameter_value.trim(), self.path.clone())
+                    .is_none()
+                {
+                    parse_properties::parse_filler_pattern_property(parameter_value.trim())?;
+                }
+                load_base_properties::load_string_property(
+                    parameter_value.trim(),
+                    scheduler,
+                    self.path.clone(),
+                    &parameter_name,
+                    self.get_state_mut(),
+                )?
+            }
+            "filler_gradient_steps" => load_base_properties::load_usize_property(
+                parameter_value.trim(),
+                scheduler,
+                self.path.clone(),
+                &parameter_name,
+                self.get_state_mut(),
+            )?,
+            "filler_header_height" => load_base_properties::load_usize_property(
+                parameter_value.trim(),
+                scheduler,
+                self.path.clone(),
+                &parameter_name,
+                self.get_state_mut(),
+            )?,
+            "filler_header_symbol" => load_base_properties::load_string_property(
+                parameter_value.trim(),
+                scheduler,
+                self.path.clone(),
+                &parameter_name,
+                self.get_state_mut(),
+            )?,
+            "fill_behind_children" => load_base_properties::load_bool_property(
+                parameter_value.trim(),
+                scheduler,
+                self.path.clone(),
+                &parameter_name,
+                self.get_state_mut(),
+            )?,
+            "slot" => load_base_properties::load_string_property(
+                parameter_value.trim(),
+                scheduler,
+                self.path.clone(),
+                &parameter_name,
+                self.get_state_mut(),
+            )?,
+            "min_terminal_height" => load_base_properties::load_usize_property(
+                parameter_value.trim(),
+                scheduler,
+                self.path.clone(),
+                &parameter_name,
+                self.get_state_mut(),
+            )?,
+            "min_terminal_size" => {
+                let (width, height) = match parameter_value.split_once(',') {
+                    Some((i, j)) => (i, j),
+                    None => {
+                        return Err(Error::new(
+                            ErrorKind::InvalidData,
+                            format!(
+                                "Invalid value for min_terminal_size: \"{}\". Required format \
+                                   is \"min_terminal_size: 80, 24\"",
+                                parameter_value
+                            ),
+                        ))
+                    }
+                };
+                load_base_properties::load_usize_property(
+                    width.trim(),
+                    scheduler,
+                    self.path.clone(),
+                    "min_terminal_width",
+                    self.get_state_mut(),
+                )?;
+                load_base_properties::load_usize_property(
+                    height.trim(),
+                    scheduler,
+                    self.path.clone(),
+                    "min_terminal_height",
+                    self.get_state_mut(),
+                )?;
+            }
+            "min_terminal_width" => load_base_properties::load_usize_property(
+                parameter_value.trim(),
+                scheduler,
+                self.path.clone(),
+                &parameter_name,
+                self.get_state_mut(),
+            )?,
+            "collapsible_on_small" => load_base_properties::load_bool_property(
+                parameter_value.trim(),
+                scheduler,
+                self.path.clone(),
+                &parameter_name,
+                self.get_state_mut(),
+            )?,
+            "align_baseline" => load_base_properties::load_bool_property(
+                parameter_value.trim(),
+                scheduler,
+                self.path.clone(),
+                &parameter_name,
+                self.get_state_mut(),
+            )?,
+            "movable" => load_base_properties::load_bool_property(
+                parameter_value.trim(),
+                scheduler,
+                self.path.clone(),
+                &parameter_name,
+                self.get_state_mut(),
+            )?,
+            "resizable" => load_base_properties::load_bool_property(
+                parameter_value.trim(),
+                scheduler,
+                self.path.clone(),
+                &parameter_name,
+                self.get_state_mut(),
+            )?,
+            "window_buttons" => load_base_properties::load_bool_property(
+                parameter_value.trim(),
+                scheduler,
+                self.path.clone(),
+                &parameter_name,
+                self.get_state_mut(),
+            )?,
             _ => {
                 return Err(Error::new(
                     ErrorKind::InvalidData,
@@ -283,6 +481,9 @@ impl EzObject for Layout {
     }
 
     fn get_contents(&self, state_tree: &mut StateTree) -> PixelMap {
+        if state_tree.get(&self.path).as_layout().get_minimized() && self.is_window(state_tree) {
+            return self.get_minimized_contents(state_tree);
+        }
         let mut merged_content = PixelMap::new();
         let mode = state_tree.get(&self.path).as_layout().get_mode().clone();
 
@@ -294,6 +495,7 @@ impl EzObject for Layout {
             LayoutMode::Float => self.get_float_mode_contents(merged_content, state_tree),
             LayoutMode::Screen => self.get_screen_mode_contents(state_tree),
             LayoutMode::Tab => self.get_tab_mode_contents(state_tree),
+            LayoutMode::Dock => self.get_dock_mode_contents(state_tree),
         };
 
         merged_content = self.add_user_filler(state_tree, merged_content);
@@ -301,6 +503,7 @@ impl EzObject for Layout {
         merged_content = self.add_empty_filler(state_tree, merged_content);
         merged_content = self.create_horizontal_scroll_box(state_tree, merged_content);
         merged_content = self.create_vertical_scroll_box(state_tree, merged_content);
+        merged_content = self.add_busy_overlay(state_tree, merged_content);
         let state = state_tree.get(&self.get_path()).as_layout();
 
         // Put border around content if border if set
@@ -318,6 +521,9 @@ impl EzObject for Layout {
             state.get_color_config().get_bg_color(),
             state.get_color_config().get_fg_color(),
         );
+        if self.is_window(state_tree) {
+            merged_content = self.add_window_buttons(state_tree, merged_content);
+        }
         merged_content = self.get_modal_contents(state_tree, merged_content);
 
         self.propagate_absolute_positions(state_tree);
@@ -352,18 +558,26 @@ impl EzObject for Layout {
                     return true;
                 }
             }
+            // Scrolling speeds up while the key is held down
+            let steps = scheduler.get_key_repeat_steps();
             if key.code == KeyCode::PageUp {
-                self.handle_scroll_up(state_tree, scheduler);
+                for _ in 0..steps {
+                    self.handle_scroll_up(state_tree, scheduler);
+                }
                 return true;
             } else if key.code == KeyCode::PageDown {
-                self.handle_scroll_down(state_tree, scheduler);
+                for _ in 0..steps {
+                    self.handle_scroll_down(state_tree, scheduler);
+                }
                 return true;
             } else if key.code == KeyCode::Left {
                 let state = state_tree.get_mut(&self.get_path()).as_layout_mut();
                 if state.get_mode() == &LayoutMode::Tab {
                     self.handle_tab_left(state_tree, scheduler);
                 } else {
-                    self.handle_scroll_left(state_tree, scheduler);
+                    for _ in 0..steps {
+                        self.handle_scroll_left(state_tree, scheduler);
+                    }
                 }
                 return true;
             } else if key.code == KeyCode::Right {
@@ -371,7 +585,9 @@ impl EzObject for Layout {
                 if state.get_mode() == &LayoutMode::Tab {
                     self.handle_tab_right(state_tree, scheduler);
                 } else {
-                    self.handle_scroll_right(state_tree, scheduler);
+                    for _ in 0..steps {
+                        self.handle_scroll_right(state_tree, scheduler);
+                    }
                 }
                 return true;
             }
@@ -418,6 +634,20 @@ impl EzObject for Layout {
         if self.on_left_mouse_click_callback(state_tree, callback_tree, scheduler, mouse_pos, custom_data) {
             return true;
         }
+        if self.is_window(state_tree)
+            && self.handle_window_click(state_tree, scheduler, mouse_pos)
+        {
+            return true;
+        }
+        if state_tree.get(&self.path).as_layout().get_mode() == &LayoutMode::Dock
+            && self.handle_dock_click(
+                state_tree,
+                scheduler,
+                IsizeCoordinates::new(mouse_pos.x as isize, mouse_pos.y as isize),
+            )
+        {
+            return true;
+        }
         let state = state_tree.get_mut(&self.path).as_layout_mut();
 
         let v_edge = if state.get_border_config().get_border() {
@@ -443,11 +673,26 @@ impl EzObject for Layout {
             }
         }
 
-        let h_edge = if state.get_border_config().get_border() {
+        let minimap_start = if state.get_border_config().get_border() {
             state.get_effective_size().width + 1
         } else {
             state.get_effective_size().width
         };
+        let minimap_width = state.get_scrolling_config().get_minimap_gutter_width();
+        if state.get_scrolling_config().get_is_scrolling_y()
+            && mouse_pos.x >= minimap_start
+            && mouse_pos.x < minimap_start + minimap_width
+        {
+            let row = if state.get_border_config().get_border() {
+                mouse_pos.y.saturating_sub(1)
+            } else {
+                mouse_pos.y
+            };
+            self.handle_minimap_click(state_tree, scheduler, row);
+            return true;
+        }
+
+        let h_edge = minimap_start + minimap_width;
         if state.get_scrolling_config().get_is_scrolling_y() && mouse_pos.x == h_edge {
             let (scrollbar_size, scrollbar_pos) = self.get_vertical_scrollbar_parameters(
                 state.get_scrolling_config().get_original_height(),
@@ -486,6 +731,16 @@ impl EzObject for Layout {
             mouse_pos,
             custom_data
         );
+        if self.is_window(state_tree)
+            && self.handle_window_drag(state_tree, scheduler, previous_pos, mouse_pos)
+        {
+            return true;
+        }
+        if state_tree.get(&self.path).as_layout().get_mode() == &LayoutMode::Dock
+            && self.handle_dock_drag(state_tree, scheduler, previous_pos, mouse_pos)
+        {
+            return true;
+        }
 
         let state = state_tree.get_mut(&self.path).as_layout_mut();
 
@@ -553,6 +808,35 @@ impl EzObject for Layout {
         consumed
     }
 
+    /// Implement releasing a window or a child that was dragged around in a dock layout.
+    fn on_drag_exit(
+        &self,
+        state_tree: &mut StateTree,
+        callback_tree: &mut CallbackTree,
+        scheduler: &mut SchedulerFrontend,
+        previous_pos: Option<IsizeCoordinates>,
+        mouse_pos: IsizeCoordinates,
+        custom_data: &mut CustomDataMap,
+    ) -> bool {
+        let consumed = self.on_drag_exit_callback(
+            state_tree,
+            callback_tree,
+            scheduler,
+            previous_pos,
+            mouse_pos,
+            custom_data,
+        );
+        let state = state_tree.get_mut(&self.path).as_layout_mut();
+        if state.get_window_drag().is_some() {
+            state.set_window_drag(None);
+            return true;
+        }
+        if state.get_mode() == &LayoutMode::Dock {
+            return self.handle_dock_drag_exit(state_tree, scheduler, mouse_pos) || consumed;
+        }
+        consumed
+    }
+
     fn on_scroll_up(
         &self,
         state_tree: &mut StateTree,
@@ -565,10 +849,10 @@ impl EzObject for Layout {
         }
         let state = state_tree.get_mut(&self.path).as_layout_mut();
         if state.get_scrolling_config().get_is_scrolling_y() {
-            self.handle_scroll_up(state_tree, scheduler);
+            self.handle_wheel_scroll(state_tree, scheduler, false, true);
             return true;
         } else if state.get_scrolling_config().get_is_scrolling_x() {
-            self.handle_scroll_left(state_tree, scheduler);
+            self.handle_wheel_scroll(state_tree, scheduler, true, true);
             return true;
         }
         false
@@ -586,10 +870,10 @@ impl EzObject for Layout {
         }
         let state = state_tree.get_mut(&self.path).as_layout_mut();
         if state.get_scrolling_config().get_is_scrolling_y() {
-            self.handle_scroll_down(state_tree, scheduler);
+            self.handle_wheel_scroll(state_tree, scheduler, false, false);
             return true;
         } else if state.get_scrolling_config().get_is_scrolling_x() {
-            self.handle_scroll_right(state_tree, scheduler);
+            self.handle_wheel_scroll(state_tree, scheduler, true, false);
             return true;
         }
         false
@@ -654,16 +938,14 @@ impl EzObject for Layout {
 impl Layout {
     /// Initialize an instance of this object using the passed config coming from [ez_parser]
     pub fn from_config(
-        config: Vec<String>,
+        config: Vec<ConfigLine>,
         id: String,
         path: String,
         scheduler: &mut SchedulerFrontend,
-        file: String,
-        line: usize,
-    ) -> Self {
+    ) -> Result<Self, ParseError> {
         let mut obj = Layout::new(id, path, scheduler);
-        obj.load_ez_config(config, scheduler, file, line).unwrap();
-        obj
+        obj.load_ez_config(config, scheduler)?;
+        Ok(obj)
     }
 
     fn handle_scroll_drag_x(
@@ -779,24 +1061,33 @@ impl Layout {
         contents
     }
 
-    /// Overwrite a PixelMap of current own content with the content of the active modal. Modals
-    /// overlap all content.
+    /// Overwrite a PixelMap of current own content with the content of the open modals. Modals
+    /// overlap all content, and are drawn in the order they were opened so the last one is on top.
     fn get_modal_contents(&self, state_tree: &mut StateTree, mut contents: PixelMap) -> PixelMap {
-        if !state_tree.get(&self.get_path()).as_layout().has_modal() {
-            return contents;
+        let modals = state_tree
+            .get(&self.get_path())
+            .as_layout()
+            .get_modals()
+            .clone();
+        for modal in modals.iter() {
+            contents = self.get_single_modal_contents(state_tree, modal, contents);
         }
+        contents
+    }
 
+    /// Overwrite a PixelMap of current own content with the content of a single modal.
+    fn get_single_modal_contents(
+        &self,
+        state_tree: &mut StateTree,
+        modal: &EzObjects,
+        mut contents: PixelMap,
+    ) -> PixelMap {
         // Size modal
         let parent_size = state_tree
             .get(&self.get_path())
             .as_layout()
             .get_size()
             .clone();
-        let modal = state_tree
-            .get(&self.get_path())
-            .as_layout()
-            .get_modal()
-            .clone();
         let state = &mut state_tree.get_mut(&modal.as_ez_object().get_path()).obj;
         resize_with_size_hint(state, parent_size.get_width(), parent_size.get_height());
         reposition_with_pos_hint(
@@ -834,41 +1125,107 @@ impl Layout {
         }
         contents
     }
+    /// Dim the content of a busy layout and draw a spinner, followed by its' [busy_text], in the
+    /// middle of it. See [LayoutState.set_busy].
+    fn add_busy_overlay(&self, state_tree: &StateTree, mut contents: PixelMap) -> PixelMap {
+        let state = state_tree.get(&self.get_path()).as_layout();
+        if !state.get_busy() || contents.is_empty() {
+            return contents;
+        }
+        for pixel in contents.iter_mut().flatten() {
+            pixel.foreground_color =
+                dim(pixel.foreground_color, pixel.background_color, BUSY_OPACITY);
+        }
+        let frame = BUSY_SPINNER_FRAMES[state.get_busy_frame() % BUSY_SPINNER_FRAMES.len()];
+        let text = format!("{} {}", frame, state.get_busy_text());
+        let symbols: Vec<char> = text.trim_end().chars().take(contents.len()).collect();
+        let start_x = (contents.len() - symbols.len()) / 2;
+        let y = contents[0].len().saturating_sub(1) / 2;
+        for (x, symbol) in symbols.into_iter().enumerate() {
+            if let Some(pixel) = contents[start_x + x].get_mut(y) {
+                pixel.symbol = symbol.to_string();
+                pixel.foreground_color = state.get_color_config().get_fg_color();
+                pixel.background_color = state.get_color_config().get_bg_color();
+            }
+        }
+        contents
+    }
+
     /// Fill any empty positions with [Pixel] from [get_filler]
     pub fn add_user_filler(&self, state_tree: &mut StateTree, mut contents: PixelMap) -> PixelMap {
-        let state = state_tree.get_mut(&self.get_path()).as_layout_mut();
+        let unfilled_areas = self.get_unfilled_areas(state_tree);
+        let state = state_tree.get(&self.get_path()).as_layout();
         if !state.get_fill() {
             return contents;
         }
+        let width = state.get_effective_size().width;
+        let height = state.get_effective_size().height;
 
-        let filler = Pixel::new(
+        // Some layout modes fill empty space with the solid filler themselves; with a pattern or
+        // header region those positions are filled again
+        let solid_filler = Pixel::new(
             state.get_filler_symbol(),
             state.get_color_config().get_filler_fg_color(),
             state.get_color_config().get_filler_bg_color(),
         );
+        let refill_solid = state.get_filler_pattern() != FillerPattern::Solid
+            || state.get_filler_header_height() > 0;
 
-        for x in 0..contents.len() {
-            for y in contents[x].iter_mut() {
-                if y.symbol.is_empty() || y.symbol == " " {
-                    y.symbol = filler.symbol.clone();
-                    y.foreground_color = filler.foreground_color;
-                    y.background_color = filler.background_color;
+        for (x, column) in contents.iter_mut().enumerate() {
+            for (y, pixel) in column.iter_mut().enumerate() {
+                if unfilled_areas.iter().any(|(area_x, area_y, area_width, area_height)| {
+                    x >= *area_x
+                        && x < area_x + area_width
+                        && y >= *area_y
+                        && y < area_y + area_height
+                }) {
+                    continue;
+                }
+                let is_solid_filler = refill_solid
+                    && pixel.symbol == solid_filler.symbol
+                    && pixel.foreground_color == solid_filler.foreground_color
+                    && pixel.background_color == solid_filler.background_color;
+                if pixel.symbol.is_empty() || pixel.symbol == " " || is_solid_filler {
+                    let filler = get_filler(state, x, y, width, height);
+                    pixel.symbol = filler.symbol;
+                    pixel.foreground_color = filler.foreground_color;
+                    pixel.background_color = filler.background_color;
                 }
             }
-            while contents[x].len() < (state.get_effective_size().height) {
-                contents[x].push(filler.clone());
+            while column.len() < height {
+                let y = column.len();
+                column.push(get_filler(state, x, y, width, height));
             }
         }
-        while contents.len() < state.get_effective_size().width {
-            let mut new_x = Vec::new();
-            for _ in 0..state.get_effective_size().height {
-                new_x.push(filler.clone());
-            }
-            contents.push(new_x);
+        while contents.len() < width {
+            let x = contents.len();
+            contents.push((0..height).map(|y| get_filler(state, x, y, width, height)).collect());
         }
         contents
     }
 
+    /// Get the areas (x, y, width, height) of children that should not be filled, because this is
+    /// a float layout that does not [fill_behind_children].
+    fn get_unfilled_areas(&self, state_tree: &mut StateTree) -> Vec<(usize, usize, usize, usize)> {
+        let state = state_tree.get(&self.get_path()).as_layout();
+        if state.get_mode() != &LayoutMode::Float || state.get_fill_behind_children() {
+            return Vec::new();
+        }
+        let mut areas = Vec::new();
+        for child in self.get_children_in_view(state_tree) {
+            let child_state = state_tree.get(&child.as_ez_object().get_path()).as_generic();
+            let position = child_state.get_position();
+            let size = child_state.get_size();
+            areas.push((
+                position.get_x(),
+                position.get_y(),
+                size.get_width(),
+                size.get_height(),
+            ));
+        }
+        areas
+    }
+
     /// Fill any empty positions with empty [Pixel]. Used to fill full size of the layout in case
     /// the user did not define a custom filler.
     pub fn add_empty_filler(&self, state_tree: &mut StateTree, mut contents: PixelMap) -> PixelMap {
@@ -889,6 +1246,41 @@ impl Layout {
         contents
     }
 
+    /// Apply [stripe_children] and [highlight_child_on_hover] to the content of a child. Children
+    /// (or table rows) with an odd index get the stripe color as background where they are drawn
+    /// in their own background color, so e.g. a selected sub widget keeps its' color. A hovered
+    /// child is drawn in the [hover_color] of its' background.
+    pub fn style_child_content(
+        &self,
+        state_tree: &StateTree,
+        child_path: &str,
+        index: usize,
+        hovered: bool,
+        mut content: PixelMap,
+    ) -> PixelMap {
+        let state = state_tree.get(&self.path).as_layout();
+        let stripe_color = state.get_stripe_children();
+        let striped = stripe_color != Color::Reset && index % 2 == 1;
+        let hovered = hovered && state.get_highlight_child_on_hover();
+        if !striped && !hovered {
+            return content;
+        }
+        let child_bg_color = state_tree
+            .get(child_path)
+            .as_generic()
+            .get_color_config()
+            .get_bg_color();
+        for pixel in content.iter_mut().flatten() {
+            if striped && pixel.background_color == child_bg_color {
+                pixel.background_color = stripe_color;
+            }
+            if hovered {
+                pixel.background_color = hover_color(pixel.background_color);
+            }
+        }
+        content
+    }
+
     /// Get layout children that are in view based on view_size and view_page properties.
     pub fn get_children_in_view(&self, state_tree: &mut StateTree) -> &[EzObjects] {
         if self.children.is_empty() {
@@ -919,3 +1311,52 @@ impl Layout {
         &self.children[child_start..child_end]
     }
 }
+
+/// Get the filler [Pixel] of a position in a layout of a size, based on its' [filler_pattern] and
+/// header region.
+fn get_filler(state: &LayoutState, x: usize, y: usize, width: usize, height: usize) -> Pixel {
+    let colors = state.get_color_config();
+    let header_height = state.get_filler_header_height();
+    if y < header_height {
+        return Pixel::new(
+            state.get_filler_header_symbol(),
+            colors.get_filler_header_fg_color(),
+            colors.get_filler_header_bg_color(),
+        );
+    }
+    // Patterns start below the header
+    let y = y - header_height;
+    let height = height.saturating_sub(header_height);
+    let color = colors.get_filler_bg_color();
+    let alt_color = colors.get_filler_alt_bg_color();
+    let steps = state.get_filler_gradient_steps();
+    let bg_color = match state.get_filler_pattern() {
+        FillerPattern::Solid => color,
+        FillerPattern::Checkerboard if (x + y) % 2 == 1 => alt_color,
+        FillerPattern::HorizontalStripes if y % 2 == 1 => alt_color,
+        FillerPattern::VerticalStripes if x % 2 == 1 => alt_color,
+        FillerPattern::Checkerboard
+        | FillerPattern::HorizontalStripes
+        | FillerPattern::VerticalStripes => color,
+        FillerPattern::HorizontalGradient => {
+            blend(color, alt_color, get_gradient_amount(x, width, steps))
+        }
+        FillerPattern::VerticalGradient => {
+            blend(color, alt_color, get_gradient_amount(y, height, steps))
+        }
+    };
+    Pixel::new(state.get_filler_symbol(), colors.get_filler_fg_color(), bg_color)
+}
+
+/// Get how far along a gradient of a length a position is, between 0 and 1. If steps is more than
+/// 0 the gradient is divided in that many bands of a single color.
+fn get_gradient_amount(position: usize, length: usize, steps: usize) -> f64 {
+    match steps {
+        0 if length > 1 => position as f64 / (length - 1) as f64,
+        0 | 1 => 0.0,
+        _ => {
+            let band = (position * steps / length.max(1)).min(steps - 1);
+            band as f64 / (steps - 1) as f64
+        }
+    }
+}
@@ -1,5 +1,6 @@
 mod box_mode;
 pub mod children;
+mod dock_mode;
 mod float_mode;
 pub mod layout;
 mod screen_mode;
@@ -7,3 +8,4 @@ mod scrolling;
 mod stack_mode;
 mod tab_mode;
 mod table_mode;
+mod window;
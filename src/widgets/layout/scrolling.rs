@@ -1,9 +1,12 @@
-use crate::run::definitions::{Pixel, PixelMap, StateTree};
+use crate::run::definitions::{IsizeCoordinates, Pixel, PixelMap, Size, StateTree};
 use crate::scheduler::scheduler::SchedulerFrontend;
 use crate::states::ez_state::GenericState;
 use crate::widgets::ez_object::EzObject;
 use crate::widgets::layout::layout::Layout;
 
+/// Symbols used by the mini-map, from empty to densely filled contents.
+const MINIMAP_SYMBOLS: [&str; 4] = [" ", "░", "▒", "▓"];
+
 // Scrolling implementations
 impl Layout {
     /// Handle command by user to scroll down by increasing the scroll_view of y
@@ -12,43 +15,20 @@ impl Layout {
         state_tree: &mut StateTree,
         scheduler: &mut SchedulerFrontend,
     ) {
-        let state = state_tree.get_mut(&self.path).as_layout_mut();
-        if !state.get_scrolling_config().get_scroll_y() {
-            return;
-        }
-        let scroll_chunk = (state.get_effective_size().height as f64
-            / state.get_scrolling_config().get_original_height() as f64)
-            * 0.75;
-        let new_view_start = f64::min(
-            1.0,
-            state.get_scrolling_config().get_scroll_start_y() + scroll_chunk,
-        );
-        state
-            .get_scrolling_config_mut()
-            .set_scroll_start_y(new_view_start);
-        state.update(scheduler);
-        self.propagate_absolute_positions(state_tree);
+        let state = state_tree.get(&self.path).as_layout();
+        let scroll_chunk = state
+            .get_scrolling_config()
+            .get_scroll_chunk_y(state.get_effective_size().height);
+        self.move_view_y(state_tree, scheduler, scroll_chunk);
     }
 
     /// Handle command by user to scroll down by decreasing the scroll_view of y
     pub fn handle_scroll_up(&self, state_tree: &mut StateTree, scheduler: &mut SchedulerFrontend) {
-        let state = state_tree.get_mut(&self.path).as_layout_mut();
-        if !state.get_scrolling_config().get_scroll_y() {
-            return;
-        }
-        let scroll_chunk = (state.get_effective_size().height as f64
-            / state.get_scrolling_config().get_original_height() as f64)
-            * 0.75;
-        let new_view_start = if state.get_scrolling_config().get_scroll_start_y() > scroll_chunk {
-            state.get_scrolling_config().get_scroll_start_y() - scroll_chunk
-        } else {
-            0.0
-        };
-        state
-            .get_scrolling_config_mut()
-            .set_scroll_start_y(new_view_start);
-        state.update(scheduler);
-        self.propagate_absolute_positions(state_tree);
+        let state = state_tree.get(&self.path).as_layout();
+        let scroll_chunk = state
+            .get_scrolling_config()
+            .get_scroll_chunk_y(state.get_effective_size().height);
+        self.move_view_y(state_tree, scheduler, -scroll_chunk);
     }
 
     /// Handle command by user to scroll down by increasing the scroll_view of x
@@ -57,16 +37,66 @@ impl Layout {
         state_tree: &mut StateTree,
         scheduler: &mut SchedulerFrontend,
     ) {
+        let state = state_tree.get(&self.path).as_layout();
+        let scroll_chunk = state
+            .get_scrolling_config()
+            .get_scroll_chunk_x(state.get_effective_size().width);
+        self.move_view_x(state_tree, scheduler, scroll_chunk);
+    }
+
+    /// Handle command by user to scroll down by decreasing the scroll_view of x
+    pub fn handle_scroll_left(
+        &self,
+        state_tree: &mut StateTree,
+        scheduler: &mut SchedulerFrontend,
+    ) {
+        let state = state_tree.get(&self.path).as_layout();
+        let scroll_chunk = state
+            .get_scrolling_config()
+            .get_scroll_chunk_x(state.get_effective_size().width);
+        self.move_view_x(state_tree, scheduler, -scroll_chunk);
+    }
+
+    /// Handle a mouse wheel tick by scrolling [scroll_step] lines or columns, or a chunk if no
+    /// scroll step is set. Scrolls down (or right) unless [backwards] is true.
+    pub fn handle_wheel_scroll(
+        &self,
+        state_tree: &mut StateTree,
+        scheduler: &mut SchedulerFrontend,
+        horizontal: bool,
+        backwards: bool,
+    ) {
+        let state = state_tree.get(&self.path).as_layout();
+        let scroll_chunk = if horizontal {
+            state
+                .get_scrolling_config()
+                .get_wheel_scroll_chunk_x(state.get_effective_size().width)
+        } else {
+            state
+                .get_scrolling_config()
+                .get_wheel_scroll_chunk_y(state.get_effective_size().height)
+        };
+        let scroll_chunk = if backwards {
+            -scroll_chunk
+        } else {
+            scroll_chunk
+        };
+        if horizontal {
+            self.move_view_x(state_tree, scheduler, scroll_chunk);
+        } else {
+            self.move_view_y(state_tree, scheduler, scroll_chunk);
+        }
+    }
+
+    /// Move the view on the x axis by a fraction of the maximum view start, if scrolling x is
+    /// enabled.
+    fn move_view_x(&self, state_tree: &mut StateTree, scheduler: &mut SchedulerFrontend, by: f64) {
         let state = state_tree.get_mut(&self.path).as_layout_mut();
         if !state.get_scrolling_config().get_scroll_x() {
             return;
         }
-        let scroll_chunk = state.get_effective_size().width as f64
-            / state.get_scrolling_config().get_original_width() as f64;
-        let new_view_start = f64::min(
-            1.0,
-            state.get_scrolling_config().get_scroll_start_x() + scroll_chunk,
-        );
+        let new_view_start =
+            (state.get_scrolling_config().get_scroll_start_x() + by).clamp(0.0, 1.0);
         state
             .get_scrolling_config_mut()
             .set_scroll_start_x(new_view_start);
@@ -74,27 +104,18 @@ impl Layout {
         self.propagate_absolute_positions(state_tree);
     }
 
-    /// Handle command by user to scroll down by decreasing the scroll_view of x
-    pub fn handle_scroll_left(
-        &self,
-        state_tree: &mut StateTree,
-        scheduler: &mut SchedulerFrontend,
-    ) {
+    /// Move the view on the y axis by a fraction of the maximum view start, if scrolling y is
+    /// enabled.
+    fn move_view_y(&self, state_tree: &mut StateTree, scheduler: &mut SchedulerFrontend, by: f64) {
         let state = state_tree.get_mut(&self.path).as_layout_mut();
-        if !state.get_scrolling_config().get_scroll_x() {
+        if !state.get_scrolling_config().get_scroll_y() {
             return;
         }
-
-        let scroll_chunk = state.get_effective_size().width as f64
-            / state.get_scrolling_config().get_original_width() as f64;
-        let new_view_start = if state.get_scrolling_config().get_scroll_start_x() > scroll_chunk {
-            state.get_scrolling_config().get_scroll_start_x() - scroll_chunk
-        } else {
-            0.0
-        };
+        let new_view_start =
+            (state.get_scrolling_config().get_scroll_start_y() + by).clamp(0.0, 1.0);
         state
             .get_scrolling_config_mut()
-            .set_scroll_start_x(new_view_start);
+            .set_scroll_start_y(new_view_start);
         state.update(scheduler);
         self.propagate_absolute_positions(state_tree);
     }
@@ -144,7 +165,11 @@ impl Layout {
             || largest <= state.get_effective_size().height
         {
             state.get_scrolling_config_mut().set_is_scrolling_y(false);
-            return contents;
+            if state.get_scrolling_config().get_minimap_gutter_width() == 0 {
+                return contents;
+            }
+            let visible_contents = contents.clone();
+            return self.add_minimap(state_tree, visible_contents, &contents, 0);
         }
         state
             .get_scrolling_config_mut()
@@ -163,9 +188,143 @@ impl Layout {
             .map(|x| x[view_start..view_end].to_vec())
             .collect();
         self.propagate_absolute_positions(state_tree);
+        let scrolled_contents = self.add_minimap(state_tree, scrolled_contents, &contents, view_start);
         self.create_vertical_scrollbar(state_tree, scrolled_contents, view_start)
     }
 
+    /// Add the mini-map to the right of the visible contents if it is enabled. The mini-map is a
+    /// compressed overview of all contents, in which the part that is in view is highlighted.
+    fn add_minimap(
+        &self,
+        state_tree: &mut StateTree,
+        mut visible_contents: PixelMap,
+        contents: &PixelMap,
+        view_start: usize,
+    ) -> PixelMap {
+        let state = state_tree.get(&self.get_path()).as_layout();
+        let width = state.get_scrolling_config().get_minimap_gutter_width();
+        let height = state.get_effective_size().height;
+        if width == 0 || height == 0 {
+            return visible_contents;
+        }
+        let (fg_color, _) = state.get_context_colors();
+        let bg_color = state.get_color_config().get_bg_color();
+        let view_fg_color = state.get_color_config().get_selection_fg_color();
+        let view_bg_color = state.get_color_config().get_selection_bg_color();
+
+        let content_width = contents.len();
+        let content_height = contents.iter().map(|x| x.len()).max().unwrap_or(0);
+        let view_end = view_start + height;
+        let col_ratio = content_width as f64 / width as f64;
+        let row_ratio = f64::max(1.0, content_height as f64 / height as f64);
+
+        for x in 0..width {
+            let (col_start, col_end) = get_minimap_range(x, col_ratio, content_width);
+            let mut column = Vec::new();
+            for y in 0..height {
+                let (row_start, row_end) = get_minimap_range(y, row_ratio, content_height);
+                let mut filled = 0;
+                let mut total = 0;
+                for content_column in contents[col_start..col_end].iter() {
+                    for pixel in content_column.iter().take(row_end).skip(row_start) {
+                        total += 1;
+                        if !pixel.symbol.trim().is_empty() {
+                            filled += 1;
+                        }
+                    }
+                }
+                let symbol = if filled == 0 {
+                    MINIMAP_SYMBOLS[0]
+                } else {
+                    let density = filled * (MINIMAP_SYMBOLS.len() - 1) / total;
+                    MINIMAP_SYMBOLS[usize::max(1, density)]
+                };
+                let (fg_color, bg_color) =
+                    if row_start < row_end && row_start < view_end && row_end > view_start {
+                        (view_fg_color, view_bg_color)
+                    } else {
+                        (fg_color, bg_color)
+                    };
+                column.push(Pixel::new(symbol.to_string(), fg_color, bg_color));
+            }
+            visible_contents.push(column);
+        }
+        visible_contents
+    }
+
+    /// Handle a click on the mini-map by scrolling the part of the contents shown at the clicked
+    /// row of the mini-map into the middle of the view.
+    pub fn handle_minimap_click(
+        &self,
+        state_tree: &mut StateTree,
+        scheduler: &mut SchedulerFrontend,
+        row: usize,
+    ) {
+        let state = state_tree.get_mut(&self.path).as_layout_mut();
+        let height = state.get_effective_size().height;
+        let max_scroll_start = state.get_scrolling_config().get_max_scroll_start_y(height);
+        if height == 0 || max_scroll_start == 0 {
+            return;
+        }
+        let row_ratio = state.get_scrolling_config().get_original_height() as f64 / height as f64;
+        let center = (row as f64 + 0.5) * row_ratio;
+        let view_start = (center - height as f64 / 2.0).clamp(0.0, max_scroll_start as f64);
+        state
+            .get_scrolling_config_mut()
+            .set_scroll_start_y(view_start / max_scroll_start as f64);
+        state.update(scheduler);
+        self.propagate_absolute_positions(state_tree);
+    }
+
+    /// Scroll the view the least amount needed to show an area of the contents, given as the
+    /// absolute position and size of e.g. a child widget. If the area is larger than the view, its'
+    /// top left corner is shown. Does nothing if the area is already in view.
+    pub fn scroll_into_view(
+        &self,
+        state_tree: &mut StateTree,
+        scheduler: &mut SchedulerFrontend,
+        pos: IsizeCoordinates,
+        size: Size,
+    ) {
+        let state = state_tree.get_mut(&self.path).as_layout_mut();
+        let content_pos = state.get_effective_absolute_position();
+        let view_size = state.get_effective_size();
+        let config = state.get_scrolling_config_mut();
+        let mut scrolled = false;
+        if config.get_is_scrolling_x() {
+            let view_start = config.get_absolute_scroll_start_x(view_size.width);
+            let max_scroll_start = config.get_max_scroll_start_x(view_size.width);
+            let area_start = (pos.x - content_pos.x + view_start as isize).max(0) as usize;
+            if let Some(new_view_start) =
+                get_view_start_for_area(view_start, view_size.width, area_start, size.width)
+            {
+                if max_scroll_start > 0 {
+                    let new_view_start = new_view_start.min(max_scroll_start);
+                    config.set_scroll_start_x(new_view_start as f64 / max_scroll_start as f64);
+                    scrolled = true;
+                }
+            }
+        }
+        if config.get_is_scrolling_y() {
+            let view_start = config.get_absolute_scroll_start_y(view_size.height);
+            let max_scroll_start = config.get_max_scroll_start_y(view_size.height);
+            let area_start = (pos.y - content_pos.y + view_start as isize).max(0) as usize;
+            if let Some(new_view_start) =
+                get_view_start_for_area(view_start, view_size.height, area_start, size.height)
+            {
+                if max_scroll_start > 0 {
+                    let new_view_start = new_view_start.min(max_scroll_start);
+                    config.set_scroll_start_y(new_view_start as f64 / max_scroll_start as f64);
+                    scrolled = true;
+                }
+            }
+        }
+        if scrolled {
+            state.update(scheduler);
+            self.propagate_absolute_positions(state_tree);
+        }
+    }
+
     /// Create a scrolling bar for a horizontal scrollbox
     fn create_horizontal_scrollbar(
         &self,
@@ -262,3 +421,28 @@ impl Layout {
         (scrollbar_size, scrollbar_pos)
     }
 }
+
+/// Get the range of content cells (start inclusive, end exclusive) shown by a single cell of the
+/// mini-map, given the amount of content cells per mini-map cell.
+fn get_minimap_range(index: usize, ratio: f64, content_size: usize) -> (usize, usize) {
+    let start = usize::min((index as f64 * ratio).floor() as usize, content_size);
+    let end = usize::min(((index + 1) as f64 * ratio).ceil() as usize, content_size);
+    (start, usize::max(start, end))
+}
+
+/// Get the start of a view on one axis that shows an area, moving the view as little as possible.
+/// Returns None if the area is already in view.
+fn get_view_start_for_area(
+    view_start: usize,
+    view_length: usize,
+    area_start: usize,
+    area_length: usize,
+) -> Option<usize> {
+    if area_start < view_start {
+        Some(area_start)
+    } else if area_start + area_length > view_start + view_length {
+        Some((area_start + area_length).saturating_sub(view_length).min(area_start))
+    } else {
+        None
+    }
+}
@@ -184,10 +184,19 @@ impl Layout {
                 state.get_size_mut().set_height(effective_size.height + 1);
             }
 
-            let child_content = generic_child.get_contents(state_tree);
+            let child_content = generic_child.get_styled_contents(state_tree);
             if child_content.is_empty() {
                 continue;
             } // handle empty widget
+            let hovered = state_tree.get(&self.path).as_layout().get_hovered_child()
+                == &generic_child.get_path();
+            let child_content = self.style_child_content(
+                state_tree,
+                &generic_child.get_path(),
+                content_list.len(),
+                hovered,
+                child_content,
+            );
             let state = state_tree
                 .get_mut(&generic_child.get_path())
                 .as_generic_mut(); // re-borrow
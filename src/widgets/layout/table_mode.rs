@@ -63,6 +63,7 @@ impl Layout {
             &own_scrolling,
             &own_effective_size,
         );
+        let content_list = self.style_table_rows(state_tree, &child_table, content_list);
 
         let content = self.draw_table(
             &child_table,
@@ -349,7 +350,7 @@ impl Layout {
                 state.get_size_mut().set_height(effective_size.height)
             }
 
-            let child_content = generic_child.get_contents(state_tree);
+            let child_content = generic_child.get_styled_contents(state_tree);
             let state = state_tree
                 .get_mut(&generic_child.get_path())
                 .as_generic_mut(); // re-borrow
@@ -363,7 +364,48 @@ impl Layout {
         content_list
     }
 
-    /// Draw a list of child content in a table using the child_table as a model for the
+    /// Apply [stripe_children] and [highlight_child_on_hover] per table row rather than per child,
+    /// so a row is striped and highlighted as a whole.
+    fn style_table_rows(
+        &self,
+        state_tree: &mut StateTree,
+        child_table: &[Vec<usize>],
+        content_list: Vec<PixelMap>,
+    ) -> Vec<PixelMap> {
+        let paths: Vec<String> = self
+            .get_children_in_view(state_tree)
+            .iter()
+            .map(|child| child.as_ez_object().get_path())
+            .collect();
+        let mut child_rows = vec![0; content_list.len()];
+        for col in child_table {
+            for (row, index) in col.iter().enumerate() {
+                if *index < child_rows.len() {
+                    child_rows[*index] = row;
+                }
+            }
+        }
+        let hovered_child = state_tree.get(&self.path).as_layout().get_hovered_child();
+        let hovered_row = paths
+            .iter()
+            .position(|path| path == hovered_child)
+            .map(|index| child_rows[index]);
+        content_list
+            .into_iter()
+            .enumerate()
+            .map(|(index, content)| {
+                self.style_child_content(
+                    state_tree,
+                    &paths[index],
+                    child_rows[index],
+                    hovered_row == Some(child_rows[index]),
+                    content,
+                )
+            })
+            .collect()
+    }
+
+        /// Draw a list of child content in a table using the child_table as a model for the
     /// orientation.
     fn draw_table(
         &self,
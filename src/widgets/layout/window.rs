@@ -0,0 +1,166 @@
+use std::cmp::{max, min};
+
+use crate::run::definitions::{Coordinates, IsizeCoordinates, Pixel, PixelMap, Size, StateTree};
+use crate::scheduler::scheduler::SchedulerFrontend;
+use crate::states::definitions::{LayoutMode, WindowDrag};
+use crate::states::ez_state::GenericState;
+use crate::widgets::layout::layout::Layout;
+
+impl Layout {
+    /// Returns a bool representing whether this layout behaves as a window, i.e. it is the child
+    /// of a float layout and can be moved, resized, minimized or maximized by the user.
+    pub fn is_window(&self, state_tree: &StateTree) -> bool {
+        if self.path == "/root" || self.path.starts_with("/root/modal") {
+            return false;
+        }
+        let parent = self.path.rsplit_once('/').unwrap().0;
+        let state = state_tree.get(&self.path).as_layout();
+        (state.get_movable() || state.get_resizable() || state.get_window_buttons())
+            && state_tree.get(parent).as_layout().get_mode() == &LayoutMode::Float
+    }
+
+    /// Get the effective size of the float layout this window is in.
+    fn get_window_parent_size(&self, state_tree: &StateTree) -> (usize, usize) {
+        let parent = self.path.rsplit_once('/').unwrap().0;
+        let parent_size = state_tree.get(parent).as_layout().get_effective_size();
+        (parent_size.width, parent_size.height)
+    }
+
+    /// Handle a left mouse drag on a window. Dragging the top row moves the window if it is
+    /// movable, dragging the right or bottom edge resizes the window if it is resizable.
+    /// Absolute mouse positions are used as the window itself moves while being dragged.
+    pub fn handle_window_drag(
+        &self,
+        state_tree: &mut StateTree,
+        scheduler: &mut SchedulerFrontend,
+        previous_pos: Option<IsizeCoordinates>,
+        mouse_pos: IsizeCoordinates,
+    ) -> bool {
+        let (parent_width, parent_height) = self.get_window_parent_size(state_tree);
+        let state = state_tree.get_mut(&self.path).as_layout_mut();
+        let absolute_position = state.get_absolute_position();
+        let abs_mouse_pos = IsizeCoordinates::new(
+            absolute_position.x + mouse_pos.x,
+            absolute_position.y + mouse_pos.y,
+        );
+
+        if previous_pos.is_none() || state.get_window_drag().is_none() {
+            if state.get_maximized() {
+                return false;
+            }
+            let width = state.get_size().get_width() as isize;
+            let height = state.get_size().get_height() as isize;
+            let on_right_edge = mouse_pos.x == width - 1;
+            let on_bottom_edge = mouse_pos.y == height - 1;
+            let drag = if state.get_resizable()
+                && !state.get_minimized()
+                && (on_right_edge || on_bottom_edge)
+            {
+                WindowDrag::Resize(on_right_edge, on_bottom_edge)
+            } else if state.get_movable() && mouse_pos.y == 0 {
+                WindowDrag::Move
+            } else {
+                return false;
+            };
+            state.set_pos_hint_x(None);
+            state.set_pos_hint_y(None);
+            state.set_window_drag(Some((drag, abs_mouse_pos)));
+            return true;
+        }
+
+        let (drag, last_pos) = state.get_window_drag().unwrap();
+        let diff_x = abs_mouse_pos.x - last_pos.x;
+        let diff_y = abs_mouse_pos.y - last_pos.y;
+        let x = state.get_position().get_x() as isize;
+        let y = state.get_position().get_y() as isize;
+        let width = state.get_size().get_width() as isize;
+        let height = state.get_size().get_height() as isize;
+        match drag {
+            WindowDrag::Move => {
+                let max_x = max(parent_width as isize - width, 0);
+                let max_y = max(parent_height as isize - height, 0);
+                state.set_x(min(max(x + diff_x, 0), max_x) as usize);
+                state.set_y(min(max(y + diff_y, 0), max_y) as usize);
+            }
+            WindowDrag::Resize(resize_width, resize_height) => {
+                let min_size = if state.get_border_config().get_border() {
+                    3
+                } else {
+                    1
+                };
+                if resize_width {
+                    state.set_size_hint_x(None);
+                    let new_width = min(width + diff_x, parent_width as isize - x);
+                    state.set_width(max(new_width, min_size) as usize);
+                }
+                if resize_height {
+                    state.set_size_hint_y(None);
+                    let new_height = min(height + diff_y, parent_height as isize - y);
+                    state.set_height(max(new_height, min_size) as usize);
+                }
+            }
+        }
+        state.set_window_drag(Some((drag, abs_mouse_pos)));
+        state.update(scheduler);
+        scheduler.update_widget(self.path.rsplit_once('/').unwrap().0);
+        true
+    }
+
+    /// Handle a left click on the minimize or maximize button of a window.
+    pub fn handle_window_click(
+        &self,
+        state_tree: &mut StateTree,
+        scheduler: &mut SchedulerFrontend,
+        mouse_pos: Coordinates,
+    ) -> bool {
+        let (parent_width, parent_height) = self.get_window_parent_size(state_tree);
+        let state = state_tree.get_mut(&self.path).as_layout_mut();
+        let width = state.get_size().get_width();
+        if !state.get_window_buttons() || mouse_pos.y != 0 || width < 4 {
+            return false;
+        }
+        if mouse_pos.x == width - 3 {
+            state.toggle_minimized();
+        } else if mouse_pos.x == width - 2 {
+            state.toggle_maximized(Size::new(parent_width, parent_height));
+        } else {
+            return false;
+        }
+        state.update(scheduler);
+        scheduler.update_widget(self.path.rsplit_once('/').unwrap().0);
+        true
+    }
+
+    /// Get the contents of a minimized window: a single row containing its' ID.
+    pub fn get_minimized_contents(&self, state_tree: &StateTree) -> PixelMap {
+        let state = state_tree.get(&self.path).as_layout();
+        let fg_color = state.get_color_config().get_border_fg_color();
+        let bg_color = state.get_color_config().get_border_bg_color();
+        let title: Vec<char> = state.get_id().chars().collect();
+        let mut content = PixelMap::new();
+        for x in 0..state.get_size().get_width() {
+            let symbol = title.get(x).map_or(" ".to_string(), |x| x.to_string());
+            content.push(vec![Pixel::new(symbol, fg_color, bg_color)]);
+        }
+        self.add_window_buttons(state_tree, content)
+    }
+
+    /// Draw the minimize and maximize buttons in the top right corner of a window if enabled.
+    pub fn add_window_buttons(&self, state_tree: &StateTree, mut content: PixelMap) -> PixelMap {
+        let state = state_tree.get(&self.path).as_layout();
+        if !state.get_window_buttons() || content.len() < 4 {
+            return content;
+        }
+        let fg_color = state.get_color_config().get_border_fg_color();
+        let bg_color = state.get_color_config().get_border_bg_color();
+        let width = content.len();
+        let maximize_symbol = if state.get_maximized() { "❐" } else { "□" };
+        if let Some(pixel) = content[width - 3].first_mut() {
+            *pixel = Pixel::new("_".to_string(), fg_color, bg_color);
+        }
+        if let Some(pixel) = content[width - 2].first_mut() {
+            *pixel = Pixel::new(maximize_symbol.to_string(), fg_color, bg_color);
+        }
+        content
+    }
+}
@@ -0,0 +1,339 @@
+//! A widget that shows a list of text items, one per line. Only the visible items are rendered, so
+//! lists with many thousands of items (e.g. logs) stay fast. Items can be selected with the mouse
+//! or the arrow keys.
+use std::io::{Error, ErrorKind};
+
+use crossterm::event::{Event, KeyCode, KeyEvent};
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::parser::ez_definition::ConfigLine;
+use crate::parser::load_base_properties;
+use crate::parser::load_common_properties::load_common_property;
+use crate::parser::parse_lang::ParseError;
+use crate::run::definitions::{CallbackTree, Coordinates, Pixel, PixelMap, StateTree};
+use crate::scheduler::definitions::CustomDataMap;
+use crate::scheduler::scheduler::SchedulerFrontend;
+use crate::states::ez_state::{EzState, GenericState};
+use crate::states::list_view_state::ListViewState;
+use crate::widgets::ez_object::{EzObject, EzObjects};
+use crate::widgets::helper_functions::add_padding;
+use crate::Context;
+
+#[derive(Clone, Debug)]
+pub struct ListView {
+    /// ID of the widget, used to construct [path]
+    pub id: String,
+
+    /// Full path to this widget, e.g. "/root_layout/layout_2/THIS_ID"
+    pub path: String,
+
+    /// Runtime state of this widget, see [ListViewState] and [State]
+    pub state: ListViewState,
+}
+
+impl ListView {
+    pub fn new(id: String, path: String, scheduler: &mut SchedulerFrontend) -> Self {
+        ListView {
+            id,
+            path: path.clone(),
+            state: ListViewState::new(path, scheduler),
+        }
+    }
+
+    pub fn from_state(
+        id: String,
+        path: String,
+        _scheduler: &mut SchedulerFrontend,
+        state: EzState,
+    ) -> Self {
+        ListView {
+            id,
+            path: path.clone(),
+            state: state.as_list_view().to_owned(),
+        }
+    }
+}
+
+impl EzObject for ListView {
+    fn load_ez_parameter(
+        &mut self,
+        parameter_name: String,
+        parameter_value: String,
+        scheduler: &mut SchedulerFrontend,
+    ) -> Result<(), Error> {
+        let consumed =
+            load_common_property(&parameter_name, parameter_value.clone(), self, scheduler)?;
+        if consumed {
+            return Ok(());
+        }
+        match parameter_name.as_str() {
+            "auto_scroll" => load_base_properties::load_bool_property(
+                parameter_value.trim(),
+                scheduler,
+                self.path.clone(),
+                &parameter_name,
+                self.get_state_mut(),
+            )?,
+            // Items are separated by commas, e.g. "first, second, third"
+            "items" => self.state.set_items(
+                parameter_value
+                    .split(',')
+                    .map(|x| x.trim().to_string())
+                    .filter(|x| !x.is_empty())
+                    .collect(),
+            ),
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("Invalid parameter name for list view: {}", parameter_name),
+                ))
+            }
+        }
+        Ok(())
+    }
+
+    fn set_id(&mut self, id: &str) {
+        self.id = id.to_string()
+    }
+
+    fn get_id(&self) -> String {
+        self.id.clone()
+    }
+
+    fn set_path(&mut self, id: &str) {
+        self.id = id.to_string()
+    }
+
+    fn get_path(&self) -> String {
+        self.path.clone()
+    }
+
+    fn get_state(&self) -> EzState {
+        EzState::ListView(self.state.clone())
+    }
+
+    fn get_state_mut(&mut self) -> &mut dyn GenericState {
+        &mut self.state
+    }
+
+    fn get_contents(&self, state_tree: &mut StateTree) -> PixelMap {
+        let state = state_tree.get_mut(&self.get_path()).as_list_view_mut();
+        if state.get_auto_scale().get_auto_scale_width() {
+            let widest = state
+                .get_items()
+                .iter()
+                .map(|x| x.graphemes(true).count())
+                .max()
+                .unwrap_or(0);
+            state.set_effective_width(widest);
+        }
+        if state.get_auto_scale().get_auto_scale_height() {
+            state.set_effective_height(state.get_item_count());
+        }
+        let width = state.get_effective_size().width;
+        let height = state.get_effective_size().height;
+        // The list may have shrunk or the widget may have grown since the last frame
+        state.set_scroll_y(state.get_scroll_y().min(state.get_max_scroll_y()));
+
+        let colors = state.get_color_config();
+        let (fg_color, bg_color) = if state.get_disabled() {
+            (colors.get_disabled_fg_color(), colors.get_disabled_bg_color())
+        } else {
+            (colors.get_fg_color(), colors.get_bg_color())
+        };
+        let (selection_fg_color, selection_bg_color) =
+            (colors.get_selection_fg_color(), colors.get_selection_bg_color());
+
+        // Only the visible items are rendered
+        let first_item = state.get_scroll_y();
+        let lines: Vec<Vec<&str>> = state
+            .get_items()
+            .iter()
+            .skip(first_item)
+            .take(height)
+            .map(|x| x.graphemes(true).take(width).collect())
+            .collect();
+
+        let mut contents = PixelMap::new();
+        for x in 0..width {
+            let mut new_y = Vec::new();
+            for y in 0..height {
+                let symbol = lines
+                    .get(y)
+                    .and_then(|line| line.get(x))
+                    .map_or(" ".to_string(), |x| x.to_string());
+                if state.get_selected_item() == Some(first_item + y) {
+                    new_y.push(Pixel::new(symbol, selection_fg_color, selection_bg_color));
+                } else {
+                    new_y.push(Pixel::new(symbol, fg_color, bg_color));
+                }
+            }
+            contents.push(new_y);
+        }
+
+        let state = state_tree.get(&self.get_path()).as_list_view();
+        let parent_colors = state_tree
+            .get(self.get_path().rsplit_once('/').unwrap().0)
+            .as_generic()
+            .get_color_config();
+        contents = add_padding(
+            contents,
+            state.get_padding(),
+            parent_colors.get_bg_color(),
+            parent_colors.get_fg_color(),
+        );
+        contents
+    }
+
+    fn handle_event(
+        &self,
+        event: Event,
+        state_tree: &mut StateTree,
+        callback_tree: &mut CallbackTree,
+        scheduler: &mut SchedulerFrontend,
+        custom_data: &mut CustomDataMap,
+    ) -> bool {
+        if let Event::Key(key) = event {
+            let state = state_tree.get(&self.path).as_list_view();
+            let last_item = match state.get_item_count() {
+                0 => return false,
+                i => i - 1,
+            };
+            let page = state.get_effective_size().height.max(1);
+            let item = match (key.code, state.get_selected_item()) {
+                (KeyCode::Down, None) | (KeyCode::Home, _) => 0,
+                (KeyCode::Down, Some(i)) => (i + 1).min(last_item),
+                (KeyCode::Up, Some(i)) => i.saturating_sub(1),
+                (KeyCode::PageDown, i) => (i.unwrap_or(0) + page).min(last_item),
+                (KeyCode::PageUp, i) => i.unwrap_or(0).saturating_sub(page),
+                (KeyCode::End, _) => last_item,
+                _ => return false,
+            };
+            self.select_item(item, state_tree, callback_tree, scheduler, custom_data);
+            return true;
+        }
+        false
+    }
+
+    fn on_left_mouse_click(
+        &self,
+        state_tree: &mut StateTree,
+        callback_tree: &mut CallbackTree,
+        scheduler: &mut SchedulerFrontend,
+        mouse_pos: Coordinates,
+        custom_data: &mut CustomDataMap,
+    ) -> bool {
+        let consumed = self.on_left_mouse_click_callback(
+            state_tree,
+            callback_tree,
+            scheduler,
+            mouse_pos,
+            custom_data,
+        );
+        if consumed {
+            return consumed;
+        }
+        scheduler.set_selected_widget(&self.path, Some(mouse_pos));
+        let state = state_tree.get(&self.path).as_list_view();
+        if let Some(pos) = state.local_to_content_position(mouse_pos) {
+            let item = state.get_scroll_y() + pos.y;
+            if item < state.get_item_count() {
+                self.select_item(item, state_tree, callback_tree, scheduler, custom_data);
+            }
+        }
+        true
+    }
+
+    fn on_scroll_up(
+        &self,
+        state_tree: &mut StateTree,
+        callback_tree: &mut CallbackTree,
+        scheduler: &mut SchedulerFrontend,
+        custom_data: &mut CustomDataMap,
+    ) -> bool {
+        if self.on_scroll_up_callback(state_tree, callback_tree, scheduler, custom_data) {
+            return true;
+        }
+        let state = state_tree.get_mut(&self.path).as_list_view_mut();
+        state.set_scroll_y(state.get_scroll_y().saturating_sub(1));
+        state.update(scheduler);
+        true
+    }
+
+    fn on_scroll_down(
+        &self,
+        state_tree: &mut StateTree,
+        callback_tree: &mut CallbackTree,
+        scheduler: &mut SchedulerFrontend,
+        custom_data: &mut CustomDataMap,
+    ) -> bool {
+        if self.on_scroll_down_callback(state_tree, callback_tree, scheduler, custom_data) {
+            return true;
+        }
+        let state = state_tree.get_mut(&self.path).as_list_view_mut();
+        state.set_scroll_y((state.get_scroll_y() + 1).min(state.get_max_scroll_y()));
+        state.update(scheduler);
+        true
+    }
+
+    fn get_clone(&self, scheduler: &mut SchedulerFrontend) -> EzObjects {
+        let mut clone = self.clone();
+        let mut new_state = ListViewState::new(self.path.clone(), scheduler);
+        new_state.copy_state_values(self.get_state());
+        clone.state = new_state;
+        EzObjects::ListView(clone)
+    }
+}
+impl ListView {
+    /// Initialize an instance of this object using the passed config coming from [ez_parser]
+    pub fn from_config(
+        config: Vec<ConfigLine>,
+        id: String,
+        path: String,
+        scheduler: &mut SchedulerFrontend,
+    ) -> Result<Self, ParseError> {
+        let mut obj = ListView::new(id, path, scheduler);
+        obj.load_ez_config(config, scheduler)?;
+        Ok(obj)
+    }
+
+    /// Select an item, scroll it into view and call the on_item_selected callback if the selection
+    /// changed.
+    fn select_item(
+        &self,
+        item: usize,
+        state_tree: &mut StateTree,
+        callback_tree: &mut CallbackTree,
+        scheduler: &mut SchedulerFrontend,
+        custom_data: &mut CustomDataMap,
+    ) {
+        let state = state_tree.get_mut(&self.path).as_list_view_mut();
+        let changed = state.get_selected_item() != Some(item);
+        state.set_selected_item(Some(item));
+        let visible_items = state.get_effective_size().height.max(1);
+        if item < state.get_scroll_y() {
+            state.set_scroll_y(item);
+        } else if item >= state.get_scroll_y() + visible_items {
+            state.set_scroll_y(item + 1 - visible_items);
+        }
+        state.update(scheduler);
+        if changed {
+            if let Some(ref mut i) = callback_tree.get_mut(&self.path).obj.on_item_selected {
+                i(Context::new(self.path.clone(), state_tree, scheduler, custom_data));
+            }
+        }
+    }
+}
+
+/// Returns a bool representing whether a list view wants to handle a key itself rather than have
+/// it handled by the global key handler. Up and Down move the selected item, unless the first or
+/// last item is selected, so the user can still leave the list view with them.
+pub fn captures_key(state: &ListViewState, key: KeyEvent) -> bool {
+    let items = state.get_item_count();
+    match (key.code, state.get_selected_item()) {
+        (KeyCode::Up, Some(i)) => i > 0,
+        (KeyCode::Down, Some(i)) => i + 1 < items,
+        (KeyCode::Down, None) => items > 0,
+        _ => false,
+    }
+}
@@ -1,12 +1,21 @@
 pub mod button;
 pub mod canvas;
 pub mod checkbox;
+pub mod data_grid;
 pub mod dropdown;
 pub mod ez_object;
+pub mod heatmap;
 mod helper_functions;
 pub mod label;
+pub mod list_view;
 pub mod layout;
 pub mod progress_bar;
+pub mod qr_code;
 pub mod radio_button;
+pub mod rating;
 pub mod slider;
+pub mod stepper;
+pub mod sub_cell;
+pub mod text_box;
 pub mod text_input;
+pub mod ticker;
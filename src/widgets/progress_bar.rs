@@ -1,14 +1,17 @@
 //! A widget that displays text non-interactively.
 use std::io::{Error, ErrorKind};
 
+use crate::parser::ez_definition::ConfigLine;
 use crate::parser::load_base_properties;
 use crate::parser::load_common_properties::load_common_property;
+use crate::parser::parse_lang::ParseError;
 use crate::run::definitions::{Pixel, PixelMap, StateTree};
 use crate::scheduler::scheduler::SchedulerFrontend;
 use crate::states::ez_state::{EzState, GenericState};
 use crate::states::progress_bar_state::ProgressBarState;
 use crate::widgets::ez_object::{EzObject, EzObjects};
 use crate::widgets::helper_functions::{add_border, add_padding};
+use crate::widgets::sub_cell::horizontal_bar;
 
 #[derive(Clone, Debug)]
 pub struct ProgressBar {
@@ -72,6 +75,13 @@ impl EzObject for ProgressBar {
                 &parameter_name,
                 self.get_state_mut(),
             )?,
+            "smooth" => load_base_properties::load_bool_property(
+                parameter_value.trim(),
+                scheduler,
+                self.path.clone(),
+                &parameter_name,
+                self.get_state_mut(),
+            )?,
             _ => {
                 return Err(Error::new(
                     ErrorKind::InvalidData,
@@ -120,12 +130,18 @@ impl EzObject for ProgressBar {
 
         let value_pos =
             ((state.get_effective_size().width - 1) as f64 * state.get_normalized_value()) as usize;
+        let smooth_bar = if state.get_smooth() {
+            horizontal_bar(state.get_normalized_value(), state.get_effective_size().width)
+        } else {
+            Vec::new()
+        };
 
         for x in 0..state.get_effective_size().width {
-            let symbol = if value_pos != 0 && x <= value_pos {
-                "█"
-            } else {
-                "▓"
+            let symbol = match smooth_bar.get(x) {
+                Some(symbol) if symbol != " " => symbol,
+                Some(_) => "▓",
+                None if value_pos != 0 && x <= value_pos => "█",
+                None => "▓",
             };
             contents.push(vec![Pixel::new(
                 symbol.to_string(),
@@ -165,15 +181,13 @@ impl EzObject for ProgressBar {
 impl ProgressBar {
     /// Initialize an instance of this object using the passed config coming from [ez_parser]
     pub fn from_config(
-        config: Vec<String>,
+        config: Vec<ConfigLine>,
         id: String,
         path: String,
         scheduler: &mut SchedulerFrontend,
-        file: String,
-        line: usize,
-    ) -> Self {
+    ) -> Result<Self, ParseError> {
         let mut obj = ProgressBar::new(id, path, scheduler);
-        obj.load_ez_config(config, scheduler, file, line).unwrap();
-        obj
+        obj.load_ez_config(config, scheduler)?;
+        Ok(obj)
     }
 }
@@ -0,0 +1,235 @@
+//! A widget that shows a text as a QR code, e.g. to share a URL or a token with a phone. Each cell
+//! holds two modules of the code on top of each other using half blocks, and modules are scaled up
+//! to fill as much of the widget as possible.
+use std::io::{Error, ErrorKind};
+
+use crate::parser::ez_definition::ConfigLine;
+use crate::parser::load_base_properties;
+use crate::parser::load_common_properties::load_common_property;
+use crate::parser::parse_lang::ParseError;
+use crate::parser::parse_properties;
+use crate::run::definitions::{Pixel, PixelMap, StateTree};
+use crate::scheduler::scheduler::SchedulerFrontend;
+use crate::states::definitions::{HorizontalAlignment, VerticalAlignment};
+use crate::states::ez_state::{EzState, GenericState};
+use crate::states::qr_code_state::QrCodeState;
+use crate::widgets::ez_object::{EzObject, EzObjects};
+use crate::widgets::helper_functions::{add_border, add_padding};
+
+#[derive(Clone, Debug)]
+pub struct QrCode {
+    /// ID of the widget, used to construct [path]
+    pub id: String,
+
+    /// Full path to this widget, e.g. "/root_layout/layout_2/THIS_ID"
+    pub path: String,
+
+    /// Runtime state of this widget, see [QrCodeState] and [State]
+    pub state: QrCodeState,
+}
+
+impl QrCode {
+    pub fn new(id: String, path: String, scheduler: &mut SchedulerFrontend) -> Self {
+        QrCode {
+            id,
+            path: path.clone(),
+            state: QrCodeState::new(path, scheduler),
+        }
+    }
+
+    pub fn from_state(
+        id: String,
+        path: String,
+        _scheduler: &mut SchedulerFrontend,
+        state: EzState,
+    ) -> Self {
+        QrCode {
+            id,
+            path,
+            state: state.as_qr_code().to_owned(),
+        }
+    }
+}
+
+impl EzObject for QrCode {
+    fn load_ez_parameter(
+        &mut self,
+        parameter_name: String,
+        parameter_value: String,
+        scheduler: &mut SchedulerFrontend,
+    ) -> Result<(), Error> {
+        let consumed =
+            load_common_property(&parameter_name, parameter_value.clone(), self, scheduler)?;
+        if consumed {
+            return Ok(());
+        }
+        match parameter_name.as_str() {
+            "text" => load_base_properties::load_string_property(
+                parameter_value
+                    .strip_prefix(' ')
+                    .unwrap_or(parameter_value.as_str()),
+                scheduler,
+                self.path.clone(),
+                &parameter_name,
+                self.get_state_mut(),
+            )?,
+            "error_correction" => {
+                if load_base_properties::resolve_property(parameter_value.trim(), self.path.clone())
+                    .is_none()
+                {
+                    parse_properties::parse_error_correction_property(parameter_value.trim())?;
+                }
+                load_base_properties::load_string_property(
+                    parameter_value.trim(),
+                    scheduler,
+                    self.path.clone(),
+                    &parameter_name,
+                    self.get_state_mut(),
+                )?
+            }
+            "quiet_zone" => load_base_properties::load_usize_property(
+                parameter_value.trim(),
+                scheduler,
+                self.path.clone(),
+                &parameter_name,
+                self.get_state_mut(),
+            )?,
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("Invalid parameter name for QR code: {}", parameter_name),
+                ))
+            }
+        }
+        Ok(())
+    }
+
+    fn set_id(&mut self, id: &str) {
+        self.id = id.to_string()
+    }
+
+    fn get_id(&self) -> String {
+        self.id.clone()
+    }
+
+    fn set_path(&mut self, id: &str) {
+        self.id = id.to_string()
+    }
+
+    fn get_path(&self) -> String {
+        self.path.clone()
+    }
+
+    fn get_state(&self) -> EzState {
+        EzState::QrCode(self.state.clone())
+    }
+
+    fn get_state_mut(&mut self) -> &mut dyn GenericState {
+        &mut self.state
+    }
+
+    fn get_contents(&self, state_tree: &mut StateTree) -> PixelMap {
+        let state = state_tree.get_mut(&self.get_path()).as_qr_code_mut();
+        let modules = state.get_modules();
+        // Every cell holds two rows of modules
+        if state.get_auto_scale().get_auto_scale_width() {
+            state.set_effective_width(modules.len());
+        }
+        if state.get_auto_scale().get_auto_scale_height() {
+            state.set_effective_height((modules.len() + 1) / 2);
+        }
+        let width = state.get_effective_size().width;
+        let height = state.get_effective_size().height;
+        // Light modules are drawn in the foreground color, so the default white on black colors
+        // give the dark on light code that scanners expect
+        let (fg_color, bg_color) = if state.get_disabled() {
+            (
+                state.get_color_config().get_disabled_fg_color(),
+                state.get_color_config().get_disabled_bg_color(),
+            )
+        } else {
+            (
+                state.get_color_config().get_fg_color(),
+                state.get_color_config().get_bg_color(),
+            )
+        };
+
+        // Largest whole amount of cells per module at which the code fits. If it does not fit at
+        // all, the widget stays empty; a cropped code cannot be scanned
+        let code_size = modules.len();
+        let scale = width.min(height * 2).checked_div(code_size).unwrap_or(0);
+        let scaled_size = code_size * scale;
+        let offset_x = match state.get_halign() {
+            HorizontalAlignment::Left => 0,
+            HorizontalAlignment::Right => width - scaled_size,
+            HorizontalAlignment::Center => (width - scaled_size) / 2,
+        };
+        let offset_y = match state.get_valign() {
+            VerticalAlignment::Top => 0,
+            VerticalAlignment::Middle => (height * 2 - scaled_size) / 2,
+            VerticalAlignment::Bottom => height * 2 - scaled_size,
+        };
+        let is_light = |x: usize, y: usize| {
+            if scale == 0 || x < offset_x || y < offset_y {
+                return false;
+            }
+            let (module_x, module_y) = ((x - offset_x) / scale, (y - offset_y) / scale);
+            module_x < code_size && module_y < code_size && !modules[module_y][module_x]
+        };
+
+        let mut contents = PixelMap::new();
+        for x in 0..width {
+            let mut new_y = Vec::new();
+            for y in 0..height {
+                let symbol = match (is_light(x, y * 2), is_light(x, y * 2 + 1)) {
+                    (true, true) => "█",
+                    (true, false) => "▀",
+                    (false, true) => "▄",
+                    (false, false) => " ",
+                };
+                new_y.push(Pixel::new(symbol.to_string(), fg_color, bg_color));
+            }
+            contents.push(new_y);
+        }
+        if state.get_border_config().get_border() {
+            contents = add_border(
+                contents,
+                state.get_border_config(),
+                state.get_color_config(),
+            );
+        }
+        let state = state_tree.get(&self.get_path()).as_qr_code();
+        let parent_colors = state_tree
+            .get(self.get_path().rsplit_once('/').unwrap().0)
+            .as_generic()
+            .get_color_config();
+        contents = add_padding(
+            contents,
+            state.get_padding(),
+            parent_colors.get_bg_color(),
+            parent_colors.get_fg_color(),
+        );
+        contents
+    }
+
+    fn get_clone(&self, scheduler: &mut SchedulerFrontend) -> EzObjects {
+        let mut clone = self.clone();
+        let mut new_state = QrCodeState::new(self.path.clone(), scheduler);
+        new_state.copy_state_values(self.get_state());
+        clone.state = new_state;
+        EzObjects::QrCode(clone)
+    }
+}
+impl QrCode {
+    /// Initialize an instance of this object using the passed config coming from [ez_parser]
+    pub fn from_config(
+        config: Vec<ConfigLine>,
+        id: String,
+        path: String,
+        scheduler: &mut SchedulerFrontend,
+    ) -> Result<Self, ParseError> {
+        let mut obj = QrCode::new(id, path, scheduler);
+        obj.load_ez_config(config, scheduler)?;
+        Ok(obj)
+    }
+}
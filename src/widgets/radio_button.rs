@@ -5,8 +5,10 @@
 //! called for the radio button that became active.
 use std::io::{Error, ErrorKind};
 
+use crate::parser::ez_definition::ConfigLine;
 use crate::parser::load_base_properties;
 use crate::parser::load_common_properties::load_common_property;
+use crate::parser::parse_lang::ParseError;
 use crate::run::definitions::{CallbackTree, Coordinates, Pixel, PixelMap, StateTree};
 use crate::scheduler::definitions::CustomDataMap;
 use crate::scheduler::scheduler::SchedulerFrontend;
@@ -101,10 +103,12 @@ impl EzObject for RadioButton {
                 &parameter_name,
                 self.get_state_mut(),
             )?,
-            _ => panic!(
-                "Invalid parameter name for radio button: {}",
-                parameter_name
-            ),
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("Invalid parameter name for radio button: {}", parameter_name),
+                ))
+            }
         }
         Ok(())
     }
@@ -225,16 +229,14 @@ impl EzObject for RadioButton {
 impl RadioButton {
     /// Initialize an instance of this object using the passed config coming from [ez_parser]
     pub fn from_config(
-        config: Vec<String>,
+        config: Vec<ConfigLine>,
         id: String,
         path: String,
         scheduler: &mut SchedulerFrontend,
-        file: String,
-        line: usize,
-    ) -> Self {
+    ) -> Result<Self, ParseError> {
         let mut obj = RadioButton::new(id, path, scheduler);
-        obj.load_ez_config(config, scheduler, file, line).unwrap();
-        obj
+        obj.load_ez_config(config, scheduler)?;
+        Ok(obj)
     }
 
     /// Function that handles this RadioButton being pressed (mouse clicked/keyboard entered).
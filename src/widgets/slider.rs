@@ -4,8 +4,10 @@ use std::io::{Error, ErrorKind};
 
 use crossterm::event::{Event, KeyCode};
 
+use crate::parser::ez_definition::ConfigLine;
 use crate::parser::load_base_properties;
 use crate::parser::load_common_properties::load_common_property;
+use crate::parser::parse_lang::ParseError;
 use crate::run::definitions::{
     CallbackTree, Coordinates, IsizeCoordinates, Pixel, PixelMap, StateTree,
 };
@@ -14,6 +16,7 @@ use crate::states::ez_state::{EzState, GenericState};
 use crate::states::slider_state::SliderState;
 use crate::widgets::ez_object::{EzObject, EzObjects};
 use crate::widgets::helper_functions::add_padding;
+use crate::widgets::sub_cell::horizontal_bar;
 use crate::Context;
 use crate::scheduler::definitions::CustomDataMap;
 
@@ -86,6 +89,13 @@ impl EzObject for Slider {
                 &parameter_name,
                 self.get_state_mut(),
             )?,
+            "smooth" => load_base_properties::load_bool_property(
+                parameter_value.trim(),
+                scheduler,
+                self.path.clone(),
+                &parameter_name,
+                self.get_state_mut(),
+            )?,
             "step" => load_base_properties::load_usize_property(
                 parameter_value.trim(),
                 scheduler,
@@ -148,9 +158,15 @@ impl EzObject for Slider {
         }
 
         let mut contents = PixelMap::new();
-        let value_pos = ((state.get_effective_size().width - 1) as f64
-            * ((state.get_value() - state.get_min()) as f64
-                / (state.get_max() - state.get_min()) as f64)) as usize;
+        let fraction = (state.get_value() - state.get_min()) as f64
+            / (state.get_max() - state.get_min()) as f64;
+        let value_pos = ((state.get_effective_size().width - 1) as f64 * fraction) as usize;
+        // A smooth slider is a bar up to the value, ending in the cell of the value
+        let smooth_bar = if state.get_smooth() {
+            horizontal_bar(fraction, state.get_effective_size().width)
+        } else {
+            Vec::new()
+        };
         for x in 0..state.get_effective_size().width {
             let fg_color = if state.get_disabled() {
                 state.get_color_config().get_disabled_fg_color()
@@ -164,12 +180,14 @@ impl EzObject for Slider {
             } else {
                 state.get_color_config().get_bg_color()
             };
+            let symbol = match smooth_bar.get(x) {
+                Some(symbol) if symbol != " " => symbol.clone(),
+                Some(_) => "━".to_string(),
+                None if x == value_pos => "🮚".to_string(),
+                None => "━".to_string(),
+            };
             contents.push(vec![Pixel::new(
-                if x == value_pos {
-                    "🮚".to_string()
-                } else {
-                    "━".to_string()
-                },
+                symbol,
                 fg_color,
                 bg_color,
             )]);
@@ -311,16 +329,14 @@ impl EzObject for Slider {
 impl Slider {
     /// Initialize an instance of this object using the passed config coming from [ez_parser]
     pub fn from_config(
-        config: Vec<String>,
+        config: Vec<ConfigLine>,
         id: String,
         path: String,
         scheduler: &mut SchedulerFrontend,
-        file: String,
-        line: usize,
-    ) -> Self {
+    ) -> Result<Self, ParseError> {
         let mut obj = Slider::new(id, path, scheduler);
-        obj.load_ez_config(config, scheduler, file, line).unwrap();
-        obj
+        obj.load_ez_config(config, scheduler)?;
+        Ok(obj)
     }
 
     fn value_from_mouse_pos(
@@ -354,11 +370,13 @@ impl Slider {
         scheduler: &mut SchedulerFrontend,
         custom_data: &mut CustomDataMap,
     ) {
+        let steps = scheduler.get_key_repeat_steps();
         let state = state_tree.get_mut(&self.path).as_slider_mut();
         if state.get_value() == state.get_min() {
             return;
         }
-        state.set_value(state.get_value() - state.get_step());
+        let value = state.get_value().saturating_sub(state.get_step() * steps);
+        state.set_value(value.max(state.get_min()));
         state.update(scheduler);
         self.on_value_change_callback(state_tree, callback_tree, scheduler, custom_data);
     }
@@ -369,11 +387,13 @@ impl Slider {
         scheduler: &mut SchedulerFrontend,
         custom_data: &mut CustomDataMap,
     ) {
+        let steps = scheduler.get_key_repeat_steps();
         let state = state_tree.get_mut(&self.path).as_slider_mut();
         if state.get_value() == state.get_max() {
             return;
         }
-        state.set_value(state.get_value() + state.get_step());
+        let value = state.get_value() + state.get_step() * steps;
+        state.set_value(value.min(state.get_max()));
         state.update(scheduler);
         self.on_value_change_callback(state_tree, callback_tree, scheduler, custom_data);
     }
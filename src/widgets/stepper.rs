@@ -0,0 +1,307 @@
+//! A widget that cycles through a list of options, displayed as "< option >".
+use std::io::{Error, ErrorKind};
+
+use crossterm::event::{Event, KeyCode};
+
+use crate::parser::ez_definition::ConfigLine;
+use crate::parser::load_base_properties;
+use crate::parser::load_common_properties::load_common_property;
+use crate::parser::parse_lang::ParseError;
+use crate::run::definitions::{CallbackTree, Coordinates, Pixel, PixelMap, StateTree};
+use crate::scheduler::definitions::CustomDataMap;
+use crate::scheduler::scheduler::SchedulerFrontend;
+use crate::states::ez_state::{EzState, GenericState};
+use crate::states::stepper_state::StepperState;
+use crate::widgets::ez_object::{EzObject, EzObjects};
+use crate::widgets::helper_functions::add_padding;
+use crate::Context;
+
+#[derive(Clone, Debug)]
+pub struct Stepper {
+    /// ID of the widget, used to construct [path]
+    pub id: String,
+
+    /// Full path to this widget, e.g. "/root_layout/layout_2/THIS_ID"
+    pub path: String,
+
+    /// Runtime state of this widget, see [StepperState] and [State]
+    pub state: StepperState,
+}
+
+impl Stepper {
+    pub fn new(id: String, path: String, scheduler: &mut SchedulerFrontend) -> Self {
+        Stepper {
+            id,
+            path: path.clone(),
+            state: StepperState::new(path, scheduler),
+        }
+    }
+
+    pub fn from_state(
+        id: String,
+        path: String,
+        _scheduler: &mut SchedulerFrontend,
+        state: EzState,
+    ) -> Self {
+        Stepper {
+            id,
+            path: path.clone(),
+            state: state.as_stepper().to_owned(),
+        }
+    }
+}
+
+impl EzObject for Stepper {
+    fn load_ez_parameter(
+        &mut self,
+        parameter_name: String,
+        parameter_value: String,
+        scheduler: &mut SchedulerFrontend,
+    ) -> Result<(), Error> {
+        let consumed =
+            load_common_property(&parameter_name, parameter_value.clone(), self, scheduler)?;
+        if consumed {
+            return Ok(());
+        }
+        match parameter_name.as_str() {
+            "options" => load_base_properties::load_string_list_property(
+                parameter_value.trim(),
+                scheduler,
+                self.path.clone(),
+                &parameter_name,
+                self.get_state_mut(),
+            )?,
+            "index" => load_base_properties::load_usize_property(
+                parameter_value.trim(),
+                scheduler,
+                self.path.clone(),
+                &parameter_name,
+                self.get_state_mut(),
+            )?,
+            "value" => load_base_properties::load_string_property(
+                parameter_value.trim(),
+                scheduler,
+                self.path.clone(),
+                &parameter_name,
+                self.get_state_mut(),
+            )?,
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("Invalid parameter name for stepper: {}", parameter_name),
+                ))
+            }
+        }
+        Ok(())
+    }
+
+    fn set_id(&mut self, id: &str) {
+        self.id = id.to_string()
+    }
+
+    fn get_id(&self) -> String {
+        self.id.clone()
+    }
+
+    fn set_path(&mut self, id: &str) {
+        self.id = id.to_string()
+    }
+
+    fn get_path(&self) -> String {
+        self.path.clone()
+    }
+
+    fn get_state(&self) -> EzState {
+        EzState::Stepper(self.state.clone())
+    }
+
+    fn get_state_mut(&mut self) -> &mut dyn GenericState {
+        &mut self.state
+    }
+
+    fn get_contents(&self, state_tree: &mut StateTree) -> PixelMap {
+        let state = state_tree.get_mut(&self.get_path()).as_stepper_mut();
+
+        state.set_effective_height(1);
+        if state.get_auto_scale().get_auto_scale_width() {
+            let longest = state
+                .get_options()
+                .iter()
+                .map(|x| x.chars().count())
+                .max()
+                .unwrap_or(0);
+            state.set_effective_width(longest + 4);
+        }
+        let width = state.get_effective_size().width;
+        if width == 0 {
+            return PixelMap::new();
+        }
+
+        // Center the value between the chevrons, cutting it off if it does not fit
+        let space = width.saturating_sub(4);
+        let value: String = state.get_value().chars().take(space).collect();
+        let left_space = (space - value.chars().count()) / 2;
+        let text = format!("{}{}", " ".repeat(left_space), value);
+        let text: Vec<char> = if width < 4 {
+            let mut text: Vec<char> = "<>".chars().collect();
+            text.truncate(width);
+            text
+        } else {
+            format!("< {:<1$} >", text, space).chars().collect()
+        };
+
+        let (fg_color, bg_color) = if state.get_disabled() {
+            (
+                state.get_color_config().get_disabled_fg_color(),
+                state.get_color_config().get_disabled_bg_color(),
+            )
+        } else if state.get_selected() {
+            (
+                state.get_color_config().get_selection_fg_color(),
+                state.get_color_config().get_selection_bg_color(),
+            )
+        } else {
+            (
+                state.get_color_config().get_fg_color(),
+                state.get_color_config().get_bg_color(),
+            )
+        };
+        let mut contents = PixelMap::new();
+        for symbol in text {
+            contents.push(vec![Pixel::new(symbol.to_string(), fg_color, bg_color)]);
+        }
+
+        let state = state_tree.get(&self.get_path()).as_stepper();
+        let parent_colors = state_tree
+            .get(self.get_path().rsplit_once('/').unwrap().0)
+            .as_generic()
+            .get_color_config();
+        contents = add_padding(
+            contents,
+            state.get_padding(),
+            parent_colors.get_bg_color(),
+            parent_colors.get_fg_color(),
+        );
+        contents
+    }
+
+    fn handle_event(
+        &self,
+        event: Event,
+        state_tree: &mut StateTree,
+        callback_tree: &mut CallbackTree,
+        scheduler: &mut SchedulerFrontend,
+        custom_data: &mut CustomDataMap,
+    ) -> bool {
+        if let Event::Key(key) = event {
+            if key.code == KeyCode::Left {
+                self.handle_step(false, state_tree, callback_tree, scheduler, custom_data);
+                return true;
+            } else if key.code == KeyCode::Right {
+                self.handle_step(true, state_tree, callback_tree, scheduler, custom_data);
+                return true;
+            } else if callback_tree
+                .get(&self.get_path())
+                .obj
+                .keymap
+                .contains(key.code, key.modifiers)
+            {
+                let func = callback_tree
+                    .get_mut(&self.get_path())
+                    .obj
+                    .keymap
+                    .get_mut(key.code, key.modifiers)
+                    .unwrap();
+                let context = Context::new(self.get_path(), state_tree, scheduler, custom_data);
+                func(context, key.code, key.modifiers);
+                return true;
+            }
+        }
+        false
+    }
+
+    fn on_left_mouse_click(
+        &self,
+        state_tree: &mut StateTree,
+        callback_tree: &mut CallbackTree,
+        scheduler: &mut SchedulerFrontend,
+        mouse_pos: Coordinates,
+        custom_data: &mut CustomDataMap,
+    ) -> bool {
+        let consumed = self.on_press_callback(state_tree, callback_tree, scheduler, custom_data);
+        if consumed {
+            return consumed;
+        }
+        let width = state_tree
+            .get(&self.path)
+            .as_stepper()
+            .get_effective_size()
+            .width;
+        if mouse_pos.x == 0 {
+            self.handle_step(false, state_tree, callback_tree, scheduler, custom_data);
+        } else if mouse_pos.x + 1 == width {
+            self.handle_step(true, state_tree, callback_tree, scheduler, custom_data);
+        }
+        true
+    }
+
+    fn on_hover(
+        &self,
+        state_tree: &mut StateTree,
+        callback_tree: &mut CallbackTree,
+        scheduler: &mut SchedulerFrontend,
+        mouse_pos: Coordinates,
+        custom_data: &mut CustomDataMap,
+    ) -> bool {
+        let consumed =
+            self.on_hover_callback(state_tree, callback_tree, scheduler, mouse_pos, custom_data);
+        if consumed {
+            return consumed;
+        }
+        scheduler.set_selected_widget(&self.path, Some(mouse_pos));
+        true
+    }
+
+    fn get_clone(&self, scheduler: &mut SchedulerFrontend) -> EzObjects {
+        let mut clone = self.clone();
+        let mut new_state = StepperState::new(self.path.clone(), scheduler);
+        new_state.copy_state_values(self.get_state());
+        clone.state = new_state;
+        EzObjects::Stepper(clone)
+    }
+}
+impl Stepper {
+    /// Initialize an instance of this object using the passed config coming from [ez_parser]
+    pub fn from_config(
+        config: Vec<ConfigLine>,
+        id: String,
+        path: String,
+        scheduler: &mut SchedulerFrontend,
+    ) -> Result<Self, ParseError> {
+        let mut obj = Stepper::new(id, path, scheduler);
+        obj.load_ez_config(config, scheduler)?;
+        Ok(obj)
+    }
+
+    /// Select the next (or previous) option and call the on_value_change callback.
+    fn handle_step(
+        &self,
+        next: bool,
+        state_tree: &mut StateTree,
+        callback_tree: &mut CallbackTree,
+        scheduler: &mut SchedulerFrontend,
+        custom_data: &mut CustomDataMap,
+    ) {
+        let state = state_tree.get_mut(&self.path).as_stepper_mut();
+        if state.get_options().len() < 2 {
+            return;
+        }
+        if next {
+            state.select_next();
+        } else {
+            state.select_previous();
+        }
+        state.update(scheduler);
+        self.on_value_change_callback(state_tree, callback_tree, scheduler, custom_data);
+    }
+}
@@ -0,0 +1,403 @@
+//! # Sub-cell drawing
+//!
+//! Utilities to draw finer-grained than whole cells, using block elements (eighths of a cell) and
+//! braille patterns (a 2x4 grid of dots per cell). Used by the Slider and ProgressBar widgets when
+//! "smooth" is enabled, and can be used to draw custom charts and visualizations on a Canvas with
+//! a [BrailleGrid]. Not every terminal font contains these symbols; see the Canvas page of the
+//! gallery to check.
+use std::cmp::Ordering;
+
+use crossterm::style::Color;
+
+use crate::run::definitions::{IsizeCoordinates, Pixel, PixelMap};
+use crate::states::ez_state::GenericState;
+
+/// Blocks filling 0 to 8 eighths of a cell from the left.
+const HORIZONTAL_EIGHTHS: [&str; 9] = [" ", "▏", "▎", "▍", "▌", "▋", "▊", "▉", "█"];
+
+/// Blocks filling 0 to 8 eighths of a cell from the bottom.
+const VERTICAL_EIGHTHS: [&str; 9] = [" ", "▁", "▂", "▃", "▄", "▅", "▆", "▇", "█"];
+
+/// Bits of the braille dots by [x][y] position within a cell, see the Unicode braille patterns
+/// block.
+const BRAILLE_DOTS: [[u32; 4]; 2] = [[0x01, 0x02, 0x04, 0x40], [0x08, 0x10, 0x20, 0x80]];
+
+/// Get the symbols of a horizontal bar 'width' cells wide, filled from the left for a fraction
+/// between 0 and 1 of its' width. The cell the bar ends in is filled partially, in eighths of a
+/// cell.
+///
+/// # Example:
+///
+/// ```
+/// use ez_term::horizontal_bar;
+///
+/// assert_eq!(horizontal_bar(0.75, 2).join(""), "█▌");
+/// ```
+pub fn horizontal_bar(fraction: f64, width: usize) -> Vec<String> {
+    bar(fraction, width, &HORIZONTAL_EIGHTHS)
+}
+
+/// Get the symbols of a vertical bar 'height' cells high, filled from the bottom for a fraction
+/// between 0 and 1 of its' height. The symbols are ordered from top to bottom. The cell the bar
+/// ends in is filled partially, in eighths of a cell.
+pub fn vertical_bar(fraction: f64, height: usize) -> Vec<String> {
+    let mut symbols = bar(fraction, height, &VERTICAL_EIGHTHS);
+    symbols.reverse();
+    symbols
+}
+
+/// Get the symbols of a bar filled for a fraction of its' length, using the eighths symbols of
+/// its' direction.
+fn bar(fraction: f64, length: usize, eighths: &[&str; 9]) -> Vec<String> {
+    let filled = (fraction.clamp(0.0, 1.0) * (length * 8) as f64).round() as usize;
+    (0..length)
+        .map(|i| eighths[filled.saturating_sub(i * 8).min(8)].to_string())
+        .collect()
+}
+
+/// Get the symbol of a cell of a horizontal bar that ends within the cell at a fraction between 0
+/// and 1 of the cell, rounded to an eighth of a cell.
+pub fn horizontal_eighth(fraction: f64) -> String {
+    HORIZONTAL_EIGHTHS[(fraction.clamp(0.0, 1.0) * 8.0).round() as usize].to_string()
+}
+
+/// A grid of braille dots for drawing in a resolution of 2x4 dots per cell, e.g. to plot a line
+/// chart on a Canvas. Dots are set by their position in dots, starting at the top left.
+///
+/// Points, lines and filled shapes can also be drawn in a virtual coordinate space set with
+/// [set_bounds], e.g. the range of the values of a chart; those are mapped to the dots of the grid
+/// and clipped to it. Each cell is drawn in the pen color of the last dot set in it (see
+/// [set_pen_color]), so several series can be plotted in different colors. Use [for_widget] to
+/// create a grid that fills a widget, and [CanvasState.set_braille_grid] to show it on a Canvas.
+///
+/// # Example:
+///
+/// ```
+/// use ez_term::BrailleGrid;
+///
+/// // A diagonal line through two cells
+/// let mut grid = BrailleGrid::new(2, 1);
+/// for x in 0..4 {
+///     grid.set(x, x);
+/// }
+/// assert_eq!(grid.get_rows(), vec!["⠑⢄".to_string()]);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BrailleGrid {
+    /// Width of the grid in cells
+    width: usize,
+
+    /// Height of the grid in cells
+    height: usize,
+
+    /// Dot bits of each cell by [x][y] cell position
+    cells: Vec<Vec<u32>>,
+
+    /// Color of each cell by [x][y] cell position; None cells use the foreground color passed to
+    /// [get_pixel_map]
+    colors: Vec<Vec<Option<Color>>>,
+
+    /// Color given to the cells of dots that are set, see [set_pen_color]
+    pen_color: Option<Color>,
+
+    /// Virtual coordinate space as (x min, x max, y min, y max), see [set_bounds]
+    bounds: Option<(f64, f64, f64, f64)>,
+}
+impl BrailleGrid {
+    /// Create an empty grid of a size in cells.
+    pub fn new(width: usize, height: usize) -> Self {
+        BrailleGrid {
+            width,
+            height,
+            cells: vec![vec![0; height]; width],
+            colors: vec![vec![None; height]; width],
+            pen_color: None,
+            bounds: None,
+        }
+    }
+
+    /// Create an empty grid filling the content area of a widget (its' size without border and
+    /// padding), e.g. to draw on a Canvas with [CanvasState.set_braille_grid].
+    pub fn for_widget(state: &dyn GenericState) -> Self {
+        let size = state.get_effective_size();
+        BrailleGrid::new(size.width, size.height)
+    }
+
+    /// Get the width of the grid in dots.
+    pub fn get_dot_width(&self) -> usize {
+        self.width * 2
+    }
+
+    /// Get the height of the grid in dots.
+    pub fn get_dot_height(&self) -> usize {
+        self.height * 4
+    }
+
+    /// Set the dot at a position in dots. Positions outside of the grid are ignored.
+    pub fn set(&mut self, x: usize, y: usize) {
+        if x < self.get_dot_width() && y < self.get_dot_height() {
+            self.cells[x / 2][y / 4] |= BRAILLE_DOTS[x % 2][y % 4];
+            if self.pen_color.is_some() {
+                self.colors[x / 2][y / 4] = self.pen_color;
+            }
+        }
+    }
+
+    /// Clear the dot at a position in dots. Positions outside of the grid are ignored.
+    pub fn unset(&mut self, x: usize, y: usize) {
+        if x < self.get_dot_width() && y < self.get_dot_height() {
+            self.cells[x / 2][y / 4] &= !BRAILLE_DOTS[x % 2][y % 4];
+        }
+    }
+
+    /// Returns a bool representing whether the dot at a position in dots is set.
+    pub fn is_set(&self, x: usize, y: usize) -> bool {
+        x < self.get_dot_width()
+            && y < self.get_dot_height()
+            && self.cells[x / 2][y / 4] & BRAILLE_DOTS[x % 2][y % 4] != 0
+    }
+
+    /// Clear all dots and colors.
+    pub fn clear(&mut self) {
+        self.cells = vec![vec![0; self.height]; self.width];
+        self.colors = vec![vec![None; self.height]; self.width];
+    }
+
+    /// Set the color that cells get when a dot is set in them, or None to draw them in the
+    /// foreground color passed to [get_pixel_map].
+    pub fn set_pen_color(&mut self, color: Option<Color>) {
+        self.pen_color = color;
+    }
+
+    pub fn get_pen_color(&self) -> Option<Color> {
+        self.pen_color
+    }
+
+    /// Set the virtual coordinate space that points, lines and shapes are drawn in. The x range
+    /// runs from left to right and the y range from bottom to top, like the axes of a chart; the
+    /// minimum and maximum map to the outermost dots of the grid. Without bounds, coordinates are
+    /// positions in dots starting at the top left.
+    ///
+    /// # Example:
+    ///
+    /// We'll plot a sine wave on a canvas:
+    /// ```
+    /// use ez_term::*;
+    /// let (root_widget, mut state_tree, mut scheduler, mut custom_data) = load_ui();
+    ///
+    /// let state = state_tree.get_mut("my_canvas").as_canvas_mut();
+    /// let mut grid = BrailleGrid::for_widget(state);
+    /// grid.set_bounds(0.0, 6.28, -1.0, 1.0);
+    /// let points: Vec<(f64, f64)> = (0..=100)
+    ///     .map(|i| (i as f64 * 0.0628, (i as f64 * 0.0628).sin()))
+    ///     .collect();
+    /// grid.draw_lines(&points);
+    /// state.set_braille_grid(&grid);
+    /// ```
+    pub fn set_bounds(&mut self, x_min: f64, x_max: f64, y_min: f64, y_max: f64) {
+        self.bounds = Some((x_min, x_max, y_min, y_max));
+    }
+
+    /// Remove the virtual coordinate space set with [set_bounds], so coordinates are positions in
+    /// dots again.
+    pub fn clear_bounds(&mut self) {
+        self.bounds = None;
+    }
+
+    /// Get the position in dots of a position in the virtual coordinate space, see [set_bounds].
+    /// The position can be outside of the grid.
+    pub fn to_dot(&self, x: f64, y: f64) -> IsizeCoordinates {
+        let (dot_x, dot_y) = self.to_dot_f64(x, y);
+        IsizeCoordinates::new(dot_x.round() as isize, dot_y.round() as isize)
+    }
+
+    /// Get the position in the virtual coordinate space of a position in dots, e.g. to find the
+    /// value under the mouse. The inverse of [to_dot].
+    pub fn from_dot(&self, x: isize, y: isize) -> (f64, f64) {
+        match self.bounds {
+            Some((x_min, x_max, y_min, y_max)) => {
+                let last_x = self.get_dot_width().saturating_sub(1).max(1) as f64;
+                let last_y = self.get_dot_height().saturating_sub(1).max(1) as f64;
+                (
+                    x_min + x as f64 / last_x * (x_max - x_min),
+                    y_max - y as f64 / last_y * (y_max - y_min),
+                )
+            }
+            None => (x as f64, y as f64),
+        }
+    }
+
+    /// Set the dot at a position in the virtual coordinate space.
+    pub fn draw_point(&mut self, x: f64, y: f64) {
+        let dot = self.to_dot(x, y);
+        self.set_dot(dot.x, dot.y);
+    }
+
+    /// Draw a straight line between two positions in the virtual coordinate space. The part of the
+    /// line outside of the grid is not drawn.
+    pub fn draw_line(&mut self, from: (f64, f64), to: (f64, f64)) {
+        let (from_x, from_y) = self.to_dot_f64(from.0, from.1);
+        let (to_x, to_y) = self.to_dot_f64(to.0, to.1);
+        // Clip the line to the grid first, so lines far outside of it are cheap to draw
+        let (mut start, mut end) = (0.0_f64, 1.0_f64);
+        let (delta_x, delta_y) = (to_x - from_x, to_y - from_y);
+        let max_x = self.get_dot_width() as f64 - 0.5;
+        let max_y = self.get_dot_height() as f64 - 0.5;
+        for (p, q) in [
+            (-delta_x, from_x + 0.5),
+            (delta_x, max_x - from_x),
+            (-delta_y, from_y + 0.5),
+            (delta_y, max_y - from_y),
+        ] {
+            if p == 0.0 {
+                if q < 0.0 {
+                    return;
+                }
+            } else if p < 0.0 {
+                start = start.max(q / p);
+            } else {
+                end = end.min(q / p);
+            }
+        }
+        if start > end {
+            return;
+        }
+        let (mut x, mut y) = (
+            (from_x + start * delta_x).round() as isize,
+            (from_y + start * delta_y).round() as isize,
+        );
+        let (end_x, end_y) = (
+            (from_x + end * delta_x).round() as isize,
+            (from_y + end * delta_y).round() as isize,
+        );
+        // Bresenham's line algorithm
+        let (step_x, step_y) = ((end_x - x).signum(), (end_y - y).signum());
+        let (distance_x, distance_y) = ((end_x - x).abs(), -(end_y - y).abs());
+        let mut error = distance_x + distance_y;
+        loop {
+            self.set_dot(x, y);
+            if x == end_x && y == end_y {
+                break;
+            }
+            if error * 2 >= distance_y {
+                error += distance_y;
+                x += step_x;
+            }
+            if error * 2 <= distance_x {
+                error += distance_x;
+                y += step_y;
+            }
+        }
+    }
+
+    /// Draw lines connecting a list of positions in the virtual coordinate space, e.g. the values
+    /// of a line chart.
+    pub fn draw_lines(&mut self, points: &[(f64, f64)]) {
+        for pair in points.windows(2) {
+            self.draw_line(pair[0], pair[1]);
+        }
+        if let [point] = points {
+            self.draw_point(point.0, point.1);
+        }
+    }
+
+    /// Fill the rectangle between two opposite corners in the virtual coordinate space, e.g. a bar
+    /// of a bar chart.
+    pub fn fill_rect(&mut self, from: (f64, f64), to: (f64, f64)) {
+        let (from, to) = (self.to_dot(from.0, from.1), self.to_dot(to.0, to.1));
+        let max_x = self.get_dot_width() as isize - 1;
+        let max_y = self.get_dot_height() as isize - 1;
+        for x in from.x.min(to.x).max(0)..=from.x.max(to.x).min(max_x) {
+            for y in from.y.min(to.y).max(0)..=from.y.max(to.y).min(max_y) {
+                self.set_dot(x, y);
+            }
+        }
+    }
+
+    /// Fill the polygon with corners at a list of positions in the virtual coordinate space. The
+    /// polygon is closed automatically. For example, filling the values of a line chart together
+    /// with two points on the x axis gives an area chart. Dots are filled if their center is
+    /// inside of the polygon.
+    pub fn fill_polygon(&mut self, points: &[(f64, f64)]) {
+        let corners: Vec<(f64, f64)> = points
+            .iter()
+            .map(|(x, y)| self.to_dot_f64(*x, *y))
+            .collect();
+        let max_x = self.get_dot_width() as isize - 1;
+        for y in 0..self.get_dot_height() {
+            let scan_y = y as f64;
+            let mut crossings = Vec::new();
+            for (i, (a_x, a_y)) in corners.iter().enumerate() {
+                let (b_x, b_y) = corners[(i + 1) % corners.len()];
+                if (*a_y <= scan_y) != (b_y <= scan_y) {
+                    crossings.push(a_x + (scan_y - a_y) / (b_y - a_y) * (b_x - a_x));
+                }
+            }
+            crossings.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+            for pair in crossings.chunks_exact(2) {
+                let start = (pair[0].ceil() as isize).max(0);
+                let end = (pair[1].floor() as isize).min(max_x);
+                for x in start..=end {
+                    self.set_dot(x, y as isize);
+                }
+            }
+        }
+    }
+
+    /// Get the symbol of a cell. Empty cells are a space rather than an empty braille pattern, so
+    /// they can be drawn over other content.
+    pub fn get_symbol(&self, x: usize, y: usize) -> String {
+        match self.cells[x][y] {
+            0 => " ".to_string(),
+            dots => char::from_u32(0x2800 + dots).unwrap().to_string(),
+        }
+    }
+
+    /// Get the symbols of the grid as one string per row of cells.
+    pub fn get_rows(&self) -> Vec<String> {
+        (0..self.height)
+            .map(|y| (0..self.width).map(|x| self.get_symbol(x, y)).collect())
+            .collect()
+    }
+
+    /// Get the grid as pixels in the given colors, e.g. to set as the contents of a Canvas with
+    /// [CanvasState.set_contents]. Cells that were drawn with a pen color use that color instead
+    /// of the foreground color.
+    pub fn get_pixel_map(&self, fg_color: Color, bg_color: Color) -> PixelMap {
+        (0..self.width)
+            .map(|x| {
+                (0..self.height)
+                    .map(|y| {
+                        Pixel::new(
+                            self.get_symbol(x, y),
+                            self.colors[x][y].unwrap_or(fg_color),
+                            bg_color,
+                        )
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Set the dot at a position in dots that may be outside of the grid.
+    fn set_dot(&mut self, x: isize, y: isize) {
+        if x >= 0 && y >= 0 {
+            self.set(x as usize, y as usize);
+        }
+    }
+
+    /// Get the unrounded position in dots of a position in the virtual coordinate space.
+    fn to_dot_f64(&self, x: f64, y: f64) -> (f64, f64) {
+        match self.bounds {
+            Some((x_min, x_max, y_min, y_max)) => {
+                let last_x = self.get_dot_width().saturating_sub(1) as f64;
+                let last_y = self.get_dot_height().saturating_sub(1) as f64;
+                (
+                    (x - x_min) / (x_max - x_min) * last_x,
+                    (y_max - y) / (y_max - y_min) * last_y,
+                )
+            }
+            None => (x, y),
+        }
+    }
+}
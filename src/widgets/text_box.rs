@@ -0,0 +1,677 @@
+//! # Text box Widget
+//! A widget implementing a multi-line field in which the user can edit text. Supports word wrap,
+//! vertical scrolling, selecting text and on_value_change callbacks. Enter inserts a line break;
+//! Up and Down move the cursor between lines and only select the previous or next widget when the
+//! cursor is on the first or last line.
+use std::io::{Error, ErrorKind};
+use std::time::Duration;
+
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+
+use crate::parser::ez_definition::ConfigLine;
+use crate::parser::load_base_properties;
+use crate::parser::load_base_properties::resolve_property;
+use crate::parser::load_common_properties::load_common_property;
+use crate::parser::parse_lang::ParseError;
+use crate::parser::parse_properties::parse_cursor_style_property;
+use crate::run::definitions::{CallbackTree, Coordinates, Pixel, PixelMap, StateTree};
+use crate::scheduler::definitions::CustomDataMap;
+use crate::scheduler::scheduler::SchedulerFrontend;
+use crate::states::definitions::CursorStyle;
+use crate::states::ez_state::{EzState, GenericState};
+use crate::states::text_box_state::TextBoxState;
+use crate::widgets::ez_object::{EzObject, EzObjects};
+use crate::widgets::helper_functions::{add_border, add_padding};
+use crate::Context;
+
+/// Milliseconds between checks of the blinking cursor.
+const BLINK_TICK: usize = 50;
+
+#[derive(Clone, Debug)]
+pub struct TextBox {
+    /// ID of the widget, used to construct [path]
+    pub id: String,
+
+    /// Full path to this widget, e.g. "/root_layout/layout_2/THIS_ID"
+    pub path: String,
+
+    /// Runtime state of this widget, see [TextBoxState] and [State]
+    pub state: TextBoxState,
+}
+
+impl TextBox {
+    pub fn new(id: String, path: String, scheduler: &mut SchedulerFrontend) -> Self {
+        TextBox {
+            id,
+            path: path.clone(),
+            state: TextBoxState::new(path, scheduler),
+        }
+    }
+
+    pub fn from_state(
+        id: String,
+        path: String,
+        _scheduler: &mut SchedulerFrontend,
+        state: EzState,
+    ) -> Self {
+        TextBox {
+            id,
+            path: path.clone(),
+            state: state.as_text_box().to_owned(),
+        }
+    }
+}
+
+impl EzObject for TextBox {
+    fn load_ez_parameter(
+        &mut self,
+        parameter_name: String,
+        parameter_value: String,
+        scheduler: &mut SchedulerFrontend,
+    ) -> Result<(), Error> {
+        let consumed =
+            load_common_property(&parameter_name, parameter_value.clone(), self, scheduler)?;
+        if consumed {
+            return Ok(());
+        }
+        match parameter_name.as_str() {
+            "max_length" | "cursor_blink_interval" => load_base_properties::load_usize_property(
+                parameter_value.trim(),
+                scheduler,
+                self.path.clone(),
+                &parameter_name,
+                self.get_state_mut(),
+            )?,
+            // Line breaks can be written as "\n" in .ez files
+            "text" => load_base_properties::load_string_property(
+                &parameter_value.trim().replace("\\n", "\n"),
+                scheduler,
+                self.path.clone(),
+                &parameter_name,
+                self.get_state_mut(),
+            )?,
+            "word_wrap" => load_base_properties::load_bool_property(
+                parameter_value.trim(),
+                scheduler,
+                self.path.clone(),
+                &parameter_name,
+                self.get_state_mut(),
+            )?,
+            "cursor_style" => {
+                if resolve_property(parameter_value.trim(), self.path.clone()).is_none() {
+                    parse_cursor_style_property(parameter_value.trim())?;
+                }
+                load_base_properties::load_string_property(
+                    parameter_value.trim(),
+                    scheduler,
+                    self.path.clone(),
+                    &parameter_name,
+                    self.get_state_mut(),
+                )?
+            }
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("Invalid parameter name for text box: {}", parameter_name),
+                ))
+            }
+        }
+        Ok(())
+    }
+    fn set_id(&mut self, id: &str) {
+        self.id = id.to_string()
+    }
+
+    fn get_id(&self) -> String {
+        self.id.clone()
+    }
+
+    fn set_path(&mut self, id: &str) {
+        self.id = id.to_string()
+    }
+
+    fn get_path(&self) -> String {
+        self.path.clone()
+    }
+
+    fn get_state(&self) -> EzState {
+        EzState::TextBox(self.state.clone())
+    }
+
+    fn get_state_mut(&mut self) -> &mut dyn GenericState {
+        &mut self.state
+    }
+
+    fn get_contents(&self, state_tree: &mut StateTree) -> PixelMap {
+        let state = state_tree.get_mut(&self.get_path()).as_text_box_mut();
+        let (fg_color, bg_color) = state.get_context_colors();
+        let chars: Vec<char> = state.get_text().chars().collect();
+        let lines = get_visual_lines(&chars, get_wrap_width(state), state.get_word_wrap());
+        if state.get_auto_scale().get_auto_scale_width() {
+            let longest = lines.iter().map(|(start, end)| end - start).max().unwrap_or(0);
+            state.set_effective_width(longest + 1);
+        }
+        if state.get_auto_scale().get_auto_scale_height() {
+            state.set_effective_height(lines.len());
+        }
+        let cursor = state.get_cursor().min(chars.len());
+        let (cursor_row, cursor_column) = get_cursor_line(&lines, cursor);
+        let selection = state.get_selection();
+
+        let mut contents = Vec::new();
+        for x in 0..state.get_effective_size().width {
+            let mut new_y = Vec::new();
+            for y in 0..state.get_effective_size().height {
+                let row = state.get_scroll_y() + y;
+                let column = state.get_scroll_x() + x;
+                let index = lines
+                    .get(row)
+                    .map(|(start, end)| (start + column, start + column < *end));
+                let symbol = match index {
+                    Some((i, true)) => chars[i].to_string(),
+                    _ => " ".to_string(),
+                };
+                let mut pixel = Pixel::new(symbol, fg_color, bg_color);
+                if let (Some((i, true)), Some((start, end))) = (index, selection) {
+                    if i >= start && i < end {
+                        pixel.foreground_color = bg_color;
+                        pixel.background_color = fg_color;
+                    }
+                }
+                if state.get_blink_switch() && row == cursor_row && column == cursor_column {
+                    draw_cursor(&mut pixel, state);
+                }
+                new_y.push(pixel);
+            }
+            contents.push(new_y);
+        }
+        if state.get_border_config().get_border() {
+            contents = add_border(
+                contents,
+                state.get_border_config(),
+                state.get_color_config(),
+            );
+        }
+        let state = state_tree.get(&self.get_path()).as_text_box();
+        let parent_colors = state_tree
+            .get(self.get_path().rsplit_once('/').unwrap().0)
+            .as_generic()
+            .get_color_config();
+        contents = add_padding(
+            contents,
+            state.get_padding(),
+            parent_colors.get_bg_color(),
+            parent_colors.get_fg_color(),
+        );
+        contents
+    }
+
+    fn handle_event(
+        &self,
+        event: Event,
+        state_tree: &mut StateTree,
+        callback_tree: &mut CallbackTree,
+        scheduler: &mut SchedulerFrontend,
+        custom_data: &mut CustomDataMap,
+    ) -> bool {
+        if let Event::Paste(text) = event {
+            return self.on_paste(state_tree, callback_tree, scheduler, text, custom_data);
+        }
+        let key = match event {
+            Event::Key(key) => key,
+            _ => return false,
+        };
+        let state = state_tree.get_mut(&self.get_path()).as_text_box_mut();
+        let current_text = state.get_text();
+        state.reset_cursor_blink();
+        let extend = key.modifiers.contains(KeyModifiers::SHIFT);
+        let control = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Backspace => handle_backspace(state),
+            KeyCode::Delete => handle_delete(state),
+            KeyCode::Enter => insert_text(state, "\n"),
+            KeyCode::Left => handle_left(state, extend),
+            KeyCode::Right => handle_right(state, extend),
+            KeyCode::Up => move_rows(state, -1, extend),
+            KeyCode::Down => move_rows(state, 1, extend),
+            KeyCode::PageUp => {
+                move_rows(state, -(state.get_effective_size().height as isize), extend)
+            }
+            KeyCode::PageDown => {
+                move_rows(state, state.get_effective_size().height as isize, extend)
+            }
+            KeyCode::Home if control => move_cursor(state, 0, extend),
+            KeyCode::End if control => move_cursor(state, usize::MAX, extend),
+            KeyCode::Home => handle_home(state, extend),
+            KeyCode::End => handle_end(state, extend),
+            KeyCode::Char('a') if control => state.select_all(),
+            KeyCode::Char(c) if !control => insert_text(state, &c.to_string()),
+            _ => return false,
+        }
+        scroll_to_cursor(state);
+        state.update(scheduler);
+        self.check_changed_text(state_tree, callback_tree, scheduler, custom_data, current_text);
+        true
+    }
+
+    fn on_paste(
+        &self,
+        state_tree: &mut StateTree,
+        callback_tree: &mut CallbackTree,
+        scheduler: &mut SchedulerFrontend,
+        text: String,
+        custom_data: &mut CustomDataMap,
+    ) -> bool {
+        if self.on_paste_callback(state_tree, callback_tree, scheduler, text.clone(), custom_data) {
+            return true;
+        }
+        let state = state_tree.get_mut(&self.get_path()).as_text_box_mut();
+        let current_text = state.get_text();
+        insert_text(state, &text.replace('\r', ""));
+        scroll_to_cursor(state);
+        state.update(scheduler);
+        self.check_changed_text(state_tree, callback_tree, scheduler, custom_data, current_text);
+        true
+    }
+
+    fn on_left_mouse_click(
+        &self,
+        state_tree: &mut StateTree,
+        callback_tree: &mut CallbackTree,
+        scheduler: &mut SchedulerFrontend,
+        mouse_pos: Coordinates,
+        custom_data: &mut CustomDataMap,
+    ) -> bool {
+        let consumed =
+            self.on_left_mouse_click_callback(state_tree, callback_tree, scheduler, mouse_pos, custom_data);
+        if consumed {
+            return consumed;
+        }
+        scheduler.deselect_widget(); // We deselect first to allow re-selecting in a different pos
+        scheduler.set_selected_widget(&self.path, Some(mouse_pos));
+        true
+    }
+
+    fn on_hover(
+        &self,
+        state_tree: &mut StateTree,
+        callback_tree: &mut CallbackTree,
+        scheduler: &mut SchedulerFrontend,
+        mouse_pos: Coordinates,
+        custom_data: &mut CustomDataMap,
+    ) -> bool {
+        let consumed = self.on_hover_callback(state_tree, callback_tree, scheduler, mouse_pos, custom_data);
+        if consumed {
+            return consumed;
+        }
+        true
+    }
+
+    fn on_scroll_up(
+        &self,
+        state_tree: &mut StateTree,
+        callback_tree: &mut CallbackTree,
+        scheduler: &mut SchedulerFrontend,
+        custom_data: &mut CustomDataMap,
+    ) -> bool {
+        if self.on_scroll_up_callback(state_tree, callback_tree, scheduler, custom_data) {
+            return true;
+        }
+        let state = state_tree.get_mut(&self.get_path()).as_text_box_mut();
+        state.set_scroll_y(state.get_scroll_y().saturating_sub(1));
+        state.update(scheduler);
+        true
+    }
+
+    fn on_scroll_down(
+        &self,
+        state_tree: &mut StateTree,
+        callback_tree: &mut CallbackTree,
+        scheduler: &mut SchedulerFrontend,
+        custom_data: &mut CustomDataMap,
+    ) -> bool {
+        if self.on_scroll_down_callback(state_tree, callback_tree, scheduler, custom_data) {
+            return true;
+        }
+        let state = state_tree.get_mut(&self.get_path()).as_text_box_mut();
+        let chars: Vec<char> = state.get_text().chars().collect();
+        let lines = get_visual_lines(&chars, get_wrap_width(state), state.get_word_wrap());
+        let max_scroll = lines
+            .len()
+            .saturating_sub(state.get_effective_size().height);
+        state.set_scroll_y((state.get_scroll_y() + 1).min(max_scroll));
+        state.update(scheduler);
+        true
+    }
+
+    fn on_select(
+        &self,
+        state_tree: &mut StateTree,
+        callback_tree: &mut CallbackTree,
+        scheduler: &mut SchedulerFrontend,
+        mouse_pos: Option<Coordinates>,
+        custom_data: &mut CustomDataMap,
+    ) -> bool {
+        let consumed = self.on_select_callback(state_tree, callback_tree, scheduler, mouse_pos, custom_data);
+        if consumed {
+            return consumed;
+        }
+
+        let state = state_tree.get_mut(&self.get_path()).as_text_box_mut();
+        state.set_selected(true);
+        // Handle this widget being selected from mouse, follow user click position. When selected
+        // from keyboard the cursor stays where it was.
+        if let Some(pos) = mouse_pos {
+            let offset = if state.get_border_config().get_border() { 1 } else { 0 };
+            let x = pos.x.saturating_sub(state.get_padding().get_padding_left() + offset);
+            let y = pos.y.saturating_sub(state.get_padding().get_padding_top() + offset);
+            let chars: Vec<char> = state.get_text().chars().collect();
+            let lines = get_visual_lines(&chars, get_wrap_width(state), state.get_word_wrap());
+            let row = (state.get_scroll_y() + y).min(lines.len() - 1);
+            let (start, end) = lines[row];
+            state.set_cursor((start + state.get_scroll_x() + x).min(end));
+        } else {
+            state.set_cursor(state.get_cursor());
+        }
+        if !state.get_active_blink_task() {
+            start_cursor_blink(state, scheduler, self.get_path());
+        } else {
+            state.reset_cursor_blink();
+        }
+        state.update(scheduler);
+        true
+    }
+
+    fn get_clone(&self, scheduler: &mut SchedulerFrontend) -> EzObjects {
+        let mut clone = self.clone();
+        let mut new_state = TextBoxState::new(self.path.clone(), scheduler);
+        new_state.copy_state_values(self.get_state());
+        clone.state = new_state;
+        EzObjects::TextBox(clone)
+    }
+}
+
+impl TextBox {
+    /// Initialize an instance of this object using the passed config coming from [ez_parser]
+    pub fn from_config(
+        config: Vec<ConfigLine>,
+        id: String,
+        path: String,
+        scheduler: &mut SchedulerFrontend,
+    ) -> Result<Self, ParseError> {
+        let mut obj = TextBox::new(id, path, scheduler);
+        obj.load_ez_config(config, scheduler)?;
+        Ok(obj)
+    }
+
+    /// Check if text has changed to call on_value_change.
+    fn check_changed_text(
+        &self,
+        state_tree: &mut StateTree,
+        callback_tree: &mut CallbackTree,
+        scheduler: &mut SchedulerFrontend,
+        custom_data: &mut CustomDataMap,
+        old_text: String,
+    ) {
+        let state = state_tree.get(&self.path).as_text_box();
+        if state.get_text() != old_text {
+            self.on_value_change_callback(state_tree, callback_tree, scheduler, custom_data);
+        }
+    }
+}
+
+/// Returns a bool representing whether a text box wants to handle a key itself rather than have it
+/// handled by the global key handler. Enter inserts a line break, and Up and Down move the cursor
+/// unless it is on the first or last line, so the user can still leave the text box with them.
+pub fn captures_key(state: &TextBoxState, key: KeyEvent) -> bool {
+    if key.modifiers.contains(KeyModifiers::SHIFT) {
+        return matches!(key.code, KeyCode::Up | KeyCode::Down);
+    }
+    let chars: Vec<char> = state.get_text().chars().collect();
+    let lines = get_visual_lines(&chars, get_wrap_width(state), state.get_word_wrap());
+    let (row, _) = get_cursor_line(&lines, state.get_cursor().min(chars.len()));
+    match key.code {
+        KeyCode::Enter => true,
+        KeyCode::Up => row > 0,
+        KeyCode::Down => row + 1 < lines.len(),
+        _ => false,
+    }
+}
+
+/// Get the width lines are wrapped at. Lines are not wrapped if word wrap is off.
+fn get_wrap_width(state: &TextBoxState) -> usize {
+    if state.get_word_wrap() {
+        state.get_effective_size().width.max(1)
+    } else {
+        usize::MAX
+    }
+}
+
+/// Split text into the lines it is displayed as, as (start, end) char indices of each line. Line
+/// breaks are not part of a line. Lines longer than 'width' are wrapped after the last space that
+/// fits, or at 'width' if a word is longer than the line.
+pub fn get_visual_lines(chars: &[char], width: usize, word_wrap: bool) -> Vec<(usize, usize)> {
+    let mut lines = Vec::new();
+    let mut line_start = 0;
+    for line_end in chars
+        .iter()
+        .enumerate()
+        .filter(|(_, x)| **x == '\n')
+        .map(|(i, _)| i)
+        .chain(std::iter::once(chars.len()))
+    {
+        let mut start = line_start;
+        while word_wrap && line_end - start > width {
+            let end = match chars[start..start + width].iter().rposition(|x| *x == ' ') {
+                Some(i) if i > 0 => start + i + 1,
+                _ => start + width,
+            };
+            lines.push((start, end));
+            start = end;
+        }
+        lines.push((start, line_end));
+        line_start = line_end + 1;
+    }
+    lines
+}
+
+/// Get the line and column of a char index. An index at the end of a wrapped line is at the start
+/// of the next line.
+fn get_cursor_line(lines: &[(usize, usize)], cursor: usize) -> (usize, usize) {
+    let row = lines
+        .iter()
+        .rposition(|(start, _)| *start <= cursor)
+        .unwrap_or(0);
+    (row, cursor - lines[row].0)
+}
+
+/// Get the lines and cursor position of the text of a text box.
+fn get_lines_and_cursor(state: &TextBoxState) -> (Vec<(usize, usize)>, usize, usize) {
+    let chars: Vec<char> = state.get_text().chars().collect();
+    let lines = get_visual_lines(&chars, get_wrap_width(state), state.get_word_wrap());
+    let (row, column) = get_cursor_line(&lines, state.get_cursor().min(chars.len()));
+    (lines, row, column)
+}
+
+/// Move the cursor to a char index. If 'extend' is true the selection is extended to the new
+/// position, otherwise the selection is cleared.
+fn move_cursor(state: &mut TextBoxState, cursor: usize, extend: bool) {
+    if extend {
+        if state.get_selection_anchor().is_none() {
+            state.set_selection_anchor(Some(state.get_cursor()));
+        }
+        state._set_cursor_keep_selection(cursor);
+    } else {
+        state.set_cursor(cursor);
+    }
+}
+
+fn handle_left(state: &mut TextBoxState, extend: bool) {
+    match state.get_selection() {
+        Some((start, _)) if !extend => state.set_cursor(start),
+        _ => move_cursor(state, state.get_cursor().saturating_sub(1), extend),
+    }
+}
+
+fn handle_right(state: &mut TextBoxState, extend: bool) {
+    match state.get_selection() {
+        Some((_, end)) if !extend => state.set_cursor(end),
+        _ => move_cursor(state, state.get_cursor() + 1, extend),
+    }
+}
+
+fn handle_home(state: &mut TextBoxState, extend: bool) {
+    let (lines, row, _) = get_lines_and_cursor(state);
+    move_cursor(state, lines[row].0, extend);
+}
+
+fn handle_end(state: &mut TextBoxState, extend: bool) {
+    let (lines, row, _) = get_lines_and_cursor(state);
+    let (start, end) = lines[row];
+    // The end of a wrapped line is the start of the next line, so stop before its' last char
+    let end = if row + 1 < lines.len() && lines[row + 1].0 == end && end > start {
+        end - 1
+    } else {
+        end
+    };
+    move_cursor(state, end, extend);
+}
+
+/// Move the cursor up (negative) or down (positive) a number of lines, keeping its' column where
+/// possible.
+fn move_rows(state: &mut TextBoxState, rows: isize, extend: bool) {
+    let (lines, row, column) = get_lines_and_cursor(state);
+    let target = (row as isize + rows).clamp(0, lines.len() as isize - 1) as usize;
+    let (start, end) = lines[target];
+    let max_column = if target + 1 < lines.len() && lines[target + 1].0 == end && end > start {
+        end - start - 1
+    } else {
+        end - start
+    };
+    move_cursor(state, start + column.min(max_column), extend);
+}
+
+/// Remove the selected text, if any, and place the cursor where it was. Returns whether text was
+/// removed.
+fn delete_selection(state: &mut TextBoxState) -> bool {
+    let (start, end) = match state.get_selection() {
+        Some(i) => i,
+        None => return false,
+    };
+    let mut chars: Vec<char> = state.get_text().chars().collect();
+    let (start, end) = (start.min(chars.len()), end.min(chars.len()));
+    chars.drain(start..end);
+    state.set_text(chars.into_iter().collect());
+    state.set_cursor(start);
+    true
+}
+
+/// Insert text at the cursor, replacing the selected text if any. Text that does not fit within
+/// the max length is dropped.
+fn insert_text(state: &mut TextBoxState, text: &str) {
+    delete_selection(state);
+    let mut chars: Vec<char> = state.get_text().chars().collect();
+    let mut insert: Vec<char> = text.chars().collect();
+    if state.get_max_length() > 0 {
+        insert.truncate(state.get_max_length().saturating_sub(chars.len()));
+    }
+    let cursor = state.get_cursor().min(chars.len());
+    let inserted = insert.len();
+    chars.splice(cursor..cursor, insert);
+    state.set_text(chars.into_iter().collect());
+    state.set_cursor(cursor + inserted);
+}
+
+fn handle_backspace(state: &mut TextBoxState) {
+    if delete_selection(state) || state.get_cursor() == 0 {
+        return;
+    }
+    let cursor = state.get_cursor();
+    state.select(cursor - 1, cursor);
+    delete_selection(state);
+}
+
+fn handle_delete(state: &mut TextBoxState) {
+    if delete_selection(state) {
+        return;
+    }
+    let cursor = state.get_cursor();
+    state.select(cursor, cursor + 1);
+    if !delete_selection(state) {
+        state.set_cursor(cursor);
+    }
+}
+
+/// Scroll the view so the cursor is visible.
+fn scroll_to_cursor(state: &mut TextBoxState) {
+    let (_, row, column) = get_lines_and_cursor(state);
+    let size = state.get_effective_size();
+    if row < state.get_scroll_y() {
+        state.set_scroll_y(row);
+    } else if size.height > 0 && row >= state.get_scroll_y() + size.height {
+        state.set_scroll_y(row + 1 - size.height);
+    }
+    if state.get_word_wrap() {
+        state.set_scroll_x(0);
+    } else if column < state.get_scroll_x() {
+        state.set_scroll_x(column);
+    } else if size.width > 0 && column >= state.get_scroll_x() + size.width {
+        state.set_scroll_x(column + 1 - size.width);
+    }
+}
+
+/// Draw the cursor on the pixel under it, in the [CursorStyle] of the text box.
+fn draw_cursor(pixel: &mut Pixel, state: &TextBoxState) {
+    let cursor_color = state.get_color_config().get_cursor_color();
+    match state.get_cursor_style() {
+        CursorStyle::Block => pixel.background_color = cursor_color,
+        CursorStyle::Bar if pixel.symbol == " " => {
+            pixel.symbol = "▏".to_string();
+            pixel.foreground_color = cursor_color;
+        }
+        CursorStyle::Underline | CursorStyle::Bar => {
+            pixel.underline = true;
+            pixel.foreground_color = cursor_color;
+        }
+    }
+}
+
+/// Start blinking the cursor while the text box is selected, see the text input widget.
+fn start_cursor_blink(state: &mut TextBoxState, scheduler: &mut SchedulerFrontend, name: String) {
+    state.set_active_blink_task(true);
+    state.reset_cursor_blink();
+    let path = state.path.clone();
+    let blink_func = move |context: Context| {
+        let state = match context.state_tree.try_get_mut(&path) {
+            Some(i) => i.obj.as_text_box_mut(),
+            None => return false,
+        };
+        if !state.get_selected() {
+            state.set_blink_switch(false);
+            state.set_active_blink_task(false);
+            state.update(context.scheduler);
+            return false;
+        };
+        // The interval is read every tick so it can be changed while the cursor is blinking
+        let interval = state.get_cursor_blink_interval();
+        let timer = state.get_blink_timer() + BLINK_TICK;
+        if interval == 0 {
+            if !state.get_blink_switch() {
+                state.set_blink_switch(true);
+                state.update(context.scheduler);
+            }
+        } else if timer >= interval {
+            state.set_blink_timer(0);
+            state.set_blink_switch(!state.get_blink_switch());
+            state.update(context.scheduler);
+        } else {
+            state.set_blink_timer(timer);
+        }
+        true
+    };
+    scheduler.schedule_recurring(
+        name.as_str(),
+        Box::new(blink_func),
+        Duration::from_millis(BLINK_TICK as u64),
+    );
+}
@@ -7,10 +7,15 @@ use std::time::Duration;
 
 use crossterm::event::{Event, KeyCode};
 
+use crate::parser::ez_definition::ConfigLine;
 use crate::parser::load_base_properties;
+use crate::parser::load_base_properties::resolve_property;
 use crate::parser::load_common_properties::load_common_property;
+use crate::parser::parse_lang::ParseError;
+use crate::parser::parse_properties::parse_cursor_style_property;
 use crate::run::definitions::{CallbackTree, Coordinates, Pixel, PixelMap, StateTree};
 use crate::scheduler::scheduler::SchedulerFrontend;
+use crate::states::definitions::CursorStyle;
 use crate::states::ez_state::{EzState, GenericState};
 use crate::states::text_input_state::TextInputState;
 use crate::widgets::ez_object::{EzObject, EzObjects};
@@ -18,6 +23,9 @@ use crate::widgets::helper_functions::{add_border, add_padding};
 use crate::Context;
 use crate::scheduler::definitions::CustomDataMap;
 
+/// Milliseconds between checks of the blinking cursor.
+const BLINK_TICK: usize = 50;
+
 #[derive(Clone, Debug)]
 pub struct TextInput {
     /// ID of the widget, used to construct [path]
@@ -80,6 +88,32 @@ impl EzObject for TextInput {
                 &parameter_name,
                 self.get_state_mut(),
             )?,
+            "cursor_style" => {
+                if resolve_property(parameter_value.trim(), self.path.clone()).is_none() {
+                    parse_cursor_style_property(parameter_value.trim())?;
+                }
+                load_base_properties::load_string_property(
+                    parameter_value.trim(),
+                    scheduler,
+                    self.path.clone(),
+                    &parameter_name,
+                    self.get_state_mut(),
+                )?
+            }
+            "cursor_blink_interval" => load_base_properties::load_usize_property(
+                parameter_value.trim(),
+                scheduler,
+                self.path.clone(),
+                &parameter_name,
+                self.get_state_mut(),
+            )?,
+            "terminal_cursor" => load_base_properties::load_bool_property(
+                parameter_value.trim(),
+                scheduler,
+                self.path.clone(),
+                &parameter_name,
+                self.get_state_mut(),
+            )?,
             _ => {
                 return Err(Error::new(
                     ErrorKind::InvalidData,
@@ -142,29 +176,18 @@ impl EzObject for TextInput {
         for x in 0..state.get_effective_size().width {
             let mut new_y = Vec::new();
             for _ in 0..write_height {
-                if !text.is_empty() {
-                    new_y.push(Pixel::new(
-                        text.pop().unwrap().to_string(),
-                        fg_color,
-                        if state.get_blink_switch()
-                            && x == state.get_cursor_pos().x
-                        {
-                            state.get_color_config().get_cursor_color()
-                        } else {
-                            bg_color
-                        }))
-                } else {
-                    new_y.push(Pixel::new(
-                        " ".to_string(),
-                        fg_color,
-                        if state.get_blink_switch()
-                            && x == state.get_cursor_pos().x
-                        {
-                            state.get_color_config().get_cursor_color()
-                        } else {
-                            bg_color
-                        }))
+                let symbol = match text.pop() {
+                    Some(i) => i.to_string(),
+                    None => " ".to_string(),
+                };
+                let mut pixel = Pixel::new(symbol, fg_color, bg_color);
+                if state.get_blink_switch()
+                    && !state.get_terminal_cursor()
+                    && x == state.get_cursor_pos().x
+                {
+                    draw_cursor(&mut pixel, state);
                 }
+                new_y.push(pixel);
             }
             contents.push(new_y);
         }
@@ -206,6 +229,9 @@ impl EzObject for TextInput {
     ) -> bool {
         let state = state_tree.get_mut(&self.get_path()).as_text_input_mut();
         let current_text = state.get_text().clone();
+        if let Event::Key(_) | Event::Paste(_) = event {
+            state.reset_cursor_blink();
+        }
         if let Event::Key(key) = event {
             if key.code == KeyCode::Backspace {
                 handle_backspace(state, scheduler);
@@ -226,10 +252,34 @@ impl EzObject for TextInput {
                 self.check_changed_text(state_tree, callback_tree, scheduler, custom_data, current_text);
                 return true;
             }
+        } else if let Event::Paste(text) = event {
+            return self.on_paste(state_tree, callback_tree, scheduler, text, custom_data);
         }
         false
     }
 
+    fn on_paste(
+        &self,
+        state_tree: &mut StateTree,
+        callback_tree: &mut CallbackTree,
+        scheduler: &mut SchedulerFrontend,
+        text: String,
+        custom_data: &mut CustomDataMap,
+    ) -> bool {
+        if self.on_paste_callback(state_tree, callback_tree, scheduler, text.clone(), custom_data) {
+            return true;
+        }
+        let state = state_tree.get_mut(&self.get_path()).as_text_input_mut();
+        let current_text = state.get_text().clone();
+        // Text inputs are single line; pasted line breaks become spaces
+        for char in text.chars().filter(|x| *x != '\r') {
+            let char = if char == '\n' { ' ' } else { char };
+            handle_char(state, char, scheduler);
+        }
+        self.check_changed_text(state_tree, callback_tree, scheduler, custom_data, current_text);
+        true
+    }
+
     fn on_left_mouse_click(
         &self,
         state_tree: &mut StateTree,
@@ -373,16 +423,14 @@ pub fn handle_char(state: &mut TextInputState, char: char, scheduler: &mut Sched
 impl TextInput {
     /// Initialize an instance of this object using the passed config coming from [ez_parser]
     pub fn from_config(
-        config: Vec<String>,
+        config: Vec<ConfigLine>,
         id: String,
         path: String,
         scheduler: &mut SchedulerFrontend,
-        file: String,
-        line: usize,
-    ) -> Self {
+    ) -> Result<Self, ParseError> {
         let mut obj = TextInput::new(id, path, scheduler);
-        obj.load_ez_config(config, scheduler, file, line).unwrap();
-        obj
+        obj.load_ez_config(config, scheduler)?;
+        Ok(obj)
     }
 
     /// Check if text has changed to call on_value_change.
@@ -401,6 +449,22 @@ impl TextInput {
     }
 }
 
+/// Draw the cursor on the pixel under it, in the [CursorStyle] of the text input.
+fn draw_cursor(pixel: &mut Pixel, state: &TextInputState) {
+    let cursor_color = state.get_color_config().get_cursor_color();
+    match state.get_cursor_style() {
+        CursorStyle::Block => pixel.background_color = cursor_color,
+        CursorStyle::Bar if pixel.symbol == " " => {
+            pixel.symbol = "▏".to_string();
+            pixel.foreground_color = cursor_color;
+        }
+        CursorStyle::Underline | CursorStyle::Bar => {
+            pixel.underline = true;
+            pixel.foreground_color = cursor_color;
+        }
+    }
+}
+
 /// Start blink the position on which the cursor is currently located. This is a custom cursor not
 /// the actual terminal cursor. This is because in dynamic interfaces with scheduled tasks changing
 /// visual content, the crossterm cursor is constantly jumping around, which cannot seem to be
@@ -413,9 +477,9 @@ fn start_cursor_blink(
 ) {
     state.set_cursor_pos(target_pos);
     state.set_active_blink_task(true);
+    state.reset_cursor_blink();
     state.update(scheduler);
     let path = state.path.clone();
-    let mut counter = 3;
     let blink_func = move |context: Context| {
         let state = context
             .state_tree
@@ -427,19 +491,28 @@ fn start_cursor_blink(
             state.update(context.scheduler);
             return false;
         };
-        if counter >= 3 {
-            counter = 0;
+        // The interval is read every tick so it can be changed while the cursor is blinking
+        let interval = state.get_cursor_blink_interval();
+        let timer = state.get_blink_timer() + BLINK_TICK;
+        // The terminal cursor blinks by itself
+        if interval == 0 || state.get_terminal_cursor() {
+            if !state.get_blink_switch() {
+                state.set_blink_switch(true);
+                state.update(context.scheduler);
+            }
+        } else if timer >= interval {
+            state.set_blink_timer(0);
             state.set_blink_switch(!state.get_blink_switch());
             state.update(context.scheduler);
         } else {
-            counter += 1;
+            state.set_blink_timer(timer);
         }
         true
     };
     scheduler.schedule_recurring(
         name.as_str(),
         Box::new(blink_func),
-        Duration::from_millis(100),
+        Duration::from_millis(BLINK_TICK as u64),
     );
 }
 
@@ -0,0 +1,295 @@
+//! A widget that scrolls a text horizontally when it does not fit, e.g. for status bars and
+//! headlines. The text moves on a task of the scheduler and pauses while the mouse is on it.
+use std::io::{Error, ErrorKind};
+use std::time::Duration;
+
+use crate::parser::ez_definition::ConfigLine;
+use crate::parser::load_base_properties;
+use crate::parser::load_common_properties::load_common_property;
+use crate::parser::parse_lang::ParseError;
+use crate::run::definitions::{CallbackTree, Coordinates, Pixel, PixelMap, StateTree};
+use crate::scheduler::definitions::CustomDataMap;
+use crate::scheduler::scheduler::SchedulerFrontend;
+use crate::states::definitions::{HorizontalAlignment, VerticalAlignment};
+use crate::states::ez_state::{EzState, GenericState};
+use crate::states::ticker_state::TickerState;
+use crate::widgets::ez_object::{EzObject, EzObjects};
+use crate::widgets::helper_functions::{add_border, add_padding};
+use crate::Context;
+
+/// Interval in milliseconds on which tickers check whether their text has to move.
+const TICK: u64 = 50;
+
+#[derive(Clone, Debug)]
+pub struct Ticker {
+    /// ID of the widget, used to construct [path]
+    pub id: String,
+
+    /// Full path to this widget, e.g. "/root_layout/layout_2/THIS_ID"
+    pub path: String,
+
+    /// Runtime state of this widget, see [TickerState] and [State]
+    pub state: TickerState,
+}
+
+impl Ticker {
+    pub fn new(id: String, path: String, scheduler: &mut SchedulerFrontend) -> Self {
+        start_ticking(path.clone(), scheduler);
+        Ticker {
+            id,
+            path: path.clone(),
+            state: TickerState::new(path, scheduler),
+        }
+    }
+
+    pub fn from_state(
+        id: String,
+        path: String,
+        scheduler: &mut SchedulerFrontend,
+        state: EzState,
+    ) -> Self {
+        start_ticking(path.clone(), scheduler);
+        Ticker {
+            id,
+            path: path.clone(),
+            state: state.as_ticker().to_owned(),
+        }
+    }
+}
+
+impl EzObject for Ticker {
+    fn load_ez_parameter(
+        &mut self,
+        parameter_name: String,
+        parameter_value: String,
+        scheduler: &mut SchedulerFrontend,
+    ) -> Result<(), Error> {
+        let consumed =
+            load_common_property(&parameter_name, parameter_value.clone(), self, scheduler)?;
+        if consumed {
+            return Ok(());
+        }
+        match parameter_name.as_str() {
+            "text" => load_base_properties::load_string_property(
+                parameter_value
+                    .strip_prefix(' ')
+                    .unwrap_or(parameter_value.as_str()),
+                scheduler,
+                self.path.clone(),
+                &parameter_name,
+                self.get_state_mut(),
+            )?,
+            "speed" => load_base_properties::load_f64_property(
+                parameter_value.trim(),
+                scheduler,
+                self.path.clone(),
+                &parameter_name,
+                self.get_state_mut(),
+            )?,
+            // Spaces are significant here, only strip the one after the colon
+            "separator" => load_base_properties::load_string_property(
+                parameter_value
+                    .strip_prefix(' ')
+                    .unwrap_or(parameter_value.as_str()),
+                scheduler,
+                self.path.clone(),
+                &parameter_name,
+                self.get_state_mut(),
+            )?,
+            "pause_on_hover" => load_base_properties::load_bool_property(
+                parameter_value.trim(),
+                scheduler,
+                self.path.clone(),
+                &parameter_name,
+                self.get_state_mut(),
+            )?,
+            "paused" => load_base_properties::load_bool_property(
+                parameter_value.trim(),
+                scheduler,
+                self.path.clone(),
+                &parameter_name,
+                self.get_state_mut(),
+            )?,
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("Invalid parameter name for ticker: {}", parameter_name),
+                ))
+            }
+        }
+        Ok(())
+    }
+
+    fn set_id(&mut self, id: &str) {
+        self.id = id.to_string()
+    }
+
+    fn get_id(&self) -> String {
+        self.id.clone()
+    }
+
+    fn set_path(&mut self, id: &str) {
+        self.id = id.to_string()
+    }
+
+    fn get_path(&self) -> String {
+        self.path.clone()
+    }
+
+    fn get_state(&self) -> EzState {
+        EzState::Ticker(self.state.clone())
+    }
+
+    fn get_state_mut(&mut self) -> &mut dyn GenericState {
+        &mut self.state
+    }
+
+    fn get_contents(&self, state_tree: &mut StateTree) -> PixelMap {
+        let state = state_tree.get_mut(&self.get_path()).as_ticker_mut();
+        if state.get_auto_scale().get_auto_scale_width() {
+            state.set_effective_width(state.get_text().chars().count());
+        }
+        if state.get_auto_scale().get_auto_scale_height() {
+            state.set_effective_height(1);
+        }
+        let width = state.get_effective_size().width;
+        let height = state.get_effective_size().height;
+        let (fg_color, bg_color) = if state.get_disabled() {
+            (
+                state.get_color_config().get_disabled_fg_color(),
+                state.get_color_config().get_disabled_bg_color(),
+            )
+        } else {
+            (
+                state.get_color_config().get_fg_color(),
+                state.get_color_config().get_bg_color(),
+            )
+        };
+
+        let text: Vec<char> = state.get_text().chars().collect();
+        let line: Vec<char> = if text.len() <= width {
+            let start = match state.get_halign() {
+                HorizontalAlignment::Left => 0,
+                HorizontalAlignment::Right => width - text.len(),
+                HorizontalAlignment::Center => (width - text.len()) / 2,
+            };
+            let mut line = vec![' '; width];
+            line[start..start + text.len()].copy_from_slice(&text);
+            line
+        } else {
+            // The text wraps around with the separator between its' end and start
+            let mut looped = text;
+            looped.extend(state.get_separator().chars());
+            (0..width)
+                .map(|x| looped[(state.get_offset() + x) % looped.len()])
+                .collect()
+        };
+        let text_row = match state.get_valign() {
+            VerticalAlignment::Top => 0,
+            VerticalAlignment::Middle => height.saturating_sub(1) / 2,
+            VerticalAlignment::Bottom => height.saturating_sub(1),
+        };
+
+        let mut contents = PixelMap::new();
+        for symbol_in_line in line {
+            let mut new_y = Vec::new();
+            for y in 0..height {
+                let symbol = if y == text_row { symbol_in_line } else { ' ' };
+                new_y.push(Pixel::new(symbol.to_string(), fg_color, bg_color));
+            }
+            contents.push(new_y);
+        }
+        if state.get_border_config().get_border() {
+            contents = add_border(
+                contents,
+                state.get_border_config(),
+                state.get_color_config(),
+            );
+        }
+        let state = state_tree.get(&self.get_path()).as_ticker();
+        let parent_colors = state_tree
+            .get(self.get_path().rsplit_once('/').unwrap().0)
+            .as_generic()
+            .get_color_config();
+        contents = add_padding(
+            contents,
+            state.get_padding(),
+            parent_colors.get_bg_color(),
+            parent_colors.get_fg_color(),
+        );
+        contents
+    }
+
+    fn on_hover(
+        &self,
+        state_tree: &mut StateTree,
+        callback_tree: &mut CallbackTree,
+        scheduler: &mut SchedulerFrontend,
+        mouse_pos: Coordinates,
+        custom_data: &mut CustomDataMap,
+    ) -> bool {
+        state_tree
+            .get_mut(&self.path)
+            .as_ticker_mut()
+            .set_hovered(true);
+        self.on_hover_callback(state_tree, callback_tree, scheduler, mouse_pos, custom_data);
+        // Consume the hover, so we are told when the mouse leaves the ticker
+        true
+    }
+
+    fn on_hover_exit(
+        &self,
+        state_tree: &mut StateTree,
+        callback_tree: &mut CallbackTree,
+        scheduler: &mut SchedulerFrontend,
+        custom_data: &mut CustomDataMap,
+    ) -> bool {
+        state_tree
+            .get_mut(&self.path)
+            .as_ticker_mut()
+            .set_hovered(false);
+        self.on_hover_exit_callback(state_tree, callback_tree, scheduler, custom_data)
+    }
+
+    fn get_clone(&self, scheduler: &mut SchedulerFrontend) -> EzObjects {
+        let mut clone = self.clone();
+        let mut new_state = TickerState::new(self.path.clone(), scheduler);
+        new_state.copy_state_values(self.get_state());
+        clone.state = new_state;
+        EzObjects::Ticker(clone)
+    }
+}
+impl Ticker {
+    /// Initialize an instance of this object using the passed config coming from [ez_parser]
+    pub fn from_config(
+        config: Vec<ConfigLine>,
+        id: String,
+        path: String,
+        scheduler: &mut SchedulerFrontend,
+    ) -> Result<Self, ParseError> {
+        let mut obj = Ticker::new(id, path, scheduler);
+        obj.load_ez_config(config, scheduler)?;
+        Ok(obj)
+    }
+}
+
+/// Schedule the task that moves the text of a ticker. The task stops by itself once the widget no
+/// longer exists, e.g. after it was removed or moved to a different layout.
+fn start_ticking(path: String, scheduler: &mut SchedulerFrontend) {
+    let name = format!("{}/ticker", path);
+    let mut last_tick = None;
+    let tick = move |context: Context| {
+        let now = context.scheduler.now();
+        let elapsed = last_tick.map_or(Duration::ZERO, |last| now - last);
+        last_tick = Some(now);
+        let state = match context.state_tree.try_get_mut(&path) {
+            Some(node) => node.as_ticker_mut(),
+            None => return false,
+        };
+        if state.advance(elapsed) {
+            state.update(context.scheduler);
+        }
+        true
+    };
+    scheduler.schedule_recurring(name.as_str(), Box::new(tick), Duration::from_millis(TICK));
+}